@@ -0,0 +1,66 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Three-way merge tool for scene files, it can be used as a git merge driver. See
+//! `fyrox::scene::merge` docs for more info.
+
+use clap::Parser;
+use fyrox::scene::merge::merge_scene_files;
+use std::{path::PathBuf, process::ExitCode};
+
+#[derive(Parser, Debug)]
+#[command(version, about = "Three-way merge of Fyrox scene files", long_about = None)]
+struct Args {
+    /// Common ancestor of both versions (`%O` in a git merge driver)
+    base: PathBuf,
+
+    /// Our version, it is overwritten with the result if no output is specified (`%A`)
+    ours: PathBuf,
+
+    /// Their version (`%B`)
+    theirs: PathBuf,
+
+    /// Path of the merged scene
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let output = args.output.as_ref().unwrap_or(&args.ours);
+    match merge_scene_files(&args.base, &args.ours, &args.theirs, output) {
+        Ok(conflicts) if conflicts.is_empty() => ExitCode::SUCCESS,
+        Ok(conflicts) => {
+            eprintln!(
+                "{} merge conflict(s) in {}, our version is kept for:",
+                conflicts.len(),
+                args.ours.display()
+            );
+            for conflict in conflicts {
+                eprintln!("    {conflict}");
+            }
+            ExitCode::FAILURE
+        }
+        Err(error) => {
+            eprintln!("Unable to merge {}: {error}", args.ours.display());
+            ExitCode::from(2)
+        }
+    }
+}
@@ -57,25 +57,25 @@ use crate::{
 use std::{any::Any, fmt::Debug};
 
 mod blendspace;
-mod canvas;
+pub(crate) mod canvas;
 pub mod command;
-mod connection;
-mod node;
+pub(crate) mod connection;
+pub(crate) mod node;
 mod parameter;
 mod segment;
 pub mod selectable;
 pub mod selection;
-mod socket;
+pub(crate) mod socket;
 mod state_graph;
 mod state_viewer;
 mod toolbar;
 mod transition;
 
-const NORMAL_BACKGROUND: Color = Color::opaque(60, 60, 60);
-const SELECTED_BACKGROUND: Color = Color::opaque(80, 80, 80);
+pub(crate) const NORMAL_BACKGROUND: Color = Color::opaque(60, 60, 60);
+pub(crate) const SELECTED_BACKGROUND: Color = Color::opaque(80, 80, 80);
 const BORDER_COLOR: Color = Color::opaque(70, 70, 70);
-const NORMAL_ROOT_COLOR: Color = Color::opaque(40, 80, 0);
-const SELECTED_ROOT_COLOR: Color = Color::opaque(60, 100, 0);
+pub(crate) const NORMAL_ROOT_COLOR: Color = Color::opaque(40, 80, 0);
+pub(crate) const SELECTED_ROOT_COLOR: Color = Color::opaque(60, 100, 0);
 
 struct PreviewModeData<N: 'static> {
     machine: Machine<Handle<N>>,
@@ -43,6 +43,7 @@ use crate::{
         },
         material::Material,
         scene::tilemap::tileset::TileSet,
+        utils::behavior::resource::BehaviorTreeDefinition,
     },
     message::MessageSender,
     Message,
@@ -145,6 +146,14 @@ impl AssetItem {
                     sender.send(Message::OpenTileSetEditor(tile_set));
                 }
             }
+        } else if self.path.extension().is_some_and(|ext| ext == "behavior") {
+            if let Ok(path) = make_relative_path(&self.path) {
+                if let Ok(behavior_tree) =
+                    block_on(resource_manager.request::<BehaviorTreeDefinition>(path))
+                {
+                    sender.send(Message::OpenBehaviorTreeEditor(behavior_tree));
+                }
+            }
         } else if self.path.is_dir() {
             sender.send(Message::SetAssetBrowserCurrentDir(self.path.clone()));
         } else {
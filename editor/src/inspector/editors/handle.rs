@@ -64,12 +64,16 @@ use std::{
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum HandlePropertyEditorMessage {
     Value(ErasedHandle),
+    /// Sets the value without producing a response message, used when the value is provided by
+    /// the editor in a deferred manner.
+    SyncValue(ErasedHandle),
     Name(Option<String>),
     Hierarchy(HierarchyNode),
 }
 
 impl HandlePropertyEditorMessage {
     define_constructor!(HandlePropertyEditorMessage:Value => fn value(ErasedHandle), layout: false);
+    define_constructor!(HandlePropertyEditorMessage:SyncValue => fn sync_value(ErasedHandle), layout: false);
     define_constructor!(HandlePropertyEditorMessage:Name => fn name(Option<String>), layout: false);
     define_constructor!(HandlePropertyEditorMessage:Hierarchy => fn hierarchy(HierarchyNode), layout: false);
 }
@@ -151,6 +155,9 @@ impl Control for HandlePropertyEditor {
                         // Sync name in any case, because it may be changed.
                         request_name_sync(&self.sender, self.handle, self.value);
                     }
+                    HandlePropertyEditorMessage::SyncValue(handle) => {
+                        self.value = *handle;
+                    }
                     HandlePropertyEditorMessage::Name(value) => {
                         // Handle messages from the editor, it will respond to requests and provide
                         // node names in efficient way.
@@ -278,7 +285,7 @@ impl Control for HandlePropertyEditor {
     }
 }
 
-struct HandlePropertyEditorBuilder {
+pub(crate) struct HandlePropertyEditorBuilder {
     widget_builder: WidgetBuilder,
     value: ErasedHandle,
     sender: MessageSender,
@@ -60,6 +60,7 @@ use crate::{
                 ColorGradingLut, Exposure, OrthographicProjection, PerspectiveProjection,
                 Projection, SkyBox,
             },
+            character::Autostep,
             collider::{
                 BallShape, BitMask, CapsuleShape, ColliderShape, ConeShape, ConvexPolyhedronShape,
                 CuboidShape, CylinderShape, GeometrySource, HeightfieldShape, InteractionGroups,
@@ -85,6 +86,7 @@ use crate::{
                 ParticleSystemRng,
             },
             ragdoll::Limb,
+            reference::SceneNodeRef,
             rigidbody::RigidBodyType,
             sound::{
                 self,
@@ -99,6 +101,16 @@ use crate::{
             terrain::{Chunk, Layer},
             tilemap::{tileset::TileSet, Tile},
             transform::Transform,
+            vehicle::Wheel,
+        },
+        utils::{
+            behavior::{
+                blackboard::{BlackboardCheck, BlackboardValue},
+                decorator::DecoratorKind,
+                parallel::ParallelPolicy,
+                resource::{BehaviorNodeKind, BehaviorTreeDefinition, BehaviorTreeResource},
+            },
+            navmesh::generator::NavmeshGenerationSettings,
         },
     },
     inspector::editors::{
@@ -110,6 +122,7 @@ use crate::{
         handle::{EntityKind, NodeHandlePropertyEditorDefinition},
         material::MaterialPropertyEditorDefinition,
         resource::ResourceFieldPropertyEditorDefinition,
+        scene_node_ref::SceneNodeRefPropertyEditorDefinition,
         script::ScriptPropertyEditorDefinition,
         spritesheet::SpriteSheetFramesContainerEditorDefinition,
         surface::SurfaceDataPropertyEditorDefinition,
@@ -125,6 +138,7 @@ pub mod font;
 pub mod handle;
 pub mod material;
 pub mod resource;
+pub mod scene_node_ref;
 pub mod script;
 pub mod spritesheet;
 pub mod surface;
@@ -239,6 +253,12 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
         EntityKind::SceneNode,
     ));
 
+    container.insert(InheritablePropertyEditorDefinition::<SceneNodeRef>::new());
+    container.register_inheritable_vec_collection::<SceneNodeRef>();
+    container.insert(SceneNodeRefPropertyEditorDefinition::<Node>::new(
+        sender.clone(),
+    ));
+
     container.insert(InheritablePropertyEditorDefinition::<Handle<UiNode>>::new());
     container.register_inheritable_vec_collection::<Handle<UiNode>>();
     container.insert(NodeHandlePropertyEditorDefinition::new(
@@ -334,6 +354,14 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_vec_collection::<Option<TileMapBrushResource>>();
     container.register_inheritable_inspectable::<TileMapBrush>();
 
+    container.insert(ResourceFieldPropertyEditorDefinition::<
+        BehaviorTreeDefinition,
+    >::new(sender.clone()));
+    container.insert(InheritablePropertyEditorDefinition::<
+        Option<BehaviorTreeResource>,
+    >::new());
+    container.register_inheritable_vec_collection::<Option<BehaviorTreeResource>>();
+
     container.register_inheritable_inspectable::<ColorGradingLut>();
     container.register_inheritable_inspectable::<InteractionGroups>();
 
@@ -347,6 +375,14 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_inspectable::<RevoluteJoint>();
     container.register_inheritable_inspectable::<PrismaticJoint>();
     container.register_inheritable_inspectable::<dim2::joint::PrismaticJoint>();
+    container.register_inheritable_inspectable::<SixDofJoint>();
+    container.register_inheritable_inspectable::<JointAxisParams>();
+    container.register_inheritable_inspectable::<RopeJoint>();
+    container.register_inheritable_inspectable::<dim2::joint::RopeJoint>();
+    container.register_inheritable_inspectable::<SpringJoint>();
+    container.register_inheritable_inspectable::<dim2::joint::SpringJoint>();
+    container.register_inheritable_inspectable::<JointMotor>();
+    container.register_inheritable_enum::<JointMotorModel, _>();
 
     container.register_inheritable_inspectable::<Base>();
     container.register_inheritable_inspectable::<BaseLight>();
@@ -385,8 +421,13 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_option::<ColorGradingLut>();
     container.register_inheritable_option::<Biquad>();
     container.register_inheritable_option::<SkyBox>();
+    container.register_inheritable_option::<Autostep>();
 
     container.register_inheritable_inspectable::<SkyBox>();
+    container.register_inheritable_inspectable::<Autostep>();
+    container.register_inheritable_inspectable::<Wheel>();
+    container.register_inheritable_vec_collection::<Wheel>();
+    container.register_inheritable_inspectable::<NavmeshGenerationSettings>();
 
     container.register_inheritable_enum::<dim2::collider::ColliderShape, _>();
     container.register_inheritable_enum::<CoefficientCombineRule, _>();
@@ -484,5 +525,11 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
 
     container.register_inheritable_enum::<TileCollider, _>();
 
+    container.insert(EnumPropertyEditorDefinition::<BehaviorNodeKind>::new());
+    container.insert(EnumPropertyEditorDefinition::<DecoratorKind>::new());
+    container.insert(EnumPropertyEditorDefinition::<ParallelPolicy>::new());
+    container.insert(EnumPropertyEditorDefinition::<BlackboardCheck>::new());
+    container.insert(EnumPropertyEditorDefinition::<BlackboardValue>::new());
+
     container
 }
@@ -0,0 +1,199 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::fyrox::graph::BaseSceneGraph;
+use crate::fyrox::{
+    core::{pool::Handle, reflect::prelude::*},
+    gui::{
+        inspector::{
+            editors::{
+                PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorInstance,
+                PropertyEditorMessageContext, PropertyEditorTranslationContext,
+            },
+            CollectionChanged, FieldKind, InspectorError, ObjectValue, PropertyChanged, Value,
+        },
+        message::MessageDirection,
+        widget::WidgetBuilder,
+    },
+    scene::{base::SceneNodeId, graph::Graph, node::Node, reference::SceneNodeRef},
+};
+use crate::{
+    inspector::editors::handle::{HandlePropertyEditorBuilder, HandlePropertyEditorMessage},
+    message::MessageSender,
+    Message, UiMessage, UiNode,
+};
+use std::{
+    any::TypeId,
+    fmt::Debug,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// A node picked in a [`SceneNodeRef`] editor. The editor only knows the handle of the node in
+/// the current scene, the actual reference is made by the scene controller using
+/// [`resolve_picked_scene_nodes`].
+#[derive(Clone, Debug, Reflect)]
+pub struct PickedSceneNode {
+    #[reflect(hidden)]
+    handle: Handle<Node>,
+    #[reflect(hidden)]
+    make: fn(Option<PathBuf>, SceneNodeId) -> Box<dyn Value>,
+}
+
+/// Replaces every [`PickedSceneNode`] in the given property change with a reference to the picked
+/// node of the scene. Returns `None` if there's nothing to replace.
+pub fn resolve_picked_scene_nodes(
+    args: &PropertyChanged,
+    scene_path: Option<&Path>,
+    graph: &Graph,
+) -> Option<PropertyChanged> {
+    fn resolve_field(
+        value: &FieldKind,
+        scene_path: Option<&Path>,
+        graph: &Graph,
+    ) -> Option<FieldKind> {
+        match value {
+            FieldKind::Object(object) => {
+                let mut picked = None;
+                object.value.as_any(&mut |any| {
+                    picked = any.downcast_ref::<PickedSceneNode>().cloned();
+                });
+                picked.map(|picked| {
+                    let id = graph
+                        .try_get(picked.handle)
+                        .map(|node| node.instance_id())
+                        .unwrap_or_default();
+                    FieldKind::Object(ObjectValue {
+                        value: (picked.make)(
+                            if picked.handle.is_some() {
+                                scene_path.map(|path| path.to_path_buf())
+                            } else {
+                                None
+                            },
+                            id,
+                        ),
+                    })
+                })
+            }
+            FieldKind::Inspectable(inner) => resolve_picked_scene_nodes(inner, scene_path, graph)
+                .map(|inner| FieldKind::Inspectable(Box::new(inner))),
+            FieldKind::Collection(collection) => match &**collection {
+                CollectionChanged::ItemChanged { index, property } => {
+                    resolve_field(property, scene_path, graph).map(|property| {
+                        FieldKind::Collection(Box::new(CollectionChanged::ItemChanged {
+                            index: *index,
+                            property,
+                        }))
+                    })
+                }
+                _ => None,
+            },
+            FieldKind::Inheritable(_) => None,
+        }
+    }
+
+    resolve_field(&args.value, scene_path, graph).map(|value| PropertyChanged {
+        name: args.name.clone(),
+        owner_type_id: args.owner_type_id,
+        value,
+    })
+}
+
+#[derive(Debug)]
+pub struct SceneNodeRefPropertyEditorDefinition<T>
+where
+    T: 'static,
+{
+    sender: Mutex<MessageSender>,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> SceneNodeRefPropertyEditorDefinition<T> {
+    pub fn new(sender: MessageSender) -> Self {
+        Self {
+            sender: Mutex::new(sender),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> PropertyEditorDefinition for SceneNodeRefPropertyEditorDefinition<T>
+where
+    T: Debug + Send + 'static,
+{
+    fn value_type_id(&self) -> TypeId {
+        TypeId::of::<SceneNodeRef<T>>()
+    }
+
+    fn create_instance(
+        &self,
+        ctx: PropertyEditorBuildContext,
+    ) -> Result<PropertyEditorInstance, InspectorError> {
+        let value = ctx.property_info.cast_value::<SceneNodeRef<T>>()?;
+
+        let sender = self.sender.lock().unwrap().clone();
+
+        let editor = HandlePropertyEditorBuilder::new(WidgetBuilder::new(), sender.clone())
+            .build(ctx.build_context);
+
+        request_sync(&sender, editor, value);
+
+        Ok(PropertyEditorInstance::Simple { editor })
+    }
+
+    fn create_message(
+        &self,
+        ctx: PropertyEditorMessageContext,
+    ) -> Result<Option<UiMessage>, InspectorError> {
+        let value = ctx.property_info.cast_value::<SceneNodeRef<T>>()?;
+
+        // The handle of the node is unknown here, the editor will provide it later.
+        request_sync(&self.sender.lock().unwrap(), ctx.instance, value);
+
+        Ok(None)
+    }
+
+    fn translate_message(&self, ctx: PropertyEditorTranslationContext) -> Option<PropertyChanged> {
+        if ctx.message.direction() == MessageDirection::FromWidget {
+            if let Some(HandlePropertyEditorMessage::Value(value)) =
+                ctx.message.data::<HandlePropertyEditorMessage>()
+            {
+                return Some(PropertyChanged {
+                    owner_type_id: ctx.owner_type_id,
+                    name: ctx.name.to_string(),
+                    value: FieldKind::object(PickedSceneNode {
+                        handle: (*value).into(),
+                        make: |scene, node| Box::new(SceneNodeRef::<T>::new(scene, node)),
+                    }),
+                });
+            }
+        }
+        None
+    }
+}
+
+fn request_sync<T>(sender: &MessageSender, editor: Handle<UiNode>, value: &SceneNodeRef<T>) {
+    sender.send(Message::SyncSceneNodeRef {
+        view: editor,
+        scene: value.scene_path().map(|path| path.to_path_buf()),
+        node: value.node_id(),
+    });
+}
@@ -35,6 +35,7 @@ use crate::fyrox::{
         grid::{Column, GridBuilder, Row},
         message::{KeyCode, MessageDirection, UiMessage},
         stack_panel::StackPanelBuilder,
+        utils::make_simple_tooltip,
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, Orientation, Thickness, UiNode, UserInterface,
@@ -58,7 +59,7 @@ use crate::{
         commands::{
             navmesh::{
                 AddNavmeshEdgeCommand, ConnectNavmeshEdgesCommand, DeleteNavmeshVertexCommand,
-                MoveNavmeshVertexCommand,
+                MoveNavmeshVertexCommand, SetNavmeshCommand,
             },
             ChangeSelectionCommand,
         },
@@ -76,6 +77,7 @@ pub mod selection;
 pub struct NavmeshPanel {
     pub window: Handle<UiNode>,
     connect_edges: Handle<UiNode>,
+    generate: Handle<UiNode>,
     sender: MessageSender,
     scene_frame: Handle<UiNode>,
 }
@@ -93,20 +95,39 @@ fn fetch_selection(editor_selection: &Selection) -> Option<NavmeshSelection> {
 impl NavmeshPanel {
     pub fn new(scene_frame: Handle<UiNode>, ctx: &mut BuildContext, sender: MessageSender) -> Self {
         let connect_edges;
+        let generate;
         let window = WindowBuilder::new(WidgetBuilder::new().with_name("NavmeshPanel"))
             .open(false)
             .with_title(WindowTitle::text("Navmesh"))
             .with_content(
                 GridBuilder::new(
                     WidgetBuilder::new().with_child(
-                        StackPanelBuilder::new(WidgetBuilder::new().with_child({
-                            connect_edges = ButtonBuilder::new(
-                                WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
-                            )
-                            .with_text("Connect Edges")
-                            .build(ctx);
-                            connect_edges
-                        }))
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .with_child({
+                                    connect_edges = ButtonBuilder::new(
+                                        WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Connect Edges")
+                                    .build(ctx);
+                                    connect_edges
+                                })
+                                .with_child({
+                                    generate = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .with_tooltip(make_simple_tooltip(
+                                                ctx,
+                                                "Generates the navmesh from the geometry of the \
+                                                source nodes, using the generation settings \
+                                                of the navmesh.",
+                                            )),
+                                    )
+                                    .with_text("Generate")
+                                    .build(ctx);
+                                    generate
+                                }),
+                        )
                         .with_orientation(Orientation::Horizontal)
                         .build(ctx),
                     ),
@@ -121,13 +142,37 @@ impl NavmeshPanel {
             window,
             sender,
             connect_edges,
+            generate,
             scene_frame,
         }
     }
 
-    pub fn handle_message(&mut self, message: &UiMessage, editor_selection: &Selection) {
+    pub fn handle_message(
+        &mut self,
+        message: &UiMessage,
+        editor_selection: &Selection,
+        game_scene: &GameScene,
+        engine: &Engine,
+    ) {
         if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
-            if message.destination() == self.connect_edges {
+            if message.destination() == self.generate {
+                if let Some(selection) = fetch_selection(editor_selection) {
+                    let graph = &engine.scenes[game_scene.scene].graph;
+                    if let Some(navmesh) =
+                        graph.try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+                    {
+                        let navmesh = navmesh.make_generator(graph).generate();
+
+                        // Previously selected vertices and edges are no longer valid.
+                        self.sender.do_command(CommandGroup::from(vec![
+                            Command::new(ChangeSelectionCommand::new(Selection::new(
+                                NavmeshSelection::new(selection.navmesh_node(), vec![]),
+                            ))),
+                            Command::new(SetNavmeshCommand::new(selection.navmesh_node(), navmesh)),
+                        ]));
+                    }
+                }
+            } else if message.destination() == self.connect_edges {
                 if let Some(selection) = fetch_selection(editor_selection) {
                     let vertices = selection
                         .entities()
@@ -59,6 +59,7 @@ pub mod preview;
 pub mod scene;
 pub mod scene_viewer;
 pub mod settings;
+pub mod profiler;
 pub mod stats;
 pub mod ui_scene;
 pub mod utils;
@@ -152,6 +153,7 @@ use crate::{
     physics::ColliderControlPanel,
     plugin::EditorPlugin,
     plugins::collider::ColliderShapePlugin,
+    profiler::{ProfilerWindow, ProfilerWindowAction},
     scene::{
         commands::{
             make_delete_selection_command, ChangeSelectionCommand, GameSceneContext, PasteCommand,
@@ -165,6 +167,7 @@ use crate::{
     settings::build::BuildCommand,
     settings::Settings,
     stats::{StatisticsWindow, StatisticsWindowAction},
+    utils::localization::{MissingTranslationsWindow, MissingTranslationsWindowAction},
     ui_scene::{
         commands::graph::PasteWidgetCommand, menu::WidgetContextMenu,
         utils::UiSceneWorldViewerDataProvider, UiScene,
@@ -188,6 +191,8 @@ use std::{
 };
 
 use crate::plugins::tilemap::TileMapEditorPlugin;
+use crate::plugins::behavior::BehaviorTreeEditorPlugin;
+use crate::plugins::vehicle::VehicleWheelsPlugin;
 pub use message::Message;
 
 pub const FIXED_TIMESTEP: f32 = 1.0 / 60.0;
@@ -535,6 +540,8 @@ pub struct Editor {
     pub highlighter: Option<Rc<RefCell<HighlightRenderPass>>>,
     pub export_window: Option<ExportWindow>,
     pub statistics_window: Option<StatisticsWindow>,
+    pub profiler_window: Option<ProfilerWindow>,
+    pub missing_translations_window: Option<MissingTranslationsWindow>,
     pub surface_data_viewer: Option<SurfaceDataViewer>,
 }
 
@@ -543,6 +550,9 @@ impl Editor {
         let (log_message_sender, log_message_receiver) = channel();
 
         Log::add_listener(log_message_sender);
+        // Show messages of third-party crates in the log panel too.
+        Log::install_log_facade();
+        Log::install_tracing_bridge();
 
         let mut settings = Settings::default();
 
@@ -859,6 +869,8 @@ impl Editor {
             plugins: vec![
                 Some(Box::new(ColliderShapePlugin::default())),
                 Some(Box::new(TileMapEditorPlugin::default())),
+                Some(Box::new(BehaviorTreeEditorPlugin::default())),
+                Some(Box::new(VehicleWheelsPlugin::default())),
             ],
             // Apparently, some window managers (like Wayland), does not send `Focused` event after the window
             // was created. So we must assume that the editor is focused by default, otherwise editor's thread
@@ -874,6 +886,8 @@ impl Editor {
             highlighter: None,
             export_window: None,
             statistics_window: None,
+            profiler_window: None,
+            missing_translations_window: None,
             surface_data_viewer: None,
         };
 
@@ -1197,6 +1211,8 @@ impl Editor {
                     ragdoll_wizard: &self.ragdoll_wizard,
                     export_window: &mut self.export_window,
                     statistics_window: &mut self.statistics_window,
+                    profiler_window: &mut self.profiler_window,
+                    missing_translations_window: &mut self.missing_translations_window,
                 },
                 settings: &mut self.settings,
             },
@@ -1243,6 +1259,20 @@ impl Editor {
                 self.statistics_window.take();
             }
         }
+        if let Some(profiler) = self.profiler_window.as_mut() {
+            if let ProfilerWindowAction::Remove =
+                profiler.handle_ui_message(message, engine.user_interfaces.first())
+            {
+                self.profiler_window.take();
+            }
+        }
+        if let Some(window) = self.missing_translations_window.as_mut() {
+            if let MissingTranslationsWindowAction::Remove =
+                window.handle_ui_message(message, engine)
+            {
+                self.missing_translations_window.take();
+            }
+        }
 
         let current_scene_entry = self.scenes.current_scene_entry_mut();
 
@@ -1331,8 +1361,12 @@ impl Editor {
                 self.scene_settings
                     .handle_ui_message(message, &self.message_sender);
 
-                self.navmesh_panel
-                    .handle_message(message, &current_scene_entry.selection);
+                self.navmesh_panel.handle_message(
+                    message,
+                    &current_scene_entry.selection,
+                    game_scene,
+                    engine,
+                );
 
                 if let Some(interaction_mode) = current_scene_entry
                     .current_interaction_mode
@@ -2276,6 +2310,10 @@ impl Editor {
             surface_data_viewer.update(&mut self.engine);
         }
 
+        if let Some(window) = self.missing_translations_window.as_mut() {
+            window.update(self.engine.user_interfaces.first());
+        }
+
         self.scene_viewer
             .pre_update(&self.settings, &mut self.engine);
         if let Some(entry) = self.scenes.current_scene_entry_ref() {
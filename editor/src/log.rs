@@ -248,7 +248,14 @@ impl LogPanel {
                 continue;
             }
 
-            let text = format!("[{:.2}s] {}", msg.time.as_secs_f32(), msg.content);
+            let mut text = format!("[{:.2}s] ", msg.time.as_secs_f32());
+            if !msg.target.is_empty() {
+                text += &format!("[{}] ", msg.target);
+            }
+            text += msg.content.trim_end();
+            for (name, value) in msg.fields.iter() {
+                text += &format!(" {name}={value}");
+            }
 
             let ctx = &mut engine.user_interfaces.first_mut().build_ctx();
             let item = BorderBuilder::new(
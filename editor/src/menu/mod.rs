@@ -35,11 +35,12 @@ use crate::{
         utils::UtilsMenu, view::ViewMenu,
     },
     message::MessageSender,
+    profiler::ProfilerWindow,
     scene::{container::EditorSceneEntry, controller::SceneController},
     send_sync_message,
     settings::Settings,
     stats::StatisticsWindow,
-    utils::ragdoll::RagdollWizard,
+    utils::{localization::MissingTranslationsWindow, ragdoll::RagdollWizard},
     AbsmEditor, CurveEditorWindow, Engine, Mode, SceneSettingsWindow,
 };
 use std::path::PathBuf;
@@ -86,6 +87,8 @@ pub struct Panels<'b> {
     pub ragdoll_wizard: &'b RagdollWizard,
     pub export_window: &'b mut Option<ExportWindow>,
     pub statistics_window: &'b mut Option<StatisticsWindow>,
+    pub profiler_window: &'b mut Option<ProfilerWindow>,
+    pub missing_translations_window: &'b mut Option<MissingTranslationsWindow>,
 }
 
 pub struct MenuContext<'a, 'b> {
@@ -211,7 +214,7 @@ impl Menu {
         self.utils_menu.handle_ui_message(
             message,
             &mut ctx.panels,
-            ctx.engine.user_interfaces.first_mut(),
+            ctx.engine,
         );
         self.file_menu.handle_ui_message(
             message,
@@ -19,11 +19,11 @@
 // SOFTWARE.
 
 use crate::fyrox::{
-    core::pool::Handle,
+    core::{algebra::Vector3, pool::Handle},
     gui::{menu::MenuItemMessage, message::UiMessage, BuildContext, UiNode},
     scene::{
-        base::BaseBuilder, collider::*, joint::*, node::Node, ragdoll::RagdollBuilder,
-        rigidbody::RigidBodyBuilder,
+        base::BaseBuilder, character::CharacterControllerBuilder, collider::*, joint::*,
+        node::Node, ragdoll::RagdollBuilder, rigidbody::RigidBodyBuilder, vehicle::*,
     },
 };
 use crate::menu::create_menu_item;
@@ -35,8 +35,13 @@ pub struct PhysicsMenu {
     create_ball_joint: Handle<UiNode>,
     create_prismatic_joint: Handle<UiNode>,
     create_fixed_joint: Handle<UiNode>,
+    create_six_dof_joint: Handle<UiNode>,
+    create_rope_joint: Handle<UiNode>,
+    create_spring_joint: Handle<UiNode>,
     create_collider: Handle<UiNode>,
     create_ragdoll: Handle<UiNode>,
+    create_character_controller: Handle<UiNode>,
+    create_vehicle: Handle<UiNode>,
 }
 
 impl PhysicsMenu {
@@ -47,7 +52,12 @@ impl PhysicsMenu {
         let create_ball_joint;
         let create_prismatic_joint;
         let create_fixed_joint;
+        let create_six_dof_joint;
+        let create_rope_joint;
+        let create_spring_joint;
         let create_ragdoll;
+        let create_character_controller;
+        let create_vehicle;
         let menu = create_menu_item(
             "Physics",
             vec![
@@ -75,10 +85,31 @@ impl PhysicsMenu {
                     create_fixed_joint = create_menu_item("Fixed Joint", vec![], ctx);
                     create_fixed_joint
                 },
+                {
+                    create_six_dof_joint = create_menu_item("Six DOF Joint", vec![], ctx);
+                    create_six_dof_joint
+                },
+                {
+                    create_rope_joint = create_menu_item("Rope Joint", vec![], ctx);
+                    create_rope_joint
+                },
+                {
+                    create_spring_joint = create_menu_item("Spring Joint", vec![], ctx);
+                    create_spring_joint
+                },
                 {
                     create_ragdoll = create_menu_item("Ragdoll", vec![], ctx);
                     create_ragdoll
                 },
+                {
+                    create_character_controller =
+                        create_menu_item("Character Controller", vec![], ctx);
+                    create_character_controller
+                },
+                {
+                    create_vehicle = create_menu_item("Vehicle", vec![], ctx);
+                    create_vehicle
+                },
             ],
             ctx,
         );
@@ -90,8 +121,13 @@ impl PhysicsMenu {
             create_ball_joint,
             create_prismatic_joint,
             create_fixed_joint,
+            create_six_dof_joint,
+            create_rope_joint,
+            create_spring_joint,
             create_collider,
             create_ragdoll,
+            create_character_controller,
+            create_vehicle,
         }
     }
 
@@ -123,6 +159,24 @@ impl PhysicsMenu {
                         .with_params(JointParams::FixedJoint(Default::default()))
                         .build_node(),
                 )
+            } else if message.destination() == self.create_six_dof_joint {
+                Some(
+                    JointBuilder::new(BaseBuilder::new().with_name("Six DOF Joint"))
+                        .with_params(JointParams::SixDofJoint(Default::default()))
+                        .build_node(),
+                )
+            } else if message.destination() == self.create_rope_joint {
+                Some(
+                    JointBuilder::new(BaseBuilder::new().with_name("Rope Joint"))
+                        .with_params(JointParams::RopeJoint(Default::default()))
+                        .build_node(),
+                )
+            } else if message.destination() == self.create_spring_joint {
+                Some(
+                    JointBuilder::new(BaseBuilder::new().with_name("Spring Joint"))
+                        .with_params(JointParams::SpringJoint(Default::default()))
+                        .build_node(),
+                )
             } else if message.destination == self.create_collider {
                 Some(
                     ColliderBuilder::new(BaseBuilder::new().with_name("Collider"))
@@ -131,6 +185,29 @@ impl PhysicsMenu {
                 )
             } else if message.destination == self.create_ragdoll {
                 Some(RagdollBuilder::new(BaseBuilder::new().with_name("Ragdoll")).build_node())
+            } else if message.destination == self.create_character_controller {
+                Some(
+                    CharacterControllerBuilder::new(
+                        BaseBuilder::new().with_name("Character Controller"),
+                    )
+                    .build_node(),
+                )
+            } else if message.destination == self.create_vehicle {
+                // Front wheels are steering, rear wheels are driving.
+                let wheels = [(-0.8, 1.2), (0.8, 1.2), (-0.8, -1.2), (0.8, -1.2)]
+                    .into_iter()
+                    .map(|(x, z)| Wheel {
+                        connection_point: Vector3::new(x, 0.0, z),
+                        steering: z > 0.0,
+                        drive: z < 0.0,
+                        ..Default::default()
+                    })
+                    .collect();
+                Some(
+                    VehicleBuilder::new(BaseBuilder::new().with_name("Vehicle"))
+                        .with_wheels(wheels)
+                        .build_node(),
+                )
             } else {
                 None
             }
@@ -29,6 +29,7 @@ use crate::fyrox::{
         button::ButtonBuilder,
         canvas::CanvasBuilder,
         check_box::CheckBoxBuilder,
+        data_grid::{DataGridBuilder, DataGridColumn},
         decorator::DecoratorBuilder,
         dropdown_list::DropdownListBuilder,
         expander::ExpanderBuilder,
@@ -48,6 +49,7 @@ use crate::fyrox::{
         scroll_bar::ScrollBarBuilder,
         scroll_viewer::ScrollViewerBuilder,
         searchbar::SearchBarBuilder,
+        slider::SliderBuilder,
         stack_panel::StackPanelBuilder,
         tab_control::TabControlBuilder,
         text::TextBuilder,
@@ -128,6 +130,11 @@ impl UiMenu {
             UiMenuEntry::new("CheckBox", |name, ctx| {
                 CheckBoxBuilder::new(WidgetBuilder::new().with_name(name)).build(ctx)
             }),
+            UiMenuEntry::new("DataGrid", |name, ctx| {
+                DataGridBuilder::new(WidgetBuilder::new().with_name(name))
+                    .with_columns(vec![DataGridColumn::new("Column")])
+                    .build(ctx)
+            }),
             UiMenuEntry::new("Decorator", |name, ctx| {
                 DecoratorBuilder::new(BorderBuilder::new(WidgetBuilder::new().with_name(name)))
                     .build(ctx)
@@ -178,6 +185,9 @@ impl UiMenu {
             UiMenuEntry::new("SearchBar", |name, ctx| {
                 SearchBarBuilder::new(WidgetBuilder::new().with_name(name)).build(ctx)
             }),
+            UiMenuEntry::new("Slider", |name, ctx| {
+                SliderBuilder::new(WidgetBuilder::new().with_name(name)).build(ctx)
+            }),
             UiMenuEntry::new("StackPanel", |name, ctx| {
                 StackPanelBuilder::new(WidgetBuilder::new().with_name(name)).build(ctx)
             }),
@@ -20,15 +20,18 @@
 
 use crate::fyrox::{
     asset::core::pool::Handle,
+    engine::Engine,
     gui::{
         menu::MenuItemMessage,
         message::{MessageDirection, UiMessage},
         window::WindowMessage,
-        BuildContext, UiNode, UserInterface,
+        BuildContext, UiNode,
     },
 };
 use crate::menu::{create_menu_item, create_root_menu_item, Panels};
+use crate::profiler::ProfilerWindow;
 use crate::stats::StatisticsWindow;
+use crate::utils::localization::MissingTranslationsWindow;
 
 pub struct UtilsMenu {
     pub menu: Handle<UiNode>,
@@ -38,6 +41,8 @@ pub struct UtilsMenu {
     animation_editor: Handle<UiNode>,
     ragdoll_wizard: Handle<UiNode>,
     rendering_statistics: Handle<UiNode>,
+    profiler: Handle<UiNode>,
+    missing_translations: Handle<UiNode>,
 }
 
 impl UtilsMenu {
@@ -48,6 +53,8 @@ impl UtilsMenu {
         let animation_editor;
         let ragdoll_wizard;
         let rendering_statistics;
+        let profiler;
+        let missing_translations;
         let menu = create_root_menu_item(
             "Utils",
             vec![
@@ -75,6 +82,14 @@ impl UtilsMenu {
                     rendering_statistics = create_menu_item("Rendering Statistics", vec![], ctx);
                     rendering_statistics
                 },
+                {
+                    profiler = create_menu_item("Profiler", vec![], ctx);
+                    profiler
+                },
+                {
+                    missing_translations = create_menu_item("Missing Translations", vec![], ctx);
+                    missing_translations
+                },
             ],
             ctx,
         );
@@ -87,6 +102,8 @@ impl UtilsMenu {
             animation_editor,
             ragdoll_wizard,
             rendering_statistics,
+            profiler,
+            missing_translations,
         }
     }
 
@@ -94,8 +111,9 @@ impl UtilsMenu {
         &mut self,
         message: &UiMessage,
         panels: &mut Panels,
-        ui: &mut UserInterface,
+        engine: &mut Engine,
     ) {
+        let ui = engine.user_interfaces.first_mut();
         if let Some(MenuItemMessage::Click) = message.data::<MenuItemMessage>() {
             if message.destination() == self.open_path_fixer {
                 ui.send_message(WindowMessage::open_modal(
@@ -117,6 +135,15 @@ impl UtilsMenu {
                     &mut ui.build_ctx(),
                     panels.scene_frame,
                 ))
+            } else if message.destination() == self.profiler {
+                *panels.profiler_window =
+                    Some(ProfilerWindow::new(&mut ui.build_ctx(), panels.scene_frame))
+            } else if message.destination() == self.missing_translations {
+                *panels.missing_translations_window = Some(MissingTranslationsWindow::new(
+                    &mut ui.build_ctx(),
+                    panels.scene_frame,
+                    &engine.resource_manager,
+                ))
             }
         }
     }
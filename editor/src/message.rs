@@ -28,12 +28,15 @@ use crate::{
         },
         gui::UiNode,
         material::MaterialResource,
-        scene::{camera::Projection, mesh::surface::SurfaceResource, node::Node},
+        scene::{
+            base::SceneNodeId, camera::Projection, mesh::surface::SurfaceResource, node::Node,
+        },
     },
     scene::Selection,
     SaveSceneConfirmationDialogAction,
 };
 use fyrox::scene::tilemap::tileset::TileSetResource;
+use fyrox::utils::behavior::resource::BehaviorTreeResource;
 use std::{path::PathBuf, sync::mpsc::Sender};
 
 #[derive(Debug)]
@@ -66,6 +69,7 @@ pub enum Message {
     OpenAbsmEditor,
     OpenMaterialEditor(MaterialResource),
     OpenTileSetEditor(TileSetResource),
+    OpenBehaviorTreeEditor(BehaviorTreeResource),
     OpenNodeRemovalDialog,
     ShowInAssetBrowser(PathBuf),
     LocateObject {
@@ -96,6 +100,11 @@ pub enum Message {
     ProvideSceneHierarchy {
         view: Handle<UiNode>,
     },
+    SyncSceneNodeRef {
+        view: Handle<UiNode>,
+        scene: Option<PathBuf>,
+        node: SceneNodeId,
+    },
     ForceSync,
     ShowDocumentation(String),
     SaveLayout,
@@ -0,0 +1,172 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{
+    command::{CommandContext, CommandTrait},
+    fyrox::{
+        core::{algebra::Vector2, log::Log, pool::Handle},
+        utils::behavior::resource::{BehaviorNodeDefinition, BehaviorTreeResource},
+    },
+};
+
+#[derive(Debug)]
+pub struct AddBehaviorNodeCommand {
+    pub resource: BehaviorTreeResource,
+    pub node: Option<BehaviorNodeDefinition>,
+    pub handle: Handle<BehaviorNodeDefinition>,
+}
+
+impl AddBehaviorNodeCommand {
+    pub fn new(resource: BehaviorTreeResource, node: BehaviorNodeDefinition) -> Self {
+        Self {
+            resource,
+            node: Some(node),
+            handle: Default::default(),
+        }
+    }
+}
+
+impl CommandTrait for AddBehaviorNodeCommand {
+    fn name(&mut self, _context: &dyn CommandContext) -> String {
+        "Add Behavior Node".into()
+    }
+
+    fn execute(&mut self, _context: &mut dyn CommandContext) {
+        let node = self.node.take().unwrap();
+        let mut definition = self.resource.data_ref();
+        if self.handle.is_some() {
+            // Keep the handle on redo, so the commands that follow in history could still refer
+            // to the node.
+            definition.nodes.spawn_at_handle(self.handle, node).unwrap();
+        } else {
+            self.handle = definition.add_node(node);
+        }
+        drop(definition);
+        Log::verify(self.resource.save_back());
+    }
+
+    fn revert(&mut self, _context: &mut dyn CommandContext) {
+        self.node = self.resource.data_ref().nodes.try_free(self.handle);
+        Log::verify(self.resource.save_back());
+    }
+}
+
+/// Removes a node from a definition. The node must be detached from its parent before (see
+/// [`SetBehaviorNodeChildrenCommand`]), otherwise the parent will keep a dangling handle on revert.
+#[derive(Debug)]
+pub struct RemoveBehaviorNodeCommand {
+    pub resource: BehaviorTreeResource,
+    pub handle: Handle<BehaviorNodeDefinition>,
+    pub node: Option<BehaviorNodeDefinition>,
+}
+
+impl RemoveBehaviorNodeCommand {
+    pub fn new(resource: BehaviorTreeResource, handle: Handle<BehaviorNodeDefinition>) -> Self {
+        Self {
+            resource,
+            handle,
+            node: None,
+        }
+    }
+}
+
+impl CommandTrait for RemoveBehaviorNodeCommand {
+    fn name(&mut self, _context: &dyn CommandContext) -> String {
+        "Remove Behavior Node".into()
+    }
+
+    fn execute(&mut self, _context: &mut dyn CommandContext) {
+        self.node = self.resource.data_ref().nodes.try_free(self.handle);
+        Log::verify(self.resource.save_back());
+    }
+
+    fn revert(&mut self, _context: &mut dyn CommandContext) {
+        self.resource
+            .data_ref()
+            .nodes
+            .spawn_at_handle(self.handle, self.node.take().unwrap())
+            .unwrap();
+        Log::verify(self.resource.save_back());
+    }
+}
+
+#[derive(Debug)]
+pub struct SetBehaviorNodeChildrenCommand {
+    pub resource: BehaviorTreeResource,
+    pub handle: Handle<BehaviorNodeDefinition>,
+    pub children: Vec<Handle<BehaviorNodeDefinition>>,
+}
+
+impl SetBehaviorNodeChildrenCommand {
+    fn swap(&mut self) {
+        let mut definition = self.resource.data_ref();
+        if let Some(node) = definition.nodes.try_borrow_mut(self.handle) {
+            std::mem::swap(&mut node.children, &mut self.children);
+        }
+        drop(definition);
+        Log::verify(self.resource.save_back());
+    }
+}
+
+impl CommandTrait for SetBehaviorNodeChildrenCommand {
+    fn name(&mut self, _context: &dyn CommandContext) -> String {
+        "Set Behavior Node Children".into()
+    }
+
+    fn execute(&mut self, _context: &mut dyn CommandContext) {
+        self.swap()
+    }
+
+    fn revert(&mut self, _context: &mut dyn CommandContext) {
+        self.swap()
+    }
+}
+
+#[derive(Debug)]
+pub struct MoveBehaviorNodeCommand {
+    pub resource: BehaviorTreeResource,
+    pub handle: Handle<BehaviorNodeDefinition>,
+    pub position: Vector2<f32>,
+}
+
+impl MoveBehaviorNodeCommand {
+    fn swap(&mut self) {
+        let mut definition = self.resource.data_ref();
+        if let Some(node) = definition.nodes.try_borrow_mut(self.handle) {
+            std::mem::swap(&mut node.position, &mut self.position);
+        }
+        drop(definition);
+        Log::verify(self.resource.save_back());
+    }
+}
+
+impl CommandTrait for MoveBehaviorNodeCommand {
+    fn name(&mut self, _context: &dyn CommandContext) -> String {
+        "Move Behavior Node".into()
+    }
+
+    fn execute(&mut self, _context: &mut dyn CommandContext) {
+        self.swap()
+    }
+
+    fn revert(&mut self, _context: &mut dyn CommandContext) {
+        self.swap()
+    }
+}
@@ -0,0 +1,296 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{
+    absm::node::AbsmNode,
+    command::{Command, CommandGroup},
+    fyrox::{
+        core::pool::Handle,
+        graph::BaseSceneGraph,
+        gui::{
+            menu::{ContextMenuBuilder, MenuItemMessage},
+            message::UiMessage,
+            popup::{Placement, PopupBuilder, PopupMessage},
+            stack_panel::StackPanelBuilder,
+            widget::WidgetBuilder,
+            BuildContext, RcUiNodeHandle, UiNode, UserInterface,
+        },
+        utils::behavior::{
+            decorator::DecoratorKind,
+            parallel::ParallelPolicy,
+            resource::{
+                BehaviorNodeDefinition, BehaviorNodeKind, BehaviorTreeDefinition,
+                BehaviorTreeResource,
+            },
+        },
+    },
+    menu::create_menu_item,
+    message::MessageSender,
+    plugins::behavior::commands::{
+        AddBehaviorNodeCommand, RemoveBehaviorNodeCommand, SetBehaviorNodeChildrenCommand,
+    },
+};
+
+/// Creates a set of commands, that detach the given nodes from their parents.
+pub fn make_unlink_commands(
+    resource: &BehaviorTreeResource,
+    definition: &BehaviorTreeDefinition,
+    nodes: &[Handle<BehaviorNodeDefinition>],
+) -> Vec<Command> {
+    definition
+        .nodes
+        .pair_iter()
+        .filter(|(_, parent)| parent.children.iter().any(|c| nodes.contains(c)))
+        .map(|(parent, parent_ref)| {
+            Command::new(SetBehaviorNodeChildrenCommand {
+                resource: resource.clone(),
+                handle: parent,
+                children: parent_ref
+                    .children
+                    .iter()
+                    .filter(|c| !nodes.contains(c))
+                    .cloned()
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+pub struct CanvasContextMenu {
+    create_sequence: Handle<UiNode>,
+    create_selector: Handle<UiNode>,
+    create_memory_sequence: Handle<UiNode>,
+    create_memory_selector: Handle<UiNode>,
+    create_parallel: Handle<UiNode>,
+    create_inverter: Handle<UiNode>,
+    create_decorator: Handle<UiNode>,
+    create_action: Handle<UiNode>,
+    pub menu: RcUiNodeHandle,
+    pub canvas: Handle<UiNode>,
+}
+
+impl CanvasContextMenu {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let create_sequence;
+        let create_selector;
+        let create_memory_sequence;
+        let create_memory_selector;
+        let create_parallel;
+        let create_inverter;
+        let create_decorator;
+        let create_action;
+        let menu = ContextMenuBuilder::new(
+            PopupBuilder::new(WidgetBuilder::new().with_visibility(false)).with_content(
+                StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            create_sequence = create_menu_item("Sequence", vec![], ctx);
+                            create_sequence
+                        })
+                        .with_child({
+                            create_selector = create_menu_item("Selector", vec![], ctx);
+                            create_selector
+                        })
+                        .with_child({
+                            create_memory_sequence =
+                                create_menu_item("Memory Sequence", vec![], ctx);
+                            create_memory_sequence
+                        })
+                        .with_child({
+                            create_memory_selector =
+                                create_menu_item("Memory Selector", vec![], ctx);
+                            create_memory_selector
+                        })
+                        .with_child({
+                            create_parallel = create_menu_item("Parallel", vec![], ctx);
+                            create_parallel
+                        })
+                        .with_child({
+                            create_inverter = create_menu_item("Inverter", vec![], ctx);
+                            create_inverter
+                        })
+                        .with_child({
+                            create_decorator = create_menu_item("Decorator", vec![], ctx);
+                            create_decorator
+                        })
+                        .with_child({
+                            create_action = create_menu_item("Action", vec![], ctx);
+                            create_action
+                        }),
+                )
+                .build(ctx),
+            ),
+        )
+        .build(ctx);
+        let menu = RcUiNodeHandle::new(menu, ctx.sender());
+
+        Self {
+            create_sequence,
+            create_selector,
+            create_memory_sequence,
+            create_memory_selector,
+            create_parallel,
+            create_inverter,
+            create_decorator,
+            create_action,
+            menu,
+            canvas: Default::default(),
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        sender: &MessageSender,
+        message: &UiMessage,
+        ui: &mut UserInterface,
+        resource: &BehaviorTreeResource,
+    ) {
+        if let Some(MenuItemMessage::Click) = message.data() {
+            let kind = if message.destination() == self.create_sequence {
+                Some(BehaviorNodeKind::Sequence)
+            } else if message.destination() == self.create_selector {
+                Some(BehaviorNodeKind::Selector)
+            } else if message.destination() == self.create_memory_sequence {
+                Some(BehaviorNodeKind::MemorySequence)
+            } else if message.destination() == self.create_memory_selector {
+                Some(BehaviorNodeKind::MemorySelector)
+            } else if message.destination() == self.create_parallel {
+                Some(BehaviorNodeKind::Parallel {
+                    success_policy: ParallelPolicy::RequireAll,
+                    failure_policy: ParallelPolicy::RequireOne,
+                })
+            } else if message.destination() == self.create_inverter {
+                Some(BehaviorNodeKind::Inverter)
+            } else if message.destination() == self.create_decorator {
+                Some(BehaviorNodeKind::Decorator(DecoratorKind::Repeat {
+                    count: 0,
+                }))
+            } else if message.destination() == self.create_action {
+                Some(BehaviorNodeKind::Action {
+                    name: Default::default(),
+                })
+            } else {
+                None
+            };
+
+            if let Some(kind) = kind {
+                let position = ui
+                    .node(self.canvas)
+                    .screen_to_local(ui.node(self.menu.handle()).screen_position());
+
+                sender.do_command(AddBehaviorNodeCommand::new(
+                    resource.clone(),
+                    BehaviorNodeDefinition::new("", kind).with_position(position),
+                ));
+            }
+        }
+    }
+}
+
+pub struct NodeContextMenu {
+    disconnect: Handle<UiNode>,
+    remove: Handle<UiNode>,
+    pub menu: RcUiNodeHandle,
+    placement_target: Handle<UiNode>,
+}
+
+impl NodeContextMenu {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let disconnect;
+        let remove;
+        let menu = ContextMenuBuilder::new(
+            PopupBuilder::new(WidgetBuilder::new().with_visibility(false)).with_content(
+                StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            disconnect = create_menu_item("Disconnect From Parent", vec![], ctx);
+                            disconnect
+                        })
+                        .with_child({
+                            remove = create_menu_item("Remove", vec![], ctx);
+                            remove
+                        }),
+                )
+                .build(ctx),
+            ),
+        )
+        .build(ctx);
+        let menu = RcUiNodeHandle::new(menu, ctx.sender());
+
+        Self {
+            disconnect,
+            remove,
+            menu,
+            placement_target: Default::default(),
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        sender: &MessageSender,
+        ui: &UserInterface,
+        resource: &BehaviorTreeResource,
+        selection: &[Handle<BehaviorNodeDefinition>],
+    ) {
+        if let Some(MenuItemMessage::Click) = message.data() {
+            if message.destination() == self.remove {
+                let definition = resource.data_ref();
+
+                // Root node can't be removed.
+                let nodes = selection
+                    .iter()
+                    .filter(|n| **n != definition.root && definition.nodes.is_valid_handle(**n))
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                let mut group = make_unlink_commands(resource, &definition, &nodes);
+                group.extend(
+                    nodes.iter().map(|n| {
+                        Command::new(RemoveBehaviorNodeCommand::new(resource.clone(), *n))
+                    }),
+                );
+
+                drop(definition);
+
+                if !group.is_empty() {
+                    sender.do_command(CommandGroup::from(group));
+                }
+            } else if message.destination() == self.disconnect {
+                if let Some(node) = ui
+                    .try_get(self.placement_target)
+                    .and_then(|n| n.query_component::<AbsmNode<BehaviorNodeDefinition>>())
+                {
+                    let definition = resource.data_ref();
+                    let group = make_unlink_commands(resource, &definition, &[node.model_handle]);
+                    drop(definition);
+
+                    if !group.is_empty() {
+                        sender.do_command(CommandGroup::from(group));
+                    }
+                }
+            }
+        } else if let Some(PopupMessage::Placement(Placement::Cursor(target))) = message.data() {
+            if message.destination() == self.menu.handle() {
+                self.placement_target = *target;
+            }
+        }
+    }
+}
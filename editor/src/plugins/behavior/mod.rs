@@ -0,0 +1,700 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Behavior tree editor allows you to author behavior tree resources (see
+//! [`crate::fyrox::utils::behavior::resource`] module docs) using a node canvas. Every node has
+//! a set of input sockets (one per child) on its left side and an output socket on its right side;
+//! connect an output socket of a node to an input socket of another node to make the first node a
+//! child of the second one.
+//!
+//! The editor is for authoring only - instantiated trees run in the game process, so they cannot
+//! be inspected here. Use [`crate::fyrox::utils::behavior::BehaviorTree::node_status`] to display
+//! the state of a running tree in the game itself.
+
+mod commands;
+mod context;
+
+use crate::{
+    absm::{
+        canvas::{AbsmCanvasBuilder, AbsmCanvasMessage},
+        connection::{Connection, ConnectionBuilder},
+        node::{AbsmNode, AbsmNodeBuilder, AbsmNodeMessage},
+        socket::{Socket, SocketBuilder, SocketDirection},
+        NORMAL_BACKGROUND, NORMAL_ROOT_COLOR, SELECTED_BACKGROUND, SELECTED_ROOT_COLOR,
+    },
+    command::{make_command, Command, CommandGroup},
+    fyrox::{
+        asset::manager::ResourceManager,
+        core::{log::Log, pool::Handle},
+        engine::SerializationContext,
+        fxhash::FxHashMap,
+        graph::BaseSceneGraph,
+        gui::{
+            border::BorderBuilder,
+            grid::{Column, GridBuilder, Row},
+            inspector::{
+                editors::PropertyEditorDefinitionContainer, Inspector, InspectorBuilder,
+                InspectorContext, InspectorMessage,
+            },
+            message::{MessageDirection, UiMessage},
+            widget::{WidgetBuilder, WidgetMessage},
+            window::{WindowBuilder, WindowMessage, WindowTitle},
+            BuildContext, Thickness, UiNode, UserInterface,
+        },
+        utils::behavior::resource::{
+            BehaviorNodeDefinition, BehaviorNodeKind, BehaviorTreeDefinition, BehaviorTreeResource,
+        },
+    },
+    inspector::EditorEnvironment,
+    message::MessageSender,
+    plugin::EditorPlugin,
+    plugins::behavior::{
+        commands::{MoveBehaviorNodeCommand, SetBehaviorNodeChildrenCommand},
+        context::{make_unlink_commands, CanvasContextMenu, NodeContextMenu},
+    },
+    send_sync_message, Editor, Message,
+};
+use std::sync::Arc;
+
+fn fetch_model_handle(view: Handle<UiNode>, ui: &UserInterface) -> Handle<BehaviorNodeDefinition> {
+    ui.node(view)
+        .query_component::<AbsmNode<BehaviorNodeDefinition>>()
+        .unwrap()
+        .model_handle
+}
+
+fn fetch_socket_model_handle(
+    socket: Handle<UiNode>,
+    ui: &UserInterface,
+) -> Handle<BehaviorNodeDefinition> {
+    ui.node(socket)
+        .query_component::<Socket>()
+        .unwrap()
+        .parent_node
+        .into()
+}
+
+fn create_socket(
+    direction: SocketDirection,
+    index: usize,
+    show_index: bool,
+    parent_node: Handle<BehaviorNodeDefinition>,
+    ui: &mut UserInterface,
+) -> Handle<UiNode> {
+    SocketBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
+        .with_direction(direction)
+        .with_parent_node(parent_node.into())
+        .with_index(index)
+        .with_show_index(show_index)
+        .build(&mut ui.build_ctx())
+}
+
+fn create_input_sockets(
+    node: &BehaviorNodeDefinition,
+    parent_node: Handle<BehaviorNodeDefinition>,
+    ui: &mut UserInterface,
+) -> Vec<Handle<UiNode>> {
+    (0..input_socket_count(node))
+        .map(|index| create_socket(SocketDirection::Input, index, true, parent_node, ui))
+        .collect()
+}
+
+fn input_socket_count(node: &BehaviorNodeDefinition) -> usize {
+    // Nodes with unlimited amount of children always have one spare socket to connect a new child.
+    node.kind.max_children().unwrap_or(node.children.len() + 1)
+}
+
+fn make_node_name(node: &BehaviorNodeDefinition) -> String {
+    let kind = match node.kind {
+        BehaviorNodeKind::Parallel {
+            success_policy,
+            failure_policy,
+        } => format!(
+            "Parallel\nSuccess: {}\nFailure: {}",
+            success_policy.as_ref(),
+            failure_policy.as_ref()
+        ),
+        BehaviorNodeKind::Decorator(ref decorator) => {
+            format!("Decorator: {}", decorator.as_ref())
+        }
+        BehaviorNodeKind::Action { ref name } => format!("Action: {name}"),
+        ref kind => kind.as_ref().to_string(),
+    };
+
+    if node.name.is_empty() {
+        kind
+    } else {
+        format!("{}\n{}", node.name, kind)
+    }
+}
+
+fn is_ancestor_of(
+    definition: &BehaviorTreeDefinition,
+    ancestor: Handle<BehaviorNodeDefinition>,
+    mut node: Handle<BehaviorNodeDefinition>,
+) -> bool {
+    while node.is_some() {
+        if node == ancestor {
+            return true;
+        }
+        node = definition.parent_of(node);
+    }
+    false
+}
+
+pub struct BehaviorTreeEditor {
+    window: Handle<UiNode>,
+    canvas: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    resource: BehaviorTreeResource,
+    canvas_context_menu: CanvasContextMenu,
+    node_context_menu: NodeContextMenu,
+    selection: Vec<Handle<BehaviorNodeDefinition>>,
+    need_save: bool,
+}
+
+impl BehaviorTreeEditor {
+    pub fn new(resource: BehaviorTreeResource, ctx: &mut BuildContext) -> Self {
+        let mut canvas_context_menu = CanvasContextMenu::new(ctx);
+        let node_context_menu = NodeContextMenu::new(ctx);
+
+        let canvas = AbsmCanvasBuilder::new(
+            WidgetBuilder::new().with_context_menu(canvas_context_menu.menu.clone()),
+        )
+        .build(ctx);
+
+        let inspector = InspectorBuilder::new(
+            WidgetBuilder::new()
+                .on_column(1)
+                .with_width(300.0)
+                .with_visibility(false),
+        )
+        .build(ctx);
+
+        let content = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_child(
+                    BorderBuilder::new(
+                        WidgetBuilder::new()
+                            .on_column(0)
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_child(canvas),
+                    )
+                    .build(ctx),
+                )
+                .with_child(inspector),
+        )
+        .add_column(Column::stretch())
+        .add_column(Column::auto())
+        .add_row(Row::stretch())
+        .build(ctx);
+
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(900.0).with_height(600.0))
+            .open(false)
+            .with_title(WindowTitle::text(format!(
+                "Behavior Tree Editor - {}",
+                resource.kind()
+            )))
+            .with_content(content)
+            .build(ctx);
+
+        ctx.sender()
+            .send(WindowMessage::open(
+                window,
+                MessageDirection::ToWidget,
+                true,
+                true,
+            ))
+            .unwrap();
+
+        canvas_context_menu.canvas = canvas;
+
+        let mut editor = Self {
+            window,
+            canvas,
+            inspector,
+            resource,
+            canvas_context_menu,
+            node_context_menu,
+            selection: Default::default(),
+            need_save: false,
+        };
+
+        editor.sync_to_model(ctx.inner_mut());
+
+        editor
+    }
+
+    fn destroy(self, ui: &UserInterface) {
+        ui.send_message(WidgetMessage::remove(
+            self.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+
+    pub fn sync_to_model(&mut self, ui: &mut UserInterface) {
+        let definition = self.resource.data_ref();
+
+        let mut views = FxHashMap::default();
+        for child in ui.node(self.canvas).children().to_vec() {
+            if let Some(view) = ui
+                .node(child)
+                .query_component::<AbsmNode<BehaviorNodeDefinition>>()
+            {
+                if definition.nodes.is_valid_handle(view.model_handle) {
+                    views.insert(view.model_handle, child);
+                } else {
+                    // Data model of the view was removed.
+                    send_sync_message(ui, WidgetMessage::remove(child, MessageDirection::ToWidget));
+                }
+            }
+        }
+
+        for (handle, node) in definition.nodes.pair_iter() {
+            let is_root = handle == definition.root;
+
+            if let Some(view) = views.get(&handle).cloned() {
+                let view_ref = ui
+                    .node(view)
+                    .query_component::<AbsmNode<BehaviorNodeDefinition>>()
+                    .unwrap();
+                let position = view_ref.actual_local_position();
+                let socket_count = view_ref.base.input_sockets.len();
+
+                let name = make_node_name(node);
+                if name != view_ref.name_value {
+                    send_sync_message(
+                        ui,
+                        AbsmNodeMessage::name(view, MessageDirection::ToWidget, name),
+                    );
+                }
+
+                if socket_count != input_socket_count(node) {
+                    let input_sockets = create_input_sockets(node, handle, ui);
+                    send_sync_message(
+                        ui,
+                        AbsmNodeMessage::input_sockets(
+                            view,
+                            MessageDirection::ToWidget,
+                            input_sockets,
+                        ),
+                    );
+                }
+
+                if position != node.position {
+                    send_sync_message(
+                        ui,
+                        WidgetMessage::desired_position(
+                            view,
+                            MessageDirection::ToWidget,
+                            node.position,
+                        ),
+                    );
+                }
+            } else {
+                let input_sockets = create_input_sockets(node, handle, ui);
+                // Root node can't have a parent.
+                let output_socket = if is_root {
+                    Handle::NONE
+                } else {
+                    create_socket(SocketDirection::Output, 0, false, handle, ui)
+                };
+
+                let view = AbsmNodeBuilder::new(
+                    WidgetBuilder::new()
+                        .with_desired_position(node.position)
+                        .with_context_menu(self.node_context_menu.menu.clone()),
+                )
+                .with_name(make_node_name(node))
+                .with_input_sockets(input_sockets)
+                .with_output_socket(output_socket)
+                .with_normal_color(if is_root {
+                    NORMAL_ROOT_COLOR
+                } else {
+                    NORMAL_BACKGROUND
+                })
+                .with_selected_color(if is_root {
+                    SELECTED_ROOT_COLOR
+                } else {
+                    SELECTED_BACKGROUND
+                })
+                .with_model_handle(handle)
+                .build(&mut ui.build_ctx());
+
+                send_sync_message(
+                    ui,
+                    WidgetMessage::link(view, MessageDirection::ToWidget, self.canvas),
+                );
+
+                views.insert(handle, view);
+            }
+        }
+
+        // Force update layout to be able to fetch positions of sockets for connections.
+        ui.update_layout(ui.screen_size());
+
+        // Sync connections - remove old ones and create new, the same way as ABSM editor does.
+        for child in ui.node(self.canvas).children().to_vec() {
+            if ui.node(child).has_component::<Connection>() {
+                send_sync_message(ui, WidgetMessage::remove(child, MessageDirection::ToWidget));
+            }
+        }
+
+        for (handle, node) in definition.nodes.pair_iter() {
+            let dest_view = views[&handle];
+            let input_sockets = ui
+                .node(dest_view)
+                .query_component::<AbsmNode<BehaviorNodeDefinition>>()
+                .unwrap()
+                .base
+                .input_sockets
+                .clone();
+
+            for (child, input_socket) in node.children.iter().zip(input_sockets) {
+                let Some(source_view) = views.get(child).cloned() else {
+                    continue;
+                };
+
+                let output_socket = ui
+                    .node(source_view)
+                    .query_component::<AbsmNode<BehaviorNodeDefinition>>()
+                    .unwrap()
+                    .base
+                    .output_socket;
+
+                let connection = ConnectionBuilder::new(WidgetBuilder::new())
+                    .with_source_socket(output_socket)
+                    .with_source_node(source_view)
+                    .with_dest_socket(input_socket)
+                    .with_dest_node(dest_view)
+                    .build(self.canvas, &mut ui.build_ctx());
+
+                send_sync_message(
+                    ui,
+                    WidgetMessage::link(connection, MessageDirection::ToWidget, self.canvas),
+                );
+                send_sync_message(
+                    ui,
+                    WidgetMessage::lowermost(connection, MessageDirection::ToWidget),
+                );
+            }
+        }
+
+        // Sync selection.
+        self.selection
+            .retain(|node| definition.nodes.is_valid_handle(*node));
+
+        send_sync_message(
+            ui,
+            AbsmCanvasMessage::selection_changed(
+                self.canvas,
+                MessageDirection::ToWidget,
+                self.selection.iter().map(|node| views[node]).collect(),
+            ),
+        );
+
+        send_sync_message(
+            ui,
+            AbsmCanvasMessage::force_sync_dependent_objects(
+                self.canvas,
+                MessageDirection::ToWidget,
+            ),
+        );
+
+        if let Some(node) = self
+            .selection
+            .first()
+            .and_then(|selection| definition.nodes.try_borrow(*selection))
+        {
+            let ctx = ui
+                .node(self.inspector)
+                .cast::<Inspector>()
+                .unwrap()
+                .context()
+                .clone();
+
+            if let Err(sync_errors) = ctx.sync(node, ui, 0, true, Default::default()) {
+                for error in sync_errors {
+                    Log::err(format!("Failed to sync property. Reason: {error:?}"))
+                }
+            }
+        }
+    }
+
+    fn set_selection(
+        &mut self,
+        selection: Vec<Handle<BehaviorNodeDefinition>>,
+        ui: &mut UserInterface,
+        resource_manager: &ResourceManager,
+        sender: &MessageSender,
+        property_editors: Arc<PropertyEditorDefinitionContainer>,
+        serialization_context: Arc<SerializationContext>,
+    ) {
+        if self.selection == selection {
+            return;
+        }
+
+        self.selection = selection;
+
+        ui.send_message(WidgetMessage::visibility(
+            self.inspector,
+            MessageDirection::ToWidget,
+            !self.selection.is_empty(),
+        ));
+
+        let definition = self.resource.data_ref();
+        let context = if let Some(node) = self
+            .selection
+            .first()
+            .and_then(|selection| definition.nodes.try_borrow(*selection))
+        {
+            let env = Arc::new(EditorEnvironment {
+                resource_manager: resource_manager.clone(),
+                serialization_context,
+                available_animations: Default::default(),
+                sender: sender.clone(),
+            });
+
+            InspectorContext::from_object(
+                node,
+                &mut ui.build_ctx(),
+                property_editors,
+                Some(env),
+                1,
+                0,
+                true,
+                Default::default(),
+                100.0,
+            )
+        } else {
+            Default::default()
+        };
+
+        ui.send_message(InspectorMessage::context(
+            self.inspector,
+            MessageDirection::ToWidget,
+            context,
+        ));
+    }
+
+    fn connect(
+        &self,
+        child: Handle<BehaviorNodeDefinition>,
+        parent: Handle<BehaviorNodeDefinition>,
+        index: usize,
+        sender: &MessageSender,
+    ) {
+        let definition = self.resource.data_ref();
+
+        if child == definition.root || is_ancestor_of(&definition, child, parent) {
+            Log::warn("A behavior tree must not have cycles!");
+            return;
+        }
+
+        let old_parent = definition.parent_of(child);
+        let mut commands = if old_parent.is_some() && old_parent != parent {
+            make_unlink_commands(&self.resource, &definition, &[child])
+        } else {
+            Vec::new()
+        };
+
+        let parent_ref = &definition.nodes[parent];
+        let mut children = parent_ref.children.clone();
+        children.retain(|c| *c != child);
+        if parent_ref.kind.max_children() == Some(1) {
+            // Replace the previous child.
+            children = vec![child];
+        } else {
+            children.insert(index.min(children.len()), child);
+        }
+
+        commands.push(Command::new(SetBehaviorNodeChildrenCommand {
+            resource: self.resource.clone(),
+            handle: parent,
+            children,
+        }));
+
+        drop(definition);
+
+        sender.do_command(CommandGroup::from(commands));
+    }
+
+    pub fn handle_ui_message(
+        mut self,
+        message: &UiMessage,
+        ui: &mut UserInterface,
+        resource_manager: &ResourceManager,
+        sender: &MessageSender,
+        property_editors: Arc<PropertyEditorDefinitionContainer>,
+        serialization_context: Arc<SerializationContext>,
+    ) -> Option<Self> {
+        if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window {
+                self.update();
+                self.destroy(ui);
+                return None;
+            }
+        } else if let Some(msg) = message.data::<AbsmCanvasMessage>() {
+            if message.destination() == self.canvas {
+                match msg {
+                    AbsmCanvasMessage::CommitDrag { entries } => {
+                        let commands = entries
+                            .iter()
+                            .map(|entry| {
+                                Command::new(MoveBehaviorNodeCommand {
+                                    resource: self.resource.clone(),
+                                    handle: fetch_model_handle(entry.node, ui),
+                                    position: ui.node(entry.node).actual_local_position(),
+                                })
+                            })
+                            .collect::<Vec<_>>();
+
+                        sender.do_command(CommandGroup::from(commands));
+                    }
+                    AbsmCanvasMessage::SelectionChanged(selection)
+                        if message.direction() == MessageDirection::FromWidget =>
+                    {
+                        let selection = selection
+                            .iter()
+                            .filter(|view| {
+                                ui.node(**view)
+                                    .has_component::<AbsmNode<BehaviorNodeDefinition>>()
+                            })
+                            .map(|view| fetch_model_handle(*view, ui))
+                            .collect();
+
+                        self.set_selection(
+                            selection,
+                            ui,
+                            resource_manager,
+                            sender,
+                            property_editors,
+                            serialization_context,
+                        );
+                    }
+                    AbsmCanvasMessage::CommitConnection {
+                        source_socket,
+                        dest_socket,
+                    } => {
+                        let child = fetch_socket_model_handle(*source_socket, ui);
+                        let parent = fetch_socket_model_handle(*dest_socket, ui);
+                        let index = ui
+                            .node(*dest_socket)
+                            .query_component::<Socket>()
+                            .unwrap()
+                            .index;
+
+                        self.connect(child, parent, index, sender);
+                    }
+                    _ => (),
+                }
+            }
+        } else if let Some(InspectorMessage::PropertyChanged(args)) = message.data() {
+            if message.destination() == self.inspector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(selection) = self.selection.first().cloned() {
+                    let resource = self.resource.clone();
+                    sender.send(Message::DoCommand(
+                        make_command(args, move |_| {
+                            // FIXME: HACK!
+                            let definition = unsafe {
+                                std::mem::transmute::<
+                                    &'_ mut BehaviorTreeDefinition,
+                                    &'static mut BehaviorTreeDefinition,
+                                >(&mut *resource.data_ref())
+                            };
+
+                            &mut definition.nodes[selection]
+                        })
+                        .unwrap(),
+                    ));
+                    self.need_save = true;
+                }
+            }
+        }
+
+        self.canvas_context_menu
+            .handle_ui_message(sender, message, ui, &self.resource);
+        self.node_context_menu.handle_ui_message(
+            message,
+            sender,
+            ui,
+            &self.resource,
+            &self.selection,
+        );
+
+        Some(self)
+    }
+
+    pub fn update(&mut self) {
+        if self.need_save {
+            Log::verify(self.resource.save_back());
+            self.need_save = false;
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct BehaviorTreeEditorPlugin {
+    behavior_tree_editor: Option<BehaviorTreeEditor>,
+}
+
+impl EditorPlugin for BehaviorTreeEditorPlugin {
+    fn on_sync_to_model(&mut self, editor: &mut Editor) {
+        if let Some(behavior_tree_editor) = self.behavior_tree_editor.as_mut() {
+            behavior_tree_editor.sync_to_model(editor.engine.user_interfaces.first_mut());
+        }
+    }
+
+    fn on_ui_message(&mut self, message: &mut UiMessage, editor: &mut Editor) {
+        if let Some(behavior_tree_editor) = self.behavior_tree_editor.take() {
+            self.behavior_tree_editor = behavior_tree_editor.handle_ui_message(
+                message,
+                editor.engine.user_interfaces.first_mut(),
+                &editor.engine.resource_manager,
+                &editor.message_sender,
+                editor.inspector.property_editors.clone(),
+                editor.engine.serialization_context.clone(),
+            );
+        }
+    }
+
+    fn on_update(&mut self, _editor: &mut Editor) {
+        if let Some(behavior_tree_editor) = self.behavior_tree_editor.as_mut() {
+            behavior_tree_editor.update();
+        }
+    }
+
+    fn on_message(&mut self, message: &Message, editor: &mut Editor) {
+        if let Message::OpenBehaviorTreeEditor(resource) = message {
+            let ui = editor.engine.user_interfaces.first_mut();
+
+            if let Some(behavior_tree_editor) = self.behavior_tree_editor.take() {
+                behavior_tree_editor.destroy(ui);
+            }
+
+            self.behavior_tree_editor = Some(BehaviorTreeEditor::new(
+                resource.clone(),
+                &mut ui.build_ctx(),
+            ));
+        }
+    }
+}
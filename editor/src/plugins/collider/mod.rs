@@ -267,7 +267,7 @@ lazy_static! {
     };
 }
 
-fn make_handle(scene: &mut Scene, root: Handle<Node>, visible: bool) -> Handle<Node> {
+pub(crate) fn make_handle(scene: &mut Scene, root: Handle<Node>, visible: bool) -> Handle<Node> {
     let mut material = Material::from_shader(GIZMO_SHADER.clone());
 
     material.bind(
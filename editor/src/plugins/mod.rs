@@ -18,5 +18,7 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+pub mod behavior;
 pub mod collider;
 pub mod tilemap;
+pub mod vehicle;
@@ -0,0 +1,545 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Vehicle wheels placement plugin. It allows to move connection points of the wheels with the
+//! move gizmo and to change their radii by dragging the handles at the rims of the wheels.
+
+use crate::{
+    camera::PickingOptions,
+    command::SetPropertyCommand,
+    fyrox::{
+        core::{
+            algebra::{UnitQuaternion, Vector2, Vector3},
+            color::Color,
+            math::{plane::Plane, Matrix4Ext},
+            pool::Handle,
+            type_traits::prelude::*,
+            Uuid,
+        },
+        engine::Engine,
+        graph::{BaseSceneGraph, SceneGraph, SceneGraphNode},
+        gui::{BuildContext, UiNode},
+        scene::{
+            node::Node,
+            sprite::Sprite,
+            vehicle::{Vehicle, Wheel},
+            Scene,
+        },
+    },
+    interaction::{
+        calculate_gizmo_distance_scaling, gizmo::move_gizmo::MoveGizmo,
+        make_interaction_mode_button, plane::PlaneKind, InteractionMode,
+    },
+    message::MessageSender,
+    plugin::EditorPlugin,
+    plugins::collider::make_handle,
+    scene::{commands::GameSceneContext, controller::SceneController, GameScene, Selection},
+    settings::Settings,
+    Editor, Message,
+};
+
+fn try_get_wheels(vehicle: Handle<Node>, scene: &Scene) -> Option<&[Wheel]> {
+    scene
+        .graph
+        .try_get_of_type::<Vehicle>(vehicle)
+        .map(|v| v.wheels())
+}
+
+fn try_get_wheel_mut(vehicle: Handle<Node>, wheel: usize, scene: &mut Scene) -> Option<&mut Wheel> {
+    scene
+        .graph
+        .try_get_mut_of_type::<Vehicle>(vehicle)
+        .and_then(|v| v.wheels_mut().get_mut(wheel))
+}
+
+// A direction in which the radius handle of the wheel is placed. It is perpendicular to both the
+// suspension and the axle, so the handle is always on the rim of the wheel.
+fn radius_axis(wheel: &Wheel) -> Vector3<f32> {
+    wheel
+        .axle
+        .cross(&wheel.direction)
+        .try_normalize(f32::EPSILON)
+        .unwrap_or_else(Vector3::z)
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum WheelHandleKind {
+    ConnectionPoint,
+    Radius,
+}
+
+struct WheelHandles {
+    connection_point: Handle<Node>,
+    radius: Handle<Node>,
+}
+
+impl WheelHandles {
+    fn new(scene: &mut Scene, root: Handle<Node>, visible: bool) -> Self {
+        Self {
+            connection_point: make_handle(scene, root, visible),
+            radius: make_handle(scene, root, visible),
+        }
+    }
+
+    fn kind_of(&self, handle: Handle<Node>) -> Option<WheelHandleKind> {
+        if handle == self.connection_point {
+            Some(WheelHandleKind::ConnectionPoint)
+        } else if handle == self.radius {
+            Some(WheelHandleKind::Radius)
+        } else {
+            None
+        }
+    }
+
+    fn local_position(&self, kind: WheelHandleKind, wheel: &Wheel) -> Vector3<f32> {
+        match kind {
+            WheelHandleKind::ConnectionPoint => wheel.connection_point,
+            WheelHandleKind::Radius => {
+                wheel.center(wheel.suspension_rest_length) + radius_axis(wheel) * wheel.radius
+            }
+        }
+    }
+}
+
+struct DragContext {
+    wheel: usize,
+    kind: WheelHandleKind,
+    initial_handle_position: Vector3<f32>,
+    plane: Plane,
+    initial_radius: f32,
+    plane_kind: Option<PlaneKind>,
+    initial_wheels: Vec<Wheel>,
+}
+
+#[derive(TypeUuidProvider)]
+#[type_uuid(id = "0f5a4a63-3d2e-4a59-8c8a-3c3f6a1d7c52")]
+pub struct VehicleWheelsInteractionMode {
+    vehicle: Handle<Node>,
+    root: Handle<Node>,
+    handles: Vec<WheelHandles>,
+    move_gizmo: MoveGizmo,
+    drag_context: Option<DragContext>,
+    // Index of the wheel, which connection point is moved by the move gizmo.
+    selected_wheel: Option<usize>,
+    message_sender: MessageSender,
+}
+
+impl VehicleWheelsInteractionMode {
+    fn for_each_handle(&self, mut func: impl FnMut(Handle<Node>)) {
+        for handles in self.handles.iter() {
+            func(handles.connection_point);
+            func(handles.radius);
+        }
+    }
+
+    fn find_handle(&self, handle: Handle<Node>) -> Option<(usize, WheelHandleKind)> {
+        self.handles
+            .iter()
+            .enumerate()
+            .find_map(|(index, handles)| handles.kind_of(handle).map(|kind| (index, kind)))
+    }
+
+    fn destroy_handles(&mut self, scene: &mut Scene) {
+        self.for_each_handle(|handle| scene.graph.remove_node(handle));
+        self.handles.clear();
+    }
+
+    fn destroy(mut self, scene: &mut Scene) {
+        self.destroy_handles(scene);
+        self.move_gizmo.destroy(&mut scene.graph);
+    }
+
+    fn set_visibility(
+        &mut self,
+        controller: &dyn SceneController,
+        engine: &mut Engine,
+        visibility: bool,
+    ) {
+        let Some(game_scene) = controller.downcast_ref::<GameScene>() else {
+            return;
+        };
+
+        let scene = &mut engine.scenes[game_scene.scene];
+
+        self.for_each_handle(|handle| {
+            scene.graph[handle].set_visibility(visibility);
+        });
+        if !visibility {
+            self.move_gizmo.set_visible(&mut scene.graph, false);
+        }
+    }
+
+    // Re-creates the handles if the amount of wheels has changed and puts them at the wheels.
+    fn sync_to_vehicle(&mut self, camera: Handle<Node>, scene: &mut Scene) {
+        let wheel_count = try_get_wheels(self.vehicle, scene).map_or(0, |wheels| wheels.len());
+        if wheel_count != self.handles.len() {
+            self.destroy_handles(scene);
+            for _ in 0..wheel_count {
+                let handles = WheelHandles::new(scene, self.root, true);
+                self.handles.push(handles);
+            }
+            self.selected_wheel = self.selected_wheel.filter(|wheel| *wheel < wheel_count);
+        }
+
+        let transform = scene.graph[self.vehicle].global_transform();
+        let rotation = UnitQuaternion::from_matrix_eps(
+            &transform.basis(),
+            f32::EPSILON,
+            16,
+            Default::default(),
+        );
+        for (index, handles) in self.handles.iter().enumerate() {
+            for kind in [WheelHandleKind::ConnectionPoint, WheelHandleKind::Radius] {
+                let Some(wheel) = try_get_wheels(self.vehicle, scene).and_then(|w| w.get(index))
+                else {
+                    continue;
+                };
+                let local_position = handles.local_position(kind, wheel);
+                let handle = match kind {
+                    WheelHandleKind::ConnectionPoint => handles.connection_point,
+                    WheelHandleKind::Radius => handles.radius,
+                };
+                let scale = calculate_gizmo_distance_scaling(&scene.graph, camera, handle);
+
+                let node = &mut scene.graph[handle];
+                node.local_transform_mut()
+                    .set_position(transform.transform_point(&local_position.into()).coords)
+                    .set_scale(scale)
+                    .set_rotation(rotation);
+                if let Some(sprite) = node.component_mut::<Sprite>() {
+                    sprite.set_size(0.05 * scale.x);
+                }
+            }
+        }
+    }
+}
+
+impl InteractionMode for VehicleWheelsInteractionMode {
+    fn on_left_mouse_button_down(
+        &mut self,
+        _editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        mouse_position: Vector2<f32>,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let scene = &mut engine.scenes[game_scene.scene];
+
+        let Some(result) = game_scene.camera_controller.pick(
+            &scene.graph,
+            PickingOptions {
+                cursor_pos: mouse_position,
+                editor_only: true,
+                filter: Some(&mut |handle, _| handle != self.move_gizmo.origin),
+                ..Default::default()
+            },
+        ) else {
+            return;
+        };
+
+        let Some(wheels) = try_get_wheels(self.vehicle, scene).map(|w| w.to_vec()) else {
+            return;
+        };
+
+        let initial_position = scene.graph[result.node].global_position();
+        let camera_view_dir = scene.graph[game_scene.camera_controller.camera]
+            .look_vector()
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_default();
+        let plane =
+            Plane::from_normal_and_point(&-camera_view_dir, &initial_position).unwrap_or_default();
+
+        if let Some((wheel, kind)) = self.find_handle(result.node) {
+            if kind == WheelHandleKind::ConnectionPoint {
+                // Connection points are moved by the move gizmo.
+                self.selected_wheel = Some(wheel);
+            } else {
+                self.drag_context = Some(DragContext {
+                    wheel,
+                    kind,
+                    initial_handle_position: initial_position,
+                    plane,
+                    initial_radius: wheels[wheel].radius,
+                    plane_kind: None,
+                    initial_wheels: wheels.clone(),
+                });
+            }
+        } else if let Some(plane_kind) = self.move_gizmo.handle_pick(result.node, &mut scene.graph)
+        {
+            if let Some(wheel) = self.selected_wheel.filter(|wheel| *wheel < wheels.len()) {
+                self.drag_context = Some(DragContext {
+                    wheel,
+                    kind: WheelHandleKind::ConnectionPoint,
+                    initial_handle_position: initial_position,
+                    plane,
+                    initial_radius: wheels[wheel].radius,
+                    plane_kind: Some(plane_kind),
+                    initial_wheels: wheels.clone(),
+                });
+            }
+        }
+    }
+
+    fn on_left_mouse_button_up(
+        &mut self,
+        _editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        _mouse_pos: Vector2<f32>,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let scene = &mut engine.scenes[game_scene.scene];
+
+        if let Some(drag_context) = self.drag_context.take() {
+            let vehicle = self.vehicle;
+
+            let Some(vehicle_ref) = scene.graph.try_get_mut_of_type::<Vehicle>(vehicle) else {
+                return;
+            };
+
+            // Revert the changes made while dragging and apply them again as a command, so they
+            // can be undone.
+            let value = vehicle_ref.set_wheels(drag_context.initial_wheels);
+
+            let command = SetPropertyCommand::new("wheels".into(), Box::new(value), move |ctx| {
+                ctx.get_mut::<GameSceneContext>()
+                    .scene
+                    .graph
+                    .node_mut(vehicle)
+            });
+            self.message_sender.do_command(command);
+        }
+    }
+
+    fn on_mouse_move(
+        &mut self,
+        mouse_offset: Vector2<f32>,
+        mouse_position: Vector2<f32>,
+        _editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let scene = &mut engine.scenes[game_scene.scene];
+
+        self.for_each_handle(|handle| {
+            scene.graph[handle].as_sprite_mut().set_color(Color::MAROON);
+        });
+        self.move_gizmo.reset_state(&mut scene.graph);
+
+        if let Some(result) = game_scene.camera_controller.pick(
+            &scene.graph,
+            PickingOptions {
+                cursor_pos: mouse_position,
+                editor_only: true,
+                filter: Some(&mut |handle, _| handle != self.move_gizmo.origin),
+                ..Default::default()
+            },
+        ) {
+            if self.find_handle(result.node).is_some() {
+                scene.graph[result.node]
+                    .as_sprite_mut()
+                    .set_color(Color::RED);
+            }
+
+            self.move_gizmo.handle_pick(result.node, &mut scene.graph);
+        }
+
+        let Some(drag_context) = self.drag_context.as_ref() else {
+            return;
+        };
+
+        let inv_transform = scene.graph[self.vehicle]
+            .global_transform()
+            .try_inverse()
+            .unwrap_or_default();
+
+        match drag_context.kind {
+            WheelHandleKind::Radius => {
+                let camera = scene.graph[game_scene.camera_controller.camera].as_camera();
+                let ray = camera.make_ray(mouse_position, frame_size);
+                if let Some(intersection) = ray.plane_intersection_point(&drag_context.plane) {
+                    let local_space_drag_dir = inv_transform
+                        .transform_vector(&(intersection - drag_context.initial_handle_position));
+                    let Some(wheel) = try_get_wheel_mut(self.vehicle, drag_context.wheel, scene)
+                    else {
+                        return;
+                    };
+                    let sign = local_space_drag_dir.dot(&radius_axis(wheel)).signum();
+                    let delta = sign
+                        * drag_context
+                            .initial_handle_position
+                            .metric_distance(&intersection);
+                    wheel.radius = (drag_context.initial_radius + delta).max(0.0);
+                }
+            }
+            WheelHandleKind::ConnectionPoint => {
+                if let Some(plane_kind) = drag_context.plane_kind {
+                    let global_offset = self.move_gizmo.calculate_offset(
+                        &scene.graph,
+                        game_scene.camera_controller.camera,
+                        mouse_offset,
+                        mouse_position,
+                        frame_size,
+                        plane_kind,
+                    );
+
+                    let local_offset = inv_transform.transform_vector(&global_offset);
+
+                    if let Some(wheel) = try_get_wheel_mut(self.vehicle, drag_context.wheel, scene)
+                    {
+                        wheel.connection_point += local_offset;
+                    }
+                }
+            }
+        }
+    }
+
+    fn update(
+        &mut self,
+        _editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let scene = &mut engine.scenes[game_scene.scene];
+
+        self.sync_to_vehicle(game_scene.camera_controller.camera, scene);
+
+        let connection_point = self.selected_wheel.and_then(|wheel| {
+            try_get_wheels(self.vehicle, scene)
+                .and_then(|wheels| wheels.get(wheel))
+                .map(|wheel| wheel.connection_point)
+        });
+        self.move_gizmo
+            .set_visible(&mut scene.graph, connection_point.is_some());
+        if let Some(connection_point) = connection_point {
+            let scale = calculate_gizmo_distance_scaling(
+                &scene.graph,
+                game_scene.camera_controller.camera,
+                self.move_gizmo.origin,
+            );
+            let position = scene.graph[self.vehicle]
+                .global_transform()
+                .transform_point(&connection_point.into())
+                .coords;
+            self.move_gizmo
+                .transform(&mut scene.graph)
+                .set_position(position)
+                .set_scale(scale);
+        }
+    }
+
+    fn activate(&mut self, controller: &dyn SceneController, engine: &mut Engine) {
+        self.set_visibility(controller, engine, true)
+    }
+
+    fn deactivate(&mut self, controller: &dyn SceneController, engine: &mut Engine) {
+        self.set_visibility(controller, engine, false)
+    }
+
+    fn make_button(&mut self, ctx: &mut BuildContext, selected: bool) -> Handle<UiNode> {
+        make_interaction_mode_button(
+            ctx,
+            include_bytes!("../../resources/circle.png"),
+            "Edit Vehicle Wheels",
+            selected,
+        )
+    }
+
+    fn uuid(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
+
+#[derive(Default)]
+pub struct VehicleWheelsPlugin {}
+
+impl EditorPlugin for VehicleWheelsPlugin {
+    fn on_message(&mut self, message: &Message, editor: &mut Editor) {
+        let Some(entry) = editor.scenes.current_scene_entry_mut() else {
+            return;
+        };
+
+        let Some(selection) = entry.selection.as_graph() else {
+            return;
+        };
+
+        let Some(game_scene) = entry.controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let scene = &mut editor.engine.scenes[game_scene.scene];
+
+        if let Message::SelectionChanged { .. } = message {
+            if let Some(mode) = entry
+                .interaction_modes
+                .remove_typed::<VehicleWheelsInteractionMode>()
+            {
+                mode.destroy(scene);
+            }
+
+            if let Some(vehicle) = selection
+                .nodes()
+                .iter()
+                .find(|handle| scene.graph.try_get_of_type::<Vehicle>(**handle).is_some())
+            {
+                let move_gizmo = MoveGizmo::new(game_scene, &mut editor.engine);
+                let scene = &mut editor.engine.scenes[game_scene.scene];
+                move_gizmo.set_visible(&mut scene.graph, false);
+
+                let mut mode = VehicleWheelsInteractionMode {
+                    vehicle: *vehicle,
+                    root: game_scene.editor_objects_root,
+                    handles: Default::default(),
+                    move_gizmo,
+                    drag_context: None,
+                    selected_wheel: None,
+                    message_sender: editor.message_sender.clone(),
+                };
+                mode.sync_to_vehicle(game_scene.camera_controller.camera, scene);
+                mode.for_each_handle(|handle| {
+                    scene.graph[handle].set_visibility(false);
+                });
+
+                entry.interaction_modes.add(mode);
+            }
+        }
+    }
+}
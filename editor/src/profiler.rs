@@ -0,0 +1,183 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::fyrox::{
+    core::{log::Log, pool::Handle, profiler::Profiler},
+    gui::{
+        button::{ButtonBuilder, ButtonContent, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
+        message::{MessageDirection, UiMessage},
+        profiler_view::{ProfilerViewBuilder, ProfilerViewMessage},
+        stack_panel::StackPanelBuilder,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+};
+
+const TRACE_PATH: &str = "profile.json";
+
+pub struct ProfilerWindow {
+    pub window: Handle<UiNode>,
+    view: Handle<UiNode>,
+    pause: Handle<UiNode>,
+    clear: Handle<UiNode>,
+    export: Handle<UiNode>,
+    live: bool,
+    was_enabled: bool,
+}
+
+pub enum ProfilerWindowAction {
+    None,
+    Remove,
+}
+
+impl ProfilerWindow {
+    pub fn new(ctx: &mut BuildContext, anchor: Handle<UiNode>) -> Self {
+        let was_enabled = Profiler::is_enabled();
+        Profiler::set_enabled(true);
+
+        let make_button = |text: &str, ctx: &mut BuildContext| {
+            ButtonBuilder::new(
+                WidgetBuilder::new()
+                    .with_width(80.0)
+                    .with_margin(Thickness::uniform(1.0)),
+            )
+            .with_text(text)
+            .build(ctx)
+        };
+
+        let pause = make_button("Pause", ctx);
+        let clear = make_button("Clear", ctx);
+        let export = make_button("Export", ctx);
+        let view = ProfilerViewBuilder::new(
+            WidgetBuilder::new()
+                .on_row(1)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .build(ctx);
+
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(700.0).with_height(400.0))
+            .open(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_child(pause)
+                                    .with_child(clear)
+                                    .with_child(export),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        )
+                        .with_child(view),
+                )
+                .add_row(Row::auto())
+                .add_row(Row::stretch())
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .with_title(WindowTitle::text("Profiler"))
+            .build(ctx);
+
+        ctx.sender()
+            .send(WindowMessage::open_and_align(
+                window,
+                MessageDirection::ToWidget,
+                anchor,
+                HorizontalAlignment::Right,
+                VerticalAlignment::Top,
+                Thickness::uniform(2.0),
+                false,
+                false,
+            ))
+            .unwrap();
+
+        Self {
+            window,
+            view,
+            pause,
+            clear,
+            export,
+            live: true,
+            was_enabled,
+        }
+    }
+
+    fn set_live(&mut self, live: bool, ui: &UserInterface) {
+        self.live = live;
+        ui.send_message(ButtonMessage::content(
+            self.pause,
+            MessageDirection::ToWidget,
+            ButtonContent::text(if live { "Pause" } else { "Resume" }),
+        ));
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &UserInterface,
+    ) -> ProfilerWindowAction {
+        if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window {
+                ui.send_message(WidgetMessage::remove(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+
+                Profiler::set_enabled(self.was_enabled);
+
+                return ProfilerWindowAction::Remove;
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.pause {
+                ui.send_message(ProfilerViewMessage::live(
+                    self.view,
+                    MessageDirection::ToWidget,
+                    !self.live,
+                ));
+            } else if message.destination() == self.clear {
+                Profiler::clear();
+                ui.send_message(ProfilerViewMessage::frames(
+                    self.view,
+                    MessageDirection::ToWidget,
+                    Vec::new(),
+                ));
+            } else if message.destination() == self.export {
+                match Profiler::save_chrome_trace(TRACE_PATH) {
+                    Ok(_) => Log::info(format!(
+                        "Profile was saved to {TRACE_PATH}. Open it in chrome://tracing or Perfetto."
+                    )),
+                    Err(e) => Log::err(format!("Unable to save profile. Reason: {e}")),
+                }
+            }
+        } else if let Some(ProfilerViewMessage::Live(live)) = message.data() {
+            if message.destination() == self.view
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.set_live(*live, ui);
+            }
+        }
+        ProfilerWindowAction::None
+    }
+}
@@ -78,7 +78,9 @@ use crate::{
     },
     highlight::HighlightRenderPass,
     inspector::{
-        editors::handle::HandlePropertyEditorMessage,
+        editors::{
+            handle::HandlePropertyEditorMessage, scene_node_ref::resolve_picked_scene_nodes,
+        },
         handlers::node::SceneNodePropertyChangedHandler,
     },
     interaction::navmesh::selection::NavmeshSelection,
@@ -219,7 +221,13 @@ impl GameScene {
             scene_content_root,
             camera_controller,
             preview_instance: None,
-            scene: engine.scenes.add(scene),
+            scene: {
+                let handle = engine.scenes.add(scene);
+                engine
+                    .scenes
+                    .set_source_path(handle, path.map(|path| path.to_path_buf()));
+                handle
+            },
             clipboard: Default::default(),
             preview_camera: Default::default(),
             graph_switches: GraphUpdateSwitches {
@@ -271,6 +279,10 @@ impl GameScene {
         if let Err(e) = visitor.save_binary(path) {
             Err(format!("Failed to save scene! Reason: {e}"))
         } else {
+            engine
+                .scenes
+                .set_source_path(self.scene, Some(path.to_path_buf()));
+
             if settings.debugging.save_scene_in_text_form {
                 let text = visitor.save_text();
                 let mut path = path.to_path_buf();
@@ -1025,6 +1037,42 @@ impl SceneController for GameScene {
                 );
                 false
             }
+            Message::SyncSceneNodeRef { view, scene, node } => {
+                let (handle, name) = if node.0.is_nil() {
+                    (Handle::NONE, None)
+                } else if scene.is_none()
+                    || scene.as_deref() == engine.scenes.source_path(self.scene)
+                {
+                    let graph = &engine.scenes[self.scene].graph;
+                    let handle = graph.id_to_node_handle(*node).cloned().unwrap_or_default();
+                    (handle, graph.try_get(handle).map(|n| n.name_owned()))
+                } else {
+                    // The node belongs to another scene, it can't be selected in this one.
+                    let scene = scene.as_ref().unwrap();
+                    let name = engine
+                        .scenes
+                        .find_by_source_path(scene)
+                        .and_then(|other| engine.scenes[other].graph.node_by_id(*node))
+                        .map(|(_, n)| n.name())
+                        .unwrap_or("<Unloaded>");
+                    (
+                        Handle::NONE,
+                        Some(format!("{} in {}", name, scene.display())),
+                    )
+                };
+                let ui = engine.user_interfaces.first_mut();
+                ui.send_message(HandlePropertyEditorMessage::sync_value(
+                    *view,
+                    MessageDirection::ToWidget,
+                    handle.into(),
+                ));
+                ui.send_message(HandlePropertyEditorMessage::name(
+                    *view,
+                    MessageDirection::ToWidget,
+                    name,
+                ));
+                false
+            }
             _ => false,
         }
     }
@@ -1135,6 +1183,13 @@ impl SceneController for GameScene {
         selection: &Selection,
         engine: &mut Engine,
     ) {
+        let resolved = resolve_picked_scene_nodes(
+            args,
+            engine.scenes.source_path(self.scene),
+            &engine.scenes[self.scene].graph,
+        );
+        let args = resolved.as_ref().unwrap_or(args);
+
         let scene = &mut engine.scenes[self.scene];
 
         let group = if let Some(selection) = selection.as_graph() {
@@ -38,7 +38,7 @@ use crate::fyrox::{
     scene::{
         dim2,
         graph::{
-            physics::{IntegrationParameters, PhysicsWorld},
+            physics::{FixedTimestep, IntegrationParameters, PhysicsWorld},
             Graph, NodePool,
         },
         SceneRenderingOptions,
@@ -82,6 +82,7 @@ impl SceneSettingsWindow {
 
         container.register_inheritable_inspectable::<Graph>();
         container.register_inheritable_inspectable::<IntegrationParameters>();
+        container.register_inheritable_inspectable::<FixedTimestep>();
         container.register_inheritable_inspectable::<PhysicsWorld>();
         container.register_inheritable_inspectable::<dim2::physics::PhysicsWorld>();
         container.register_inheritable_inspectable::<SceneRenderingOptions>();
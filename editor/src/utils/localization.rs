@@ -0,0 +1,238 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! A window that shows missing translations of every string table in the project.
+
+use crate::fyrox::{
+    asset::{manager::ResourceManager, state::ResourceState, ResourceData},
+    core::pool::Handle,
+    engine::Engine,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
+        localization::{StringTable, StringTableResource},
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        text::{TextBuilder, TextMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+};
+use std::{fmt::Write, path::Path};
+
+pub struct MissingTranslationsWindow {
+    pub window: Handle<UiNode>,
+    text: Handle<UiNode>,
+    refresh: Handle<UiNode>,
+    tables: Vec<StringTableResource>,
+    report: String,
+}
+
+pub enum MissingTranslationsWindowAction {
+    None,
+    Remove,
+}
+
+fn collect_string_tables(dir: &Path, tables: &mut Vec<std::path::PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            // Skip build artifacts and hidden folders (.git, etc.).
+            let skip = path.file_name().map_or(true, |name| {
+                name == "target" || name.to_string_lossy().starts_with('.')
+            });
+            if !skip {
+                collect_string_tables(&path, tables);
+            }
+        } else if path.extension().is_some_and(|ext| ext == "strings") {
+            tables.push(path);
+        }
+    }
+}
+
+fn make_report(tables: &[StringTableResource]) -> String {
+    if tables.is_empty() {
+        return "No string tables (*.strings) found in the project.".to_string();
+    }
+
+    let mut report = String::new();
+    for table in tables {
+        let _ = writeln!(report, "{}", table.kind());
+        let header = table.header();
+        match header.state {
+            ResourceState::Pending { .. } => {
+                let _ = writeln!(report, "    Loading...");
+            }
+            ResourceState::LoadError { ref error, .. } => {
+                let _ = writeln!(report, "    Failed to load: {error:?}");
+            }
+            ResourceState::Ok(ref data) => {
+                let Some(table) = ResourceData::as_any(&**data).downcast_ref::<StringTable>()
+                else {
+                    continue;
+                };
+                let missing = table.missing_translations();
+                if missing.is_empty() {
+                    let _ = writeln!(report, "    No missing translations.");
+                }
+                let mut iter = missing.iter().peekable();
+                while let Some(first) = iter.next() {
+                    let mut locales = vec![first.locale.as_str()];
+                    while let Some(next) = iter.next_if(|next| next.key == first.key) {
+                        locales.push(next.locale.as_str());
+                    }
+                    let _ = writeln!(report, "    {}: {}", first.key, locales.join(", "));
+                }
+            }
+        }
+        report.push('\n');
+    }
+    report
+}
+
+impl MissingTranslationsWindow {
+    pub fn new(
+        ctx: &mut BuildContext,
+        anchor: Handle<UiNode>,
+        resource_manager: &ResourceManager,
+    ) -> Self {
+        let text;
+        let refresh;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(350.0).with_height(400.0))
+            .open(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            ScrollViewerBuilder::new(WidgetBuilder::new().on_row(0))
+                                .with_content({
+                                    text = TextBuilder::new(
+                                        WidgetBuilder::new().with_margin(Thickness::uniform(2.0)),
+                                    )
+                                    .build(ctx);
+                                    text
+                                })
+                                .build(ctx),
+                        )
+                        .with_child({
+                            refresh = ButtonBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_width(100.0)
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .with_text("Refresh")
+                            .build(ctx);
+                            refresh
+                        }),
+                )
+                .add_row(Row::stretch())
+                .add_row(Row::strict(26.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .with_title(WindowTitle::text("Missing Translations"))
+            .build(ctx);
+
+        ctx.sender()
+            .send(WindowMessage::open_and_align(
+                window,
+                MessageDirection::ToWidget,
+                anchor,
+                HorizontalAlignment::Right,
+                VerticalAlignment::Top,
+                Thickness::uniform(2.0),
+                false,
+                false,
+            ))
+            .unwrap();
+
+        let mut this = Self {
+            window,
+            text,
+            refresh,
+            tables: Default::default(),
+            report: Default::default(),
+        };
+        this.scan(resource_manager);
+        this
+    }
+
+    fn scan(&mut self, resource_manager: &ResourceManager) {
+        let mut paths = Vec::new();
+        collect_string_tables(Path::new("."), &mut paths);
+        paths.sort();
+        self.tables = paths
+            .into_iter()
+            .map(|path| {
+                let path = path.strip_prefix("./").unwrap_or(&path).to_path_buf();
+                resource_manager.request::<StringTable>(path)
+            })
+            .collect();
+        self.report.clear();
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        engine: &Engine,
+    ) -> MissingTranslationsWindowAction {
+        let ui = engine.user_interfaces.first();
+        if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window {
+                ui.send_message(WidgetMessage::remove(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+
+                return MissingTranslationsWindowAction::Remove;
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.refresh {
+                // Reload the tables, they could be edited externally.
+                for table in self.tables.iter() {
+                    engine
+                        .resource_manager
+                        .state()
+                        .reload_resource(table.clone().into());
+                }
+                self.scan(&engine.resource_manager);
+            }
+        }
+        MissingTranslationsWindowAction::None
+    }
+
+    pub fn update(&mut self, ui: &UserInterface) {
+        let report = make_report(&self.tables);
+        if report != self.report {
+            ui.send_message(TextMessage::text(
+                self.text,
+                MessageDirection::ToWidget,
+                report.clone(),
+            ));
+            self.report = report;
+        }
+    }
+}
@@ -32,6 +32,7 @@ use crate::fyrox::{
 use std::{fs::File, io::Read, path::Path};
 
 pub mod doc;
+pub mod localization;
 pub mod path_fixer;
 pub mod ragdoll;
 
@@ -29,9 +29,15 @@ use syn::*;
 // impl `#[derive(Visit)]` for `struct` or `enum`
 pub fn impl_visit(ast: DeriveInput) -> TokenStream2 {
     let ty_args = args::TypeArgs::from_derive_input(&ast).unwrap();
-    match &ty_args.data {
+    let visit_impl = match &ty_args.data {
         ast::Data::Struct(ref field_args) => self::impl_visit_struct(&ty_args, field_args),
         ast::Data::Enum(ref variants) => self::impl_visit_enum(&ty_args, variants),
+    };
+    let version_key = utils::create_version_key(&ty_args);
+
+    quote! {
+        #visit_impl
+        #version_key
     }
 }
 
@@ -43,6 +49,8 @@ fn impl_visit_struct(
     let visit_fn_body = if field_args.style == ast::Style::Unit {
         quote! { Ok(()) }
     } else {
+        let version_visit = utils::create_version_visit(ty_args);
+
         // `field.visit(..)?;` parts
         let field_visits = utils::create_field_visits(
             true,
@@ -56,6 +64,7 @@ fn impl_visit_struct(
                 Ok(x) => x,
                 Err(err) => return Err(err),
             };
+            #version_visit
             #(#field_visits)*
             Ok(())
         }
@@ -172,6 +181,8 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
         }
     };
 
+    let version_visit = utils::create_version_visit(ty_args);
+
     // visit every field of each variant
     let variant_visits = variant_args.iter().map(|variant| {
         let (fields, style) = (&variant.fields, variant.fields.style);
@@ -220,6 +231,8 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
                  Err(err) => return Err(err),
              };
 
+             #version_visit
+
              let mut id = id(self);
              if let Err(err) = id.visit("Id", &mut region) {
                  return Err(err);
@@ -29,6 +29,16 @@ pub struct TypeArgs {
     pub data: ast::Data<VariantArgs, FieldArgs>,
     #[darling(default)]
     pub optional: bool,
+    /// `#[visit(version = N)]`
+    ///
+    /// Store the version in the region and upgrade older data with registered migrations
+    #[darling(default)]
+    pub version: Option<u32>,
+    /// `#[visit(version_key = "..")]`
+    ///
+    /// Key of the migrations of the type, defaults to the path of the type
+    #[darling(default)]
+    pub version_key: Option<String>,
 }
 
 /// Parsed from struct's or enum variant's field
@@ -68,6 +68,44 @@ fn create_impl_generics(
     generics
 }
 
+/// `impl Type { pub const VISIT_VERSION_KEY: &str = ".."; }` if the type has `#[visit(version = N)]`
+pub fn create_version_key(ty_args: &args::TypeArgs) -> TokenStream2 {
+    if ty_args.version.is_none() {
+        return quote! {};
+    }
+
+    let ty_ident = &ty_args.ident;
+    let key = match ty_args.version_key {
+        Some(ref key) => quote! { #key },
+        None => {
+            let ty_name = ty_ident.to_string();
+            quote! { concat!(module_path!(), "::", #ty_name) }
+        }
+    };
+    let (impl_generics, ty_generics, where_clause) = ty_args.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #ty_ident #ty_generics #where_clause {
+            /// The key of the migrations of this type, see `VisitMigrations`.
+            pub const VISIT_VERSION_KEY: &'static str = #key;
+        }
+    }
+}
+
+/// `region.visit_version(Self::VISIT_VERSION_KEY, version)?;` if the type has `#[visit(version = N)]`
+pub fn create_version_visit(ty_args: &args::TypeArgs) -> TokenStream2 {
+    match ty_args.version {
+        Some(version) => {
+            quote! {
+                if let Err(err) = region.visit_version(Self::VISIT_VERSION_KEY, #version) {
+                    return Err(err);
+                }
+            }
+        }
+        None => quote! {},
+    }
+}
+
 /// `<prefix>field.visit("name", visitor)?;`
 pub fn create_field_visits<'a>(
     // false if enum variant
@@ -20,7 +20,7 @@
 
 //! Fight the compatibility hell with attributes! .. someday :)
 
-use fyrox_core::visitor::prelude::*;
+use fyrox_core::visitor::{prelude::*, VisitMigrations};
 
 // Comment it out and make sure it panics
 // #[derive(Debug, Clone, PartialEq, Visit)]
@@ -45,3 +45,158 @@ fn rename() {
 
     assert_eq!(data, data_default);
 }
+
+#[derive(Debug, Clone, PartialEq, Visit)]
+#[visit(version = 2)]
+pub struct Versioned {
+    pub range: f32,
+    pub color: Color,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Visit)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+}
+
+// Version 0: `radius` instead of `range`, version 1: flat `red` and `green` fields.
+#[derive(Visit)]
+pub struct VersionedV0 {
+    pub radius: f32,
+    pub red: u8,
+    pub green: u8,
+}
+
+#[test]
+fn version_migrations() {
+    VisitMigrations::register(Versioned::VISIT_VERSION_KEY, 0, 1, |visitor, region| {
+        visitor
+            .node_mut(region)
+            .unwrap()
+            .rename_field("Radius", "Range")
+    })
+    .unwrap();
+    VisitMigrations::register(Versioned::VISIT_VERSION_KEY, 1, 2, |visitor, region| {
+        let color = visitor.add_region(region, "Color")?;
+        for (old, new) in [("Red", "R"), ("Green", "G")] {
+            let mut field = visitor
+                .node_mut(region)
+                .unwrap()
+                .remove_field(old)
+                .ok_or_else(|| VisitError::FieldDoesNotExist(old.to_string()))?;
+            field.set_name(new);
+            visitor.node_mut(color).unwrap().add_field(field)?;
+        }
+        Ok(())
+    })
+    .unwrap();
+
+    let mut visitor = Visitor::new();
+    VersionedV0 {
+        radius: 5.0,
+        red: 10,
+        green: 20,
+    }
+    .visit("Data", &mut visitor)
+    .unwrap();
+
+    let mut visitor = Visitor::load_from_memory(&visitor.save_binary_to_vec().unwrap()).unwrap();
+    let mut data = Versioned {
+        range: 0.0,
+        color: Default::default(),
+    };
+    data.visit("Data", &mut visitor).unwrap();
+
+    assert_eq!(
+        data,
+        Versioned {
+            range: 5.0,
+            color: Color { r: 10, g: 20 }
+        }
+    );
+    assert_eq!(
+        visitor
+            .applied_migrations()
+            .iter()
+            .map(|m| m.to_string())
+            .collect::<Vec<_>>(),
+        [
+            "it::visit::compat::Versioned at __ROOT__/Data: 0 -> 1",
+            "it::visit::compat::Versioned at __ROOT__/Data: 1 -> 2"
+        ]
+    );
+
+    // Current data is read as is.
+    let mut data_default = Versioned {
+        range: 0.0,
+        color: Default::default(),
+    };
+    super::save_load("version_migrations", &mut data, &mut data_default);
+    assert_eq!(data, data_default);
+
+    // Data from the future can't be read.
+    let mut visitor = Visitor::new();
+    {
+        let mut region = visitor.enter_region("Data").unwrap();
+        region
+            .visit_version(Versioned::VISIT_VERSION_KEY, 3)
+            .unwrap();
+    }
+    let mut visitor = Visitor::load_from_memory(&visitor.save_binary_to_vec().unwrap()).unwrap();
+    assert!(data.visit("Data", &mut visitor).is_err());
+}
+
+mod other {
+    use fyrox_core::visitor::prelude::*;
+
+    // Same name as the type above, but its migrations must not be shared.
+    #[derive(Debug, Default, PartialEq, Visit)]
+    #[visit(version = 1)]
+    pub struct Versioned {
+        pub value: u32,
+    }
+
+    #[derive(Debug, Default, PartialEq, Visit)]
+    #[visit(version = 1, version_key = "explicit-key")]
+    pub struct Keyed {
+        pub value: u32,
+    }
+}
+
+#[test]
+fn version_keys() {
+    assert_eq!(
+        other::Versioned::VISIT_VERSION_KEY,
+        "it::visit::compat::other::Versioned"
+    );
+    assert_ne!(
+        other::Versioned::VISIT_VERSION_KEY,
+        Versioned::VISIT_VERSION_KEY
+    );
+    assert_eq!(other::Keyed::VISIT_VERSION_KEY, "explicit-key");
+
+    VisitMigrations::register(other::Keyed::VISIT_VERSION_KEY, 0, 1, |_, _| Ok(())).unwrap();
+    // The second registration is rejected and does not replace the first one.
+    assert!(
+        VisitMigrations::register(other::Keyed::VISIT_VERSION_KEY, 0, 1, |_, _| {
+            Err(VisitError::User("must not be called".to_string()))
+        })
+        .is_err()
+    );
+    assert!(!VisitMigrations::is_registered(
+        other::Versioned::VISIT_VERSION_KEY,
+        0
+    ));
+
+    let mut visitor = Visitor::new();
+    {
+        let mut region = visitor.enter_region("Data").unwrap();
+        region.visit_version("explicit-key", 0).unwrap();
+        let mut value = 7u32;
+        value.visit("Value", &mut region).unwrap();
+    }
+    let mut visitor = Visitor::load_from_memory(&visitor.save_binary_to_vec().unwrap()).unwrap();
+    let mut data = other::Keyed::default();
+    data.visit("Data", &mut visitor).unwrap();
+    assert_eq!(data, other::Keyed { value: 7 });
+}
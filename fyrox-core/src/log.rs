@@ -18,15 +18,45 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-//! Simple logger, it writes in file and in console at the same time.
+//! Logger with message targets, per-target verbosity, structured key-value fields and pluggable
+//! sinks. By default it writes in file and in console at the same time.
+//!
+//! Messages could be written using simple [`Log::info`], [`Log::warn`], [`Log::err`] methods or
+//! using [`LogRecord`], that allows you to specify a target and a set of fields of the message:
+//!
+//! ```rust
+//! use fyrox_core::log::{Log, LogRecord, MessageKind};
+//!
+//! Log::set_target_verbosity("physics", MessageKind::Warning);
+//!
+//! Log::record(
+//!     LogRecord::new(MessageKind::Warning, "Too many contacts")
+//!         .with_target("physics::contacts")
+//!         .with_field("count", 1024),
+//! );
+//! ```
+//!
+//! Every message goes through a set of sinks (see [`LogSink`]). There are a few built-in sinks:
+//! [`ConsoleSink`], [`FileSink`] (with optional rotation) and [`TcpSink`] that sends messages to
+//! a remote application. The logger also keeps a small ring buffer of the last messages, that
+//! could be used for crash reports (see [`Log::crash_report`]).
+//!
+//! Messages of third-party crates that use `log` or `tracing` facades could be forwarded to the
+//! logger using [`Log::install_log_facade`] and [`Log::install_tracing_bridge`].
 
 use crate::lazy_static::lazy_static;
+use crate::net::NetStream;
 use crate::parking_lot::Mutex;
-use std::fmt::{Debug, Display};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt::{Debug, Display, Write as _};
 
 use crate::instant::Instant;
 #[cfg(not(target_arch = "wasm32"))]
 use std::io::{self, Write};
+use std::net::ToSocketAddrs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::mpsc::Sender;
 use std::time::Duration;
 
@@ -43,6 +73,7 @@ extern "C" {
 }
 
 /// A message that could be sent by the logger to all listeners.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct LogMessage {
     /// Kind of the message: information, warning or error.
     pub kind: MessageKind,
@@ -51,20 +82,256 @@ pub struct LogMessage {
     /// Time point at which the message was recorded. It is relative to the moment when the
     /// logger was initialized.
     pub time: Duration,
+    /// Target of the message, usually a module path or a name of a subsystem. Empty if the
+    /// message has no target.
+    pub target: String,
+    /// Structured key-value fields of the message.
+    pub fields: Vec<(String, String)>,
+}
+
+impl LogMessage {
+    /// Formats the message the same way as it is written to the console: with the kind prefix,
+    /// the target and the fields.
+    pub fn format(&self) -> String {
+        let mut text = self.kind.as_str().to_string();
+        if !self.target.is_empty() {
+            let _ = write!(text, "[{}] ", self.target);
+        }
+        let content = self.content.strip_suffix('\n');
+        text += content.unwrap_or(&self.content);
+        for (name, value) in self.fields.iter() {
+            let _ = write!(text, " {name}={value}");
+        }
+        if content.is_some() {
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// A message with a target and a set of fields, that could be passed to [`Log::record`].
+#[derive(Clone, Debug)]
+pub struct LogRecord {
+    kind: MessageKind,
+    target: String,
+    content: String,
+    fields: Vec<(String, String)>,
+}
+
+impl LogRecord {
+    /// Creates a new record of the given kind, without a target and fields.
+    pub fn new<S>(kind: MessageKind, content: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            kind,
+            target: Default::default(),
+            content: content.into(),
+            fields: Default::default(),
+        }
+    }
+
+    /// Sets the target of the record. Targets are usually module paths (`fyrox::scene::graph`)
+    /// or names of subsystems (`physics`), they're used to filter messages (see
+    /// [`Log::set_target_verbosity`]).
+    pub fn with_target<S>(mut self, target: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.target = target.into();
+        self
+    }
+
+    /// Adds a key-value field to the record.
+    pub fn with_field<K, V>(mut self, name: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Display,
+    {
+        self.fields.push((name.into(), value.to_string()));
+        self
+    }
+}
+
+/// A destination of log messages. Sinks are called while the logger is locked, so they must not
+/// write anything to the log by themselves.
+pub trait LogSink: Send {
+    /// Writes the message.
+    fn write(&mut self, message: &LogMessage);
+
+    /// Flushes all buffered messages, if any.
+    fn flush(&mut self) {}
+}
+
+/// A sink that writes messages to the standard output (or to the browser console on WebAssembly).
+#[derive(Default)]
+pub struct ConsoleSink;
+
+impl LogSink for ConsoleSink {
+    fn write(&mut self, message: &LogMessage) {
+        #[cfg(target_arch = "wasm32")]
+        {
+            log(&message.format());
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let _ = io::stdout().write_all(message.format().as_bytes());
+        }
+    }
+
+    fn flush(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let _ = io::stdout().flush();
+        }
+    }
+}
+
+/// A sink that writes messages to a file. The file could be rotated when it becomes too large,
+/// see [`Self::with_rotation`].
+pub struct FileSink {
+    path: PathBuf,
+    file: Option<std::fs::File>,
+    size: u64,
+    max_size: Option<u64>,
+    max_files: usize,
+}
+
+impl FileSink {
+    /// Creates a new file (or truncates existing one) at the given path.
+    pub fn new<P>(path: P) -> std::io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_path_buf();
+        Ok(Self {
+            file: Some(std::fs::File::create(&path)?),
+            path,
+            size: 0,
+            max_size: None,
+            max_files: 0,
+        })
+    }
+
+    /// Enables file rotation. When the size of the file exceeds `max_size` bytes, the file is
+    /// renamed to `<path>.1` (previous `<path>.1` becomes `<path>.2` and so on) and a new file is
+    /// created. Only `max_files` previous files are kept.
+    pub fn with_rotation(mut self, max_size: u64, max_files: usize) -> Self {
+        self.max_size = Some(max_size);
+        self.max_files = max_files;
+        self
+    }
+
+    /// Returns the path of the current file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{index}"));
+        path.into()
+    }
+
+    fn rotate(&mut self) {
+        self.file = None;
+        if self.max_files == 0 {
+            let _ = std::fs::remove_file(&self.path);
+        } else {
+            let _ = std::fs::remove_file(self.rotated_path(self.max_files));
+            for index in (1..self.max_files).rev() {
+                let _ = std::fs::rename(self.rotated_path(index), self.rotated_path(index + 1));
+            }
+            let _ = std::fs::rename(&self.path, self.rotated_path(1));
+        }
+        self.file = std::fs::File::create(&self.path).ok();
+        self.size = 0;
+    }
+}
+
+impl LogSink for FileSink {
+    fn write(&mut self, message: &LogMessage) {
+        let text = message.format();
+        if self
+            .max_size
+            .is_some_and(|max_size| self.size > 0 && self.size + text.len() as u64 > max_size)
+        {
+            self.rotate();
+        }
+        if let Some(file) = self.file.as_mut() {
+            if std::io::Write::write_all(file, text.as_bytes()).is_ok() {
+                self.size += text.len() as u64;
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Some(file) = self.file.as_mut() {
+            let _ = std::io::Write::flush(file);
+        }
+    }
+}
+
+/// A sink that sends messages to a remote application over TCP. Every message is sent as
+/// [`LogMessage`] using [`NetStream::send_message`], so the receiving side could read them using
+/// [`NetStream::process_input`]. The sink stops sending messages once the connection is broken.
+pub struct TcpSink {
+    stream: Option<NetStream>,
+}
+
+impl TcpSink {
+    /// Connects to the given address.
+    pub fn connect<A>(address: A) -> std::io::Result<Self>
+    where
+        A: ToSocketAddrs,
+    {
+        Ok(Self {
+            stream: Some(NetStream::connect(address)?),
+        })
+    }
+
+    /// Returns `true` if the sink is still connected.
+    pub fn is_connected(&self) -> bool {
+        self.stream.is_some()
+    }
+}
+
+impl LogSink for TcpSink {
+    fn write(&mut self, message: &LogMessage) {
+        if let Some(stream) = self.stream.as_mut() {
+            if stream.send_message(message).is_err() {
+                self.stream = None;
+            }
+        }
+    }
+}
+
+fn default_sinks() -> Vec<Box<dyn LogSink>> {
+    #[allow(unused_mut)]
+    let mut sinks: Vec<Box<dyn LogSink>> = vec![Box::new(ConsoleSink)];
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    if let Ok(file) = FileSink::new("fyrox.log") {
+        sinks.push(Box::new(file));
+    }
+    sinks
 }
 
 lazy_static! {
     static ref LOG: Mutex<Log> = Mutex::new(Log {
-        #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
-        file: std::fs::File::create("fyrox.log").unwrap(),
         verbosity: MessageKind::Information,
+        target_verbosity: Default::default(),
         listeners: Default::default(),
+        sinks: default_sinks(),
+        recent: Default::default(),
+        recent_capacity: 256,
         time_origin: Instant::now()
     });
 }
 
 /// A kind of message.
-#[derive(Copy, Clone, PartialOrd, PartialEq, Eq, Ord, Hash)]
+#[derive(Copy, Clone, Debug, PartialOrd, PartialEq, Eq, Ord, Hash, Serialize, Deserialize)]
 #[repr(u32)]
 pub enum MessageKind {
     /// Some useful information.
@@ -87,51 +354,67 @@ impl MessageKind {
 
 /// See module docs.
 pub struct Log {
-    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
-    file: std::fs::File,
     verbosity: MessageKind,
+    target_verbosity: Vec<(String, MessageKind)>,
     listeners: Vec<Sender<LogMessage>>,
+    sinks: Vec<Box<dyn LogSink>>,
+    recent: VecDeque<LogMessage>,
+    recent_capacity: usize,
     time_origin: Instant,
 }
 
+fn target_matches(target: &str, prefix: &str) -> bool {
+    target
+        .strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::") || rest.starts_with('.'))
+}
+
 impl Log {
-    fn write_internal<S>(&mut self, kind: MessageKind, message: S)
-    where
-        S: AsRef<str>,
-    {
-        let mut msg = message.as_ref().to_owned();
-        if kind as u32 >= self.verbosity as u32 {
-            // Notify listeners about the message and remove all disconnected listeners.
-            self.listeners.retain(|listener| {
-                listener
-                    .send(LogMessage {
-                        kind,
-                        content: msg.clone(),
-                        time: Instant::now() - self.time_origin,
-                    })
-                    .is_ok()
-            });
+    fn verbosity_of(&self, target: &str) -> MessageKind {
+        self.target_verbosity
+            .iter()
+            .filter(|(prefix, _)| target_matches(target, prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, kind)| *kind)
+            .unwrap_or(self.verbosity)
+    }
+
+    fn write_record(&mut self, record: LogRecord) {
+        if record.kind < self.verbosity_of(&record.target) {
+            return;
+        }
 
-            msg.insert_str(0, kind.as_str());
+        let message = LogMessage {
+            kind: record.kind,
+            content: record.content,
+            time: Instant::now() - self.time_origin,
+            target: record.target,
+            fields: record.fields,
+        };
 
-            #[cfg(target_arch = "wasm32")]
-            {
-                log(&msg);
-            }
+        // Notify listeners about the message and remove all disconnected listeners.
+        self.listeners
+            .retain(|listener| listener.send(message.clone()).is_ok());
 
-            #[cfg(all(not(target_os = "android"), not(target_arch = "wasm32")))]
-            {
-                let _ = io::stdout().write_all(msg.as_bytes());
-                let _ = self.file.write_all(msg.as_bytes());
-            }
+        for sink in self.sinks.iter_mut() {
+            sink.write(&message);
+        }
 
-            #[cfg(target_os = "android")]
-            {
-                let _ = io::stdout().write_all(msg.as_bytes());
+        if self.recent_capacity > 0 {
+            while self.recent.len() >= self.recent_capacity {
+                self.recent.pop_front();
             }
+            self.recent.push_back(message);
         }
     }
 
+    fn write_internal<S>(&mut self, kind: MessageKind, message: S)
+    where
+        S: AsRef<str>,
+    {
+        self.write_record(LogRecord::new(kind, message.as_ref()))
+    }
+
     fn writeln_internal<S>(&mut self, kind: MessageKind, message: S)
     where
         S: AsRef<str>,
@@ -157,6 +440,15 @@ impl Log {
         LOG.lock().writeln_internal(kind, msg);
     }
 
+    /// Writes a record with a target and a set of fields. A new line is added to the message
+    /// automatically.
+    pub fn record(mut record: LogRecord) {
+        if !record.content.ends_with('\n') {
+            record.content.push('\n');
+        }
+        LOG.lock().write_record(record);
+    }
+
     /// Writes information message.
     pub fn info<S>(msg: S)
     where
@@ -186,11 +478,140 @@ impl Log {
         LOG.lock().verbosity = kind;
     }
 
+    /// Sets verbosity level for the messages of the given target and all its sub-targets. For
+    /// example, verbosity of `fyrox::scene` is applied to `fyrox::scene::graph` target too. The
+    /// most specific target wins, messages of other targets use global verbosity (see
+    /// [`Self::set_verbosity`]).
+    pub fn set_target_verbosity<S>(target: S, kind: MessageKind)
+    where
+        S: Into<String>,
+    {
+        let target = target.into();
+        let mut log = LOG.lock();
+        if let Some(entry) = log.target_verbosity.iter_mut().find(|(t, _)| *t == target) {
+            entry.1 = kind;
+        } else {
+            log.target_verbosity.push((target, kind));
+        }
+    }
+
+    /// Removes verbosity level of the given target, so its messages will use verbosity of its
+    /// parent target or global verbosity.
+    pub fn reset_target_verbosity(target: &str) {
+        LOG.lock().target_verbosity.retain(|(t, _)| t != target);
+    }
+
+    /// Returns `true` if a message of the given kind and target will be written to the log.
+    pub fn is_enabled(kind: MessageKind, target: &str) -> bool {
+        kind >= LOG.lock().verbosity_of(target)
+    }
+
     /// Adds a listener that will receive a copy of every message passed into the log.
     pub fn add_listener(listener: Sender<LogMessage>) {
         LOG.lock().listeners.push(listener)
     }
 
+    /// Adds a new sink, that will receive every message passed into the log.
+    pub fn add_sink<S>(sink: S)
+    where
+        S: LogSink + 'static,
+    {
+        LOG.lock().sinks.push(Box::new(sink))
+    }
+
+    /// Removes all sinks, including the default ones (console and `fyrox.log` file).
+    pub fn clear_sinks() {
+        LOG.lock().sinks.clear()
+    }
+
+    /// Flushes every sink.
+    pub fn flush() {
+        for sink in LOG.lock().sinks.iter_mut() {
+            sink.flush();
+        }
+    }
+
+    /// Sets the amount of recent messages kept by the logger (256 by default). Zero disables
+    /// the ring buffer.
+    pub fn set_recent_messages_capacity(capacity: usize) {
+        let mut log = LOG.lock();
+        log.recent_capacity = capacity;
+        while log.recent.len() > capacity {
+            log.recent.pop_front();
+        }
+    }
+
+    /// Returns a copy of recent messages, from the oldest to the newest one.
+    pub fn recent_messages() -> Vec<LogMessage> {
+        LOG.lock().recent.iter().cloned().collect()
+    }
+
+    /// Formats recent messages as a single string, that could be attached to a crash report.
+    pub fn crash_report() -> String {
+        Self::format_crash_report(&LOG.lock())
+    }
+
+    fn format_crash_report(log: &Log) -> String {
+        let mut report = String::new();
+        for message in log.recent.iter() {
+            let _ = write!(
+                report,
+                "[{:.3}s] {}",
+                message.time.as_secs_f32(),
+                message.format()
+            );
+            if !report.ends_with('\n') {
+                report.push('\n');
+            }
+        }
+        report
+    }
+
+    /// Installs a panic hook, that writes recent messages and the panic info to a file at the
+    /// given path. Previous panic hook is called after that.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn install_crash_handler<P>(path: P)
+    where
+        P: Into<PathBuf>,
+    {
+        let path = path.into();
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            // The panic could happen while the logger is locked.
+            let mut report = LOG
+                .try_lock()
+                .map(|log| Self::format_crash_report(&log))
+                .unwrap_or_default();
+            let _ = writeln!(report, "{info}");
+            let _ = std::fs::write(&path, report);
+            previous(info);
+        }));
+    }
+
+    /// Forwards messages of the `log` facade to the logger. `Debug` and `Trace` messages are
+    /// written as information messages and they're disabled by default, use
+    /// [`log::set_max_level`] to enable them. Returns `false` if another logger was installed
+    /// already.
+    pub fn install_log_facade() -> bool {
+        static FACADE: LogFacade = LogFacade;
+        if ::log::set_logger(&FACADE).is_ok() {
+            ::log::set_max_level(::log::LevelFilter::Info);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Forwards events of the `tracing` facade to the logger. Only `Info`, `Warn` and `Error`
+    /// events are forwarded, spans are ignored. Returns `false` if another global subscriber
+    /// was installed already.
+    pub fn install_tracing_bridge() -> bool {
+        tracing_core::dispatcher::set_global_default(tracing_core::Dispatch::new(
+            TracingBridge::default(),
+        ))
+        .is_ok()
+    }
+
     /// Allows you to verify that the result of operation is Ok, or print the error in the log.
     ///
     /// # Use cases
@@ -225,3 +646,199 @@ impl Log {
         }
     }
 }
+
+struct LogFacade;
+
+impl LogFacade {
+    fn kind(level: ::log::Level) -> MessageKind {
+        match level {
+            ::log::Level::Error => MessageKind::Error,
+            ::log::Level::Warn => MessageKind::Warning,
+            _ => MessageKind::Information,
+        }
+    }
+}
+
+impl ::log::Log for LogFacade {
+    fn enabled(&self, metadata: &::log::Metadata) -> bool {
+        Log::is_enabled(Self::kind(metadata.level()), metadata.target())
+    }
+
+    fn log(&self, record: &::log::Record) {
+        Log::record(
+            LogRecord::new(Self::kind(record.level()), record.args().to_string())
+                .with_target(record.target()),
+        )
+    }
+
+    fn flush(&self) {
+        Log::flush()
+    }
+}
+
+#[derive(Default)]
+struct TracingBridge {
+    next_span_id: AtomicU64,
+}
+
+impl TracingBridge {
+    fn kind(level: &tracing_core::Level) -> MessageKind {
+        match *level {
+            tracing_core::Level::ERROR => MessageKind::Error,
+            tracing_core::Level::WARN => MessageKind::Warning,
+            _ => MessageKind::Information,
+        }
+    }
+}
+
+#[derive(Default)]
+struct TracingFieldVisitor {
+    content: String,
+    fields: Vec<(String, String)>,
+}
+
+impl tracing_core::field::Visit for TracingFieldVisitor {
+    fn record_str(&mut self, field: &tracing_core::Field, value: &str) {
+        if field.name() == "message" {
+            self.content = value.to_string();
+        } else {
+            self.fields
+                .push((field.name().to_string(), value.to_string()));
+        }
+    }
+
+    fn record_debug(&mut self, field: &tracing_core::Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.content = format!("{value:?}");
+        } else {
+            self.fields
+                .push((field.name().to_string(), format!("{value:?}")));
+        }
+    }
+}
+
+impl tracing_core::Subscriber for TracingBridge {
+    fn enabled(&self, metadata: &tracing_core::Metadata<'_>) -> bool {
+        *metadata.level() <= tracing_core::Level::INFO
+            && Log::is_enabled(Self::kind(metadata.level()), metadata.target())
+    }
+
+    fn new_span(&self, _span: &tracing_core::span::Attributes<'_>) -> tracing_core::span::Id {
+        tracing_core::span::Id::from_u64(self.next_span_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _span: &tracing_core::span::Id, _values: &tracing_core::span::Record<'_>) {}
+
+    fn record_follows_from(
+        &self,
+        _span: &tracing_core::span::Id,
+        _follows: &tracing_core::span::Id,
+    ) {
+    }
+
+    fn event(&self, event: &tracing_core::Event<'_>) {
+        let mut visitor = TracingFieldVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        let mut record = LogRecord::new(Self::kind(metadata.level()), visitor.content)
+            .with_target(metadata.target());
+        record.fields = visitor.fields;
+        Log::record(record)
+    }
+
+    fn enter(&self, _span: &tracing_core::span::Id) {}
+
+    fn exit(&self, _span: &tracing_core::span::Id) {}
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::{mpsc::channel, Arc};
+
+    struct TestSink(Arc<Mutex<Vec<String>>>);
+
+    impl LogSink for TestSink {
+        fn write(&mut self, message: &LogMessage) {
+            self.0.lock().push(message.format());
+        }
+    }
+
+    #[test]
+    fn test_log_targets_and_sinks() {
+        let (sender, receiver) = channel();
+        Log::add_listener(sender);
+        let written = Arc::new(Mutex::new(Vec::new()));
+        Log::add_sink(TestSink(written.clone()));
+
+        Log::set_target_verbosity("test_log_targets", MessageKind::Error);
+        Log::set_target_verbosity("test_log_targets::verbose", MessageKind::Information);
+        assert!(!Log::is_enabled(
+            MessageKind::Warning,
+            "test_log_targets::quiet"
+        ));
+        assert!(Log::is_enabled(
+            MessageKind::Information,
+            "test_log_targets::verbose::inner"
+        ));
+        assert!(Log::is_enabled(MessageKind::Warning, "test_log_targetsfoo"));
+
+        Log::record(
+            LogRecord::new(MessageKind::Warning, "Hidden").with_target("test_log_targets::quiet"),
+        );
+        Log::record(
+            LogRecord::new(MessageKind::Information, "Shown")
+                .with_target("test_log_targets::verbose")
+                .with_field("count", 3),
+        );
+
+        let messages = receiver
+            .try_iter()
+            .filter(|m| m.target.starts_with("test_log_targets"))
+            .collect::<Vec<_>>();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].content, "Shown\n");
+        assert_eq!(
+            messages[0].fields,
+            vec![("count".to_string(), "3".to_string())]
+        );
+        assert!(written
+            .lock()
+            .contains(&"[INFO]: [test_log_targets::verbose] Shown count=3\n".to_string()));
+        assert!(Log::recent_messages()
+            .iter()
+            .any(|m| m.target == "test_log_targets::verbose"));
+        assert!(Log::crash_report().contains("Shown count=3"));
+
+        Log::reset_target_verbosity("test_log_targets");
+        Log::reset_target_verbosity("test_log_targets::verbose");
+    }
+
+    #[test]
+    fn test_file_sink_rotation() {
+        let dir = std::env::temp_dir().join(format!("fyrox_log_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("test.log");
+        let mut sink = FileSink::new(&path).unwrap().with_rotation(16, 2);
+        for i in 0..4 {
+            sink.write(&LogMessage {
+                kind: MessageKind::Information,
+                content: format!("Message {i}\n"),
+                time: Default::default(),
+                target: Default::default(),
+                fields: Default::default(),
+            });
+        }
+        sink.flush();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "[INFO]: Message 3\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(sink.rotated_path(2)).unwrap(),
+            "[INFO]: Message 1\n"
+        );
+        assert!(!sink.rotated_path(3).exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
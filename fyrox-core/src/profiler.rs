@@ -0,0 +1,538 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Frame profiler, that records hierarchical timings of scopes on every thread and groups them
+//! by frames. See [`Profiler`] docs for more info.
+
+use crate::{instant::Instant, lazy_static::lazy_static, parking_lot::Mutex};
+use std::{
+    borrow::Cow,
+    cell::Cell,
+    collections::VecDeque,
+    fmt::Write,
+    future::Future,
+    path::Path,
+    pin::Pin,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// Timings of a single scope.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScopeRecord {
+    /// Name of the scope.
+    pub name: Cow<'static, str>,
+    /// Index of the thread on which the scope was executed. See [`Profiler::thread_names`].
+    pub thread: usize,
+    /// Time point at which the scope was entered. It is relative to the moment when the
+    /// profiler was initialized.
+    pub start: Duration,
+    /// Duration of the scope.
+    pub duration: Duration,
+}
+
+impl ScopeRecord {
+    /// Returns the time point at which the scope was left.
+    pub fn end(&self) -> Duration {
+        self.start + self.duration
+    }
+}
+
+/// All scopes that were finished during a single frame.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FrameRecord {
+    /// Index of the frame.
+    pub index: u64,
+    /// Time point at which the frame was started. It is relative to the moment when the profiler
+    /// was initialized.
+    pub start: Duration,
+    /// Duration of the frame.
+    pub duration: Duration,
+    /// Scopes of the frame sorted by start time.
+    pub scopes: Vec<ScopeRecord>,
+}
+
+impl FrameRecord {
+    /// Calculates nesting depth of every scope of the frame. Scopes of different threads are
+    /// never nested.
+    pub fn scope_depths(&self) -> Vec<usize> {
+        let mut depths = Vec::with_capacity(self.scopes.len());
+        let mut stacks: Vec<Vec<Duration>> = Vec::new();
+        for scope in self.scopes.iter() {
+            if stacks.len() <= scope.thread {
+                stacks.resize(scope.thread + 1, Vec::new());
+            }
+            let stack = &mut stacks[scope.thread];
+            while stack.last().is_some_and(|end| *end <= scope.start) {
+                stack.pop();
+            }
+            depths.push(stack.len());
+            stack.push(scope.end());
+        }
+        depths
+    }
+
+    /// Returns total duration of every scope with the given name.
+    pub fn total_time_of(&self, name: &str) -> Duration {
+        self.scopes
+            .iter()
+            .filter(|scope| scope.name == name)
+            .map(|scope| scope.duration)
+            .sum()
+    }
+}
+
+struct ProfilerState {
+    current: FrameRecord,
+    frames: VecDeque<FrameRecord>,
+    capacity: usize,
+    threads: Vec<String>,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(cfg!(feature = "enable_profiler"));
+static THREAD_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+lazy_static! {
+    static ref TIME_ORIGIN: Instant = Instant::now();
+    static ref PROFILER: Mutex<ProfilerState> = Mutex::new(ProfilerState {
+        current: Default::default(),
+        frames: Default::default(),
+        capacity: 120,
+        threads: Default::default(),
+    });
+}
+
+thread_local! {
+    static THREAD_INDEX: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+fn current_thread_index() -> usize {
+    THREAD_INDEX.with(|index| match index.get() {
+        Some(index) => index,
+        None => {
+            let new_index = THREAD_COUNTER.fetch_add(1, Ordering::Relaxed);
+            let thread = std::thread::current();
+            let name = thread
+                .name()
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| format!("Thread {new_index}"));
+            let mut profiler = PROFILER.lock();
+            if profiler.threads.len() <= new_index {
+                profiler.threads.resize(new_index + 1, String::new());
+            }
+            profiler.threads[new_index] = name;
+            index.set(Some(new_index));
+            new_index
+        }
+    })
+}
+
+fn duration_between(start: Instant, end: Instant) -> Duration {
+    if end > start {
+        end - start
+    } else {
+        Duration::default()
+    }
+}
+
+fn time_since_origin(instant: Instant) -> Duration {
+    duration_between(*TIME_ORIGIN, instant)
+}
+
+/// Frame profiler. It records timings of scopes (see [`ScopeGuard`] and [`crate::scope_profile`])
+/// on every thread, including the jobs of the task pool. Recorded scopes are grouped by frames,
+/// a frame is finished by [`Profiler::next_frame`] (the engine does this at the beginning of every
+/// update). The profiler keeps a ring buffer of recent frames, that could be exported to Chrome
+/// trace format (see [`Profiler::chrome_trace`]) and viewed in `chrome://tracing`, Perfetto or in
+/// a profiler view widget.
+///
+/// The profiler is disabled by default, unless `enable_profiler` feature is enabled. It could be
+/// enabled at any time using [`Profiler::set_enabled`]. Disabled profiler has almost no overhead.
+///
+/// ```rust
+/// use fyrox_core::{profiler::Profiler, scope_profile};
+///
+/// fn update_ai() {
+///     scope_profile!();
+///     // ...
+/// }
+///
+/// Profiler::set_enabled(true);
+/// update_ai();
+/// Profiler::next_frame();
+/// let frame = Profiler::last_frame().unwrap();
+/// assert!(frame.scopes.iter().any(|scope| scope.name.ends_with("update_ai")));
+/// let trace = Profiler::chrome_trace(&Profiler::frames());
+/// ```
+pub struct Profiler;
+
+impl Profiler {
+    /// Enables or disables the profiler.
+    pub fn set_enabled(enabled: bool) {
+        lazy_static::initialize(&TIME_ORIGIN);
+        ENABLED.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if the profiler is enabled.
+    #[inline]
+    pub fn is_enabled() -> bool {
+        ENABLED.load(Ordering::Relaxed)
+    }
+
+    /// Sets the amount of recent frames kept by the profiler (120 by default).
+    pub fn set_frame_capacity(capacity: usize) {
+        let mut profiler = PROFILER.lock();
+        profiler.capacity = capacity;
+        while profiler.frames.len() > capacity {
+            profiler.frames.pop_front();
+        }
+    }
+
+    /// Returns the amount of recent frames kept by the profiler.
+    pub fn frame_capacity() -> usize {
+        PROFILER.lock().capacity
+    }
+
+    /// Records a scope with the given name and timings. Usually there's no need to call this
+    /// method directly, use [`ScopeGuard`] or [`crate::scope_profile`] instead.
+    pub fn record_scope(name: Cow<'static, str>, start: Instant, end: Instant) {
+        if !Self::is_enabled() {
+            return;
+        }
+        let thread = current_thread_index();
+        let scope = ScopeRecord {
+            name,
+            thread,
+            start: time_since_origin(start),
+            duration: duration_between(start, end),
+        };
+        PROFILER.lock().current.scopes.push(scope);
+    }
+
+    /// Finishes current frame and starts a new one. Finished frame is added to the ring buffer of
+    /// recent frames. The engine calls this method at the beginning of every update.
+    pub fn next_frame() {
+        let now = time_since_origin(Instant::now());
+        let mut profiler = PROFILER.lock();
+        let index = profiler.current.index + 1;
+        let mut frame = std::mem::replace(
+            &mut profiler.current,
+            FrameRecord {
+                index,
+                start: now,
+                ..Default::default()
+            },
+        );
+        if !Self::is_enabled() && frame.scopes.is_empty() {
+            return;
+        }
+        frame.duration = now.saturating_sub(frame.start);
+        // Scopes are recorded when they end, so the outer scopes go after the inner ones.
+        frame
+            .scopes
+            .sort_by(|a, b| a.start.cmp(&b.start).then(b.duration.cmp(&a.duration)));
+        if profiler.capacity > 0 {
+            while profiler.frames.len() >= profiler.capacity {
+                profiler.frames.pop_front();
+            }
+            profiler.frames.push_back(frame);
+        }
+    }
+
+    /// Returns a copy of recent frames, from the oldest to the newest one.
+    pub fn frames() -> Vec<FrameRecord> {
+        PROFILER.lock().frames.iter().cloned().collect()
+    }
+
+    /// Returns a copy of the last finished frame.
+    pub fn last_frame() -> Option<FrameRecord> {
+        PROFILER.lock().frames.back().cloned()
+    }
+
+    /// Returns the index of the last finished frame. It could be used to check whether there's a
+    /// new frame without copying it.
+    pub fn last_frame_index() -> Option<u64> {
+        PROFILER.lock().frames.back().map(|frame| frame.index)
+    }
+
+    /// Removes every recent frame.
+    pub fn clear() {
+        PROFILER.lock().frames.clear();
+    }
+
+    /// Returns names of every thread that recorded at least one scope. Indices of the names match
+    /// [`ScopeRecord::thread`].
+    pub fn thread_names() -> Vec<String> {
+        PROFILER.lock().threads.clone()
+    }
+
+    /// Converts the given frames to Chrome trace event format (JSON). The result could be viewed
+    /// in `chrome://tracing` or in Perfetto. Every event is written on its own line.
+    pub fn chrome_trace(frames: &[FrameRecord]) -> String {
+        fn micros(duration: Duration) -> f64 {
+            duration.as_secs_f64() * 1_000_000.0
+        }
+
+        fn write_str(out: &mut String, string: &str) {
+            out.push('"');
+            for c in string.chars() {
+                match c {
+                    '"' => out.push_str("\\\""),
+                    '\\' => out.push_str("\\\\"),
+                    '\n' => out.push_str("\\n"),
+                    '\r' => out.push_str("\\r"),
+                    '\t' => out.push_str("\\t"),
+                    c if c.is_control() => {
+                        let _ = write!(out, "\\u{:04x}", c as u32);
+                    }
+                    c => out.push(c),
+                }
+            }
+            out.push('"');
+        }
+
+        let mut events = Vec::new();
+        for (index, name) in Self::thread_names().into_iter().enumerate() {
+            let mut event = String::from(r#"{"name":"thread_name","ph":"M","pid":1,"tid":"#);
+            let _ = write!(event, r#"{index},"args":{{"name":"#);
+            write_str(&mut event, &name);
+            event.push_str("}}");
+            events.push(event);
+        }
+        for frame in frames {
+            events.push(format!(
+                r#"{{"name":"Frame {}","cat":"frame","ph":"i","s":"g","pid":1,"tid":0,"ts":{}}}"#,
+                frame.index,
+                micros(frame.start)
+            ));
+            for scope in frame.scopes.iter() {
+                let mut event = String::from(r#"{"name":"#);
+                write_str(&mut event, &scope.name);
+                let _ = write!(
+                    event,
+                    r#","cat":"scope","ph":"X","pid":1,"tid":{},"ts":{},"dur":{}}}"#,
+                    scope.thread,
+                    micros(scope.start),
+                    micros(scope.duration)
+                );
+                events.push(event);
+            }
+        }
+
+        format!("{{\"traceEvents\":[\n{}\n]}}", events.join(",\n"))
+    }
+
+    /// Saves recent frames to a file in Chrome trace event format. See [`Self::chrome_trace`].
+    pub fn save_chrome_trace<P>(path: P) -> std::io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        std::fs::write(path, Self::chrome_trace(&Self::frames()))
+    }
+}
+
+/// A guard that records a scope from the moment of its creation till the moment it is dropped.
+/// See [`crate::scope_profile`] for a more convenient way of profiling scopes.
+pub struct ScopeGuard {
+    name: Option<Cow<'static, str>>,
+    start: Instant,
+}
+
+impl ScopeGuard {
+    /// Enters a new scope with the given name. Does nothing if the profiler is disabled.
+    #[inline]
+    pub fn new<N>(name: N) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+    {
+        Self {
+            name: Profiler::is_enabled().then(|| name.into()),
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for ScopeGuard {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(name) = self.name.take() {
+            Profiler::record_scope(name, self.start, Instant::now());
+        }
+    }
+}
+
+/// A future, that records a scope every time it is polled. See [`profile_future`].
+pub struct ProfiledFuture<F> {
+    name: Cow<'static, str>,
+    future: Pin<Box<F>>,
+}
+
+impl<F> Future for ProfiledFuture<F>
+where
+    F: Future,
+{
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let _guard = ScopeGuard::new(self.name.clone());
+        self.future.as_mut().poll(cx)
+    }
+}
+
+/// Wraps the given future, so every poll of it will be recorded as a scope with the given name.
+/// It is useful to profile asynchronous jobs, such as resource loading, that could be executed on
+/// multiple threads.
+pub fn profile_future<F, N>(name: N, future: F) -> ProfiledFuture<F>
+where
+    F: Future,
+    N: Into<Cow<'static, str>>,
+{
+    ProfiledFuture {
+        name: name.into(),
+        future: Box::pin(future),
+    }
+}
+
+/// Returns the name of a function by a full type name of a function item declared inside of
+/// it. Used by [`crate::scope_profile`].
+#[doc(hidden)]
+pub fn function_name(type_name: &'static str) -> &'static str {
+    let name = type_name.strip_suffix("::f").unwrap_or(type_name);
+    // Keep only the last two segments (usually `Type::method`).
+    match name.rmatch_indices("::").nth(1) {
+        Some((index, _)) => &name[index + 2..],
+        None => name,
+    }
+}
+
+/// Records the current scope (till the end of the block) in the profiler. The name of the scope
+/// is the name of the enclosing function, or the given name.
+///
+/// ```rust
+/// use fyrox_core::scope_profile;
+///
+/// fn update() {
+///     scope_profile!();
+///     {
+///         scope_profile!("Heavy part");
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! scope_profile {
+    () => {
+        let _profiler_scope = $crate::profiler::ScopeGuard::new({
+            fn f() {}
+            fn type_name_of<T>(_: T) -> &'static str {
+                std::any::type_name::<T>()
+            }
+            $crate::profiler::function_name(type_name_of(f))
+        });
+    };
+    ($name:expr) => {
+        let _profiler_scope = $crate::profiler::ScopeGuard::new($name);
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_profiler() {
+        assert_eq!(
+            function_name("fyrox::engine::Engine::update::f"),
+            "Engine::update"
+        );
+        assert_eq!(function_name("update::f"), "update");
+
+        Profiler::set_enabled(true);
+        fn test_profiler_outer() {
+            scope_profile!();
+            for _ in 0..2 {
+                scope_profile!("test_profiler_inner");
+                std::thread::sleep(Duration::from_millis(1));
+            }
+        }
+        test_profiler_outer();
+        std::thread::Builder::new()
+            .name("test_profiler_worker".to_string())
+            .spawn(|| {
+                scope_profile!("test_profiler_worker_scope");
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+        Profiler::next_frame();
+
+        let frame = Profiler::frames()
+            .into_iter()
+            .rev()
+            .find(|frame| {
+                frame
+                    .scopes
+                    .iter()
+                    .any(|scope| scope.name == "test_profiler_inner")
+            })
+            .unwrap();
+        let depths = frame.scope_depths();
+        let outer = frame
+            .scopes
+            .iter()
+            .position(|scope| scope.name.ends_with("test_profiler_outer"))
+            .unwrap();
+        let inner = frame
+            .scopes
+            .iter()
+            .enumerate()
+            .filter(|(_, scope)| scope.name == "test_profiler_inner")
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        assert_eq!(inner.len(), 2);
+        assert!(inner
+            .iter()
+            .all(|i| *i > outer && depths[*i] == depths[outer] + 1));
+        assert!(frame.total_time_of("test_profiler_inner") >= Duration::from_millis(2));
+
+        let worker = frame
+            .scopes
+            .iter()
+            .find(|scope| scope.name == "test_profiler_worker_scope")
+            .unwrap();
+        assert_ne!(worker.thread, frame.scopes[outer].thread);
+        assert_eq!(
+            Profiler::thread_names()[worker.thread],
+            "test_profiler_worker"
+        );
+
+        let trace = Profiler::chrome_trace(&[frame]);
+        assert!(trace.starts_with("{\"traceEvents\":["));
+        assert!(trace.lines().any(|event| {
+            event.starts_with(r#"{"name":"test_profiler_inner","cat":"scope","ph":"X""#)
+                && event
+                    .rsplit_once(r#""dur":"#)
+                    .and_then(|(_, dur)| dur.trim_end_matches([',', '}']).parse::<f64>().ok())
+                    .is_some_and(|dur| dur >= 1000.0)
+        }));
+        assert!(trace.lines().any(|event| event.contains(r#""ph":"M""#)
+            && event.contains(r#""args":{"name":"test_profiler_worker"}"#)));
+    }
+}
@@ -21,6 +21,7 @@
 //! Runtime reflection
 
 mod external_impls;
+pub mod serde_bridge;
 mod std_impls;
 
 pub use fyrox_core_derive::Reflect;
@@ -0,0 +1,955 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! A bridge between [`Reflect`] and serde. It allows to write any `dyn Reflect` value to any serde
+//! data format and to read it back into an existing value.
+//!
+//! The value is walked using reflection only, so the types do not have to implement `Serialize` or
+//! `Deserialize`. The mapping is:
+//!
+//! - types registered with [`ReflectSerde::register_leaf`] (numbers, strings, vectors, etc.) use
+//!   their own serde implementation;
+//! - inheritable variables are written as their inner value;
+//! - lists and arrays are written as sequences;
+//! - hash maps are written as sequences of `[key, value]` pairs, so the keys may be of any type;
+//! - enum variants with fields are written as `{"Variant": {"field": value, ..}}`, unit variants of
+//!   enums registered with [`ReflectSerde::register_enum`] are written as `"Variant"`;
+//! - everything else is written as a map of its fields.
+//!
+//! Reading is done in-place: the data is applied to an existing value, fields that are missing in
+//! the data keep their current values and unknown fields are ignored. Reflection can't create new
+//! values, so growing lists and hash maps or switching enum variants requires a factory registered
+//! in [`ReflectSerde`]. Deserialization needs a self-describing format (JSON, RON, etc.).
+//!
+//! The values are passed to the serializer exactly as they are, so the floats are written as
+//! precisely as the format allows (non-finite floats are kept by RON, but JSON can't store them).
+//!
+//! ```
+//! # use fyrox_core::reflect::{
+//! #     prelude::*,
+//! #     serde_bridge::{ReflectDeserializer, ReflectSerde, ReflectSerializer},
+//! # };
+//! # use serde::de::DeserializeSeed;
+//! #[derive(Reflect, Debug, Default)]
+//! struct Player {
+//!     name: String,
+//!     health: f32,
+//! }
+//!
+//! let serde = ReflectSerde::new();
+//! let player = Player { name: "Alice".to_string(), health: 100.0 };
+//! let text = ron::to_string(&ReflectSerializer::new(&player, &serde)).unwrap();
+//!
+//! let mut loaded = Player::default();
+//! ReflectDeserializer::new(&mut loaded, &serde)
+//!     .deserialize(&mut ron::Deserializer::from_str(&text).unwrap())
+//!     .unwrap();
+//! assert_eq!(loaded.name, "Alice");
+//! ```
+
+mod content;
+
+use crate::{reflect::prelude::*, sstorage::ImmutableString};
+use content::Content;
+use fxhash::FxHashMap;
+use serde::{
+    de::{value::Error, DeserializeOwned, DeserializeSeed},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    any::{Any, TypeId},
+    fmt::{Display, Formatter},
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
+
+/// An error that may occur when a reflected value is converted to serde data or back.
+#[derive(Debug)]
+pub enum ReflectSerdeError {
+    /// A registered leaf type failed to serialize or deserialize itself.
+    Serde {
+        /// Name of the type.
+        type_name: String,
+        /// Description of the error.
+        message: String,
+    },
+    /// The data has a different shape than the value it is applied to.
+    UnexpectedData {
+        /// Name of the type.
+        type_name: String,
+        /// What kind of data was expected.
+        expected: &'static str,
+    },
+    /// The value must be changed to another enum variant, but the enum is not registered.
+    UnknownVariant {
+        /// Name of the type.
+        type_name: String,
+        /// Name of the variant from the data.
+        variant: String,
+    },
+    /// A new item must be created for a list or a hash map, but there is no factory for it.
+    NoFactory {
+        /// Name of the collection type.
+        type_name: String,
+    },
+    /// An array has a fixed size that is different from the size in the data.
+    LengthMismatch {
+        /// Name of the array type.
+        type_name: String,
+        /// Length of the array.
+        expected: usize,
+        /// Length of the data.
+        actual: usize,
+    },
+}
+
+impl std::error::Error for ReflectSerdeError {}
+
+impl Display for ReflectSerdeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Serde { type_name, message } => write!(f, "{type_name}: {message}"),
+            Self::UnexpectedData {
+                type_name,
+                expected,
+            } => write!(f, "{type_name}: expected {expected}"),
+            Self::UnknownVariant { type_name, variant } => write!(
+                f,
+                "{type_name}: unable to switch to variant {variant}, the enum is not registered"
+            ),
+            Self::NoFactory { type_name } => write!(
+                f,
+                "{type_name}: unable to create new items, the collection is not registered"
+            ),
+            Self::LengthMismatch {
+                type_name,
+                expected,
+                actual,
+            } => write!(f, "{type_name}: expected {expected} items, got {actual}"),
+        }
+    }
+}
+
+struct Leaf {
+    to_content: fn(&dyn Any) -> Result<Content, Error>,
+    apply: fn(&mut dyn Any, &Content) -> Result<(), Error>,
+}
+
+struct EnumVariants {
+    variant_name: fn(&dyn Any) -> String,
+    make_variant: fn(&str) -> Option<Box<dyn Reflect>>,
+}
+
+type Factory = fn() -> Box<dyn Reflect>;
+
+/// A registry of types that need special treatment when a reflected value is converted to serde
+/// data or back, see the [module docs](self). [`ReflectSerde::new`] registers the standard types,
+/// the types of the engine can be added by the users of the bridge.
+pub struct ReflectSerde {
+    leaves: FxHashMap<TypeId, Leaf>,
+    enums: FxHashMap<TypeId, EnumVariants>,
+    list_items: FxHashMap<TypeId, Factory>,
+    map_entries: FxHashMap<TypeId, (Factory, Factory)>,
+}
+
+impl Default for ReflectSerde {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+macro_rules! register_leaves {
+    ($serde:ident, $($ty:ty),*) => {
+        $(
+            $serde.register_leaf::<$ty>();
+            $serde.register_list::<$ty>();
+            $serde.register_option::<$ty>();
+        )*
+    };
+}
+
+fn leaf_to_content<T: Serialize + 'static>(any: &dyn Any) -> Result<Content, Error> {
+    Content::from_value(any.downcast_ref::<T>().unwrap())
+}
+
+fn apply_leaf<T: DeserializeOwned + 'static>(
+    any: &mut dyn Any,
+    content: &Content,
+) -> Result<(), Error> {
+    *any.downcast_mut::<T>().unwrap() = content.to_value()?;
+    Ok(())
+}
+
+fn make_default<T: Reflect + Default>() -> Box<dyn Reflect> {
+    Box::<T>::default()
+}
+
+fn concrete_type_id(value: &dyn Reflect) -> TypeId {
+    let mut type_id = TypeId::of::<()>();
+    value.as_any(&mut |any| type_id = any.type_id());
+    type_id
+}
+
+fn unexpected(type_name: &str, expected: &'static str) -> ReflectSerdeError {
+    ReflectSerdeError::UnexpectedData {
+        type_name: type_name.to_string(),
+        expected,
+    }
+}
+
+impl ReflectSerde {
+    /// Creates a registry with the primitive types, strings, paths, uuids, durations and math types
+    /// registered as leaves, and with factories for their lists and options.
+    pub fn new() -> Self {
+        let mut serde = Self {
+            leaves: Default::default(),
+            enums: Default::default(),
+            list_items: Default::default(),
+            map_entries: Default::default(),
+        };
+
+        register_leaves!(
+            serde,
+            bool,
+            char,
+            u8,
+            i8,
+            u16,
+            i16,
+            u32,
+            i32,
+            u64,
+            i64,
+            usize,
+            isize,
+            f32,
+            f64,
+            String,
+            ImmutableString,
+            PathBuf,
+            Duration
+        );
+
+        #[cfg(feature = "serde")]
+        {
+            use crate::algebra::{
+                Matrix2, Matrix3, Matrix4, UnitQuaternion, Vector2, Vector3, Vector4,
+            };
+            use uuid::Uuid;
+
+            register_leaves!(
+                serde,
+                Uuid,
+                Vector2<f32>,
+                Vector3<f32>,
+                Vector4<f32>,
+                Vector2<f64>,
+                Vector3<f64>,
+                Vector4<f64>,
+                Vector2<i32>,
+                Vector3<i32>,
+                Vector2<u32>,
+                Vector3<u32>,
+                Matrix2<f32>,
+                Matrix3<f32>,
+                Matrix4<f32>
+            );
+            serde.register_leaf::<UnitQuaternion<f32>>();
+        }
+
+        serde
+    }
+
+    /// Registers a type that is written using its own serde implementation instead of reflection.
+    pub fn register_leaf<T>(&mut self) -> &mut Self
+    where
+        T: Reflect + Serialize + DeserializeOwned,
+    {
+        self.leaves.insert(
+            TypeId::of::<T>(),
+            Leaf {
+                to_content: leaf_to_content::<T>,
+                apply: apply_leaf::<T>,
+            },
+        );
+        self
+    }
+
+    /// Allows to grow `Vec<T>` when reading, new items are created using `T::default()`.
+    pub fn register_list<T>(&mut self) -> &mut Self
+    where
+        T: Reflect + Default,
+    {
+        self.list_items
+            .insert(TypeId::of::<Vec<T>>(), make_default::<T>);
+        self
+    }
+
+    /// Allows to add entries to the given hash map type when reading, new keys and values are
+    /// created using `Default`.
+    pub fn register_hash_map<M, K, V>(&mut self) -> &mut Self
+    where
+        M: ReflectHashMap,
+        K: Reflect + Default,
+        V: Reflect + Default,
+    {
+        self.map_entries
+            .insert(TypeId::of::<M>(), (make_default::<K>, make_default::<V>));
+        self
+    }
+
+    /// Registers an enum, which allows to write its unit variants by name and to switch between
+    /// the variants when reading. Enums usually get the required traits from `strum`
+    /// (`AsRefStr` and `EnumString`), variants with fields are created with default fields.
+    pub fn register_enum<T>(&mut self) -> &mut Self
+    where
+        T: Reflect + AsRef<str> + FromStr,
+    {
+        fn variant_name<T: AsRef<str> + 'static>(any: &dyn Any) -> String {
+            any.downcast_ref::<T>().unwrap().as_ref().to_string()
+        }
+
+        fn make_variant<T: Reflect + FromStr>(name: &str) -> Option<Box<dyn Reflect>> {
+            T::from_str(name)
+                .ok()
+                .map(|value| Box::new(value) as Box<dyn Reflect>)
+        }
+
+        self.enums.insert(
+            TypeId::of::<T>(),
+            EnumVariants {
+                variant_name: variant_name::<T>,
+                make_variant: make_variant::<T>,
+            },
+        );
+        self
+    }
+
+    /// Allows to switch `Option<T>` between `None` and `Some` when reading, `Some` is created
+    /// with `T::default()`.
+    pub fn register_option<T>(&mut self) -> &mut Self
+    where
+        T: Reflect + Default,
+    {
+        fn variant_name<T: 'static>(any: &dyn Any) -> String {
+            if any.downcast_ref::<Option<T>>().unwrap().is_some() {
+                "Some".to_string()
+            } else {
+                "None".to_string()
+            }
+        }
+
+        fn make_variant<T: Reflect + Default>(name: &str) -> Option<Box<dyn Reflect>> {
+            match name {
+                "Some" => Some(Box::new(Some(T::default()))),
+                "None" => Some(Box::new(None::<T>)),
+                _ => None,
+            }
+        }
+
+        self.enums.insert(
+            TypeId::of::<Option<T>>(),
+            EnumVariants {
+                variant_name: variant_name::<T>,
+                make_variant: make_variant::<T>,
+            },
+        );
+        self
+    }
+
+    fn to_content(&self, value: &dyn Reflect) -> Result<Content, ReflectSerdeError> {
+        let mut result = None;
+
+        // Inheritable variables forward `as_any` to their inner values, so they must be checked
+        // before the leaves.
+        value.as_inheritable_variable(&mut |variable| {
+            if let Some(variable) = variable {
+                result = Some(self.to_content(variable.inner_value_ref()));
+            }
+        });
+        if let Some(result) = result {
+            return result;
+        }
+
+        let type_id = concrete_type_id(value);
+
+        if let Some(leaf) = self.leaves.get(&type_id) {
+            let mut result = Ok(Content::Unit);
+            value.as_any(&mut |any| result = (leaf.to_content)(any));
+            return result.map_err(|err| ReflectSerdeError::Serde {
+                type_name: value.type_name().to_string(),
+                message: err.to_string(),
+            });
+        }
+
+        value.as_array(&mut |array| {
+            if let Some(array) = array {
+                result = Some(
+                    (0..array.reflect_len())
+                        .filter_map(|i| array.reflect_index(i))
+                        .map(|item| self.to_content(item))
+                        .collect::<Result<Vec<_>, _>>()
+                        .map(Content::Seq),
+                );
+            }
+        });
+        if let Some(result) = result {
+            return result;
+        }
+
+        value.as_hash_map(&mut |hash_map| {
+            if let Some(hash_map) = hash_map {
+                result = Some(
+                    (0..hash_map.reflect_len())
+                        .filter_map(|i| hash_map.reflect_get_at(i))
+                        .map(|(key, value)| {
+                            Ok(Content::Seq(vec![
+                                self.to_content(key)?,
+                                self.to_content(value)?,
+                            ]))
+                        })
+                        .collect::<Result<Vec<_>, _>>()
+                        .map(Content::Seq),
+                );
+            }
+        });
+        if let Some(result) = result {
+            return result;
+        }
+
+        let mut variant = None;
+        let mut fields = Vec::new();
+        let mut error = None;
+        value.fields_info(&mut |infos| {
+            for info in infos {
+                let name = match info.name.split_once('@') {
+                    Some((variant_name, field_name)) => {
+                        variant = Some(variant_name.to_string());
+                        field_name
+                    }
+                    None => info.name,
+                };
+                match self.to_content(info.reflect_value) {
+                    Ok(field) => fields.push((Content::String(name.to_string()), field)),
+                    Err(err) => {
+                        error = Some(err);
+                        return;
+                    }
+                }
+            }
+        });
+        if let Some(error) = error {
+            return Err(error);
+        }
+
+        if let Some(enum_variants) = self.enums.get(&type_id) {
+            let mut name = String::new();
+            value.as_any(&mut |any| name = (enum_variants.variant_name)(any));
+            variant = Some(name);
+        }
+
+        Ok(match variant {
+            Some(variant) if fields.is_empty() => Content::String(variant),
+            Some(variant) => Content::Map(vec![(Content::String(variant), Content::Map(fields))]),
+            None => Content::Map(fields),
+        })
+    }
+
+    fn apply_content(
+        &self,
+        target: &mut dyn Reflect,
+        value: &Content,
+    ) -> Result<(), ReflectSerdeError> {
+        let mut result = None;
+
+        target.as_inheritable_variable_mut(&mut |variable| {
+            if let Some(variable) = variable {
+                // Only the values that were actually changed are marked as modified, otherwise
+                // loaded prefab instances would stop inheriting properties from their prefabs.
+                let before = self.to_content(variable.inner_value_ref());
+                result = Some(
+                    self.apply_content(variable.inner_value_mut(), value)
+                        .map(|_| {
+                            let after = self.to_content(variable.inner_value_ref());
+                            if !matches!((before, after), (Ok(before), Ok(after)) if before == after)
+                            {
+                                variable.mark_modified();
+                            }
+                        }),
+                );
+            }
+        });
+        if let Some(result) = result {
+            return result;
+        }
+
+        let type_id = concrete_type_id(target);
+
+        if let Some(leaf) = self.leaves.get(&type_id) {
+            let mut result = Ok(());
+            target.as_any_mut(&mut |any| result = (leaf.apply)(any, value));
+            return result.map_err(|err| ReflectSerdeError::Serde {
+                type_name: target.type_name().to_string(),
+                message: err.to_string(),
+            });
+        }
+
+        target.as_list_mut(&mut |list| {
+            if let Some(list) = list {
+                result = Some(self.apply_list(list, type_id, value));
+            }
+        });
+        if let Some(result) = result {
+            return result;
+        }
+
+        target.as_array_mut(&mut |array| {
+            if let Some(array) = array {
+                result = Some(self.apply_array(array, value));
+            }
+        });
+        if let Some(result) = result {
+            return result;
+        }
+
+        target.as_hash_map_mut(&mut |hash_map| {
+            if let Some(hash_map) = hash_map {
+                result = Some(self.apply_hash_map(hash_map, type_id, value));
+            }
+        });
+        if let Some(result) = result {
+            return result;
+        }
+
+        self.apply_fields(target, type_id, value)
+    }
+
+    fn apply_array(
+        &self,
+        array: &mut dyn ReflectArray,
+        value: &Content,
+    ) -> Result<(), ReflectSerdeError> {
+        let items = value
+            .as_seq()
+            .ok_or_else(|| unexpected(array.type_name(), "a sequence"))?;
+        if items.len() != array.reflect_len() {
+            return Err(ReflectSerdeError::LengthMismatch {
+                type_name: array.type_name().to_string(),
+                expected: array.reflect_len(),
+                actual: items.len(),
+            });
+        }
+        for (i, item) in items.iter().enumerate() {
+            if let Some(target) = array.reflect_index_mut(i) {
+                self.apply_content(target, item)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_list(
+        &self,
+        list: &mut dyn ReflectList,
+        type_id: TypeId,
+        value: &Content,
+    ) -> Result<(), ReflectSerdeError> {
+        let items = value
+            .as_seq()
+            .ok_or_else(|| unexpected(list.type_name(), "a sequence"))?;
+        while list.reflect_len() > items.len() {
+            list.reflect_pop();
+        }
+        if list.reflect_len() < items.len() {
+            let factory =
+                self.list_items
+                    .get(&type_id)
+                    .ok_or_else(|| ReflectSerdeError::NoFactory {
+                        type_name: list.type_name().to_string(),
+                    })?;
+            while list.reflect_len() < items.len() {
+                if list.reflect_push(factory()).is_err() {
+                    return Err(ReflectSerdeError::NoFactory {
+                        type_name: list.type_name().to_string(),
+                    });
+                }
+            }
+        }
+        for (i, item) in items.iter().enumerate() {
+            if let Some(target) = list.reflect_index_mut(i) {
+                self.apply_content(target, item)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_hash_map(
+        &self,
+        hash_map: &mut dyn ReflectHashMap,
+        type_id: TypeId,
+        value: &Content,
+    ) -> Result<(), ReflectSerdeError> {
+        let type_name = hash_map.type_name().to_string();
+        let entries = value
+            .as_seq()
+            .ok_or_else(|| unexpected(&type_name, "a sequence of pairs"))?;
+        let (make_key, make_value) =
+            self.map_entries
+                .get(&type_id)
+                .ok_or_else(|| ReflectSerdeError::NoFactory {
+                    type_name: type_name.clone(),
+                })?;
+
+        // Keys can't be cloned using reflection, so the old keys are recreated from their data
+        // to remove the entries.
+        let old_keys = (0..hash_map.reflect_len())
+            .filter_map(|i| hash_map.reflect_get_at(i))
+            .map(|(key, _)| self.to_content(key))
+            .collect::<Result<Vec<_>, _>>()?;
+        for old_key in old_keys {
+            let mut key = make_key();
+            self.apply_content(&mut *key, &old_key)?;
+            hash_map.reflect_remove(&*key, &mut |_| {});
+        }
+
+        for entry in entries {
+            let Some([key_data, value_data]) = entry.as_seq() else {
+                return Err(ReflectSerdeError::UnexpectedData {
+                    type_name,
+                    expected: "a [key, value] pair",
+                });
+            };
+            let mut key = make_key();
+            self.apply_content(&mut *key, key_data)?;
+            let mut value = make_value();
+            self.apply_content(&mut *value, value_data)?;
+            hash_map.reflect_insert(key, value);
+        }
+        Ok(())
+    }
+
+    fn apply_fields(
+        &self,
+        target: &mut dyn Reflect,
+        type_id: TypeId,
+        value: &Content,
+    ) -> Result<(), ReflectSerdeError> {
+        let mut current_variant = None;
+        target.fields_info(&mut |infos| {
+            current_variant = infos
+                .first()
+                .and_then(|info| info.name.split_once('@'))
+                .map(|(variant, _)| variant.to_string());
+        });
+        if let Some(enum_variants) = self.enums.get(&type_id) {
+            let mut name = String::new();
+            target.as_any(&mut |any| name = (enum_variants.variant_name)(any));
+            current_variant = Some(name);
+        }
+
+        let (fields, variant) = match (value, current_variant.is_some()) {
+            (Content::String(variant), true) => (None, Some(variant.as_str())),
+            (Content::Map(map), true) if map.len() == 1 => {
+                let (variant, fields) = &map[0];
+                let (Some(variant), Some(fields)) = (variant.as_str(), fields.as_map()) else {
+                    return Err(unexpected(target.type_name(), "a map of variant fields"));
+                };
+                (Some(fields), Some(variant))
+            }
+            (Content::Map(map), false) => (Some(map.as_slice()), None),
+            _ => {
+                return Err(unexpected(
+                    target.type_name(),
+                    "a map of fields or a variant",
+                ))
+            }
+        };
+
+        if let Some(variant) = variant {
+            if current_variant.as_deref() != Some(variant) {
+                let new_value = self
+                    .enums
+                    .get(&type_id)
+                    .and_then(|enum_variants| (enum_variants.make_variant)(variant))
+                    .ok_or_else(|| ReflectSerdeError::UnknownVariant {
+                        type_name: target.type_name().to_string(),
+                        variant: variant.to_string(),
+                    })?;
+                if target.set(new_value).is_err() {
+                    return Err(ReflectSerdeError::UnknownVariant {
+                        type_name: target.type_name().to_string(),
+                        variant: variant.to_string(),
+                    });
+                }
+            }
+        }
+
+        let Some(fields) = fields else {
+            return Ok(());
+        };
+
+        let mut result = Ok(());
+        for (name, field_value) in fields {
+            let name = name
+                .as_str()
+                .ok_or_else(|| unexpected(target.type_name(), "field names"))?;
+            let field_name = match variant {
+                Some(variant) => format!("{variant}@{name}"),
+                None => name.to_string(),
+            };
+            target.field_mut(&field_name, &mut |field| {
+                if let Some(field) = field {
+                    result = self.apply_content(field, field_value);
+                }
+            });
+            if result.is_err() {
+                break;
+            }
+        }
+        result
+    }
+}
+
+/// Serializes a reflected value with any serde serializer.
+///
+/// ```
+/// # use fyrox_core::reflect::serde_bridge::{ReflectSerde, ReflectSerializer};
+/// let serde = ReflectSerde::new();
+/// let text = ron::to_string(&ReflectSerializer::new(&vec![1u32, 2], &serde)).unwrap();
+/// assert_eq!(text, "[1,2]");
+/// ```
+pub struct ReflectSerializer<'a> {
+    value: &'a dyn Reflect,
+    serde: &'a ReflectSerde,
+}
+
+impl<'a> ReflectSerializer<'a> {
+    /// Creates a serializer of the value.
+    pub fn new(value: &'a dyn Reflect, serde: &'a ReflectSerde) -> Self {
+        Self { value, serde }
+    }
+}
+
+impl<'a> Serialize for ReflectSerializer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.serde
+            .to_content(self.value)
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+/// Deserializes data into an existing reflected value with any serde deserializer of a
+/// self-describing format.
+///
+/// ```
+/// # use fyrox_core::reflect::serde_bridge::{ReflectDeserializer, ReflectSerde};
+/// # use serde::de::DeserializeSeed;
+/// let serde = ReflectSerde::new();
+/// let mut value = Vec::<u32>::new();
+/// let mut deserializer = ron::Deserializer::from_str("[1,2]").unwrap();
+/// ReflectDeserializer::new(&mut value, &serde)
+///     .deserialize(&mut deserializer)
+///     .unwrap();
+/// assert_eq!(value, [1, 2]);
+/// ```
+pub struct ReflectDeserializer<'a> {
+    target: &'a mut dyn Reflect,
+    serde: &'a ReflectSerde,
+}
+
+impl<'a> ReflectDeserializer<'a> {
+    /// Creates a deserializer that writes the data into the given value.
+    pub fn new(target: &'a mut dyn Reflect, serde: &'a ReflectSerde) -> Self {
+        Self { target, serde }
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ReflectDeserializer<'a> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        let content = Content::deserialize(deserializer)?;
+        self.serde
+            .apply_content(self.target, &content)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        reflect::{
+            prelude::*,
+            serde_bridge::{ReflectDeserializer, ReflectSerde, ReflectSerializer},
+        },
+        variable::InheritableVariable,
+    };
+    use serde::de::DeserializeSeed;
+    use std::{collections::HashMap, str::FromStr};
+
+    #[derive(Reflect, Debug, Default, PartialEq, Clone)]
+    struct Item {
+        payload: u32,
+    }
+
+    #[derive(Reflect, Debug, PartialEq, Clone)]
+    enum Shape {
+        Point,
+        Circle { radius: f32 },
+        Rect(f32, f32),
+    }
+
+    impl AsRef<str> for Shape {
+        fn as_ref(&self) -> &str {
+            match self {
+                Shape::Point => "Point",
+                Shape::Circle { .. } => "Circle",
+                Shape::Rect(..) => "Rect",
+            }
+        }
+    }
+
+    impl FromStr for Shape {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "Point" => Ok(Shape::Point),
+                "Circle" => Ok(Shape::Circle { radius: 0.0 }),
+                "Rect" => Ok(Shape::Rect(0.0, 0.0)),
+                _ => Err(()),
+            }
+        }
+    }
+
+    #[derive(Reflect, Debug, PartialEq, Clone)]
+    struct Foo {
+        name: String,
+        speed: InheritableVariable<f32>,
+        health: InheritableVariable<f32>,
+        items: Vec<Item>,
+        map: HashMap<u32, Item>,
+        target: Option<u32>,
+        shape: Shape,
+        other_shape: Shape,
+        array: [u8; 2],
+    }
+
+    fn serde() -> ReflectSerde {
+        let mut serde = ReflectSerde::new();
+        serde
+            .register_list::<Item>()
+            .register_hash_map::<HashMap<u32, Item>, u32, Item>()
+            .register_enum::<Shape>();
+        serde
+    }
+
+    #[test]
+    fn test_reflect_serde_round_trip() {
+        let serde = serde();
+        let foo = Foo {
+            name: "Foo".to_string(),
+            speed: 5.0.into(),
+            health: 100.0.into(),
+            items: vec![Item { payload: 1 }, Item { payload: 2 }],
+            map: [(10, Item { payload: 3 })].into(),
+            target: Some(7),
+            shape: Shape::Circle { radius: 2.0 },
+            other_shape: Shape::Point,
+            array: [1, 2],
+        };
+
+        let text = ron::to_string(&ReflectSerializer::new(&foo, &serde)).unwrap();
+        assert_eq!(
+            text,
+            r#"{"name":"Foo","speed":5.0,"health":100.0,"items":[{"payload":1},{"payload":2}],"map":[[10,{"payload":3}]],"target":{"Some":{"0":7}},"shape":{"Circle":{"radius":2.0}},"other_shape":"Point","array":[1,2]}"#
+        );
+
+        let mut loaded = Foo {
+            name: Default::default(),
+            speed: InheritableVariable::new_non_modified(1.0),
+            health: InheritableVariable::new_non_modified(100.0),
+            items: vec![Item { payload: 9 }; 3],
+            map: [(11, Item { payload: 4 })].into(),
+            target: None,
+            shape: Shape::Rect(1.0, 1.0),
+            other_shape: Shape::Circle { radius: 1.0 },
+            array: [0, 0],
+        };
+        ReflectDeserializer::new(&mut loaded, &serde)
+            .deserialize(&mut ron::Deserializer::from_str(&text).unwrap())
+            .unwrap();
+        assert_eq!(loaded, foo);
+
+        // Unchanged inheritable variables must stay non-modified.
+        assert!(loaded.speed.is_modified());
+        assert!(!loaded.health.is_modified());
+    }
+
+    fn apply(serde: &ReflectSerde, target: &mut dyn Reflect, text: &str) -> Result<(), ron::Error> {
+        ReflectDeserializer::new(target, serde).deserialize(&mut ron::Deserializer::from_str(text)?)
+    }
+
+    #[test]
+    fn test_reflect_serde_non_finite_floats() {
+        let serde = ReflectSerde::new();
+        let values = vec![
+            f32::NAN,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            -0.0,
+            f32::MIN_POSITIVE,
+        ];
+
+        let text = ron::to_string(&ReflectSerializer::new(&values, &serde)).unwrap();
+        let mut loaded = Vec::<f32>::new();
+        apply(&serde, &mut loaded, &text).unwrap();
+        assert_eq!(
+            loaded.iter().map(|v| v.to_bits()).collect::<Vec<_>>(),
+            values.iter().map(|v| v.to_bits()).collect::<Vec<_>>()
+        );
+
+        // Unchanged NaN must not mark the variable as modified.
+        let mut variable = InheritableVariable::new_non_modified(f64::NAN);
+        let text = ron::to_string(&ReflectSerializer::new(&variable, &serde)).unwrap();
+        apply(&serde, &mut variable, &text).unwrap();
+        assert!(!variable.is_modified());
+    }
+
+    #[test]
+    fn test_reflect_serde_errors() {
+        let serde = ReflectSerde::new();
+
+        let mut items = Vec::<Item>::new();
+        assert!(apply(&serde, &mut items, r#"[{"payload": 1}]"#).is_err());
+
+        let mut array = [0u8; 2];
+        assert!(apply(&serde, &mut array, "[1, 2, 3]").is_err());
+
+        let mut shape = Shape::Circle { radius: 1.0 };
+        assert!(apply(&serde, &mut shape, r#"{"Rect": {"0": 1.0, "1": 2.0}}"#).is_err());
+        apply(&serde, &mut shape, r#"{"Circle": {"radius": 3.0}}"#).unwrap();
+        assert_eq!(shape, Shape::Circle { radius: 3.0 });
+
+        let mut number = 0u32;
+        assert!(apply(&serde, &mut number, r#""text""#).is_err());
+    }
+}
@@ -20,6 +20,7 @@
 
 #[cfg(not(target_arch = "wasm32"))]
 use crate::futures::executor::ThreadPool;
+use crate::profiler::{profile_future, Profiler};
 use parking_lot::Mutex;
 use std::{
     any::Any,
@@ -118,7 +119,11 @@ impl TaskPool {
     where
         F: Future<Output = ()> + 'static,
     {
-        crate::wasm_bindgen_futures::spawn_local(future);
+        if Profiler::is_enabled() {
+            crate::wasm_bindgen_futures::spawn_local(profile_future("Task", future));
+        } else {
+            crate::wasm_bindgen_futures::spawn_local(future);
+        }
     }
 
     #[inline]
@@ -127,7 +132,11 @@ impl TaskPool {
     where
         F: Future<Output = ()> + Send + 'static,
     {
-        self.thread_pool.spawn_ok(future);
+        if Profiler::is_enabled() {
+            self.thread_pool.spawn_ok(profile_future("Task", future));
+        } else {
+            self.thread_pool.spawn_ok(future);
+        }
     }
 
     #[inline]
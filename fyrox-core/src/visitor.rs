@@ -49,19 +49,21 @@ use base64::Engine;
 use bitflags::bitflags;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use fxhash::FxHashMap;
+use lazy_static::lazy_static;
 use std::any::TypeId;
 use std::error::Error;
 use std::{
     any::Any,
     cell::{Cell, RefCell},
     collections::{hash_map::Entry, HashMap, HashSet},
-    fmt::{Display, Formatter},
+    fmt::{Debug, Display, Formatter, Write as _},
     fs::File,
     hash::{BuildHasher, Hash},
     io::{BufWriter, Cursor, Read, Write},
     ops::{Deref, DerefMut, Range},
     path::{Path, PathBuf},
     rc::Rc,
+    str::{FromStr, SplitWhitespace},
     string::FromUtf8Error,
     sync::{Arc, Mutex, RwLock},
     time::Duration,
@@ -73,6 +75,7 @@ use uuid::Uuid;
 /// of these types.
 /// Fields can be accessed from a visitor using [Visit::visit] on a variable with the
 /// same type as the field.
+#[derive(Clone, PartialEq, Debug)]
 pub enum FieldKind {
     Bool(bool),
     U8(u8),
@@ -439,6 +442,278 @@ impl FieldKind {
     }
 }
 
+impl FieldKind {
+    /// Writes the type tag of the field followed by its value(s) separated by spaces, for example
+    /// `vec3f32 1.0 2.0 3.0`. Floating-point numbers are written in the shortest form that parses
+    /// back to exactly the same value.
+    fn write_text(&self, out: &mut String) {
+        // `Debug` is used instead of `Display`, because it prints floating-point numbers in
+        // exponential form when it is shorter.
+        fn write_values<T: Debug>(
+            out: &mut String,
+            tag: &str,
+            values: impl IntoIterator<Item = T>,
+        ) {
+            out.push_str(tag);
+            for value in values {
+                let _ = write!(out, " {value:?}");
+            }
+        }
+
+        match self {
+            Self::Bool(data) => write_values(out, "bool", [data]),
+            Self::U8(data) => write_values(out, "u8", [data]),
+            Self::I8(data) => write_values(out, "i8", [data]),
+            Self::U16(data) => write_values(out, "u16", [data]),
+            Self::I16(data) => write_values(out, "i16", [data]),
+            Self::U32(data) => write_values(out, "u32", [data]),
+            Self::I32(data) => write_values(out, "i32", [data]),
+            Self::U64(data) => write_values(out, "u64", [data]),
+            Self::I64(data) => write_values(out, "i64", [data]),
+            Self::F32(data) => write_values(out, "f32", [data]),
+            Self::F64(data) => write_values(out, "f64", [data]),
+            Self::UnitQuaternion(data) => {
+                write_values(out, "quat", [data.i, data.j, data.k, data.w])
+            }
+            Self::Matrix4(data) => write_values(out, "mat4", data.iter()),
+            Self::BinaryBlob(data) => match std::str::from_utf8(data) {
+                // Most of the blobs are strings, keep them readable.
+                Ok(string) => {
+                    out.push_str("str ");
+                    write_quoted_text(out, string);
+                }
+                Err(_) => {
+                    out.push_str("bytes ");
+                    out.push_str(&base64::engine::general_purpose::STANDARD.encode(data));
+                }
+            },
+            Self::Matrix3(data) => write_values(out, "mat3", data.iter()),
+            Self::Uuid(uuid) => write_values(out, "uuid", [uuid]),
+            Self::UnitComplex(data) => write_values(out, "complex", [data.re, data.im]),
+            Self::PodArray {
+                type_id,
+                element_size,
+                bytes,
+            } => {
+                write_values(out, "podarray", [*type_id as u32, *element_size]);
+                out.push(' ');
+                out.push_str(&base64::engine::general_purpose::STANDARD.encode(bytes));
+            }
+            Self::Matrix2(data) => write_values(out, "mat2", data.iter()),
+
+            Self::Vector2F32(data) => write_values(out, "vec2f32", data.iter()),
+            Self::Vector3F32(data) => write_values(out, "vec3f32", data.iter()),
+            Self::Vector4F32(data) => write_values(out, "vec4f32", data.iter()),
+
+            Self::Vector2F64(data) => write_values(out, "vec2f64", data.iter()),
+            Self::Vector3F64(data) => write_values(out, "vec3f64", data.iter()),
+            Self::Vector4F64(data) => write_values(out, "vec4f64", data.iter()),
+
+            Self::Vector2U8(data) => write_values(out, "vec2u8", data.iter()),
+            Self::Vector3U8(data) => write_values(out, "vec3u8", data.iter()),
+            Self::Vector4U8(data) => write_values(out, "vec4u8", data.iter()),
+
+            Self::Vector2I8(data) => write_values(out, "vec2i8", data.iter()),
+            Self::Vector3I8(data) => write_values(out, "vec3i8", data.iter()),
+            Self::Vector4I8(data) => write_values(out, "vec4i8", data.iter()),
+
+            Self::Vector2U16(data) => write_values(out, "vec2u16", data.iter()),
+            Self::Vector3U16(data) => write_values(out, "vec3u16", data.iter()),
+            Self::Vector4U16(data) => write_values(out, "vec4u16", data.iter()),
+
+            Self::Vector2I16(data) => write_values(out, "vec2i16", data.iter()),
+            Self::Vector3I16(data) => write_values(out, "vec3i16", data.iter()),
+            Self::Vector4I16(data) => write_values(out, "vec4i16", data.iter()),
+
+            Self::Vector2U32(data) => write_values(out, "vec2u32", data.iter()),
+            Self::Vector3U32(data) => write_values(out, "vec3u32", data.iter()),
+            Self::Vector4U32(data) => write_values(out, "vec4u32", data.iter()),
+
+            Self::Vector2I32(data) => write_values(out, "vec2i32", data.iter()),
+            Self::Vector3I32(data) => write_values(out, "vec3i32", data.iter()),
+            Self::Vector4I32(data) => write_values(out, "vec4i32", data.iter()),
+
+            Self::Vector2U64(data) => write_values(out, "vec2u64", data.iter()),
+            Self::Vector3U64(data) => write_values(out, "vec3u64", data.iter()),
+            Self::Vector4U64(data) => write_values(out, "vec4u64", data.iter()),
+
+            Self::Vector2I64(data) => write_values(out, "vec2i64", data.iter()),
+            Self::Vector3I64(data) => write_values(out, "vec3i64", data.iter()),
+            Self::Vector4I64(data) => write_values(out, "vec4i64", data.iter()),
+        }
+    }
+
+    /// Parses a field value written by [`Self::write_text`]. `tag` is the type tag and `value` is
+    /// the rest of the line after it.
+    fn read_text(tag: &str, value: &str) -> Result<Self, String> {
+        fn read_values<T: FromStr>(
+            tokens: &mut SplitWhitespace,
+            count: usize,
+        ) -> Result<Vec<T>, String> {
+            (0..count)
+                .map(|_| {
+                    let token = tokens.next().ok_or("not enough values")?;
+                    token
+                        .parse()
+                        .map_err(|_| format!("invalid value `{token}`"))
+                })
+                .collect()
+        }
+
+        fn read_value<T: FromStr>(tokens: &mut SplitWhitespace) -> Result<T, String> {
+            Ok(read_values(tokens, 1)?.remove(0))
+        }
+
+        fn decode_base64(token: &str) -> Result<Vec<u8>, String> {
+            base64::engine::general_purpose::STANDARD
+                .decode(token)
+                .map_err(|_| format!("invalid base64 data `{token}`"))
+        }
+
+        if tag == "str" {
+            let (string, rest) = read_quoted_text(value)?;
+            return if rest.trim().is_empty() {
+                Ok(Self::BinaryBlob(string.into_bytes()))
+            } else {
+                Err("unexpected data after string".to_string())
+            };
+        }
+
+        let tokens = &mut value.split_whitespace();
+        let kind = match tag {
+            "bool" => Self::Bool(read_value(tokens)?),
+            "u8" => Self::U8(read_value(tokens)?),
+            "i8" => Self::I8(read_value(tokens)?),
+            "u16" => Self::U16(read_value(tokens)?),
+            "i16" => Self::I16(read_value(tokens)?),
+            "u32" => Self::U32(read_value(tokens)?),
+            "i32" => Self::I32(read_value(tokens)?),
+            "u64" => Self::U64(read_value(tokens)?),
+            "i64" => Self::I64(read_value(tokens)?),
+            "f32" => Self::F32(read_value(tokens)?),
+            "f64" => Self::F64(read_value(tokens)?),
+            "quat" => {
+                let v = read_values(tokens, 4)?;
+                Self::UnitQuaternion(UnitQuaternion::new_normalize(Quaternion::new(
+                    v[3], v[0], v[1], v[2],
+                )))
+            }
+            "mat4" => Self::Matrix4(Matrix4::from_column_slice(&read_values(tokens, 16)?)),
+            "bytes" => Self::BinaryBlob(decode_base64(tokens.next().unwrap_or_default())?),
+            "mat3" => Self::Matrix3(Matrix3::from_column_slice(&read_values(tokens, 9)?)),
+            "uuid" => Self::Uuid(read_value(tokens)?),
+            "complex" => {
+                let v = read_values(tokens, 2)?;
+                Self::UnitComplex(UnitComplex::from_complex(Complex::new(v[0], v[1])))
+            }
+            "podarray" => Self::PodArray {
+                type_id: read_value(tokens)?,
+                element_size: read_value(tokens)?,
+                bytes: decode_base64(tokens.next().unwrap_or_default())?,
+            },
+            "mat2" => Self::Matrix2(Matrix2::from_column_slice(&read_values(tokens, 4)?)),
+
+            "vec2f32" => Self::Vector2F32(Vector2::from_column_slice(&read_values(tokens, 2)?)),
+            "vec3f32" => Self::Vector3F32(Vector3::from_column_slice(&read_values(tokens, 3)?)),
+            "vec4f32" => Self::Vector4F32(Vector4::from_column_slice(&read_values(tokens, 4)?)),
+
+            "vec2f64" => Self::Vector2F64(Vector2::from_column_slice(&read_values(tokens, 2)?)),
+            "vec3f64" => Self::Vector3F64(Vector3::from_column_slice(&read_values(tokens, 3)?)),
+            "vec4f64" => Self::Vector4F64(Vector4::from_column_slice(&read_values(tokens, 4)?)),
+
+            "vec2u8" => Self::Vector2U8(Vector2::from_column_slice(&read_values(tokens, 2)?)),
+            "vec3u8" => Self::Vector3U8(Vector3::from_column_slice(&read_values(tokens, 3)?)),
+            "vec4u8" => Self::Vector4U8(Vector4::from_column_slice(&read_values(tokens, 4)?)),
+
+            "vec2i8" => Self::Vector2I8(Vector2::from_column_slice(&read_values(tokens, 2)?)),
+            "vec3i8" => Self::Vector3I8(Vector3::from_column_slice(&read_values(tokens, 3)?)),
+            "vec4i8" => Self::Vector4I8(Vector4::from_column_slice(&read_values(tokens, 4)?)),
+
+            "vec2u16" => Self::Vector2U16(Vector2::from_column_slice(&read_values(tokens, 2)?)),
+            "vec3u16" => Self::Vector3U16(Vector3::from_column_slice(&read_values(tokens, 3)?)),
+            "vec4u16" => Self::Vector4U16(Vector4::from_column_slice(&read_values(tokens, 4)?)),
+
+            "vec2i16" => Self::Vector2I16(Vector2::from_column_slice(&read_values(tokens, 2)?)),
+            "vec3i16" => Self::Vector3I16(Vector3::from_column_slice(&read_values(tokens, 3)?)),
+            "vec4i16" => Self::Vector4I16(Vector4::from_column_slice(&read_values(tokens, 4)?)),
+
+            "vec2u32" => Self::Vector2U32(Vector2::from_column_slice(&read_values(tokens, 2)?)),
+            "vec3u32" => Self::Vector3U32(Vector3::from_column_slice(&read_values(tokens, 3)?)),
+            "vec4u32" => Self::Vector4U32(Vector4::from_column_slice(&read_values(tokens, 4)?)),
+
+            "vec2i32" => Self::Vector2I32(Vector2::from_column_slice(&read_values(tokens, 2)?)),
+            "vec3i32" => Self::Vector3I32(Vector3::from_column_slice(&read_values(tokens, 3)?)),
+            "vec4i32" => Self::Vector4I32(Vector4::from_column_slice(&read_values(tokens, 4)?)),
+
+            "vec2u64" => Self::Vector2U64(Vector2::from_column_slice(&read_values(tokens, 2)?)),
+            "vec3u64" => Self::Vector3U64(Vector3::from_column_slice(&read_values(tokens, 3)?)),
+            "vec4u64" => Self::Vector4U64(Vector4::from_column_slice(&read_values(tokens, 4)?)),
+
+            "vec2i64" => Self::Vector2I64(Vector2::from_column_slice(&read_values(tokens, 2)?)),
+            "vec3i64" => Self::Vector3I64(Vector3::from_column_slice(&read_values(tokens, 3)?)),
+            "vec4i64" => Self::Vector4I64(Vector4::from_column_slice(&read_values(tokens, 4)?)),
+
+            _ => return Err(format!("unknown field type `{tag}`")),
+        };
+
+        if tokens.next().is_some() {
+            Err("too many values".to_string())
+        } else {
+            Ok(kind)
+        }
+    }
+}
+
+/// Writes the given string in double quotes. Quotes, backslashes and every character that is not
+/// printable ASCII are escaped, so the output is always plain ASCII that fits on one line.
+fn write_quoted_text(out: &mut String, string: &str) {
+    out.push('"');
+    out.extend(string.escape_default());
+    out.push('"');
+}
+
+/// Reads a string written by [`write_quoted_text`] from the beginning of the given text and returns
+/// it together with the rest of the text.
+fn read_quoted_text(text: &str) -> Result<(String, &str), String> {
+    let mut chars = text.char_indices();
+    if !matches!(chars.next(), Some((_, '"'))) {
+        return Err("expected a quoted string".to_string());
+    }
+    let mut string = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((string, &text[i + 1..])),
+            '\\' => match chars.next().map(|(_, c)| c) {
+                Some('t') => string.push('\t'),
+                Some('r') => string.push('\r'),
+                Some('n') => string.push('\n'),
+                Some('0') => string.push('\0'),
+                Some(c @ ('\\' | '\'' | '"')) => string.push(c),
+                Some('u') => {
+                    let rest = &text[i + 2..];
+                    let code = rest
+                        .strip_prefix('{')
+                        .and_then(|rest| rest.split_once('}'))
+                        .map(|(code, _)| code)
+                        .ok_or("invalid unicode escape")?;
+                    let c = u32::from_str_radix(code, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| format!("invalid unicode escape `{code}`"))?;
+                    string.push(c);
+                    // Skip `{code}`.
+                    for _ in 0..code.len() + 2 {
+                        chars.next();
+                    }
+                }
+                _ => return Err("invalid escape sequence".to_string()),
+            },
+            c => string.push(c),
+        }
+    }
+    Err("unterminated string".to_string())
+}
+
 macro_rules! impl_field_data {
     ($type_name:ty, $($kind:tt)*) => {
         impl Visit for $type_name {
@@ -643,6 +918,13 @@ pub enum VisitError {
     PoisonedMutex,
     /// A FileLoadError was encountered while trying to decode Visitor data from a file.
     FileLoadError(FileLoadError),
+    /// The text produced by [Visitor::save_text] is malformed.
+    InvalidText {
+        /// One-based number of the line where the error was found.
+        line: usize,
+        /// Description of the error.
+        message: String,
+    },
 }
 
 impl Error for VisitError {}
@@ -667,6 +949,9 @@ impl Display for VisitError {
             Self::UnexpectedRcNullIndex => write!(f, "unexpected rc null index"),
             Self::PoisonedMutex => write!(f, "attempt to lock poisoned mutex"),
             Self::FileLoadError(e) => write!(f, "file load error: {e:?}"),
+            Self::InvalidText { line, message } => {
+                write!(f, "invalid text at line {line}: {message}")
+            }
         }
     }
 }
@@ -756,6 +1041,26 @@ impl Field {
         }
     }
 
+    /// The key string that allows access to the field.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The data stored in the visitor for this field.
+    pub fn kind(&self) -> &FieldKind {
+        &self.kind
+    }
+
+    /// Sets a new name of the field. It is up to the caller to keep the names within a node unique.
+    pub fn set_name(&mut self, name: &str) {
+        name.clone_into(&mut self.name);
+    }
+
+    /// Mutable access to the data of the field, mostly useful for [migrations](VisitMigrations).
+    pub fn kind_mut(&mut self) -> &mut FieldKind {
+        &mut self.kind
+    }
+
     fn save(field: &Field, file: &mut dyn Write) -> VisitResult {
         fn write_vec_n<T, const N: usize>(
             file: &mut dyn Write,
@@ -1017,12 +1322,13 @@ impl Field {
                     let w = file.read_f32::<LittleEndian>()?;
                     UnitQuaternion::new_normalize(Quaternion::new(w, x, y, z))
                 }),
+                // Matrices are written in column-major order, see `Field::save`.
                 13 => FieldKind::Matrix4({
                     let mut f = [0.0f32; 16];
                     for n in &mut f {
                         *n = file.read_f32::<LittleEndian>()?;
                     }
-                    Matrix4::from_row_slice(&f)
+                    Matrix4::from_column_slice(&f)
                 }),
                 14 => FieldKind::BinaryBlob({
                     let len = file.read_u32::<LittleEndian>()? as usize;
@@ -1036,7 +1342,7 @@ impl Field {
                     for n in &mut f {
                         *n = file.read_f32::<LittleEndian>()?;
                     }
-                    Matrix3::from_row_slice(&f)
+                    Matrix3::from_column_slice(&f)
                 }),
                 17 => FieldKind::Vector2F32({
                     let x = file.read_f32::<LittleEndian>()?;
@@ -1073,11 +1379,11 @@ impl Field {
                     }
                 }
                 22 => FieldKind::Matrix2({
-                    let mut f = [0.0f32; 3];
+                    let mut f = [0.0f32; 4];
                     for n in &mut f {
                         *n = file.read_f32::<LittleEndian>()?;
                     }
-                    Matrix2::from_row_slice(&f)
+                    Matrix2::from_column_slice(&f)
                 }),
                 23 => FieldKind::Vector2F64(read_vec_n(file)?),
                 24 => FieldKind::Vector3F64(read_vec_n(file)?),
@@ -1144,6 +1450,65 @@ impl VisitorNode {
             children: Vec::new(),
         }
     }
+
+    /// The name of the node (region).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The fields of the node in the order they were written.
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// Handles of the child nodes, use [Visitor::node] to access them.
+    pub fn children(&self) -> &[Handle<VisitorNode>] {
+        &self.children
+    }
+
+    /// Sets a new name of the node. It is up to the caller to keep the names of the siblings unique.
+    pub fn set_name(&mut self, name: &str) {
+        name.clone_into(&mut self.name);
+    }
+
+    /// Finds a field with the given name.
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Finds a field with the given name and gives mutable access to it.
+    pub fn field_mut(&mut self, name: &str) -> Option<&mut Field> {
+        self.fields.iter_mut().find(|field| field.name == name)
+    }
+
+    /// Adds a new field to the node. Returns an error if a field with the same name already exists.
+    pub fn add_field(&mut self, field: Field) -> VisitResult {
+        if self.field(&field.name).is_some() {
+            Err(VisitError::FieldAlreadyExists(field.name))
+        } else {
+            self.fields.push(field);
+            Ok(())
+        }
+    }
+
+    /// Removes a field with the given name from the node and returns it.
+    pub fn remove_field(&mut self, name: &str) -> Option<Field> {
+        let index = self.fields.iter().position(|field| field.name == name)?;
+        Some(self.fields.remove(index))
+    }
+
+    /// Renames a field. Returns an error if there is no field with the `old` name or if a field
+    /// with the `new` name already exists.
+    pub fn rename_field(&mut self, old: &str, new: &str) -> VisitResult {
+        if self.field(new).is_some() {
+            return Err(VisitError::FieldAlreadyExists(new.to_owned()));
+        }
+        let field = self
+            .field_mut(old)
+            .ok_or_else(|| VisitError::FieldDoesNotExist(old.to_owned()))?;
+        new.clone_into(&mut field.name);
+        Ok(())
+    }
 }
 
 impl Default for VisitorNode {
@@ -1217,6 +1582,124 @@ impl Blackboard {
     }
 }
 
+/// A function that upgrades the raw data of a type from one version to a newer one. It receives
+/// the visitor and the handle of the region of the type, and is free to add, remove, rename or move
+/// any fields and regions using [Visitor::node_mut] and friends.
+pub type VisitMigrationFn = dyn Fn(&mut Visitor, Handle<VisitorNode>) -> VisitResult + Send + Sync;
+
+struct VisitMigration {
+    to: u32,
+    func: Arc<VisitMigrationFn>,
+}
+
+/// A global registry of upgrade functions for types with `#[visit(version = N)]`.
+///
+/// Types that derive [Visit] with the `version` attribute store the version in their region. When
+/// a region with an older version is read, the migrations registered for the type are applied one
+/// after another, starting from the stored version, until the current version is reached. Every
+/// applied migration is recorded in [Visitor::applied_migrations]. A missing step is an error, so
+/// old data is never silently read with default values.
+///
+/// Migrations are registered by the version key of the type, which the derive exposes as the
+/// `VISIT_VERSION_KEY` associated constant. By default it is the full path of the type (for example
+/// `my_game::light::Light`), so types with the same name in different modules or crates never share
+/// migrations. The key can be set explicitly with `#[visit(version_key = "..")]`, which is useful
+/// to keep the migrations working when the type is moved to another module.
+///
+/// ```
+/// # use fyrox_core::visitor::{prelude::*, VisitMigrations};
+/// #[derive(Visit, Default)]
+/// #[visit(version = 1)]
+/// struct Light {
+///     // Was called `radius` in version 0.
+///     range: f32,
+/// }
+///
+/// VisitMigrations::register(Light::VISIT_VERSION_KEY, 0, 1, |visitor, region| {
+///     visitor.node_mut(region).unwrap().rename_field("Radius", "Range")
+/// })
+/// .unwrap();
+/// ```
+#[derive(Default)]
+pub struct VisitMigrations {
+    items: FxHashMap<(String, u32), VisitMigration>,
+}
+
+lazy_static! {
+    static ref MIGRATIONS: parking_lot::Mutex<VisitMigrations> = Default::default();
+}
+
+impl VisitMigrations {
+    /// Registers a function that upgrades the data of the type with the given version key from
+    /// version `from` to version `to`. Returns an error if there is already a migration for the
+    /// same key and `from` version, the registered migration is kept in this case.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `to` is not greater than `from`.
+    pub fn register<F>(version_key: &str, from: u32, to: u32, func: F) -> VisitResult
+    where
+        F: Fn(&mut Visitor, Handle<VisitorNode>) -> VisitResult + Send + Sync + 'static,
+    {
+        assert!(
+            to > from,
+            "migration of {version_key} must increase the version ({from} -> {to})"
+        );
+
+        match MIGRATIONS.lock().items.entry((version_key.to_owned(), from)) {
+            Entry::Occupied(_) => Err(VisitError::User(format!(
+                "there is already a migration of {version_key} from version {from}"
+            ))),
+            Entry::Vacant(entry) => {
+                entry.insert(VisitMigration {
+                    to,
+                    func: Arc::new(func),
+                });
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns `true` if there is a migration for the given version key from the given version.
+    pub fn is_registered(version_key: &str, from: u32) -> bool {
+        MIGRATIONS
+            .lock()
+            .items
+            .contains_key(&(version_key.to_owned(), from))
+    }
+
+    fn find(version_key: &str, from: u32) -> Option<(u32, Arc<VisitMigrationFn>)> {
+        MIGRATIONS
+            .lock()
+            .items
+            .get(&(version_key.to_owned(), from))
+            .map(|migration| (migration.to, migration.func.clone()))
+    }
+}
+
+/// A record about a migration that was applied while reading, see [VisitMigrations].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AppliedMigration {
+    /// Version key of the migrated type, see [VisitMigrations].
+    pub version_key: String,
+    /// Path of the migrated region, for example `__ROOT__/Scene/Graph`.
+    pub path: String,
+    /// Version of the data before the migration.
+    pub from: u32,
+    /// Version of the data after the migration.
+    pub to: u32,
+}
+
+impl Display for AppliedMigration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at {}: {} -> {}",
+            self.version_key, self.path, self.from, self.to
+        )
+    }
+}
+
 bitflags! {
     /// Flags that can be used to influence the behaviour of [Visit::visit] methods.
     pub struct VisitorFlags: u32 {
@@ -1255,6 +1738,7 @@ pub struct Visitor {
     /// Flags that can activate special behaviour in some Visit values, such as
     /// [crate::variable::InheritableVariable].
     pub flags: VisitorFlags,
+    applied_migrations: Vec<AppliedMigration>,
 }
 
 /// Trait of types that can be read from a [Visitor] or written to a Visitor.
@@ -1300,6 +1784,16 @@ pub trait Visit {
     fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult;
 }
 
+impl Debug for Visitor {
+    /// Prints a compact dump of the data, each node on its own line with tabs to indent child nodes.
+    /// Use [Visitor::save_text] to get the text that can be loaded back.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut out_string = String::new();
+        self.print_node(self.root, 0, &mut out_string);
+        f.write_str(&out_string)
+    }
+}
+
 impl Default for Visitor {
     fn default() -> Self {
         Self::new()
@@ -1316,6 +1810,14 @@ impl Visitor {
     /// of the given slice.
     pub const MAGIC: &'static str = "RG3D";
 
+    /// The first line of the text produced by [Visitor::save_text]. [Visitor::load_from_memory] and
+    /// [Visitor::load_binary] use it to tell the text format from the binary one.
+    pub const TEXT_MAGIC: &'static str = "RG3D-TEXT 1";
+
+    /// Name of the field that stores the version of types with `#[visit(version = N)]`,
+    /// see [Visitor::visit_version].
+    pub const VERSION_FIELD: &'static str = "__Version";
+
     /// Creates a Visitor containing only a single node called "`__ROOT__`" which will be the
     /// current region of the visitor.
     pub fn new() -> Self {
@@ -1330,6 +1832,7 @@ impl Visitor {
             root,
             blackboard: Blackboard::new(),
             flags: VisitorFlags::NONE,
+            applied_migrations: Vec::new(),
         }
     }
 
@@ -1400,6 +1903,196 @@ impl Visitor {
         }
     }
 
+    /// The handle of the root node of the visitor's data, it is always called "`__ROOT__`".
+    pub fn root(&self) -> Handle<VisitorNode> {
+        self.root
+    }
+
+    /// Gives read access to the raw data of the visitor, which is useful for tools that work with
+    /// the data without knowing the types that wrote it (diff, merge, etc.).
+    pub fn node(&self, handle: Handle<VisitorNode>) -> Option<&VisitorNode> {
+        self.nodes.try_borrow(handle)
+    }
+
+    /// Adds the given raw field to the current region. This is the counterpart of [Visitor::node]
+    /// for tools that build the visitor's data directly instead of using [Visit::visit].
+    /// Return an error if a field with the same name already exists in the current region.
+    pub fn write_field(&mut self, field: Field) -> VisitResult {
+        if self.find_field(&field.name).is_some() {
+            Err(VisitError::FieldAlreadyExists(field.name))
+        } else {
+            self.current_node().fields.push(field);
+            Ok(())
+        }
+    }
+
+    /// Gives write access to the raw data of the visitor, this is mainly intended for
+    /// [migrations](VisitMigrations).
+    pub fn node_mut(&mut self, handle: Handle<VisitorNode>) -> Option<&mut VisitorNode> {
+        self.nodes.try_borrow_mut(handle)
+    }
+
+    /// Finds a child region of the given node by its name.
+    pub fn find_child(&self, parent: Handle<VisitorNode>, name: &str) -> Handle<VisitorNode> {
+        self.nodes
+            .try_borrow(parent)
+            .and_then(|node| {
+                node.children
+                    .iter()
+                    .find(|child| self.nodes.borrow(**child).name == name)
+                    .cloned()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Adds a new empty region to the given node. Returns an error if the node already has a
+    /// region with the same name.
+    pub fn add_region(
+        &mut self,
+        parent: Handle<VisitorNode>,
+        name: &str,
+    ) -> Result<Handle<VisitorNode>, VisitError> {
+        if !self.nodes.is_valid_handle(parent) {
+            return Err(VisitError::InvalidCurrentNode);
+        }
+        if self.find_child(parent, name).is_some() {
+            return Err(VisitError::RegionAlreadyExists(name.to_owned()));
+        }
+        let handle = self.nodes.spawn(VisitorNode::new(name, parent));
+        self.nodes.borrow_mut(parent).children.push(handle);
+        Ok(handle)
+    }
+
+    /// Moves the region to another parent node, keeping all its content. Returns an error if the
+    /// new parent already has a region with the same name, or if the region is the root or
+    /// contains the new parent.
+    pub fn move_region(
+        &mut self,
+        handle: Handle<VisitorNode>,
+        new_parent: Handle<VisitorNode>,
+    ) -> VisitResult {
+        if !self.nodes.is_valid_handle(handle) || !self.nodes.is_valid_handle(new_parent) {
+            return Err(VisitError::InvalidCurrentNode);
+        }
+        let mut ancestor = new_parent;
+        while ancestor.is_some() {
+            if ancestor == handle {
+                return Err(VisitError::InvalidCurrentNode);
+            }
+            ancestor = self.nodes.borrow(ancestor).parent;
+        }
+        let name = self.nodes.borrow(handle).name.clone();
+        if self.find_child(new_parent, &name).is_some() {
+            return Err(VisitError::RegionAlreadyExists(name));
+        }
+        self.detach_region(handle);
+        self.nodes.borrow_mut(handle).parent = new_parent;
+        self.nodes.borrow_mut(new_parent).children.push(handle);
+        Ok(())
+    }
+
+    /// Removes the region with all its content. The root region can't be removed.
+    pub fn remove_region(&mut self, handle: Handle<VisitorNode>) -> VisitResult {
+        if handle == self.root || !self.nodes.is_valid_handle(handle) {
+            return Err(VisitError::InvalidCurrentNode);
+        }
+        self.detach_region(handle);
+        let mut stack = vec![handle];
+        while let Some(handle) = stack.pop() {
+            stack.extend(self.nodes.free(handle).children);
+        }
+        Ok(())
+    }
+
+    fn detach_region(&mut self, handle: Handle<VisitorNode>) {
+        let parent = self.nodes.borrow(handle).parent;
+        if let Some(parent) = self.nodes.try_borrow_mut(parent) {
+            parent.children.retain(|child| *child != handle);
+        }
+    }
+
+    /// Path of the given node, the names of the nodes from the root separated by `/`.
+    pub fn node_path(&self, handle: Handle<VisitorNode>) -> String {
+        let mut names = Vec::new();
+        let mut node = handle;
+        while let Some(current) = self.nodes.try_borrow(node) {
+            names.push(current.name.as_str());
+            node = current.parent;
+        }
+        names.reverse();
+        names.join("/")
+    }
+
+    /// Reads or writes the version of a type in the current region. This is used by the types
+    /// with `#[visit(version = N)]` right after they entered their region.
+    ///
+    /// When writing, the version is stored in the [Visitor::VERSION_FIELD] field. When reading, the
+    /// stored version is read (data without the field has version 0) and the
+    /// [migrations](VisitMigrations) registered for the version key are applied until the data has
+    /// the given version. Returns an error if the data is newer than the given version, if there is
+    /// no migration for some intermediate version or if a migration has failed.
+    pub fn visit_version(&mut self, version_key: &str, version: u32) -> VisitResult {
+        if !self.reading {
+            let mut version = version;
+            return version.visit(Self::VERSION_FIELD, self);
+        }
+
+        let mut stored = 0u32;
+        if self.find_field(Self::VERSION_FIELD).is_some() {
+            stored.visit(Self::VERSION_FIELD, self)?;
+        }
+
+        if stored > version {
+            return Err(VisitError::User(format!(
+                "{version_key} has version {stored}, but only versions up to {version} are supported"
+            )));
+        }
+
+        while stored < version {
+            let (to, func) = VisitMigrations::find(version_key, stored).ok_or_else(|| {
+                VisitError::User(format!(
+                    "there is no migration for {version_key} from version {stored} to {version}"
+                ))
+            })?;
+            if to > version {
+                return Err(VisitError::User(format!(
+                    "migration of {version_key} from version {stored} leads to version {to}, \
+                    which is newer than {version}"
+                )));
+            }
+
+            let region = self.current_node;
+            func(self, region)?;
+            self.current_node = region;
+
+            self.applied_migrations.push(AppliedMigration {
+                version_key: version_key.to_owned(),
+                path: self.node_path(region),
+                from: stored,
+                to,
+            });
+            stored = to;
+        }
+
+        // Keep the stored version up to date, so visiting the same data again won't run the
+        // migrations twice.
+        let node = self.current_node();
+        match node.field_mut(Self::VERSION_FIELD) {
+            Some(field) => field.kind = FieldKind::U32(stored),
+            None => node
+                .fields
+                .push(Field::new(Self::VERSION_FIELD, FieldKind::U32(stored))),
+        }
+
+        Ok(())
+    }
+
+    /// Migrations that were applied while reading the data, in the order they were applied.
+    /// Empty for visitors in write mode. See [VisitMigrations].
+    pub fn applied_migrations(&self) -> &[AppliedMigration] {
+        &self.applied_migrations
+    }
+
     /// The name of the current region.
     /// This should never be None if the Visitor is operating normally,
     /// because there should be no way to leave the initial `__ROOT__` region.
@@ -1445,13 +2138,127 @@ impl Visitor {
         }
     }
 
-    /// Create a String containing all the data of this Visitor.
-    /// The String is formatted to be human-readable with each node on its own line
-    /// and tabs to indent child nodes.
+    fn write_node_text(&self, node_handle: Handle<VisitorNode>, nesting: usize, out: &mut String) {
+        let offset = "    ".repeat(nesting);
+        let node = self.nodes.borrow(node_handle);
+
+        out.push_str(&offset);
+        write_quoted_text(out, &node.name);
+        out.push_str(" {\n");
+
+        // Names are unique within a node, sorting them makes the output independent of the
+        // order in which the values were visited.
+        let mut fields = node.fields.iter().collect::<Vec<_>>();
+        fields.sort_by(|a, b| a.name.cmp(&b.name));
+        for field in fields {
+            out.push_str(&offset);
+            out.push_str("    ");
+            write_quoted_text(out, &field.name);
+            out.push(' ');
+            field.kind.write_text(out);
+            out.push('\n');
+        }
+
+        let mut children = node.children.clone();
+        children.sort_by(|a, b| self.nodes.borrow(*a).name.cmp(&self.nodes.borrow(*b).name));
+        for child_handle in children {
+            self.write_node_text(child_handle, nesting + 1, out);
+        }
+
+        out.push_str(&offset);
+        out.push_str("}\n");
+    }
+
+    /// Create a String containing all the data of this Visitor in a human-readable text format
+    /// that can be read back using [Visitor::load_text]. The text starts with [Visitor::TEXT_MAGIC]
+    /// and contains only ASCII characters. Every node is written as `"Name" {`, followed by its
+    /// fields (one per line, as `"Name" type value`), its child nodes and a closing `}`. Fields and
+    /// nodes are sorted by name, so the same data always produces the same text.
     pub fn save_text(&self) -> String {
-        let mut out_string = String::new();
-        self.print_node(self.root, 0, &mut out_string);
-        out_string
+        let mut out = String::new();
+        out.push_str(Self::TEXT_MAGIC);
+        out.push('\n');
+        self.write_node_text(self.root, 0, &mut out);
+        out
+    }
+
+    /// Create a file at the given path and write the data of this visitor into that file
+    /// in the text format produced by [Visitor::save_text].
+    pub fn save_text_to_file<P: AsRef<Path>>(&self, path: P) -> VisitResult {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(self.save_text().as_bytes())?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Create a visitor by parsing the text produced by [Visitor::save_text].
+    /// Return a [VisitError::NotSupportedFormat] if the text does not start with [Visitor::TEXT_MAGIC].
+    pub fn load_text(text: &str) -> Result<Self, VisitError> {
+        let mut lines = text.lines().enumerate();
+        if !lines
+            .next()
+            .is_some_and(|(_, header)| header.trim_end() == Self::TEXT_MAGIC)
+        {
+            return Err(VisitError::NotSupportedFormat);
+        }
+
+        let mut visitor = Self::new_reading();
+        let mut stack: Vec<Handle<VisitorNode>> = Vec::new();
+        for (index, line) in lines {
+            let error = |message: String| VisitError::InvalidText {
+                line: index + 1,
+                message,
+            };
+
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if line == "}" {
+                if stack.pop().is_none() {
+                    return Err(error("unexpected `}`".to_string()));
+                }
+                continue;
+            }
+
+            let (name, rest) = read_quoted_text(line).map_err(error)?;
+            let rest = rest.trim_start();
+            if rest == "{" {
+                let parent = stack.last().cloned().unwrap_or_default();
+                if parent.is_none() && visitor.root.is_some() {
+                    return Err(error("more than one root node".to_string()));
+                }
+                let handle = visitor.nodes.spawn(VisitorNode::new(&name, parent));
+                if let Some(parent) = visitor.nodes.try_borrow_mut(parent) {
+                    parent.children.push(handle);
+                } else {
+                    visitor.root = handle;
+                }
+                stack.push(handle);
+            } else {
+                let Some(node) = stack.last().cloned() else {
+                    return Err(error("field outside of a node".to_string()));
+                };
+                let (tag, value) = rest.split_once(' ').unwrap_or((rest, ""));
+                let kind = FieldKind::read_text(tag, value).map_err(error)?;
+                visitor
+                    .nodes
+                    .borrow_mut(node)
+                    .fields
+                    .push(Field::new(&name, kind));
+            }
+        }
+
+        if visitor.root.is_none() || !stack.is_empty() {
+            return Err(VisitError::InvalidText {
+                line: text.lines().count(),
+                message: "unexpected end of text".to_string(),
+            });
+        }
+
+        visitor.current_node = visitor.root;
+        Ok(visitor)
     }
 
     /// Write the data of this Visitor to the given writer.
@@ -1528,33 +2335,46 @@ impl Visitor {
     }
 
     /// Create a visitor by reading data from the file at the given path,
-    /// assuming that the file was created using [Visitor::save_binary].
-    /// Return a [VisitError::NotSupportedFormat] if [Visitor::MAGIC] is not the first bytes read from the file.
+    /// assuming that the file was created using [Visitor::save_binary] or [Visitor::save_text_to_file].
+    /// Return a [VisitError::NotSupportedFormat] if neither [Visitor::MAGIC] nor [Visitor::TEXT_MAGIC]
+    /// are the first bytes read from the file.
     pub async fn load_binary<P: AsRef<Path>>(path: P) -> Result<Self, VisitError> {
         Self::load_from_memory(&io::load_file(path).await?)
     }
 
+    fn new_reading() -> Self {
+        Self {
+            nodes: Pool::new(),
+            rc_map: Default::default(),
+            arc_map: Default::default(),
+            reading: true,
+            current_node: Handle::NONE,
+            root: Handle::NONE,
+            blackboard: Blackboard::new(),
+            flags: VisitorFlags::NONE,
+            applied_migrations: Vec::new(),
+        }
+    }
+
     /// Create a visitor by decoding data from the given byte slice,
     /// assuming that the bytes are in the format that would be produced
-    /// by [Visitor::save_binary_to_vec].
-    /// Return a [VisitError::NotSupportedFormat] if [Visitor::MAGIC] is not the first bytes read from the slice.
+    /// by [Visitor::save_binary_to_vec] or [Visitor::save_text]. The format is detected
+    /// by the header, so the resource loaders accept both formats transparently.
+    /// Return a [VisitError::NotSupportedFormat] if neither [Visitor::MAGIC] nor [Visitor::TEXT_MAGIC]
+    /// are the first bytes read from the slice.
     pub fn load_from_memory(data: &[u8]) -> Result<Self, VisitError> {
+        if data.starts_with(Self::TEXT_MAGIC.as_bytes()) {
+            let text = std::str::from_utf8(data).map_err(|_| VisitError::NotSupportedFormat)?;
+            return Self::load_text(text);
+        }
+
         let mut reader = Cursor::new(data);
         let mut magic: [u8; 4] = Default::default();
         reader.read_exact(&mut magic)?;
         if !magic.eq(Self::MAGIC.as_bytes()) {
             return Err(VisitError::NotSupportedFormat);
         }
-        let mut visitor = Self {
-            nodes: Pool::new(),
-            rc_map: Default::default(),
-            arc_map: Default::default(),
-            reading: true,
-            current_node: Handle::NONE,
-            root: Handle::NONE,
-            blackboard: Blackboard::new(),
-            flags: VisitorFlags::NONE,
-        };
+        let mut visitor = Self::new_reading();
         visitor.root = visitor.load_node_binary(&mut reader)?;
         visitor.current_node = visitor.root;
         Ok(visitor)
@@ -2455,4 +3275,124 @@ mod test {
             "<vec4i64 = 0; 0; 0; 0>, ".to_string()
         );
     }
+
+    #[derive(Visit, Default, Debug, PartialEq)]
+    struct TextFormatData {
+        flag: bool,
+        int: i64,
+        real: f32,
+        tiny: f64,
+        name: String,
+        bytes: Vec<u8>,
+        id: Uuid,
+        rotation: UnitQuaternion<f32>,
+        transform: Matrix4<f32>,
+        position: Vector3<f32>,
+        items: Vec<Vector2<u16>>,
+        nested: Option<Vector4<f64>>,
+    }
+
+    #[test]
+    fn text_format_round_trip() {
+        let mut data = TextFormatData {
+            flag: true,
+            int: -42,
+            real: 0.1,
+            tiny: 1.0e-300,
+            name: "Quote \" and \\ slash\nnew line, Юникод".to_string(),
+            bytes: vec![0xFF, 0x00, 0x7F],
+            id: Uuid::new_v4(),
+            rotation: UnitQuaternion::from_euler_angles(0.1, 0.2, 0.3),
+            transform: Matrix4::new_translation(&Vector3::new(1.0, 2.0, 3.0)),
+            position: Vector3::new(f32::MAX, f32::MIN_POSITIVE, -0.5),
+            items: vec![Vector2::new(1, 2), Vector2::new(3, 4)],
+            nested: Some(Vector4::new(1.0, 2.0, 3.0, 4.0)),
+        };
+        let mut pod = vec![1.5f32, -2.0];
+
+        let mut visitor = Visitor::new();
+        data.visit("Data", &mut visitor).unwrap();
+        PodVecView::from_pod_vec(&mut pod)
+            .visit("Pod", &mut visitor)
+            .unwrap();
+        let text = visitor.save_text();
+        assert!(text.starts_with(Visitor::TEXT_MAGIC));
+        assert!(text.is_ascii());
+        assert!(text.contains("\"Int\" i64 -42\n"));
+
+        // The text must be parsed back to exactly the same data.
+        let mut visitor = Visitor::load_text(&text).unwrap();
+        assert_eq!(visitor.save_text(), text);
+        let mut loaded = TextFormatData::default();
+        loaded.visit("Data", &mut visitor).unwrap();
+        // Quaternion is normalized on load, compare everything else.
+        assert!((loaded.rotation.coords - data.rotation.coords).norm() < 1.0e-6);
+        loaded.rotation = data.rotation;
+        assert_eq!(loaded, data);
+        let mut loaded_pod = Vec::<f32>::new();
+        PodVecView::from_pod_vec(&mut loaded_pod)
+            .visit("Pod", &mut visitor)
+            .unwrap();
+        assert_eq!(loaded_pod, pod);
+
+        // Both formats are accepted by the same loader and produce the same text.
+        let binary = Visitor::load_from_memory(&visitor.save_binary_to_vec().unwrap()).unwrap();
+        assert_eq!(binary.save_text(), text);
+        let sniffed = Visitor::load_from_memory(text.as_bytes()).unwrap();
+        assert_eq!(sniffed.save_text(), text);
+    }
+
+    #[test]
+    fn binary_matrix_round_trip() {
+        // Matrices are written in column-major order, so they must be read back the same way.
+        let mut m4 = Matrix4::from_fn(|r, c| (r * 4 + c) as f32);
+        let mut m3 = Matrix3::from_fn(|r, c| (r * 3 + c) as f32);
+        let mut m2 = Matrix2::new(1.0, 2.0, 3.0, 4.0);
+        let mut after = 123u32;
+
+        let mut visitor = Visitor::new();
+        m4.visit("M4", &mut visitor).unwrap();
+        m3.visit("M3", &mut visitor).unwrap();
+        m2.visit("M2", &mut visitor).unwrap();
+        after.visit("After", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let mut visitor = Visitor::load_from_memory(&data).unwrap();
+        let mut loaded_m4 = Matrix4::<f32>::zeros();
+        let mut loaded_m3 = Matrix3::<f32>::zeros();
+        let mut loaded_m2 = Matrix2::<f32>::zeros();
+        let mut loaded_after = 0u32;
+        loaded_m4.visit("M4", &mut visitor).unwrap();
+        loaded_m3.visit("M3", &mut visitor).unwrap();
+        loaded_m2.visit("M2", &mut visitor).unwrap();
+        loaded_after.visit("After", &mut visitor).unwrap();
+        assert_eq!(loaded_m4, m4);
+        assert_eq!(loaded_m3, m3);
+        assert_eq!(loaded_m2, m2);
+        // Matrix2 used to be read with a wrong element count, which broke the following fields.
+        assert_eq!(loaded_after, after);
+    }
+
+    #[test]
+    fn text_format_errors() {
+        assert!(matches!(
+            Visitor::load_text("\"__ROOT__\" {\n}\n"),
+            Err(VisitError::NotSupportedFormat)
+        ));
+
+        let text = format!(
+            "{}\n\"__ROOT__\" {{\n    \"Foo\" u8 300\n}}\n",
+            Visitor::TEXT_MAGIC
+        );
+        assert!(matches!(
+            Visitor::load_text(&text),
+            Err(VisitError::InvalidText { line: 3, .. })
+        ));
+
+        let text = format!("{}\n\"__ROOT__\" {{\n", Visitor::TEXT_MAGIC);
+        assert!(matches!(
+            Visitor::load_text(&text),
+            Err(VisitError::InvalidText { .. })
+        ));
+    }
 }
@@ -174,6 +174,18 @@ where
         node.as_reflect_mut(&mut |node| self.remap_handles_internal(node, &name, ignored_types));
     }
 
+    /// Same as [`Self::remap_handles`], but works with any entity that holds handles to nodes (for
+    /// example, a plugin or a script). `owner_name` is used only in the warning messages.
+    #[inline]
+    pub fn remap_handles_in(
+        &self,
+        entity: &mut dyn Reflect,
+        owner_name: &str,
+        ignored_types: &[TypeId],
+    ) {
+        self.remap_handles_internal(entity, owner_name, ignored_types);
+    }
+
     fn remap_handles_internal(
         &self,
         entity: &mut dyn Reflect,
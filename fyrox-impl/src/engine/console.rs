@@ -0,0 +1,1268 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! In-game developer console. See [`Console`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Vector2, Vector3, Vector4},
+        color::Color,
+        log::{Log, LogMessage},
+        pool::Handle,
+        profiler::Profiler,
+        reflect::{prelude::*, SetFieldByPathError},
+    },
+    event::{ElementState, Event, WindowEvent},
+    gui::{
+        border::BorderBuilder,
+        brush::Brush,
+        grid::{Column, GridBuilder, Row},
+        message::{KeyCode as UiKeyCode, MessageDirection, UiMessage},
+        scroll_viewer::{ScrollViewerBuilder, ScrollViewerMessage},
+        text::{TextBuilder, TextMessage},
+        text_box::{TextBoxBuilder, TextCommitMode},
+        widget::{WidgetBuilder, WidgetMessage},
+        BuildContext, UiNode, UserInterface, VerticalAlignment,
+    },
+    keyboard::{KeyCode, PhysicalKey},
+    plugin::{Plugin, PluginContainer},
+    scene::{
+        graph::query::{short_type_name, NodeQuery},
+        SceneContainer,
+    },
+};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt::Debug,
+    str::FromStr,
+    sync::mpsc::{channel, Receiver},
+};
+
+/// Result of a console command. `Ok` contains the text that will be printed to the console (it
+/// could be empty), `Err` contains the reason of the failure.
+pub type ConsoleResult = Result<String, String>;
+
+/// A value that could be parsed from an argument of a console command.
+pub trait ConsoleArgument: Sized {
+    /// Name of the type of the argument, it is shown in the usage of the command.
+    fn type_name() -> String;
+
+    /// Parses the argument.
+    fn parse(text: &str) -> Result<Self, String>;
+
+    /// Parses the argument, that might be omitted. Fails by default, if the argument is omitted.
+    fn parse_optional(text: Option<&str>) -> Result<Self, String> {
+        match text {
+            Some(text) => Self::parse(text),
+            None => Err(format!("missing argument of type {}", Self::type_name())),
+        }
+    }
+}
+
+macro_rules! impl_from_str_argument {
+    ($($ty:ty),*) => {
+        $(
+            impl ConsoleArgument for $ty {
+                fn type_name() -> String {
+                    stringify!($ty).to_string()
+                }
+
+                fn parse(text: &str) -> Result<Self, String> {
+                    <$ty>::from_str(text).map_err(|err| format!("{text}: {err}"))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_str_argument!(f32, f64, i8, u8, i16, u16, i32, u32, i64, u64, isize, usize);
+
+impl ConsoleArgument for bool {
+    fn type_name() -> String {
+        "bool".to_string()
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        match text.to_lowercase().as_str() {
+            "true" | "on" | "1" => Ok(true),
+            "false" | "off" | "0" => Ok(false),
+            _ => Err(format!("{text}: expected true/false, on/off or 1/0")),
+        }
+    }
+}
+
+impl ConsoleArgument for String {
+    fn type_name() -> String {
+        "string".to_string()
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        Ok(text.to_string())
+    }
+}
+
+impl<T: ConsoleArgument> ConsoleArgument for Option<T> {
+    fn type_name() -> String {
+        format!("[{}]", T::type_name())
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        T::parse(text).map(Some)
+    }
+
+    fn parse_optional(text: Option<&str>) -> Result<Self, String> {
+        text.map(T::parse).transpose()
+    }
+}
+
+fn parse_components<const N: usize>(text: &str) -> Result<[f32; N], String> {
+    let components = text
+        .split([',', ' '])
+        .filter(|c| !c.is_empty())
+        .map(f32::parse)
+        .collect::<Result<Vec<_>, _>>()?;
+    components
+        .try_into()
+        .map_err(|_| format!("{text}: expected {N} comma-separated numbers"))
+}
+
+macro_rules! impl_vector_argument {
+    ($($ty:ident => $n:literal),*) => {
+        $(
+            impl ConsoleArgument for $ty<f32> {
+                fn type_name() -> String {
+                    stringify!($ty).to_string()
+                }
+
+                fn parse(text: &str) -> Result<Self, String> {
+                    parse_components::<$n>(text).map(Self::from)
+                }
+            }
+        )*
+    };
+}
+
+impl_vector_argument!(Vector2 => 2, Vector3 => 3, Vector4 => 4);
+
+/// A set of typed arguments of a console command. It is implemented for tuples of up to four
+/// [`ConsoleArgument`]s.
+pub trait ConsoleArguments: Sized {
+    /// Returns type names of the arguments.
+    fn usage() -> Vec<String>;
+
+    /// Parses the arguments.
+    fn parse(args: &[String]) -> Result<Self, String>;
+}
+
+macro_rules! impl_console_arguments {
+    ($($arg:ident),*) => {
+        impl<$($arg: ConsoleArgument),*> ConsoleArguments for ($($arg,)*) {
+            fn usage() -> Vec<String> {
+                vec![$($arg::type_name()),*]
+            }
+
+            #[allow(unused_variables, unused_mut)]
+            fn parse(args: &[String]) -> Result<Self, String> {
+                let count = Self::usage().len();
+                if args.len() > count {
+                    return Err(format!("expected at most {count} arguments, got {}", args.len()));
+                }
+                let mut args = args.iter().map(|arg| arg.as_str());
+                Ok(($($arg::parse_optional(args.next())?,)*))
+            }
+        }
+    };
+}
+
+impl_console_arguments!();
+impl_console_arguments!(A);
+impl_console_arguments!(A, B);
+impl_console_arguments!(A, B, C);
+impl_console_arguments!(A, B, C, D);
+
+/// Context of a console command, it provides access to the scenes and the plugins.
+pub struct ConsoleContext<'a> {
+    /// All the scenes of the engine.
+    pub scenes: &'a mut SceneContainer,
+    /// All the plugins of the engine.
+    pub plugins: &'a mut [PluginContainer],
+}
+
+impl ConsoleContext<'_> {
+    /// Tries to find a plugin of the given type.
+    pub fn plugin<T: Plugin>(&self) -> Option<&T> {
+        self.plugins.iter().find_map(|plugin| plugin.cast::<T>())
+    }
+
+    /// Tries to find a plugin of the given type.
+    pub fn plugin_mut<T: Plugin>(&mut self) -> Option<&mut T> {
+        self.plugins
+            .iter_mut()
+            .find_map(|plugin| plugin.cast_mut::<T>())
+    }
+}
+
+type CommandHandler = Box<dyn FnMut(&mut ConsoleContext, &[String]) -> ConsoleResult>;
+
+struct ConsoleCommand {
+    description: String,
+    usage: String,
+    handler: CommandHandler,
+}
+
+/// Splits a command line into arguments. Arguments are separated by whitespace, quotes could be
+/// used to put whitespace into an argument. Outer quotes of an argument are removed.
+pub fn split_command_line(line: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    for c in line.chars() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        } else if c.is_whitespace() && !in_quotes {
+            if !current.is_empty() {
+                args.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        args.push(current);
+    }
+    for arg in args.iter_mut() {
+        if arg.len() >= 2 && arg.starts_with('"') && arg.ends_with('"') {
+            *arg = arg[1..arg.len() - 1].to_string();
+        }
+    }
+    args
+}
+
+/// Splits a target of `get` and `set` commands into the head (a node query or `@Plugin`) and the
+/// property path. Dots inside of filters and quotes do not split the target.
+fn split_target(target: &str) -> (&str, Option<&str>) {
+    let mut depth = 0;
+    let mut in_quotes = false;
+    for (i, c) in target.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '[' if !in_quotes => depth += 1,
+            ']' if !in_quotes => depth -= 1,
+            '.' if !in_quotes && depth == 0 => return (&target[..i], Some(&target[i + 1..])),
+            _ => (),
+        }
+    }
+    (target, None)
+}
+
+/// Formats a reflected value for the console. Known types are printed as values, other types are
+/// printed as a list of their fields.
+fn format_value(value: &dyn Reflect) -> String {
+    let mut result = None;
+    value.as_any(&mut |any| {
+        macro_rules! format_as {
+            ($($ty:ty),*) => {
+                $(if let Some(value) = any.downcast_ref::<$ty>() {
+                    result = Some(format!("{value:?}"));
+                    return;
+                })*
+            };
+        }
+        format_as!(f32, f64, i8, u8, i16, u16, i32, u32, i64, u64, isize, usize, bool, String);
+
+        macro_rules! format_vector {
+            ($($ty:ty),*) => {
+                $(if let Some(value) = any.downcast_ref::<$ty>() {
+                    let components = value.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+                    result = Some(components.join(", "));
+                    return;
+                })*
+            };
+        }
+        format_vector!(Vector2<f32>, Vector3<f32>, Vector4<f32>);
+    });
+    if let Some(result) = result {
+        return result;
+    }
+    let mut fields = Vec::new();
+    value.fields_info(&mut |info| fields.extend(info.iter().map(|f| f.name.to_string())));
+    if fields.is_empty() {
+        format!("{value:?}")
+    } else {
+        format!(
+            "{} {{ {} }}",
+            short_type_name(value.type_name()),
+            fields.join(", ")
+        )
+    }
+}
+
+type ValueFactory = Box<dyn FnMut() -> Box<dyn Reflect>>;
+
+/// Parses a value of the same type as the given property.
+fn parse_value(property: &dyn Reflect, text: &str) -> Result<ValueFactory, String> {
+    fn factory<T: ConsoleArgument + Reflect + Clone>(text: &str) -> Result<ValueFactory, String> {
+        let value = T::parse(text)?;
+        Ok(Box::new(move || Box::new(value.clone())))
+    }
+
+    let mut result = None;
+    property.as_any(&mut |any| {
+        macro_rules! parse_as {
+            ($($ty:ty),*) => {
+                $(if any.is::<$ty>() {
+                    result = Some(factory::<$ty>(text));
+                    return;
+                })*
+            };
+        }
+        parse_as!(
+            f32,
+            f64,
+            i8,
+            u8,
+            i16,
+            u16,
+            i32,
+            u32,
+            i64,
+            u64,
+            isize,
+            usize,
+            bool,
+            String,
+            Vector2<f32>,
+            Vector3<f32>,
+            Vector4<f32>
+        );
+    });
+    result.unwrap_or_else(|| {
+        Err(format!(
+            "properties of type {} can't be set from the console",
+            property.type_name()
+        ))
+    })
+}
+
+/// Builds the overlay widgets of the console.
+struct ConsoleOverlay {
+    root: Handle<UiNode>,
+    scroll_viewer: Handle<UiNode>,
+    output: Handle<UiNode>,
+    input: Handle<UiNode>,
+}
+
+impl ConsoleOverlay {
+    fn new(ctx: &mut BuildContext) -> Self {
+        let scroll_viewer;
+        let output;
+        let input;
+        let root = BorderBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(false)
+                .with_height(300.0)
+                .with_vertical_alignment(VerticalAlignment::Top)
+                .with_background(Brush::Solid(Color::from_rgba(0, 0, 0, 200)))
+                .with_child(
+                    GridBuilder::new(
+                        WidgetBuilder::new()
+                            .with_child({
+                                output = TextBuilder::new(WidgetBuilder::new()).build(ctx);
+                                scroll_viewer = ScrollViewerBuilder::new(WidgetBuilder::new())
+                                    .with_content(output)
+                                    .build(ctx);
+                                scroll_viewer
+                            })
+                            .with_child({
+                                input = TextBoxBuilder::new(WidgetBuilder::new().on_row(1))
+                                    .with_text_commit_mode(TextCommitMode::Immediate)
+                                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                                    .build(ctx);
+                                input
+                            }),
+                    )
+                    .add_row(Row::stretch())
+                    .add_row(Row::strict(24.0))
+                    .add_column(Column::stretch())
+                    .build(ctx),
+                ),
+        )
+        .build(ctx);
+
+        Self {
+            root,
+            scroll_viewer,
+            output,
+            input,
+        }
+    }
+}
+
+/// In-game developer console. The console is an overlay of the first user interface of the engine,
+/// it is toggled by a key (<kbd>`</kbd> by default) and executes commands typed by a user. It
+/// keeps the history of commands (<kbd>↑</kbd> and <kbd>↓</kbd> keys) and completes commands and
+/// property paths (<kbd>Tab</kbd> key).
+///
+/// Plugins could register their own commands with typed arguments, usually in
+/// [`Plugin::init`]:
+///
+/// ```rust
+/// # use fyrox_impl::engine::console::{Console, ConsoleContext};
+/// # let mut console = Console::new();
+/// console.register(
+///     "give",
+///     "Gives an item to the player",
+///     |_ctx: &mut ConsoleContext, (item, count): (String, Option<u32>)| {
+///         Ok(format!("Gave {} {item}", count.unwrap_or(1)))
+///     },
+/// );
+/// ```
+///
+/// There's a number of built-in commands:
+///
+/// - `help [command]` - prints the list of commands or the usage of a command.
+/// - `get <target>` - prints a reflected property of nodes or plugins.
+/// - `set <target> <value>` - sets a reflected property of nodes or plugins.
+/// - `log [count]` - prints the last messages of the [`Log`].
+/// - `history` - prints the history of commands.
+/// - `clear` - clears the console.
+///
+/// A target is either a node query (see [`NodeQuery`] docs), that selects nodes in every scene,
+/// or a plugin type name prefixed with `@`, followed by a property path. For example,
+/// `Pivot[name=Player].base.local_transform.local_position` or `@Game.difficulty`.
+///
+/// ## Enabling
+///
+/// The console gives unrestricted access to every reflected property, so it is **disabled** by
+/// default and can't be opened in shipped games. There are two ways to enable it for development
+/// builds:
+///
+/// - enable the `dev_console` feature of the engine crate (for example, only in a separate cargo
+///   profile or a feature of your game crate, that is used for development builds);
+/// - enable it manually in [`Plugin::init`], for example only in debug builds:
+///
+/// ```rust
+/// # use fyrox_impl::engine::console::Console;
+/// # let mut console = Console::new();
+/// if cfg!(debug_assertions) {
+///     console.set_enabled(true);
+/// }
+/// ```
+pub struct Console {
+    commands: BTreeMap<String, ConsoleCommand>,
+    history: Vec<String>,
+    history_position: Option<usize>,
+    output: VecDeque<String>,
+    output_changed: bool,
+    log: VecDeque<LogMessage>,
+    log_receiver: Receiver<LogMessage>,
+    pending: Vec<String>,
+    toggle_key: KeyCode,
+    enabled: bool,
+    overlay: Option<ConsoleOverlay>,
+    open: bool,
+    input: String,
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Console {
+    /// Maximum amount of lines in the output of the console.
+    pub const MAX_OUTPUT_LINES: usize = 512;
+
+    /// Maximum amount of log messages kept by the console.
+    pub const MAX_LOG_MESSAGES: usize = 512;
+
+    /// Creates a new console with the built-in commands.
+    pub fn new() -> Self {
+        let (sender, log_receiver) = channel();
+        Log::add_listener(sender);
+
+        let mut console = Self {
+            commands: Default::default(),
+            history: Default::default(),
+            history_position: None,
+            output: Default::default(),
+            output_changed: false,
+            log: Default::default(),
+            log_receiver,
+            pending: Default::default(),
+            toggle_key: KeyCode::Backquote,
+            enabled: cfg!(feature = "dev_console"),
+            overlay: None,
+            open: false,
+            input: Default::default(),
+        };
+
+        console.register_raw(
+            "get",
+            "Prints a property of nodes or plugins",
+            "<target>",
+            |ctx, args| match args {
+                [target] => get_property(ctx, target),
+                _ => Err("expected a target".to_string()),
+            },
+        );
+        console.register_raw(
+            "set",
+            "Sets a property of nodes or plugins",
+            "<target> <value>",
+            |ctx, args| match args {
+                [target, value @ ..] if !value.is_empty() => {
+                    set_property(ctx, target, &value.join(" "))
+                }
+                _ => Err("expected a target and a value".to_string()),
+            },
+        );
+        console.register_raw(
+            "profiler",
+            "Controls the frame profiler",
+            "[on|off|clear|save <path>]",
+            |_, args| match args {
+                [] => Ok(format!(
+                    "Profiler is {}, {} frames recorded",
+                    if Profiler::is_enabled() { "on" } else { "off" },
+                    Profiler::frames().len()
+                )),
+                [state] if state == "on" || state == "off" => {
+                    Profiler::set_enabled(state == "on");
+                    Ok(format!("Profiler is {state}"))
+                }
+                [command] if command == "clear" => {
+                    Profiler::clear();
+                    Ok(String::new())
+                }
+                [command, path] if command == "save" => Profiler::save_chrome_trace(path)
+                    .map(|_| format!("Chrome trace was saved to {path}"))
+                    .map_err(|e| e.to_string()),
+                _ => Err("expected on, off, clear or save <path>".to_string()),
+            },
+        );
+
+        console
+    }
+
+    /// Sets a key that opens and closes the console.
+    pub fn set_toggle_key(&mut self, key: KeyCode) {
+        self.toggle_key = key;
+    }
+
+    /// Returns the key that opens and closes the console.
+    pub fn toggle_key(&self) -> KeyCode {
+        self.toggle_key
+    }
+
+    /// Enables or disables the console. Disabled console can't be opened by the toggle key, but
+    /// commands still could be executed from code. The console is disabled by default, unless the
+    /// `dev_console` feature is enabled.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Returns `true` if the console is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns `true` if the overlay of the console is shown.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Registers a command with typed arguments, arguments are parsed before the handler is
+    /// called. Use [`Option`] for optional trailing arguments. A command with the same name will
+    /// be replaced.
+    pub fn register<A, F>(&mut self, name: &str, description: &str, mut handler: F)
+    where
+        A: ConsoleArguments,
+        F: FnMut(&mut ConsoleContext, A) -> ConsoleResult + 'static,
+    {
+        let usage = A::usage()
+            .into_iter()
+            .map(|arg| {
+                if arg.starts_with('[') {
+                    arg
+                } else {
+                    format!("<{arg}>")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.register_raw(name, description, &usage, move |ctx, args| {
+            handler(ctx, A::parse(args)?)
+        });
+    }
+
+    /// Registers a command that parses its arguments itself. A command with the same name will be
+    /// replaced.
+    pub fn register_raw<F>(&mut self, name: &str, description: &str, usage: &str, handler: F)
+    where
+        F: FnMut(&mut ConsoleContext, &[String]) -> ConsoleResult + 'static,
+    {
+        self.commands.insert(
+            name.to_string(),
+            ConsoleCommand {
+                description: description.to_string(),
+                usage: usage.to_string(),
+                handler: Box::new(handler),
+            },
+        );
+    }
+
+    /// Removes a command. Returns `true` if the command existed.
+    pub fn unregister(&mut self, name: &str) -> bool {
+        self.commands.remove(name).is_some()
+    }
+
+    /// Returns `true` if there's a command with the given name.
+    pub fn has_command(&self, name: &str) -> bool {
+        self.commands.contains_key(name)
+    }
+
+    /// Returns an iterator over the names of the commands.
+    pub fn command_names(&self) -> impl Iterator<Item = &str> {
+        self.commands.keys().map(|name| name.as_str())
+    }
+
+    /// Prints a line to the console.
+    pub fn print<S: AsRef<str>>(&mut self, text: S) {
+        for line in text.as_ref().lines() {
+            if self.output.len() == Self::MAX_OUTPUT_LINES {
+                self.output.pop_front();
+            }
+            self.output.push_back(line.to_string());
+        }
+        self.output_changed = true;
+    }
+
+    /// Removes every line from the console.
+    pub fn clear(&mut self) {
+        self.output.clear();
+        self.output_changed = true;
+    }
+
+    /// Returns the lines printed to the console.
+    pub fn output(&self) -> impl Iterator<Item = &str> {
+        self.output.iter().map(|line| line.as_str())
+    }
+
+    /// Returns the history of the commands, from the oldest to the newest one.
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Returns the last log messages, from the oldest to the newest one.
+    pub fn log(&self) -> impl Iterator<Item = &LogMessage> {
+        self.log.iter()
+    }
+
+    /// Adds a command line to the history and puts it in the queue. The command will be executed
+    /// by the engine on the next update.
+    pub fn submit<S: AsRef<str>>(&mut self, line: S) {
+        let line = line.as_ref().trim();
+        if line.is_empty() {
+            return;
+        }
+        if self.history.last().map(|last| last.as_str()) != Some(line) {
+            self.history.push(line.to_string());
+        }
+        self.history_position = None;
+        self.pending.push(line.to_string());
+    }
+
+    /// Executes every command in the queue.
+    pub fn execute_pending(&mut self, ctx: &mut ConsoleContext) {
+        for line in std::mem::take(&mut self.pending) {
+            self.print(format!("> {line}"));
+            let _ = self.execute(&line, ctx);
+        }
+    }
+
+    /// Executes a command line immediately and prints its result to the console.
+    pub fn execute(&mut self, line: &str, ctx: &mut ConsoleContext) -> ConsoleResult {
+        let result = self.execute_internal(line, ctx);
+        match result.as_ref() {
+            Ok(text) if !text.is_empty() => self.print(text),
+            Err(reason) => self.print(format!("Error: {reason}")),
+            _ => (),
+        }
+        result
+    }
+
+    fn execute_internal(&mut self, line: &str, ctx: &mut ConsoleContext) -> ConsoleResult {
+        let args = split_command_line(line);
+        let Some((name, args)) = args.split_first() else {
+            return Ok(String::new());
+        };
+
+        match name.as_str() {
+            "help" => return self.help(args.first().map(|name| name.as_str())),
+            "clear" => {
+                self.clear();
+                return Ok(String::new());
+            }
+            "history" => return Ok(self.history.join("\n")),
+            "log" => {
+                let count = <Option<usize>>::parse_optional(args.first().map(|a| a.as_str()))?;
+                let skip = self.log.len().saturating_sub(count.unwrap_or(20));
+                return Ok(self
+                    .log
+                    .iter()
+                    .skip(skip)
+                    .map(|message| {
+                        format!(
+                            "[{:.2}s] {:?}: {}",
+                            message.time.as_secs_f32(),
+                            message.kind,
+                            message.content
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"));
+            }
+            _ => (),
+        }
+
+        match self.commands.get_mut(name.as_str()) {
+            Some(command) => (command.handler)(ctx, args),
+            None => Err(format!(
+                "unknown command {name}, type help for the list of commands"
+            )),
+        }
+    }
+
+    fn help(&self, command: Option<&str>) -> ConsoleResult {
+        let builtin = [
+            (
+                "help",
+                "[command]",
+                "Prints the list of commands or the usage of a command",
+            ),
+            ("clear", "", "Clears the console"),
+            ("history", "", "Prints the history of commands"),
+            ("log", "[count]", "Prints the last log messages"),
+        ];
+        let mut lines = builtin
+            .iter()
+            .map(|(name, usage, description)| (*name, *usage, *description))
+            .chain(self.commands.iter().map(|(name, command)| {
+                (
+                    name.as_str(),
+                    command.usage.as_str(),
+                    command.description.as_str(),
+                )
+            }))
+            .filter(|(name, _, _)| command.map_or(true, |command| command == *name))
+            .map(|(name, usage, description)| {
+                if usage.is_empty() {
+                    format!("{name} - {description}")
+                } else {
+                    format!("{name} {usage} - {description}")
+                }
+            })
+            .collect::<Vec<_>>();
+        if lines.is_empty() {
+            return Err(format!("unknown command {}", command.unwrap_or_default()));
+        }
+        lines.sort();
+        Ok(lines.join("\n"))
+    }
+
+    /// Returns the possible completions of the given command line. Every completion is a whole
+    /// command line. Command names are completed for the first argument, plugin names and
+    /// property paths are completed for the targets of `get` and `set` commands.
+    pub fn complete(&self, line: &str, ctx: &ConsoleContext) -> Vec<String> {
+        let args = split_command_line(line);
+        let ends_with_space = line.ends_with(char::is_whitespace);
+        let (prefix, partial) = match (args.len(), ends_with_space) {
+            (0, _) => ("", ""),
+            (1, false) => ("", args[0].as_str()),
+            _ => {
+                let partial = if ends_with_space {
+                    ""
+                } else {
+                    args.last().unwrap().as_str()
+                };
+                (&line[..line.len() - partial.len()], partial)
+            }
+        };
+
+        let mut candidates = if prefix.is_empty() {
+            ["help", "clear", "history", "log"]
+                .into_iter()
+                .chain(self.command_names())
+                .filter(|name| name.starts_with(partial))
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>()
+        } else if args.len() <= 2 && matches!(args[0].as_str(), "get" | "set") {
+            complete_target(partial, ctx)
+        } else {
+            Vec::new()
+        };
+        candidates.sort();
+        candidates.dedup();
+        candidates
+            .into_iter()
+            .map(|candidate| format!("{prefix}{candidate}"))
+            .collect()
+    }
+
+    /// Moves through the history of commands. Returns the command to show, if any.
+    fn navigate_history(&mut self, back: bool) -> Option<String> {
+        if self.history.is_empty() {
+            return None;
+        }
+        let position = match (self.history_position, back) {
+            (None, true) => Some(self.history.len() - 1),
+            (None, false) => None,
+            (Some(position), true) => Some(position.saturating_sub(1)),
+            (Some(position), false) if position + 1 < self.history.len() => Some(position + 1),
+            (Some(_), false) => None,
+        };
+        self.history_position = position;
+        Some(position.map_or_else(String::new, |position| self.history[position].clone()))
+    }
+
+    /// Reads new log messages. Called by the engine on every update.
+    pub(crate) fn update(&mut self) {
+        for message in self.log_receiver.try_iter() {
+            if self.log.len() == Self::MAX_LOG_MESSAGES {
+                self.log.pop_front();
+            }
+            self.log.push_back(message);
+        }
+    }
+
+    /// Opens or closes the overlay of the console.
+    pub fn set_open(&mut self, open: bool, ui: &mut UserInterface) {
+        self.open = open;
+        let overlay = self
+            .overlay
+            .get_or_insert_with(|| ConsoleOverlay::new(&mut ui.build_ctx()));
+        ui.send_message(WidgetMessage::visibility(
+            overlay.root,
+            MessageDirection::ToWidget,
+            open,
+        ));
+        // The toggle key might be typed in the input field, clear it in any case.
+        ui.send_message(TextMessage::text(
+            overlay.input,
+            MessageDirection::ToWidget,
+            Default::default(),
+        ));
+        if open {
+            ui.send_message(WidgetMessage::width(
+                overlay.root,
+                MessageDirection::ToWidget,
+                ui.screen_size().x,
+            ));
+            ui.send_message(WidgetMessage::topmost(
+                overlay.root,
+                MessageDirection::ToWidget,
+            ));
+            ui.send_message(WidgetMessage::focus(
+                overlay.input,
+                MessageDirection::ToWidget,
+            ));
+            self.output_changed = true;
+        }
+    }
+
+    pub(crate) fn on_os_event(&mut self, event: &Event<()>, ui: &mut UserInterface) {
+        if !self.enabled {
+            return;
+        }
+        if let Event::WindowEvent {
+            event: WindowEvent::KeyboardInput { event, .. },
+            ..
+        } = event
+        {
+            if event.state == ElementState::Pressed
+                && !event.repeat
+                && event.physical_key == PhysicalKey::Code(self.toggle_key)
+            {
+                self.set_open(!self.open, ui);
+            }
+        }
+    }
+
+    pub(crate) fn on_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &mut UserInterface,
+        ctx: &ConsoleContext,
+    ) {
+        let Some(overlay) = self.overlay.as_ref() else {
+            return;
+        };
+        if message.destination() != overlay.input
+            || message.direction() != MessageDirection::FromWidget
+        {
+            return;
+        }
+        let input = overlay.input;
+
+        if let Some(TextMessage::Text(text)) = message.data() {
+            self.input.clone_from(text);
+        } else if let Some(WidgetMessage::KeyDown(key)) = message.data() {
+            let text = match key {
+                UiKeyCode::Enter | UiKeyCode::NumpadEnter => {
+                    self.submit(self.input.clone());
+                    Some(String::new())
+                }
+                UiKeyCode::ArrowUp => self.navigate_history(true),
+                UiKeyCode::ArrowDown => self.navigate_history(false),
+                UiKeyCode::Tab => {
+                    let candidates = self.complete(&self.input, ctx);
+                    match candidates.as_slice() {
+                        [] => None,
+                        [single] => Some(single.clone()),
+                        [first, ..] => {
+                            let common = candidates.iter().fold(first.as_str(), |common, c| {
+                                let len = common
+                                    .char_indices()
+                                    .zip(c.chars())
+                                    .take_while(|((_, a), b)| a == b)
+                                    .last()
+                                    .map_or(0, |((i, a), _)| i + a.len_utf8());
+                                &common[..len]
+                            });
+                            let common = common.to_string();
+                            self.print(candidates.join("    "));
+                            Some(common)
+                        }
+                    }
+                }
+                _ => None,
+            };
+            if let Some(text) = text {
+                self.input.clone_from(&text);
+                ui.send_message(TextMessage::text(input, MessageDirection::ToWidget, text));
+            }
+        }
+    }
+
+    /// Shows new output lines in the overlay. Called by the engine on every update.
+    pub(crate) fn sync_overlay(&mut self, ui: &mut UserInterface) {
+        let Some(overlay) = self.overlay.as_ref() else {
+            return;
+        };
+        if !self.output_changed || !self.open {
+            return;
+        }
+        self.output_changed = false;
+        let text = self.output.iter().cloned().collect::<Vec<_>>().join("\n");
+        ui.send_message(TextMessage::text(
+            overlay.output,
+            MessageDirection::ToWidget,
+            text,
+        ));
+        ui.send_message(ScrollViewerMessage::scroll_to_end(
+            overlay.scroll_viewer,
+            MessageDirection::ToWidget,
+        ));
+    }
+}
+
+impl Debug for Console {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Console")
+            .field("commands", &self.commands.keys().collect::<Vec<_>>())
+            .field("open", &self.open)
+            .finish()
+    }
+}
+
+/// Calls the given closure for every plugin with the given short type name.
+fn for_each_plugin(
+    plugins: &mut [PluginContainer],
+    name: &str,
+    mut func: impl FnMut(&mut dyn Reflect),
+) -> Result<(), String> {
+    let mut found = false;
+    for plugin in plugins.iter_mut() {
+        plugin.as_reflect_mut(&mut |plugin| {
+            if short_type_name(plugin.type_name()) == name {
+                found = true;
+                func(plugin);
+            }
+        });
+    }
+    if found {
+        Ok(())
+    } else {
+        Err(format!("there's no plugin {name}"))
+    }
+}
+
+fn get_property(ctx: &mut ConsoleContext, target: &str) -> ConsoleResult {
+    let (head, path) = split_target(target);
+    let mut lines = Vec::new();
+    let mut print = |owner: String, result: Result<&dyn Reflect, String>| match result {
+        Ok(value) => lines.push(format!("{owner}: {}", format_value(value))),
+        Err(reason) => lines.push(format!("{owner}: {reason}")),
+    };
+
+    if let Some(plugin) = head.strip_prefix('@') {
+        for_each_plugin(ctx.plugins, plugin, |reflect| match path {
+            Some(path) => reflect.resolve_path(path, &mut |result| {
+                print(plugin.to_string(), result.map_err(|err| err.to_string()))
+            }),
+            None => print(plugin.to_string(), Ok(reflect)),
+        })?;
+    } else {
+        let query = target.parse::<NodeQuery>().map_err(|err| err.to_string())?;
+        for scene in ctx.scenes.iter() {
+            scene.graph.query_property(&query, |handle, result| {
+                print(
+                    format!("{} ({handle})", scene.graph[handle].name()),
+                    result.map_err(|err| err.to_string()),
+                )
+            });
+        }
+    }
+
+    if lines.is_empty() {
+        Err(format!("{target} does not match anything"))
+    } else {
+        Ok(lines.join("\n"))
+    }
+}
+
+fn set_property(ctx: &mut ConsoleContext, target: &str, value: &str) -> ConsoleResult {
+    let (head, path) = split_target(target);
+    let path = path.ok_or_else(|| format!("{target} has no property path"))?;
+    let mut lines = Vec::new();
+
+    if let Some(plugin) = head.strip_prefix('@') {
+        for_each_plugin(ctx.plugins, plugin, |reflect| {
+            let mut factory = Err(String::new());
+            reflect.resolve_path(path, &mut |result| {
+                factory = result
+                    .map_err(|err| err.to_string())
+                    .and_then(|property| parse_value(property, value));
+            });
+            match factory {
+                Ok(mut factory) => reflect.set_field_by_path(path, factory(), &mut |result| {
+                    lines.push(match result {
+                        Ok(_) => format!("{plugin}: {value}"),
+                        Err(SetFieldByPathError::InvalidPath { reason, .. }) => {
+                            format!("{plugin}: {reason}")
+                        }
+                        Err(SetFieldByPathError::InvalidValue(_)) => {
+                            format!("{plugin}: invalid value {value}")
+                        }
+                    })
+                }),
+                Err(reason) => lines.push(format!("{plugin}: {reason}")),
+            }
+        })?;
+    } else {
+        let query = target.parse::<NodeQuery>().map_err(|err| err.to_string())?;
+        for scene in ctx.scenes.iter_mut() {
+            // Take the type of the property from the first selected node.
+            let mut factory = None;
+            scene.graph.query_property(&query, |_, result| {
+                if factory.is_none() {
+                    factory = Some(
+                        result
+                            .map_err(|err| err.to_string())
+                            .and_then(|property| parse_value(property, value)),
+                    );
+                }
+            });
+            let mut factory = match factory {
+                Some(Ok(factory)) => factory,
+                Some(Err(reason)) => return Err(reason),
+                None => continue,
+            };
+            for (handle, result) in scene.graph.batch_set_property_with(&query, &mut factory) {
+                let owner = format!("{} ({handle})", scene.graph[handle].name());
+                lines.push(match result {
+                    Ok(_) => format!("{owner}: {value}"),
+                    Err(reason) => format!("{owner}: {reason}"),
+                });
+            }
+        }
+    }
+
+    if lines.is_empty() {
+        Err(format!("{target} does not match anything"))
+    } else {
+        Ok(lines.join("\n"))
+    }
+}
+
+/// Completes a target of `get` and `set` commands.
+fn complete_target(partial: &str, ctx: &ConsoleContext) -> Vec<String> {
+    let (head, path) = split_target(partial);
+    let Some(path) = path else {
+        // Only plugin names could be completed without a path.
+        return match head.strip_prefix('@') {
+            Some(name) => ctx
+                .plugins
+                .iter()
+                .filter_map(|plugin| {
+                    let mut plugin_name = None;
+                    plugin.as_reflect(&mut |plugin| {
+                        plugin_name = Some(short_type_name(plugin.type_name()).to_string())
+                    });
+                    plugin_name.filter(|plugin_name| plugin_name.starts_with(name))
+                })
+                .map(|name| format!("@{name}."))
+                .collect(),
+            None => Vec::new(),
+        };
+    };
+
+    let (parent, field) = match path.rfind('.') {
+        Some(position) => (Some(&path[..position]), &path[position + 1..]),
+        None => (None, path),
+    };
+    let start = &partial[..partial.len() - field.len()];
+
+    let mut names = Vec::new();
+    let mut collect = |reflect: &dyn Reflect| {
+        let mut collect_fields = |reflect: &dyn Reflect| {
+            reflect.fields_info(&mut |info| {
+                names.extend(
+                    info.iter()
+                        .filter(|info| info.name.starts_with(field))
+                        .map(|info| format!("{start}{}", info.name)),
+                )
+            })
+        };
+        match parent {
+            Some(parent) => reflect.resolve_path(parent, &mut |result| {
+                if let Ok(reflect) = result {
+                    collect_fields(reflect)
+                }
+            }),
+            None => collect_fields(reflect),
+        }
+    };
+
+    if let Some(name) = head.strip_prefix('@') {
+        for plugin in ctx.plugins.iter() {
+            plugin.as_reflect(&mut |plugin| {
+                if short_type_name(plugin.type_name()) == name {
+                    collect(plugin)
+                }
+            });
+        }
+    } else if let Ok(query) = head.parse::<NodeQuery>() {
+        if let Some((scene, handle)) = ctx
+            .scenes
+            .iter()
+            .find_map(|scene| scene.graph.select(&query).first().map(|h| (scene, *h)))
+        {
+            collect(&scene.graph[handle]);
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        engine::console::{split_command_line, Console, ConsoleContext},
+        scene::{base::BaseBuilder, pivot::PivotBuilder, Scene, SceneContainer},
+    };
+
+    #[test]
+    fn test_split_command_line() {
+        assert_eq!(
+            split_command_line(r#"set "1 2"  3"#),
+            vec!["set", "1 2", "3"]
+        );
+        assert_eq!(
+            split_command_line(r#"get Pivot[name="Door 1"].base"#),
+            vec!["get", r#"Pivot[name="Door 1"].base"#]
+        );
+        assert!(split_command_line("   ").is_empty());
+    }
+
+    #[test]
+    fn test_console_commands() {
+        let mut scenes = SceneContainer::new(Default::default());
+        let mut scene = Scene::new();
+        let player =
+            PivotBuilder::new(BaseBuilder::new().with_name("Player")).build(&mut scene.graph);
+        scenes.add(scene);
+        let mut ctx = ConsoleContext {
+            scenes: &mut scenes,
+            plugins: &mut [],
+        };
+
+        let mut console = Console::new();
+        assert_eq!(console.is_enabled(), cfg!(feature = "dev_console"));
+        console.register(
+            "add",
+            "Adds numbers",
+            |_: &mut ConsoleContext, (a, b): (i32, Option<i32>)| {
+                Ok((a + b.unwrap_or(1)).to_string())
+            },
+        );
+        assert_eq!(console.execute("add 2 3", &mut ctx), Ok("5".to_string()));
+        assert_eq!(console.execute("add 2", &mut ctx), Ok("3".to_string()));
+        assert!(console.execute("add x", &mut ctx).is_err());
+        assert!(console.execute("add 1 2 3", &mut ctx).is_err());
+        assert!(console.execute("unknown", &mut ctx).is_err());
+        assert_eq!(
+            console.execute("help add", &mut ctx),
+            Ok("add <i32> [i32] - Adds numbers".to_string())
+        );
+
+        console.submit("set Pivot[name=Player].base.local_transform.local_position 1,2,3");
+        console.execute_pending(&mut ctx);
+        let position = **ctx.scenes.iter().next().unwrap().graph[player]
+            .local_transform()
+            .position();
+        assert_eq!(position, Vector3::new(1.0, 2.0, 3.0));
+        assert_eq!(
+            console.execute(
+                "get [name=Player].base.local_transform.local_position",
+                &mut ctx
+            ),
+            Ok(format!("Player ({player}): 1, 2, 3"))
+        );
+        assert!(console
+            .execute(
+                "set [name=Player].base.local_transform.local_position x",
+                &mut ctx
+            )
+            .is_err());
+        assert_eq!(console.history().len(), 1);
+
+        assert_eq!(console.complete("he", &ctx), vec!["help".to_string()]);
+        assert_eq!(
+            console.complete("get Pivot.base.local_transform.local_p", &ctx),
+            vec!["get Pivot.base.local_transform.local_position".to_string()]
+        );
+        assert!(console.output().any(
+            |line| line == "> set Pivot[name=Player].base.local_transform.local_position 1,2,3"
+        ));
+    }
+}
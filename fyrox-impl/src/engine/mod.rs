@@ -23,6 +23,7 @@
 
 #![warn(missing_docs)]
 
+pub mod console;
 pub mod error;
 pub mod executor;
 pub mod task;
@@ -39,22 +40,29 @@ use crate::{
     },
     core::{
         algebra::Vector2,
+        arrayvec::ArrayVec,
         futures::{executor::block_on, future::join_all},
         instant,
         log::Log,
         pool::Handle,
+        profiler::Profiler,
         reflect::Reflect,
         task::TaskPool,
         variable::try_inherit_properties,
         visitor::VisitError,
     },
-    engine::{error::EngineError, task::TaskPoolHandler},
+    engine::{
+        console::{Console, ConsoleContext},
+        error::EngineError,
+        task::TaskPoolHandler,
+    },
     event::Event,
     graph::{BaseSceneGraph, NodeMapping, SceneGraph},
     gui::{
         constructor::WidgetConstructorContainer,
         font::{loader::FontLoader, Font, BUILT_IN_FONT},
         loader::UserInterfaceLoader,
+        localization::{loader::StringTableLoader, StringTable},
         UiContainer, UiUpdateSwitches, UserInterface,
     },
     material::{
@@ -76,7 +84,12 @@ use crate::{
     scene::{
         base::NodeScriptMessage,
         camera::SkyBoxKind,
-        graph::{GraphUpdateSwitches, NodePool},
+        dim2,
+        graph::{
+            physics::{CollisionEvent, CollisionEventKind},
+            GraphUpdateSwitches, NodePool,
+        },
+        joint::Joint,
         mesh::surface::{self, SurfaceData, SurfaceDataLoader},
         navmesh,
         node::{constructor::NodeConstructorContainer, Node},
@@ -88,10 +101,11 @@ use crate::{
         Scene, SceneContainer, SceneLoader,
     },
     script::{
-        constructor::ScriptConstructorContainer, PluginsRefMut, RoutingStrategy, Script,
+        constructor::ScriptConstructorContainer, Collision, PluginsRefMut, RoutingStrategy, Script,
         ScriptContext, ScriptDeinitContext, ScriptMessage, ScriptMessageContext, ScriptMessageKind,
         ScriptMessageSender, UniversalScriptContext,
     },
+    utils::behavior::resource::{BehaviorTreeDefinition, BehaviorTreeLoader},
     window::{Window, WindowBuilder},
 };
 use fxhash::{FxHashMap, FxHashSet};
@@ -456,6 +470,9 @@ pub struct Engine {
 
     /// Script processor is used to run script methods in a strict order.
     pub script_processor: ScriptProcessor,
+
+    /// In-game developer console. See [`Console`] docs for more info.
+    pub console: Console,
 }
 
 /// Performs dispatch of script messages.
@@ -846,6 +863,61 @@ impl ScriptProcessor {
                 }
             }
 
+            // Notify scripts about collisions and broken joints that happened during the last
+            // physics step.
+            let collision_events = scene
+                .graph
+                .physics
+                .collision_events()
+                .iter()
+                .chain(scene.graph.physics2d.collision_events())
+                .cloned()
+                .collect::<Vec<_>>();
+            let broken_joints = scene
+                .graph
+                .physics
+                .broken_joints()
+                .iter()
+                .chain(scene.graph.physics2d.broken_joints())
+                .cloned()
+                .collect::<Vec<_>>();
+            if !collision_events.is_empty() || !broken_joints.is_empty() {
+                let mut context = ScriptContext {
+                    dt,
+                    elapsed_time,
+                    plugins: PluginsRefMut(plugins),
+                    handle: Default::default(),
+                    scene,
+                    scene_handle: scripted_scene.handle,
+                    resource_manager,
+                    message_sender: &scripted_scene.message_sender,
+                    message_dispatcher: &mut scripted_scene.message_dispatcher,
+                    task_pool,
+                    graphics_context,
+                    user_interfaces,
+                    script_index: 0,
+                };
+
+                for event in collision_events {
+                    dispatch_collision_event(
+                        &event,
+                        event.collider1,
+                        event.collider2,
+                        &mut context,
+                    );
+                    dispatch_collision_event(
+                        &event,
+                        event.collider2,
+                        event.collider1,
+                        &mut context,
+                    );
+                }
+
+                for joint in broken_joints {
+                    dispatch_joint_break(joint, &mut context);
+                }
+            }
+
             // Dispatch script messages only when everything is initialized and updated. This has to
             // be done this way, because all those methods could spawn new messages. However, if a new
             // message is spawned directly in `on_message` the dispatcher will correctly handle it
@@ -1104,6 +1176,83 @@ where
     }
 }
 
+/// Calls collision-related methods of scripts of the given collider and of its nearest body
+/// (rigid body or character controller) ancestor.
+fn dispatch_collision_event(
+    event: &CollisionEvent,
+    collider: Handle<Node>,
+    other: Handle<Node>,
+    context: &mut ScriptContext,
+) {
+    let graph = &context.scene.graph;
+    if !graph.is_valid_handle(collider) {
+        return;
+    }
+
+    let mut targets = ArrayVec::<Handle<Node>, 2>::new();
+    targets.push(collider);
+    let mut ancestor = graph[collider].parent();
+    while let Some(node) = graph.try_get(ancestor) {
+        if node.is_rigid_body()
+            || node.is_rigid_body2d()
+            || node.is_character_controller()
+            || node.is_character_controller2d()
+        {
+            targets.push(ancestor);
+            break;
+        }
+        ancestor = node.parent();
+    }
+
+    let collision = Collision { collider, other };
+    for target in targets {
+        context.handle = target;
+        process_node_scripts(context, &mut |script, context| {
+            if script.initialized && script.started {
+                match (event.kind, event.sensor) {
+                    (CollisionEventKind::Started, false) => {
+                        script.on_collision_enter(&collision, context)
+                    }
+                    (CollisionEventKind::Stopped, false) => {
+                        script.on_collision_exit(&collision, context)
+                    }
+                    (CollisionEventKind::Started, true) => {
+                        script.on_trigger_enter(&collision, context)
+                    }
+                    (CollisionEventKind::Stopped, true) => {
+                        script.on_trigger_exit(&collision, context)
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Calls [`crate::script::ScriptTrait::on_joint_break`] of scripts of the given joint and of both
+/// bodies attached to it.
+fn dispatch_joint_break(joint: Handle<Node>, context: &mut ScriptContext) {
+    let Some(node) = context.scene.graph.try_get(joint) else {
+        return;
+    };
+
+    let bodies = if let Some(joint) = node.cast::<Joint>() {
+        [joint.body1(), joint.body2()]
+    } else if let Some(joint) = node.cast::<dim2::joint::Joint>() {
+        [joint.body1(), joint.body2()]
+    } else {
+        return;
+    };
+
+    for target in std::iter::once(joint).chain(bodies) {
+        context.handle = target;
+        process_node_scripts(context, &mut |script, context| {
+            if script.initialized && script.started {
+                script.on_joint_break(joint, context)
+            }
+        });
+    }
+}
+
 pub(crate) fn process_scripts<T>(
     scene: &mut Scene,
     scene_handle: Handle<Scene>,
@@ -1210,6 +1359,8 @@ pub(crate) fn initialize_resource_manager_loaders(
     state.constructors_container.add::<SurfaceData>();
     state.constructors_container.add::<TileSet>();
     state.constructors_container.add::<TileMapBrush>();
+    state.constructors_container.add::<StringTable>();
+    state.constructors_container.add::<BehaviorTreeDefinition>();
 
     let loaders = &mut state.loaders;
     loaders.set(model_loader);
@@ -1234,6 +1385,8 @@ pub(crate) fn initialize_resource_manager_loaders(
         resource_manager: resource_manager.clone(),
     });
     state.loaders.set(TileMapBrushLoader {});
+    state.loaders.set(StringTableLoader);
+    state.loaders.set(BehaviorTreeLoader);
 }
 
 impl Engine {
@@ -1316,6 +1469,7 @@ impl Engine {
             plugins_enabled: false,
             elapsed_time: 0.0,
             task_pool: TaskPoolHandler::new(task_pool),
+            console: Console::new(),
         })
     }
 
@@ -1545,6 +1699,7 @@ impl Engine {
                             async_scene_loader: &mut self.async_scene_loader,
                             window_target: Some(window_target),
                             task_pool: &mut self.task_pool,
+                            console: &mut self.console,
                         };
 
                         for plugin in self.plugins.iter_mut() {
@@ -1578,6 +1733,7 @@ impl Engine {
                     async_scene_loader: &mut self.async_scene_loader,
                     window_target: Some(window_target),
                     task_pool: &mut self.task_pool,
+                    console: &mut self.console,
                 };
 
                 match loading_result.result {
@@ -1651,6 +1807,9 @@ impl Engine {
                         }
 
                         let scene_handle = context.scenes.add(scene);
+                        context
+                            .scenes
+                            .set_source_path(scene_handle, Some(request.path.clone()));
 
                         // Notify plugins about newly loaded scene.
                         if self.plugins_enabled {
@@ -1709,8 +1868,12 @@ impl Engine {
         lag: &mut f32,
         switches: FxHashMap<Handle<Scene>, GraphUpdateSwitches>,
     ) {
+        Profiler::next_frame();
+        crate::core::scope_profile!();
+
         self.resource_manager.state().update(dt);
         self.handle_model_events();
+        self.console.update();
 
         let window_size = if let GraphicsContext::Initialized(ctx) = &mut self.graphics_context {
             let inner_size = ctx.window.inner_size();
@@ -1751,11 +1914,14 @@ impl Engine {
     /// Normally, this is called from `Engine::update()`.
     /// You should only call this manually if you don't use that method.
     pub fn post_update(&mut self, dt: f32, ui_update_switches: &UiUpdateSwitches) {
+        crate::core::scope_profile!();
+
         if let GraphicsContext::Initialized(ref ctx) = self.graphics_context {
             let inner_size = ctx.window.inner_size();
             let window_size = Vector2::new(inner_size.width as f32, inner_size.height as f32);
 
             let time = instant::Instant::now();
+            self.user_interfaces.sync_localization();
             for ui in self.user_interfaces.iter_mut() {
                 ui.update(window_size, dt, ui_update_switches);
             }
@@ -1764,6 +1930,20 @@ impl Engine {
         }
     }
 
+    /// Sets current locale of every user interface of the engine. Localized widgets will be updated on
+    /// the next frame. The locale is stored in the localization of [`Self::user_interfaces`] container,
+    /// which is shared with every user interface in the container (see [`UiContainer::localization`]).
+    /// User interfaces that are not in the container are not affected. See
+    /// [`crate::gui::localization::Localization`] docs for more info.
+    pub fn set_locale(&self, locale: impl Into<String>) {
+        self.user_interfaces.localization().set_locale(locale)
+    }
+
+    /// Returns current locale of the engine.
+    pub fn locale(&self) -> String {
+        self.user_interfaces.localization().locale()
+    }
+
     /// Returns true if the scene is registered for script processing.
     pub fn has_scripted_scene(&self, scene: Handle<Scene>) -> bool {
         self.script_processor.has_scripted_scene(scene)
@@ -1776,6 +1956,8 @@ impl Engine {
     }
 
     fn handle_scripts(&mut self, dt: f32) {
+        crate::core::scope_profile!();
+
         let time = instant::Instant::now();
 
         self.script_processor.handle_scripts(
@@ -1819,6 +2001,7 @@ impl Engine {
                         async_scene_loader: &mut self.async_scene_loader,
                         window_target: Some(window_target),
                         task_pool: &mut self.task_pool,
+                        console: &mut self.console,
                     },
                 )
             } else if let Some(node_task_handler) = self.task_pool.pop_node_task_handler(result.id)
@@ -1891,6 +2074,8 @@ impl Engine {
         window_target: &EventLoopWindowTarget<()>,
         lag: &mut f32,
     ) {
+        crate::core::scope_profile!();
+
         let time = instant::Instant::now();
 
         if self.plugins_enabled {
@@ -1913,6 +2098,7 @@ impl Engine {
                 async_scene_loader: &mut self.async_scene_loader,
                 window_target: Some(window_target),
                 task_pool: &mut self.task_pool,
+                console: &mut self.console,
             };
 
             for plugin in self.plugins.iter_mut() {
@@ -1925,12 +2111,25 @@ impl Engine {
                 .map(|(h, _)| h)
                 .collect::<VecDeque<_>>();
 
+            let console_ui = uis.front().cloned();
+
             while let Some(ui) = uis.pop_front() {
                 while let Some(message) = self
                     .user_interfaces
                     .try_get_mut(ui)
                     .and_then(|ui| ui.poll_message())
                 {
+                    if Some(ui) == console_ui {
+                        self.console.on_ui_message(
+                            &message,
+                            &mut self.user_interfaces[ui],
+                            &ConsoleContext {
+                                scenes: &mut self.scenes,
+                                plugins: &mut self.plugins,
+                            },
+                        );
+                    }
+
                     let mut context = PluginContext {
                         scenes: &mut self.scenes,
                         resource_manager: &self.resource_manager,
@@ -1946,6 +2145,7 @@ impl Engine {
                         async_scene_loader: &mut self.async_scene_loader,
                         window_target: Some(window_target),
                         task_pool: &mut self.task_pool,
+                        console: &mut self.console,
                     };
 
                     for plugin in self.plugins.iter_mut() {
@@ -1953,6 +2153,12 @@ impl Engine {
                     }
                 }
             }
+
+            self.console.execute_pending(&mut ConsoleContext {
+                scenes: &mut self.scenes,
+                plugins: &mut self.plugins,
+            });
+            self.console.sync_overlay(self.user_interfaces.first_mut());
         }
 
         self.performance_statistics.plugins_time = instant::Instant::now() - time;
@@ -1966,6 +2172,9 @@ impl Engine {
         lag: &mut f32,
     ) {
         if self.plugins_enabled {
+            self.console
+                .on_os_event(event, self.user_interfaces.first_mut());
+
             for plugin in self.plugins.iter_mut() {
                 plugin.on_os_event(
                     event,
@@ -1984,6 +2193,7 @@ impl Engine {
                         async_scene_loader: &mut self.async_scene_loader,
                         window_target: Some(window_target),
                         task_pool: &mut self.task_pool,
+                        console: &mut self.console,
                     },
                 );
             }
@@ -2013,6 +2223,7 @@ impl Engine {
                     async_scene_loader: &mut self.async_scene_loader,
                     window_target: Some(window_target),
                     task_pool: &mut self.task_pool,
+                    console: &mut self.console,
                 });
             }
         }
@@ -2041,6 +2252,7 @@ impl Engine {
                     async_scene_loader: &mut self.async_scene_loader,
                     window_target: Some(window_target),
                     task_pool: &mut self.task_pool,
+                    console: &mut self.console,
                 });
             }
         }
@@ -2069,6 +2281,7 @@ impl Engine {
                     async_scene_loader: &mut self.async_scene_loader,
                     window_target: Some(window_target),
                     task_pool: &mut self.task_pool,
+                    console: &mut self.console,
                 });
             }
         }
@@ -2150,6 +2363,8 @@ impl Engine {
     /// see anything.
     #[inline]
     pub fn render(&mut self) -> Result<(), FrameworkError> {
+        crate::core::scope_profile!();
+
         for ui in self.user_interfaces.iter_mut() {
             ui.draw();
         }
@@ -2204,6 +2419,7 @@ impl Engine {
                             async_scene_loader: &mut self.async_scene_loader,
                             window_target,
                             task_pool: &mut self.task_pool,
+                            console: &mut self.console,
                         },
                     );
                 }
@@ -2227,6 +2443,7 @@ impl Engine {
                         async_scene_loader: &mut self.async_scene_loader,
                         window_target,
                         task_pool: &mut self.task_pool,
+                        console: &mut self.console,
                     });
                 }
             }
@@ -2533,6 +2750,7 @@ impl Engine {
             async_scene_loader: &mut self.async_scene_loader,
             window_target: Some(window_target),
             task_pool: &mut self.task_pool,
+            console: &mut self.console,
         });
 
         Log::info(format!("Plugin {plugin_index} was successfully reloaded!"));
@@ -2601,15 +2819,27 @@ mod test {
     use crate::{
         asset::manager::ResourceManager,
         core::{
-            pool::Handle, reflect::prelude::*, task::TaskPool, type_traits::prelude::*,
+            algebra::{Vector2, Vector3},
+            pool::Handle,
+            reflect::prelude::*,
+            task::TaskPool,
+            type_traits::prelude::*,
             visitor::prelude::*,
         },
         engine::{task::TaskPoolHandler, GraphicsContext, ScriptProcessor},
         graph::BaseSceneGraph,
-        scene::{base::BaseBuilder, node::Node, pivot::PivotBuilder, Scene, SceneContainer},
+        scene::{
+            base::BaseBuilder,
+            collider::{ColliderBuilder, ColliderShape},
+            node::Node,
+            pivot::PivotBuilder,
+            rigidbody::{RigidBodyBuilder, RigidBodyType},
+            transform::TransformBuilder,
+            Scene, SceneContainer,
+        },
         script::{
-            ScriptContext, ScriptDeinitContext, ScriptMessageContext, ScriptMessagePayload,
-            ScriptTrait,
+            Collision, ScriptContext, ScriptDeinitContext, ScriptMessageContext,
+            ScriptMessagePayload, ScriptTrait,
         },
     };
     use fyrox_ui::UiContainer;
@@ -3315,4 +3545,91 @@ mod test {
             }
         }
     }
+
+    #[derive(Debug, Clone, Reflect, Visit, TypeUuidProvider, ComponentProvider)]
+    #[type_uuid(id = "0b4ab4a6-94b7-4b4c-a0f7-0a9d6f8e43de")]
+    struct CollisionListener {
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<(Handle<Node>, Collision)>,
+    }
+
+    impl ScriptTrait for CollisionListener {
+        fn on_collision_enter(&mut self, collision: &Collision, ctx: &mut ScriptContext) {
+            self.sender.send((ctx.handle, *collision)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_collision_callbacks() {
+        let resource_manager = ResourceManager::new(Arc::new(Default::default()));
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+
+        let ground_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(10.0, 0.5, 10.0))
+            .build(&mut scene.graph);
+        RigidBodyBuilder::new(BaseBuilder::new().with_children(&[ground_collider]))
+            .with_body_type(RigidBodyType::Static)
+            .build(&mut scene.graph);
+
+        let box_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(0.5, 0.5, 0.5))
+            .build(&mut scene.graph);
+        let body = RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_children(&[box_collider])
+                .with_script(CollisionListener { sender: tx })
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 2.0, 0.0))
+                        .build(),
+                ),
+        )
+        .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+        script_processor.register_scripted_scene(scene_handle, &resource_manager);
+
+        let mut task_pool = TaskPoolHandler::new(Arc::new(TaskPool::new()));
+        let mut gc = GraphicsContext::Uninitialized(Default::default());
+        let mut user_interfaces = UiContainer::default();
+
+        for _ in 0..120 {
+            scene_container[scene_handle].graph.update(
+                Vector2::new(800.0, 600.0),
+                1.0 / 60.0,
+                Default::default(),
+            );
+
+            script_processor.handle_scripts(
+                &mut scene_container,
+                &mut Vec::new(),
+                &resource_manager,
+                &mut task_pool,
+                &mut gc,
+                &mut user_interfaces,
+                1.0 / 60.0,
+                0.0,
+            );
+        }
+
+        // The box fell on the ground, the script of the body must be notified about the collision
+        // of its collider exactly once.
+        assert_eq!(
+            rx.try_recv(),
+            Ok((
+                body,
+                Collision {
+                    collider: box_collider,
+                    other: ground_collider,
+                }
+            ))
+        );
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    }
 }
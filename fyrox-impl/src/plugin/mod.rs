@@ -32,8 +32,8 @@ use crate::{
         visitor::{Visit, VisitError},
     },
     engine::{
-        task::TaskPoolHandler, AsyncSceneLoader, GraphicsContext, PerformanceStatistics,
-        ScriptProcessor, SerializationContext,
+        console::Console, task::TaskPoolHandler, AsyncSceneLoader, GraphicsContext,
+        PerformanceStatistics, ScriptProcessor, SerializationContext,
     },
     event::Event,
     gui::{
@@ -185,6 +185,10 @@ pub struct PluginContext<'a, 'b> {
 
     /// Task pool for asynchronous task management.
     pub task_pool: &'a mut TaskPoolHandler,
+
+    /// In-game developer console. Plugins could register their own commands in it. See
+    /// [`Console`] docs for more info.
+    pub console: &'a mut Console,
 }
 
 /// Base plugin automatically implements type casting for plugins.
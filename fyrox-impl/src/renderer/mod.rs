@@ -1709,6 +1709,8 @@ impl Renderer {
         scenes: &SceneContainer,
         drawing_contexts: impl Iterator<Item = &'a DrawingContext>,
     ) -> Result<(), FrameworkError> {
+        crate::core::scope_profile!();
+
         if self.frame_size.0 == 0 || self.frame_size.1 == 0 {
             return Ok(());
         }
@@ -1363,7 +1363,7 @@ impl BaseBuilder {
             frustum_culling: self.frustum_culling.into(),
             cast_shadows: self.cast_shadows.into(),
             scripts: self.scripts,
-            instance_id: SceneNodeId(Uuid::new_v4()),
+            instance_id: self.instance_id,
             enabled: self.enabled.into(),
             global_enabled: Cell::new(true),
         }
@@ -1050,13 +1050,18 @@ impl ColliderBuilder {
 
 #[cfg(test)]
 mod test {
-    use crate::core::algebra::Vector2;
+    use crate::core::algebra::{Isometry3, Point3, Vector2, Vector3};
     use crate::scene::{
         base::BaseBuilder,
         collider::{ColliderBuilder, ColliderShape},
-        graph::Graph,
+        graph::{
+            physics::{QueryFilter, RayCastOptions},
+            Graph,
+        },
         rigidbody::{RigidBodyBuilder, RigidBodyType},
+        transform::TransformBuilder,
     };
+    use rapier3d::parry::shape::Ball;
 
     #[test]
     fn test_collider_intersect() {
@@ -1119,4 +1124,107 @@ mod test {
                 .count()
         );
     }
+
+    #[test]
+    fn test_shape_and_point_queries() {
+        let mut graph = Graph::new();
+
+        let mut create_box = |position: Vector3<f32>| {
+            let collider = ColliderBuilder::new(BaseBuilder::new())
+                .with_shape(ColliderShape::cuboid(0.5, 0.5, 0.5))
+                .build(&mut graph);
+            RigidBodyBuilder::new(
+                BaseBuilder::new()
+                    .with_children(&[collider])
+                    .with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_position(position)
+                            .build(),
+                    ),
+            )
+            .with_body_type(RigidBodyType::Static)
+            .build(&mut graph);
+            collider
+        };
+
+        let near = create_box(Vector3::new(0.0, 0.0, 0.0));
+        let far = create_box(Vector3::new(5.0, 0.0, 0.0));
+
+        // Native colliders are created on the second update.
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+
+        let mut overlaps = Vec::new();
+        graph.physics.intersections_with_shape(
+            &graph,
+            &Ball::new(1.0),
+            &Isometry3::translation(1.0, 0.0, 0.0),
+            QueryFilter::default(),
+            |collider| {
+                overlaps.push(collider);
+                true
+            },
+        );
+        assert_eq!(overlaps, vec![near]);
+
+        let mut overlaps = Vec::new();
+        graph.physics.intersections_with_point(
+            &graph,
+            &Point3::new(5.2, 0.1, 0.0),
+            QueryFilter::default(),
+            |collider| {
+                overlaps.push(collider);
+                true
+            },
+        );
+        assert_eq!(overlaps, vec![far]);
+
+        let projection = graph
+            .physics
+            .project_point(
+                &graph,
+                &Point3::new(2.0, 0.0, 0.0),
+                true,
+                QueryFilter {
+                    exclude_collider: Some(near),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(projection.collider, far);
+        assert!(!projection.is_inside);
+        assert!((projection.point - Point3::new(4.5, 0.0, 0.0)).norm() < 0.001);
+
+        // Callbacks are allowed to do other scene queries.
+        let mut hits = Vec::new();
+        let mut nested_query = |collider| {
+            let mut intersections = Vec::new();
+            graph.physics.cast_ray(
+                RayCastOptions {
+                    ray_origin: Point3::new(-5.0, 0.0, 0.0),
+                    ray_direction: Vector3::x(),
+                    max_len: 100.0,
+                    groups: Default::default(),
+                    sort_results: true,
+                },
+                &mut intersections,
+            );
+            hits.push((collider, intersections.first().map(|i| i.collider)));
+            true
+        };
+        graph.physics.intersections_with_shape(
+            &graph,
+            &Ball::new(1.0),
+            &Isometry3::translation(1.0, 0.0, 0.0),
+            QueryFilter::default(),
+            &mut nested_query,
+        );
+        graph.physics.intersections_with_point(
+            &graph,
+            &Point3::new(5.2, 0.1, 0.0),
+            QueryFilter::default(),
+            &mut nested_query,
+        );
+        assert_eq!(hits, vec![(near, Some(near)), (far, Some(near))]);
+    }
 }
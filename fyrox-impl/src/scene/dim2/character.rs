@@ -0,0 +1,576 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! 2D character controller is a kinematic physics entity that moves a 2D collider through the
+//! world without penetrating other colliders. See [`CharacterController`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Vector2},
+        log::Log,
+        math::{aabb::AxisAlignedBoundingBox, m4x4_approx_eq},
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        character::Autostep,
+        dim2::collider::Collider,
+        graph::Graph,
+        node::{Node, NodeTrait, SyncContext, UpdateContext},
+        Scene,
+    },
+};
+use fyrox_graph::{BaseSceneGraph, SceneGraph};
+use rapier2d::prelude::RigidBodyHandle;
+use std::{
+    cell::Cell,
+    f32::consts::FRAC_PI_4,
+    fmt::{Debug, Formatter},
+    ops::{Deref, DerefMut},
+};
+
+/// A collision of a character with a collider that happened during the last movement.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CharacterCollision {
+    /// A handle of the collider the character has collided with.
+    pub collider: Handle<Node>,
+    /// A contact point on the collider in world coordinates.
+    pub position: Vector2<f32>,
+    /// A normal of the collider at the contact point in world coordinates.
+    pub normal: Vector2<f32>,
+    /// A part of the desired translation that was applied before the collision.
+    pub translation_applied: Vector2<f32>,
+    /// A part of the desired translation that was left after the collision.
+    pub translation_remaining: Vector2<f32>,
+}
+
+/// Results of the last movement of a character controller.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CharacterMovement {
+    /// Actual translation of the character, it could differ from the desired translation because
+    /// of obstacles.
+    pub translation: Vector2<f32>,
+    /// `true` if the character touches the ground after the movement.
+    pub grounded: bool,
+    /// `true` if the character is sliding down a slope that is steeper than
+    /// [`CharacterController::min_slope_slide_angle`].
+    pub sliding_down_slope: bool,
+    /// A list of collisions that happened during the movement.
+    pub collisions: Vec<CharacterCollision>,
+}
+
+/// 2D character controller is a kinematic physics entity that moves its collider through the
+/// world without penetrating other colliders. It is a 2D version of
+/// [`crate::scene::character::CharacterController`], see its docs for more info.
+///
+/// 2D character controller must have a 2D [`Collider`] as a direct child node, the shape of the
+/// collider is used to resolve collisions.
+///
+/// # Movement
+///
+/// Desired translations are accumulated during a frame and applied by the simulation steps of the
+/// physics world when the graph is updated, the gravity of the physics world is applied
+/// automatically (see [`Self::set_gravity_scale`]). When the
+/// [fixed time step](crate::scene::graph::physics::FixedTimestep) is enabled, the desired
+/// translation of a frame is treated as a velocity (translation divided by the frame time) and
+/// every fixed step moves the character by its part of it, so the movement is deterministic and
+/// interpolated like the movement of dynamic rigid bodies. Results of the movement (actual
+/// translation, ground contact, collisions) of all the steps of the last update are available
+/// until the next update, which makes them accessible from scripts.
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::{algebra::Vector2, pool::Handle},
+/// #     scene::{dim2::character::CharacterController, graph::Graph, node::Node},
+/// # };
+/// fn walk(graph: &mut Graph, character: Handle<Node>, velocity: Vector2<f32>, dt: f32) -> bool {
+///     let character = graph[character].as_character_controller2d_mut();
+///     character.move_by(velocity * dt);
+///     character.is_grounded()
+/// }
+/// ```
+#[derive(Visit, Reflect)]
+pub struct CharacterController {
+    base: Base,
+
+    #[reflect(setter = "set_up")]
+    pub(crate) up: InheritableVariable<Vector2<f32>>,
+
+    #[reflect(min_value = 0.0, step = 0.005)]
+    #[reflect(setter = "set_offset")]
+    pub(crate) offset: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_slide")]
+    pub(crate) slide: InheritableVariable<bool>,
+
+    #[reflect(setter = "set_autostep")]
+    pub(crate) autostep: InheritableVariable<Option<Autostep>>,
+
+    #[reflect(min_value = 0.0, max_value = 1.571, step = 0.01)]
+    #[reflect(setter = "set_max_slope_climb_angle")]
+    pub(crate) max_slope_climb_angle: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, max_value = 1.571, step = 0.01)]
+    #[reflect(setter = "set_min_slope_slide_angle")]
+    pub(crate) min_slope_slide_angle: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_snap_to_ground")]
+    pub(crate) snap_to_ground: InheritableVariable<Option<f32>>,
+
+    #[reflect(setter = "set_push_dynamic_bodies")]
+    pub(crate) push_dynamic_bodies: InheritableVariable<bool>,
+
+    #[reflect(min_value = 0.0, step = 0.05)]
+    #[reflect(setter = "set_mass")]
+    pub(crate) mass: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_gravity_scale")]
+    pub(crate) gravity_scale: InheritableVariable<f32>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) native: Cell<RigidBodyHandle>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) desired_translation: Vector2<f32>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) fall_velocity: Vector2<f32>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) movement: CharacterMovement,
+}
+
+impl Debug for CharacterController {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CharacterController")
+    }
+}
+
+impl Default for CharacterController {
+    fn default() -> Self {
+        CharacterControllerBuilder::new(BaseBuilder::new()).build_character_controller()
+    }
+}
+
+impl Deref for CharacterController {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for CharacterController {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl Clone for CharacterController {
+    fn clone(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+            up: self.up.clone(),
+            offset: self.offset.clone(),
+            slide: self.slide.clone(),
+            autostep: self.autostep.clone(),
+            max_slope_climb_angle: self.max_slope_climb_angle.clone(),
+            min_slope_slide_angle: self.min_slope_slide_angle.clone(),
+            snap_to_ground: self.snap_to_ground.clone(),
+            push_dynamic_bodies: self.push_dynamic_bodies.clone(),
+            mass: self.mass.clone(),
+            gravity_scale: self.gravity_scale.clone(),
+            // Do not copy. The copy will have its own native representation.
+            native: Cell::new(RigidBodyHandle::invalid()),
+            desired_translation: self.desired_translation,
+            fall_velocity: self.fall_velocity,
+            movement: self.movement.clone(),
+        }
+    }
+}
+
+impl TypeUuidProvider for CharacterController {
+    fn type_uuid() -> Uuid {
+        uuid!("0459daab-41c7-413f-a6a0-c6cbe68c246a")
+    }
+}
+
+impl CharacterController {
+    /// Adds the given translation to the desired translation of the character. The character will
+    /// be moved on the next update of the graph.
+    pub fn move_by(&mut self, translation: Vector2<f32>) {
+        self.desired_translation += translation;
+    }
+
+    /// Returns the desired translation accumulated since the last update.
+    pub fn desired_translation(&self) -> Vector2<f32> {
+        self.desired_translation
+    }
+
+    /// Sets the up vector of the character. It is used to tell the ground from walls and
+    /// ceilings. Default is (0.0, 1.0).
+    pub fn set_up(&mut self, up: Vector2<f32>) -> Vector2<f32> {
+        self.up.set_value_and_mark_modified(up)
+    }
+
+    /// Returns the up vector of the character.
+    pub fn up(&self) -> Vector2<f32> {
+        *self.up
+    }
+
+    /// Sets a small gap to preserve between the character and its surroundings. It should be
+    /// small, but not zero, to prevent numerical issues. Default is 0.01.
+    pub fn set_offset(&mut self, offset: f32) -> f32 {
+        self.offset.set_value_and_mark_modified(offset)
+    }
+
+    /// Returns the gap between the character and its surroundings.
+    pub fn offset(&self) -> f32 {
+        *self.offset
+    }
+
+    /// Sets whether the character should slide along obstacles or stop on them. Default is
+    /// `true`.
+    pub fn set_slide(&mut self, slide: bool) -> bool {
+        self.slide.set_value_and_mark_modified(slide)
+    }
+
+    /// Returns `true` if the character slides along obstacles.
+    pub fn is_slide(&self) -> bool {
+        *self.slide
+    }
+
+    /// Sets autostep parameters of the character. `None` disables autostep. Default is `None`.
+    pub fn set_autostep(&mut self, autostep: Option<Autostep>) -> Option<Autostep> {
+        self.autostep.set_value_and_mark_modified(autostep)
+    }
+
+    /// Returns current autostep parameters.
+    pub fn autostep(&self) -> Option<&Autostep> {
+        self.autostep.as_ref()
+    }
+
+    /// Sets the maximum angle (in radians) of a slope the character can climb. Default is π/4.
+    pub fn set_max_slope_climb_angle(&mut self, angle: f32) -> f32 {
+        self.max_slope_climb_angle
+            .set_value_and_mark_modified(angle)
+    }
+
+    /// Returns the maximum angle (in radians) of a slope the character can climb.
+    pub fn max_slope_climb_angle(&self) -> f32 {
+        *self.max_slope_climb_angle
+    }
+
+    /// Sets the minimum angle (in radians) of a slope that makes the character slide down.
+    /// Default is π/4.
+    pub fn set_min_slope_slide_angle(&mut self, angle: f32) -> f32 {
+        self.min_slope_slide_angle
+            .set_value_and_mark_modified(angle)
+    }
+
+    /// Returns the minimum angle (in radians) of a slope that makes the character slide down.
+    pub fn min_slope_slide_angle(&self) -> f32 {
+        *self.min_slope_slide_angle
+    }
+
+    /// Sets the maximum distance to the ground the character will be snapped to, this prevents the
+    /// character from "flying" when moving downhill or down the stairs. `None` disables snapping.
+    /// Default is 0.2.
+    pub fn set_snap_to_ground(&mut self, distance: Option<f32>) -> Option<f32> {
+        self.snap_to_ground.set_value_and_mark_modified(distance)
+    }
+
+    /// Returns the maximum distance to the ground the character will be snapped to.
+    pub fn snap_to_ground(&self) -> Option<f32> {
+        *self.snap_to_ground
+    }
+
+    /// Sets whether the character should push dynamic rigid bodies it collides with. Default is
+    /// `true`.
+    pub fn set_push_dynamic_bodies(&mut self, push: bool) -> bool {
+        self.push_dynamic_bodies.set_value_and_mark_modified(push)
+    }
+
+    /// Returns `true` if the character pushes dynamic rigid bodies.
+    pub fn is_push_dynamic_bodies(&self) -> bool {
+        *self.push_dynamic_bodies
+    }
+
+    /// Sets the mass of the character, it is used to calculate impulses applied to dynamic rigid
+    /// bodies. Default is 1.0.
+    pub fn set_mass(&mut self, mass: f32) -> f32 {
+        self.mass.set_value_and_mark_modified(mass)
+    }
+
+    /// Returns the mass of the character.
+    pub fn mass(&self) -> f32 {
+        *self.mass
+    }
+
+    /// Sets a gravity scale coefficient. Zero can be used to disable gravity. Default is 1.0.
+    pub fn set_gravity_scale(&mut self, scale: f32) -> f32 {
+        self.gravity_scale.set_value_and_mark_modified(scale)
+    }
+
+    /// Returns current gravity scale coefficient.
+    pub fn gravity_scale(&self) -> f32 {
+        *self.gravity_scale
+    }
+
+    /// Returns the velocity the character gained from the gravity. It is reset when the character
+    /// touches the ground.
+    pub fn fall_velocity(&self) -> Vector2<f32> {
+        self.fall_velocity
+    }
+
+    /// Returns results of the last movement.
+    pub fn last_movement(&self) -> &CharacterMovement {
+        &self.movement
+    }
+
+    /// Returns `true` if the character touched the ground after the last movement.
+    pub fn is_grounded(&self) -> bool {
+        self.movement.grounded
+    }
+
+    /// Returns `true` if the character was sliding down a slope during the last movement.
+    pub fn is_sliding_down_slope(&self) -> bool {
+        self.movement.sliding_down_slope
+    }
+
+    /// Returns a list of collisions that happened during the last movement.
+    pub fn collisions(&self) -> &[CharacterCollision] {
+        &self.movement.collisions
+    }
+
+    pub(crate) fn native_controller(&self) -> rapier2d::control::KinematicCharacterController {
+        use rapier2d::control::{CharacterAutostep, CharacterLength, KinematicCharacterController};
+
+        KinematicCharacterController {
+            up: rapier2d::na::UnitVector2::try_new(*self.up, f32::EPSILON)
+                .unwrap_or_else(Vector2::y_axis),
+            offset: CharacterLength::Absolute(*self.offset),
+            slide: *self.slide,
+            autostep: self.autostep.as_ref().map(|autostep| CharacterAutostep {
+                max_height: CharacterLength::Absolute(autostep.max_height),
+                min_width: CharacterLength::Absolute(autostep.min_width),
+                include_dynamic_bodies: autostep.include_dynamic_bodies,
+            }),
+            max_slope_climb_angle: *self.max_slope_climb_angle,
+            min_slope_slide_angle: *self.min_slope_slide_angle,
+            snap_to_ground: self.snap_to_ground.map(CharacterLength::Absolute),
+            ..Default::default()
+        }
+    }
+}
+
+impl NodeTrait for CharacterController {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.world_bounding_box()
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn on_removed_from_graph(&mut self, graph: &mut Graph) {
+        graph.physics2d.remove_body(self.native.get());
+        self.native.set(RigidBodyHandle::invalid());
+
+        Log::info(format!(
+            "Native character controller was removed for node: {}",
+            self.name()
+        ));
+    }
+
+    fn sync_native(&self, self_handle: Handle<Node>, context: &mut SyncContext) {
+        context
+            .physics2d
+            .sync_to_character_controller_node(context.nodes, self_handle, self);
+    }
+
+    fn sync_transform(&self, new_global_transform: &Matrix4<f32>, context: &mut SyncContext) {
+        if !m4x4_approx_eq(new_global_transform, &self.global_transform()) {
+            context
+                .physics2d
+                .set_character_controller_position(self, new_global_transform);
+        }
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        let parent_transform = context
+            .nodes
+            .try_borrow(self.parent)
+            .map(|p| p.global_transform())
+            .unwrap_or_else(Matrix4::identity);
+        context
+            .physics2d
+            .sync_character_controller_node(self, parent_transform);
+    }
+
+    fn validate(&self, scene: &Scene) -> Result<(), String> {
+        for &child in self.children() {
+            if scene.graph.try_get_of_type::<Collider>(child).is_some() {
+                return Ok(());
+            }
+        }
+
+        Err("The 2D character controller must have a 2D collider as a \
+        direct child node to work correctly!"
+            .to_string())
+    }
+}
+
+/// Allows you to create 2D character controller in declarative manner.
+pub struct CharacterControllerBuilder {
+    base_builder: BaseBuilder,
+    up: Vector2<f32>,
+    offset: f32,
+    slide: bool,
+    autostep: Option<Autostep>,
+    max_slope_climb_angle: f32,
+    min_slope_slide_angle: f32,
+    snap_to_ground: Option<f32>,
+    push_dynamic_bodies: bool,
+    mass: f32,
+    gravity_scale: f32,
+}
+
+impl CharacterControllerBuilder {
+    /// Creates new 2D character controller builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            up: Vector2::y(),
+            offset: 0.01,
+            slide: true,
+            autostep: None,
+            max_slope_climb_angle: FRAC_PI_4,
+            min_slope_slide_angle: FRAC_PI_4,
+            snap_to_ground: Some(0.2),
+            push_dynamic_bodies: true,
+            mass: 1.0,
+            gravity_scale: 1.0,
+        }
+    }
+
+    /// Sets the desired up vector.
+    pub fn with_up(mut self, up: Vector2<f32>) -> Self {
+        self.up = up;
+        self
+    }
+
+    /// Sets the desired gap between the character and its surroundings.
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets whether the character should slide along obstacles or not.
+    pub fn with_slide(mut self, slide: bool) -> Self {
+        self.slide = slide;
+        self
+    }
+
+    /// Sets the desired autostep parameters.
+    pub fn with_autostep(mut self, autostep: Option<Autostep>) -> Self {
+        self.autostep = autostep;
+        self
+    }
+
+    /// Sets the desired maximum angle (in radians) of a slope the character can climb.
+    pub fn with_max_slope_climb_angle(mut self, angle: f32) -> Self {
+        self.max_slope_climb_angle = angle;
+        self
+    }
+
+    /// Sets the desired minimum angle (in radians) of a slope that makes the character slide down.
+    pub fn with_min_slope_slide_angle(mut self, angle: f32) -> Self {
+        self.min_slope_slide_angle = angle;
+        self
+    }
+
+    /// Sets the desired maximum distance to the ground the character will be snapped to.
+    pub fn with_snap_to_ground(mut self, distance: Option<f32>) -> Self {
+        self.snap_to_ground = distance;
+        self
+    }
+
+    /// Sets whether the character should push dynamic rigid bodies or not.
+    pub fn with_push_dynamic_bodies(mut self, push: bool) -> Self {
+        self.push_dynamic_bodies = push;
+        self
+    }
+
+    /// Sets the desired mass of the character.
+    pub fn with_mass(mut self, mass: f32) -> Self {
+        self.mass = mass;
+        self
+    }
+
+    /// Sets the desired gravity scale.
+    pub fn with_gravity_scale(mut self, gravity_scale: f32) -> Self {
+        self.gravity_scale = gravity_scale;
+        self
+    }
+
+    /// Creates 2D CharacterController node but does not add it to the graph.
+    pub fn build_character_controller(self) -> CharacterController {
+        CharacterController {
+            base: self.base_builder.build_base(),
+            up: self.up.into(),
+            offset: self.offset.into(),
+            slide: self.slide.into(),
+            autostep: self.autostep.into(),
+            max_slope_climb_angle: self.max_slope_climb_angle.into(),
+            min_slope_slide_angle: self.min_slope_slide_angle.into(),
+            snap_to_ground: self.snap_to_ground.into(),
+            push_dynamic_bodies: self.push_dynamic_bodies.into(),
+            mass: self.mass.into(),
+            gravity_scale: self.gravity_scale.into(),
+            native: Cell::new(RigidBodyHandle::invalid()),
+            desired_translation: Default::default(),
+            fall_velocity: Default::default(),
+            movement: Default::default(),
+        }
+    }
+
+    /// Creates 2D CharacterController node but does not add it to the graph.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_character_controller())
+    }
+
+    /// Creates 2D CharacterController node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
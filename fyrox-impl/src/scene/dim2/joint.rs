@@ -36,7 +36,8 @@ use crate::{
         base::{Base, BaseBuilder},
         dim2::rigidbody::RigidBody,
         graph::Graph,
-        node::{Node, NodeTrait, SyncContext},
+        joint::JointMotor,
+        node::{Node, NodeTrait, SyncContext, UpdateContext},
         Scene,
     },
 };
@@ -63,6 +64,11 @@ pub struct BallJoint {
     #[reflect(description = "Allowed angles range for the joint (in radians).")]
     #[visit(optional)] // Backward compatibility
     pub limits_angles: Range<f32>,
+
+    /// A motor that drives rotation of the joint.
+    #[reflect(description = "A motor that drives rotation of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub motor: JointMotor,
 }
 
 impl Default for BallJoint {
@@ -70,6 +76,7 @@ impl Default for BallJoint {
         Self {
             limits_enabled: false,
             limits_angles: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
         }
     }
 }
@@ -94,6 +101,11 @@ pub struct PrismaticJoint {
     #[reflect(description = "Allowed linear distance range along local X axis of the joint.")]
     #[visit(optional)] // Backward compatibility
     pub limits: Range<f32>,
+
+    /// A motor that drives translation along local X axis of the joint.
+    #[reflect(description = "A motor that drives translation along local X axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub motor: JointMotor,
 }
 
 impl Default for PrismaticJoint {
@@ -101,6 +113,56 @@ impl Default for PrismaticJoint {
         Self {
             limits_enabled: false,
             limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
+        }
+    }
+}
+
+/// Rope joint limits the maximum distance between two bodies, but allows them to move freely
+/// while the distance is less than the maximum. The real world example is a rope or a chain.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct RopeJoint {
+    /// Maximum distance between the anchors of the attached bodies.
+    #[reflect(
+        description = "Maximum distance between the anchors of the attached bodies.",
+        min_value = 0.0
+    )]
+    pub max_distance: f32,
+}
+
+impl Default for RopeJoint {
+    fn default() -> Self {
+        Self { max_distance: 1.0 }
+    }
+}
+
+/// Spring joint tries to keep the distance between two bodies equal to its rest length, it acts
+/// like a spring that connects the bodies.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct SpringJoint {
+    /// The distance between the anchors of the attached bodies at which the spring applies no
+    /// force.
+    #[reflect(
+        description = "The distance between the anchors of the attached bodies at which the spring applies no force.",
+        min_value = 0.0
+    )]
+    pub rest_length: f32,
+
+    /// Stiffness of the spring.
+    #[reflect(description = "Stiffness of the spring.", min_value = 0.0)]
+    pub stiffness: f32,
+
+    /// Damping of the spring.
+    #[reflect(description = "Damping of the spring.", min_value = 0.0)]
+    pub damping: f32,
+}
+
+impl Default for SpringJoint {
+    fn default() -> Self {
+        Self {
+            rest_length: 1.0,
+            stiffness: 100.0,
+            damping: 1.0,
         }
     }
 }
@@ -114,6 +176,10 @@ pub enum JointParams {
     FixedJoint(FixedJoint),
     /// See [`PrismaticJoint`] for more info.
     PrismaticJoint(PrismaticJoint),
+    /// See [`RopeJoint`] for more info.
+    RopeJoint(RopeJoint),
+    /// See [`SpringJoint`] for more info.
+    SpringJoint(SpringJoint),
 }
 
 uuid_provider!(JointParams = "e1fa2015-3ea3-47bb-8ad3-d408559c9643");
@@ -173,6 +239,18 @@ pub struct Joint {
     #[reflect(setter = "set_contacts_enabled")]
     pub(crate) contacts_enabled: InheritableVariable<bool>,
 
+    #[visit(optional)] // Backward compatibility
+    #[reflect(setter = "set_break_force", min_value = 0.0)]
+    pub(crate) break_force: InheritableVariable<f32>,
+
+    #[visit(optional)] // Backward compatibility
+    #[reflect(setter = "set_break_torque", min_value = 0.0)]
+    pub(crate) break_torque: InheritableVariable<f32>,
+
+    #[visit(optional)]
+    #[reflect(hidden)]
+    pub(crate) broken: bool,
+
     #[visit(optional)]
     #[reflect(hidden)]
     pub(crate) local_frames: RefCell<Option<JointLocalFrames>>,
@@ -191,6 +269,9 @@ impl Default for Joint {
             body2: Default::default(),
             local_frames: Default::default(),
             contacts_enabled: InheritableVariable::new_modified(true),
+            break_force: f32::MAX.into(),
+            break_torque: f32::MAX.into(),
+            broken: false,
             // Do not copy. The copy will have its own native representation.
             native: Cell::new(ImpulseJointHandle::invalid()),
         }
@@ -220,6 +301,9 @@ impl Clone for Joint {
             body2: self.body2.clone(),
             local_frames: self.local_frames.clone(),
             contacts_enabled: self.contacts_enabled.clone(),
+            break_force: self.break_force.clone(),
+            break_torque: self.break_torque.clone(),
+            broken: self.broken,
             native: Cell::new(ImpulseJointHandle::invalid()),
         }
     }
@@ -279,6 +363,42 @@ impl Joint {
     pub fn is_contacts_enabled(&self) -> bool {
         *self.contacts_enabled
     }
+
+    /// Sets the maximum linear force the joint can withstand. When the force applied by the joint
+    /// exceeds this value, the joint breaks (see [`Self::is_broken`]). Default is [`f32::MAX`],
+    /// which means that the joint is unbreakable.
+    pub fn set_break_force(&mut self, force: f32) -> f32 {
+        self.break_force.set_value_and_mark_modified(force)
+    }
+
+    /// Returns the maximum linear force the joint can withstand.
+    pub fn break_force(&self) -> f32 {
+        *self.break_force
+    }
+
+    /// Sets the maximum torque the joint can withstand. When the torque applied by the joint
+    /// exceeds this value, the joint breaks (see [`Self::is_broken`]). Default is [`f32::MAX`],
+    /// which means that the joint is unbreakable.
+    pub fn set_break_torque(&mut self, torque: f32) -> f32 {
+        self.break_torque.set_value_and_mark_modified(torque)
+    }
+
+    /// Returns the maximum torque the joint can withstand.
+    pub fn break_torque(&self) -> f32 {
+        *self.break_torque
+    }
+
+    /// Returns `true` if the joint was broken because of exceeding its break force or torque. Broken
+    /// joint does not affect the attached bodies. Scripts are notified about breaking via
+    /// [`crate::script::ScriptTrait::on_joint_break`].
+    pub fn is_broken(&self) -> bool {
+        self.broken
+    }
+
+    /// Sets whether the joint is broken or not. Could be used to repair a broken joint.
+    pub fn set_broken(&mut self, broken: bool) {
+        self.broken = broken;
+    }
 }
 
 impl NodeTrait for Joint {
@@ -318,6 +438,12 @@ impl NodeTrait for Joint {
         }
     }
 
+    fn update(&mut self, context: &mut UpdateContext) {
+        if let Some(enabled) = context.physics2d.is_joint_enabled(self.native.get()) {
+            self.broken = !enabled;
+        }
+    }
+
     fn validate(&self, scene: &Scene) -> Result<(), String> {
         if let Some(body1) = scene.graph.try_get(self.body1()) {
             if body1.query_component_ref::<RigidBody>().is_none() {
@@ -354,6 +480,8 @@ pub struct JointBuilder {
     body1: Handle<Node>,
     body2: Handle<Node>,
     contacts_enabled: bool,
+    break_force: f32,
+    break_torque: f32,
 }
 
 impl JointBuilder {
@@ -365,6 +493,8 @@ impl JointBuilder {
             body1: Default::default(),
             body2: Default::default(),
             contacts_enabled: true,
+            break_force: f32::MAX,
+            break_torque: f32::MAX,
         }
     }
 
@@ -394,6 +524,18 @@ impl JointBuilder {
         self
     }
 
+    /// Sets the maximum linear force the joint can withstand before breaking.
+    pub fn with_break_force(mut self, force: f32) -> Self {
+        self.break_force = force;
+        self
+    }
+
+    /// Sets the maximum torque the joint can withstand before breaking.
+    pub fn with_break_torque(mut self, torque: f32) -> Self {
+        self.break_torque = torque;
+        self
+    }
+
     /// Creates new Joint node, but does not add it to the graph.
     pub fn build_joint(self) -> Joint {
         Joint {
@@ -403,6 +545,9 @@ impl JointBuilder {
             body2: self.body2.into(),
             local_frames: Default::default(),
             contacts_enabled: self.contacts_enabled.into(),
+            break_force: self.break_force.into(),
+            break_torque: self.break_torque.into(),
+            broken: false,
             native: Cell::new(ImpulseJointHandle::invalid()),
         }
     }
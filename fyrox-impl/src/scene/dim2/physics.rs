@@ -49,27 +49,32 @@ use crate::{
         },
         graph::{
             isometric_global_transform,
-            physics::{FeatureId, IntegrationParameters, PhysicsPerformanceStatistics},
+            physics::{
+                CollisionEvent, CollisionEventKind, FeatureId, FixedTimestep,
+                IntegrationParameters, PhysicsPerformanceStatistics,
+            },
             Graph, NodePool,
         },
+        joint::{JointMotor, JointMotorModel},
         node::{Node, NodeTrait},
         tilemap::{tileset::TileCollider, TileMap},
     },
 };
+use fxhash::FxHashMap;
 pub use rapier2d::geometry::shape::*;
 use rapier2d::{
     dynamics::{
         CCDSolver, GenericJoint, GenericJointBuilder, ImpulseJointHandle, ImpulseJointSet,
-        IslandManager, JointAxesMask, JointAxis, MultibodyJointHandle, MultibodyJointSet,
-        RigidBody, RigidBodyActivation, RigidBodyBuilder, RigidBodyHandle, RigidBodySet,
-        RigidBodyType,
+        IslandManager, JointAxesMask, JointAxis, MotorModel, MultibodyJointHandle,
+        MultibodyJointSet, RigidBody, RigidBodyActivation, RigidBodyBuilder, RigidBodyHandle,
+        RigidBodySet, RigidBodyType,
     },
     geometry::{
         Collider, ColliderBuilder, ColliderHandle, ColliderSet, Cuboid, DefaultBroadPhase,
         InteractionGroups, NarrowPhase, Ray, SharedShape,
     },
     parry::query::ShapeCastOptions,
-    pipeline::{DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryPipeline},
+    pipeline::{ActiveEvents, DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryPipeline},
 };
 use std::{
     cell::RefCell,
@@ -278,6 +283,50 @@ pub struct IntersectionPair {
     pub has_any_active_contact: bool,
 }
 
+// Collects collision events produced by the physics pipeline.
+#[derive(Default)]
+struct CollisionEventCollector {
+    events: Mutex<Vec<CollisionEvent>>,
+}
+
+impl EventHandler for CollisionEventCollector {
+    fn handle_collision_event(
+        &self,
+        _bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        event: rapier2d::geometry::CollisionEvent,
+        _contact_pair: Option<&rapier2d::geometry::ContactPair>,
+    ) {
+        let owner = |handle| {
+            colliders
+                .get(handle)
+                .map(|c| Handle::decode_from_u128(c.user_data))
+                .unwrap_or_default()
+        };
+
+        self.events.lock().push(CollisionEvent {
+            kind: if event.started() {
+                CollisionEventKind::Started
+            } else {
+                CollisionEventKind::Stopped
+            },
+            collider1: owner(event.collider1()),
+            collider2: owner(event.collider2()),
+            sensor: event.sensor(),
+        });
+    }
+
+    fn handle_contact_force_event(
+        &self,
+        _dt: f32,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        _contact_pair: &rapier2d::geometry::ContactPair,
+        _total_force_magnitude: f32,
+    ) {
+    }
+}
+
 pub(super) struct Container<S, A>
 where
     A: Hash + Eq + Clone,
@@ -286,6 +335,27 @@ where
     map: BiDirHashMap<A, Handle<Node>>,
 }
 
+fn set_joint_motor(joint: &mut GenericJoint, axis: JointAxis, motor: &JointMotor) {
+    if motor.enabled {
+        joint
+            .set_motor(
+                axis,
+                motor.target_position,
+                motor.target_velocity,
+                motor.stiffness,
+                motor.damping,
+            )
+            .set_motor_max_force(axis, motor.max_force)
+            .set_motor_model(
+                axis,
+                match motor.model {
+                    JointMotorModel::AccelerationBased => MotorModel::AccelerationBased,
+                    JointMotorModel::ForceBased => MotorModel::ForceBased,
+                },
+            );
+    }
+}
+
 fn convert_joint_params(
     params: scene::dim2::joint::JointParams,
     local_frame1: Isometry2<f32>,
@@ -295,6 +365,7 @@ fn convert_joint_params(
         JointParams::BallJoint(_) => JointAxesMask::LOCKED_REVOLUTE_AXES,
         JointParams::FixedJoint(_) => JointAxesMask::LOCKED_FIXED_AXES,
         JointParams::PrismaticJoint(_) => JointAxesMask::LOCKED_PRISMATIC_AXES,
+        JointParams::RopeJoint(_) | JointParams::SpringJoint(_) => JointAxesMask::empty(),
     };
 
     let mut joint = GenericJointBuilder::new(locked_axis)
@@ -310,12 +381,24 @@ fn convert_joint_params(
                     [v.limits_angles.start, v.limits_angles.end],
                 );
             }
+            set_joint_motor(&mut joint, JointAxis::AngX, &v.motor);
         }
         scene::dim2::joint::JointParams::FixedJoint(_) => {}
         scene::dim2::joint::JointParams::PrismaticJoint(v) => {
             if v.limits_enabled {
                 joint.set_limits(JointAxis::LinX, [v.limits.start, v.limits.end]);
             }
+            set_joint_motor(&mut joint, JointAxis::LinX, &v.motor);
+        }
+        scene::dim2::joint::JointParams::RopeJoint(v) => {
+            joint.coupled_axes = JointAxesMask::LIN_AXES;
+            joint.set_limits(JointAxis::LinX, [0.0, v.max_distance]);
+        }
+        scene::dim2::joint::JointParams::SpringJoint(v) => {
+            joint.coupled_axes = JointAxesMask::LIN_AXES;
+            joint
+                .set_motor_position(JointAxis::LinX, v.rest_length, v.stiffness, v.damping)
+                .set_motor_model(JointAxis::LinX, MotorModel::ForceBased);
         }
     }
 
@@ -449,6 +532,10 @@ pub struct PhysicsWorld {
     /// A set of parameters that define behavior of every rigid body.
     pub integration_parameters: InheritableVariable<IntegrationParameters>,
 
+    /// Parameters of fixed time step simulation. See [`FixedTimestep`] docs for more info.
+    #[visit(optional)]
+    pub fixed_timestep: InheritableVariable<FixedTimestep>,
+
     /// Current gravity vector. Default is (0.0, -9.81)
     pub gravity: InheritableVariable<Vector2<f32>>,
 
@@ -497,13 +584,60 @@ pub struct PhysicsWorld {
     // Event handler collects info about contacts and proximity events.
     #[visit(skip)]
     #[reflect(hidden)]
-    event_handler: Box<dyn EventHandler>,
+    event_handler: CollisionEventCollector,
+    // Collision events of the last update.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    collision_events: Vec<CollisionEvent>,
+    // Break force and torque of joints that could be broken.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    breakable_joints: FxHashMap<ImpulseJointHandle, (f32, f32)>,
+    // Joints that were broken during the last update.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    broken_joints: Vec<Handle<Node>>,
+    // Time that was not simulated yet in fixed time step mode.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    accumulator: f32,
+    // Interpolation factor between previous and current poses of rigid bodies.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    interpolation_factor: f32,
+    // Poses of rigid bodies before the last simulation step.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    previous_poses: FxHashMap<RigidBodyHandle, Isometry2<f32>>,
+    // Interpolated poses of rigid bodies that were written to the scene nodes.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    interpolated_poses: FxHashMap<RigidBodyHandle, Isometry2<f32>>,
     #[visit(skip)]
     #[reflect(hidden)]
     query: RefCell<QueryPipeline>,
     #[visit(skip)]
     #[reflect(hidden)]
     debug_render_pipeline: Mutex<DebugRenderPipeline>,
+    // Character controllers, that are moved by the simulation steps.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    characters: FxHashMap<RigidBodyHandle, NativeCharacter>,
+}
+
+// A character controller, that is moved by the simulation steps. It is synced with its scene node
+// once per frame.
+struct NativeCharacter {
+    controller: rapier2d::control::KinematicCharacterController,
+    collider: ColliderHandle,
+    // Desired translation of the frame, it is spread over the simulation steps of the frame.
+    desired_translation: Vector2<f32>,
+    gravity_scale: f32,
+    mass: f32,
+    push_dynamic_bodies: bool,
+    fall_velocity: Vector2<f32>,
+    // Results of every step of the frame.
+    movement: dim2::character::CharacterMovement,
 }
 
 fn isometry_from_global_transform(transform: &Matrix4<f32>) -> Isometry2<f32> {
@@ -551,6 +685,17 @@ pub struct QueryFilter<'a> {
     pub predicate: Option<&'a dyn Fn(Handle<Node>, &collider::Collider) -> bool>,
 }
 
+/// The result of a point projection (see [`PhysicsWorld::project_point`]).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PointProjection {
+    /// A handle of the collider the point was projected on.
+    pub collider: Handle<Node>,
+    /// The projection of the point in world coordinates.
+    pub point: Point2<f32>,
+    /// `true` if the point is inside the collider.
+    pub is_inside: bool,
+}
+
 /// The result of a time-of-impact (TOI) computation.
 #[derive(Copy, Clone, Debug)]
 pub struct TOI {
@@ -584,6 +729,7 @@ impl PhysicsWorld {
             pipeline: PhysicsPipeline::new(),
             gravity: Vector2::new(0.0, -9.81).into(),
             integration_parameters: IntegrationParameters::default().into(),
+            fixed_timestep: Default::default(),
             broad_phase: DefaultBroadPhase::new(),
             narrow_phase: NarrowPhase::new(),
             ccd_solver: CCDSolver::new(),
@@ -598,7 +744,15 @@ impl PhysicsWorld {
                 set: MultibodyJointSet::new(),
                 map: Default::default(),
             },
-            event_handler: Box::new(()),
+            event_handler: Default::default(),
+            collision_events: Default::default(),
+            breakable_joints: Default::default(),
+            broken_joints: Default::default(),
+            accumulator: 0.0,
+            interpolation_factor: 1.0,
+            previous_poses: Default::default(),
+            interpolated_poses: Default::default(),
+            characters: Default::default(),
             query: RefCell::new(Default::default()),
             performance_statistics: Default::default(),
             debug_render_pipeline: Default::default(),
@@ -608,66 +762,220 @@ impl PhysicsWorld {
     pub(crate) fn update(&mut self, dt: f32) {
         let time = instant::Instant::now();
 
+        self.collision_events.clear();
+        self.broken_joints.clear();
+
         if *self.enabled {
-            let integration_parameters = rapier2d::dynamics::IntegrationParameters {
-                dt: self.integration_parameters.dt.unwrap_or(dt),
-                min_ccd_dt: self.integration_parameters.min_ccd_dt,
-                contact_damping_ratio: self.integration_parameters.contact_damping_ratio,
-                contact_natural_frequency: self.integration_parameters.contact_natural_frequency,
-                joint_natural_frequency: self.integration_parameters.joint_natural_frequency,
-                joint_damping_ratio: self.integration_parameters.joint_damping_ratio,
-                warmstart_coefficient: self.integration_parameters.warmstart_coefficient,
-                length_unit: self.integration_parameters.length_unit,
-                normalized_allowed_linear_error: self.integration_parameters.allowed_linear_error,
-                normalized_max_corrective_velocity: self
-                    .integration_parameters
-                    .normalized_max_corrective_velocity,
-                normalized_prediction_distance: self.integration_parameters.prediction_distance,
-                num_solver_iterations: NonZeroUsize::new(
-                    self.integration_parameters.num_solver_iterations,
-                )
-                .unwrap(),
-                num_additional_friction_iterations: self
-                    .integration_parameters
-                    .num_additional_friction_iterations,
-                num_internal_pgs_iterations: self
-                    .integration_parameters
-                    .num_internal_pgs_iterations,
-                num_internal_stabilization_iterations: self
-                    .integration_parameters
-                    .num_internal_stabilization_iterations,
-                min_island_size: self.integration_parameters.min_island_size as usize,
-                max_ccd_substeps: self.integration_parameters.max_ccd_substeps as usize,
-            };
+            for character in self.characters.values_mut() {
+                character.movement = Default::default();
+            }
 
-            self.pipeline.step(
-                &self.gravity,
-                &integration_parameters,
-                &mut self.islands,
-                &mut self.broad_phase,
-                &mut self.narrow_phase,
-                &mut self.bodies,
-                &mut self.colliders,
-                &mut self.joints.set,
-                &mut self.multibody_joints.set,
-                &mut self.ccd_solver,
-                // In Rapier 0.17 passing query pipeline here sometimes causing panic in numeric overflow,
-                // so we keep updating it manually.
-                None,
-                &(),
-                &*self.event_handler,
-            );
+            let fixed_timestep = *self.fixed_timestep;
+            if fixed_timestep.enabled {
+                let step = fixed_timestep.step.max(0.001);
+                // Desired translations of characters are made for the whole frame, every step
+                // applies its part of them.
+                let translation_scale = if dt > 0.0 { step / dt } else { 0.0 };
+                self.accumulator += dt;
+                // Tolerate rounding errors of the accumulation, otherwise a frame that is a
+                // multiple of the step could occasionally perform one step less.
+                let tolerance = step * 1.0e-3;
+                let mut steps = 0;
+                while self.accumulator + tolerance >= step {
+                    if steps >= fixed_timestep.max_steps_per_update {
+                        // Drop the time that cannot be simulated in this update.
+                        self.accumulator %= step;
+                        break;
+                    }
+                    self.step(step, translation_scale, fixed_timestep.interpolation);
+                    self.accumulator = (self.accumulator - step).max(0.0);
+                    steps += 1;
+                }
+                self.interpolation_factor = if fixed_timestep.interpolation {
+                    (self.accumulator / step).clamp(0.0, 1.0)
+                } else {
+                    1.0
+                };
+            } else {
+                self.step(self.integration_parameters.dt.unwrap_or(dt), 1.0, false);
+                self.interpolation_factor = 1.0;
+            }
         }
 
         self.performance_statistics.step_time += instant::Instant::now() - time;
     }
 
+    // Performs a single simulation step.
+    fn step(&mut self, dt: f32, translation_scale: f32, remember_poses: bool) {
+        if remember_poses {
+            self.previous_poses.clear();
+            let characters = &self.characters;
+            self.previous_poses.extend(
+                self.bodies
+                    .iter()
+                    .filter(|(handle, body)| {
+                        body.body_type() == RigidBodyType::Dynamic
+                            || characters.contains_key(handle)
+                    })
+                    .map(|(handle, body)| (handle, *body.position())),
+            );
+        } else {
+            self.previous_poses.clear();
+        }
+
+        self.move_characters(dt, translation_scale);
+
+        let integration_parameters = rapier2d::dynamics::IntegrationParameters {
+            dt,
+            min_ccd_dt: self.integration_parameters.min_ccd_dt,
+            contact_damping_ratio: self.integration_parameters.contact_damping_ratio,
+            contact_natural_frequency: self.integration_parameters.contact_natural_frequency,
+            joint_natural_frequency: self.integration_parameters.joint_natural_frequency,
+            joint_damping_ratio: self.integration_parameters.joint_damping_ratio,
+            warmstart_coefficient: self.integration_parameters.warmstart_coefficient,
+            length_unit: self.integration_parameters.length_unit,
+            normalized_allowed_linear_error: self.integration_parameters.allowed_linear_error,
+            normalized_max_corrective_velocity: self
+                .integration_parameters
+                .normalized_max_corrective_velocity,
+            normalized_prediction_distance: self.integration_parameters.prediction_distance,
+            num_solver_iterations: NonZeroUsize::new(
+                self.integration_parameters.num_solver_iterations,
+            )
+            .unwrap(),
+            num_additional_friction_iterations: self
+                .integration_parameters
+                .num_additional_friction_iterations,
+            num_internal_pgs_iterations: self.integration_parameters.num_internal_pgs_iterations,
+            num_internal_stabilization_iterations: self
+                .integration_parameters
+                .num_internal_stabilization_iterations,
+            min_island_size: self.integration_parameters.min_island_size as usize,
+            max_ccd_substeps: self.integration_parameters.max_ccd_substeps as usize,
+        };
+
+        self.pipeline.step(
+            &self.gravity,
+            &integration_parameters,
+            &mut self.islands,
+            &mut self.broad_phase,
+            &mut self.narrow_phase,
+            &mut self.bodies,
+            &mut self.colliders,
+            &mut self.joints.set,
+            &mut self.multibody_joints.set,
+            &mut self.ccd_solver,
+            // In Rapier 0.17 passing query pipeline here sometimes causing panic in numeric overflow,
+            // so we keep updating it manually.
+            None,
+            &(),
+            &self.event_handler,
+        );
+
+        self.collision_events
+            .append(&mut self.event_handler.events.lock());
+
+        self.break_joints(integration_parameters.dt);
+    }
+
+    // Moves the character controllers by the given part of their desired translations. The bodies
+    // of the characters are moved by the simulation step that follows, so the dynamic bodies react
+    // on the movement in the same step.
+    fn move_characters(&mut self, dt: f32, translation_scale: f32) {
+        if self.characters.is_empty() {
+            return;
+        }
+
+        let mut query = self.query.borrow_mut();
+        query.update(&self.colliders);
+
+        for (&body_handle, character) in self.characters.iter_mut() {
+            let (Some(body), Some(collider)) = (
+                self.bodies.get(body_handle),
+                self.colliders.get(character.collider),
+            ) else {
+                continue;
+            };
+
+            let position = *body.position();
+            let shape = collider.shared_shape().clone();
+            let shape_position = position
+                * collider
+                    .position_wrt_parent()
+                    .cloned()
+                    .unwrap_or_else(Isometry2::identity);
+
+            character.fall_velocity += *self.gravity * character.gravity_scale * dt;
+            let translation =
+                character.desired_translation * translation_scale + character.fall_velocity * dt;
+
+            let filter = rapier2d::pipeline::QueryFilter::new()
+                .exclude_rigid_body(body_handle)
+                .exclude_sensors();
+
+            let mut collisions = Vec::new();
+            let movement = character.controller.move_shape(
+                dt,
+                &self.bodies,
+                &self.colliders,
+                &query,
+                shape.as_ref(),
+                &shape_position,
+                translation,
+                filter,
+                |collision| collisions.push(collision),
+            );
+
+            if character.push_dynamic_bodies {
+                character.controller.solve_character_collision_impulses(
+                    dt,
+                    &mut self.bodies,
+                    &self.colliders,
+                    &query,
+                    shape.as_ref(),
+                    character.mass,
+                    &collisions,
+                    filter,
+                );
+            }
+
+            if movement.grounded {
+                character.fall_velocity = Vector2::default();
+            }
+
+            let result = &mut character.movement;
+            result.translation += movement.translation;
+            result.grounded = movement.grounded;
+            result.sliding_down_slope = movement.is_sliding_down_slope;
+            result
+                .collisions
+                .extend(collisions.iter().filter_map(|collision| {
+                    Some(dim2::character::CharacterCollision {
+                        collider: Handle::decode_from_u128(
+                            self.colliders.get(collision.handle)?.user_data,
+                        ),
+                        position: collision.hit.witness1.coords,
+                        normal: *collision.hit.normal1,
+                        translation_applied: collision.translation_applied,
+                        translation_remaining: collision.translation_remaining,
+                    })
+                }));
+
+            if let Some(body) = self.bodies.get_mut(body_handle) {
+                let mut next_position = position;
+                next_position.translation.vector += movement.translation;
+                body.set_next_kinematic_position(next_position);
+            }
+        }
+    }
+
     pub(crate) fn add_body(&mut self, owner: Handle<Node>, mut body: RigidBody) -> RigidBodyHandle {
         body.user_data = owner.encode_to_u128();
         self.bodies.insert(body)
     }
 
     pub(crate) fn remove_body(&mut self, handle: RigidBodyHandle) {
+        self.characters.remove(&handle);
         self.bodies.remove(
             handle,
             &mut self.islands,
@@ -711,6 +1019,51 @@ impl PhysicsWorld {
         if self.joints.set.remove(handle, false).is_some() {
             assert!(self.joints.map.remove_by_key(&handle).is_some());
         }
+        self.breakable_joints.remove(&handle);
+    }
+
+    fn set_joint_break_limits(&mut self, handle: ImpulseJointHandle, force: f32, torque: f32) {
+        if force < f32::MAX || torque < f32::MAX {
+            self.breakable_joints.insert(handle, (force, torque));
+        } else {
+            self.breakable_joints.remove(&handle);
+        }
+    }
+
+    // Disables every joint, that applied a force or a torque larger than its break limits on the
+    // last step.
+    fn break_joints(&mut self, dt: f32) {
+        if dt <= 0.0 {
+            return;
+        }
+
+        for (handle, (max_force, max_torque)) in self.breakable_joints.iter() {
+            if let Some(joint) = self.joints.set.get_mut(*handle) {
+                if !joint.data.is_enabled() {
+                    continue;
+                }
+
+                let force = joint.impulses.fixed_rows::<2>(0).norm() / dt;
+                let torque = joint.impulses[2].abs() / dt;
+                if force > *max_force || torque > *max_torque {
+                    joint.data.set_enabled(false);
+
+                    if let Some(owner) = self.joints.map.value_of(handle) {
+                        self.broken_joints.push(*owner);
+                    }
+                }
+            }
+        }
+    }
+
+    pub(crate) fn is_joint_enabled(&self, handle: ImpulseJointHandle) -> Option<bool> {
+        self.joints.set.get(handle).map(|j| j.data.is_enabled())
+    }
+
+    /// Returns a list of joints (their handles) that were broken during the last update of the
+    /// physics world, because of exceeding their break force or torque.
+    pub fn broken_joints(&self) -> &[Handle<Node>] {
+        &self.broken_joints
     }
 
     /// Draws physics world. Very useful for debugging, it allows you to see where are
@@ -726,6 +1079,12 @@ impl PhysicsWorld {
         );
     }
 
+    /// Returns a list of collision events (contacts and intersections that started or stopped)
+    /// produced by the last update of the physics world.
+    pub fn collision_events(&self) -> &[CollisionEvent] {
+        &self.collision_events
+    }
+
     /// Casts a ray with given options.
     pub fn cast_ray<S: QueryResultsStorage>(&self, opts: RayCastOptions, query_buffer: &mut S) {
         let time = instant::Instant::now();
@@ -815,6 +1174,176 @@ impl PhysicsWorld {
         stop_at_penetration: bool,
         filter: QueryFilter,
     ) -> Option<(Handle<Node>, TOI)> {
+        let query = self.query.borrow_mut();
+
+        let opts = ShapeCastOptions {
+            max_time_of_impact: max_toi,
+            target_distance: 0.0,
+            stop_at_penetration,
+            compute_impact_geometry_on_penetration: true,
+        };
+
+        self.with_native_filter(graph, filter, |filter| {
+            query.cast_shape(
+                &self.bodies,
+                &self.colliders,
+                shape_pos,
+                shape_vel,
+                shape,
+                opts,
+                filter,
+            )
+        })
+        .map(|(handle, toi)| {
+            (
+                Handle::decode_from_u128(self.colliders.get(handle).unwrap().user_data),
+                TOI {
+                    toi: toi.time_of_impact,
+                    witness1: toi.witness1,
+                    witness2: toi.witness2,
+                    normal1: toi.normal1,
+                    normal2: toi.normal2,
+                    status: toi.status.into(),
+                },
+            )
+        })
+    }
+
+    /// Finds all colliders that intersect the given shape and calls the `callback` for each of them.
+    /// The callback must return `false` to stop the search.
+    ///
+    /// # Parameters
+    ///
+    /// * `graph` - a reference to the scene graph.
+    /// * `shape` - the shape to test.
+    /// * `shape_pos` - the position of the shape in world coordinates.
+    /// * `filter`: set of rules used to determine which collider is taken into account by this scene
+    ///   query.
+    /// * `callback` - a function that will be called for every intersecting collider. It is called
+    ///   after the search is done, so it is allowed to perform other scene queries.
+    pub fn intersections_with_shape(
+        &self,
+        graph: &Graph,
+        shape: &dyn Shape,
+        shape_pos: &Isometry2<f32>,
+        filter: QueryFilter,
+        mut callback: impl FnMut(Handle<Node>) -> bool,
+    ) {
+        let mut intersections = Vec::new();
+        {
+            let mut query = self.query.borrow_mut();
+            query.update(&self.colliders);
+
+            self.with_native_filter(graph, filter, |filter| {
+                query.intersections_with_shape(
+                    &self.bodies,
+                    &self.colliders,
+                    shape_pos,
+                    shape,
+                    filter,
+                    |handle| {
+                        intersections.push(Handle::decode_from_u128(
+                            self.colliders.get(handle).unwrap().user_data,
+                        ));
+                        true
+                    },
+                )
+            });
+        }
+
+        // The query pipeline is released at this point, so the callback can do other queries.
+        for collider in intersections {
+            if !callback(collider) {
+                break;
+            }
+        }
+    }
+
+    /// Finds all colliders that contain the given point and calls the `callback` for each of them.
+    /// The callback must return `false` to stop the search.
+    ///
+    /// # Parameters
+    ///
+    /// * `graph` - a reference to the scene graph.
+    /// * `point` - the point to test in world coordinates.
+    /// * `filter`: set of rules used to determine which collider is taken into account by this scene
+    ///   query.
+    /// * `callback` - a function that will be called for every collider that contains the point. It is called
+    ///   after the search is done, so it is allowed to perform other scene queries.
+    pub fn intersections_with_point(
+        &self,
+        graph: &Graph,
+        point: &Point2<f32>,
+        filter: QueryFilter,
+        mut callback: impl FnMut(Handle<Node>) -> bool,
+    ) {
+        let mut intersections = Vec::new();
+        {
+            let mut query = self.query.borrow_mut();
+            query.update(&self.colliders);
+
+            self.with_native_filter(graph, filter, |filter| {
+                query.intersections_with_point(
+                    &self.bodies,
+                    &self.colliders,
+                    point,
+                    filter,
+                    |handle| {
+                        intersections.push(Handle::decode_from_u128(
+                            self.colliders.get(handle).unwrap().user_data,
+                        ));
+                        true
+                    },
+                )
+            });
+        }
+
+        // The query pipeline is released at this point, so the callback can do other queries.
+        for collider in intersections {
+            if !callback(collider) {
+                break;
+            }
+        }
+    }
+
+    /// Finds the projection of the given point on the closest collider.
+    ///
+    /// # Parameters
+    ///
+    /// * `graph` - a reference to the scene graph.
+    /// * `point` - the point to project in world coordinates.
+    /// * `solid` - if `true`, a point inside of a collider will be projected on itself (and the
+    ///   projection will be marked as inside). Otherwise the point will be projected on the boundary
+    ///   of the collider.
+    /// * `filter`: set of rules used to determine which collider is taken into account by this scene
+    ///   query.
+    pub fn project_point(
+        &self,
+        graph: &Graph,
+        point: &Point2<f32>,
+        solid: bool,
+        filter: QueryFilter,
+    ) -> Option<PointProjection> {
+        let mut query = self.query.borrow_mut();
+        query.update(&self.colliders);
+
+        self.with_native_filter(graph, filter, |filter| {
+            query.project_point(&self.bodies, &self.colliders, point, solid, filter)
+        })
+        .map(|(handle, projection)| PointProjection {
+            collider: Handle::decode_from_u128(self.colliders.get(handle).unwrap().user_data),
+            point: projection.point,
+            is_inside: projection.is_inside,
+        })
+    }
+
+    // Converts the given query filter to the native one and passes it to the given function.
+    fn with_native_filter<R>(
+        &self,
+        graph: &Graph,
+        filter: QueryFilter,
+        func: impl FnOnce(rapier2d::pipeline::QueryFilter) -> R,
+    ) -> R {
         let predicate = |handle: ColliderHandle, _: &Collider| -> bool {
             if let Some(pred) = filter.predicate {
                 let h = Handle::decode_from_u128(self.colliders.get(handle).unwrap().user_data);
@@ -827,7 +1356,7 @@ impl PhysicsWorld {
             }
         };
 
-        let filter = rapier2d::pipeline::QueryFilter {
+        func(rapier2d::pipeline::QueryFilter {
             flags: rapier2d::pipeline::QueryFilterFlags::from_bits(filter.flags.bits()).unwrap(),
             groups: filter.groups.map(|g| {
                 InteractionGroups::new(u32_to_group(g.memberships.0), u32_to_group(g.filter.0))
@@ -838,45 +1367,12 @@ impl PhysicsWorld {
                 .and_then(|n| n.component_ref::<dim2::collider::Collider>())
                 .map(|c| c.native.get()),
             exclude_rigid_body: filter
-                .exclude_collider
+                .exclude_rigid_body
                 .and_then(|h| graph.try_get(h))
                 .and_then(|n| n.component_ref::<dim2::rigidbody::RigidBody>())
                 .map(|c| c.native.get()),
             predicate: Some(&predicate),
-        };
-
-        let query = self.query.borrow_mut();
-
-        let opts = ShapeCastOptions {
-            max_time_of_impact: max_toi,
-            target_distance: 0.0,
-            stop_at_penetration,
-            compute_impact_geometry_on_penetration: true,
-        };
-
-        query
-            .cast_shape(
-                &self.bodies,
-                &self.colliders,
-                shape_pos,
-                shape_vel,
-                shape,
-                opts,
-                filter,
-            )
-            .map(|(handle, toi)| {
-                (
-                    Handle::decode_from_u128(self.colliders.get(handle).unwrap().user_data),
-                    TOI {
-                        toi: toi.time_of_impact,
-                        witness1: toi.witness1,
-                        witness2: toi.witness2,
-                        normal1: toi.normal1,
-                        normal2: toi.normal2,
-                        status: toi.status.into(),
-                    },
-                )
-            })
+        })
     }
 
     pub(crate) fn set_rigid_body_position(
@@ -884,9 +1380,23 @@ impl PhysicsWorld {
         rigid_body: &scene::dim2::rigidbody::RigidBody,
         new_global_transform: &Matrix4<f32>,
     ) {
-        if let Some(native) = self.bodies.get_mut(rigid_body.native.get()) {
+        let handle = rigid_body.native.get();
+        let new_position = isometry_from_global_transform(new_global_transform);
+
+        // Ignore the changes made by interpolation of the node transform.
+        if self.interpolated_poses.get(&handle).is_some_and(|pose| {
+            (pose.translation.vector - new_position.translation.vector).norm() < 1.0e-3
+                && pose.rotation.angle_to(&new_position.rotation) < 1.0e-3
+        }) {
+            return;
+        }
+
+        self.previous_poses.remove(&handle);
+        self.interpolated_poses.remove(&handle);
+
+        if let Some(native) = self.bodies.get_mut(handle) {
             native.set_position(
-                isometry_from_global_transform(new_global_transform),
+                new_position,
                 // Do not wake up body, it is too expensive and must be done **only** by explicit
                 // `wake_up` call!
                 false,
@@ -900,12 +1410,26 @@ impl PhysicsWorld {
         parent_transform: Matrix4<f32>,
     ) {
         if *self.enabled {
-            if let Some(native) = self.bodies.get(rigid_body.native.get()) {
+            let handle = rigid_body.native.get();
+            if let Some(native) = self.bodies.get(handle) {
                 if native.body_type() == RigidBodyType::Dynamic {
+                    let position = match self.previous_poses.get(&handle) {
+                        Some(previous) if self.interpolation_factor < 1.0 => {
+                            let position =
+                                previous.lerp_slerp(native.position(), self.interpolation_factor);
+                            self.interpolated_poses.insert(handle, position);
+                            position
+                        }
+                        _ => {
+                            self.interpolated_poses.remove(&handle);
+                            *native.position()
+                        }
+                    };
+
                     let local_transform: Matrix4<f32> = parent_transform
                         .try_inverse()
                         .unwrap_or_else(Matrix4::identity)
-                        * isometry2_to_mat4(native.position());
+                        * isometry2_to_mat4(&position);
 
                     let new_local_rotation = UnitQuaternion::from_matrix_eps(
                         &local_transform.basis(),
@@ -1082,6 +1606,141 @@ impl PhysicsWorld {
         }
     }
 
+    pub(crate) fn set_character_controller_position(
+        &mut self,
+        controller: &dim2::character::CharacterController,
+        new_global_transform: &Matrix4<f32>,
+    ) {
+        let handle = controller.native.get();
+        let new_position = isometry_from_global_transform(new_global_transform);
+
+        // Ignore the changes made by interpolation of the node transform.
+        if self.interpolated_poses.get(&handle).is_some_and(|pose| {
+            (pose.translation.vector - new_position.translation.vector).norm() < 1.0e-3
+                && pose.rotation.angle_to(&new_position.rotation) < 1.0e-3
+        }) {
+            return;
+        }
+
+        self.previous_poses.remove(&handle);
+        self.interpolated_poses.remove(&handle);
+
+        if let Some(native) = self.bodies.get_mut(handle) {
+            native.set_position(new_position, false);
+        }
+    }
+
+    /// Writes the results of the movement of the given character controller, that was made by the
+    /// simulation steps of the last update, to its scene node.
+    pub(crate) fn sync_character_controller_node(
+        &mut self,
+        controller: &mut dim2::character::CharacterController,
+        parent_transform: Matrix4<f32>,
+    ) {
+        // Desired translation is consumed by the steps of the frame.
+        controller.desired_translation = Vector2::default();
+
+        if !*self.enabled {
+            return;
+        }
+
+        let handle = controller.native.get();
+        let (Some(character), Some(body)) = (self.characters.get(&handle), self.bodies.get(handle))
+        else {
+            return;
+        };
+
+        controller.fall_velocity = character.fall_velocity;
+        controller.movement = character.movement.clone();
+
+        let position = match self.previous_poses.get(&handle) {
+            Some(previous) if self.interpolation_factor < 1.0 => {
+                let position = previous.lerp_slerp(body.position(), self.interpolation_factor);
+                self.interpolated_poses.insert(handle, position);
+                position
+            }
+            _ => {
+                self.interpolated_poses.remove(&handle);
+                *body.position()
+            }
+        };
+
+        let local_transform = parent_transform
+            .try_inverse()
+            .unwrap_or_else(Matrix4::identity)
+            * isometry2_to_mat4(&position);
+        let new_local_position = Vector3::new(local_transform[12], local_transform[13], 0.0);
+        if **controller.local_transform().position() != new_local_position {
+            controller
+                .local_transform_mut()
+                .set_position(new_local_position);
+        }
+    }
+
+    pub(crate) fn sync_to_character_controller_node(
+        &mut self,
+        nodes: &NodePool,
+        handle: Handle<Node>,
+        controller: &dim2::character::CharacterController,
+    ) {
+        if !controller.is_globally_enabled() {
+            self.remove_body(controller.native.get());
+            controller.native.set(Default::default());
+            return;
+        }
+
+        if controller.native.get() == RigidBodyHandle::invalid() {
+            let body = RigidBodyBuilder::kinematic_position_based()
+                .position(isometry_from_global_transform(
+                    &controller.global_transform(),
+                ))
+                .build();
+
+            controller.native.set(self.add_body(handle, body));
+
+            Log::writeln(
+                MessageKind::Information,
+                format!(
+                    "Native character controller was created for node {}",
+                    controller.name()
+                ),
+            );
+        }
+
+        // The first collider is used as the shape of the character.
+        let body = controller.native.get();
+        let Some(collider) = controller.children().iter().find_map(|child| {
+            nodes
+                .try_borrow(*child)
+                .and_then(|n| n.cast::<dim2::collider::Collider>())
+                .map(|c| c.native.get())
+                .filter(|collider| self.colliders.get(*collider).is_some())
+        }) else {
+            self.characters.remove(&body);
+            return;
+        };
+
+        let character = self
+            .characters
+            .entry(body)
+            .or_insert_with(|| NativeCharacter {
+                controller: Default::default(),
+                collider,
+                desired_translation: Default::default(),
+                gravity_scale: 1.0,
+                mass: 0.0,
+                push_dynamic_bodies: false,
+                fall_velocity: controller.fall_velocity,
+                movement: Default::default(),
+            });
+        character.controller = controller.native_controller();
+        character.collider = collider;
+        character.desired_translation = controller.desired_translation;
+        character.gravity_scale = controller.gravity_scale();
+        character.mass = controller.mass();
+        character.push_dynamic_bodies = controller.is_push_dynamic_bodies();
+    }
+
     pub(crate) fn sync_to_collider_node(
         &mut self,
         nodes: &NodePool,
@@ -1163,12 +1822,18 @@ impl PhysicsWorld {
                     }
                 }
             }
-        } else if let Some(parent_body) = nodes
-            .try_borrow(collider_node.parent())
-            .and_then(|n| n.cast::<dim2::rigidbody::RigidBody>())
+        } else if let Some(rigid_body_native) =
+            nodes.try_borrow(collider_node.parent()).and_then(|n| {
+                // Character controllers have their own native rigid bodies too.
+                n.cast::<dim2::rigidbody::RigidBody>()
+                    .map(|body| body.native.get())
+                    .or_else(|| {
+                        n.cast::<dim2::character::CharacterController>()
+                            .map(|c| c.native.get())
+                    })
+            })
         {
-            if parent_body.native.get() != RigidBodyHandle::invalid() {
-                let rigid_body_native = parent_body.native.get();
+            if rigid_body_native != RigidBodyHandle::invalid() {
                 let inv_global_transform = isometric_global_transform(nodes, handle)
                     .try_inverse()
                     .unwrap();
@@ -1178,6 +1843,7 @@ impl PhysicsWorld {
                     nodes,
                 ) {
                     let mut builder = ColliderBuilder::new(shape)
+                        .active_events(ActiveEvents::COLLISION_EVENTS)
                         .position(Isometry2 {
                             rotation: UnitComplex::from_angle(
                                 collider_node.local_transform().rotation().euler_angles().2,
@@ -1258,6 +1924,9 @@ impl PhysicsWorld {
             joint.contacts_enabled.try_sync_model(|v| {
                 native.data.set_contacts_enabled(v);
             });
+            if native.data.is_enabled() == joint.is_broken() {
+                native.data.set_enabled(!joint.is_broken());
+            }
             let mut local_frames = joint.local_frames.borrow_mut();
             if local_frames.is_none() {
                 if let (Some(body1), Some(body2)) = (
@@ -1271,9 +1940,29 @@ impl PhysicsWorld {
                     let (local_frame1, local_frame2) = calculate_local_frames(joint, body1, body2);
                     native.data =
                         convert_joint_params((*joint.params).clone(), local_frame1, local_frame2);
+                    native
+                        .data
+                        .set_contacts_enabled(joint.is_contacts_enabled());
+                    native.data.set_enabled(!joint.is_broken());
                     *local_frames = Some(JointLocalFrames::new(&local_frame1, &local_frame2));
                 }
             }
+
+            let native_handle = joint.native.get();
+            let mut break_limits_changed = false;
+            joint
+                .break_force
+                .try_sync_model(|_| break_limits_changed = true);
+            joint
+                .break_torque
+                .try_sync_model(|_| break_limits_changed = true);
+            if break_limits_changed {
+                self.set_joint_break_limits(
+                    native_handle,
+                    joint.break_force(),
+                    joint.break_torque(),
+                );
+            }
         } else {
             let body1_handle = joint.body1();
             let body2_handle = joint.body2();
@@ -1318,8 +2007,14 @@ impl PhysicsWorld {
 
                 let mut native_joint = convert_joint_params(params, local_frame1, local_frame2);
                 native_joint.contacts_enabled = joint.is_contacts_enabled();
+                native_joint.set_enabled(!joint.is_broken());
                 let native_handle =
                     self.add_joint(handle, native_body1, native_body2, native_joint);
+                self.set_joint_break_limits(
+                    native_handle,
+                    joint.break_force(),
+                    joint.break_torque(),
+                );
 
                 joint.native.set(native_handle);
                 *local_frames = Some(JointLocalFrames::new(&local_frame1, &local_frame2));
@@ -88,6 +88,7 @@ use std::{
 
 pub mod event;
 pub mod physics;
+pub mod query;
 
 /// Graph performance statistics. Allows you to find out "hot" parts of the scene graph, which
 /// parts takes the most time to update.
@@ -1102,6 +1103,8 @@ impl Graph {
     /// Update switches allows you to disable update for parts of the update pipeline, it could be useful for editors
     /// where you need to have preview mode to update only specific set of nodes, etc.
     pub fn update(&mut self, frame_size: Vector2<f32>, dt: f32, switches: GraphUpdateSwitches) {
+        crate::core::scope_profile!();
+
         self.sound_context.state().pause(switches.paused);
 
         if switches.paused {
@@ -1118,12 +1121,14 @@ impl Graph {
         self.performance_statistics.sync_time = instant::Instant::now() - last_time;
 
         if switches.physics {
+            crate::core::scope_profile!("Physics");
             self.physics.performance_statistics.reset();
             self.physics.update(dt);
             self.performance_statistics.physics = self.physics.performance_statistics.clone();
         }
 
         if switches.physics2d {
+            crate::core::scope_profile!("Physics 2D");
             self.physics2d.performance_statistics.reset();
             self.physics2d.update(dt);
             self.performance_statistics.physics2d = self.physics2d.performance_statistics.clone();
@@ -29,9 +29,9 @@ use crate::{
         arrayvec::ArrayVec,
         instant,
         log::{Log, MessageKind},
-        math::Matrix4Ext,
+        math::{m4x4_approx_eq, Matrix4Ext, TriangleDefinition},
         parking_lot::Mutex,
-        pool::Handle,
+        pool::{Handle, Pool},
         reflect::prelude::*,
         uuid_provider,
         variable::{InheritableVariable, VariableFlags},
@@ -40,10 +40,11 @@ use crate::{
     },
     scene::{
         self,
+        character::{CharacterCollision, CharacterController, CharacterMovement},
         collider::{self, ColliderShape, GeometrySource},
         debug::SceneDrawingContext,
         graph::{isometric_global_transform, Graph, NodePool},
-        joint::{JointLocalFrames, JointParams},
+        joint::{JointLocalFrames, JointMotor, JointMotorModel, JointParams},
         mesh::{
             buffer::{VertexAttributeUsage, VertexReadTrait},
             Mesh,
@@ -55,17 +56,19 @@ use crate::{
     utils::raw_mesh::{RawMeshBuilder, RawVertex},
 };
 use rapier3d::{
+    control::{DynamicRayCastVehicleController, WheelTuning},
     dynamics::{
         CCDSolver, GenericJoint, GenericJointBuilder, ImpulseJointHandle, ImpulseJointSet,
-        IslandManager, JointAxesMask, MultibodyJointHandle, MultibodyJointSet, RigidBody,
-        RigidBodyActivation, RigidBodyBuilder, RigidBodyHandle, RigidBodySet, RigidBodyType,
+        IslandManager, JointAxesMask, MotorModel, MultibodyJointHandle, MultibodyJointSet,
+        RigidBody, RigidBodyActivation, RigidBodyBuilder, RigidBodyHandle, RigidBodySet,
+        RigidBodyType,
     },
     geometry::{
         Collider, ColliderBuilder, ColliderHandle, ColliderSet, Cuboid, DefaultBroadPhase,
         InteractionGroups, NarrowPhase, Ray, SharedShape,
     },
     parry::{query::ShapeCastOptions, shape::HeightField},
-    pipeline::{DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryPipeline},
+    pipeline::{ActiveEvents, DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryPipeline},
     prelude::{HeightFieldCellStatus, JointAxis},
 };
 use std::{
@@ -79,6 +82,7 @@ use std::{
 };
 use strum_macros::{AsRefStr, EnumString, VariantNames};
 
+use fxhash::FxHashMap;
 use fyrox_graph::{BaseSceneGraph, SceneGraphNode};
 pub use rapier3d::geometry::shape::*;
 
@@ -400,6 +404,77 @@ pub struct IntersectionPair {
     pub has_any_active_contact: bool,
 }
 
+/// A kind of [`CollisionEvent`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CollisionEventKind {
+    /// Two colliders started to touch (or to intersect, if one of them is a sensor).
+    Started,
+    /// Two colliders stopped touching each other (or stopped intersecting, if one of them is a
+    /// sensor). It is also reported when one of the colliders was removed.
+    Stopped,
+}
+
+/// An event that is reported when two colliders start or stop touching each other. Collision
+/// events are collected during physics steps of a frame and available until the next update of
+/// the graph, they are also dispatched to scripts (see [`crate::script::ScriptTrait::on_collision_enter`]).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CollisionEvent {
+    /// A kind of the event.
+    pub kind: CollisionEventKind,
+    /// The first collider involved in the event. Could be [`Handle::NONE`] if the collider was
+    /// removed.
+    pub collider1: Handle<Node>,
+    /// The second collider involved in the event. Could be [`Handle::NONE`] if the collider was
+    /// removed.
+    pub collider2: Handle<Node>,
+    /// `true` if at least one of the colliders is a sensor.
+    pub sensor: bool,
+}
+
+// Collects collision events produced by the physics pipeline.
+#[derive(Default)]
+struct CollisionEventCollector {
+    events: Mutex<Vec<CollisionEvent>>,
+}
+
+impl EventHandler for CollisionEventCollector {
+    fn handle_collision_event(
+        &self,
+        _bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        event: rapier3d::geometry::CollisionEvent,
+        _contact_pair: Option<&rapier3d::geometry::ContactPair>,
+    ) {
+        let owner = |handle| {
+            colliders
+                .get(handle)
+                .map(|c| Handle::decode_from_u128(c.user_data))
+                .unwrap_or_default()
+        };
+
+        self.events.lock().push(CollisionEvent {
+            kind: if event.started() {
+                CollisionEventKind::Started
+            } else {
+                CollisionEventKind::Stopped
+            },
+            collider1: owner(event.collider1()),
+            collider2: owner(event.collider2()),
+            sensor: event.sensor(),
+        });
+    }
+
+    fn handle_contact_force_event(
+        &self,
+        _dt: f32,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        _contact_pair: &rapier3d::geometry::ContactPair,
+        _total_force_magnitude: f32,
+    ) {
+    }
+}
+
 pub(super) struct Container<S, A>
 where
     A: Hash + Eq + Clone,
@@ -408,6 +483,27 @@ where
     map: BiDirHashMap<A, Handle<Node>>,
 }
 
+fn set_joint_motor(joint: &mut GenericJoint, axis: JointAxis, motor: &JointMotor) {
+    if motor.enabled {
+        joint
+            .set_motor(
+                axis,
+                motor.target_position,
+                motor.target_velocity,
+                motor.stiffness,
+                motor.damping,
+            )
+            .set_motor_max_force(axis, motor.max_force)
+            .set_motor_model(
+                axis,
+                match motor.model {
+                    JointMotorModel::AccelerationBased => MotorModel::AccelerationBased,
+                    JointMotorModel::ForceBased => MotorModel::ForceBased,
+                },
+            );
+    }
+}
+
 fn convert_joint_params(
     params: scene::joint::JointParams,
     local_frame1: Isometry3<f32>,
@@ -418,6 +514,23 @@ fn convert_joint_params(
         JointParams::FixedJoint(_) => JointAxesMask::LOCKED_FIXED_AXES,
         JointParams::PrismaticJoint(_) => JointAxesMask::LOCKED_PRISMATIC_AXES,
         JointParams::RevoluteJoint(_) => JointAxesMask::LOCKED_REVOLUTE_AXES,
+        JointParams::SixDofJoint(ref v) => {
+            let mut mask = JointAxesMask::empty();
+            for (params, axis) in [
+                (&v.linear_x, JointAxesMask::LIN_X),
+                (&v.linear_y, JointAxesMask::LIN_Y),
+                (&v.linear_z, JointAxesMask::LIN_Z),
+                (&v.angular_x, JointAxesMask::ANG_X),
+                (&v.angular_y, JointAxesMask::ANG_Y),
+                (&v.angular_z, JointAxesMask::ANG_Z),
+            ] {
+                if params.locked {
+                    mask |= axis;
+                }
+            }
+            mask
+        }
+        JointParams::RopeJoint(_) | JointParams::SpringJoint(_) => JointAxesMask::empty(),
     };
 
     let mut joint = GenericJointBuilder::new(locked_axis)
@@ -445,17 +558,49 @@ fn convert_joint_params(
                     [v.z_limits_angles.start, v.z_limits_angles.end],
                 );
             }
+            set_joint_motor(&mut joint, JointAxis::AngX, &v.x_motor);
+            set_joint_motor(&mut joint, JointAxis::AngY, &v.y_motor);
+            set_joint_motor(&mut joint, JointAxis::AngZ, &v.z_motor);
         }
         scene::joint::JointParams::FixedJoint(_) => {}
         scene::joint::JointParams::PrismaticJoint(v) => {
             if v.limits_enabled {
                 joint.set_limits(JointAxis::LinX, [v.limits.start, v.limits.end]);
             }
+            set_joint_motor(&mut joint, JointAxis::LinX, &v.motor);
         }
         scene::joint::JointParams::RevoluteJoint(v) => {
             if v.limits_enabled {
                 joint.set_limits(JointAxis::AngX, [v.limits.start, v.limits.end]);
             }
+            set_joint_motor(&mut joint, JointAxis::AngX, &v.motor);
+        }
+        scene::joint::JointParams::SixDofJoint(v) => {
+            for (params, axis) in [
+                (&v.linear_x, JointAxis::LinX),
+                (&v.linear_y, JointAxis::LinY),
+                (&v.linear_z, JointAxis::LinZ),
+                (&v.angular_x, JointAxis::AngX),
+                (&v.angular_y, JointAxis::AngY),
+                (&v.angular_z, JointAxis::AngZ),
+            ] {
+                if !params.locked {
+                    if params.limits_enabled {
+                        joint.set_limits(axis, [params.limits.start, params.limits.end]);
+                    }
+                    set_joint_motor(&mut joint, axis, &params.motor);
+                }
+            }
+        }
+        scene::joint::JointParams::RopeJoint(v) => {
+            joint.coupled_axes = JointAxesMask::LIN_AXES;
+            joint.set_limits(JointAxis::LinX, [0.0, v.max_distance]);
+        }
+        scene::joint::JointParams::SpringJoint(v) => {
+            joint.coupled_axes = JointAxesMask::LIN_AXES;
+            joint
+                .set_motor_position(JointAxis::LinX, v.rest_length, v.stiffness, v.damping)
+                .set_motor_model(JointAxis::LinX, MotorModel::ForceBased);
         }
     }
 
@@ -775,6 +920,77 @@ fn collider_shape_into_native_shape(
     }
 }
 
+// Appends a triangle mesh, that approximates the given shape, to the given buffers.
+fn append_shape_trimesh(
+    shape: &dyn Shape,
+    transform: &Isometry3<f32>,
+    vertices: &mut Vec<Vector3<f32>>,
+    triangles: &mut Vec<TriangleDefinition>,
+) {
+    let (shape_vertices, shape_triangles) = match shape.as_typed_shape() {
+        TypedShape::Ball(ball) => ball.to_trimesh(8, 16),
+        TypedShape::Cuboid(cuboid) => cuboid.to_trimesh(),
+        TypedShape::Capsule(capsule) => capsule.to_trimesh(8, 8),
+        TypedShape::Cylinder(cylinder) => cylinder.to_trimesh(16),
+        TypedShape::Cone(cone) => cone.to_trimesh(16),
+        TypedShape::Triangle(triangle) => {
+            (vec![triangle.a, triangle.b, triangle.c], vec![[0, 1, 2]])
+        }
+        TypedShape::TriMesh(trimesh) => (trimesh.vertices().to_vec(), trimesh.indices().to_vec()),
+        TypedShape::HeightField(heightfield) => heightfield.to_trimesh(),
+        TypedShape::ConvexPolyhedron(polyhedron) => polyhedron.to_trimesh(),
+        TypedShape::Compound(compound) => {
+            for (isometry, shape) in compound.shapes() {
+                append_shape_trimesh(shape.as_ref(), &(transform * isometry), vertices, triangles);
+            }
+            return;
+        }
+        // Segments and other degenerate shapes do not have any surface.
+        _ => return,
+    };
+
+    let offset = vertices.len() as u32;
+    vertices.extend(
+        shape_vertices
+            .iter()
+            .map(|vertex| transform.transform_point(vertex).coords),
+    );
+    triangles.extend(
+        shape_triangles
+            .iter()
+            .map(|t| TriangleDefinition([t[0] + offset, t[1] + offset, t[2] + offset])),
+    );
+}
+
+/// Creates a triangle mesh in world space, that approximates the shape of the given collider.
+/// Returns `None` if there's no such collider or its shape cannot be created.
+pub(crate) fn make_collider_trimesh(
+    graph: &Graph,
+    collider: Handle<Node>,
+) -> Option<(Vec<Vector3<f32>>, Vec<TriangleDefinition>)> {
+    let collider_ref = graph
+        .pool
+        .try_borrow(collider)
+        .and_then(|n| n.cast::<collider::Collider>())?;
+    let global_transform = isometric_global_transform(&graph.pool, collider);
+    let shape = collider_shape_into_native_shape(
+        collider_ref.shape(),
+        global_transform.try_inverse().unwrap_or_default(),
+        collider,
+        &graph.pool,
+    )?;
+
+    let mut vertices = Vec::new();
+    let mut triangles = Vec::new();
+    append_shape_trimesh(
+        shape.as_ref(),
+        &isometry_from_global_transform(&global_transform),
+        &mut vertices,
+        &mut triangles,
+    );
+    Some((vertices, triangles))
+}
+
 /// Parameters for a time-step of the physics engine.
 ///
 /// # Notes
@@ -947,6 +1163,56 @@ impl Default for IntegrationParameters {
     }
 }
 
+/// Parameters of fixed time step simulation. When enabled, the physics world accumulates the time
+/// passed to it and advances the simulation with steps of constant length, performing as many
+/// steps per frame as needed. This makes the simulation independent of the frame rate - the
+/// state of the world depends only on the number of performed steps, which is required for
+/// lockstep networking and replays. Enable `physics_determinism` feature of the engine to
+/// make the simulation bit-exact across different platforms.
+#[derive(Copy, Clone, Visit, Reflect, Debug, PartialEq)]
+#[visit(optional)]
+pub struct FixedTimestep {
+    /// Whether fixed time step simulation is enabled or not. Default is `false`.
+    #[reflect(description = "Whether fixed time step simulation is enabled or not.")]
+    pub enabled: bool,
+
+    /// Length of a single simulation step in seconds. Default is `1.0 / 60.0`.
+    #[reflect(
+        min_value = 0.001,
+        description = "Length of a single simulation step in seconds."
+    )]
+    pub step: f32,
+
+    /// Maximum amount of steps per single update. If a frame took too long, the rest of the
+    /// accumulated time will be dropped to prevent the simulation from falling further behind.
+    /// Default is `8`.
+    #[reflect(
+        min_value = 1.0,
+        description = "Maximum amount of steps per single update."
+    )]
+    pub max_steps_per_update: u32,
+
+    /// Whether the transforms of dynamic rigid bodies and character controllers should be
+    /// interpolated between the last two simulation steps or not. Interpolation removes visual jittering when the frame rate does not
+    /// match the simulation rate, at the cost of a delay of one step. Default is `true`.
+    #[reflect(
+        description = "Whether the transforms of dynamic rigid bodies should be interpolated \
+        between the last two simulation steps or not."
+    )]
+    pub interpolation: bool,
+}
+
+impl Default for FixedTimestep {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            step: 1.0 / 60.0,
+            max_steps_per_update: 8,
+            interpolation: true,
+        }
+    }
+}
+
 /// Physics world is responsible for physics simulation in the engine. There is a very few public
 /// methods, mostly for ray casting. You should add physical entities using scene graph nodes, such
 /// as RigidBody, Collider, Joint.
@@ -959,6 +1225,10 @@ pub struct PhysicsWorld {
     #[visit(optional)]
     pub integration_parameters: InheritableVariable<IntegrationParameters>,
 
+    /// Parameters of fixed time step simulation. See [`FixedTimestep`] docs for more info.
+    #[visit(optional)]
+    pub fixed_timestep: InheritableVariable<FixedTimestep>,
+
     /// Current gravity vector. Default is (0.0, -9.81, 0.0)
     pub gravity: InheritableVariable<Vector3<f32>>,
 
@@ -1007,13 +1277,64 @@ pub struct PhysicsWorld {
     // Event handler collects info about contacts and proximity events.
     #[visit(skip)]
     #[reflect(hidden)]
-    event_handler: Box<dyn EventHandler>,
+    event_handler: CollisionEventCollector,
+    // Collision events of the last update.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    collision_events: Vec<CollisionEvent>,
+    // Break force and torque of joints that could be broken.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    breakable_joints: FxHashMap<ImpulseJointHandle, (f32, f32)>,
+    // Joints that were broken during the last update.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    broken_joints: Vec<Handle<Node>>,
+    // Time that was not simulated yet in fixed time step mode.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    accumulator: f32,
+    // Interpolation factor between previous and current poses of rigid bodies.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    interpolation_factor: f32,
+    // Poses of rigid bodies before the last simulation step.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    previous_poses: FxHashMap<RigidBodyHandle, Isometry3<f32>>,
+    // Interpolated poses of rigid bodies that were written to the scene nodes.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    interpolated_poses: FxHashMap<RigidBodyHandle, Isometry3<f32>>,
     #[visit(skip)]
     #[reflect(hidden)]
     query: RefCell<QueryPipeline>,
     #[visit(skip)]
     #[reflect(hidden)]
     debug_render_pipeline: Mutex<DebugRenderPipeline>,
+    // A container of ray-cast vehicles.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    vehicles: Pool<DynamicRayCastVehicleController>,
+    // Character controllers, that are moved by the simulation steps.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    characters: FxHashMap<RigidBodyHandle, NativeCharacter>,
+}
+
+// A character controller, that is moved by the simulation steps. It is synced with its scene node
+// once per frame.
+struct NativeCharacter {
+    controller: rapier3d::control::KinematicCharacterController,
+    collider: ColliderHandle,
+    // Desired translation of the frame, it is spread over the simulation steps of the frame.
+    desired_translation: Vector3<f32>,
+    gravity_scale: f32,
+    mass: f32,
+    push_dynamic_bodies: bool,
+    fall_velocity: Vector3<f32>,
+    // Results of every step of the frame.
+    movement: CharacterMovement,
 }
 
 fn isometry_from_global_transform(transform: &Matrix4<f32>) -> Isometry3<f32> {
@@ -1062,6 +1383,17 @@ pub struct QueryFilter<'a> {
     pub predicate: Option<&'a dyn Fn(Handle<Node>, &collider::Collider) -> bool>,
 }
 
+/// The result of a point projection (see [`PhysicsWorld::project_point`]).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PointProjection {
+    /// A handle of the collider the point was projected on.
+    pub collider: Handle<Node>,
+    /// The projection of the point in world coordinates.
+    pub point: Point3<f32>,
+    /// `true` if the point is inside the collider.
+    pub is_inside: bool,
+}
+
 /// The result of a time-of-impact (TOI) computation.
 #[derive(Copy, Clone, Debug)]
 pub struct TOI {
@@ -1095,6 +1427,7 @@ impl PhysicsWorld {
             pipeline: PhysicsPipeline::new(),
             gravity: Vector3::new(0.0, -9.81, 0.0).into(),
             integration_parameters: IntegrationParameters::default().into(),
+            fixed_timestep: Default::default(),
             broad_phase: DefaultBroadPhase::new(),
             narrow_phase: NarrowPhase::new(),
             ccd_solver: CCDSolver::new(),
@@ -1109,76 +1442,258 @@ impl PhysicsWorld {
                 set: MultibodyJointSet::new(),
                 map: Default::default(),
             },
-            event_handler: Box::new(()),
+            event_handler: Default::default(),
+            collision_events: Default::default(),
+            breakable_joints: Default::default(),
+            broken_joints: Default::default(),
+            accumulator: 0.0,
+            interpolation_factor: 1.0,
+            previous_poses: Default::default(),
+            interpolated_poses: Default::default(),
             query: RefCell::new(Default::default()),
             performance_statistics: Default::default(),
             debug_render_pipeline: Default::default(),
+            vehicles: Default::default(),
+            characters: Default::default(),
         }
     }
 
     pub(super) fn update(&mut self, dt: f32) {
         let time = instant::Instant::now();
 
+        self.collision_events.clear();
+        self.broken_joints.clear();
+
         if *self.enabled {
-            let integration_parameters = rapier3d::dynamics::IntegrationParameters {
-                dt: self.integration_parameters.dt.unwrap_or(dt),
-                min_ccd_dt: self.integration_parameters.min_ccd_dt,
-                contact_damping_ratio: self.integration_parameters.contact_damping_ratio,
-                contact_natural_frequency: self.integration_parameters.contact_natural_frequency,
-                joint_natural_frequency: self.integration_parameters.joint_natural_frequency,
-                joint_damping_ratio: self.integration_parameters.joint_damping_ratio,
-                warmstart_coefficient: self.integration_parameters.warmstart_coefficient,
-                length_unit: self.integration_parameters.length_unit,
-                normalized_allowed_linear_error: self.integration_parameters.allowed_linear_error,
-                normalized_max_corrective_velocity: self
-                    .integration_parameters
-                    .normalized_max_corrective_velocity,
-                normalized_prediction_distance: self.integration_parameters.prediction_distance,
-                num_solver_iterations: NonZeroUsize::new(
-                    self.integration_parameters.num_solver_iterations,
-                )
-                .unwrap(),
-                num_additional_friction_iterations: self
-                    .integration_parameters
-                    .num_additional_friction_iterations,
-                num_internal_pgs_iterations: self
-                    .integration_parameters
-                    .num_internal_pgs_iterations,
-                num_internal_stabilization_iterations: self
-                    .integration_parameters
-                    .num_internal_stabilization_iterations,
-                min_island_size: self.integration_parameters.min_island_size as usize,
-                max_ccd_substeps: self.integration_parameters.max_ccd_substeps as usize,
-            };
+            for character in self.characters.values_mut() {
+                character.movement = Default::default();
+            }
 
-            self.pipeline.step(
-                &self.gravity,
-                &integration_parameters,
-                &mut self.islands,
-                &mut self.broad_phase,
-                &mut self.narrow_phase,
-                &mut self.bodies,
-                &mut self.colliders,
-                &mut self.joints.set,
-                &mut self.multibody_joints.set,
-                &mut self.ccd_solver,
-                // In Rapier 0.17 passing query pipeline here sometimes causing panic in numeric overflow,
-                // so we keep updating it manually.
-                None,
-                &(),
-                &*self.event_handler,
-            );
+            let fixed_timestep = *self.fixed_timestep;
+            if fixed_timestep.enabled {
+                let step = fixed_timestep.step.max(0.001);
+                // Desired translations of characters are made for the whole frame, every step
+                // applies its part of them.
+                let translation_scale = if dt > 0.0 { step / dt } else { 0.0 };
+                self.accumulator += dt;
+                // Tolerate rounding errors of the accumulation, otherwise a frame that is a
+                // multiple of the step could occasionally perform one step less.
+                let tolerance = step * 1.0e-3;
+                let mut steps = 0;
+                while self.accumulator + tolerance >= step {
+                    if steps >= fixed_timestep.max_steps_per_update {
+                        // Drop the time that cannot be simulated in this update.
+                        self.accumulator %= step;
+                        break;
+                    }
+                    self.step(step, translation_scale, fixed_timestep.interpolation);
+                    self.accumulator = (self.accumulator - step).max(0.0);
+                    steps += 1;
+                }
+                self.interpolation_factor = if fixed_timestep.interpolation {
+                    (self.accumulator / step).clamp(0.0, 1.0)
+                } else {
+                    1.0
+                };
+            } else {
+                self.step(self.integration_parameters.dt.unwrap_or(dt), 1.0, false);
+                self.interpolation_factor = 1.0;
+            }
         }
 
         self.performance_statistics.step_time += instant::Instant::now() - time;
     }
 
+    // Performs a single simulation step.
+    fn step(&mut self, dt: f32, translation_scale: f32, remember_poses: bool) {
+        if remember_poses {
+            self.previous_poses.clear();
+            let characters = &self.characters;
+            self.previous_poses.extend(
+                self.bodies
+                    .iter()
+                    .filter(|(handle, body)| {
+                        body.body_type() == RigidBodyType::Dynamic
+                            || characters.contains_key(handle)
+                    })
+                    .map(|(handle, body)| (handle, *body.position())),
+            );
+        } else {
+            self.previous_poses.clear();
+        }
+
+        let integration_parameters = rapier3d::dynamics::IntegrationParameters {
+            dt,
+            min_ccd_dt: self.integration_parameters.min_ccd_dt,
+            contact_damping_ratio: self.integration_parameters.contact_damping_ratio,
+            contact_natural_frequency: self.integration_parameters.contact_natural_frequency,
+            joint_natural_frequency: self.integration_parameters.joint_natural_frequency,
+            joint_damping_ratio: self.integration_parameters.joint_damping_ratio,
+            warmstart_coefficient: self.integration_parameters.warmstart_coefficient,
+            length_unit: self.integration_parameters.length_unit,
+            normalized_allowed_linear_error: self.integration_parameters.allowed_linear_error,
+            normalized_max_corrective_velocity: self
+                .integration_parameters
+                .normalized_max_corrective_velocity,
+            normalized_prediction_distance: self.integration_parameters.prediction_distance,
+            num_solver_iterations: NonZeroUsize::new(
+                self.integration_parameters.num_solver_iterations,
+            )
+            .unwrap(),
+            num_additional_friction_iterations: self
+                .integration_parameters
+                .num_additional_friction_iterations,
+            num_internal_pgs_iterations: self.integration_parameters.num_internal_pgs_iterations,
+            num_internal_stabilization_iterations: self
+                .integration_parameters
+                .num_internal_stabilization_iterations,
+            min_island_size: self.integration_parameters.min_island_size as usize,
+            max_ccd_substeps: self.integration_parameters.max_ccd_substeps as usize,
+        };
+
+        self.move_characters(dt, translation_scale);
+
+        if self.vehicles.alive_count() > 0 {
+            let mut query = self.query.borrow_mut();
+            query.update(&self.colliders);
+            for vehicle in self.vehicles.iter_mut() {
+                if self.bodies.contains(vehicle.chassis) {
+                    let filter = rapier3d::pipeline::QueryFilter::new()
+                        .exclude_rigid_body(vehicle.chassis)
+                        .exclude_sensors();
+                    vehicle.update_vehicle(
+                        integration_parameters.dt,
+                        &mut self.bodies,
+                        &self.colliders,
+                        &query,
+                        filter,
+                    );
+                }
+            }
+        }
+
+        self.pipeline.step(
+            &self.gravity,
+            &integration_parameters,
+            &mut self.islands,
+            &mut self.broad_phase,
+            &mut self.narrow_phase,
+            &mut self.bodies,
+            &mut self.colliders,
+            &mut self.joints.set,
+            &mut self.multibody_joints.set,
+            &mut self.ccd_solver,
+            // In Rapier 0.17 passing query pipeline here sometimes causing panic in numeric overflow,
+            // so we keep updating it manually.
+            None,
+            &(),
+            &self.event_handler,
+        );
+
+        self.collision_events
+            .append(&mut self.event_handler.events.lock());
+
+        self.break_joints(integration_parameters.dt);
+    }
+
+    // Moves the character controllers by the given part of their desired translations. The bodies
+    // of the characters are moved by the simulation step that follows, so the dynamic bodies react
+    // on the movement in the same step.
+    fn move_characters(&mut self, dt: f32, translation_scale: f32) {
+        if self.characters.is_empty() {
+            return;
+        }
+
+        let mut query = self.query.borrow_mut();
+        query.update(&self.colliders);
+
+        for (&body_handle, character) in self.characters.iter_mut() {
+            let (Some(body), Some(collider)) = (
+                self.bodies.get(body_handle),
+                self.colliders.get(character.collider),
+            ) else {
+                continue;
+            };
+
+            let position = *body.position();
+            let shape = collider.shared_shape().clone();
+            let shape_position = position
+                * collider
+                    .position_wrt_parent()
+                    .cloned()
+                    .unwrap_or_else(Isometry3::identity);
+
+            character.fall_velocity += *self.gravity * character.gravity_scale * dt;
+            let translation =
+                character.desired_translation * translation_scale + character.fall_velocity * dt;
+
+            let filter = rapier3d::pipeline::QueryFilter::new()
+                .exclude_rigid_body(body_handle)
+                .exclude_sensors();
+
+            let mut collisions = Vec::new();
+            let movement = character.controller.move_shape(
+                dt,
+                &self.bodies,
+                &self.colliders,
+                &query,
+                shape.as_ref(),
+                &shape_position,
+                translation,
+                filter,
+                |collision| collisions.push(collision),
+            );
+
+            if character.push_dynamic_bodies {
+                character.controller.solve_character_collision_impulses(
+                    dt,
+                    &mut self.bodies,
+                    &self.colliders,
+                    &query,
+                    shape.as_ref(),
+                    character.mass,
+                    &collisions,
+                    filter,
+                );
+            }
+
+            if movement.grounded {
+                character.fall_velocity = Vector3::default();
+            }
+
+            let result = &mut character.movement;
+            result.translation += movement.translation;
+            result.grounded = movement.grounded;
+            result.sliding_down_slope = movement.is_sliding_down_slope;
+            result
+                .collisions
+                .extend(collisions.iter().filter_map(|collision| {
+                    Some(CharacterCollision {
+                        collider: Handle::decode_from_u128(
+                            self.colliders.get(collision.handle)?.user_data,
+                        ),
+                        position: collision.hit.witness1.coords,
+                        normal: *collision.hit.normal1,
+                        translation_applied: collision.translation_applied,
+                        translation_remaining: collision.translation_remaining,
+                    })
+                }));
+
+            if let Some(body) = self.bodies.get_mut(body_handle) {
+                let mut next_position = position;
+                next_position.translation.vector += movement.translation;
+                body.set_next_kinematic_position(next_position);
+            }
+        }
+    }
+
     pub(super) fn add_body(&mut self, owner: Handle<Node>, mut body: RigidBody) -> RigidBodyHandle {
         body.user_data = owner.encode_to_u128();
         self.bodies.insert(body)
     }
 
     pub(crate) fn remove_body(&mut self, handle: RigidBodyHandle) {
+        self.characters.remove(&handle);
         self.bodies.remove(
             handle,
             &mut self.islands,
@@ -1222,6 +1737,51 @@ impl PhysicsWorld {
         if self.joints.set.remove(handle, false).is_some() {
             assert!(self.joints.map.remove_by_key(&handle).is_some());
         }
+        self.breakable_joints.remove(&handle);
+    }
+
+    fn set_joint_break_limits(&mut self, handle: ImpulseJointHandle, force: f32, torque: f32) {
+        if force < f32::MAX || torque < f32::MAX {
+            self.breakable_joints.insert(handle, (force, torque));
+        } else {
+            self.breakable_joints.remove(&handle);
+        }
+    }
+
+    // Disables every joint, that applied a force or a torque larger than its break limits on the
+    // last step.
+    fn break_joints(&mut self, dt: f32) {
+        if dt <= 0.0 {
+            return;
+        }
+
+        for (handle, (max_force, max_torque)) in self.breakable_joints.iter() {
+            if let Some(joint) = self.joints.set.get_mut(*handle) {
+                if !joint.data.is_enabled() {
+                    continue;
+                }
+
+                let force = joint.impulses.fixed_rows::<3>(0).norm() / dt;
+                let torque = joint.impulses.fixed_rows::<3>(3).norm() / dt;
+                if force > *max_force || torque > *max_torque {
+                    joint.data.set_enabled(false);
+
+                    if let Some(owner) = self.joints.map.value_of(handle) {
+                        self.broken_joints.push(*owner);
+                    }
+                }
+            }
+        }
+    }
+
+    pub(crate) fn is_joint_enabled(&self, handle: ImpulseJointHandle) -> Option<bool> {
+        self.joints.set.get(handle).map(|j| j.data.is_enabled())
+    }
+
+    /// Returns a list of joints (their handles) that were broken during the last update of the
+    /// physics world, because of exceeding their break force or torque.
+    pub fn broken_joints(&self) -> &[Handle<Node>] {
+        &self.broken_joints
     }
 
     /// Draws physics world. Very useful for debugging, it allows you to see where are
@@ -1237,6 +1797,12 @@ impl PhysicsWorld {
         );
     }
 
+    /// Returns a list of collision events (contacts and intersections that started or stopped)
+    /// produced by the last update of the physics world.
+    pub fn collision_events(&self) -> &[CollisionEvent] {
+        &self.collision_events
+    }
+
     /// Casts a ray with given options.
     pub fn cast_ray<S: QueryResultsStorage>(&self, opts: RayCastOptions, query_buffer: &mut S) {
         let time = instant::Instant::now();
@@ -1326,6 +1892,176 @@ impl PhysicsWorld {
         stop_at_penetration: bool,
         filter: QueryFilter,
     ) -> Option<(Handle<Node>, TOI)> {
+        let query = self.query.borrow_mut();
+
+        let opts = ShapeCastOptions {
+            max_time_of_impact: max_toi,
+            target_distance: 0.0,
+            stop_at_penetration,
+            compute_impact_geometry_on_penetration: true,
+        };
+
+        self.with_native_filter(graph, filter, |filter| {
+            query.cast_shape(
+                &self.bodies,
+                &self.colliders,
+                shape_pos,
+                shape_vel,
+                shape,
+                opts,
+                filter,
+            )
+        })
+        .map(|(handle, toi)| {
+            (
+                Handle::decode_from_u128(self.colliders.get(handle).unwrap().user_data),
+                TOI {
+                    toi: toi.time_of_impact,
+                    witness1: toi.witness1,
+                    witness2: toi.witness2,
+                    normal1: toi.normal1,
+                    normal2: toi.normal2,
+                    status: toi.status.into(),
+                },
+            )
+        })
+    }
+
+    /// Finds all colliders that intersect the given shape and calls the `callback` for each of them.
+    /// The callback must return `false` to stop the search.
+    ///
+    /// # Parameters
+    ///
+    /// * `graph` - a reference to the scene graph.
+    /// * `shape` - the shape to test.
+    /// * `shape_pos` - the position of the shape in world coordinates.
+    /// * `filter`: set of rules used to determine which collider is taken into account by this scene
+    ///   query.
+    /// * `callback` - a function that will be called for every intersecting collider. It is called
+    ///   after the search is done, so it is allowed to perform other scene queries.
+    pub fn intersections_with_shape(
+        &self,
+        graph: &Graph,
+        shape: &dyn Shape,
+        shape_pos: &Isometry3<f32>,
+        filter: QueryFilter,
+        mut callback: impl FnMut(Handle<Node>) -> bool,
+    ) {
+        let mut intersections = Vec::new();
+        {
+            let mut query = self.query.borrow_mut();
+            query.update(&self.colliders);
+
+            self.with_native_filter(graph, filter, |filter| {
+                query.intersections_with_shape(
+                    &self.bodies,
+                    &self.colliders,
+                    shape_pos,
+                    shape,
+                    filter,
+                    |handle| {
+                        intersections.push(Handle::decode_from_u128(
+                            self.colliders.get(handle).unwrap().user_data,
+                        ));
+                        true
+                    },
+                )
+            });
+        }
+
+        // The query pipeline is released at this point, so the callback can do other queries.
+        for collider in intersections {
+            if !callback(collider) {
+                break;
+            }
+        }
+    }
+
+    /// Finds all colliders that contain the given point and calls the `callback` for each of them.
+    /// The callback must return `false` to stop the search.
+    ///
+    /// # Parameters
+    ///
+    /// * `graph` - a reference to the scene graph.
+    /// * `point` - the point to test in world coordinates.
+    /// * `filter`: set of rules used to determine which collider is taken into account by this scene
+    ///   query.
+    /// * `callback` - a function that will be called for every collider that contains the point. It is called
+    ///   after the search is done, so it is allowed to perform other scene queries.
+    pub fn intersections_with_point(
+        &self,
+        graph: &Graph,
+        point: &Point3<f32>,
+        filter: QueryFilter,
+        mut callback: impl FnMut(Handle<Node>) -> bool,
+    ) {
+        let mut intersections = Vec::new();
+        {
+            let mut query = self.query.borrow_mut();
+            query.update(&self.colliders);
+
+            self.with_native_filter(graph, filter, |filter| {
+                query.intersections_with_point(
+                    &self.bodies,
+                    &self.colliders,
+                    point,
+                    filter,
+                    |handle| {
+                        intersections.push(Handle::decode_from_u128(
+                            self.colliders.get(handle).unwrap().user_data,
+                        ));
+                        true
+                    },
+                )
+            });
+        }
+
+        // The query pipeline is released at this point, so the callback can do other queries.
+        for collider in intersections {
+            if !callback(collider) {
+                break;
+            }
+        }
+    }
+
+    /// Finds the projection of the given point on the closest collider.
+    ///
+    /// # Parameters
+    ///
+    /// * `graph` - a reference to the scene graph.
+    /// * `point` - the point to project in world coordinates.
+    /// * `solid` - if `true`, a point inside of a collider will be projected on itself (and the
+    ///   projection will be marked as inside). Otherwise the point will be projected on the boundary
+    ///   of the collider.
+    /// * `filter`: set of rules used to determine which collider is taken into account by this scene
+    ///   query.
+    pub fn project_point(
+        &self,
+        graph: &Graph,
+        point: &Point3<f32>,
+        solid: bool,
+        filter: QueryFilter,
+    ) -> Option<PointProjection> {
+        let mut query = self.query.borrow_mut();
+        query.update(&self.colliders);
+
+        self.with_native_filter(graph, filter, |filter| {
+            query.project_point(&self.bodies, &self.colliders, point, solid, filter)
+        })
+        .map(|(handle, projection)| PointProjection {
+            collider: Handle::decode_from_u128(self.colliders.get(handle).unwrap().user_data),
+            point: projection.point,
+            is_inside: projection.is_inside,
+        })
+    }
+
+    // Converts the given query filter to the native one and passes it to the given function.
+    fn with_native_filter<R>(
+        &self,
+        graph: &Graph,
+        filter: QueryFilter,
+        func: impl FnOnce(rapier3d::pipeline::QueryFilter) -> R,
+    ) -> R {
         let predicate = |handle: ColliderHandle, _: &Collider| -> bool {
             if let Some(pred) = filter.predicate {
                 let h = Handle::decode_from_u128(self.colliders.get(handle).unwrap().user_data);
@@ -1338,7 +2074,7 @@ impl PhysicsWorld {
             }
         };
 
-        let filter = rapier3d::pipeline::QueryFilter {
+        func(rapier3d::pipeline::QueryFilter {
             flags: rapier3d::pipeline::QueryFilterFlags::from_bits(filter.flags.bits()).unwrap(),
             groups: filter.groups.map(|g| {
                 InteractionGroups::new(u32_to_group(g.memberships.0), u32_to_group(g.filter.0))
@@ -1349,45 +2085,12 @@ impl PhysicsWorld {
                 .and_then(|n| n.component_ref::<collider::Collider>())
                 .map(|c| c.native.get()),
             exclude_rigid_body: filter
-                .exclude_collider
+                .exclude_rigid_body
                 .and_then(|h| graph.try_get(h))
                 .and_then(|n| n.component_ref::<rigidbody::RigidBody>())
                 .map(|c| c.native.get()),
             predicate: Some(&predicate),
-        };
-
-        let query = self.query.borrow_mut();
-
-        let opts = ShapeCastOptions {
-            max_time_of_impact: max_toi,
-            target_distance: 0.0,
-            stop_at_penetration,
-            compute_impact_geometry_on_penetration: true,
-        };
-
-        query
-            .cast_shape(
-                &self.bodies,
-                &self.colliders,
-                shape_pos,
-                shape_vel,
-                shape,
-                opts,
-                filter,
-            )
-            .map(|(handle, toi)| {
-                (
-                    Handle::decode_from_u128(self.colliders.get(handle).unwrap().user_data),
-                    TOI {
-                        toi: toi.time_of_impact,
-                        witness1: toi.witness1,
-                        witness2: toi.witness2,
-                        normal1: toi.normal1,
-                        normal2: toi.normal2,
-                        status: toi.status.into(),
-                    },
-                )
-            })
+        })
     }
 
     pub(crate) fn set_rigid_body_position(
@@ -1395,9 +2098,23 @@ impl PhysicsWorld {
         rigid_body: &scene::rigidbody::RigidBody,
         new_global_transform: &Matrix4<f32>,
     ) {
-        if let Some(native) = self.bodies.get_mut(rigid_body.native.get()) {
+        let handle = rigid_body.native.get();
+        let new_position = isometry_from_global_transform(new_global_transform);
+
+        // Ignore the changes made by interpolation of the node transform.
+        if self.interpolated_poses.get(&handle).is_some_and(|pose| {
+            (pose.translation.vector - new_position.translation.vector).norm() < 1.0e-3
+                && pose.rotation.angle_to(&new_position.rotation) < 1.0e-3
+        }) {
+            return;
+        }
+
+        self.previous_poses.remove(&handle);
+        self.interpolated_poses.remove(&handle);
+
+        if let Some(native) = self.bodies.get_mut(handle) {
             native.set_position(
-                isometry_from_global_transform(new_global_transform),
+                new_position,
                 // Do not wake up body, it is too expensive and must be done **only** by explicit
                 // `wake_up` call!
                 false,
@@ -1411,12 +2128,26 @@ impl PhysicsWorld {
         parent_transform: Matrix4<f32>,
     ) {
         if *self.enabled {
-            if let Some(native) = self.bodies.get(rigid_body.native.get()) {
+            let handle = rigid_body.native.get();
+            if let Some(native) = self.bodies.get(handle) {
                 if native.body_type() == RigidBodyType::Dynamic {
+                    let position = match self.previous_poses.get(&handle) {
+                        Some(previous) if self.interpolation_factor < 1.0 => {
+                            let position =
+                                previous.lerp_slerp(native.position(), self.interpolation_factor);
+                            self.interpolated_poses.insert(handle, position);
+                            position
+                        }
+                        _ => {
+                            self.interpolated_poses.remove(&handle);
+                            *native.position()
+                        }
+                    };
+
                     let local_transform: Matrix4<f32> = parent_transform
                         .try_inverse()
                         .unwrap_or_else(Matrix4::identity)
-                        * native.position().to_homogeneous();
+                        * position.to_homogeneous();
 
                     let new_local_rotation = UnitQuaternion::from_matrix_eps(
                         &local_transform.basis(),
@@ -1615,6 +2346,268 @@ impl PhysicsWorld {
         }
     }
 
+    pub(crate) fn set_character_controller_position(
+        &mut self,
+        controller: &CharacterController,
+        new_global_transform: &Matrix4<f32>,
+    ) {
+        let handle = controller.native.get();
+        let new_position = isometry_from_global_transform(new_global_transform);
+
+        // Ignore the changes made by interpolation of the node transform.
+        if self.interpolated_poses.get(&handle).is_some_and(|pose| {
+            (pose.translation.vector - new_position.translation.vector).norm() < 1.0e-3
+                && pose.rotation.angle_to(&new_position.rotation) < 1.0e-3
+        }) {
+            return;
+        }
+
+        self.previous_poses.remove(&handle);
+        self.interpolated_poses.remove(&handle);
+
+        if let Some(native) = self.bodies.get_mut(handle) {
+            native.set_position(new_position, false);
+        }
+    }
+
+    /// Writes the results of the movement of the given character controller, that was made by the
+    /// simulation steps of the last update, to its scene node.
+    pub(crate) fn sync_character_controller_node(
+        &mut self,
+        controller: &mut CharacterController,
+        parent_transform: Matrix4<f32>,
+    ) {
+        // Desired translation is consumed by the steps of the frame.
+        controller.desired_translation = Vector3::default();
+
+        if !*self.enabled {
+            return;
+        }
+
+        let handle = controller.native.get();
+        let (Some(character), Some(body)) = (self.characters.get(&handle), self.bodies.get(handle))
+        else {
+            return;
+        };
+
+        controller.fall_velocity = character.fall_velocity;
+        controller.movement = character.movement.clone();
+
+        let position = match self.previous_poses.get(&handle) {
+            Some(previous) if self.interpolation_factor < 1.0 => {
+                let position = previous.lerp_slerp(body.position(), self.interpolation_factor);
+                self.interpolated_poses.insert(handle, position);
+                position
+            }
+            _ => {
+                self.interpolated_poses.remove(&handle);
+                *body.position()
+            }
+        };
+
+        let new_local_position = parent_transform
+            .try_inverse()
+            .unwrap_or_else(Matrix4::identity)
+            .transform_point(&Point3::from(position.translation.vector))
+            .coords;
+        if **controller.local_transform().position() != new_local_position {
+            controller
+                .local_transform_mut()
+                .set_position(new_local_position);
+        }
+    }
+
+    pub(crate) fn sync_to_character_controller_node(
+        &mut self,
+        nodes: &NodePool,
+        handle: Handle<Node>,
+        controller: &CharacterController,
+    ) {
+        if !controller.is_globally_enabled() {
+            self.remove_body(controller.native.get());
+            controller.native.set(Default::default());
+            return;
+        }
+
+        if controller.native.get() == RigidBodyHandle::invalid() {
+            let body = RigidBodyBuilder::kinematic_position_based()
+                .position(isometry_from_global_transform(
+                    &controller.global_transform(),
+                ))
+                .build();
+
+            controller.native.set(self.add_body(handle, body));
+
+            Log::writeln(
+                MessageKind::Information,
+                format!(
+                    "Native character controller was created for node {}",
+                    controller.name()
+                ),
+            );
+        }
+
+        // The first collider is used as the shape of the character.
+        let body = controller.native.get();
+        let Some(collider) = controller.children().iter().find_map(|child| {
+            nodes
+                .try_borrow(*child)
+                .and_then(|n| n.cast::<collider::Collider>())
+                .map(|c| c.native.get())
+                .filter(|collider| self.colliders.get(*collider).is_some())
+        }) else {
+            self.characters.remove(&body);
+            return;
+        };
+
+        let character = self
+            .characters
+            .entry(body)
+            .or_insert_with(|| NativeCharacter {
+                controller: Default::default(),
+                collider,
+                desired_translation: Default::default(),
+                gravity_scale: 1.0,
+                mass: 0.0,
+                push_dynamic_bodies: false,
+                fall_velocity: controller.fall_velocity,
+                movement: Default::default(),
+            });
+        character.controller = controller.native_controller();
+        character.collider = collider;
+        character.desired_translation = controller.desired_translation;
+        character.gravity_scale = controller.gravity_scale();
+        character.mass = controller.mass();
+        character.push_dynamic_bodies = controller.is_push_dynamic_bodies();
+    }
+
+    pub(crate) fn remove_vehicle(&mut self, handle: Handle<DynamicRayCastVehicleController>) {
+        self.vehicles.try_free(handle);
+    }
+
+    pub(crate) fn sync_to_vehicle_node(
+        &mut self,
+        nodes: &NodePool,
+        vehicle: &scene::vehicle::Vehicle,
+    ) {
+        if !vehicle.is_globally_enabled() {
+            self.remove_vehicle(vehicle.native.get());
+            vehicle.native.set(Handle::NONE);
+            return;
+        }
+
+        let chassis = nodes
+            .try_borrow(vehicle.parent())
+            .and_then(|n| n.cast::<scene::rigidbody::RigidBody>())
+            .map(|body| body.native.get())
+            .unwrap_or_else(RigidBodyHandle::invalid);
+
+        // Wheels cannot be modified in place, so the native vehicle is re-created when the wheels,
+        // the chassis or the local transform of the vehicle (the wheels are placed in the space of
+        // the chassis) are changed.
+        let transform = vehicle.local_transform().matrix();
+        let mut need_rebuild = false;
+        vehicle.wheels.try_sync_model(|_| need_rebuild = true);
+        if need_rebuild
+            || self
+                .vehicles
+                .try_borrow(vehicle.native.get())
+                .is_some_and(|native| {
+                    native.chassis != chassis
+                        || !m4x4_approx_eq(&vehicle.native_transform.get(), &transform)
+                })
+        {
+            self.remove_vehicle(vehicle.native.get());
+            vehicle.native.set(Handle::NONE);
+        }
+
+        if vehicle.native.get().is_none() && self.bodies.contains(chassis) {
+            let mut native = DynamicRayCastVehicleController::new(chassis);
+            // Forward direction of the engine is +Z.
+            native.index_forward_axis = 2;
+            native.index_up_axis = 1;
+
+            for wheel in vehicle.wheels.iter() {
+                native.add_wheel(
+                    transform.transform_point(&Point3::from(wheel.connection_point)),
+                    transform
+                        .transform_vector(&wheel.direction)
+                        .try_normalize(f32::EPSILON)
+                        .unwrap_or_else(|| -Vector3::y()),
+                    transform
+                        .transform_vector(&wheel.axle)
+                        .try_normalize(f32::EPSILON)
+                        .unwrap_or_else(|| -Vector3::x()),
+                    wheel.suspension_rest_length,
+                    wheel.radius,
+                    &WheelTuning {
+                        suspension_stiffness: wheel.suspension_stiffness,
+                        suspension_compression: wheel.suspension_compression,
+                        suspension_damping: wheel.suspension_damping,
+                        max_suspension_travel: wheel.max_suspension_travel,
+                        side_friction_stiffness: wheel.side_friction_stiffness,
+                        friction_slip: wheel.friction_slip,
+                        max_suspension_force: wheel.max_suspension_force,
+                    },
+                );
+            }
+
+            vehicle.native.set(self.vehicles.spawn(native));
+            vehicle.native_transform.set(transform);
+
+            Log::writeln(
+                MessageKind::Information,
+                format!("Native vehicle was created for node {}", vehicle.name()),
+            );
+        }
+
+        if let Some(native) = self.vehicles.try_borrow_mut(vehicle.native.get()) {
+            for (wheel, native_wheel) in vehicle.wheels.iter().zip(native.wheels_mut()) {
+                native_wheel.engine_force = if wheel.drive {
+                    *vehicle.engine_force
+                } else {
+                    0.0
+                };
+                native_wheel.steering = if wheel.steering {
+                    *vehicle.steering
+                } else {
+                    0.0
+                };
+                native_wheel.brake = if wheel.brake { *vehicle.brake } else { 0.0 };
+            }
+        }
+    }
+
+    pub(crate) fn sync_vehicle_node(&self, vehicle: &mut scene::vehicle::Vehicle) {
+        let Some(native) = self.vehicles.try_borrow(vehicle.native.get()) else {
+            vehicle.wheel_states.clear();
+            return;
+        };
+
+        vehicle.speed = native.current_vehicle_speed;
+        vehicle.wheel_states = native
+            .wheels()
+            .iter()
+            .map(|wheel| {
+                let info = wheel.raycast_info();
+                scene::vehicle::WheelState {
+                    in_contact: info.is_in_contact,
+                    ground: info
+                        .ground_object
+                        .and_then(|collider| self.colliders.get(collider))
+                        .map(|collider| Handle::decode_from_u128(collider.user_data))
+                        .unwrap_or_default(),
+                    contact_point: info.contact_point_ws.coords,
+                    contact_normal: info.contact_normal_ws,
+                    suspension_length: info.suspension_length,
+                    steering: wheel.steering,
+                    rotation: wheel.rotation,
+                    suspension_force: wheel.wheel_suspension_force,
+                }
+            })
+            .collect();
+    }
+
     pub(crate) fn sync_to_collider_node(
         &mut self,
         nodes: &NodePool,
@@ -1697,15 +2690,18 @@ impl PhysicsWorld {
                     }
                 }
             }
-        } else if let Some(parent_body) = nodes
-            .try_borrow(collider_node.parent())
-            .and_then(|n| n.cast::<scene::rigidbody::RigidBody>())
+        } else if let Some(rigid_body_native) =
+            nodes.try_borrow(collider_node.parent()).and_then(|n| {
+                // Character controllers have their own native rigid bodies too.
+                n.cast::<scene::rigidbody::RigidBody>()
+                    .map(|body| body.native.get())
+                    .or_else(|| n.cast::<CharacterController>().map(|c| c.native.get()))
+            })
         {
-            if parent_body.native.get() != RigidBodyHandle::invalid() {
+            if rigid_body_native != RigidBodyHandle::invalid() {
                 let inv_global_transform = isometric_global_transform(nodes, handle)
                     .try_inverse()
                     .unwrap();
-                let rigid_body_native = parent_body.native.get();
                 if let Some(shape) = collider_shape_into_native_shape(
                     collider_node.shape(),
                     inv_global_transform,
@@ -1713,6 +2709,7 @@ impl PhysicsWorld {
                     nodes,
                 ) {
                     let mut builder = ColliderBuilder::new(shape)
+                        .active_events(ActiveEvents::COLLISION_EVENTS)
                         .position(Isometry3 {
                             rotation: **collider_node.local_transform().rotation(),
                             translation: Translation3 {
@@ -1791,6 +2788,9 @@ impl PhysicsWorld {
             joint.contacts_enabled.try_sync_model(|v| {
                 native.data.set_contacts_enabled(v);
             });
+            if native.data.is_enabled() == joint.is_broken() {
+                native.data.set_enabled(!joint.is_broken());
+            }
 
             let mut local_frames = joint.local_frames.borrow_mut();
             if local_frames.is_none() {
@@ -1805,9 +2805,29 @@ impl PhysicsWorld {
                     let (local_frame1, local_frame2) = calculate_local_frames(joint, body1, body2);
                     native.data =
                         convert_joint_params((*joint.params).clone(), local_frame1, local_frame2);
+                    native
+                        .data
+                        .set_contacts_enabled(joint.is_contacts_enabled());
+                    native.data.set_enabled(!joint.is_broken());
                     *local_frames = Some(JointLocalFrames::new(&local_frame1, &local_frame2));
                 }
             }
+
+            let native_handle = joint.native.get();
+            let mut break_limits_changed = false;
+            joint
+                .break_force
+                .try_sync_model(|_| break_limits_changed = true);
+            joint
+                .break_torque
+                .try_sync_model(|_| break_limits_changed = true);
+            if break_limits_changed {
+                self.set_joint_break_limits(
+                    native_handle,
+                    joint.break_force(),
+                    joint.break_torque(),
+                );
+            }
         } else {
             let body1_handle = joint.body1();
             let body2_handle = joint.body2();
@@ -1852,8 +2872,14 @@ impl PhysicsWorld {
 
                 let mut native_joint = convert_joint_params(params, local_frame1, local_frame2);
                 native_joint.contacts_enabled = joint.is_contacts_enabled();
+                native_joint.set_enabled(!joint.is_broken());
                 let native_handle =
                     self.add_joint(handle, native_body1, native_body2, native_joint);
+                self.set_joint_break_limits(
+                    native_handle,
+                    joint.break_force(),
+                    joint.break_torque(),
+                );
 
                 joint.native.set(native_handle);
                 *local_frames = Some(JointLocalFrames::new(&local_frame1, &local_frame2));
@@ -0,0 +1,491 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Node queries select nodes of a graph by their type, name, tag, scripts and components and
+//! optionally address a reflected property of every selected node. See [`NodeQuery`] docs for
+//! more info.
+
+use crate::{
+    core::{
+        pool::Handle,
+        reflect::{prelude::*, ReflectPathError, SetFieldByPathError},
+    },
+    scene::{graph::Graph, node::Node},
+};
+use std::{
+    any::TypeId,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// Matches a string with a glob pattern, where `*` matches any sequence of characters and `?`
+/// matches exactly one character.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` in the pattern and the position in the text it was matched at.
+    let mut backtrack = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Short name of a type, without the module path and generic arguments.
+pub(crate) fn short_type_name(type_name: &str) -> &str {
+    let type_name = type_name.split('<').next().unwrap_or(type_name);
+    type_name.rsplit("::").next().unwrap_or(type_name)
+}
+
+/// An error that may occur during parsing of a [`NodeQuery`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeQueryError {
+    /// A filter is not closed by `]`.
+    UnclosedBrackets(String),
+    /// A filter has no `=` sign.
+    InvalidFilter(String),
+    /// A filter with unknown name.
+    UnknownFilter(String),
+    /// A property path is empty.
+    EmptyPath,
+}
+
+impl Display for NodeQueryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NodeQueryError::UnclosedBrackets(s) => write!(f, "unclosed brackets: `{s}`"),
+            NodeQueryError::InvalidFilter(s) => {
+                write!(f, "invalid filter `{s}`, expected `[name=value]`")
+            }
+            NodeQueryError::UnknownFilter(s) => write!(
+                f,
+                "unknown filter `{s}`, supported filters are `name`, `tag` and `script`"
+            ),
+            NodeQueryError::EmptyPath => write!(f, "empty property path"),
+        }
+    }
+}
+
+impl std::error::Error for NodeQueryError {}
+
+/// A query that selects nodes of a [`Graph`] and optionally addresses a property of every selected
+/// node. A query could be built using the builder methods or parsed from a string:
+///
+/// ```text
+/// Type[filter=value][filter=value].property.path
+/// ```
+///
+/// - `Type` - short type name of a node (`Mesh`, `RigidBody`, etc.), `*` or nothing selects nodes
+/// of any type.
+/// - `[name=pattern]` - selects nodes with names that match the glob pattern (`*` matches any
+/// sequence of characters, `?` matches exactly one character).
+/// - `[tag=pattern]` - selects nodes with tags that match the glob pattern.
+/// - `[script=Type]` - selects nodes that have a script of the given type.
+/// - `.property.path` - a reflected property path (see [`Reflect`]) that is used by
+/// [`Graph::query_property`] and [`Graph::batch_set_property`].
+///
+/// Values could be quoted if they contain `]` or `.`: `[name="Door.001"]`. All the filters must
+/// match for a node to be selected.
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::algebra::Vector3,
+/// #     graph::BaseSceneGraph,
+/// #     scene::{base::BaseBuilder, graph::{query::NodeQuery, Graph}, pivot::PivotBuilder},
+/// # };
+/// let mut graph = Graph::new();
+/// PivotBuilder::new(BaseBuilder::new().with_name("Enemy1")).build(&mut graph);
+/// PivotBuilder::new(BaseBuilder::new().with_name("Enemy2")).build(&mut graph);
+///
+/// let query = "Pivot[name=Enemy*].base.local_transform.local_position"
+///     .parse::<NodeQuery>()
+///     .unwrap();
+/// assert_eq!(graph.select(&query).len(), 2);
+/// for (_, result) in graph.batch_set_property(&query, Vector3::new(1.0f32, 2.0, 3.0)) {
+///     assert!(result.is_ok());
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct NodeQuery {
+    type_name: Option<String>,
+    name: Option<String>,
+    tag: Option<String>,
+    scripts: Vec<String>,
+    components: Vec<TypeId>,
+    property: Option<String>,
+}
+
+impl NodeQuery {
+    /// Creates a query that selects every node.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Selects nodes of the type with the given short name (`Mesh`, `Pivot`, etc.).
+    pub fn with_type(mut self, type_name: &str) -> Self {
+        self.type_name = Some(type_name.to_string());
+        self
+    }
+
+    /// Selects nodes with names that match the glob pattern.
+    pub fn with_name(mut self, pattern: &str) -> Self {
+        self.name = Some(pattern.to_string());
+        self
+    }
+
+    /// Selects nodes with tags that match the glob pattern.
+    pub fn with_tag(mut self, pattern: &str) -> Self {
+        self.tag = Some(pattern.to_string());
+        self
+    }
+
+    /// Selects nodes that have a script with the given short type name.
+    pub fn with_script(mut self, type_name: &str) -> Self {
+        self.scripts.push(type_name.to_string());
+        self
+    }
+
+    /// Selects nodes that provide a component of the given type (see
+    /// [`crate::scene::node::NodeTrait::query_component_ref`]).
+    pub fn with_component<T: 'static>(mut self) -> Self {
+        self.components.push(TypeId::of::<T>());
+        self
+    }
+
+    /// Sets a path of a reflected property of the selected nodes.
+    pub fn with_property(mut self, path: &str) -> Self {
+        self.property = Some(path.to_string());
+        self
+    }
+
+    /// Returns the property path of the query, if any.
+    pub fn property(&self) -> Option<&str> {
+        self.property.as_deref()
+    }
+
+    /// Checks whether the node is selected by the query.
+    pub fn matches(&self, node: &Node) -> bool {
+        self.type_name
+            .as_ref()
+            .map_or(true, |t| short_type_name(Reflect::type_name(node)) == t)
+            && self
+                .name
+                .as_ref()
+                .map_or(true, |pattern| glob_match(pattern, node.name()))
+            && self
+                .tag
+                .as_ref()
+                .map_or(true, |pattern| glob_match(pattern, node.tag()))
+            && self.scripts.iter().all(|script| {
+                node.scripts()
+                    .any(|s| short_type_name(s.type_name()) == script)
+            })
+            && self
+                .components
+                .iter()
+                .all(|type_id| (**node).query_component_ref(*type_id).is_some())
+    }
+}
+
+/// Splits the string at the first occurrence of `c` that is not quoted.
+fn split_unquoted(s: &str, c: char) -> Option<(&str, &str)> {
+    let mut quoted = false;
+    for (i, ch) in s.char_indices() {
+        if ch == '"' {
+            quoted = !quoted;
+        } else if ch == c && !quoted {
+            return Some((&s[..i], &s[i + ch.len_utf8()..]));
+        }
+    }
+    None
+}
+
+impl FromStr for NodeQuery {
+    type Err = NodeQueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let mut query = NodeQuery::new();
+
+        let type_end = s.find(['[', '.']).unwrap_or(s.len());
+        let type_name = s[..type_end].trim();
+        if !type_name.is_empty() && type_name != "*" {
+            query.type_name = Some(type_name.to_string());
+        }
+
+        let mut rest = &s[type_end..];
+        while let Some(filter) = rest.strip_prefix('[') {
+            let (filter, tail) = split_unquoted(filter, ']')
+                .ok_or_else(|| NodeQueryError::UnclosedBrackets(rest.to_string()))?;
+            let (name, value) = filter
+                .split_once('=')
+                .ok_or_else(|| NodeQueryError::InvalidFilter(filter.to_string()))?;
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value)
+                .to_string();
+            match name.trim() {
+                "name" => query.name = Some(value),
+                "tag" => query.tag = Some(value),
+                "script" => query.scripts.push(value),
+                name => return Err(NodeQueryError::UnknownFilter(name.to_string())),
+            }
+            rest = tail.trim_start();
+        }
+
+        if let Some(path) = rest.strip_prefix('.') {
+            if path.is_empty() {
+                return Err(NodeQueryError::EmptyPath);
+            }
+            query.property = Some(path.to_string());
+        } else if !rest.is_empty() {
+            return Err(NodeQueryError::InvalidFilter(rest.to_string()));
+        }
+
+        Ok(query)
+    }
+}
+
+/// An error that may occur when a property of a selected node is set by
+/// [`Graph::batch_set_property`].
+#[derive(Debug)]
+pub enum BatchEditError {
+    /// The query has no property path.
+    NoProperty,
+    /// The property path is invalid for the node.
+    InvalidPath(String),
+    /// The value has a type that does not match the type of the property.
+    InvalidValue(Box<dyn Reflect>),
+}
+
+impl Display for BatchEditError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BatchEditError::NoProperty => write!(f, "the query has no property path"),
+            BatchEditError::InvalidPath(reason) => write!(f, "invalid property path: {reason}"),
+            BatchEditError::InvalidValue(value) => {
+                write!(f, "value of type {} does not match", value.type_name())
+            }
+        }
+    }
+}
+
+/// Result of setting a property of a single node. Contains the previous value on success, so it
+/// could be used to revert the edit.
+pub type BatchEditResult = Result<Box<dyn Reflect>, BatchEditError>;
+
+impl Graph {
+    /// Returns handles of the nodes selected by the query, in depth-first order starting from the
+    /// root.
+    pub fn select(&self, query: &NodeQuery) -> Vec<Handle<Node>> {
+        let mut selected = Vec::new();
+        let mut stack = vec![self.get_root()];
+        while let Some(handle) = stack.pop() {
+            let node = &self[handle];
+            if query.matches(node) {
+                selected.push(handle);
+            }
+            stack.extend(node.children().iter().rev());
+        }
+        selected
+    }
+
+    /// Calls the given closure with the property addressed by the query for every selected node.
+    /// The whole node is passed, if the query has no property path.
+    pub fn query_property<F>(&self, query: &NodeQuery, mut func: F)
+    where
+        F: FnMut(Handle<Node>, Result<&dyn Reflect, ReflectPathError>),
+    {
+        for handle in self.select(query) {
+            let node = &self[handle] as &dyn Reflect;
+            match query.property() {
+                Some(path) => node.resolve_path(path, &mut |result| func(handle, result)),
+                None => func(handle, Ok(node)),
+            }
+        }
+    }
+
+    /// Sets the property addressed by the query for every selected node to a value produced by
+    /// the given closure. Property setters are used, so the nodes stay consistent. Returns the
+    /// result of the edit for every selected node.
+    pub fn batch_set_property_with<F>(
+        &mut self,
+        query: &NodeQuery,
+        mut value: F,
+    ) -> Vec<(Handle<Node>, BatchEditResult)>
+    where
+        F: FnMut() -> Box<dyn Reflect>,
+    {
+        let selected = self.select(query);
+        let Some(path) = query.property() else {
+            return selected
+                .into_iter()
+                .map(|handle| (handle, Err(BatchEditError::NoProperty)))
+                .collect();
+        };
+        selected
+            .into_iter()
+            .map(|handle| {
+                // Unknown fields are reported by `set_field` as invalid values, check the path
+                // first to report them properly.
+                let mut path_error = None;
+                (&self[handle] as &dyn Reflect).resolve_path(path, &mut |result| {
+                    path_error = result.err().map(|reason| reason.to_string())
+                });
+                if let Some(reason) = path_error {
+                    return (handle, Err(BatchEditError::InvalidPath(reason)));
+                }
+                let mut edit_result = Err(BatchEditError::NoProperty);
+                (&mut self[handle] as &mut dyn Reflect).set_field_by_path(
+                    path,
+                    value(),
+                    &mut |result| {
+                        edit_result = result.map_err(|err| match err {
+                            SetFieldByPathError::InvalidPath { reason, .. } => {
+                                BatchEditError::InvalidPath(reason.to_string())
+                            }
+                            SetFieldByPathError::InvalidValue(value) => {
+                                BatchEditError::InvalidValue(value)
+                            }
+                        })
+                    },
+                );
+                (handle, edit_result)
+            })
+            .collect()
+    }
+
+    /// Sets the property addressed by the query for every selected node to a copy of the value.
+    /// See [`Self::batch_set_property_with`] docs for more info.
+    pub fn batch_set_property<T: Reflect + Clone>(
+        &mut self,
+        query: &NodeQuery,
+        value: T,
+    ) -> Vec<(Handle<Node>, BatchEditResult)> {
+        self.batch_set_property_with(query, || Box::new(value.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        scene::{
+            base::BaseBuilder,
+            graph::{
+                query::{glob_match, BatchEditError, NodeQuery, NodeQueryError},
+                Graph,
+            },
+            pivot::PivotBuilder,
+            sprite::SpriteBuilder,
+        },
+    };
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("Enemy*", "Enemy12"));
+        assert!(glob_match("*12", "Enemy12"));
+        assert!(glob_match("E?emy*2", "Enemy12"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("Enemy?", "Enemy12"));
+        assert!(!glob_match("*3", "Enemy12"));
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let query = "Mesh[name=\"Door.*\"][tag=door].base.visibility"
+            .parse::<NodeQuery>()
+            .unwrap();
+        assert_eq!(query.type_name.as_deref(), Some("Mesh"));
+        assert_eq!(query.name.as_deref(), Some("Door.*"));
+        assert_eq!(query.tag.as_deref(), Some("door"));
+        assert_eq!(query.property(), Some("base.visibility"));
+
+        let query = "[script=Health]".parse::<NodeQuery>().unwrap();
+        assert_eq!(query.type_name, None);
+        assert_eq!(query.scripts, vec!["Health".to_string()]);
+
+        assert_eq!(
+            "*[name=A".parse::<NodeQuery>().unwrap_err(),
+            NodeQueryError::UnclosedBrackets("[name=A".to_string())
+        );
+        assert_eq!(
+            "*[color=red]".parse::<NodeQuery>().unwrap_err(),
+            NodeQueryError::UnknownFilter("color".to_string())
+        );
+    }
+
+    #[test]
+    fn test_batch_set_property() {
+        let mut graph = Graph::new();
+        let a = PivotBuilder::new(BaseBuilder::new().with_name("EnemyA")).build(&mut graph);
+        let b = PivotBuilder::new(BaseBuilder::new().with_name("EnemyB")).build(&mut graph);
+        let c = SpriteBuilder::new(BaseBuilder::new().with_name("EnemyC")).build(&mut graph);
+
+        let query = "Pivot[name=Enemy*].base.local_transform.local_position"
+            .parse::<NodeQuery>()
+            .unwrap();
+        assert_eq!(graph.select(&query), vec![a, b]);
+
+        let position = Vector3::new(1.0, 2.0, 3.0);
+        let results = graph.batch_set_property(&query, position);
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|(_, result)| result.is_ok()));
+        for handle in [a, b] {
+            assert_eq!(**graph[handle].local_transform().position(), position);
+        }
+        assert_eq!(**graph[c].local_transform().position(), Vector3::default());
+
+        let mut count = 0;
+        graph.query_property(&query, |_, result| {
+            result.unwrap().downcast_ref::<Vector3<f32>>(&mut |value| {
+                assert_eq!(value, Some(&position));
+                count += 1;
+            })
+        });
+        assert_eq!(count, 2);
+
+        let results = graph.batch_set_property(&query, 1.0f32);
+        assert!(matches!(results[0].1, Err(BatchEditError::InvalidValue(_))));
+        let query = query.with_property("base.no_such_field");
+        let results = graph.batch_set_property(&query, 1.0f32);
+        assert!(matches!(results[0].1, Err(BatchEditError::InvalidPath(_))));
+    }
+}
@@ -35,7 +35,7 @@ use crate::{
     scene::{
         base::{Base, BaseBuilder},
         graph::Graph,
-        node::{Node, NodeTrait, SyncContext},
+        node::{Node, NodeTrait, SyncContext, UpdateContext},
         rigidbody::RigidBody,
         Scene,
     },
@@ -52,6 +52,83 @@ use std::{
 };
 use strum_macros::{AsRefStr, EnumString, VariantNames};
 
+/// Defines how a joint motor computes its force.
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Eq, Visit, Reflect, AsRefStr, EnumString, VariantNames,
+)]
+pub enum JointMotorModel {
+    /// The stiffness and damping are automatically scaled by the mass of the bodies, which makes
+    /// the motor easier to tune.
+    #[default]
+    AccelerationBased,
+    /// The stiffness and damping are used as is, which makes the motor behave like a real spring.
+    ForceBased,
+}
+
+uuid_provider!(JointMotorModel = "0a8c2d4e-8c1f-4a57-9c64-5b7f1e2d3a91");
+
+/// A motor that drives a joint axis towards a target position and/or velocity. The motor acts as
+/// a spring-damper: `stiffness` pulls the axis towards [`Self::target_position`] and `damping`
+/// pulls its velocity towards [`Self::target_velocity`]. A pure velocity motor (for example a fan)
+/// has zero stiffness and non-zero damping, a spring has non-zero stiffness and zero target
+/// velocity.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct JointMotor {
+    /// Whether the motor is enabled or not. Default is `false`.
+    #[reflect(description = "Whether the motor is enabled or not.")]
+    pub enabled: bool,
+
+    /// Target position of the motor (in radians for angular axes).
+    #[reflect(description = "Target position of the motor (in radians for angular axes).")]
+    pub target_position: f32,
+
+    /// Target velocity of the motor (in radians per second for angular axes).
+    #[reflect(
+        description = "Target velocity of the motor (in radians per second for angular axes)."
+    )]
+    pub target_velocity: f32,
+
+    /// Stiffness of the motor, defines how strong the motor pulls the axis to the target position.
+    #[reflect(
+        description = "Stiffness of the motor, defines how strong the motor pulls the axis to the target position.",
+        min_value = 0.0
+    )]
+    pub stiffness: f32,
+
+    /// Damping of the motor, defines how strong the motor pulls the axis velocity to the target
+    /// velocity.
+    #[reflect(
+        description = "Damping of the motor, defines how strong the motor pulls the axis velocity to the target velocity.",
+        min_value = 0.0
+    )]
+    pub damping: f32,
+
+    /// Maximum force (or torque for angular axes) the motor can apply.
+    #[reflect(
+        description = "Maximum force (or torque for angular axes) the motor can apply.",
+        min_value = 0.0
+    )]
+    pub max_force: f32,
+
+    /// Defines how the motor computes its force.
+    #[reflect(description = "Defines how the motor computes its force.")]
+    pub model: JointMotorModel,
+}
+
+impl Default for JointMotor {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_position: 0.0,
+            target_velocity: 0.0,
+            stiffness: 0.0,
+            damping: 0.0,
+            max_force: f32::MAX,
+            model: Default::default(),
+        }
+    }
+}
+
 /// Ball joint locks any translational moves between two objects on the axis between objects, but
 /// allows rigid bodies to perform relative rotations. The real world example is a human shoulder,
 /// pendulum, etc.
@@ -86,6 +163,21 @@ pub struct BallJoint {
     #[reflect(description = "Allowed angle range around local Z axis of the joint (in radians).")]
     #[visit(optional)] // Backward compatibility
     pub z_limits_angles: Range<f32>,
+
+    /// A motor that drives rotation around local X axis of the joint.
+    #[reflect(description = "A motor that drives rotation around local X axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub x_motor: JointMotor,
+
+    /// A motor that drives rotation around local Y axis of the joint.
+    #[reflect(description = "A motor that drives rotation around local Y axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub y_motor: JointMotor,
+
+    /// A motor that drives rotation around local Z axis of the joint.
+    #[reflect(description = "A motor that drives rotation around local Z axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub z_motor: JointMotor,
 }
 
 impl Default for BallJoint {
@@ -97,6 +189,9 @@ impl Default for BallJoint {
             y_limits_angles: -std::f32::consts::PI..std::f32::consts::PI,
             z_limits_enabled: false,
             z_limits_angles: -std::f32::consts::PI..std::f32::consts::PI,
+            x_motor: Default::default(),
+            y_motor: Default::default(),
+            z_motor: Default::default(),
         }
     }
 }
@@ -121,6 +216,11 @@ pub struct PrismaticJoint {
     )]
     #[visit(optional)] // Backward compatibility
     pub limits: Range<f32>,
+
+    /// A motor that drives translation along local X axis of the joint.
+    #[reflect(description = "A motor that drives translation along local X axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub motor: JointMotor,
 }
 
 impl Default for PrismaticJoint {
@@ -128,6 +228,7 @@ impl Default for PrismaticJoint {
         Self {
             limits_enabled: false,
             limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
         }
     }
 }
@@ -148,6 +249,11 @@ pub struct RevoluteJoint {
     #[reflect(description = "Allowed angle range around local X axis of the joint (in radians).")]
     #[visit(optional)] // Backward compatibility
     pub limits: Range<f32>,
+
+    /// A motor that drives rotation around local X axis of the joint.
+    #[reflect(description = "A motor that drives rotation around local X axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub motor: JointMotor,
 }
 
 impl Default for RevoluteJoint {
@@ -155,6 +261,123 @@ impl Default for RevoluteJoint {
         Self {
             limits_enabled: false,
             limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
+        }
+    }
+}
+
+/// Parameters of a single axis of [`SixDofJoint`].
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct JointAxisParams {
+    /// Whether the axis is locked or not. Locked axis does not allow any relative movement of the
+    /// bodies along (or around) it. Default is `false`.
+    #[reflect(description = "Whether the axis is locked or not.")]
+    pub locked: bool,
+
+    /// Whether limits of the axis are enabled or not. Default is `false`.
+    #[reflect(description = "Whether limits of the axis are enabled or not.")]
+    pub limits_enabled: bool,
+
+    /// Allowed range of relative movement along (or around) the axis. Angular limits are defined
+    /// in radians.
+    #[reflect(
+        description = "Allowed range of relative movement along (or around) the axis. Angular limits are defined in radians."
+    )]
+    pub limits: Range<f32>,
+
+    /// A motor that drives the axis.
+    #[reflect(description = "A motor that drives the axis.")]
+    pub motor: JointMotor,
+}
+
+impl Default for JointAxisParams {
+    fn default() -> Self {
+        Self {
+            locked: false,
+            limits_enabled: false,
+            limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
+        }
+    }
+}
+
+/// Six degrees of freedom joint is a generic joint that allows you to configure each of its linear
+/// and angular axes separately - lock it, limit it or drive it with a motor. It can be used to
+/// create joints that can't be built with other kinds of joints, for example a crane hook that
+/// can slide along one axis and swing around two others.
+#[derive(Clone, Debug, Default, Visit, PartialEq, Reflect)]
+pub struct SixDofJoint {
+    /// Parameters of the local X axis of the joint (translation).
+    #[reflect(description = "Parameters of the local X axis of the joint (translation).")]
+    pub linear_x: JointAxisParams,
+
+    /// Parameters of the local Y axis of the joint (translation).
+    #[reflect(description = "Parameters of the local Y axis of the joint (translation).")]
+    pub linear_y: JointAxisParams,
+
+    /// Parameters of the local Z axis of the joint (translation).
+    #[reflect(description = "Parameters of the local Z axis of the joint (translation).")]
+    pub linear_z: JointAxisParams,
+
+    /// Parameters of the local X axis of the joint (rotation).
+    #[reflect(description = "Parameters of the local X axis of the joint (rotation).")]
+    pub angular_x: JointAxisParams,
+
+    /// Parameters of the local Y axis of the joint (rotation).
+    #[reflect(description = "Parameters of the local Y axis of the joint (rotation).")]
+    pub angular_y: JointAxisParams,
+
+    /// Parameters of the local Z axis of the joint (rotation).
+    #[reflect(description = "Parameters of the local Z axis of the joint (rotation).")]
+    pub angular_z: JointAxisParams,
+}
+
+/// Rope joint limits the maximum distance between two bodies, but allows them to move freely
+/// while the distance is less than the maximum. The real world example is a rope or a chain.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct RopeJoint {
+    /// Maximum distance between the anchors of the attached bodies.
+    #[reflect(
+        description = "Maximum distance between the anchors of the attached bodies.",
+        min_value = 0.0
+    )]
+    pub max_distance: f32,
+}
+
+impl Default for RopeJoint {
+    fn default() -> Self {
+        Self { max_distance: 1.0 }
+    }
+}
+
+/// Spring joint tries to keep the distance between two bodies equal to its rest length, it acts
+/// like a spring that connects the bodies. The real world example is a car suspension spring or
+/// a bungee cord.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct SpringJoint {
+    /// The distance between the anchors of the attached bodies at which the spring applies no
+    /// force.
+    #[reflect(
+        description = "The distance between the anchors of the attached bodies at which the spring applies no force.",
+        min_value = 0.0
+    )]
+    pub rest_length: f32,
+
+    /// Stiffness of the spring.
+    #[reflect(description = "Stiffness of the spring.", min_value = 0.0)]
+    pub stiffness: f32,
+
+    /// Damping of the spring.
+    #[reflect(description = "Damping of the spring.", min_value = 0.0)]
+    pub damping: f32,
+}
+
+impl Default for SpringJoint {
+    fn default() -> Self {
+        Self {
+            rest_length: 1.0,
+            stiffness: 100.0,
+            damping: 1.0,
         }
     }
 }
@@ -170,6 +393,12 @@ pub enum JointParams {
     PrismaticJoint(PrismaticJoint),
     /// See [`RevoluteJoint`] for more info.
     RevoluteJoint(RevoluteJoint),
+    /// See [`SixDofJoint`] for more info.
+    SixDofJoint(SixDofJoint),
+    /// See [`RopeJoint`] for more info.
+    RopeJoint(RopeJoint),
+    /// See [`SpringJoint`] for more info.
+    SpringJoint(SpringJoint),
 }
 
 uuid_provider!(JointParams = "a3e09303-9de4-4123-9492-05e27f29aaa3");
@@ -233,6 +462,18 @@ pub struct Joint {
     #[visit(optional)] // Backward compatibility
     pub(crate) auto_rebind: InheritableVariable<bool>,
 
+    #[reflect(setter = "set_break_force", min_value = 0.0)]
+    #[visit(optional)] // Backward compatibility
+    pub(crate) break_force: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_break_torque", min_value = 0.0)]
+    #[visit(optional)] // Backward compatibility
+    pub(crate) break_torque: InheritableVariable<f32>,
+
+    #[visit(optional)]
+    #[reflect(hidden)]
+    pub(crate) broken: bool,
+
     #[visit(optional)]
     #[reflect(hidden)]
     pub(crate) local_frames: RefCell<Option<JointLocalFrames>>,
@@ -251,6 +492,9 @@ impl Default for Joint {
             body2: Default::default(),
             contacts_enabled: InheritableVariable::new_modified(true),
             auto_rebind: true.into(),
+            break_force: f32::MAX.into(),
+            break_torque: f32::MAX.into(),
+            broken: false,
             local_frames: Default::default(),
             native: Cell::new(ImpulseJointHandle::invalid()),
         }
@@ -280,8 +524,11 @@ impl Clone for Joint {
             body2: self.body2.clone(),
             contacts_enabled: self.contacts_enabled.clone(),
             local_frames: self.local_frames.clone(),
-            // Do not copy. The copy will have its own native representation.
             auto_rebind: self.auto_rebind.clone(),
+            break_force: self.break_force.clone(),
+            break_torque: self.break_torque.clone(),
+            broken: self.broken,
+            // Do not copy. The copy will have its own native representation.
             native: Cell::new(ImpulseJointHandle::invalid()),
         }
     }
@@ -352,6 +599,42 @@ impl Joint {
     pub fn is_auto_rebinding_enabled(&self) -> bool {
         *self.auto_rebind
     }
+
+    /// Sets the maximum linear force the joint can withstand. When the force applied by the joint
+    /// exceeds this value, the joint breaks (see [`Self::is_broken`]). Default is [`f32::MAX`],
+    /// which means that the joint is unbreakable.
+    pub fn set_break_force(&mut self, force: f32) -> f32 {
+        self.break_force.set_value_and_mark_modified(force)
+    }
+
+    /// Returns the maximum linear force the joint can withstand.
+    pub fn break_force(&self) -> f32 {
+        *self.break_force
+    }
+
+    /// Sets the maximum torque the joint can withstand. When the torque applied by the joint
+    /// exceeds this value, the joint breaks (see [`Self::is_broken`]). Default is [`f32::MAX`],
+    /// which means that the joint is unbreakable.
+    pub fn set_break_torque(&mut self, torque: f32) -> f32 {
+        self.break_torque.set_value_and_mark_modified(torque)
+    }
+
+    /// Returns the maximum torque the joint can withstand.
+    pub fn break_torque(&self) -> f32 {
+        *self.break_torque
+    }
+
+    /// Returns `true` if the joint was broken because of exceeding its break force or torque. Broken
+    /// joint does not affect the attached bodies. Scripts are notified about breaking via
+    /// [`crate::script::ScriptTrait::on_joint_break`].
+    pub fn is_broken(&self) -> bool {
+        self.broken
+    }
+
+    /// Sets whether the joint is broken or not. Could be used to repair a broken joint.
+    pub fn set_broken(&mut self, broken: bool) {
+        self.broken = broken;
+    }
 }
 
 impl NodeTrait for Joint {
@@ -391,6 +674,12 @@ impl NodeTrait for Joint {
         }
     }
 
+    fn update(&mut self, context: &mut UpdateContext) {
+        if let Some(enabled) = context.physics.is_joint_enabled(self.native.get()) {
+            self.broken = !enabled;
+        }
+    }
+
     fn validate(&self, scene: &Scene) -> Result<(), String> {
         if let Some(body1) = scene.graph.try_get(self.body1()) {
             if body1.query_component_ref::<RigidBody>().is_none() {
@@ -428,6 +717,8 @@ pub struct JointBuilder {
     body2: Handle<Node>,
     contacts_enabled: bool,
     auto_rebind: bool,
+    break_force: f32,
+    break_torque: f32,
 }
 
 impl JointBuilder {
@@ -440,6 +731,8 @@ impl JointBuilder {
             body2: Default::default(),
             contacts_enabled: true,
             auto_rebind: true,
+            break_force: f32::MAX,
+            break_torque: f32::MAX,
         }
     }
 
@@ -476,6 +769,18 @@ impl JointBuilder {
         self
     }
 
+    /// Sets the maximum linear force the joint can withstand before breaking.
+    pub fn with_break_force(mut self, force: f32) -> Self {
+        self.break_force = force;
+        self
+    }
+
+    /// Sets the maximum torque the joint can withstand before breaking.
+    pub fn with_break_torque(mut self, torque: f32) -> Self {
+        self.break_torque = torque;
+        self
+    }
+
     /// Creates new Joint node, but does not add it to the graph.
     pub fn build_joint(self) -> Joint {
         Joint {
@@ -485,6 +790,9 @@ impl JointBuilder {
             body2: self.body2.into(),
             contacts_enabled: self.contacts_enabled.into(),
             auto_rebind: self.auto_rebind.into(),
+            break_force: self.break_force.into(),
+            break_torque: self.break_torque.into(),
+            broken: false,
             local_frames: Default::default(),
             native: Cell::new(ImpulseJointHandle::invalid()),
         }
@@ -500,3 +808,97 @@ impl JointBuilder {
         graph.add_node(self.build_node())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Vector2, Vector3},
+        scene::{
+            base::BaseBuilder,
+            collider::{ColliderBuilder, ColliderShape},
+            graph::Graph,
+            joint::{FixedJoint, JointBuilder, JointMotor, JointParams, RevoluteJoint},
+            node::Node,
+            rigidbody::{RigidBodyBuilder, RigidBodyType},
+            transform::TransformBuilder,
+        },
+    };
+    use fyrox_core::pool::Handle;
+
+    fn create_body(graph: &mut Graph, body_type: RigidBodyType, y: f32) -> Handle<Node> {
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(0.25, 0.25, 0.25))
+            .build(graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_children(&[collider])
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, y, 0.0))
+                        .build(),
+                ),
+        )
+        .with_body_type(body_type)
+        .build(graph)
+    }
+
+    #[test]
+    fn test_joint_breaks_under_load() {
+        let mut graph = Graph::new();
+
+        let anchor = create_body(&mut graph, RigidBodyType::Static, 0.0);
+        let load = create_body(&mut graph, RigidBodyType::Dynamic, -1.0);
+        let joint = JointBuilder::new(BaseBuilder::new())
+            .with_params(JointParams::FixedJoint(FixedJoint))
+            .with_body1(anchor)
+            .with_body2(load)
+            .with_contacts_enabled(false)
+            .with_break_force(1.0)
+            .build(&mut graph);
+
+        let mut broken_at_frame = false;
+        for _ in 0..60 {
+            graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+            broken_at_frame |= graph.physics.broken_joints().contains(&joint);
+        }
+
+        assert!(broken_at_frame);
+        assert!(graph[joint].as_joint().is_broken());
+        // The load is not held by the joint anymore.
+        assert!(graph[load].global_position().y < -1.5);
+    }
+
+    #[test]
+    fn test_revolute_joint_velocity_motor() {
+        let mut graph = Graph::new();
+
+        let anchor = create_body(&mut graph, RigidBodyType::Static, 0.0);
+        let wheel = create_body(&mut graph, RigidBodyType::Dynamic, 0.0);
+        graph[wheel].as_rigid_body_mut().set_gravity_scale(0.0);
+        JointBuilder::new(BaseBuilder::new())
+            .with_params(JointParams::RevoluteJoint(RevoluteJoint {
+                motor: JointMotor {
+                    enabled: true,
+                    target_velocity: 2.0,
+                    damping: 100.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }))
+            .with_body1(anchor)
+            .with_body2(wheel)
+            .with_contacts_enabled(false)
+            .build(&mut graph);
+
+        for _ in 0..60 {
+            graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        }
+
+        let angular_velocity = graph[wheel].as_rigid_body().ang_vel();
+        assert!(
+            (angular_velocity.x.abs() - 2.0).abs() < 0.1,
+            "{:?}",
+            angular_velocity
+        );
+    }
+}
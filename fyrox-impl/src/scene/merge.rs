@@ -0,0 +1,1044 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Structural three-way merge of scene files. See [`merge_scenes`] docs for more info.
+//!
+//! # Git integration
+//!
+//! The `fyrox-scene-merge` tool (shipped with the standalone editor) can be registered as a git
+//! merge driver, so git will use it instead of the line-based merge for scene files:
+//!
+//! ```text
+//! # .gitattributes
+//! *.rgs merge=fyrox-scene
+//!
+//! # .git/config
+//! [merge "fyrox-scene"]
+//!     name = Fyrox scene merge
+//!     driver = fyrox-scene-merge %O %A %B
+//! ```
+
+use crate::{
+    core::{
+        pool::Handle,
+        uuid::Uuid,
+        visitor::{Field, FieldKind, VisitError, VisitResult, Visitor, VisitorNode},
+    },
+    scene::base::SceneNodeId,
+};
+use fxhash::FxHashMap;
+use std::{
+    fmt::{Display, Formatter},
+    path::Path,
+};
+
+/// Name of the field that temporarily marks a handle as a reference to a scene node with the id.
+pub(super) const NODE_ID_FIELD: &str = "__SceneNodeId";
+
+/// A kind of a conflict found by [`merge_scenes`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SceneMergeConflictKind {
+    /// Both sides changed a property (or a node) in different ways. Our version is kept.
+    BothModified,
+    /// We changed a property (or a node), but they deleted it. Our version is kept.
+    ModifiedDeleted,
+    /// We deleted a property (or a node), but they changed it. It stays deleted.
+    DeletedModified,
+    /// A handle references a node that does not exist in the merged scene. The handle is kept as is.
+    DanglingReference,
+}
+
+/// A conflict found by [`merge_scenes`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SceneMergeConflict {
+    /// Id of a scene node with the conflict, `None` if the conflict is outside of the scene nodes.
+    pub node: Option<SceneNodeId>,
+    /// Name of the scene node with the conflict (empty if there is no node).
+    pub node_name: String,
+    /// Path of the conflicting property in the serialized data, for example
+    /// `NodeData/Transform/LocalPosition`.
+    pub property: String,
+    /// Kind of the conflict.
+    pub kind: SceneMergeConflictKind,
+}
+
+impl Display for SceneMergeConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            SceneMergeConflictKind::BothModified => "modified on both sides",
+            SceneMergeConflictKind::ModifiedDeleted => "modified by us, deleted by them",
+            SceneMergeConflictKind::DeletedModified => "deleted by us, modified by them",
+            SceneMergeConflictKind::DanglingReference => "references a deleted node",
+        };
+        match self.node {
+            Some(id) => write!(
+                f,
+                "node `{}` ({}): `{}` {kind}",
+                self.node_name, id.0, self.property
+            ),
+            None => write!(f, "`{}` {kind}", self.property),
+        }
+    }
+}
+
+/// Result of [`merge_scenes`].
+pub struct SceneMerge {
+    /// Merged data, ready to be saved. If there are conflicts, it contains our version of every
+    /// conflicting property.
+    pub visitor: Visitor,
+    /// A list of conflicts that must be resolved manually.
+    pub conflicts: Vec<SceneMergeConflict>,
+}
+
+/// A region of visitor's data detached from the visitor, so it can be freely edited.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub(super) struct Region {
+    pub(super) name: String,
+    pub(super) fields: Vec<(String, FieldKind)>,
+    pub(super) children: Vec<Region>,
+}
+
+impl Region {
+    pub(super) fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub(super) fn from_visitor(visitor: &Visitor, handle: Handle<VisitorNode>) -> Self {
+        let node = visitor
+            .node(handle)
+            .expect("Handles of a visitor's nodes must be valid!");
+        Self {
+            name: node.name().to_string(),
+            fields: node
+                .fields()
+                .iter()
+                .map(|field| (field.name().to_string(), field.kind().clone()))
+                .collect(),
+            children: node
+                .children()
+                .iter()
+                .map(|child| Self::from_visitor(visitor, *child))
+                .collect(),
+        }
+    }
+
+    pub(super) fn write_contents(&self, visitor: &mut Visitor) -> VisitResult {
+        for (name, kind) in self.fields.iter() {
+            visitor.write_field(Field::new(name, kind.clone()))?;
+        }
+        for child in self.children.iter() {
+            let mut region = visitor.enter_region(&child.name)?;
+            child.write_contents(&mut region)?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn with_field(mut self, name: &str, kind: FieldKind) -> Self {
+        self.fields.push((name.to_string(), kind));
+        self
+    }
+
+    pub(super) fn with_child(mut self, child: Region) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub(super) fn field(&self, name: &str) -> Option<&FieldKind> {
+        self.fields.iter().find(|(n, _)| n == name).map(|(_, k)| k)
+    }
+
+    pub(super) fn u32_field(&self, name: &str) -> Option<u32> {
+        match self.field(name) {
+            Some(FieldKind::U32(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub(super) fn child(&self, name: &str) -> Option<&Region> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    pub(super) fn child_mut(&mut self, name: &str) -> Option<&mut Region> {
+        self.children.iter_mut().find(|c| c.name == name)
+    }
+
+    pub(super) fn take_child(&mut self, name: &str) -> Option<Region> {
+        let index = self.children.iter().position(|c| c.name == name)?;
+        Some(self.children.remove(index))
+    }
+
+    pub(super) fn path(&self, path: &[&str]) -> Option<&Region> {
+        path.iter()
+            .try_fold(self, |region, name| region.child(name))
+    }
+
+    pub(super) fn path_mut(&mut self, path: &[&str]) -> Option<&mut Region> {
+        path.iter()
+            .try_fold(self, |region, name| region.child_mut(name))
+    }
+
+    /// Returns `(index, generation)` pair if the region is a serialized [`Handle`].
+    pub(super) fn as_handle(&self) -> Option<(u32, u32)> {
+        if self.children.is_empty() && self.fields.len() == 2 {
+            Some((self.u32_field("Index")?, self.u32_field("Generation")?))
+        } else {
+            None
+        }
+    }
+
+    pub(super) fn node_ref(&self) -> Option<Uuid> {
+        match self.field(NODE_ID_FIELD) {
+            Some(FieldKind::Uuid(id)) => Some(*id),
+            _ => None,
+        }
+    }
+
+    /// Marks the region with the id of a scene node if the region is a handle that points to the
+    /// node. Returns `false` if the region is not a handle.
+    pub(super) fn mark_node_ref(&mut self, ids: &FxHashMap<(u32, u32), Uuid>) -> bool {
+        let Some(handle) = self.as_handle() else {
+            return false;
+        };
+        if let Some(id) = ids.get(&handle) {
+            self.fields
+                .push((NODE_ID_FIELD.to_string(), FieldKind::Uuid(*id)));
+        }
+        true
+    }
+
+    /// Marks every handle in the region that points to a scene node with the id of the node.
+    pub(super) fn mark_node_refs(&mut self, ids: &FxHashMap<(u32, u32), Uuid>) {
+        for child in self.children.iter_mut() {
+            // The handle of the node in the source prefab does not point to this graph.
+            if child.name != "Original" && !child.mark_node_ref(ids) {
+                child.mark_node_refs(ids);
+            }
+        }
+    }
+
+    /// Replaces every marked node reference with the handle of the node in the merged graph.
+    /// Paths of the references to missing nodes are added to `dangling`.
+    pub(super) fn resolve_node_refs(
+        &mut self,
+        path: &str,
+        handles: &FxHashMap<Uuid, (u32, u32)>,
+        dangling: &mut Vec<String>,
+        keep_dangling: bool,
+    ) {
+        for child in self.children.iter_mut() {
+            let child_path = join_path(path, &child.name);
+            if let Some(id) = child.node_ref() {
+                child.fields.retain(|(name, _)| name != NODE_ID_FIELD);
+                if let Some((index, generation)) = handles.get(&id) {
+                    child.fields = vec![
+                        ("Index".to_string(), FieldKind::U32(*index)),
+                        ("Generation".to_string(), FieldKind::U32(*generation)),
+                    ];
+                } else {
+                    if !keep_dangling {
+                        child.fields = vec![
+                            ("Index".to_string(), FieldKind::U32(0)),
+                            ("Generation".to_string(), FieldKind::U32(0)),
+                        ];
+                    }
+                    dangling.push(child_path);
+                }
+            } else {
+                child.resolve_node_refs(&child_path, handles, dangling, keep_dangling);
+            }
+        }
+    }
+
+    /// Checks whether the region is a serialized [`crate::scene::base::Base`] of a scene node.
+    pub(super) fn is_base(&self) -> bool {
+        ["Parent", "Transform", "Original"]
+            .iter()
+            .all(|name| self.child(name).is_some())
+    }
+
+    pub(super) fn base(&self) -> Option<&Region> {
+        if self.is_base() {
+            Some(self)
+        } else {
+            self.children.iter().find_map(|c| c.base())
+        }
+    }
+
+    pub(super) fn base_mut(&mut self) -> Option<&mut Region> {
+        if self.is_base() {
+            Some(self)
+        } else {
+            self.children.iter_mut().find_map(|c| c.base_mut())
+        }
+    }
+}
+
+pub(super) fn join_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{path}/{name}")
+    }
+}
+
+/// Collects ids of the shared data (`Rc`, `Arc`) in the region. Ids are memory addresses, so
+/// they're different every time the data is saved, they're replaced with a hash of the path of the
+/// shared data to make them comparable between versions.
+pub(super) fn collect_shared_ids(region: &Region, path: &str, ids: &mut FxHashMap<u64, u64>) {
+    if let Some(FieldKind::U64(id)) = region.field("Id") {
+        if region.child("RcData").is_some() || region.child("ArcData").is_some() {
+            ids.entry(*id).or_insert(fxhash::hash64(path) | 1);
+        }
+    }
+    for child in region.children.iter() {
+        collect_shared_ids(child, &join_path(path, &child.name), ids);
+    }
+}
+
+pub(super) fn replace_shared_ids(region: &mut Region, ids: &FxHashMap<u64, u64>) {
+    for (name, kind) in region.fields.iter_mut() {
+        if let ("Id", FieldKind::U64(id)) = (name.as_str(), kind) {
+            if let Some(new_id) = ids.get(id) {
+                *id = *new_id;
+            }
+        }
+    }
+    for child in region.children.iter_mut() {
+        replace_shared_ids(child, ids);
+    }
+}
+
+/// Names of `ours` items followed by the names of `theirs` items that are not in `ours`.
+fn union_names<'a>(
+    ours: impl Iterator<Item = &'a str>,
+    theirs: impl Iterator<Item = &'a str>,
+) -> Vec<&'a str> {
+    let mut names = ours.collect::<Vec<_>>();
+    for name in theirs {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+type RawConflicts = Vec<(String, SceneMergeConflictKind)>;
+
+fn merge_fields(
+    base: Option<&FieldKind>,
+    ours: Option<&FieldKind>,
+    theirs: Option<&FieldKind>,
+    path: String,
+    conflicts: &mut RawConflicts,
+) -> Option<FieldKind> {
+    if ours == theirs || base == theirs {
+        return ours.cloned();
+    }
+    if base == ours {
+        return theirs.cloned();
+    }
+    let kind = match (ours, theirs) {
+        (Some(_), Some(_)) => SceneMergeConflictKind::BothModified,
+        (Some(_), None) => SceneMergeConflictKind::ModifiedDeleted,
+        _ => SceneMergeConflictKind::DeletedModified,
+    };
+    conflicts.push((path, kind));
+    ours.cloned()
+}
+
+fn merge_regions(
+    base: Option<&Region>,
+    ours: Option<&Region>,
+    theirs: Option<&Region>,
+    path: &str,
+    conflicts: &mut RawConflicts,
+) -> Option<Region> {
+    if ours == theirs || base == theirs {
+        return ours.cloned();
+    }
+    if base == ours {
+        return theirs.cloned();
+    }
+    match (ours, theirs) {
+        (Some(ours), Some(theirs)) => {
+            let mut merged = Region::new(&ours.name);
+            for name in union_names(
+                ours.fields.iter().map(|(n, _)| n.as_str()),
+                theirs.fields.iter().map(|(n, _)| n.as_str()),
+            ) {
+                if let Some(kind) = merge_fields(
+                    base.and_then(|b| b.field(name)),
+                    ours.field(name),
+                    theirs.field(name),
+                    join_path(path, name),
+                    conflicts,
+                ) {
+                    merged.fields.push((name.to_string(), kind));
+                }
+            }
+            for name in union_names(
+                ours.children.iter().map(|c| c.name.as_str()),
+                theirs.children.iter().map(|c| c.name.as_str()),
+            ) {
+                if let Some(child) = merge_regions(
+                    base.and_then(|b| b.child(name)),
+                    ours.child(name),
+                    theirs.child(name),
+                    &join_path(path, name),
+                    conflicts,
+                ) {
+                    merged.children.push(child);
+                }
+            }
+            Some(merged)
+        }
+        (Some(ours), None) => {
+            conflicts.push((path.to_string(), SceneMergeConflictKind::ModifiedDeleted));
+            Some(ours.clone())
+        }
+        _ => {
+            conflicts.push((path.to_string(), SceneMergeConflictKind::DeletedModified));
+            None
+        }
+    }
+}
+
+/// A record of the graph's pool.
+pub(super) struct Record {
+    pub(super) generation: u32,
+    /// `Data` region of the node container, `None` if the record is vacant.
+    pub(super) data: Option<Region>,
+}
+
+/// A scene split into the graph nodes and the rest of the data.
+pub(super) struct SceneVersion {
+    /// Everything except the records of the graph's pool.
+    pub(super) rest: Region,
+    pub(super) records: Vec<Record>,
+    /// Ids of the live nodes in the order of their handles.
+    pub(super) order: Vec<Uuid>,
+    /// Indices of the records of the live nodes.
+    pub(super) indices: FxHashMap<Uuid, usize>,
+    /// Children of every node, they are removed from the nodes and restored after merging.
+    pub(super) children: FxHashMap<Uuid, Vec<Uuid>>,
+}
+
+pub(super) const POOL_PATH: [&str; 3] = ["Scene", "Graph", "Pool"];
+
+pub(super) fn invalid_scene(message: &str) -> VisitError {
+    VisitError::User(format!("Invalid scene data: {message}"))
+}
+
+impl SceneVersion {
+    /// Returns `None` if the visitor does not contain a scene.
+    pub(super) fn new(visitor: &Visitor) -> Result<Option<Self>, VisitError> {
+        let mut rest = Region::from_visitor(visitor, visitor.root());
+        let Some(pool) = rest.path_mut(&POOL_PATH) else {
+            return Ok(None);
+        };
+        let records_region = pool
+            .take_child("Records")
+            .ok_or_else(|| invalid_scene("no pool records"))?;
+        pool.take_child("FreeStack");
+
+        let mut records = Vec::new();
+        let length = records_region
+            .u32_field("Length")
+            .ok_or_else(|| invalid_scene("no pool length"))?;
+        for i in 0..length {
+            let item = records_region
+                .child(&format!("Item{i}"))
+                .and_then(|item| item.child("ItemData"))
+                .ok_or_else(|| invalid_scene("no pool record"))?;
+            let payload = item
+                .child("Payload")
+                .ok_or_else(|| invalid_scene("no pool record payload"))?;
+            records.push(Record {
+                generation: item
+                    .u32_field("Generation")
+                    .ok_or_else(|| invalid_scene("no pool record generation"))?,
+                data: payload.child("Data").cloned(),
+            });
+        }
+
+        let mut order = Vec::new();
+        let mut indices = FxHashMap::default();
+        let mut ids = FxHashMap::default();
+        for (index, record) in records.iter().enumerate() {
+            if let Some(data) = record.data.as_ref() {
+                let id = match data.base().and_then(|base| base.field("InstanceId")) {
+                    Some(FieldKind::Uuid(id)) => *id,
+                    // Scenes saved before instance ids were added, fall back to handles.
+                    _ => Uuid::from_u128(index as u128 + 1),
+                };
+                order.push(id);
+                indices.insert(id, index);
+                ids.insert((index as u32, record.generation), id);
+            }
+        }
+
+        let mut shared_ids = FxHashMap::default();
+        collect_shared_ids(&rest, "", &mut shared_ids);
+        for id in order.iter() {
+            if let Some(data) = records[indices[id]].data.as_ref() {
+                collect_shared_ids(data, &id.to_string(), &mut shared_ids);
+            }
+        }
+        replace_shared_ids(&mut rest, &shared_ids);
+
+        let mut children = FxHashMap::default();
+        for id in order.iter() {
+            let Some(data) = records[indices[id]].data.as_mut() else {
+                continue;
+            };
+            replace_shared_ids(data, &shared_ids);
+            data.mark_node_refs(&ids);
+            if let Some(list) = data.base_mut().and_then(|base| base.take_child("Children")) {
+                children.insert(
+                    *id,
+                    list.children
+                        .iter()
+                        .filter_map(child_ref)
+                        .collect::<Vec<_>>(),
+                );
+            }
+        }
+        if let Some(root) = rest.path_mut(&["Scene", "Graph", "Root"]) {
+            root.mark_node_ref(&ids);
+        }
+
+        Ok(Some(Self {
+            rest,
+            records,
+            order,
+            indices,
+            children,
+        }))
+    }
+
+    pub(super) fn node(&self, id: &Uuid) -> Option<&Region> {
+        self.indices
+            .get(id)
+            .and_then(|index| self.records[*index].data.as_ref())
+    }
+}
+
+/// Node id of an item of serialized `Vec<Handle<Node>>`.
+pub(super) fn child_ref(item: &Region) -> Option<Uuid> {
+    item.child("ItemData").and_then(|handle| handle.node_ref())
+}
+
+pub(super) fn node_name(data: &Region) -> String {
+    match data
+        .base()
+        .and_then(|base| base.child("Name"))
+        .and_then(|name| name.field("Data"))
+    {
+        Some(FieldKind::BinaryBlob(bytes)) => String::from_utf8_lossy(bytes).to_string(),
+        _ => String::new(),
+    }
+}
+
+pub(super) fn parent_ref(data: &Region) -> Option<Uuid> {
+    data.base()
+        .and_then(|base| base.child("Parent"))
+        .and_then(|parent| parent.node_ref())
+}
+
+/// Where [`assemble_graph`] should put a node in the pool.
+#[derive(Copy, Clone, Debug)]
+pub(super) enum Placement {
+    /// The node must have exactly this handle (index and generation).
+    Exact(u32, u32),
+    /// The node keeps the handle if the place in the pool is free and has lower generation,
+    /// otherwise the node is moved to the end of the pool.
+    Preferred(u32, u32),
+}
+
+/// A reference to a node that does not exist in the graph built by [`assemble_graph`].
+pub(super) struct DanglingReference {
+    /// Id of the node with the reference, `None` if the reference is outside of the nodes.
+    pub node: Option<Uuid>,
+    /// Name of the node with the reference.
+    pub node_name: String,
+    /// Path of the reference in the node data (or in the scene data).
+    pub path: String,
+}
+
+/// Puts the nodes (`Data` regions of the pool records, with the node references marked by ids and
+/// without the lists of children, as prepared by [`SceneVersion`]) into a new pool of the graph
+/// in the `scene` region. Children lists are restored from the parent links, keeping the order
+/// given by `children_order` for the known children. Node references are replaced with the new
+/// handles; references to missing nodes are collected to `dangling` and either keep their old
+/// handles or become [`Handle::NONE`], depending on `keep_dangling`. `generations` are the
+/// generations of the pool records before the nodes were placed. Returns the new handles of the
+/// nodes as `(index, generation)` pairs.
+pub(super) fn assemble_graph<'a>(
+    scene: &mut Region,
+    mut nodes: Vec<(Uuid, Region, Placement)>,
+    mut generations: Vec<u32>,
+    children_order: &dyn Fn(&Uuid) -> Option<&'a Vec<Uuid>>,
+    dangling: &mut Vec<DanglingReference>,
+    keep_dangling: bool,
+) -> Result<FxHashMap<Uuid, (u32, u32)>, VisitError> {
+    // Restore children lists from the parent links, keeping the original order of the children.
+    let mut children_of = FxHashMap::<Uuid, Vec<Uuid>>::default();
+    for (id, data, _) in nodes.iter() {
+        if let Some(parent) = parent_ref(data) {
+            children_of.entry(parent).or_default().push(*id);
+        }
+    }
+    for (id, data, _) in nodes.iter_mut() {
+        let Some(base_region) = data.base_mut() else {
+            continue;
+        };
+        let actual = children_of.remove(id).unwrap_or_default();
+        let mut children = children_order(id)
+            .map(|original| {
+                original
+                    .iter()
+                    .filter(|child| actual.contains(child))
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        for child in actual {
+            if !children.contains(&child) {
+                children.push(child);
+            }
+        }
+        let mut list =
+            Region::new("Children").with_field("Length", FieldKind::U32(children.len() as u32));
+        for (i, child) in children.iter().enumerate() {
+            list = list.with_child(Region::new(&format!("Item{i}")).with_child(
+                Region::new("ItemData").with_field(NODE_ID_FIELD, FieldKind::Uuid(*child)),
+            ));
+        }
+        base_region.children.push(list);
+    }
+
+    let mut slots = vec![None; generations.len()];
+    // Makes sure that the place exists in the pool and returns its index.
+    fn reserve(slots: &mut Vec<Option<usize>>, generations: &mut Vec<u32>, index: u32) -> usize {
+        let index = index as usize;
+        if index >= slots.len() {
+            slots.resize(index + 1, None);
+            generations.resize(index + 1, 0);
+        }
+        index
+    }
+    for (position, (_, _, placement)) in nodes.iter().enumerate() {
+        if let Placement::Exact(index, generation) = *placement {
+            let index = reserve(&mut slots, &mut generations, index);
+            slots[index] = Some(position);
+            generations[index] = generation;
+        }
+    }
+    let mut relocated = Vec::new();
+    for (position, (_, _, placement)) in nodes.iter().enumerate() {
+        if let Placement::Preferred(index, generation) = *placement {
+            let index = reserve(&mut slots, &mut generations, index);
+            if slots[index].is_none() && generations[index] < generation {
+                slots[index] = Some(position);
+                generations[index] = generation;
+            } else {
+                relocated.push(position);
+            }
+        }
+    }
+    for position in relocated {
+        slots.push(Some(position));
+        generations.push(1);
+    }
+
+    let handles = slots
+        .iter()
+        .enumerate()
+        .filter_map(|(index, slot)| {
+            slot.map(|position| (nodes[position].0, (index as u32, generations[index])))
+        })
+        .collect::<FxHashMap<_, _>>();
+
+    for (id, data, _) in nodes.iter_mut() {
+        let mut paths = Vec::new();
+        data.resolve_node_refs("", &handles, &mut paths, keep_dangling);
+        let node_name = node_name(data);
+        dangling.extend(paths.into_iter().map(|path| DanglingReference {
+            node: Some(*id),
+            node_name: node_name.clone(),
+            path,
+        }));
+    }
+
+    let graph = scene
+        .path_mut(&POOL_PATH[..2])
+        .ok_or_else(|| invalid_scene("no graph"))?;
+    let mut paths = Vec::new();
+    graph.resolve_node_refs("Scene/Graph", &handles, &mut paths, keep_dangling);
+    dangling.extend(paths.into_iter().map(|path| DanglingReference {
+        node: None,
+        node_name: Default::default(),
+        path,
+    }));
+
+    let mut records =
+        Region::new("Records").with_field("Length", FieldKind::U32(slots.len() as u32));
+    let mut free_stack = Vec::new();
+    let mut nodes = nodes
+        .into_iter()
+        .map(|(_, data, _)| Some(data))
+        .collect::<Vec<_>>();
+    for (index, slot) in slots.iter().enumerate() {
+        let mut payload = Region::new("Payload");
+        if let Some(position) = slot {
+            payload = payload
+                .with_field("IsSome", FieldKind::U8(1))
+                .with_child(nodes[*position].take().unwrap_or_default());
+        } else {
+            payload = payload.with_field("IsSome", FieldKind::U8(0));
+            free_stack.push(index as u32);
+        }
+        records = records.with_child(
+            Region::new(&format!("Item{index}")).with_child(
+                Region::new("ItemData")
+                    .with_field("Generation", FieldKind::U32(generations[index]))
+                    .with_child(payload),
+            ),
+        );
+    }
+    let mut free_stack_region =
+        Region::new("FreeStack").with_field("Length", FieldKind::U32(free_stack.len() as u32));
+    for (i, index) in free_stack.into_iter().enumerate() {
+        free_stack_region = free_stack_region.with_child(
+            Region::new(&format!("Item{i}")).with_field("ItemData", FieldKind::U32(index)),
+        );
+    }
+    let pool = scene
+        .path_mut(&POOL_PATH)
+        .ok_or_else(|| invalid_scene("no graph pool"))?;
+    pool.children.insert(0, free_stack_region);
+    pool.children.insert(0, records);
+
+    Ok(handles)
+}
+
+/// Performs a structural three-way merge of two versions of the same scene (`ours` and `theirs`)
+/// against their common ancestor (`base`). All three visitors must contain the data of the scenes
+/// in the native format (as saved by the editor).
+///
+/// Unlike the line-based merge, scene nodes are matched by their [`SceneNodeId`] (see
+/// [`crate::scene::graph::Graph::node_by_id`]) and every property of a node is merged separately.
+/// Handles of the nodes are remapped, so references between nodes, hierarchy and inheritance data
+/// stay valid even if both sides have added new nodes. A change made on one side only is taken as
+/// is; if both sides changed the same property in different ways, the conflict is reported and our
+/// version of the property is kept.
+///
+/// Visitors without a scene graph (materials, curves, etc.) are merged property-by-property
+/// without any special handling.
+pub fn merge_scenes(
+    base: &Visitor,
+    ours: &Visitor,
+    theirs: &Visitor,
+) -> Result<SceneMerge, VisitError> {
+    let mut conflicts = Vec::new();
+    let mut raw_conflicts = RawConflicts::new();
+
+    let (Some(base), Some(ours), Some(theirs)) = (
+        SceneVersion::new(base)?,
+        SceneVersion::new(ours)?,
+        SceneVersion::new(theirs)?,
+    ) else {
+        let [base, ours, theirs] = [base, ours, theirs].map(|visitor| {
+            let mut region = Region::from_visitor(visitor, visitor.root());
+            let mut shared_ids = FxHashMap::default();
+            collect_shared_ids(&region, "", &mut shared_ids);
+            replace_shared_ids(&mut region, &shared_ids);
+            region
+        });
+        let merged = merge_regions(
+            Some(&base),
+            Some(&ours),
+            Some(&theirs),
+            "",
+            &mut raw_conflicts,
+        )
+        .unwrap_or_default();
+        let mut visitor = Visitor::new();
+        merged.write_contents(&mut visitor)?;
+        return Ok(SceneMerge {
+            visitor,
+            conflicts: raw_conflicts
+                .into_iter()
+                .map(|(property, kind)| SceneMergeConflict {
+                    node: None,
+                    node_name: Default::default(),
+                    property,
+                    kind,
+                })
+                .collect(),
+        });
+    };
+
+    let mut add_conflicts = |id: Option<Uuid>, name: &str, raw: &mut RawConflicts| {
+        conflicts.extend(raw.drain(..).map(|(property, kind)| SceneMergeConflict {
+            node: id.map(SceneNodeId),
+            node_name: name.to_string(),
+            property,
+            kind,
+        }));
+    };
+
+    // Merge every node separately.
+    let mut nodes = Vec::<(Uuid, Region)>::new();
+    let theirs_new = theirs
+        .order
+        .iter()
+        .filter(|id| !ours.indices.contains_key(id));
+    for id in ours.order.iter().chain(theirs_new) {
+        let (b, o, t) = (base.node(id), ours.node(id), theirs.node(id));
+        let type_of = |data: Option<&Region>| data.and_then(|d| d.field("TypeUuid")).cloned();
+        let merged = if o.is_some() && t.is_some() && type_of(o) != type_of(t) {
+            // Nodes of different types cannot be merged property-by-property.
+            if type_of(b) == type_of(o) {
+                t.cloned()
+            } else {
+                if type_of(b) != type_of(t) {
+                    raw_conflicts
+                        .push(("TypeUuid".to_string(), SceneMergeConflictKind::BothModified));
+                }
+                o.cloned()
+            }
+        } else {
+            merge_regions(b, o, t, "", &mut raw_conflicts)
+        };
+        let name = o.or(t).map(node_name).unwrap_or_default();
+        add_conflicts(Some(*id), &name, &mut raw_conflicts);
+        if let Some(merged) = merged {
+            nodes.push((*id, merged));
+        }
+    }
+
+    // Merge the rest of the data, the new pool is put into it.
+    let mut rest_conflicts = RawConflicts::new();
+    let mut merged = merge_regions(
+        Some(&base.rest),
+        Some(&ours.rest),
+        Some(&theirs.rest),
+        "",
+        &mut rest_conflicts,
+    )
+    .unwrap_or_default();
+
+    // Our nodes keep their handles, new nodes from their version keep their handles too if the
+    // place in the pool is free, otherwise they're moved to the end of the pool.
+    let nodes = nodes
+        .into_iter()
+        .map(|(id, data)| {
+            let placement = match ours.indices.get(&id) {
+                Some(index) => Placement::Exact(*index as u32, ours.records[*index].generation),
+                None => {
+                    let index = theirs.indices[&id];
+                    Placement::Preferred(index as u32, theirs.records[index].generation)
+                }
+            };
+            (id, data, placement)
+        })
+        .collect();
+    let generations = ours
+        .records
+        .iter()
+        .map(|record| record.generation)
+        .collect();
+    let mut dangling = Vec::new();
+    assemble_graph(
+        &mut merged,
+        nodes,
+        generations,
+        &|id| ours.children.get(id).or_else(|| theirs.children.get(id)),
+        &mut dangling,
+        true,
+    )?;
+
+    let mut rest_dangling = Vec::new();
+    for reference in dangling {
+        let raw = (reference.path, SceneMergeConflictKind::DanglingReference);
+        match reference.node {
+            Some(id) => {
+                raw_conflicts.push(raw);
+                add_conflicts(Some(id), &reference.node_name, &mut raw_conflicts);
+            }
+            None => rest_dangling.push(raw),
+        }
+    }
+    raw_conflicts.extend(rest_conflicts);
+    raw_conflicts.extend(rest_dangling);
+    add_conflicts(None, "", &mut raw_conflicts);
+
+    let mut visitor = Visitor::new();
+    merged.write_contents(&mut visitor)?;
+    Ok(SceneMerge { visitor, conflicts })
+}
+
+/// Merges scene files at the given paths (see [`merge_scenes`]) and writes the result to `output`
+/// in the format of `ours` (text or binary). Returns a list of conflicts, the result is written
+/// even if there are conflicts.
+pub fn merge_scene_files(
+    base: &Path,
+    ours: &Path,
+    theirs: &Path,
+    output: &Path,
+) -> Result<Vec<SceneMergeConflict>, VisitError> {
+    let ours_data = std::fs::read(ours)?;
+    let merge = merge_scenes(
+        &Visitor::load_from_memory(&std::fs::read(base)?)?,
+        &Visitor::load_from_memory(&ours_data)?,
+        &Visitor::load_from_memory(&std::fs::read(theirs)?)?,
+    )?;
+    if ours_data.starts_with(Visitor::TEXT_MAGIC.as_bytes()) {
+        merge.visitor.save_text_to_file(output)?;
+    } else {
+        merge.visitor.save_binary(output)?;
+    }
+    Ok(merge.conflicts)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::manager::ResourceManager,
+        core::{algebra::Vector3, pool::Handle, visitor::Visitor},
+        engine::SerializationContext,
+        graph::{BaseSceneGraph, SceneGraph},
+        scene::{
+            base::BaseBuilder,
+            merge::{merge_scenes, SceneMergeConflictKind},
+            node::Node,
+            pivot::PivotBuilder,
+            Scene, SceneLoader,
+        },
+    };
+    use std::sync::Arc;
+
+    fn save(scene: &mut Scene) -> Visitor {
+        let mut visitor = Visitor::new();
+        scene.save("Scene", &mut visitor).unwrap();
+        visitor
+    }
+
+    fn load(visitor: &Visitor) -> Scene {
+        let mut visitor =
+            Visitor::load_from_memory(&visitor.save_binary_to_vec().unwrap()).unwrap();
+        SceneLoader::load(
+            "Scene",
+            Arc::new(SerializationContext::new()),
+            ResourceManager::new(Arc::new(Default::default())),
+            &mut visitor,
+            None,
+        )
+        .unwrap()
+        .scene
+    }
+
+    fn find(scene: &Scene, name: &str) -> Handle<Node> {
+        scene.graph.find_by_name_from_root(name).unwrap().0
+    }
+
+    fn add_pivot(scene: &mut Scene, name: &str, parent: Handle<Node>) -> Handle<Node> {
+        let pivot = PivotBuilder::new(BaseBuilder::new().with_name(name)).build(&mut scene.graph);
+        scene.graph.link_nodes(pivot, parent);
+        pivot
+    }
+
+    #[test]
+    fn test_merge_scenes() {
+        let mut scene = Scene::new();
+        let root = scene.graph.get_root();
+        add_pivot(&mut scene, "A", root);
+        let base = save(&mut scene);
+
+        // Both sides add a node, the new nodes get the same handle.
+        let mut ours = load(&base);
+        let a = find(&ours, "A");
+        ours.graph[a]
+            .local_transform_mut()
+            .set_position(Vector3::new(1.0, 0.0, 0.0));
+        let b = add_pivot(&mut ours, "B", a);
+        let ours = save(&mut ours);
+
+        let mut theirs = load(&base);
+        let a = find(&theirs, "A");
+        theirs.graph[a].set_visibility(false);
+        let root = theirs.graph.get_root();
+        let c = add_pivot(&mut theirs, "C", root);
+        assert_eq!(b, c);
+        let theirs = save(&mut theirs);
+
+        let merge = merge_scenes(&base, &ours, &theirs).unwrap();
+        assert!(merge.conflicts.is_empty(), "{:?}", merge.conflicts);
+
+        let merged = load(&merge.visitor);
+        let (a, b, c) = (find(&merged, "A"), find(&merged, "B"), find(&merged, "C"));
+        assert_ne!(b, c);
+        assert_eq!(
+            **merged.graph[a].local_transform().position(),
+            Vector3::new(1.0, 0.0, 0.0)
+        );
+        assert!(!merged.graph[a].visibility());
+        assert_eq!(merged.graph[b].parent(), a);
+        assert_eq!(merged.graph[a].children(), &[b]);
+        assert_eq!(merged.graph[c].parent(), merged.graph.get_root());
+        assert_eq!(merged.graph[merged.graph.get_root()].children(), &[a, c]);
+    }
+
+    #[test]
+    fn test_merge_scenes_conflict() {
+        let mut scene = Scene::new();
+        let root = scene.graph.get_root();
+        add_pivot(&mut scene, "A", root);
+        let base = save(&mut scene);
+
+        let mut versions = [1.0, 2.0].map(|x| {
+            let mut scene = load(&base);
+            let a = find(&scene, "A");
+            scene.graph[a]
+                .local_transform_mut()
+                .set_position(Vector3::new(x, 0.0, 0.0));
+            save(&mut scene)
+        });
+        let [ours, theirs] = &mut versions;
+
+        let merge = merge_scenes(&base, ours, theirs).unwrap();
+        assert_eq!(merge.conflicts.len(), 1);
+        let conflict = &merge.conflicts[0];
+        assert_eq!(conflict.node_name, "A");
+        assert_eq!(conflict.kind, SceneMergeConflictKind::BothModified);
+        assert!(conflict.property.contains("Transform"));
+
+        let merged = load(&merge.visitor);
+        let a = find(&merged, "A");
+        assert_eq!(
+            **merged.graph[a].local_transform().position(),
+            Vector3::new(1.0, 0.0, 0.0)
+        );
+    }
+}
@@ -28,6 +28,7 @@ pub mod accel;
 pub mod animation;
 pub mod base;
 pub mod camera;
+pub mod character;
 pub mod collider;
 pub mod debug;
 pub mod decal;
@@ -35,18 +36,22 @@ pub mod dim2;
 pub mod graph;
 pub mod joint;
 pub mod light;
+pub mod merge;
 pub mod mesh;
 pub mod navmesh;
 pub mod node;
 pub mod particle_system;
 pub mod pivot;
 pub mod ragdoll;
+pub mod reference;
 pub mod rigidbody;
+pub mod savegame;
 pub mod sound;
 pub mod sprite;
 pub mod terrain;
 pub mod tilemap;
 pub mod transform;
+pub mod vehicle;
 
 use crate::renderer::framework::PolygonFillMode;
 use crate::{
@@ -62,7 +67,7 @@ use crate::{
     },
     engine::SerializationContext,
     graph::NodeHandleMap,
-    resource::texture::TextureResource,
+    resource::{model::ModelResource, texture::TextureResource},
     scene::{
         base::BaseBuilder,
         camera::Camera,
@@ -70,12 +75,13 @@ use crate::{
         graph::{Graph, GraphPerformanceStatistics, GraphUpdateSwitches},
         navmesh::NavigationalMeshBuilder,
         node::Node,
+        savegame::{RestoredGame, SaveGame, SaveGameOptions},
         sound::SoundEngine,
     },
     utils::navmesh::Navmesh,
 };
 use asset::io::ResourceIo;
-use fxhash::FxHashSet;
+use fxhash::{FxHashMap, FxHashSet};
 use fyrox_core::variable::InheritableVariable;
 use std::{
     fmt::{Display, Formatter},
@@ -546,6 +552,7 @@ impl Scene {
 pub struct SceneContainer {
     pool: Pool<Scene>,
     sound_engine: SoundEngine,
+    sources: FxHashMap<Handle<Scene>, PathBuf>,
     pub(crate) destruction_list: Vec<(Handle<Scene>, Scene)>,
 }
 
@@ -554,6 +561,7 @@ impl SceneContainer {
         Self {
             pool: Pool::new(),
             sound_engine,
+            sources: Default::default(),
             destruction_list: Default::default(),
         }
     }
@@ -607,12 +615,14 @@ impl SceneContainer {
     /// Removes all scenes from container.
     #[inline]
     pub fn clear(&mut self) {
+        self.sources.clear();
         self.pool.clear()
     }
 
     /// Removes given scene from container. The scene will be destroyed on a next update call.
     #[inline]
     pub fn remove(&mut self, handle: Handle<Scene>) {
+        self.sources.remove(&handle);
         self.sound_engine
             .state()
             .remove_context(self.pool[handle].graph.sound_context.native.clone());
@@ -635,6 +645,56 @@ impl SceneContainer {
     pub fn forget_ticket(&mut self, ticket: Ticket<Scene>) {
         self.pool.forget_ticket(ticket)
     }
+
+    /// Sets the path of the resource the scene was loaded from. Scenes loaded by the engine have
+    /// their paths set automatically. The path is used to resolve cross-scene references, see
+    /// [`reference::SceneNodeRef`] docs for more info.
+    pub fn set_source_path(&mut self, handle: Handle<Scene>, path: Option<PathBuf>) {
+        match path {
+            Some(path) => {
+                self.sources.insert(handle, path);
+            }
+            None => {
+                self.sources.remove(&handle);
+            }
+        }
+    }
+
+    /// Returns the path of the resource the scene was loaded from, if any.
+    pub fn source_path(&self, handle: Handle<Scene>) -> Option<&Path> {
+        self.sources.get(&handle).map(|path| path.as_path())
+    }
+
+    /// Tries to find a scene loaded from the resource with the given path.
+    pub fn find_by_source_path(&self, path: &Path) -> Option<Handle<Scene>> {
+        self.sources
+            .iter()
+            .find_map(|(handle, source)| (source == path).then_some(*handle))
+    }
+
+    /// Creates a save game of the scene and the given state (for example, the state of a plugin).
+    /// `original` is the scene resource the scene was loaded from, only the difference between
+    /// the scene and the resource is stored. See [`SaveGame`] docs for more info.
+    pub fn save_game<S: Visit + ?Sized>(
+        &mut self,
+        handle: Handle<Scene>,
+        original: &ModelResource,
+        state: &mut S,
+        options: &SaveGameOptions,
+    ) -> Result<SaveGame, VisitError> {
+        SaveGame::new(&mut self.pool[handle], original, state, options)
+    }
+
+    /// Adds a scene restored by [`SaveGame::restore`] to the container and loads the saved state,
+    /// handles to the nodes in the state are remapped to the restored nodes.
+    pub fn load_game<S: Visit + Reflect + ?Sized>(
+        &mut self,
+        restored: RestoredGame,
+        state: &mut S,
+    ) -> Result<Handle<Scene>, VisitError> {
+        restored.load_state(state)?;
+        Ok(self.add(restored.scene))
+    }
 }
 
 impl Index<Handle<Scene>> for SceneContainer {
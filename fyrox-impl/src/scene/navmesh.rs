@@ -39,7 +39,10 @@ use crate::{
         graph::Graph,
         node::{Node, NodeTrait},
     },
-    utils::navmesh::Navmesh,
+    utils::navmesh::{
+        generator::{NavmeshGenerationSettings, NavmeshGenerator, NavmeshGeometry},
+        Navmesh,
+    },
 };
 use fyrox_core::parking_lot::{RwLockReadGuard, RwLockWriteGuard};
 use fyrox_graph::BaseSceneGraph;
@@ -98,6 +101,23 @@ impl Visit for Container {
 /// }
 /// ```
 ///
+/// ## Generation
+///
+/// Navigational mesh could also be generated automatically from the geometry of the scene. The node stores
+/// [generation settings](NavmeshGenerationSettings) and a list of source nodes, whose meshes and colliders
+/// (including the ones of their descendants) will be used as a source geometry. If the list is empty, the
+/// entire scene will be used. Use [`NavigationalMesh::make_generator`] to collect the geometry and then run
+/// the generator. See [`NavmeshGenerator`] docs for more info and an example of asynchronous generation.
+///
+/// ```rust
+/// # use fyrox_impl::scene::{graph::Graph, navmesh::NavigationalMesh, node::Node};
+/// # use fyrox_impl::core::pool::Handle;
+/// fn regenerate_navmesh(graph: &mut Graph, navmesh: Handle<Node>) {
+///     let generator = graph[navmesh].as_navigational_mesh().make_generator(graph);
+///     *graph[navmesh].as_navigational_mesh_mut().navmesh_mut() = generator.generate();
+/// }
+/// ```
+///
 /// ## Agents
 ///
 /// Navigational mesh agent helps you to build paths along the surface of a navigational mesh and follow it. Agents can be
@@ -155,6 +175,14 @@ pub struct NavigationalMesh {
     base: Base,
     #[reflect(read_only)]
     navmesh: InheritableVariable<Container>,
+
+    #[reflect(setter = "set_generation_settings")]
+    #[visit(optional)]
+    generation_settings: InheritableVariable<NavmeshGenerationSettings>,
+
+    #[reflect(setter = "set_generation_sources")]
+    #[visit(optional)]
+    generation_sources: InheritableVariable<Vec<Handle<Node>>>,
 }
 
 impl TypeUuidProvider for NavigationalMesh {
@@ -227,12 +255,50 @@ impl NavigationalMesh {
     pub fn navmesh(&self) -> Arc<RwLock<Navmesh>> {
         self.navmesh.0.clone()
     }
+
+    /// Sets new settings, that will be used for navmesh generation. Returns old settings.
+    pub fn set_generation_settings(
+        &mut self,
+        settings: NavmeshGenerationSettings,
+    ) -> NavmeshGenerationSettings {
+        self.generation_settings
+            .set_value_and_mark_modified(settings)
+    }
+
+    /// Returns current generation settings.
+    pub fn generation_settings(&self) -> &NavmeshGenerationSettings {
+        &self.generation_settings
+    }
+
+    /// Sets new source nodes for navmesh generation. Meshes and colliders of the nodes and all their
+    /// descendants will be used as a source geometry. Empty list means that the entire scene will be
+    /// used. Returns old sources.
+    pub fn set_generation_sources(&mut self, sources: Vec<Handle<Node>>) -> Vec<Handle<Node>> {
+        self.generation_sources.set_value_and_mark_modified(sources)
+    }
+
+    /// Returns current source nodes for navmesh generation.
+    pub fn generation_sources(&self) -> &[Handle<Node>] {
+        &self.generation_sources
+    }
+
+    /// Collects source geometry from the graph and creates a navmesh generator with current settings.
+    /// The generator does not borrow anything, so it could be moved to another thread to perform the
+    /// generation.
+    pub fn make_generator(&self, graph: &Graph) -> NavmeshGenerator {
+        NavmeshGenerator::new(
+            *self.generation_settings,
+            NavmeshGeometry::from_graph(graph, &self.generation_sources),
+        )
+    }
 }
 
 /// Creates navigational meshes and adds them to a scene graph.
 pub struct NavigationalMeshBuilder {
     base_builder: BaseBuilder,
     navmesh: Navmesh,
+    generation_settings: NavmeshGenerationSettings,
+    generation_sources: Vec<Handle<Node>>,
 }
 
 impl NavigationalMeshBuilder {
@@ -241,6 +307,8 @@ impl NavigationalMeshBuilder {
         Self {
             base_builder,
             navmesh: Default::default(),
+            generation_settings: Default::default(),
+            generation_sources: Default::default(),
         }
     }
 
@@ -250,12 +318,26 @@ impl NavigationalMeshBuilder {
         self
     }
 
+    /// Sets desired settings for navmesh generation.
+    pub fn with_generation_settings(mut self, settings: NavmeshGenerationSettings) -> Self {
+        self.generation_settings = settings;
+        self
+    }
+
+    /// Sets desired source nodes for navmesh generation.
+    pub fn with_generation_sources(mut self, sources: Vec<Handle<Node>>) -> Self {
+        self.generation_sources = sources;
+        self
+    }
+
     fn build_navigational_mesh(self) -> NavigationalMesh {
         NavigationalMesh {
             base: self.base_builder.build_base(),
             navmesh: InheritableVariable::new_modified(Container(Arc::new(RwLock::new(
                 self.navmesh,
             )))),
+            generation_settings: self.generation_settings.into(),
+            generation_sources: self.generation_sources.into(),
         }
     }
 
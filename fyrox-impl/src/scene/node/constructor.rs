@@ -71,6 +71,7 @@ impl NodeConstructorContainer {
         container.add::<dim2::joint::Joint>();
         container.add::<Rectangle>();
         container.add::<dim2::rigidbody::RigidBody>();
+        container.add::<dim2::character::CharacterController>();
         container.add::<DirectionalLight>();
         container.add::<PointLight>();
         container.add::<SpotLight>();
@@ -84,6 +85,8 @@ impl NodeConstructorContainer {
         container.add::<scene::joint::Joint>();
         container.add::<Pivot>();
         container.add::<scene::rigidbody::RigidBody>();
+        container.add::<scene::character::CharacterController>();
+        container.add::<scene::vehicle::Vehicle>();
         container.add::<Sprite>();
         container.add::<Terrain>();
         container.add::<AnimationPlayer>();
@@ -0,0 +1,307 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Weak references to scene nodes that work across scenes. See [`SceneNodeRef`] docs for more
+//! info.
+
+use crate::{
+    core::{
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        uuid::{uuid, Uuid},
+        visitor::{Visit, VisitResult, Visitor},
+    },
+    graph::BaseSceneGraph,
+    scene::{
+        base::SceneNodeId,
+        node::{Node, NodeTrait},
+        Scene, SceneContainer,
+    },
+};
+use std::{
+    cell::Cell,
+    fmt::{Debug, Formatter},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+/// A weak reference to a scene node, that is keyed by the path of the scene resource the node
+/// was loaded from and the [`SceneNodeId`] of the node. Unlike [`Handle<Node>`], which is only
+/// meaningful inside one graph, the reference could point to a node of any scene in a
+/// [`SceneContainer`]. For example, a node of an additive scene could reference a node of the main
+/// level, and the reference will still be valid after both scenes are loaded again.
+///
+/// The reference is resolved lazily through a [`SceneContainer`], the result is cached until the
+/// node is destroyed. A reference without a scene path looks for the node in every scene. Scenes
+/// loaded by the engine remember their paths automatically, other scenes must be registered using
+/// [`SceneContainer::set_source_path`].
+///
+/// The type parameter defines the type of the node the reference points to, it is used by
+/// [`Self::get`] and [`Self::get_mut`] to cast the node.
+#[derive(Reflect)]
+pub struct SceneNodeRef<T = Node>
+where
+    T: 'static,
+{
+    scene: Option<PathBuf>,
+    #[reflect(hidden)]
+    node: SceneNodeId,
+    #[reflect(hidden)]
+    cache: Cell<Option<(Handle<Scene>, Handle<Node>)>>,
+    #[reflect(hidden)]
+    phantom: PhantomData<T>,
+}
+
+impl<T> Debug for SceneNodeRef<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.scene.as_ref() {
+            Some(scene) => write!(f, "{} in {}", self.node.0, scene.display()),
+            None => write!(f, "{}", self.node.0),
+        }
+    }
+}
+
+impl<T> Clone for SceneNodeRef<T> {
+    fn clone(&self) -> Self {
+        Self {
+            scene: self.scene.clone(),
+            node: self.node,
+            cache: Cell::new(self.cache.get()),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for SceneNodeRef<T> {
+    fn default() -> Self {
+        Self {
+            scene: None,
+            node: Default::default(),
+            cache: Default::default(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> PartialEq for SceneNodeRef<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.scene == other.scene && self.node == other.node
+    }
+}
+
+impl<T> Eq for SceneNodeRef<T> {}
+
+impl<T> TypeUuidProvider for SceneNodeRef<T>
+where
+    T: TypeUuidProvider,
+{
+    fn type_uuid() -> Uuid {
+        combine_uuids(
+            uuid!("6cf403eb-6ed4-4afb-9202-8765a2778940"),
+            T::type_uuid(),
+        )
+    }
+}
+
+impl<T> Visit for SceneNodeRef<T> {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.scene.visit("Scene", &mut region)?;
+        self.node.visit("Node", &mut region)?;
+
+        if region.is_reading() {
+            self.cache.set(None);
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> SceneNodeRef<T> {
+    /// Creates a reference to the node with the given id in the scene loaded from the given path.
+    /// If the path is `None`, the node is searched in every scene.
+    pub fn new(scene: Option<PathBuf>, node: SceneNodeId) -> Self {
+        Self {
+            scene,
+            node,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a reference to the node of the scene. The scene must be in the container.
+    pub fn from_node(scenes: &SceneContainer, scene: Handle<Scene>, node: Handle<Node>) -> Self {
+        let this = Self::new(
+            scenes.source_path(scene).map(|path| path.to_path_buf()),
+            scenes[scene].graph[node].instance_id(),
+        );
+        this.cache.set(Some((scene, node)));
+        this
+    }
+
+    /// Returns the path of the scene resource, if any.
+    pub fn scene_path(&self) -> Option<&Path> {
+        self.scene.as_deref()
+    }
+
+    /// Returns the id of the node.
+    pub fn node_id(&self) -> SceneNodeId {
+        self.node
+    }
+
+    /// Returns `true` if the reference points to a node, the node might not exist though.
+    pub fn is_some(&self) -> bool {
+        !self.node.0.is_nil()
+    }
+
+    /// Returns `true` if the reference does not point to a node.
+    pub fn is_none(&self) -> bool {
+        !self.is_some()
+    }
+
+    /// Finds the scene and the node the reference points to.
+    pub fn resolve(&self, scenes: &SceneContainer) -> Option<(Handle<Scene>, Handle<Node>)> {
+        if self.is_none() {
+            return None;
+        }
+
+        if let Some((scene, node)) = self.cache.get() {
+            if (self.scene.is_none() || scenes.source_path(scene) == self.scene.as_deref())
+                && scenes
+                    .try_get(scene)
+                    .and_then(|s| s.graph.try_get(node))
+                    .is_some_and(|n| n.instance_id() == self.node)
+            {
+                return Some((scene, node));
+            }
+        }
+
+        let find = |handle: Handle<Scene>, scene: &Scene| {
+            scene
+                .graph
+                .id_to_node_handle(self.node)
+                .map(|node| (handle, *node))
+        };
+        let resolved = match self.scene.as_ref() {
+            Some(path) => scenes
+                .pair_iter()
+                .filter(|(handle, _)| scenes.source_path(*handle) == Some(path.as_path()))
+                .find_map(|(handle, scene)| find(handle, scene)),
+            None => scenes
+                .pair_iter()
+                .find_map(|(handle, scene)| find(handle, scene)),
+        };
+        self.cache.set(resolved);
+        resolved
+    }
+
+    /// Tries to borrow the node the reference points to.
+    pub fn node<'a>(&self, scenes: &'a SceneContainer) -> Option<&'a Node> {
+        self.resolve(scenes)
+            .map(|(scene, node)| &scenes[scene].graph[node])
+    }
+
+    /// Tries to borrow the node the reference points to.
+    pub fn node_mut<'a>(&self, scenes: &'a mut SceneContainer) -> Option<&'a mut Node> {
+        self.resolve(scenes)
+            .map(|(scene, node)| &mut scenes[scene].graph[node])
+    }
+}
+
+impl<T> SceneNodeRef<T>
+where
+    T: NodeTrait,
+{
+    /// Tries to borrow the node the reference points to and cast it to the type of the reference.
+    pub fn get<'a>(&self, scenes: &'a SceneContainer) -> Option<&'a T> {
+        self.node(scenes).and_then(|node| node.cast::<T>())
+    }
+
+    /// Tries to borrow the node the reference points to and cast it to the type of the reference.
+    pub fn get_mut<'a>(&self, scenes: &'a mut SceneContainer) -> Option<&'a mut T> {
+        self.node_mut(scenes).and_then(|node| node.cast_mut::<T>())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::visitor::prelude::*,
+        scene::{
+            base::BaseBuilder, graph::Graph, pivot::Pivot, pivot::PivotBuilder,
+            reference::SceneNodeRef, sprite::Sprite, Scene, SceneContainer,
+        },
+    };
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn test_scene_node_ref() {
+        let mut scenes = SceneContainer::new(Default::default());
+
+        let mut level = Scene::new();
+        let door = PivotBuilder::new(BaseBuilder::new().with_name("Door")).build(&mut level.graph);
+        let door_id = level.graph[door].instance_id();
+        let level = scenes.add(level);
+        scenes.set_source_path(level, Some(PathBuf::from("level.rgs")));
+
+        let additive = scenes.add(Scene::new());
+        let reference = SceneNodeRef::<Pivot>::from_node(&scenes, level, door);
+        assert_eq!(reference.scene_path(), Some(Path::new("level.rgs")));
+        assert_eq!(reference.node_id(), door_id);
+
+        // The reference survives serialization.
+        let mut copy = SceneNodeRef::<Pivot>::default();
+        let mut visitor = Visitor::new();
+        reference.clone().visit("Ref", &mut visitor).unwrap();
+        let mut visitor =
+            Visitor::load_from_memory(&visitor.save_binary_to_vec().unwrap()).unwrap();
+        copy.visit("Ref", &mut visitor).unwrap();
+        assert_eq!(copy, reference);
+        assert_eq!(copy.resolve(&scenes), Some((level, door)));
+        assert_eq!(copy.get(&scenes).unwrap().name(), "Door");
+        assert!(SceneNodeRef::<Sprite>::new(None, door_id)
+            .get(&scenes)
+            .is_none());
+
+        // The node is found again after the scene was reloaded with different handles.
+        let mut reloaded = Scene {
+            graph: Graph::new(),
+            ..Default::default()
+        };
+        PivotBuilder::new(BaseBuilder::new()).build(&mut reloaded.graph);
+        let door = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name("Door")
+                .with_instance_id(door_id),
+        )
+        .build(&mut reloaded.graph);
+        scenes.remove(level);
+        let level = scenes.add(reloaded);
+        scenes.set_source_path(level, Some(PathBuf::from("level.rgs")));
+        assert_eq!(copy.resolve(&scenes), Some((level, door)));
+
+        // Wrong scene.
+        scenes.set_source_path(level, None);
+        scenes.set_source_path(additive, Some(PathBuf::from("level.rgs")));
+        assert_eq!(copy.resolve(&scenes), None);
+        assert!(SceneNodeRef::<Pivot>::default().resolve(&scenes).is_none());
+    }
+}
@@ -764,3 +764,68 @@ impl RigidBodyBuilder {
         graph.add_node(self.build_node())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Vector2, Vector3},
+            pool::Handle,
+        },
+        scene::{
+            base::BaseBuilder,
+            collider::{ColliderBuilder, ColliderShape},
+            graph::{physics::FixedTimestep, Graph},
+            node::Node,
+            rigidbody::RigidBodyBuilder,
+            transform::TransformBuilder,
+        },
+    };
+
+    fn create_falling_body(graph: &mut Graph) -> Handle<Node> {
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(0.25, 0.25, 0.25))
+            .build(graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_children(&[collider])
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 10.0, 0.0))
+                        .build(),
+                ),
+        )
+        .with_lin_vel(Vector3::new(1.0, 0.0, 0.0))
+        .build(graph)
+    }
+
+    fn simulate(frames: usize, dt: f32) -> Vector3<f32> {
+        let mut graph = Graph::new();
+        graph
+            .physics
+            .fixed_timestep
+            .set_value_and_mark_modified(FixedTimestep {
+                enabled: true,
+                step: 1.0 / 60.0,
+                interpolation: false,
+                ..Default::default()
+            });
+        let body = create_falling_body(&mut graph);
+        // Native rigid body is created on the first update.
+        graph.update(Vector2::new(800.0, 600.0), 0.0, Default::default());
+        for _ in 0..frames {
+            graph.update(Vector2::new(800.0, 600.0), dt, Default::default());
+        }
+        **graph[body].local_transform().position()
+    }
+
+    #[test]
+    fn test_fixed_timestep_is_frame_rate_independent() {
+        // Both simulations perform the same amount of fixed steps, so the results must match
+        // exactly regardless of the frame rate.
+        let at_60_fps = simulate(120, 1.0 / 60.0);
+        let at_30_fps = simulate(60, 1.0 / 30.0);
+        assert_eq!(at_60_fps, at_30_fps);
+        assert!(at_60_fps.y < 10.0);
+    }
+}
@@ -0,0 +1,717 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Save games built on scene snapshots. See [`SaveGame`] docs for more info.
+
+use crate::{
+    asset::manager::ResourceManager,
+    core::{
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        visitor::{FieldKind, PodVecView, Visit, VisitError, VisitResult, Visitor},
+    },
+    engine::SerializationContext,
+    graph::NodeHandleMap,
+    resource::model::ModelResource,
+    scene::{
+        merge::{assemble_graph, invalid_scene, parent_ref, Placement, Region, SceneVersion},
+        node::Node,
+        Scene, SceneLoader,
+    },
+};
+use fxhash::{FxHashMap, FxHashSet};
+use std::{path::Path, sync::Arc};
+
+/// Name of the region of a diff that lists the names of removed fields (`0`) and regions (`1`).
+const REMOVED_REGION: &str = "__Removed";
+
+/// Defines whether a part of a scene is stored in a save game or not.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub enum Persistence {
+    /// The data is stored in a save game.
+    #[default]
+    Persistent,
+    /// The data is not stored in a save game. Transient nodes, scripts and fields are restored from
+    /// the original scene, transient nodes that are not in the original scene are not restored at
+    /// all.
+    Transient,
+}
+
+/// Defines which parts of a scene are stored in a save game. See [`SaveGame`] docs for more info.
+#[derive(Clone, Default, Debug)]
+pub struct SaveGameOptions {
+    default_persistence: Persistence,
+    nodes: FxHashMap<Handle<Node>, Persistence>,
+    scripts: FxHashMap<Uuid, Persistence>,
+    transient_fields: FxHashMap<Uuid, Vec<String>>,
+}
+
+impl SaveGameOptions {
+    /// Creates new options where everything is persistent.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the persistence of the nodes that are not marked explicitly.
+    pub fn with_default_persistence(mut self, persistence: Persistence) -> Self {
+        self.default_persistence = persistence;
+        self
+    }
+
+    /// Sets the persistence of a node and its descendants. Persistence of the nearest marked
+    /// ancestor is used for every node. Descendants of a transient node are not restored if the
+    /// node is not in the original scene.
+    pub fn with_node(mut self, node: Handle<Node>, persistence: Persistence) -> Self {
+        self.nodes.insert(node, persistence);
+        self
+    }
+
+    /// Sets the persistence of every script of the given type.
+    pub fn with_script<T: TypeUuidProvider>(self, persistence: Persistence) -> Self {
+        self.with_script_uuid(T::type_uuid(), persistence)
+    }
+
+    /// Sets the persistence of every script with the given type uuid.
+    pub fn with_script_uuid(mut self, type_uuid: Uuid, persistence: Persistence) -> Self {
+        self.scripts.insert(type_uuid, persistence);
+        self
+    }
+
+    /// Marks a field of every node or script of the given type as transient. `field` is the name
+    /// of the field in serialized data (the name that is passed to [`Visit::visit`]).
+    pub fn with_transient_field<T: TypeUuidProvider>(self, field: &str) -> Self {
+        self.with_transient_field_uuid(T::type_uuid(), field)
+    }
+
+    /// Marks a field of every node or script with the given type uuid as transient. See
+    /// [`Self::with_transient_field`] docs for more info.
+    pub fn with_transient_field_uuid(mut self, type_uuid: Uuid, field: &str) -> Self {
+        self.transient_fields
+            .entry(type_uuid)
+            .or_default()
+            .push(field.to_string());
+        self
+    }
+
+    /// Replaces transient fields in the `data_name` region of `container` (a region with a type
+    /// uuid) with the fields of the original data. Fields of the objects that are not in the
+    /// original scene are kept.
+    fn restore_transient_fields(
+        &self,
+        container: &mut Region,
+        original: Option<&Region>,
+        data_name: &str,
+    ) {
+        let Some(FieldKind::Uuid(type_uuid)) = container.field("TypeUuid") else {
+            return;
+        };
+        let Some(names) = self.transient_fields.get(type_uuid) else {
+            return;
+        };
+        let Some(original) = original
+            .filter(|original| original.field("TypeUuid") == container.field("TypeUuid"))
+            .and_then(|original| original.child(data_name))
+        else {
+            return;
+        };
+        let Some(data) = container.child_mut(data_name) else {
+            return;
+        };
+        for name in names {
+            data.fields.retain(|(n, _)| n != name);
+            data.children.retain(|c| &c.name != name);
+            if let Some(kind) = original.field(name) {
+                data.fields.push((name.clone(), kind.clone()));
+            }
+            data.children.extend(original.child(name).cloned());
+        }
+    }
+
+    /// Replaces transient parts of the `Data` region of a node with the original data. Transient
+    /// scripts of the nodes that are not in the original scene are removed.
+    fn restore_transient(&self, data: &mut Region, original: Option<&Region>) {
+        self.restore_transient_fields(data, original, "NodeData");
+
+        let original_scripts = original
+            .and_then(|original| original.base())
+            .and_then(|base| base.child("Scripts"));
+        let Some(scripts) = data.base_mut().and_then(|base| base.child_mut("Scripts")) else {
+            return;
+        };
+        for item in scripts.children.iter_mut() {
+            let original_script = original_scripts
+                .and_then(|scripts| scripts.child(&item.name))
+                .and_then(|item| item.child("ItemData"))
+                .filter(|original| {
+                    original.field("TypeUuid")
+                        == item.child("ItemData").and_then(|d| d.field("TypeUuid"))
+                });
+            let Some(script) = item.child_mut("ItemData") else {
+                continue;
+            };
+            let persistence = match script.field("TypeUuid") {
+                Some(FieldKind::Uuid(type_uuid)) => {
+                    self.scripts.get(type_uuid).cloned().unwrap_or_default()
+                }
+                _ => Persistence::Persistent,
+            };
+            if persistence == Persistence::Transient {
+                *script = original_script.cloned().unwrap_or_else(|| {
+                    Region::new("ItemData").with_field("TypeUuid", FieldKind::Uuid(Uuid::nil()))
+                });
+            } else {
+                self.restore_transient_fields(script, original_script, "ScriptData");
+            }
+        }
+    }
+}
+
+/// Returns the changes that turn `original` into `current`, `None` if there are no changes.
+/// Changed and new fields and regions are stored as is, names of the removed ones are stored
+/// in the [`REMOVED_REGION`].
+fn diff_regions(original: &Region, current: &Region) -> Option<Region> {
+    let mut diff = Region::new(&current.name);
+    let mut removed = Region::new(REMOVED_REGION);
+    for (name, kind) in current.fields.iter() {
+        if original.field(name) != Some(kind) {
+            diff.fields.push((name.clone(), kind.clone()));
+        }
+    }
+    for (name, _) in original.fields.iter() {
+        if current.field(name).is_none() {
+            removed.fields.push((name.clone(), FieldKind::U8(0)));
+        }
+    }
+    for child in current.children.iter() {
+        match original.child(&child.name) {
+            Some(original_child) => diff.children.extend(diff_regions(original_child, child)),
+            None => diff.children.push(child.clone()),
+        }
+    }
+    for child in original.children.iter() {
+        if current.child(&child.name).is_none() {
+            removed.fields.push((child.name.clone(), FieldKind::U8(1)));
+        }
+    }
+    if !removed.fields.is_empty() {
+        diff.children.push(removed);
+    }
+    if diff.fields.is_empty() && diff.children.is_empty() {
+        None
+    } else {
+        Some(diff)
+    }
+}
+
+/// Applies the changes made by [`diff_regions`] to the region.
+fn apply_diff(region: &mut Region, diff: &Region) {
+    for (name, kind) in diff.fields.iter() {
+        match region.fields.iter_mut().find(|(n, _)| n == name) {
+            Some((_, existing)) => *existing = kind.clone(),
+            None => region.fields.push((name.clone(), kind.clone())),
+        }
+    }
+    for child in diff.children.iter() {
+        if child.name == REMOVED_REGION {
+            for (name, kind) in child.fields.iter() {
+                if *kind == FieldKind::U8(0) {
+                    region.fields.retain(|(n, _)| n != name);
+                } else {
+                    region.children.retain(|c| &c.name != name);
+                }
+            }
+        } else if let Some(existing) = region.child_mut(&child.name) {
+            apply_diff(existing, child);
+        } else {
+            region.children.push(child.clone());
+        }
+    }
+}
+
+fn invalid_save(message: &str) -> VisitError {
+    VisitError::User(format!("Invalid save game data: {message}"))
+}
+
+fn save_scene(scene: &mut Scene) -> Result<Visitor, VisitError> {
+    let mut visitor = Visitor::new();
+    scene.save("Scene", &mut visitor)?;
+    Ok(visitor)
+}
+
+fn original_version(original: &ModelResource) -> Result<SceneVersion, VisitError> {
+    if !original.is_ok() {
+        return Err(VisitError::User(format!(
+            "The original scene {} is not loaded!",
+            original.kind()
+        )));
+    }
+    let visitor = save_scene(original.data_ref().get_scene_mut())?;
+    SceneVersion::new(&visitor)?.ok_or_else(|| invalid_scene("no graph"))
+}
+
+/// A snapshot of a scene and an arbitrary state (for example, the state of a plugin). The scene
+/// is stored as a delta against the scene resource it was loaded from, so save games are small
+/// and survive changes in the parts of the scene that were not changed by the game.
+///
+/// A save game is created by [`super::SceneContainer::save_game`] and restored by [`Self::restore`]
+/// and [`super::SceneContainer::load_game`]. Nodes keep their handles when possible, handles to
+/// nodes in the restored state are remapped automatically. [`SaveGameOptions`] define which nodes,
+/// scripts and fields are stored in a save game and which are restored from the original scene.
+///
+/// ```rust,no_run
+/// # use fyrox_impl::{
+/// #     asset::manager::ResourceManager,
+/// #     core::{pool::Handle, reflect::prelude::*, visitor::prelude::*},
+/// #     engine::SerializationContext,
+/// #     resource::model::ModelResource,
+/// #     scene::{
+/// #         node::Node,
+/// #         savegame::{Persistence, SaveGame, SaveGameOptions},
+/// #         Scene, SceneContainer,
+/// #     },
+/// # };
+/// # use std::sync::Arc;
+/// #[derive(Visit, Reflect, Default, Debug)]
+/// struct GameState {
+///     player: Handle<Node>,
+///     score: u32,
+/// }
+///
+/// async fn save_and_load(
+///     scenes: &mut SceneContainer,
+///     scene: Handle<Scene>,
+///     original: &ModelResource,
+///     effects: Handle<Node>,
+///     state: &mut GameState,
+///     resource_manager: ResourceManager,
+///     serialization_context: Arc<SerializationContext>,
+/// ) -> Result<Handle<Scene>, VisitError> {
+///     let options = SaveGameOptions::new().with_node(effects, Persistence::Transient);
+///     let mut save = scenes.save_game(scene, original, state, &options)?;
+///     save.save("save.bin")?;
+///
+///     let save = SaveGame::load("save.bin", resource_manager.clone()).await?;
+///     let restored = save.restore(resource_manager, serialization_context).await?;
+///     scenes.load_game(restored, state)
+/// }
+/// ```
+#[derive(Default, Clone, Debug)]
+pub struct SaveGame {
+    original: ModelResource,
+    scene: Vec<u8>,
+    state: Vec<u8>,
+}
+
+impl Visit for SaveGame {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.original.visit("Original", &mut region)?;
+        PodVecView::from_pod_vec(&mut self.scene).visit("Scene", &mut region)?;
+        PodVecView::from_pod_vec(&mut self.state).visit("State", &mut region)?;
+
+        Ok(())
+    }
+}
+
+impl SaveGame {
+    /// Creates a save game of the scene that was loaded from the `original` resource. The original
+    /// resource must be loaded.
+    pub fn new<S: Visit + ?Sized>(
+        scene: &mut Scene,
+        original: &ModelResource,
+        state: &mut S,
+        options: &SaveGameOptions,
+    ) -> Result<Self, VisitError> {
+        let base = original_version(original)?;
+        let current =
+            SceneVersion::new(&save_scene(scene)?)?.ok_or_else(|| invalid_scene("no graph"))?;
+
+        let handle_of = |id: &Uuid| {
+            let index = current.indices[id];
+            (index as u32, current.records[index].generation)
+        };
+        let marked = current
+            .order
+            .iter()
+            .filter_map(|id| {
+                let (index, generation) = handle_of(id);
+                options
+                    .nodes
+                    .get(&Handle::new(index, generation))
+                    .map(|persistence| (*id, *persistence))
+            })
+            .collect::<FxHashMap<_, _>>();
+
+        let mut delta = Region::default();
+        if let Some(mut rest) = diff_regions(&base.rest, &current.rest) {
+            rest.name = "Rest".to_string();
+            delta.children.push(rest);
+        }
+
+        // Visit the hierarchy, so the persistence is inherited from the ancestors.
+        let mut nodes = Vec::new();
+        let mut stack = current
+            .rest
+            .path(&["Scene", "Graph", "Root"])
+            .and_then(|root| root.node_ref())
+            .map(|root| (root, options.default_persistence))
+            .into_iter()
+            .collect::<Vec<_>>();
+        while let Some((id, inherited)) = stack.pop() {
+            let persistence = marked.get(&id).cloned().unwrap_or(inherited);
+            let original = base.node(&id);
+            if persistence == Persistence::Transient && original.is_none() {
+                continue;
+            }
+            let (index, generation) = handle_of(&id);
+            let mut item = Region::new(&format!("Item{}", nodes.len()))
+                .with_field("Id", FieldKind::Uuid(id))
+                .with_field("Index", FieldKind::U32(index))
+                .with_field("Generation", FieldKind::U32(generation));
+            let children = current.children.get(&id).cloned().unwrap_or_default();
+            if persistence == Persistence::Persistent {
+                let mut data = current.node(&id).cloned().unwrap_or_default();
+                options.restore_transient(&mut data, original);
+                item.fields
+                    .push(("Persistent".to_string(), FieldKind::U8(1)));
+                let mut list = Region::new("Children")
+                    .with_field("Length", FieldKind::U32(children.len() as u32));
+                for (i, child) in children.iter().enumerate() {
+                    list.fields
+                        .push((format!("Item{i}"), FieldKind::Uuid(*child)));
+                }
+                item.children.push(list);
+                item.children.extend(diff_regions(
+                    original.unwrap_or(&Region::new(&data.name)),
+                    &data,
+                ));
+            }
+            nodes.push(item);
+            stack.extend(children.into_iter().rev().map(|child| (child, persistence)));
+        }
+        let mut nodes_region =
+            Region::new("Nodes").with_field("Length", FieldKind::U32(nodes.len() as u32));
+        nodes_region.children = nodes;
+        delta.children.push(nodes_region);
+
+        let mut visitor = Visitor::new();
+        delta.write_contents(&mut visitor)?;
+        let scene = visitor.save_binary_to_vec()?;
+
+        let mut visitor = Visitor::new();
+        state.visit("State", &mut visitor)?;
+        let state = visitor.save_binary_to_vec()?;
+
+        Ok(Self {
+            original: original.clone(),
+            scene,
+            state,
+        })
+    }
+
+    /// Returns the scene resource the save game was made from.
+    pub fn original(&self) -> &ModelResource {
+        &self.original
+    }
+
+    /// Writes the save game to a file.
+    pub fn save<P: AsRef<Path>>(&mut self, path: P) -> VisitResult {
+        let mut visitor = Visitor::new();
+        self.visit("SaveGame", &mut visitor)?;
+        visitor.save_binary(path)
+    }
+
+    /// Loads a save game from a file. The original scene resource is requested from the resource
+    /// manager.
+    pub async fn load<P: AsRef<Path>>(
+        path: P,
+        resource_manager: ResourceManager,
+    ) -> Result<Self, VisitError> {
+        let mut visitor = Visitor::load_binary(path).await?;
+        visitor.blackboard.register(Arc::new(resource_manager));
+        let mut save_game = Self::default();
+        save_game.visit("SaveGame", &mut visitor)?;
+        Ok(save_game)
+    }
+
+    /// Restores the scene of the save game. The saved state must be loaded separately using
+    /// [`RestoredGame::load_state`] or [`super::SceneContainer::load_game`].
+    pub async fn restore(
+        &self,
+        resource_manager: ResourceManager,
+        serialization_context: Arc<SerializationContext>,
+    ) -> Result<RestoredGame, VisitError> {
+        let original = self.original.clone().await.map_err(|err| {
+            VisitError::User(format!("Unable to load the original scene: {err:?}"))
+        })?;
+        let base = original_version(&original)?;
+        let visitor = Visitor::load_from_memory(&self.scene)?;
+        let delta = Region::from_visitor(&visitor, visitor.root());
+
+        let mut rest = base.rest.clone();
+        if let Some(diff) = delta.child("Rest") {
+            apply_diff(&mut rest, diff);
+        }
+
+        let mut nodes = Vec::new();
+        let mut old_handles = FxHashMap::default();
+        let mut children_order = FxHashMap::default();
+        for item in delta
+            .child("Nodes")
+            .ok_or_else(|| invalid_save("no nodes"))?
+            .children
+            .iter()
+        {
+            let (Some(FieldKind::Uuid(id)), Some(index), Some(generation)) = (
+                item.field("Id"),
+                item.u32_field("Index"),
+                item.u32_field("Generation"),
+            ) else {
+                return Err(invalid_save("no node handle"));
+            };
+            old_handles.insert(*id, Handle::<Node>::new(index, generation));
+            let original = base.node(id);
+            if item.field("Persistent") == Some(&FieldKind::U8(1)) {
+                let mut data = original.cloned().unwrap_or_else(|| Region::new("Data"));
+                if let Some(diff) = item.child("Data") {
+                    apply_diff(&mut data, diff);
+                }
+                let children = item
+                    .child("Children")
+                    .map(|list| {
+                        list.fields
+                            .iter()
+                            .filter_map(|(_, kind)| match kind {
+                                FieldKind::Uuid(child) => Some(*child),
+                                _ => None,
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                children_order.insert(*id, children);
+                nodes.push((*id, data, Placement::Exact(index, generation)));
+            } else if let Some(original) = original {
+                nodes.push((
+                    *id,
+                    original.clone(),
+                    Placement::Preferred(index, generation),
+                ));
+            }
+        }
+
+        // Transient nodes are restored from the original scene, their original parents might be
+        // gone.
+        loop {
+            let ids = nodes.iter().map(|(id, _, _)| *id).collect::<FxHashSet<_>>();
+            let count = nodes.len();
+            nodes.retain(|(_, data, _)| parent_ref(data).map_or(true, |p| ids.contains(&p)));
+            if nodes.len() == count {
+                break;
+            }
+        }
+
+        let mut dangling = Vec::new();
+        let handles = assemble_graph(
+            &mut rest,
+            nodes,
+            Vec::new(),
+            &|id| children_order.get(id).or_else(|| base.children.get(id)),
+            &mut dangling,
+            false,
+        )?;
+
+        let mut handle_map = NodeHandleMap::default();
+        for (id, old) in old_handles {
+            if let Some((index, generation)) = handles.get(&id) {
+                handle_map.insert(old, Handle::new(*index, *generation));
+            }
+        }
+
+        let mut visitor = Visitor::new();
+        rest.write_contents(&mut visitor)?;
+        let mut visitor = Visitor::load_from_memory(&visitor.save_binary_to_vec()?)?;
+        let scene = SceneLoader::load(
+            "Scene",
+            serialization_context.clone(),
+            resource_manager.clone(),
+            &mut visitor,
+            None,
+        )?
+        .finish()
+        .await;
+
+        Ok(RestoredGame {
+            scene,
+            handle_map,
+            state: self.state.clone(),
+            resource_manager,
+            serialization_context,
+        })
+    }
+}
+
+/// A scene restored by [`SaveGame::restore`].
+pub struct RestoredGame {
+    /// The restored scene.
+    pub scene: Scene,
+    /// Maps the handles of the nodes at the moment of saving to the handles of the restored nodes.
+    pub handle_map: NodeHandleMap<Node>,
+    state: Vec<u8>,
+    resource_manager: ResourceManager,
+    serialization_context: Arc<SerializationContext>,
+}
+
+impl RestoredGame {
+    /// Loads the saved state and remaps its handles to the nodes of the restored scene.
+    pub fn load_state<S: Visit + Reflect + ?Sized>(&self, state: &mut S) -> VisitResult {
+        let mut visitor = Visitor::load_from_memory(&self.state)?;
+        visitor
+            .blackboard
+            .register(self.serialization_context.clone());
+        visitor
+            .blackboard
+            .register(Arc::new(self.resource_manager.clone()));
+        state.visit("State", &mut visitor)?;
+        state.as_reflect_mut(&mut |state| {
+            self.handle_map
+                .remap_handles_in(state, "save game state", &[])
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::{manager::ResourceManager, untyped::ResourceKind},
+        core::{
+            algebra::Vector3, futures::executor::block_on, pool::Handle, reflect::prelude::*,
+            visitor::prelude::*,
+        },
+        engine::SerializationContext,
+        graph::{BaseSceneGraph, NodeMapping, SceneGraph},
+        resource::model::{Model, ModelResource},
+        scene::{
+            base::BaseBuilder,
+            node::Node,
+            pivot::PivotBuilder,
+            savegame::{Persistence, SaveGame, SaveGameOptions},
+            Scene, SceneLoader,
+        },
+    };
+    use std::sync::Arc;
+
+    #[derive(Visit, Reflect, Default, Debug)]
+    struct State {
+        target: Handle<Node>,
+        score: u32,
+    }
+
+    fn copy(scene: &mut Scene) -> Scene {
+        let mut visitor = Visitor::new();
+        scene.save("Scene", &mut visitor).unwrap();
+        let mut visitor =
+            Visitor::load_from_memory(&visitor.save_binary_to_vec().unwrap()).unwrap();
+        SceneLoader::load(
+            "Scene",
+            Arc::new(SerializationContext::new()),
+            ResourceManager::new(Arc::new(Default::default())),
+            &mut visitor,
+            None,
+        )
+        .unwrap()
+        .scene
+    }
+
+    fn find(scene: &Scene, name: &str) -> Option<Handle<Node>> {
+        scene.graph.find_by_name_from_root(name).map(|(h, _)| h)
+    }
+
+    fn add_pivot(scene: &mut Scene, name: &str, parent: Handle<Node>) -> Handle<Node> {
+        let pivot = PivotBuilder::new(BaseBuilder::new().with_name(name)).build(&mut scene.graph);
+        scene.graph.link_nodes(pivot, parent);
+        pivot
+    }
+
+    #[test]
+    fn test_save_game() {
+        let mut scene = Scene::new();
+        let root = scene.graph.get_root();
+        add_pivot(&mut scene, "A", root);
+        let b = add_pivot(&mut scene, "B", root);
+        add_pivot(&mut scene, "C", root);
+        add_pivot(&mut scene, "BChild", b);
+        let mut game = copy(&mut scene);
+        let original = ModelResource::new_ok(
+            ResourceKind::Embedded,
+            Model::new(NodeMapping::UseNames, scene),
+        );
+
+        let (a, b, c) = (
+            find(&game, "A").unwrap(),
+            find(&game, "B").unwrap(),
+            find(&game, "C").unwrap(),
+        );
+        game.graph[a]
+            .local_transform_mut()
+            .set_position(Vector3::new(1.0, 2.0, 3.0));
+        game.graph[b].set_visibility(false);
+        game.graph.remove_node(c);
+        let d = add_pivot(&mut game, "D", a);
+        add_pivot(&mut game, "E", b);
+        let mut state = State {
+            target: d,
+            score: 42,
+        };
+
+        let options = SaveGameOptions::new().with_node(b, Persistence::Transient);
+        let save = SaveGame::new(&mut game, &original, &mut state, &options).unwrap();
+
+        let mut state = State::default();
+        let restored = block_on(save.restore(
+            ResourceManager::new(Arc::new(Default::default())),
+            Arc::new(SerializationContext::new()),
+        ))
+        .unwrap();
+        restored.load_state(&mut state).unwrap();
+        let scene = &restored.scene;
+
+        let (a, b, d) = (
+            find(scene, "A").unwrap(),
+            find(scene, "B").unwrap(),
+            find(scene, "D").unwrap(),
+        );
+        assert_eq!(
+            **scene.graph[a].local_transform().position(),
+            Vector3::new(1.0, 2.0, 3.0)
+        );
+        // Transient node is restored from the original scene, new transient nodes are dropped.
+        assert!(scene.graph[b].visibility());
+        assert!(find(scene, "BChild").is_some());
+        assert!(find(scene, "E").is_none());
+        // Removed node stays removed, new node is restored.
+        assert!(find(scene, "C").is_none());
+        assert_eq!(scene.graph[d].parent(), a);
+        assert_eq!(state.target, d);
+        assert_eq!(state.score, 42);
+    }
+}
@@ -0,0 +1,567 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Vehicle is a ray-cast based vehicle simulation, see [`Vehicle`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Point3, Translation3, Unit, UnitQuaternion, Vector3},
+        color::Color,
+        log::Log,
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        uuid_provider,
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        debug::{Line, SceneDrawingContext},
+        graph::Graph,
+        node::{Node, NodeTrait, SyncContext, UpdateContext},
+        rigidbody::{RigidBody, RigidBodyType},
+        Scene,
+    },
+};
+use fyrox_graph::{BaseSceneGraph, SceneGraph};
+use rapier3d::control::DynamicRayCastVehicleController;
+use std::{
+    cell::Cell,
+    fmt::{Debug, Formatter},
+    ops::{Deref, DerefMut},
+};
+
+/// A wheel of a [`Vehicle`]. All the vectors are defined in the local coordinates of the vehicle.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct Wheel {
+    /// A point where the suspension of the wheel is attached to the chassis.
+    pub connection_point: Vector3<f32>,
+    /// A direction of the suspension, usually it points down.
+    pub direction: Vector3<f32>,
+    /// A rotation axis of the wheel. The vehicle moves along `direction x axle` when the engine
+    /// force is positive, it is `-X` for the vehicles that move along `+Z`.
+    pub axle: Vector3<f32>,
+    /// A radius of the wheel.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub radius: f32,
+    /// A length of the suspension when it is neither compressed nor stretched.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub suspension_rest_length: f32,
+    /// The maximum distance the suspension can travel before and after its rest length.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub max_suspension_travel: f32,
+    /// The stiffness of the suspension. Increase this value if the suspension does not push the
+    /// vehicle strong enough.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub suspension_stiffness: f32,
+    /// The damping of the suspension when it is being compressed.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub suspension_compression: f32,
+    /// The damping of the suspension when it is being released. Increase this value if the
+    /// suspension overshoots.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub suspension_damping: f32,
+    /// The maximum force the suspension can apply.
+    #[reflect(min_value = 0.0, step = 1.0)]
+    pub max_suspension_force: f32,
+    /// Defines how much traction the tire has. The larger the value, the more instantaneous
+    /// braking will happen (with the risk of flipping the vehicle over).
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub friction_slip: f32,
+    /// The multiplier of the side friction of the tire.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub side_friction_stiffness: f32,
+    /// Whether the wheel is rotated by [`Vehicle::set_steering`] or not.
+    pub steering: bool,
+    /// Whether the wheel is driven by [`Vehicle::set_engine_force`] or not.
+    pub drive: bool,
+    /// Whether the wheel is braked by [`Vehicle::set_brake`] or not.
+    pub brake: bool,
+    /// A node that will be moved and rotated with the wheel. It must be a direct child of the
+    /// vehicle, its rotation axis must be aligned with [`Self::axle`].
+    pub visual: Handle<Node>,
+}
+
+uuid_provider!(Wheel = "925f02b6-fc83-495b-a348-e44b2813de88");
+
+impl Default for Wheel {
+    fn default() -> Self {
+        Self {
+            connection_point: Default::default(),
+            direction: -Vector3::y(),
+            axle: -Vector3::x(),
+            radius: 0.35,
+            suspension_rest_length: 0.3,
+            max_suspension_travel: 0.2,
+            suspension_stiffness: 20.0,
+            suspension_compression: 0.83,
+            suspension_damping: 0.88,
+            max_suspension_force: 6000.0,
+            friction_slip: 10.5,
+            side_friction_stiffness: 1.0,
+            steering: false,
+            drive: false,
+            brake: true,
+            visual: Default::default(),
+        }
+    }
+}
+
+impl Wheel {
+    fn up(&self) -> Vector3<f32> {
+        -self
+            .direction
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_else(|| -Vector3::y())
+    }
+
+    /// Returns local position of the center of the wheel for the given suspension length.
+    pub fn center(&self, suspension_length: f32) -> Vector3<f32> {
+        self.connection_point - self.up() * suspension_length
+    }
+}
+
+/// Actual state of a wheel after the last physics step.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WheelState {
+    /// `true` if the wheel touches the ground.
+    pub in_contact: bool,
+    /// A handle of the collider the wheel touches.
+    pub ground: Handle<Node>,
+    /// A contact point of the wheel in world coordinates.
+    pub contact_point: Vector3<f32>,
+    /// A normal of the ground at the contact point in world coordinates.
+    pub contact_normal: Vector3<f32>,
+    /// Current length of the suspension.
+    pub suspension_length: f32,
+    /// Current steering angle of the wheel (in radians).
+    pub steering: f32,
+    /// Current rotation angle of the wheel around its axle (in radians).
+    pub rotation: f32,
+    /// A force that is applied by the suspension.
+    pub suspension_force: f32,
+}
+
+/// Vehicle is a ray-cast based vehicle simulation. Every wheel is represented by a ray that is
+/// cast along the direction of its suspension, the suspension pushes the chassis up and the wheels
+/// apply engine, brake and friction forces to the chassis. This is much more stable than wheels
+/// made of rigid bodies and joints and allows you to tune the handling of the vehicle easily.
+///
+/// Vehicle must be a direct child of a dynamic [`RigidBody`] (chassis). Forward direction of the
+/// vehicle is the `+Z` axis of the chassis, up direction is `+Y`. Wheels are defined in the local
+/// coordinates of the vehicle node (see [`Wheel`]), every wheel could have a visual node that
+/// will be moved and rotated accordingly to the suspension, steering and rotation of the wheel.
+///
+/// The vehicle is controlled by [`Self::set_engine_force`], [`Self::set_steering`] and
+/// [`Self::set_brake`], each of them is applied to the wheels that have respective flags set.
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::pool::Handle,
+/// #     scene::{graph::Graph, node::Node, vehicle::Vehicle},
+/// # };
+/// fn drive(graph: &mut Graph, vehicle: Handle<Node>, throttle: f32, steer: f32) {
+///     let vehicle = graph[vehicle].as_vehicle_mut();
+///     vehicle.set_engine_force(throttle * 100.0);
+///     vehicle.set_steering(steer * 0.5);
+/// }
+/// ```
+#[derive(Visit, Reflect)]
+pub struct Vehicle {
+    base: Base,
+
+    #[reflect(setter = "set_wheels")]
+    pub(crate) wheels: InheritableVariable<Vec<Wheel>>,
+
+    #[reflect(setter = "set_engine_force")]
+    pub(crate) engine_force: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_steering")]
+    pub(crate) steering: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, step = 0.05)]
+    #[reflect(setter = "set_brake")]
+    pub(crate) brake: InheritableVariable<f32>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) native: Cell<Handle<DynamicRayCastVehicleController>>,
+    // Local transform that was used to place the wheels of the native vehicle.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) native_transform: Cell<Matrix4<f32>>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) wheel_states: Vec<WheelState>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) speed: f32,
+}
+
+impl Debug for Vehicle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Vehicle")
+    }
+}
+
+impl Default for Vehicle {
+    fn default() -> Self {
+        VehicleBuilder::new(BaseBuilder::new()).build_vehicle()
+    }
+}
+
+impl Deref for Vehicle {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Vehicle {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl Clone for Vehicle {
+    fn clone(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+            wheels: self.wheels.clone(),
+            engine_force: self.engine_force.clone(),
+            steering: self.steering.clone(),
+            brake: self.brake.clone(),
+            // Do not copy. The copy will have its own native representation.
+            native: Default::default(),
+            native_transform: Cell::new(Matrix4::identity()),
+            wheel_states: self.wheel_states.clone(),
+            speed: self.speed,
+        }
+    }
+}
+
+impl TypeUuidProvider for Vehicle {
+    fn type_uuid() -> Uuid {
+        uuid!("b569fb28-fd04-4ea6-9c7c-e55b5767443b")
+    }
+}
+
+impl Vehicle {
+    /// Sets new wheels of the vehicle.
+    pub fn set_wheels(&mut self, wheels: Vec<Wheel>) -> Vec<Wheel> {
+        self.wheels.set_value_and_mark_modified(wheels)
+    }
+
+    /// Returns a reference to the wheels of the vehicle.
+    pub fn wheels(&self) -> &[Wheel] {
+        &self.wheels
+    }
+
+    /// Returns a mutable reference to the wheels of the vehicle.
+    pub fn wheels_mut(&mut self) -> &mut Vec<Wheel> {
+        self.wheels.get_value_mut_and_mark_modified()
+    }
+
+    /// Sets the force that is applied by every driving wheel. Negative values move the vehicle
+    /// backwards.
+    pub fn set_engine_force(&mut self, force: f32) -> f32 {
+        self.engine_force.set_value_and_mark_modified(force)
+    }
+
+    /// Returns the force that is applied by every driving wheel.
+    pub fn engine_force(&self) -> f32 {
+        *self.engine_force
+    }
+
+    /// Sets the steering angle (in radians) of every steering wheel.
+    pub fn set_steering(&mut self, angle: f32) -> f32 {
+        self.steering.set_value_and_mark_modified(angle)
+    }
+
+    /// Returns the steering angle (in radians).
+    pub fn steering(&self) -> f32 {
+        *self.steering
+    }
+
+    /// Sets the brake force that is applied by every braking wheel.
+    pub fn set_brake(&mut self, brake: f32) -> f32 {
+        self.brake.set_value_and_mark_modified(brake)
+    }
+
+    /// Returns the brake force.
+    pub fn brake(&self) -> f32 {
+        *self.brake
+    }
+
+    /// Returns actual states of the wheels after the last physics step. The states are in the same
+    /// order as the wheels.
+    pub fn wheel_states(&self) -> &[WheelState] {
+        &self.wheel_states
+    }
+
+    /// Returns current forward speed of the vehicle, it is negative when the vehicle moves
+    /// backwards.
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+}
+
+impl NodeTrait for Vehicle {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.world_bounding_box()
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn on_removed_from_graph(&mut self, graph: &mut Graph) {
+        graph.physics.remove_vehicle(self.native.get());
+        self.native.set(Handle::NONE);
+
+        Log::info(format!(
+            "Native vehicle was removed for node: {}",
+            self.name()
+        ));
+    }
+
+    fn sync_native(&self, _self_handle: Handle<Node>, context: &mut SyncContext) {
+        context.physics.sync_to_vehicle_node(context.nodes, self);
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        context.physics.sync_vehicle_node(self);
+
+        for (wheel, state) in self.wheels.iter().zip(self.wheel_states.iter()) {
+            if let Some(visual) = context.nodes.try_borrow_mut(wheel.visual) {
+                let up = wheel.up();
+                let axle = wheel
+                    .axle
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_else(|| -Vector3::x());
+                visual
+                    .local_transform_mut()
+                    .set_position(wheel.center(state.suspension_length))
+                    .set_rotation(
+                        UnitQuaternion::from_axis_angle(&Unit::new_unchecked(up), state.steering)
+                            * UnitQuaternion::from_axis_angle(
+                                &Unit::new_unchecked(-axle),
+                                state.rotation,
+                            ),
+                    );
+            }
+        }
+    }
+
+    fn debug_draw(&self, ctx: &mut SceneDrawingContext) {
+        let transform = self.global_transform();
+        for (i, wheel) in self.wheels.iter().enumerate() {
+            let suspension_length = self
+                .wheel_states
+                .get(i)
+                .map_or(wheel.suspension_rest_length, |s| s.suspension_length);
+            let center = wheel.center(suspension_length);
+
+            ctx.add_line(Line {
+                begin: transform
+                    .transform_point(&Point3::from(wheel.connection_point))
+                    .coords,
+                end: transform.transform_point(&Point3::from(center)).coords,
+                color: Color::DARK_ORANGE,
+            });
+
+            let orientation = UnitQuaternion::rotation_between(&Vector3::z(), &wheel.axle)
+                .unwrap_or_else(|| {
+                    UnitQuaternion::from_axis_angle(&Vector3::y_axis(), std::f32::consts::PI)
+                });
+            ctx.draw_circle(
+                Vector3::default(),
+                wheel.radius,
+                16,
+                transform
+                    * Translation3::from(center).to_homogeneous()
+                    * orientation.to_homogeneous(),
+                Color::GREEN,
+            );
+        }
+    }
+
+    fn validate(&self, scene: &Scene) -> Result<(), String> {
+        if scene
+            .graph
+            .try_get_of_type::<RigidBody>(self.parent())
+            .is_some_and(|body| body.body_type() == RigidBodyType::Dynamic)
+        {
+            Ok(())
+        } else {
+            Err("The vehicle must be a direct child of a dynamic rigid body!".to_string())
+        }
+    }
+}
+
+/// Allows you to create vehicle in declarative manner.
+pub struct VehicleBuilder {
+    base_builder: BaseBuilder,
+    wheels: Vec<Wheel>,
+}
+
+impl VehicleBuilder {
+    /// Creates new vehicle builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            wheels: Default::default(),
+        }
+    }
+
+    /// Sets the desired wheels.
+    pub fn with_wheels(mut self, wheels: Vec<Wheel>) -> Self {
+        self.wheels = wheels;
+        self
+    }
+
+    /// Creates Vehicle node but does not add it to the graph.
+    pub fn build_vehicle(self) -> Vehicle {
+        Vehicle {
+            base: self.base_builder.build_base(),
+            wheels: self.wheels.into(),
+            engine_force: Default::default(),
+            steering: Default::default(),
+            brake: Default::default(),
+            native: Default::default(),
+            native_transform: Cell::new(Matrix4::identity()),
+            wheel_states: Default::default(),
+            speed: 0.0,
+        }
+    }
+
+    /// Creates Vehicle node but does not add it to the graph.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_vehicle())
+    }
+
+    /// Creates Vehicle node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::core::algebra::{Vector2, Vector3};
+    use crate::scene::{
+        base::BaseBuilder,
+        collider::{ColliderBuilder, ColliderShape},
+        graph::Graph,
+        pivot::PivotBuilder,
+        rigidbody::{RigidBodyBuilder, RigidBodyType},
+        transform::TransformBuilder,
+        vehicle::{VehicleBuilder, Wheel},
+    };
+
+    #[test]
+    fn test_vehicle_stands_on_wheels_and_drives() {
+        let mut graph = Graph::new();
+
+        let ground_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(50.0, 0.5, 50.0))
+            .build(&mut graph);
+        RigidBodyBuilder::new(BaseBuilder::new().with_children(&[ground_collider]))
+            .with_body_type(RigidBodyType::Static)
+            .build(&mut graph);
+
+        let mut visuals = Vec::new();
+        let mut wheels = Vec::new();
+        for (x, z) in [(-1.0, 1.5), (1.0, 1.5), (-1.0, -1.5), (1.0, -1.5)] {
+            let visual = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+            visuals.push(visual);
+            wheels.push(Wheel {
+                connection_point: Vector3::new(x, 0.0, z),
+                steering: z > 0.0,
+                drive: true,
+                visual,
+                ..Default::default()
+            });
+        }
+        let vehicle = VehicleBuilder::new(BaseBuilder::new().with_children(&visuals))
+            .with_wheels(wheels)
+            .build(&mut graph);
+        let chassis_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(1.0, 0.25, 2.0))
+            .with_density(Some(100.0))
+            .build(&mut graph);
+        let chassis = RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_children(&[chassis_collider, vehicle])
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 2.0, 0.0))
+                        .build(),
+                ),
+        )
+        .with_can_sleep(false)
+        .build(&mut graph);
+
+        for _ in 0..180 {
+            graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        }
+
+        let states = graph[vehicle].as_vehicle().wheel_states();
+        assert_eq!(states.len(), 4);
+        assert!(states
+            .iter()
+            .all(|state| state.in_contact && state.ground == ground_collider));
+        // The chassis stands on the wheels and does not touch the ground.
+        let height = graph[chassis].global_position().y;
+        assert!(height > 0.75 && height < 1.5, "{}", height);
+        // Visual nodes follow the suspension.
+        let visual_position = **graph[visuals[0]].local_transform().position();
+        assert!(visual_position.y < 0.0 && visual_position.y > -1.0);
+
+        graph[vehicle].as_vehicle_mut().set_engine_force(200.0);
+        for _ in 0..60 {
+            graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        }
+
+        assert!(graph[vehicle].as_vehicle().speed() > 0.0);
+        assert!(graph[chassis].global_position().z > 0.1);
+
+        // The wheels are placed in the space of the chassis, so moving the vehicle node must
+        // re-create the native vehicle.
+        let native = graph[vehicle].as_vehicle().native.get();
+        graph[vehicle]
+            .local_transform_mut()
+            .set_position(Vector3::new(0.0, 0.1, 0.0));
+        graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        let vehicle = graph[vehicle].as_vehicle();
+        assert_ne!(vehicle.native.get(), native);
+        assert!(vehicle.native.get().is_some());
+    }
+}
@@ -566,6 +566,18 @@ impl<'a, 'b, 'c> UniversalScriptContext for ScriptDeinitContext<'a, 'b, 'c> {
     }
 }
 
+/// Describes a collision (or an intersection) of two colliders, that is passed to collision-related
+/// methods of [`ScriptTrait`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Collision {
+    /// A collider that belongs to the node with the script, or to one of its descendants (if the
+    /// script is assigned to a rigid body).
+    pub collider: Handle<Node>,
+    /// A collider that collided with [`Self::collider`]. Could be [`Handle::NONE`] if the collider
+    /// was removed.
+    pub other: Handle<Node>,
+}
+
 /// Script is a set predefined methods that are called on various stages by the engine. It is used to add
 /// custom behaviour to game entities.
 pub trait ScriptTrait: BaseScript + ComponentProvider {
@@ -650,6 +662,53 @@ pub trait ScriptTrait: BaseScript + ComponentProvider {
         #[allow(unused_variables)] ctx: &mut ScriptMessageContext,
     ) {
     }
+
+    /// Called when a collider of the node (or a collider attached to the rigid body the script is
+    /// assigned to) started touching other collider. The method is called for both sides of the
+    /// collision, after [`ScriptTrait::on_update`] of the same frame.
+    fn on_collision_enter(
+        &mut self,
+        #[allow(unused_variables)] collision: &Collision,
+        #[allow(unused_variables)] ctx: &mut ScriptContext,
+    ) {
+    }
+
+    /// Called when a collider of the node (or a collider attached to the rigid body the script is
+    /// assigned to) stopped touching other collider.
+    fn on_collision_exit(
+        &mut self,
+        #[allow(unused_variables)] collision: &Collision,
+        #[allow(unused_variables)] ctx: &mut ScriptContext,
+    ) {
+    }
+
+    /// The same as [`ScriptTrait::on_collision_enter`], but called when at least one of the colliders
+    /// is a sensor (trigger).
+    fn on_trigger_enter(
+        &mut self,
+        #[allow(unused_variables)] collision: &Collision,
+        #[allow(unused_variables)] ctx: &mut ScriptContext,
+    ) {
+    }
+
+    /// The same as [`ScriptTrait::on_collision_exit`], but called when at least one of the colliders
+    /// is a sensor (trigger).
+    fn on_trigger_exit(
+        &mut self,
+        #[allow(unused_variables)] collision: &Collision,
+        #[allow(unused_variables)] ctx: &mut ScriptContext,
+    ) {
+    }
+
+    /// Called when a joint breaks because of exceeding its break force or torque (see
+    /// [`crate::scene::joint::Joint::set_break_force`]). The method is called for scripts of the
+    /// joint node and of both bodies attached to the joint.
+    fn on_joint_break(
+        &mut self,
+        #[allow(unused_variables)] joint: Handle<Node>,
+        #[allow(unused_variables)] ctx: &mut ScriptContext,
+    ) {
+    }
 }
 
 /// A wrapper for actual script instance internals, it used by the engine.
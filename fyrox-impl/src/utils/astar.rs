@@ -435,6 +435,38 @@ impl<T: VertexDataProvider> Graph<T> {
         to: usize,
         path: &mut Vec<usize>,
     ) -> Result<PathKind, PathError> {
+        self.build_indexed_path_with_cost(
+            from,
+            to,
+            |current, neighbour| {
+                let current = &self.vertices[current];
+                let neighbour = &self.vertices[neighbour];
+                Some((current.position - neighbour.position).norm_squared() * neighbour.g_penalty)
+            },
+            path,
+        )
+    }
+
+    /// Tries to build path of vertex indices from beginning point to endpoint, using the given function
+    /// to calculate the cost of travel between two neighbour vertices. The function takes indices of the
+    /// current vertex and its neighbour and should return `None` if the neighbour cannot be reached
+    /// from the current vertex. Returns path kind the same way as [`Self::build_indexed_path`].
+    ///
+    /// # Notes
+    ///
+    /// Cost function is called only for valid vertex indices. The heuristic is the squared distance
+    /// to the end point, so the cost should be comparable with squared distance between the vertices
+    /// for the search to be efficient.
+    pub fn build_indexed_path_with_cost<F>(
+        &self,
+        from: usize,
+        to: usize,
+        mut cost: F,
+        path: &mut Vec<usize>,
+    ) -> Result<PathKind, PathError>
+    where
+        F: FnMut(usize, usize) -> Option<f32>,
+    {
         path.clear();
 
         if self.vertices.is_empty() {
@@ -483,14 +515,16 @@ impl<T: VertexDataProvider> Graph<T> {
                 .get(current_index)
                 .ok_or(PathError::InvalidIndex(current_index))?;
 
+            // breaks if end is found, it could have worse score than the best path, if the costs
+            // of travel are not uniform
+            if current_index == to {
+                best_path = current_path;
+                break;
+            }
+
             // updates best path
             if current_path > best_path {
                 best_path = current_path.clone();
-
-                // breaks if end is found
-                if current_index == to {
-                    break;
-                }
             }
 
             // evaluates path scores one level deeper and adds the paths to the heap
@@ -508,16 +542,19 @@ impl<T: VertexDataProvider> Graph<T> {
                     continue;
                 }
 
-                let neighbour = self
+                let neighbour_position = self
                     .vertices
                     .get(neighbour_index)
-                    .ok_or(PathError::InvalidIndex(neighbour_index))?;
+                    .ok_or(PathError::InvalidIndex(neighbour_index))?
+                    .position;
+
+                let Some(travel_cost) = cost(current_index, neighbour_index) else {
+                    continue;
+                };
 
-                let neighbour_g_score = current_path.g_score
-                    + ((current_vertex.position - neighbour.position).norm_squared()
-                        * neighbour.g_penalty);
+                let neighbour_g_score = current_path.g_score + travel_cost;
 
-                let neighbour_f_score = neighbour_g_score + heuristic(neighbour.position, end_pos);
+                let neighbour_f_score = neighbour_g_score + heuristic(neighbour_position, end_pos);
 
                 search_heap.push(current_path.clone_and_add(
                     neighbour_index,
@@ -0,0 +1,236 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Blackboard is a shared key-value storage of a behavior tree. It allows nodes of the tree to
+//! exchange data between each other without knowing about each other - for example one action
+//! could find a target and write its handle in the blackboard and another action could read the
+//! handle and move an actor towards the target. See [`Blackboard`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Vector2, Vector3},
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        visitor::prelude::*,
+    },
+    scene::node::Node,
+};
+use fxhash::FxHashMap;
+use std::marker::PhantomData;
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// A value that could be stored in a blackboard.
+#[derive(
+    Clone, Debug, PartialEq, Visit, Reflect, AsRefStr, EnumString, VariantNames, TypeUuidProvider,
+)]
+#[type_uuid(id = "48b2111e-2661-4633-9512-83a400f1ff07")]
+pub enum BlackboardValue {
+    /// Boolean value.
+    Bool(bool),
+    /// Integer value.
+    Integer(i64),
+    /// Floating-point value.
+    Float(f32),
+    /// String value.
+    String(String),
+    /// Two-dimensional vector.
+    Vector2(Vector2<f32>),
+    /// Three-dimensional vector.
+    Vector3(Vector3<f32>),
+    /// A handle of a scene node.
+    Node(Handle<Node>),
+}
+
+impl Default for BlackboardValue {
+    fn default() -> Self {
+        Self::Bool(false)
+    }
+}
+
+/// A type that could be stored in a blackboard. It is implemented for every type that has a
+/// respective variant in [`BlackboardValue`].
+pub trait BlackboardValueType: Sized {
+    /// Wraps the value into [`BlackboardValue`].
+    fn into_value(self) -> BlackboardValue;
+
+    /// Tries to extract a value of this type from the given [`BlackboardValue`]. Returns [`None`]
+    /// if the value has different type.
+    fn from_value(value: &BlackboardValue) -> Option<Self>;
+}
+
+macro_rules! define_blackboard_value_type {
+    ($ty:ty, $variant:ident) => {
+        impl BlackboardValueType for $ty {
+            fn into_value(self) -> BlackboardValue {
+                BlackboardValue::$variant(self)
+            }
+
+            fn from_value(value: &BlackboardValue) -> Option<Self> {
+                if let BlackboardValue::$variant(value) = value {
+                    Some(value.clone())
+                } else {
+                    None
+                }
+            }
+        }
+    };
+}
+
+define_blackboard_value_type!(bool, Bool);
+define_blackboard_value_type!(i64, Integer);
+define_blackboard_value_type!(f32, Float);
+define_blackboard_value_type!(String, String);
+define_blackboard_value_type!(Vector2<f32>, Vector2);
+define_blackboard_value_type!(Vector3<f32>, Vector3);
+define_blackboard_value_type!(Handle<Node>, Node);
+
+/// A typed key of a blackboard entry. Typed keys are meant to be declared as constants, so the type
+/// of an entry is checked by the compiler on every access:
+///
+/// ```rust
+/// use fyrox_impl::{
+///     core::algebra::Vector3,
+///     utils::behavior::blackboard::{Blackboard, BlackboardKey},
+/// };
+///
+/// const TARGET_POSITION: BlackboardKey<Vector3<f32>> = BlackboardKey::new("TargetPosition");
+/// const AMMO: BlackboardKey<i64> = BlackboardKey::new("Ammo");
+///
+/// let mut blackboard = Blackboard::default();
+/// blackboard.set(&TARGET_POSITION, Vector3::new(1.0, 0.0, 2.0));
+/// blackboard.set(&AMMO, 30);
+///
+/// assert_eq!(blackboard.get(&AMMO), Some(30));
+/// ```
+pub struct BlackboardKey<T> {
+    name: &'static str,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for BlackboardKey<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for BlackboardKey<T> {}
+
+impl<T> BlackboardKey<T> {
+    /// Creates new typed key with the given name.
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns name of the key.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+/// Blackboard is a shared key-value storage of a behavior tree. Use typed keys ([`BlackboardKey`])
+/// to access the values in a type-safe manner, or [`Self::value`] and [`Self::set_value`] to work
+/// with untyped values (for example, when the key comes from a data file).
+#[derive(Clone, Debug, Default, PartialEq, Visit)]
+pub struct Blackboard {
+    values: FxHashMap<String, BlackboardValue>,
+}
+
+impl Blackboard {
+    /// Sets a new value for the given key. Previous value (if any) will be replaced.
+    pub fn set<T: BlackboardValueType>(&mut self, key: &BlackboardKey<T>, value: T) {
+        self.values.insert(key.name.to_string(), value.into_value());
+    }
+
+    /// Tries to fetch a value of the given key. Returns [`None`] if there's no such value or it has
+    /// a different type.
+    pub fn get<T: BlackboardValueType>(&self, key: &BlackboardKey<T>) -> Option<T> {
+        self.values.get(key.name).and_then(T::from_value)
+    }
+
+    /// Sets a new untyped value for the given key.
+    pub fn set_value(&mut self, name: impl Into<String>, value: BlackboardValue) {
+        self.values.insert(name.into(), value);
+    }
+
+    /// Returns a reference to an untyped value of the given key.
+    pub fn value(&self, name: &str) -> Option<&BlackboardValue> {
+        self.values.get(name)
+    }
+
+    /// Removes a value of the given key and returns it.
+    pub fn remove(&mut self, name: &str) -> Option<BlackboardValue> {
+        self.values.remove(name)
+    }
+
+    /// Returns `true` if the blackboard has a value with the given key.
+    pub fn contains(&self, name: &str) -> bool {
+        self.values.contains_key(name)
+    }
+
+    /// Returns an iterator over every key-value pair of the blackboard.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &BlackboardValue)> {
+        self.values.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Removes every value from the blackboard.
+    pub fn clear(&mut self) {
+        self.values.clear()
+    }
+}
+
+/// A condition, that could be checked against a blackboard value. See
+/// [`super::decorator::DecoratorKind::Condition`].
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    TypeUuidProvider,
+)]
+#[type_uuid(id = "3f4342ce-6857-46c8-ac99-13ad92203db9")]
+pub enum BlackboardCheck {
+    /// The blackboard must have a value with the given key.
+    #[default]
+    IsSet,
+    /// The blackboard must not have a value with the given key.
+    IsNotSet,
+    /// The blackboard must have a value equal to the given one.
+    Equals(BlackboardValue),
+}
+
+impl BlackboardCheck {
+    /// Checks the condition against a value of the given key.
+    pub fn check(&self, blackboard: &Blackboard, key: &str) -> bool {
+        match self {
+            BlackboardCheck::IsSet => blackboard.contains(key),
+            BlackboardCheck::IsNotSet => !blackboard.contains(key),
+            BlackboardCheck::Equals(expected) => blackboard.value(key) == Some(expected),
+        }
+    }
+}
@@ -24,11 +24,17 @@
 //! implement AND logical function. `Selector` node will execute children until `Status::Success`
 //! is returned from any descendant node. In other worlds `Selector` implement OR logical
 //! function.
+//!
+//! Both kinds are reactive - they tick their children from the first one on every tick, so a
+//! preceding child could interrupt a running one. Memory variants (`MemorySequence` and
+//! `MemorySelector`) remember a child, that returned `Status::Running`, and continue execution
+//! from it on the next tick, instead of re-running already finished children.
 
 use crate::{
     core::{pool::Handle, visitor::prelude::*},
     utils::behavior::{BehaviorNode, BehaviorTree},
 };
+use std::cell::Cell;
 
 /// Defines exact behavior of the composite node.
 #[derive(Debug, PartialEq, Visit, Eq, Clone)]
@@ -41,6 +47,12 @@ pub enum CompositeNodeKind {
     /// is returned from any descendant node. In other worlds `Selector` implement OR logical
     /// function.
     Selector,
+    /// Same as `Sequence`, but remembers a child, that returned `Status::Running`, and continues
+    /// execution from it on the next tick, without re-running preceding children.
+    MemorySequence,
+    /// Same as `Selector`, but remembers a child, that returned `Status::Running`, and continues
+    /// execution from it on the next tick, without re-running preceding children.
+    MemorySelector,
 }
 
 impl Default for CompositeNodeKind {
@@ -49,6 +61,13 @@ impl Default for CompositeNodeKind {
     }
 }
 
+impl CompositeNodeKind {
+    /// Returns `true` if the composite node continues execution from its running child.
+    pub fn has_memory(&self) -> bool {
+        matches!(self, Self::MemorySequence | Self::MemorySelector)
+    }
+}
+
 /// See module docs.
 #[derive(Debug, PartialEq, Visit, Eq, Clone)]
 pub struct CompositeNode<B>
@@ -59,6 +78,10 @@ where
     pub children: Vec<Handle<BehaviorNode<B>>>,
     /// Current kind of the node.
     pub kind: CompositeNodeKind,
+    /// Index of a child, from which the execution will be continued on the next tick. Used only by
+    /// memory variants.
+    #[visit(optional)]
+    pub(super) running_child: Cell<u32>,
 }
 
 impl<B> Default for CompositeNode<B>
@@ -69,6 +92,7 @@ where
         Self {
             children: Default::default(),
             kind: Default::default(),
+            running_child: Default::default(),
         }
     }
 }
@@ -79,7 +103,11 @@ where
 {
     /// Creates new composite node of given kind and set of children nodes.
     pub fn new(kind: CompositeNodeKind, children: Vec<Handle<BehaviorNode<B>>>) -> Self {
-        Self { children, kind }
+        Self {
+            children,
+            kind,
+            running_child: Default::default(),
+        }
     }
 
     /// Creates new sequence composite node with a set of children nodes.
@@ -87,6 +115,7 @@ where
         Self {
             children,
             kind: CompositeNodeKind::Sequence,
+            running_child: Default::default(),
         }
     }
 
@@ -95,9 +124,20 @@ where
         Self {
             children,
             kind: CompositeNodeKind::Selector,
+            running_child: Default::default(),
         }
     }
 
+    /// Creates new memory sequence composite node with a set of children nodes.
+    pub fn new_memory_sequence(children: Vec<Handle<BehaviorNode<B>>>) -> Self {
+        Self::new(CompositeNodeKind::MemorySequence, children)
+    }
+
+    /// Creates new memory selector composite node with a set of children nodes.
+    pub fn new_memory_selector(children: Vec<Handle<BehaviorNode<B>>>) -> Self {
+        Self::new(CompositeNodeKind::MemorySelector, children)
+    }
+
     /// Adds self to the tree and return handle to self.
     pub fn add_to(self, tree: &mut BehaviorTree<B>) -> Handle<BehaviorNode<B>> {
         tree.add_node(BehaviorNode::Composite(self))
@@ -0,0 +1,147 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Decorator is a node with a single child, that modifies execution or the result of its child.
+//! See [`DecoratorKind`] docs for the list of available decorators.
+
+use crate::{
+    core::{pool::Handle, reflect::prelude::*, type_traits::prelude::*, visitor::prelude::*},
+    utils::behavior::{blackboard::BlackboardCheck, BehaviorNode, BehaviorTree},
+};
+use std::cell::Cell;
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// Defines exact behavior of the decorator node.
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    TypeUuidProvider,
+)]
+#[type_uuid(id = "56f0fc0d-5a12-4ecf-9a55-c83bf54576a9")]
+pub enum DecoratorKind {
+    /// Runs the child again and again until it succeeds `count` times in a row, then succeeds.
+    /// Fails as soon as the child fails. Zero `count` means infinite repetition. The child is
+    /// restarted on the next tick of the tree, not within the same tick.
+    Repeat {
+        /// Amount of successful runs of the child.
+        count: u32,
+    },
+    /// Runs the child again until it succeeds, but no more than `attempts` times. Fails if the
+    /// child has failed on every attempt.
+    Retry {
+        /// Max amount of attempts.
+        #[reflect(min_value = 1.0)]
+        attempts: u32,
+    },
+    /// Fails if the child runs longer than `duration` seconds. The time is measured using
+    /// [`BehaviorTree::update`].
+    Timeout {
+        /// Max duration (in seconds) of the child execution.
+        #[reflect(min_value = 0.0)]
+        duration: f32,
+    },
+    /// Fails without ticking the child, if less than `duration` seconds passed since the child
+    /// has finished the last time. The time is measured using [`BehaviorTree::update`].
+    Cooldown {
+        /// Duration (in seconds) of the cooldown.
+        #[reflect(min_value = 0.0)]
+        duration: f32,
+    },
+    /// Succeeds when the child finishes, no matter of its result.
+    #[default]
+    ForceSuccess,
+    /// Fails when the child finishes, no matter of its result.
+    ForceFailure,
+    /// Ticks the child only if a blackboard value of the given key passes the check. Fails
+    /// otherwise, aborting the child if it was running.
+    Condition {
+        /// A key of the blackboard value.
+        key: String,
+        /// A check that will be performed.
+        check: BlackboardCheck,
+    },
+}
+
+/// See module docs.
+#[derive(Debug, PartialEq, Visit, Clone)]
+pub struct DecoratorNode<B>
+where
+    B: Clone,
+{
+    /// A handle of child node.
+    pub child: Handle<BehaviorNode<B>>,
+    /// Current kind of the node.
+    pub kind: DecoratorKind,
+    /// Amount of successful runs or failed attempts of the child, depending on the kind.
+    #[visit(optional)]
+    pub(super) counter: Cell<u32>,
+    /// Start time of the child (timeout) or end time of the last run of the child (cooldown).
+    #[visit(optional)]
+    pub(super) timestamp: Cell<Option<f32>>,
+}
+
+impl<B> Default for DecoratorNode<B>
+where
+    B: Clone,
+{
+    fn default() -> Self {
+        Self {
+            child: Default::default(),
+            kind: Default::default(),
+            counter: Default::default(),
+            timestamp: Default::default(),
+        }
+    }
+}
+
+impl<B> DecoratorNode<B>
+where
+    B: Clone + 'static,
+{
+    /// Creates new decorator node of given kind with the given child.
+    pub fn new(kind: DecoratorKind, child: Handle<BehaviorNode<B>>) -> Self {
+        Self {
+            child,
+            kind,
+            counter: Default::default(),
+            timestamp: Default::default(),
+        }
+    }
+
+    /// Adds self to given behavior tree and returns handle to self.
+    pub fn add_to(self, tree: &mut BehaviorTree<B>) -> Handle<BehaviorNode<B>> {
+        tree.add_node(BehaviorNode::Decorator(self))
+    }
+
+    pub(super) fn reset(&self) {
+        self.counter.set(0);
+        // Cooldown must survive resets, otherwise it could be bypassed by aborting the parent.
+        if !matches!(self.kind, DecoratorKind::Cooldown { .. }) {
+            self.timestamp.set(None);
+        }
+    }
+}
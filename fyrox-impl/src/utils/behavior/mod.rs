@@ -26,8 +26,14 @@
 //! games. The main concept is in its name. Tree is a set of connected nodes, where each node could
 //! have single parent and zero or more children nodes. Execution path of the tree is defined by the
 //! actions of the nodes. Behavior tree has a set of hard coded nodes as well as leaf nodes with
-//! user-defined logic. Hard coded nodes are: Sequence, Selector, Leaf. Leaf is special - it has
-//! custom method `tick` that can contain any logic you want.
+//! user-defined logic. Hard coded nodes are: Sequence, Selector, Parallel, Inverter, decorators
+//! (Repeat, Retry, Timeout, Cooldown, etc.) and Leaf. Leaf is special - it has custom method `tick`
+//! that can contain any logic you want.
+//!
+//! Nodes of the tree could exchange data using [`Blackboard`] - a shared key-value storage, that is
+//! owned by the tree. Memory variants of Sequence and Selector continue their execution from the
+//! child, that returned [`Status::Running`], on the next tick. Trees could also be authored in the editor and stored as a
+//! resource, see [`resource`] module docs for more info.
 //!
 //! For more info see:
 //! - [Wikipedia article](https://en.wikipedia.org/wiki/Behavior_tree_(artificial_intelligence,_robotics_and_control))
@@ -39,21 +45,31 @@ use crate::{
         visitor::prelude::*,
     },
     utils::behavior::{
+        blackboard::Blackboard,
         composite::{CompositeNode, CompositeNodeKind},
+        decorator::{DecoratorKind, DecoratorNode},
         inverter::Inverter,
         leaf::LeafNode,
+        parallel::{ParallelChildState, ParallelNode, ParallelPolicy},
     },
 };
+use fxhash::FxHashMap;
 use std::{
+    cell::{Cell, Ref, RefCell},
     fmt::Debug,
     ops::{Index, IndexMut},
 };
 
+pub mod blackboard;
 pub mod composite;
+pub mod decorator;
 pub mod inverter;
 pub mod leaf;
+pub mod parallel;
+pub mod resource;
 
 /// Status of execution of behavior tree node.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Status {
     /// Action was successful.
     Success,
@@ -72,6 +88,16 @@ pub trait Behavior<'a>: Visit + Default + PartialEq + Debug + Clone {
     /// the current execution path of the behavior tree it belongs
     /// to.
     fn tick(&mut self, context: &mut Self::Context) -> Status;
+
+    /// Same as [`Self::tick`], but also provides access to the blackboard of the tree. This method
+    /// is called by the tree, default implementation ignores the blackboard and calls [`Self::tick`].
+    fn tick_with_blackboard(
+        &mut self,
+        context: &mut Self::Context,
+        _blackboard: &mut Blackboard,
+    ) -> Status {
+        self.tick(context)
+    }
 }
 
 /// Root node of the tree.
@@ -95,7 +121,7 @@ where
 }
 
 /// Possible variations of behavior nodes.
-#[derive(Debug, PartialEq, Visit, Eq, Clone)]
+#[derive(Debug, PartialEq, Visit, Clone)]
 pub enum BehaviorNode<B>
 where
     B: Clone,
@@ -111,8 +137,15 @@ where
     /// A node, that inverts its child state ([`Status::Failure`] becomes [`Status::Success`] and vice versa, [`Status::Running`] remains
     /// unchanged)
     Inverter(Inverter<B>),
+    /// A node, that ticks all its children at once, see [`ParallelNode`] docs for more info.
+    Parallel(ParallelNode<B>),
+    /// A node, that modifies execution of its child, see [`DecoratorKind`] docs for more info.
+    Decorator(DecoratorNode<B>),
 }
 
+// Decorator parameters are floats, that are never NaN in practice, so the equality is total.
+impl<B> Eq for BehaviorNode<B> where B: Clone + Eq {}
+
 impl<B> Default for BehaviorNode<B>
 where
     B: Clone,
@@ -130,6 +163,12 @@ where
 {
     nodes: Pool<BehaviorNode<B>>,
     root: Handle<BehaviorNode<B>>,
+    #[visit(optional)]
+    blackboard: RefCell<Blackboard>,
+    #[visit(optional)]
+    time: Cell<f32>,
+    #[visit(skip)]
+    statuses: RefCell<FxHashMap<Handle<BehaviorNode<B>>, Status>>,
 }
 
 impl<B> Default for BehaviorTree<B>
@@ -140,6 +179,9 @@ where
         Self {
             nodes: Default::default(),
             root: Default::default(),
+            blackboard: Default::default(),
+            time: Default::default(),
+            statuses: Default::default(),
         }
     }
 }
@@ -154,7 +196,11 @@ where
         let root = nodes.spawn(BehaviorNode::Root(RootNode {
             child: Default::default(),
         }));
-        Self { nodes, root }
+        Self {
+            nodes,
+            root,
+            ..Default::default()
+        }
     }
 
     /// Adds a node to the tree, returns its handle.
@@ -171,55 +217,106 @@ where
         }
     }
 
-    fn tick_recursive<'a, Ctx>(&self, handle: Handle<BehaviorNode<B>>, context: &mut Ctx) -> Status
+    fn tick_recursive<'a, Ctx>(
+        &self,
+        handle: Handle<BehaviorNode<B>>,
+        context: &mut Ctx,
+        blackboard: &mut Blackboard,
+    ) -> Status
+    where
+        B: Behavior<'a, Context = Ctx>,
+    {
+        let status = self.tick_node(handle, context, blackboard);
+        self.statuses.borrow_mut().insert(handle, status);
+        status
+    }
+
+    fn tick_node<'a, Ctx>(
+        &self,
+        handle: Handle<BehaviorNode<B>>,
+        context: &mut Ctx,
+        blackboard: &mut Blackboard,
+    ) -> Status
     where
         B: Behavior<'a, Context = Ctx>,
     {
         match self.nodes[handle] {
             BehaviorNode::Root(ref root) => {
                 if root.child.is_some() {
-                    self.tick_recursive(root.child, context)
+                    self.tick_recursive(root.child, context, blackboard)
                 } else {
                     Status::Success
                 }
             }
-            BehaviorNode::Composite(ref composite) => match composite.kind {
-                CompositeNodeKind::Sequence => {
-                    let mut all_succeeded = true;
-                    for child in composite.children.iter() {
-                        match self.tick_recursive(*child, context) {
-                            Status::Failure => {
-                                all_succeeded = false;
-                                break;
-                            }
-                            Status::Running => {
-                                return Status::Running;
+            BehaviorNode::Composite(ref composite) => {
+                let (interrupting_status, final_status) = match composite.kind {
+                    CompositeNodeKind::Sequence | CompositeNodeKind::MemorySequence => {
+                        (Status::Failure, Status::Success)
+                    }
+                    CompositeNodeKind::Selector | CompositeNodeKind::MemorySelector => {
+                        (Status::Success, Status::Failure)
+                    }
+                };
+                // Reactive composites re-evaluate all their children on every tick.
+                let has_memory = composite.kind.has_memory();
+                let start = if has_memory {
+                    composite.running_child.get() as usize
+                } else {
+                    0
+                };
+                for (index, child) in composite.children.iter().enumerate().skip(start) {
+                    match self.tick_recursive(*child, context, blackboard) {
+                        Status::Running => {
+                            if has_memory {
+                                composite.running_child.set(index as u32);
                             }
-                            _ => (),
+                            return Status::Running;
                         }
+                        status if status == interrupting_status => {
+                            composite.running_child.set(0);
+                            return status;
+                        }
+                        _ => (),
                     }
-                    if all_succeeded {
-                        Status::Success
-                    } else {
-                        Status::Failure
+                }
+                composite.running_child.set(0);
+                final_status
+            }
+            BehaviorNode::Parallel(ref parallel) => {
+                let mut states = parallel.states.borrow_mut();
+                states.resize(parallel.children.len(), ParallelChildState::Pending);
+                for (child, state) in parallel.children.iter().zip(states.iter_mut()) {
+                    if *state == ParallelChildState::Pending {
+                        *state = match self.tick_recursive(*child, context, blackboard) {
+                            Status::Success => ParallelChildState::Succeeded,
+                            Status::Failure => ParallelChildState::Failed,
+                            Status::Running => ParallelChildState::Pending,
+                        };
                     }
                 }
-                CompositeNodeKind::Selector => {
-                    for child in composite.children.iter() {
-                        match self.tick_recursive(*child, context) {
-                            Status::Success => return Status::Success,
-                            Status::Running => return Status::Running,
-                            _ => (),
+                let status = parallel.resolve(&states);
+                if status != Status::Running {
+                    // Abort the children, that are still running.
+                    for (child, state) in parallel.children.iter().zip(states.iter()) {
+                        if *state == ParallelChildState::Pending {
+                            self.reset_recursive(*child);
                         }
                     }
-                    Status::Failure
+                    states.clear();
                 }
-            },
-            BehaviorNode::Leaf(ref leaf) => {
-                leaf.behavior.as_ref().unwrap().borrow_mut().tick(context)
+                status
+            }
+            BehaviorNode::Decorator(ref decorator) => {
+                self.tick_decorator(decorator, context, blackboard)
             }
+            BehaviorNode::Leaf(ref leaf) => leaf
+                .behavior
+                .as_ref()
+                .unwrap()
+                .borrow_mut()
+                .tick_with_blackboard(context, blackboard),
             BehaviorNode::Inverter(ref inverter) => {
-                match self.tick_recursive(inverter.child, context) {
+                match self.tick_recursive(inverter.child, context, blackboard) {
                     Status::Success => Status::Failure,
                     Status::Failure => Status::Success,
                     Status::Running => Status::Running,
@@ -231,6 +328,138 @@ where
         }
     }
 
+    fn tick_decorator<'a, Ctx>(
+        &self,
+        decorator: &DecoratorNode<B>,
+        context: &mut Ctx,
+        blackboard: &mut Blackboard,
+    ) -> Status
+    where
+        B: Behavior<'a, Context = Ctx>,
+    {
+        let time = self.time.get();
+        match decorator.kind {
+            DecoratorKind::Repeat { count } => {
+                match self.tick_recursive(decorator.child, context, blackboard) {
+                    Status::Success => {
+                        let repetitions = decorator.counter.get() + 1;
+                        if count != 0 && repetitions >= count {
+                            decorator.counter.set(0);
+                            Status::Success
+                        } else {
+                            decorator.counter.set(repetitions);
+                            Status::Running
+                        }
+                    }
+                    Status::Failure => {
+                        decorator.counter.set(0);
+                        Status::Failure
+                    }
+                    Status::Running => Status::Running,
+                }
+            }
+            DecoratorKind::Retry { attempts } => {
+                match self.tick_recursive(decorator.child, context, blackboard) {
+                    Status::Success => {
+                        decorator.counter.set(0);
+                        Status::Success
+                    }
+                    Status::Failure => {
+                        let failed_attempts = decorator.counter.get() + 1;
+                        if failed_attempts >= attempts {
+                            decorator.counter.set(0);
+                            Status::Failure
+                        } else {
+                            decorator.counter.set(failed_attempts);
+                            Status::Running
+                        }
+                    }
+                    Status::Running => Status::Running,
+                }
+            }
+            DecoratorKind::Timeout { duration } => {
+                let start_time = decorator.timestamp.get().unwrap_or(time);
+                match self.tick_recursive(decorator.child, context, blackboard) {
+                    Status::Running => {
+                        if time - start_time >= duration {
+                            self.reset_recursive(decorator.child);
+                            decorator.timestamp.set(None);
+                            Status::Failure
+                        } else {
+                            decorator.timestamp.set(Some(start_time));
+                            Status::Running
+                        }
+                    }
+                    status => {
+                        decorator.timestamp.set(None);
+                        status
+                    }
+                }
+            }
+            DecoratorKind::Cooldown { duration } => {
+                if decorator
+                    .timestamp
+                    .get()
+                    .is_some_and(|end_time| time - end_time < duration)
+                {
+                    return Status::Failure;
+                }
+                let status = self.tick_recursive(decorator.child, context, blackboard);
+                if status != Status::Running {
+                    decorator.timestamp.set(Some(time));
+                }
+                status
+            }
+            DecoratorKind::ForceSuccess => {
+                match self.tick_recursive(decorator.child, context, blackboard) {
+                    Status::Running => Status::Running,
+                    _ => Status::Success,
+                }
+            }
+            DecoratorKind::ForceFailure => {
+                match self.tick_recursive(decorator.child, context, blackboard) {
+                    Status::Running => Status::Running,
+                    _ => Status::Failure,
+                }
+            }
+            DecoratorKind::Condition { ref key, ref check } => {
+                if check.check(blackboard, key) {
+                    self.tick_recursive(decorator.child, context, blackboard)
+                } else {
+                    self.reset_recursive(decorator.child);
+                    Status::Failure
+                }
+            }
+        }
+    }
+
+    fn reset_recursive(&self, handle: Handle<BehaviorNode<B>>) {
+        let Some(node) = self.nodes.try_borrow(handle) else {
+            return;
+        };
+        match node {
+            BehaviorNode::Root(root) => self.reset_recursive(root.child),
+            BehaviorNode::Composite(composite) => {
+                composite.running_child.set(0);
+                for child in composite.children.iter() {
+                    self.reset_recursive(*child);
+                }
+            }
+            BehaviorNode::Parallel(parallel) => {
+                parallel.states.borrow_mut().clear();
+                for child in parallel.children.iter() {
+                    self.reset_recursive(*child);
+                }
+            }
+            BehaviorNode::Decorator(decorator) => {
+                decorator.reset();
+                self.reset_recursive(decorator.child);
+            }
+            BehaviorNode::Inverter(inverter) => self.reset_recursive(inverter.child),
+            BehaviorNode::Leaf(_) | BehaviorNode::Unknown => (),
+        }
+    }
+
     /// Tries to get a shared reference to a node by given handle.
     pub fn node(&self, handle: Handle<BehaviorNode<B>>) -> Option<&BehaviorNode<B>> {
         self.nodes.try_borrow(handle)
@@ -241,12 +470,65 @@ where
         self.nodes.try_borrow_mut(handle)
     }
 
-    /// Performs a single update tick with given context.
+    /// Returns a handle of the root node of the tree.
+    pub fn root(&self) -> Handle<BehaviorNode<B>> {
+        self.root
+    }
+
+    /// Performs a single update tick with given context. This method does not advance the internal
+    /// time of the tree, use [`Self::update`] if the tree has time-based decorators.
     pub fn tick<'a, Ctx>(&self, context: &mut Ctx) -> Status
     where
         B: Behavior<'a, Context = Ctx>,
     {
-        self.tick_recursive(self.root, context)
+        self.statuses.borrow_mut().clear();
+        let mut blackboard = self.blackboard.borrow_mut();
+        self.tick_recursive(self.root, context, &mut blackboard)
+    }
+
+    /// Advances the internal time of the tree by the given amount of seconds and performs a single
+    /// update tick with given context.
+    pub fn update<'a, Ctx>(&self, dt: f32, context: &mut Ctx) -> Status
+    where
+        B: Behavior<'a, Context = Ctx>,
+    {
+        self.time.set(self.time.get() + dt);
+        self.tick(context)
+    }
+
+    /// Returns internal time of the tree (in seconds), that is advanced by [`Self::update`].
+    pub fn time(&self) -> f32 {
+        self.time.get()
+    }
+
+    /// Returns a reference to the blackboard of the tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called during a tick of the tree, use [`Behavior::tick_with_blackboard`] to access
+    /// the blackboard from actions.
+    pub fn blackboard(&self) -> Ref<'_, Blackboard> {
+        self.blackboard.borrow()
+    }
+
+    /// Returns a reference to the blackboard of the tree.
+    pub fn blackboard_mut(&mut self) -> &mut Blackboard {
+        self.blackboard.get_mut()
+    }
+
+    /// Returns a status of the node with the given handle after the last tick. Returns [`None`]
+    /// if the node wasn't ticked during the last tick (it is not on the current execution path).
+    /// This method could be used to build in-game debugging tools, for example to display the
+    /// current execution path of the tree.
+    pub fn node_status(&self, handle: Handle<BehaviorNode<B>>) -> Option<Status> {
+        self.statuses.borrow().get(&handle).cloned()
+    }
+
+    /// Resets the execution state of every node of the tree, so the next tick will start from the
+    /// beginning. Cooldowns are preserved. Values of the blackboard are preserved as well.
+    pub fn reset(&self) {
+        self.statuses.borrow_mut().clear();
+        self.reset_recursive(self.root);
     }
 }
 
@@ -286,6 +568,28 @@ where
     CompositeNode::new_selector(children.to_vec()).add_to(tree)
 }
 
+/// Creates a new memory sequence.
+pub fn memory_sequence<B, const N: usize>(
+    children: [Handle<BehaviorNode<B>>; N],
+    tree: &mut BehaviorTree<B>,
+) -> Handle<BehaviorNode<B>>
+where
+    B: Clone + 'static,
+{
+    CompositeNode::new_memory_sequence(children.to_vec()).add_to(tree)
+}
+
+/// Creates a new memory selector.
+pub fn memory_selector<B, const N: usize>(
+    children: [Handle<BehaviorNode<B>>; N],
+    tree: &mut BehaviorTree<B>,
+) -> Handle<BehaviorNode<B>>
+where
+    B: Clone + 'static,
+{
+    CompositeNode::new_memory_selector(children.to_vec()).add_to(tree)
+}
+
 /// Creates a new leaf.
 pub fn leaf<B>(behavior: B, tree: &mut BehaviorTree<B>) -> Handle<BehaviorNode<B>>
 where
@@ -294,6 +598,31 @@ where
     LeafNode::new(behavior).add_to(tree)
 }
 
+/// Creates a new parallel node.
+pub fn parallel<B, const N: usize>(
+    success_policy: ParallelPolicy,
+    failure_policy: ParallelPolicy,
+    children: [Handle<BehaviorNode<B>>; N],
+    tree: &mut BehaviorTree<B>,
+) -> Handle<BehaviorNode<B>>
+where
+    B: Clone + 'static,
+{
+    ParallelNode::new(success_policy, failure_policy, children.to_vec()).add_to(tree)
+}
+
+/// Creates a new decorator of the given kind.
+pub fn decorator<B>(
+    kind: DecoratorKind,
+    child: Handle<BehaviorNode<B>>,
+    tree: &mut BehaviorTree<B>,
+) -> Handle<BehaviorNode<B>>
+where
+    B: Clone + 'static,
+{
+    DecoratorNode::new(kind, child).add_to(tree)
+}
+
 /// Creates a new inverter.
 pub fn inverter<B>(
     child: Handle<BehaviorNode<B>>,
@@ -310,9 +639,19 @@ mod test {
     use crate::{
         core::{futures::executor::block_on, visitor::prelude::*},
         utils::behavior::{
+            blackboard::{Blackboard, BlackboardCheck, BlackboardKey, BlackboardValue},
             composite::{CompositeNode, CompositeNodeKind},
+            decorator,
+            decorator::DecoratorKind,
+            leaf,
             leaf::LeafNode,
-            Behavior, BehaviorTree, Status,
+            memory_sequence, parallel,
+            parallel::ParallelPolicy,
+            resource::{
+                BehaviorActionRegistry, BehaviorNodeDefinition, BehaviorNodeKind,
+                BehaviorTreeDefinition, BehaviorTreeError,
+            },
+            sequence, Behavior, BehaviorTree, Status,
         },
     };
     use std::{env, fs::File, io::Write, path::PathBuf};
@@ -489,4 +828,284 @@ mod test {
 
         assert_eq!(saved_tree, loaded_tree);
     }
+
+    const FLAG: BlackboardKey<bool> = BlackboardKey::new("Flag");
+
+    #[derive(Debug, PartialEq, Default, Visit, Clone)]
+    enum TestAction {
+        #[default]
+        Succeed,
+        Fail,
+        Run {
+            ticks: u32,
+        },
+        SetFlag,
+    }
+
+    #[derive(Default)]
+    struct Counter {
+        ticks: u32,
+    }
+
+    impl<'a> Behavior<'a> for TestAction {
+        type Context = Counter;
+
+        fn tick(&mut self, context: &mut Self::Context) -> Status {
+            context.ticks += 1;
+            match self {
+                TestAction::Succeed | TestAction::SetFlag => Status::Success,
+                TestAction::Fail => Status::Failure,
+                TestAction::Run { ticks } => {
+                    if *ticks == 0 {
+                        Status::Success
+                    } else {
+                        *ticks -= 1;
+                        Status::Running
+                    }
+                }
+            }
+        }
+
+        fn tick_with_blackboard(
+            &mut self,
+            context: &mut Self::Context,
+            blackboard: &mut Blackboard,
+        ) -> Status {
+            if let TestAction::SetFlag = self {
+                blackboard.set(&FLAG, true);
+            }
+            self.tick(context)
+        }
+    }
+
+    fn tick_n(tree: &BehaviorTree<TestAction>, dt: f32, n: usize) -> Vec<Status> {
+        let mut ctx = Counter::default();
+        (0..n).map(|_| tree.update(dt, &mut ctx)).collect()
+    }
+
+    #[test]
+    fn test_reactive_sequence() {
+        let mut tree = BehaviorTree::new();
+        let succeed = leaf(TestAction::Succeed, &mut tree);
+        let run = leaf(TestAction::Run { ticks: 1 }, &mut tree);
+        let entry = sequence([succeed, run], &mut tree);
+        tree.set_entry_node(entry);
+
+        let mut ctx = Counter::default();
+        assert_eq!(tree.tick(&mut ctx), Status::Running);
+        assert_eq!(tree.tick(&mut ctx), Status::Success);
+        // The first child is ticked again while the second one is running.
+        assert_eq!(tree.node_status(succeed), Some(Status::Success));
+        assert_eq!(ctx.ticks, 4);
+    }
+
+    #[test]
+    fn test_running_state_persistence() {
+        let mut tree = BehaviorTree::new();
+        let succeed = leaf(TestAction::Succeed, &mut tree);
+        let run = leaf(TestAction::Run { ticks: 2 }, &mut tree);
+        let entry = memory_sequence([succeed, run], &mut tree);
+        tree.set_entry_node(entry);
+
+        let mut ctx = Counter::default();
+        assert_eq!(tree.tick(&mut ctx), Status::Running);
+        assert_eq!(tree.node_status(succeed), Some(Status::Success));
+        assert_eq!(tree.tick(&mut ctx), Status::Running);
+        // The first child must not be ticked again while the second one is running.
+        assert_eq!(tree.node_status(succeed), None);
+        assert_eq!(tree.node_status(run), Some(Status::Running));
+        assert_eq!(tree.tick(&mut ctx), Status::Success);
+        assert_eq!(ctx.ticks, 4);
+
+        tree.tick(&mut ctx);
+        tree.reset();
+        assert_eq!(tree.node_status(run), None);
+        tree.tick(&mut ctx);
+        assert_eq!(tree.node_status(succeed), Some(Status::Success));
+    }
+
+    #[test]
+    fn test_parallel() {
+        let mut tree = BehaviorTree::new();
+        let run = leaf(TestAction::Run { ticks: 1 }, &mut tree);
+        let succeed = leaf(TestAction::Succeed, &mut tree);
+        let entry = parallel(
+            ParallelPolicy::RequireAll,
+            ParallelPolicy::RequireOne,
+            [run, succeed],
+            &mut tree,
+        );
+        tree.set_entry_node(entry);
+
+        let mut ctx = Counter::default();
+        assert_eq!(tree.tick(&mut ctx), Status::Running);
+        assert_eq!(tree.tick(&mut ctx), Status::Success);
+        // Finished child must not be ticked again.
+        assert_eq!(ctx.ticks, 3);
+
+        let mut tree = BehaviorTree::new();
+        let fail = leaf(TestAction::Fail, &mut tree);
+        let run = leaf(TestAction::Run { ticks: 5 }, &mut tree);
+        let entry = parallel(
+            ParallelPolicy::RequireOne,
+            ParallelPolicy::RequireOne,
+            [fail, run],
+            &mut tree,
+        );
+        tree.set_entry_node(entry);
+        assert_eq!(tick_n(&tree, 0.0, 1), [Status::Failure]);
+    }
+
+    fn decorated(kind: DecoratorKind, action: TestAction) -> BehaviorTree<TestAction> {
+        let mut tree = BehaviorTree::new();
+        let child = leaf(action, &mut tree);
+        let entry = decorator(kind, child, &mut tree);
+        tree.set_entry_node(entry);
+        tree
+    }
+
+    #[test]
+    fn test_decorators() {
+        let tree = decorated(DecoratorKind::Repeat { count: 3 }, TestAction::Succeed);
+        assert_eq!(
+            tick_n(&tree, 0.0, 4),
+            [
+                Status::Running,
+                Status::Running,
+                Status::Success,
+                Status::Running
+            ]
+        );
+
+        let tree = decorated(DecoratorKind::Retry { attempts: 2 }, TestAction::Fail);
+        assert_eq!(
+            tick_n(&tree, 0.0, 3),
+            [Status::Running, Status::Failure, Status::Running]
+        );
+
+        let tree = decorated(
+            DecoratorKind::Timeout { duration: 1.0 },
+            TestAction::Run { ticks: 100 },
+        );
+        assert_eq!(
+            tick_n(&tree, 0.4, 4),
+            [
+                Status::Running,
+                Status::Running,
+                Status::Running,
+                Status::Failure
+            ]
+        );
+
+        let tree = decorated(
+            DecoratorKind::Cooldown { duration: 1.0 },
+            TestAction::Succeed,
+        );
+        assert_eq!(
+            tick_n(&tree, 0.4, 4),
+            [
+                Status::Success,
+                Status::Failure,
+                Status::Failure,
+                Status::Success
+            ]
+        );
+
+        let tree = decorated(DecoratorKind::ForceSuccess, TestAction::Fail);
+        assert_eq!(tick_n(&tree, 0.0, 1), [Status::Success]);
+
+        let tree = decorated(DecoratorKind::ForceFailure, TestAction::Succeed);
+        assert_eq!(tick_n(&tree, 0.0, 1), [Status::Failure]);
+    }
+
+    #[test]
+    fn test_blackboard() {
+        let mut tree = BehaviorTree::new();
+        let set_flag = leaf(TestAction::SetFlag, &mut tree);
+        let succeed = leaf(TestAction::Succeed, &mut tree);
+        let condition = decorator(
+            DecoratorKind::Condition {
+                key: FLAG.name().to_string(),
+                check: BlackboardCheck::Equals(BlackboardValue::Bool(true)),
+            },
+            succeed,
+            &mut tree,
+        );
+        let entry = sequence([set_flag, condition], &mut tree);
+        tree.set_entry_node(entry);
+
+        assert_eq!(tick_n(&tree, 0.0, 1), [Status::Success]);
+        assert_eq!(tree.blackboard().get(&FLAG), Some(true));
+        // Typed access must not return values of a different type.
+        assert_eq!(
+            tree.blackboard()
+                .get(&BlackboardKey::<i64>::new(FLAG.name())),
+            None
+        );
+
+        let mut tree = BehaviorTree::new();
+        let succeed = leaf(TestAction::Succeed, &mut tree);
+        let entry = decorator(
+            DecoratorKind::Condition {
+                key: FLAG.name().to_string(),
+                check: BlackboardCheck::IsSet,
+            },
+            succeed,
+            &mut tree,
+        );
+        tree.set_entry_node(entry);
+        assert_eq!(tick_n(&tree, 0.0, 1), [Status::Failure]);
+        tree.blackboard_mut().set(&FLAG, false);
+        assert_eq!(tick_n(&tree, 0.0, 1), [Status::Success]);
+    }
+
+    #[test]
+    fn test_definition_instantiation() {
+        let mut definition = BehaviorTreeDefinition::default();
+        let sequence = definition.add_node(BehaviorNodeDefinition::new(
+            "Sequence",
+            BehaviorNodeKind::MemorySequence,
+        ));
+        let succeed = definition.add_node(BehaviorNodeDefinition::new(
+            "Succeed",
+            BehaviorNodeKind::Action {
+                name: "Succeed".to_string(),
+            },
+        ));
+        let run = definition.add_node(BehaviorNodeDefinition::new(
+            "Run",
+            BehaviorNodeKind::Action {
+                name: "Run".to_string(),
+            },
+        ));
+        definition.link(definition.root, sequence);
+        definition.link(sequence, succeed);
+        definition.link(sequence, run);
+        assert_eq!(definition.parent_of(run), sequence);
+
+        let mut registry = BehaviorActionRegistry::default();
+        registry.register("Succeed", || TestAction::Succeed);
+        assert_eq!(
+            definition.instantiate(&registry).unwrap_err(),
+            BehaviorTreeError::UnknownAction("Run".to_string())
+        );
+
+        registry.register("Run", || TestAction::Run { ticks: 1 });
+        let tree = definition.instantiate(&registry).unwrap();
+        assert_eq!(tick_n(&tree, 0.0, 2), [Status::Running, Status::Success]);
+        assert_eq!(tree.node_status(run.transmute()), Some(Status::Success));
+        assert_eq!(tree.node_status(succeed.transmute()), None);
+
+        let inverter = definition.add_node(BehaviorNodeDefinition::new(
+            "Inverter",
+            BehaviorNodeKind::Inverter,
+        ));
+        definition.link(sequence, inverter);
+        assert_eq!(
+            definition.instantiate(&registry).unwrap_err(),
+            BehaviorTreeError::MissingChild("Inverter".to_string())
+        );
+        definition.remove_node(inverter);
+        assert!(!definition.nodes[sequence].children.contains(&inverter));
+    }
 }
@@ -0,0 +1,142 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Parallel node ticks all its children "simultaneously" (within the same tick of the tree) and
+//! combines their statuses according to its policies. It is useful to run several actions at
+//! once, for example to move towards a target while shooting at it.
+
+use crate::{
+    core::{pool::Handle, reflect::prelude::*, type_traits::prelude::*, visitor::prelude::*},
+    utils::behavior::{BehaviorNode, BehaviorTree, Status},
+};
+use std::cell::RefCell;
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// Defines how many children of a parallel node must finish with a specific status, so the
+/// parallel node finishes with the same status.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Default,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    TypeUuidProvider,
+)]
+#[type_uuid(id = "486de4e2-699e-46a2-87a9-00ad5d1f23dc")]
+pub enum ParallelPolicy {
+    /// At least one child must finish with the status.
+    #[default]
+    RequireOne,
+    /// All children must finish with the status.
+    RequireAll,
+}
+
+/// Execution state of a child of a parallel node.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Visit)]
+pub(super) enum ParallelChildState {
+    /// The child is not finished yet.
+    #[default]
+    Pending,
+    /// The child finished with [`Status::Success`].
+    Succeeded,
+    /// The child finished with [`Status::Failure`].
+    Failed,
+}
+
+/// See module docs.
+#[derive(Debug, PartialEq, Visit, Eq, Clone)]
+pub struct ParallelNode<B>
+where
+    B: Clone,
+{
+    /// A set of children.
+    pub children: Vec<Handle<BehaviorNode<B>>>,
+    /// Defines when the node finishes with [`Status::Success`].
+    pub success_policy: ParallelPolicy,
+    /// Defines when the node finishes with [`Status::Failure`]. Failure policy has priority over
+    /// the success policy.
+    pub failure_policy: ParallelPolicy,
+    #[visit(optional)]
+    pub(super) states: RefCell<Vec<ParallelChildState>>,
+}
+
+impl<B> Default for ParallelNode<B>
+where
+    B: Clone,
+{
+    fn default() -> Self {
+        Self {
+            children: Default::default(),
+            success_policy: ParallelPolicy::RequireAll,
+            failure_policy: ParallelPolicy::RequireOne,
+            states: Default::default(),
+        }
+    }
+}
+
+impl<B> ParallelNode<B>
+where
+    B: Clone + 'static,
+{
+    /// Creates new parallel node with the given policies and a set of children nodes.
+    pub fn new(
+        success_policy: ParallelPolicy,
+        failure_policy: ParallelPolicy,
+        children: Vec<Handle<BehaviorNode<B>>>,
+    ) -> Self {
+        Self {
+            children,
+            success_policy,
+            failure_policy,
+            states: Default::default(),
+        }
+    }
+
+    /// Adds self to the tree and return handle to self.
+    pub fn add_to(self, tree: &mut BehaviorTree<B>) -> Handle<BehaviorNode<B>> {
+        tree.add_node(BehaviorNode::Parallel(self))
+    }
+
+    /// Combines the states of the children into the final status of the node.
+    pub(super) fn resolve(&self, states: &[ParallelChildState]) -> Status {
+        let count = |state| states.iter().filter(|s| **s == state).count();
+        let satisfied = |policy, count| match policy {
+            ParallelPolicy::RequireOne => count > 0,
+            ParallelPolicy::RequireAll => count == states.len(),
+        };
+
+        if satisfied(self.failure_policy, count(ParallelChildState::Failed)) {
+            Status::Failure
+        } else if satisfied(self.success_policy, count(ParallelChildState::Succeeded)) {
+            Status::Success
+        } else if count(ParallelChildState::Pending) == 0 {
+            // Every child is finished, but the success policy can't be satisfied anymore.
+            Status::Failure
+        } else {
+            Status::Running
+        }
+    }
+}
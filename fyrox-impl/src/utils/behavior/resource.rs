@@ -0,0 +1,506 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Behavior tree resource allows you to author behavior trees in the editor and store them in
+//! files. The resource contains a [`BehaviorTreeDefinition`] - a data-only description of a tree,
+//! where leaf nodes are referenced by names of actions. Names are mapped to actual actions using
+//! [`BehaviorActionRegistry`]:
+//!
+//! ```rust
+//! use fyrox_impl::{
+//!     core::visitor::prelude::*,
+//!     utils::behavior::{
+//!         resource::{BehaviorActionRegistry, BehaviorTreeResource},
+//!         Behavior, BehaviorTree, Status,
+//!     },
+//! };
+//!
+//! #[derive(Debug, Default, PartialEq, Clone, Visit)]
+//! enum BotAction {
+//!     #[default]
+//!     Idle,
+//!     Patrol,
+//! }
+//!
+//! struct Bot;
+//!
+//! impl<'a> Behavior<'a> for BotAction {
+//!     type Context = Bot;
+//!
+//!     fn tick(&mut self, _bot: &mut Bot) -> Status {
+//!         Status::Success
+//!     }
+//! }
+//!
+//! fn instantiate(resource: &BehaviorTreeResource) -> Option<BehaviorTree<BotAction>> {
+//!     let mut registry = BehaviorActionRegistry::default();
+//!     registry.register("Idle", || BotAction::Idle);
+//!     registry.register("Patrol", || BotAction::Patrol);
+//!
+//!     resource.data_ref().instantiate(&registry).ok()
+//! }
+//! ```
+//!
+//! Instantiated tree has the same node handles as the definition (handles could be converted
+//! using [`Handle::transmute`]), so statuses of the runtime nodes (see
+//! [`BehaviorTree::node_status`]) could be matched with the nodes of the definition.
+
+use crate::{
+    asset::{
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, LoaderPayload, ResourceLoader},
+        state::LoadError,
+        Resource, ResourceData,
+    },
+    core::{
+        algebra::Vector2,
+        io::FileLoadError,
+        pool::{Handle, Pool},
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        visitor::prelude::*,
+    },
+    utils::behavior::{
+        composite::CompositeNode, decorator::DecoratorKind, decorator::DecoratorNode,
+        inverter::Inverter, leaf::LeafNode, parallel::ParallelNode, parallel::ParallelPolicy,
+        BehaviorNode, BehaviorTree, RootNode,
+    },
+};
+use fxhash::FxHashMap;
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Debug, Display, Formatter},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// An error that may occur during behavior tree resource loading.
+#[derive(Debug)]
+pub enum BehaviorTreeResourceError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+}
+
+impl Display for BehaviorTreeResourceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            Self::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for BehaviorTreeResourceError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for BehaviorTreeResourceError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// An error that may occur during instantiation of a behavior tree definition.
+#[derive(Debug, PartialEq, Eq)]
+pub enum BehaviorTreeError {
+    /// The definition does not have a root node.
+    NoRoot,
+    /// A node with the given name must have a child, but it doesn't.
+    MissingChild(String),
+    /// There's no registered action with the given name.
+    UnknownAction(String),
+}
+
+impl Display for BehaviorTreeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoRoot => write!(f, "Behavior tree definition does not have a root node."),
+            Self::MissingChild(name) => {
+                write!(f, "Behavior tree node {name} must have a child.")
+            }
+            Self::UnknownAction(name) => {
+                write!(f, "There's no registered behavior tree action {name}.")
+            }
+        }
+    }
+}
+
+impl Error for BehaviorTreeError {}
+
+/// Kind of a node of a behavior tree definition.
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    TypeUuidProvider,
+)]
+#[type_uuid(id = "5cc4ab0b-71a7-4489-a382-00775bdad721")]
+pub enum BehaviorNodeKind {
+    /// Root node of the tree. There must be exactly one root node in a definition.
+    Root,
+    /// See [`super::composite::CompositeNodeKind::Sequence`].
+    #[default]
+    Sequence,
+    /// See [`super::composite::CompositeNodeKind::Selector`].
+    Selector,
+    /// See [`ParallelNode`].
+    Parallel {
+        /// See [`ParallelNode::success_policy`].
+        success_policy: ParallelPolicy,
+        /// See [`ParallelNode::failure_policy`].
+        failure_policy: ParallelPolicy,
+    },
+    /// See [`Inverter`].
+    Inverter,
+    /// See [`DecoratorKind`].
+    Decorator(DecoratorKind),
+    /// A leaf node with an action, that is registered in [`BehaviorActionRegistry`] with the given
+    /// name.
+    Action {
+        /// Name of the action.
+        name: String,
+    },
+    /// See [`super::composite::CompositeNodeKind::MemorySequence`].
+    MemorySequence,
+    /// See [`super::composite::CompositeNodeKind::MemorySelector`].
+    MemorySelector,
+}
+
+impl BehaviorNodeKind {
+    /// Returns max amount of children of a node of this kind, [`None`] means unlimited amount.
+    pub fn max_children(&self) -> Option<usize> {
+        match self {
+            Self::Root | Self::Inverter | Self::Decorator(_) => Some(1),
+            Self::Action { .. } => Some(0),
+            Self::Sequence
+            | Self::Selector
+            | Self::MemorySequence
+            | Self::MemorySelector
+            | Self::Parallel { .. } => None,
+        }
+    }
+}
+
+/// A node of a behavior tree definition.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+#[visit(optional)]
+pub struct BehaviorNodeDefinition {
+    /// Name of the node. It is used only for display purposes.
+    pub name: String,
+    /// Kind of the node.
+    pub kind: BehaviorNodeKind,
+    /// Children of the node. The order of children defines the order of their execution.
+    #[reflect(hidden)]
+    pub children: Vec<Handle<BehaviorNodeDefinition>>,
+    /// Position of the node in the editor.
+    #[reflect(hidden)]
+    pub position: Vector2<f32>,
+}
+
+impl BehaviorNodeDefinition {
+    /// Creates new node definition with the given name and kind.
+    pub fn new(name: impl Into<String>, kind: BehaviorNodeKind) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            children: Default::default(),
+            position: Default::default(),
+        }
+    }
+
+    /// Sets the desired position of the node in the editor.
+    pub fn with_position(mut self, position: Vector2<f32>) -> Self {
+        self.position = position;
+        self
+    }
+}
+
+/// Behavior tree definition is a data-only description of a behavior tree. See module docs for
+/// more info.
+#[derive(Clone, Debug, Reflect, Visit, TypeUuidProvider, ComponentProvider)]
+#[type_uuid(id = "cb7f8db7-5fc8-4377-a05a-1fc33f5af70b")]
+pub struct BehaviorTreeDefinition {
+    /// Nodes of the definition.
+    pub nodes: Pool<BehaviorNodeDefinition>,
+    /// A handle of the root node.
+    #[reflect(hidden)]
+    pub root: Handle<BehaviorNodeDefinition>,
+}
+
+impl Default for BehaviorTreeDefinition {
+    fn default() -> Self {
+        let mut nodes = Pool::new();
+        let root = nodes.spawn(BehaviorNodeDefinition::new("Root", BehaviorNodeKind::Root));
+        Self { nodes, root }
+    }
+}
+
+impl BehaviorTreeDefinition {
+    /// Adds a node to the definition and returns its handle.
+    pub fn add_node(&mut self, node: BehaviorNodeDefinition) -> Handle<BehaviorNodeDefinition> {
+        self.nodes.spawn(node)
+    }
+
+    /// Removes a node from the definition. The node is also removed from the children of its
+    /// parent, children of the node will be detached from it.
+    pub fn remove_node(
+        &mut self,
+        handle: Handle<BehaviorNodeDefinition>,
+    ) -> BehaviorNodeDefinition {
+        self.unlink(handle);
+        self.nodes.free(handle)
+    }
+
+    /// Returns a handle of the parent node of the given node.
+    pub fn parent_of(
+        &self,
+        handle: Handle<BehaviorNodeDefinition>,
+    ) -> Handle<BehaviorNodeDefinition> {
+        self.nodes
+            .pair_iter()
+            .find_map(|(parent, node)| node.children.contains(&handle).then_some(parent))
+            .unwrap_or_default()
+    }
+
+    /// Attaches the child node to the parent node. The child will be detached from its previous
+    /// parent first, since every node could have only one parent.
+    pub fn link(
+        &mut self,
+        parent: Handle<BehaviorNodeDefinition>,
+        child: Handle<BehaviorNodeDefinition>,
+    ) {
+        self.unlink(child);
+        self.nodes[parent].children.push(child);
+    }
+
+    /// Detaches the node from its parent.
+    pub fn unlink(&mut self, child: Handle<BehaviorNodeDefinition>) {
+        for node in self.nodes.iter_mut() {
+            node.children.retain(|c| *c != child);
+        }
+    }
+
+    /// Load a behavior tree definition from the specific file path.
+    pub async fn from_file(
+        path: &Path,
+        io: &dyn ResourceIo,
+    ) -> Result<Self, BehaviorTreeResourceError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        let mut definition = BehaviorTreeDefinition::default();
+        definition.visit("BehaviorTree", &mut visitor)?;
+        Ok(definition)
+    }
+
+    /// Creates a new behavior tree using the definition. Leaf nodes of the tree are created using
+    /// the given registry of actions. Handles of the nodes of the new tree match the handles of the
+    /// definition nodes.
+    pub fn instantiate<B>(
+        &self,
+        registry: &BehaviorActionRegistry<B>,
+    ) -> Result<BehaviorTree<B>, BehaviorTreeError>
+    where
+        B: Clone + 'static,
+    {
+        if self
+            .nodes
+            .try_borrow(self.root)
+            .map_or(true, |root| root.kind != BehaviorNodeKind::Root)
+        {
+            return Err(BehaviorTreeError::NoRoot);
+        }
+
+        let mut tree = BehaviorTree {
+            root: self.root.transmute(),
+            ..Default::default()
+        };
+
+        for (handle, definition) in self.nodes.pair_iter() {
+            let children = definition
+                .children
+                .iter()
+                .map(|child| child.transmute())
+                .collect::<Vec<_>>();
+            let first_child = || {
+                children
+                    .first()
+                    .cloned()
+                    .ok_or_else(|| BehaviorTreeError::MissingChild(definition.name.clone()))
+            };
+
+            let node = match definition.kind {
+                BehaviorNodeKind::Root => BehaviorNode::Root(RootNode {
+                    child: children.first().cloned().unwrap_or_default(),
+                }),
+                BehaviorNodeKind::Sequence => {
+                    BehaviorNode::Composite(CompositeNode::new_sequence(children))
+                }
+                BehaviorNodeKind::Selector => {
+                    BehaviorNode::Composite(CompositeNode::new_selector(children))
+                }
+                BehaviorNodeKind::MemorySequence => {
+                    BehaviorNode::Composite(CompositeNode::new_memory_sequence(children))
+                }
+                BehaviorNodeKind::MemorySelector => {
+                    BehaviorNode::Composite(CompositeNode::new_memory_selector(children))
+                }
+                BehaviorNodeKind::Parallel {
+                    success_policy,
+                    failure_policy,
+                } => BehaviorNode::Parallel(ParallelNode::new(
+                    success_policy,
+                    failure_policy,
+                    children,
+                )),
+                BehaviorNodeKind::Inverter => BehaviorNode::Inverter(Inverter::new(first_child()?)),
+                BehaviorNodeKind::Decorator(ref kind) => {
+                    BehaviorNode::Decorator(DecoratorNode::new(kind.clone(), first_child()?))
+                }
+                BehaviorNodeKind::Action { ref name } => BehaviorNode::Leaf(LeafNode::new(
+                    registry
+                        .create(name)
+                        .ok_or_else(|| BehaviorTreeError::UnknownAction(name.clone()))?,
+                )),
+            };
+
+            // Can't fail, because the tree is empty and handles of the definition are unique.
+            let _ = tree.nodes.spawn_at_handle(handle.transmute(), node);
+        }
+
+        Ok(tree)
+    }
+}
+
+impl ResourceData for BehaviorTreeDefinition {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        self.visit("BehaviorTree", &mut visitor)?;
+        visitor.save_binary(path)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+}
+
+/// An alias for `Resource<BehaviorTreeDefinition>`.
+pub type BehaviorTreeResource = Resource<BehaviorTreeDefinition>;
+
+/// Standard behavior tree resource loader.
+pub struct BehaviorTreeLoader;
+
+impl ResourceLoader for BehaviorTreeLoader {
+    fn extensions(&self) -> &[&str] {
+        &["behavior"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        <BehaviorTreeDefinition as TypeUuidProvider>::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let definition = BehaviorTreeDefinition::from_file(&path, io.as_ref())
+                .await
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(definition))
+        })
+    }
+}
+
+type ActionConstructor<B> = Box<dyn Fn() -> B + Send + Sync>;
+
+/// A registry of actions, that maps action names of a [`BehaviorTreeDefinition`] to actual actions.
+pub struct BehaviorActionRegistry<B> {
+    constructors: FxHashMap<String, ActionConstructor<B>>,
+}
+
+impl<B> Default for BehaviorActionRegistry<B> {
+    fn default() -> Self {
+        Self {
+            constructors: Default::default(),
+        }
+    }
+}
+
+impl<B> Debug for BehaviorActionRegistry<B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.constructors.keys()).finish()
+    }
+}
+
+impl<B> BehaviorActionRegistry<B> {
+    /// Registers a new action constructor with the given name. Previous constructor with the same
+    /// name (if any) will be replaced.
+    pub fn register<F>(&mut self, name: impl Into<String>, constructor: F)
+    where
+        F: Fn() -> B + Send + Sync + 'static,
+    {
+        self.constructors.insert(name.into(), Box::new(constructor));
+    }
+
+    /// Creates a new action using a constructor with the given name.
+    pub fn create(&self, name: &str) -> Option<B> {
+        self.constructors.get(name).map(|constructor| constructor())
+    }
+
+    /// Returns `true` if the registry has an action with the given name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.constructors.contains_key(name)
+    }
+
+    /// Returns an iterator over the names of registered actions.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.constructors.keys().map(|name| name.as_str())
+    }
+}
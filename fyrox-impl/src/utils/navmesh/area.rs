@@ -0,0 +1,115 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Area types of navmesh triangles and filters, that define how agents treat the areas. See
+//! [`NavmeshAreaFilter`] docs for more info.
+
+use crate::core::{reflect::prelude::*, visitor::prelude::*};
+
+/// Maximum amount of area types. Area type of a triangle must be less than this value.
+pub const MAX_AREAS: usize = 64;
+
+/// Default area type of navmesh triangles.
+pub const DEFAULT_AREA: u8 = 0;
+
+/// Area filter defines traversal costs of area types and which area types could be traversed at
+/// all. Every navmesh agent has its own filter, so different agents could prefer different paths
+/// on the same navmesh. For example, a soldier could avoid water (high cost), while a boat could
+/// move only on water (every other area is excluded).
+///
+/// Area type is a number in `0..MAX_AREAS` range, its meaning is defined by the game. The cost
+/// multiplies the travel distance, so the cost of `2.0` makes the area twice as "long" for the
+/// pathfinder. Default cost of every area is `1.0`.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_impl::utils::navmesh::area::NavmeshAreaFilter;
+/// const GROUND: u8 = 0;
+/// const WATER: u8 = 1;
+/// const LAVA: u8 = 2;
+///
+/// fn make_filter() -> NavmeshAreaFilter {
+///     let mut filter = NavmeshAreaFilter::default();
+///     filter.set_area_cost(WATER, 5.0);
+///     filter.set_area_enabled(LAVA, false);
+///     filter
+/// }
+/// # assert!(make_filter().is_area_enabled(GROUND));
+/// ```
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+#[visit(optional)]
+pub struct NavmeshAreaFilter {
+    costs: Vec<f32>,
+    excluded_areas: u64,
+}
+
+impl Default for NavmeshAreaFilter {
+    fn default() -> Self {
+        Self {
+            costs: vec![1.0; MAX_AREAS],
+            excluded_areas: 0,
+        }
+    }
+}
+
+impl NavmeshAreaFilter {
+    /// Sets traversal cost of the given area. The cost must be positive, zero or negative values
+    /// will be clamped to a small positive value.
+    pub fn set_area_cost(&mut self, area: u8, cost: f32) {
+        let area = area as usize;
+        if area < MAX_AREAS {
+            if self.costs.len() < MAX_AREAS {
+                self.costs.resize(MAX_AREAS, 1.0);
+            }
+            self.costs[area] = cost.max(f32::EPSILON);
+        }
+    }
+
+    /// Returns traversal cost of the given area.
+    pub fn area_cost(&self, area: u8) -> f32 {
+        self.costs.get(area as usize).cloned().unwrap_or(1.0)
+    }
+
+    /// Enables or disables the given area. Disabled areas cannot be traversed by agents.
+    pub fn set_area_enabled(&mut self, area: u8, enabled: bool) {
+        if (area as usize) < MAX_AREAS {
+            if enabled {
+                self.excluded_areas &= !(1 << area);
+            } else {
+                self.excluded_areas |= 1 << area;
+            }
+        }
+    }
+
+    /// Returns `true` if the given area could be traversed, `false` - otherwise.
+    pub fn is_area_enabled(&self, area: u8) -> bool {
+        (area as usize) < MAX_AREAS && self.excluded_areas & (1 << area) == 0
+    }
+
+    /// Returns traversal cost of the given area or `None` if the area is disabled.
+    pub fn cost(&self, area: u8) -> Option<f32> {
+        if self.is_area_enabled(area) {
+            Some(self.area_cost(area))
+        } else {
+            None
+        }
+    }
+}
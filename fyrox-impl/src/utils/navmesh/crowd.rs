@@ -0,0 +1,955 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Crowd simulation for navmesh agents. See [`Crowd`] docs for more info and usage examples.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, UnitQuaternion, Vector2, Vector3},
+        color::Color,
+        pool::{Handle, Pool},
+        visitor::prelude::*,
+    },
+    scene::debug::{Line, SceneDrawingContext},
+    utils::{
+        astar::{PathError, PathKind},
+        navmesh::{link::NavmeshLinkTraversal, Navmesh, NavmeshAgent},
+    },
+};
+use fxhash::FxHashMap;
+
+/// A set of parameters, that defines the behavior of every agent in a crowd.
+#[derive(Copy, Clone, Debug, PartialEq, Visit)]
+#[visit(optional)]
+pub struct CrowdSettings {
+    /// Maximum distance at which other agents are taken into account for avoidance and separation.
+    /// Default is `4.0`.
+    pub neighbor_distance: f32,
+    /// Maximum amount of nearest neighbors, that are taken into account by an agent. Default is `8`.
+    pub max_neighbors: usize,
+    /// Time interval (in seconds) in which agents guarantee to avoid collisions with each other.
+    /// Larger values make agents react earlier, but make them more "shy". Default is `1.5`.
+    pub time_horizon: f32,
+    /// Additional distance, that agents are trying to keep between each other. Default is `0.1`.
+    pub separation_distance: f32,
+    /// Strength of the separation between agents. Zero disables separation. Default is `1.0`.
+    pub separation_weight: f32,
+    /// Maximum distance (in meters) an agent could deviate from its path before the path is
+    /// recalculated. Default is `1.0`.
+    pub max_path_deviation: f32,
+}
+
+impl Default for CrowdSettings {
+    fn default() -> Self {
+        Self {
+            neighbor_distance: 4.0,
+            max_neighbors: 8,
+            time_horizon: 1.5,
+            separation_distance: 0.1,
+            separation_weight: 1.0,
+            max_path_deviation: 1.0,
+        }
+    }
+}
+
+/// An agent of a crowd. It uses [`NavmeshAgent`] to build paths, but its movement is controlled by
+/// the crowd, which steers agents away from each other.
+#[derive(Clone, Debug, Visit)]
+#[visit(optional)]
+pub struct CrowdAgent {
+    agent: NavmeshAgent,
+    velocity: Vector3<f32>,
+    desired_velocity: Vector3<f32>,
+    max_acceleration: f32,
+    priority: u8,
+    #[visit(skip)]
+    last_path_result: Option<Result<PathKind, PathError>>,
+    // Navmesh triangle the agent is on, it is used to keep the agent on the navmesh without
+    // searching through all the triangles.
+    #[visit(skip)]
+    triangle: Option<usize>,
+    #[visit(skip)]
+    navmesh_revision: u64,
+}
+
+impl Default for CrowdAgent {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl From<NavmeshAgent> for CrowdAgent {
+    fn from(agent: NavmeshAgent) -> Self {
+        Self::new(agent)
+    }
+}
+
+impl CrowdAgent {
+    /// Creates new crowd agent, that will use the given navmesh agent to build paths. Position,
+    /// target, speed and radius of the crowd agent are taken from the navmesh agent.
+    pub fn new(agent: NavmeshAgent) -> Self {
+        Self {
+            agent,
+            velocity: Default::default(),
+            desired_velocity: Default::default(),
+            max_acceleration: 20.0,
+            priority: 0,
+            last_path_result: None,
+            triangle: None,
+            navmesh_revision: 0,
+        }
+    }
+
+    /// Returns a reference to the underlying navmesh agent.
+    pub fn navmesh_agent(&self) -> &NavmeshAgent {
+        &self.agent
+    }
+
+    /// Returns current position of the agent.
+    pub fn position(&self) -> Vector3<f32> {
+        self.agent.position
+    }
+
+    /// Moves the agent to the new position immediately. The path will be recalculated if the
+    /// distance is large enough.
+    pub fn set_position(&mut self, position: Vector3<f32>) {
+        self.agent.set_position(position);
+        self.triangle = None;
+    }
+
+    /// Sets new target for the agent.
+    pub fn set_target(&mut self, target: Vector3<f32>) {
+        self.agent.set_target(target);
+    }
+
+    /// Returns current target of the agent.
+    pub fn target(&self) -> Vector3<f32> {
+        self.agent.target
+    }
+
+    /// Returns current path of the agent.
+    pub fn path(&self) -> &[Vector3<f32>] {
+        &self.agent.path
+    }
+
+    /// Returns the result of the last path calculation, if any.
+    pub fn last_path_result(&self) -> Option<&Result<PathKind, PathError>> {
+        self.last_path_result.as_ref()
+    }
+
+    /// Sets maximum movement speed of the agent.
+    pub fn set_speed(&mut self, speed: f32) {
+        self.agent.set_speed(speed);
+    }
+
+    /// Returns maximum movement speed of the agent.
+    pub fn speed(&self) -> f32 {
+        self.agent.speed
+    }
+
+    /// Sets radius of the agent. The radius is used for avoidance and to walk around corners.
+    pub fn set_radius(&mut self, radius: f32) {
+        self.agent.set_radius(radius);
+    }
+
+    /// Returns radius of the agent.
+    pub fn radius(&self) -> f32 {
+        self.agent.radius
+    }
+
+    /// Sets maximum acceleration of the agent. Lower values result in smoother, but less responsive
+    /// movement. Default is `20.0`.
+    pub fn set_max_acceleration(&mut self, max_acceleration: f32) {
+        self.max_acceleration = max_acceleration;
+    }
+
+    /// Returns maximum acceleration of the agent.
+    pub fn max_acceleration(&self) -> f32 {
+        self.max_acceleration
+    }
+
+    /// Sets priority of the agent. Agents with lower priority give way to the agents with higher
+    /// priority. Default is `0`.
+    pub fn set_priority(&mut self, priority: u8) {
+        self.priority = priority;
+    }
+
+    /// Returns priority of the agent.
+    pub fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    /// Returns actual velocity of the agent.
+    pub fn velocity(&self) -> Vector3<f32> {
+        self.velocity
+    }
+
+    /// Returns the velocity, that the agent would have without other agents around.
+    pub fn desired_velocity(&self) -> Vector3<f32> {
+        self.desired_velocity
+    }
+
+    /// Returns `true` if the agent has reached the end of its path.
+    pub fn is_arrived(&self) -> bool {
+        self.agent.path.last().map_or(true, |end| {
+            horizontal(end - self.agent.position).norm() <= ARRIVAL_DISTANCE
+        })
+    }
+
+    // Advances the agent along its path corridor, skipping passed corners, and returns the
+    // velocity towards the next corner. Starts traversal of an off-mesh link, if the agent has
+    // reached it.
+    fn follow_path(&mut self, max_path_deviation: f32) -> Vector3<f32> {
+        let agent = &mut self.agent;
+        let position = agent.position;
+        let path = &agent.path;
+        if path.len() < 2 {
+            return Vector3::default();
+        }
+
+        let mut current = (agent.current as usize).min(path.len() - 2);
+
+        // The agent must not skip off-mesh links, so the corridor ends at the next link.
+        let link_point = agent
+            .path_links
+            .iter()
+            .map(|path_link| path_link.point as usize)
+            .filter(|point| *point >= current)
+            .min();
+        if link_point == Some(current) {
+            agent.try_begin_link_traversal();
+            return Vector3::default();
+        }
+        let last_segment = link_point.map_or(path.len() - 2, |point| point - 1);
+
+        let segment_distance = |i: usize| {
+            distance_to_segment(
+                horizontal(position),
+                horizontal(path[i]),
+                horizontal(path[i + 1]),
+            )
+        };
+
+        // An agent could be pushed by other agents, so it might be closer to further segments of
+        // the path.
+        let mut distance = segment_distance(current);
+        for i in current + 1..=last_segment {
+            let next_distance = segment_distance(i);
+            if next_distance <= distance {
+                current = i;
+                distance = next_distance;
+            }
+        }
+        while current + 2 < path.len()
+            && horizontal(path[current + 1] - position).norm() <= agent.radius.max(ARRIVAL_DISTANCE)
+        {
+            current += 1;
+            if link_point == Some(current) {
+                agent.current = current as u32;
+                agent.try_begin_link_traversal();
+                return Vector3::default();
+            }
+        }
+        agent.current = current as u32;
+
+        if distance > max_path_deviation {
+            agent.path_dirty = true;
+        }
+
+        let corner = path[current + 1];
+        let delta = horizontal(corner - position);
+        let distance = delta.norm();
+        if distance <= ARRIVAL_DISTANCE && current + 2 == path.len() {
+            return Vector3::default();
+        }
+
+        // Slow down near the end of the path.
+        let mut speed = agent.speed;
+        if current + 2 == path.len() {
+            let slowdown_distance = (agent.radius * 2.0).max(ARRIVAL_DISTANCE);
+            speed *= (distance / slowdown_distance).min(1.0);
+        }
+
+        let direction = delta.scale(1.0 / distance.max(f32::EPSILON));
+        Vector3::new(direction.x, 0.0, direction.y).scale(speed)
+    }
+}
+
+const ARRIVAL_DISTANCE: f32 = 0.05;
+
+fn horizontal(v: Vector3<f32>) -> Vector2<f32> {
+    Vector2::new(v.x, v.z)
+}
+
+fn distance_to_segment(p: Vector2<f32>, a: Vector2<f32>, b: Vector2<f32>) -> f32 {
+    let ab = b - a;
+    let length_sqr = ab.norm_squared();
+    let t = if length_sqr > f32::EPSILON {
+        ((p - a).dot(&ab) / length_sqr).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (a + ab.scale(t) - p).norm()
+}
+
+fn det(a: Vector2<f32>, b: Vector2<f32>) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+// A half-plane of permitted velocities. Permitted velocities are on the left of the line.
+#[derive(Copy, Clone, Debug)]
+struct OrcaLine {
+    point: Vector2<f32>,
+    direction: Vector2<f32>,
+}
+
+// Solves a one-dimensional linear program on the given line, subject to the constraints of all
+// previous lines and a circular constraint.
+fn linear_program1(
+    lines: &[OrcaLine],
+    line_no: usize,
+    radius: f32,
+    opt_velocity: Vector2<f32>,
+    direction_opt: bool,
+    result: &mut Vector2<f32>,
+) -> bool {
+    let line = &lines[line_no];
+    let dot_product = line.point.dot(&line.direction);
+    let discriminant = dot_product * dot_product + radius * radius - line.point.norm_squared();
+    if discriminant < 0.0 {
+        // Max speed circle fully invalidates the line.
+        return false;
+    }
+
+    let sqrt_discriminant = discriminant.sqrt();
+    let mut t_left = -dot_product - sqrt_discriminant;
+    let mut t_right = -dot_product + sqrt_discriminant;
+
+    for other in &lines[..line_no] {
+        let denominator = det(line.direction, other.direction);
+        let numerator = det(other.direction, line.point - other.point);
+
+        if denominator.abs() <= f32::EPSILON {
+            // The lines are almost parallel.
+            if numerator < 0.0 {
+                return false;
+            }
+            continue;
+        }
+
+        let t = numerator / denominator;
+        if denominator >= 0.0 {
+            t_right = t_right.min(t);
+        } else {
+            t_left = t_left.max(t);
+        }
+
+        if t_left > t_right {
+            return false;
+        }
+    }
+
+    let t = if direction_opt {
+        if opt_velocity.dot(&line.direction) > 0.0 {
+            t_right
+        } else {
+            t_left
+        }
+    } else {
+        line.direction
+            .dot(&(opt_velocity - line.point))
+            .clamp(t_left, t_right)
+    };
+    *result = line.point + line.direction.scale(t);
+
+    true
+}
+
+// Solves a two-dimensional linear program subject to the given lines and a circular constraint.
+// Returns the index of the line on which the program failed, or the number of lines on success.
+fn linear_program2(
+    lines: &[OrcaLine],
+    radius: f32,
+    opt_velocity: Vector2<f32>,
+    direction_opt: bool,
+    result: &mut Vector2<f32>,
+) -> usize {
+    *result = if direction_opt {
+        // Optimize direction, the velocity is a unit vector in this case.
+        opt_velocity.scale(radius)
+    } else if opt_velocity.norm_squared() > radius * radius {
+        opt_velocity.normalize().scale(radius)
+    } else {
+        opt_velocity
+    };
+
+    for (i, line) in lines.iter().enumerate() {
+        if det(line.direction, line.point - *result) > 0.0 {
+            // The result does not satisfy the constraint of the line.
+            let previous_result = *result;
+            if !linear_program1(lines, i, radius, opt_velocity, direction_opt, result) {
+                *result = previous_result;
+                return i;
+            }
+        }
+    }
+
+    lines.len()
+}
+
+// Finds the velocity, that minimizes the maximum penetration into the half-planes, when the
+// two-dimensional program is infeasible.
+fn linear_program3(lines: &[OrcaLine], begin_line: usize, radius: f32, result: &mut Vector2<f32>) {
+    let mut distance = 0.0;
+    let mut projected_lines = Vec::new();
+
+    for i in begin_line..lines.len() {
+        let line = &lines[i];
+        if det(line.direction, line.point - *result) <= distance {
+            continue;
+        }
+
+        // The result does not satisfy the constraint of the line.
+        projected_lines.clear();
+        for other in &lines[..i] {
+            let determinant = det(line.direction, other.direction);
+            let point = if determinant.abs() <= f32::EPSILON {
+                if line.direction.dot(&other.direction) > 0.0 {
+                    // The lines point in the same direction.
+                    continue;
+                }
+                // The lines point in opposite directions.
+                (line.point + other.point).scale(0.5)
+            } else {
+                line.point
+                    + line
+                        .direction
+                        .scale(det(other.direction, line.point - other.point) / determinant)
+            };
+            let Some(direction) = (other.direction - line.direction).try_normalize(f32::EPSILON)
+            else {
+                continue;
+            };
+            projected_lines.push(OrcaLine { point, direction });
+        }
+
+        let previous_result = *result;
+        if linear_program2(
+            &projected_lines,
+            radius,
+            Vector2::new(-line.direction.y, line.direction.x),
+            true,
+            result,
+        ) < projected_lines.len()
+        {
+            // This should in principle not happen, the result is by definition already in the
+            // feasible region of this linear program. If it fails, it is due to small floating
+            // point error, and the current result is kept.
+            *result = previous_result;
+        }
+
+        distance = det(line.direction, line.point - *result);
+    }
+}
+
+#[derive(Copy, Clone)]
+struct AgentSnapshot {
+    position: Vector2<f32>,
+    velocity: Vector2<f32>,
+    radius: f32,
+    priority: u8,
+}
+
+// Returns the part of the avoidance, that the agent with the first priority takes. Agents with
+// higher priority take smaller part, so the agents with lower priority give way.
+fn responsibility(priority: u8, other_priority: u8) -> f32 {
+    let weight = 1.0 + priority as f32;
+    let other_weight = 1.0 + other_priority as f32;
+    other_weight / (weight + other_weight)
+}
+
+/// Crowd is a set of navmesh agents, that are updated together and avoid collisions with each other.
+/// It uses the following techniques:
+///
+/// - **Velocity obstacles** - every agent selects its velocity using Optimal Reciprocal Collision
+/// Avoidance (ORCA), which guarantees collision-free movement of agents within the time horizon
+/// (see [`CrowdSettings::time_horizon`]), if possible. Agents share the responsibility for avoidance
+/// according to their priorities - agents with lower priorities give way to the agents with higher
+/// ones.
+/// - **Separation** - agents are pushed away from each other if they're too close, which helps
+/// to resolve jams in narrow passages.
+/// - **Corridor optimization** - since agents could be pushed away from their paths, every agent
+/// keeps track of its progress along the path corridor, skips the corners it has already passed and
+/// recalculates its path if it has deviated too much.
+///
+/// Agents are kept on the navigational mesh, every agent is projected on the closest point of the
+/// navmesh after its movement.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::{algebra::Vector3, pool::Handle},
+/// #     utils::navmesh::{
+/// #         crowd::{Crowd, CrowdAgent},
+/// #         Navmesh, NavmeshAgentBuilder,
+/// #     },
+/// # };
+/// fn update_crowd(crowd: &mut Crowd, navmesh: &Navmesh, target: Vector3<f32>, dt: f32) {
+///     // Every agent follows the same target.
+///     for agent in crowd.agents_mut() {
+///         agent.set_target(target);
+///     }
+///
+///     crowd.update(dt, navmesh);
+///
+///     for agent in crowd.agents() {
+///         // Use the position to move your game characters.
+///         println!("{}", agent.position());
+///     }
+/// }
+///
+/// fn add_agent(crowd: &mut Crowd, position: Vector3<f32>) -> Handle<CrowdAgent> {
+///     crowd.add_agent(CrowdAgent::new(
+///         NavmeshAgentBuilder::new()
+///             .with_position(position)
+///             .with_speed(2.0)
+///             .build(),
+///     ))
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Visit)]
+#[visit(optional)]
+pub struct Crowd {
+    settings: CrowdSettings,
+    agents: Pool<CrowdAgent>,
+}
+
+impl Crowd {
+    /// Creates new empty crowd with the given settings.
+    pub fn new(settings: CrowdSettings) -> Self {
+        Self {
+            settings,
+            agents: Default::default(),
+        }
+    }
+
+    /// Returns a reference to current settings of the crowd.
+    pub fn settings(&self) -> &CrowdSettings {
+        &self.settings
+    }
+
+    /// Sets new settings of the crowd.
+    pub fn set_settings(&mut self, settings: CrowdSettings) {
+        self.settings = settings;
+    }
+
+    /// Adds new agent to the crowd and returns its handle.
+    pub fn add_agent(&mut self, agent: CrowdAgent) -> Handle<CrowdAgent> {
+        self.agents.spawn(agent)
+    }
+
+    /// Removes the agent from the crowd. Returns the removed agent or `None` if the handle is invalid.
+    pub fn remove_agent(&mut self, handle: Handle<CrowdAgent>) -> Option<CrowdAgent> {
+        self.agents.try_free(handle)
+    }
+
+    /// Returns a reference to the agent with the given handle.
+    pub fn agent(&self, handle: Handle<CrowdAgent>) -> Option<&CrowdAgent> {
+        self.agents.try_borrow(handle)
+    }
+
+    /// Returns a reference to the agent with the given handle.
+    pub fn agent_mut(&mut self, handle: Handle<CrowdAgent>) -> Option<&mut CrowdAgent> {
+        self.agents.try_borrow_mut(handle)
+    }
+
+    /// Returns an iterator over all agents of the crowd.
+    pub fn agents(&self) -> impl Iterator<Item = &CrowdAgent> {
+        self.agents.iter()
+    }
+
+    /// Returns an iterator over all agents of the crowd.
+    pub fn agents_mut(&mut self) -> impl Iterator<Item = &mut CrowdAgent> {
+        self.agents.iter_mut()
+    }
+
+    /// Returns an iterator over all agents of the crowd and their handles.
+    pub fn pair_iter(&self) -> impl Iterator<Item = (Handle<CrowdAgent>, &CrowdAgent)> {
+        self.agents.pair_iter()
+    }
+
+    /// Performs a single update tick of every agent in the crowd - updates paths of the agents,
+    /// calculates their velocities and moves them. Off-mesh links are traversed in a straight line
+    /// with normal speed of the agents (or instantly, if the link is a teleport).
+    pub fn update(&mut self, dt: f32, navmesh: &Navmesh) {
+        self.update_internal(dt, navmesh, &mut |_, speed, traversal| {
+            traversal.advance(speed * dt)
+        })
+    }
+
+    /// Performs a single update tick of every agent in the crowd, the same as [`Self::update`], but
+    /// allows you to control the traversal of off-mesh links. See
+    /// [`NavmeshAgent::update_with_link_handler`] docs for more info.
+    pub fn update_with_link_handler<F>(&mut self, dt: f32, navmesh: &Navmesh, mut handler: F)
+    where
+        F: FnMut(Handle<CrowdAgent>, &mut NavmeshLinkTraversal) -> bool,
+    {
+        self.update_internal(dt, navmesh, &mut |handle, _, traversal| {
+            handler(handle, traversal)
+        })
+    }
+
+    fn update_internal(
+        &mut self,
+        dt: f32,
+        navmesh: &Navmesh,
+        handler: &mut dyn FnMut(Handle<CrowdAgent>, f32, &mut NavmeshLinkTraversal) -> bool,
+    ) {
+        if dt <= 0.0 {
+            return;
+        }
+
+        let settings = self.settings;
+
+        for (handle, agent) in self.agents.pair_iter_mut() {
+            let speed = agent.agent.speed;
+            if agent
+                .agent
+                .update_link_traversal(dt, &mut |traversal: &mut NavmeshLinkTraversal| {
+                    handler(handle, speed, traversal)
+                })
+            {
+                agent.desired_velocity = Vector3::default();
+                continue;
+            }
+
+            agent.agent.check_navmesh_changes(navmesh);
+            if agent.agent.path_dirty {
+                let position = agent.agent.position;
+                let target = agent.agent.target;
+                agent.last_path_result =
+                    Some(agent.agent.calculate_path(navmesh, position, target));
+                agent.agent.path_dirty = false;
+            }
+            agent.desired_velocity = agent.follow_path(settings.max_path_deviation);
+        }
+
+        let handles = self
+            .agents
+            .pair_iter()
+            .map(|(handle, _)| handle)
+            .collect::<Vec<_>>();
+        let snapshots = handles
+            .iter()
+            .map(|handle| {
+                let agent = &self.agents[*handle];
+                AgentSnapshot {
+                    position: horizontal(agent.agent.position),
+                    velocity: horizontal(agent.velocity),
+                    radius: agent.agent.radius,
+                    priority: agent.priority,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Spatial hash to speed up neighbor search.
+        let cell_size = settings.neighbor_distance.max(0.1);
+        let cell_of = |p: Vector2<f32>| {
+            (
+                (p.x / cell_size).floor() as i32,
+                (p.y / cell_size).floor() as i32,
+            )
+        };
+        let mut grid = FxHashMap::<(i32, i32), Vec<usize>>::default();
+        for (i, snapshot) in snapshots.iter().enumerate() {
+            grid.entry(cell_of(snapshot.position)).or_default().push(i);
+        }
+
+        let mut neighbors = Vec::new();
+        let mut lines = Vec::new();
+        for (i, handle) in handles.iter().enumerate() {
+            let snapshot = snapshots[i];
+
+            if self.agents[*handle].agent.is_traversing_link() {
+                self.agents[*handle].velocity = Vector3::default();
+                continue;
+            }
+
+            neighbors.clear();
+            let (cx, cz) = cell_of(snapshot.position);
+            for x in cx - 1..=cx + 1 {
+                for z in cz - 1..=cz + 1 {
+                    for &j in grid.get(&(x, z)).into_iter().flatten() {
+                        let distance = (snapshots[j].position - snapshot.position).norm();
+                        if j != i && distance <= settings.neighbor_distance {
+                            neighbors.push((distance, j));
+                        }
+                    }
+                }
+            }
+            neighbors.sort_by(|a, b| a.0.total_cmp(&b.0));
+            neighbors.truncate(settings.max_neighbors);
+
+            let agent = &mut self.agents[*handle];
+            let mut preferred_velocity = horizontal(agent.desired_velocity);
+
+            // Separation.
+            if settings.separation_weight > 0.0 {
+                let mut separation = Vector2::default();
+                for &(distance, j) in neighbors.iter() {
+                    let other = &snapshots[j];
+                    let range = snapshot.radius + other.radius + settings.separation_distance;
+                    if distance < range {
+                        let direction = if distance > f32::EPSILON {
+                            (snapshot.position - other.position).scale(1.0 / distance)
+                        } else {
+                            // Agents at the same spot, pick an arbitrary but stable direction.
+                            let angle = i as f32;
+                            Vector2::new(angle.cos(), angle.sin())
+                        };
+                        separation += direction.scale(1.0 - distance / range);
+                    }
+                }
+                preferred_velocity +=
+                    separation.scale(settings.separation_weight * agent.agent.speed);
+            }
+
+            // Reciprocal collision avoidance.
+            lines.clear();
+            let inv_time_horizon = 1.0 / settings.time_horizon.max(f32::EPSILON);
+            for &(_, j) in neighbors.iter() {
+                let other = &snapshots[j];
+                let relative_position = other.position - snapshot.position;
+                let relative_velocity = snapshot.velocity - other.velocity;
+                let distance_sqr = relative_position.norm_squared();
+                let combined_radius = snapshot.radius + other.radius;
+                let combined_radius_sqr = combined_radius * combined_radius;
+
+                let (direction, u) = if distance_sqr > combined_radius_sqr {
+                    // No collision. Vector from cutoff center to relative velocity.
+                    let w = relative_velocity - relative_position.scale(inv_time_horizon);
+                    let w_length_sqr = w.norm_squared();
+                    let dot_product = w.dot(&relative_position);
+
+                    if dot_product < 0.0
+                        && dot_product * dot_product > combined_radius_sqr * w_length_sqr
+                    {
+                        // Project on cut-off circle.
+                        let w_length = w_length_sqr.sqrt();
+                        let unit_w = w.scale(1.0 / w_length);
+                        (
+                            Vector2::new(unit_w.y, -unit_w.x),
+                            unit_w.scale(combined_radius * inv_time_horizon - w_length),
+                        )
+                    } else {
+                        // Project on legs.
+                        let leg = (distance_sqr - combined_radius_sqr).sqrt();
+                        let direction = if det(relative_position, w) > 0.0 {
+                            // Project on left leg.
+                            Vector2::new(
+                                relative_position.x * leg - relative_position.y * combined_radius,
+                                relative_position.x * combined_radius + relative_position.y * leg,
+                            )
+                        } else {
+                            // Project on right leg.
+                            -Vector2::new(
+                                relative_position.x * leg + relative_position.y * combined_radius,
+                                -relative_position.x * combined_radius + relative_position.y * leg,
+                            )
+                        }
+                        .scale(1.0 / distance_sqr);
+                        let dot_product = relative_velocity.dot(&direction);
+                        (direction, direction.scale(dot_product) - relative_velocity)
+                    }
+                } else {
+                    // Collision. Project on cut-off circle of time step.
+                    let inv_time_step = 1.0 / dt;
+                    let w = relative_velocity - relative_position.scale(inv_time_step);
+                    let w_length = w.norm();
+                    let unit_w = if w_length > f32::EPSILON {
+                        w.scale(1.0 / w_length)
+                    } else {
+                        Vector2::new(1.0, 0.0)
+                    };
+                    (
+                        Vector2::new(unit_w.y, -unit_w.x),
+                        unit_w.scale(combined_radius * inv_time_step - w_length),
+                    )
+                };
+
+                lines.push(OrcaLine {
+                    point: snapshot.velocity
+                        + u.scale(responsibility(snapshot.priority, other.priority)),
+                    direction,
+                });
+            }
+
+            let max_speed = agent.agent.speed;
+            let mut new_velocity = Vector2::default();
+            let failed_line = linear_program2(
+                &lines,
+                max_speed,
+                preferred_velocity,
+                false,
+                &mut new_velocity,
+            );
+            if failed_line < lines.len() {
+                linear_program3(&lines, failed_line, max_speed, &mut new_velocity);
+            }
+
+            // Limit the acceleration.
+            let velocity_change = new_velocity - snapshot.velocity;
+            let max_change = agent.max_acceleration * dt;
+            if velocity_change.norm() > max_change {
+                new_velocity = snapshot.velocity + velocity_change.normalize().scale(max_change);
+            }
+
+            agent.velocity = Vector3::new(new_velocity.x, 0.0, new_velocity.y);
+        }
+
+        // Integrate and keep the agents on the navmesh.
+        for agent in self.agents.iter_mut() {
+            if agent.agent.is_traversing_link() {
+                // The agent will leave the link somewhere else.
+                agent.triangle = None;
+                continue;
+            }
+            let new_position = agent.agent.position + agent.velocity.scale(dt);
+            // Agents move a little per frame, so the closest point is searched near the triangle
+            // the agent was on. The search through all the triangles is used only when the local
+            // search fails.
+            let closest = agent
+                .triangle
+                .filter(|_| agent.navmesh_revision == navmesh.revision())
+                .and_then(|triangle| navmesh.query_closest_near(new_position, triangle))
+                .or_else(|| navmesh.query_closest(new_position));
+            agent.triangle = closest.map(|(_, triangle)| triangle);
+            agent.navmesh_revision = navmesh.revision();
+            agent.agent.position = closest.map_or(new_position, |(position, _)| position);
+        }
+    }
+
+    /// Draws agents, their velocities and paths. Agents are drawn as yellow circles, their actual
+    /// velocities - green lines, desired velocities - red lines and the paths - blue lines.
+    pub fn debug_draw(&self, ctx: &mut SceneDrawingContext) {
+        let rotation =
+            UnitQuaternion::from_axis_angle(&Vector3::x_axis(), std::f32::consts::FRAC_PI_2)
+                .to_homogeneous();
+
+        for agent in self.agents.iter() {
+            let position = agent.agent.position;
+
+            ctx.draw_circle(
+                Default::default(),
+                agent.agent.radius,
+                16,
+                Matrix4::new_translation(&position) * rotation,
+                Color::opaque(255, 255, 0),
+            );
+            ctx.add_line(Line {
+                begin: position,
+                end: position + agent.velocity,
+                color: Color::GREEN,
+            });
+            ctx.add_line(Line {
+                begin: position,
+                end: position + agent.desired_velocity,
+                color: Color::RED,
+            });
+
+            for segment in agent.agent.path.windows(2) {
+                ctx.add_line(Line {
+                    begin: segment[0],
+                    end: segment[1],
+                    color: Color::BLUE,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector3, math::TriangleDefinition},
+        utils::navmesh::{
+            crowd::{Crowd, CrowdAgent, CrowdSettings},
+            Navmesh, NavmeshAgentBuilder,
+        },
+    };
+
+    // 10x3 meters corridor along X axis.
+    fn make_corridor() -> Navmesh {
+        let mut vertices = Vec::new();
+        let mut triangles = Vec::new();
+        for i in 0..=10u32 {
+            vertices.push(Vector3::new(i as f32, 0.0, -1.5));
+            vertices.push(Vector3::new(i as f32, 0.0, 1.5));
+            if i < 10 {
+                triangles.push(TriangleDefinition([2 * i, 2 * i + 1, 2 * i + 2]));
+                triangles.push(TriangleDefinition([2 * i + 1, 2 * i + 3, 2 * i + 2]));
+            }
+        }
+        Navmesh::new(triangles, vertices)
+    }
+
+    fn make_agent(position: Vector3<f32>, target: Vector3<f32>) -> CrowdAgent {
+        let mut agent = CrowdAgent::new(
+            NavmeshAgentBuilder::new()
+                .with_position(position)
+                .with_speed(2.0)
+                .build(),
+        );
+        agent.set_radius(0.4);
+        agent.set_target(target);
+        agent
+    }
+
+    #[test]
+    fn test_agents_avoid_each_other() {
+        let navmesh = make_corridor();
+        let mut crowd = Crowd::new(CrowdSettings::default());
+
+        let a_target = Vector3::new(9.0, 0.0, 0.0);
+        let b_target = Vector3::new(1.0, 0.0, 0.0);
+        let a = crowd.add_agent(make_agent(b_target, a_target));
+        let b = crowd.add_agent(make_agent(a_target, b_target));
+
+        let mut min_distance = f32::MAX;
+        for _ in 0..600 {
+            crowd.update(1.0 / 60.0, &navmesh);
+            let a_position = crowd.agent(a).unwrap().position();
+            let b_position = crowd.agent(b).unwrap().position();
+            min_distance = min_distance.min((a_position - b_position).norm());
+        }
+
+        assert!(min_distance >= 0.75, "{min_distance}");
+
+        for (handle, target) in [(a, a_target), (b, b_target)] {
+            let agent = crowd.agent(handle).unwrap();
+            assert!(agent.is_arrived());
+            assert!((agent.position() - target).norm() < 0.2);
+            // The triangle is tracked by the local search.
+            let (_, triangle) = navmesh.query_closest(agent.position()).unwrap();
+            assert_eq!(agent.triangle, Some(triangle));
+        }
+    }
+}
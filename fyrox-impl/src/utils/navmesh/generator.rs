@@ -0,0 +1,1320 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Automatic generation of navigational meshes from arbitrary scene geometry. See [`NavmeshGenerator`]
+//! docs for more info and usage examples.
+
+use crate::{
+    core::{
+        algebra::{Point3, Vector3},
+        math::{aabb::AxisAlignedBoundingBox, TriangleDefinition},
+        pool::Handle,
+        reflect::prelude::*,
+        visitor::prelude::*,
+    },
+    scene::{
+        collider::Collider,
+        graph::{physics::make_collider_trimesh, Graph},
+        mesh::{
+            buffer::{VertexAttributeUsage, VertexReadTrait},
+            Mesh,
+        },
+        node::Node,
+    },
+    utils::navmesh::Navmesh,
+};
+use fxhash::FxHashMap;
+use fyrox_graph::{BaseSceneGraph, SceneGraph};
+use std::{collections::VecDeque, ops::Range};
+
+/// A set of parameters, that defines how a navigational mesh will be generated. Agent parameters
+/// are defined in world units and converted to the units of the voxel grid internally.
+#[derive(Copy, Clone, Debug, PartialEq, Visit, Reflect)]
+#[visit(optional)]
+pub struct NavmeshGenerationSettings {
+    /// Horizontal size of a voxel. Smaller values result in more precise navigational meshes,
+    /// at the cost of longer generation time. Default is `0.25`.
+    #[reflect(
+        min_value = 0.01,
+        description = "Horizontal size of a voxel. Smaller values result in more precise \
+        navigational meshes, at the cost of longer generation time."
+    )]
+    pub cell_size: f32,
+
+    /// Vertical size of a voxel. Default is `0.2`.
+    #[reflect(min_value = 0.01, description = "Vertical size of a voxel.")]
+    pub cell_height: f32,
+
+    /// Radius of agents. Walkable area is shrunk by this distance from walls and ledges.
+    /// Default is `0.5`.
+    #[reflect(
+        min_value = 0.0,
+        description = "Radius of agents. Walkable area is shrunk by this distance from walls \
+        and ledges."
+    )]
+    pub agent_radius: f32,
+
+    /// Height of agents. Areas with lower ceiling are not walkable. Default is `2.0`.
+    #[reflect(
+        min_value = 0.0,
+        description = "Height of agents. Areas with lower ceiling are not walkable."
+    )]
+    pub agent_height: f32,
+
+    /// Maximum height of a step, that agents can climb. Default is `0.5`.
+    #[reflect(
+        min_value = 0.0,
+        description = "Maximum height of a step, that agents can climb."
+    )]
+    pub agent_max_climb: f32,
+
+    /// Maximum slope (in degrees) of a surface, that is still walkable. Default is `45.0`.
+    #[reflect(
+        min_value = 0.0,
+        max_value = 90.0,
+        description = "Maximum slope (in degrees) of a surface, that is still walkable."
+    )]
+    pub agent_max_slope: f32,
+
+    /// Minimum amount of cells in an isolated walkable area. Smaller areas (such as table tops)
+    /// will be discarded. Default is `8`.
+    #[reflect(
+        description = "Minimum amount of cells in an isolated walkable area. Smaller areas will \
+        be discarded."
+    )]
+    pub min_region_area: u32,
+
+    /// Maximum distance, that simplified borders of walkable area could deviate from the voxelized
+    /// ones. Default is `0.3`.
+    #[reflect(
+        min_value = 0.0,
+        description = "Maximum distance, that simplified borders of walkable area could deviate \
+        from the voxelized ones."
+    )]
+    pub max_edge_error: f32,
+}
+
+impl Default for NavmeshGenerationSettings {
+    fn default() -> Self {
+        Self {
+            cell_size: 0.25,
+            cell_height: 0.2,
+            agent_radius: 0.5,
+            agent_height: 2.0,
+            agent_max_climb: 0.5,
+            agent_max_slope: 45.0,
+            min_region_area: 8,
+            max_edge_error: 0.3,
+        }
+    }
+}
+
+/// Triangulated geometry in world space, that is used as a source for navigational mesh
+/// generation.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NavmeshGeometry {
+    /// Vertices of the geometry.
+    pub vertices: Vec<Vector3<f32>>,
+    /// Triangles of the geometry.
+    pub triangles: Vec<TriangleDefinition>,
+}
+
+impl NavmeshGeometry {
+    /// Collects geometry of every enabled mesh and collider in the given subtrees of the graph.
+    /// If `sources` is empty, the entire graph will be used.
+    pub fn from_graph(graph: &Graph, sources: &[Handle<Node>]) -> Self {
+        let mut geometry = Self::default();
+        let root = [graph.get_root()];
+        let sources = if sources.is_empty() { &root } else { sources };
+        for &source in sources {
+            if !graph.is_valid_handle(source) {
+                continue;
+            }
+
+            for handle in graph.traverse_handle_iter(source) {
+                let node = &graph[handle];
+                if !node.is_globally_enabled() {
+                    continue;
+                }
+
+                if let Some(mesh) = node.cast::<Mesh>() {
+                    geometry.add_mesh(mesh);
+                } else if node.cast::<Collider>().is_some() {
+                    geometry.add_collider(graph, handle);
+                }
+            }
+        }
+        geometry
+    }
+
+    /// Adds a set of triangles to the geometry. Triangles must reference the given vertices.
+    pub fn add_triangles(
+        &mut self,
+        vertices: impl IntoIterator<Item = Vector3<f32>>,
+        triangles: impl IntoIterator<Item = TriangleDefinition>,
+    ) {
+        let offset = self.vertices.len() as u32;
+        self.vertices.extend(vertices);
+        self.triangles.extend(
+            triangles
+                .into_iter()
+                .map(|t| TriangleDefinition([t[0] + offset, t[1] + offset, t[2] + offset])),
+        );
+    }
+
+    /// Adds every surface of the given mesh to the geometry.
+    pub fn add_mesh(&mut self, mesh: &Mesh) {
+        let global_transform = mesh.global_transform();
+        for surface in mesh.surfaces() {
+            let data = surface.data();
+            let data = data.data_ref();
+            self.add_triangles(
+                data.vertex_buffer.iter().map(|vertex| {
+                    let position = vertex
+                        .read_3_f32(VertexAttributeUsage::Position)
+                        .unwrap_or_default();
+                    global_transform
+                        .transform_point(&Point3::from(position))
+                        .coords
+                }),
+                data.geometry_buffer.iter().cloned(),
+            );
+        }
+    }
+
+    /// Adds the shape of the given collider to the geometry.
+    pub fn add_collider(&mut self, graph: &Graph, collider: Handle<Node>) {
+        if let Some((vertices, triangles)) = make_collider_trimesh(graph, collider) {
+            self.add_triangles(vertices, triangles);
+        }
+    }
+
+    /// Returns `true` if the geometry has no triangles.
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty()
+    }
+
+    fn bounds(&self) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::from_points(&self.vertices)
+    }
+}
+
+/// Navigational mesh generator creates a [`Navmesh`] from arbitrary triangulated geometry. The
+/// generator does not require the geometry to be clean - it could have overlapping or intersecting
+/// triangles, holes, etc. The generation consists of the following steps:
+///
+/// 1. The geometry is voxelized into a grid of vertical columns of solid spans. Spans, that are
+/// formed by triangles with a slope less than [`NavmeshGenerationSettings::agent_max_slope`], are
+/// marked as walkable.
+/// 2. Walkable spans are filtered - spans with low ceiling, ledges and steep steps become
+/// non-walkable, while low obstacles that could be stepped over become walkable.
+/// 3. Walkable area is shrunk by the radius of agents and small isolated areas are removed.
+/// 4. Walkable area is split into regions without holes.
+/// 5. The outlines of the regions are traced and simplified.
+/// 6. The outlines are triangulated and the resulting triangles form the navigational mesh.
+///
+/// Generation could take a significant amount of time for large scenes, so it is better to run
+/// it off the main thread. Since the generator owns all the data it needs, it could be moved to a
+/// task, for example:
+///
+/// ```rust ,no_run
+/// # use fyrox_impl::{
+/// #     core::{pool::Handle, reflect::prelude::*, visitor::prelude::*, impl_component_provider},
+/// #     scene::{navmesh::NavigationalMesh, node::Node},
+/// #     script::{ScriptContext, ScriptTrait},
+/// # };
+/// # use fyrox_core::uuid_provider;
+/// #
+/// #[derive(Reflect, Visit, Default, Debug, Clone)]
+/// struct Level {
+///     navmesh: Handle<Node>,
+/// }
+///
+/// # impl_component_provider!(Level);
+/// # uuid_provider!(Level = "f5ded79e-6101-4e23-b20d-48cbdb25d87a");
+/// #
+/// impl ScriptTrait for Level {
+///     fn on_start(&mut self, ctx: &mut ScriptContext) {
+///         // Collect the geometry on the main thread.
+///         let generator = ctx.scene.graph[self.navmesh]
+///             .as_navigational_mesh()
+///             .make_generator(&ctx.scene.graph);
+///
+///         ctx.task_pool.spawn_script_task(
+///             ctx.scene_handle,
+///             ctx.handle,
+///             ctx.script_index,
+///             // Generate the navmesh in a background thread.
+///             async move { generator.generate() },
+///             |navmesh, level: &mut Level, ctx| {
+///                 *ctx.scene.graph[level.navmesh]
+///                     .as_navigational_mesh_mut()
+///                     .navmesh_mut() = navmesh;
+///             },
+///         );
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct NavmeshGenerator {
+    settings: NavmeshGenerationSettings,
+    geometry: NavmeshGeometry,
+}
+
+impl NavmeshGenerator {
+    /// Creates new generator, that will use the given settings and geometry.
+    pub fn new(settings: NavmeshGenerationSettings, geometry: NavmeshGeometry) -> Self {
+        Self { settings, geometry }
+    }
+
+    /// Returns a reference to the generation settings.
+    pub fn settings(&self) -> &NavmeshGenerationSettings {
+        &self.settings
+    }
+
+    /// Returns a reference to the source geometry.
+    pub fn geometry(&self) -> &NavmeshGeometry {
+        &self.geometry
+    }
+
+    /// Generates new navigational mesh. Returns an empty navmesh if there's no walkable area in
+    /// the source geometry.
+    pub fn generate(&self) -> Navmesh {
+        if self.geometry.is_empty() {
+            return Navmesh::default();
+        }
+
+        let settings = &self.settings;
+        let cell_size = settings.cell_size.max(0.01);
+        let cell_height = settings.cell_height.max(0.01);
+        let config = Config {
+            walkable_height: (settings.agent_height / cell_height).ceil() as i32,
+            walkable_climb: (settings.agent_max_climb / cell_height).floor() as i32,
+            walkable_radius: (settings.agent_radius / cell_size).ceil() as i32,
+            walkable_slope_cos: settings.agent_max_slope.clamp(0.0, 90.0).to_radians().cos(),
+            max_error: settings.max_edge_error / cell_size,
+        };
+
+        let mut heightfield = Heightfield::new(self.geometry.bounds(), cell_size, cell_height);
+        heightfield.rasterize(&self.geometry, &config);
+        heightfield.filter_low_hanging_obstacles(&config);
+        heightfield.filter_ledges(&config);
+        heightfield.filter_low_height_spans(&config);
+
+        let mut compact = CompactHeightfield::new(&heightfield, &config);
+        compact.erode(config.walkable_radius);
+        compact.remove_small_islands(settings.min_region_area as usize);
+        compact.build_regions();
+
+        let contours = compact.build_contours(config.max_error);
+        heightfield.triangulate(&contours, &config)
+    }
+}
+
+struct Config {
+    walkable_height: i32,
+    walkable_climb: i32,
+    walkable_radius: i32,
+    walkable_slope_cos: f32,
+    max_error: f32,
+}
+
+// Offsets of the neighbor columns in the following directions: -X, +Z, +X, -Z.
+const DIRECTIONS: [(i32, i32); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+fn column_index(width: i32, depth: i32, x: i32, z: i32) -> Option<usize> {
+    if x >= 0 && z >= 0 && x < width && z < depth {
+        Some((x + z * width) as usize)
+    } else {
+        None
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Span {
+    min: i32,
+    max: i32,
+    walkable: bool,
+}
+
+// Returns the bottom of the span above the span at the given index.
+fn ceiling(column: &[Span], index: usize) -> i32 {
+    column.get(index + 1).map_or(i32::MAX, |span| span.min)
+}
+
+// Clips the polygon by an axis-aligned plane.
+fn clip_polygon(
+    polygon: &[Vector3<f32>],
+    axis: usize,
+    value: f32,
+    keep_greater: bool,
+) -> Vec<Vector3<f32>> {
+    let distance = |v: &Vector3<f32>| {
+        if keep_greater {
+            v[axis] - value
+        } else {
+            value - v[axis]
+        }
+    };
+
+    let mut result = Vec::with_capacity(polygon.len() + 1);
+    for (i, a) in polygon.iter().enumerate() {
+        let b = &polygon[(i + 1) % polygon.len()];
+        let (da, db) = (distance(a), distance(b));
+        if da >= 0.0 {
+            result.push(*a);
+        }
+        if (da >= 0.0) != (db >= 0.0) {
+            result.push(a + (b - a).scale(da / (da - db)));
+        }
+    }
+    result
+}
+
+struct Heightfield {
+    origin: Vector3<f32>,
+    cell_size: f32,
+    cell_height: f32,
+    width: i32,
+    depth: i32,
+    columns: Vec<Vec<Span>>,
+}
+
+impl Heightfield {
+    fn new(bounds: AxisAlignedBoundingBox, cell_size: f32, cell_height: f32) -> Self {
+        let size = bounds.max - bounds.min;
+        let width = ((size.x / cell_size).ceil() as i32).max(1);
+        let depth = ((size.z / cell_size).ceil() as i32).max(1);
+        Self {
+            origin: bounds.min,
+            cell_size,
+            cell_height,
+            width,
+            depth,
+            columns: vec![Vec::new(); (width * depth) as usize],
+        }
+    }
+
+    fn add_span(&mut self, index: usize, mut new_span: Span, merge_threshold: i32) {
+        let column = &mut self.columns[index];
+        let mut i = 0;
+        while i < column.len() {
+            let span = column[i];
+            if span.min > new_span.max {
+                break;
+            } else if span.max < new_span.min {
+                i += 1;
+                continue;
+            }
+
+            // The spans are overlapping - merge them. Walkability is defined by the top surface.
+            if (span.max - new_span.max).abs() <= merge_threshold {
+                new_span.walkable |= span.walkable;
+            } else if span.max > new_span.max {
+                new_span.walkable = span.walkable;
+            }
+            new_span.min = new_span.min.min(span.min);
+            new_span.max = new_span.max.max(span.max);
+            column.remove(i);
+        }
+        column.insert(i, new_span);
+    }
+
+    fn rasterize(&mut self, geometry: &NavmeshGeometry, config: &Config) {
+        for triangle in geometry.triangles.iter() {
+            let (Some(a), Some(b), Some(c)) = (
+                geometry.vertices.get(triangle[0] as usize),
+                geometry.vertices.get(triangle[1] as usize),
+                geometry.vertices.get(triangle[2] as usize),
+            ) else {
+                continue;
+            };
+
+            // The winding of the source geometry is arbitrary, so both sides of a triangle are
+            // considered as walkable.
+            let Some(normal) = (b - a).cross(&(c - a)).try_normalize(f32::EPSILON) else {
+                continue;
+            };
+            let walkable = normal.y.abs() >= config.walkable_slope_cos;
+
+            self.rasterize_triangle(&[*a, *b, *c], walkable, config.walkable_climb);
+        }
+    }
+
+    fn rasterize_triangle(&mut self, triangle: &[Vector3<f32>], walkable: bool, merge: i32) {
+        let (min, max) = triangle.iter().fold(
+            (Vector3::repeat(f32::MAX), Vector3::repeat(-f32::MAX)),
+            |(min, max), v| (min.inf(v), max.sup(v)),
+        );
+
+        let to_cell = |value: f32, origin: f32, limit: i32| {
+            (((value - origin) / self.cell_size).floor() as i32).clamp(0, limit - 1)
+        };
+        let z_range =
+            to_cell(min.z, self.origin.z, self.depth)..=to_cell(max.z, self.origin.z, self.depth);
+        let x_range =
+            to_cell(min.x, self.origin.x, self.width)..=to_cell(max.x, self.origin.x, self.width);
+
+        for z in z_range {
+            let row_min = self.origin.z + z as f32 * self.cell_size;
+            let row = clip_polygon(
+                &clip_polygon(triangle, 2, row_min, true),
+                2,
+                row_min + self.cell_size,
+                false,
+            );
+            if row.len() < 3 {
+                continue;
+            }
+
+            for x in x_range.clone() {
+                let cell_min = self.origin.x + x as f32 * self.cell_size;
+                let cell = clip_polygon(
+                    &clip_polygon(&row, 0, cell_min, true),
+                    0,
+                    cell_min + self.cell_size,
+                    false,
+                );
+                if cell.len() < 3 {
+                    continue;
+                }
+
+                let (min_y, max_y) = cell.iter().fold((f32::MAX, -f32::MAX), |(min, max), v| {
+                    (min.min(v.y), max.max(v.y))
+                });
+                let span_min = ((min_y - self.origin.y) / self.cell_height).floor() as i32;
+                let span_max =
+                    (((max_y - self.origin.y) / self.cell_height).ceil() as i32).max(span_min + 1);
+                self.add_span(
+                    (x + z * self.width) as usize,
+                    Span {
+                        min: span_min,
+                        max: span_max,
+                        walkable,
+                    },
+                    merge,
+                );
+            }
+        }
+    }
+
+    // Makes low obstacles (such as curbs or stairs) walkable, if they're placed on a walkable
+    // surface and their height is less than the climb height.
+    fn filter_low_hanging_obstacles(&mut self, config: &Config) {
+        for column in self.columns.iter_mut() {
+            let mut previous: Option<Span> = None;
+            for span in column.iter_mut() {
+                let original = *span;
+                if let Some(previous) = previous {
+                    if !span.walkable
+                        && previous.walkable
+                        && span.max - previous.max <= config.walkable_climb
+                    {
+                        span.walkable = true;
+                    }
+                }
+                previous = Some(original);
+            }
+        }
+    }
+
+    // Makes spans with a drop (or steep slope) to a neighbor span non-walkable.
+    fn filter_ledges(&mut self, config: &Config) {
+        let mut ledges = Vec::new();
+        for z in 0..self.depth {
+            for x in 0..self.width {
+                let index = (x + z * self.width) as usize;
+                let column = &self.columns[index];
+                for (i, span) in column.iter().enumerate() {
+                    if !span.walkable {
+                        continue;
+                    }
+
+                    let floor = span.max;
+                    let top = ceiling(column, i);
+                    let mut lowest_drop = i32::MAX;
+                    let (mut accessible_min, mut accessible_max) = (floor, floor);
+                    for (dx, dz) in DIRECTIONS {
+                        let Some(neighbor_index) =
+                            column_index(self.width, self.depth, x + dx, z + dz)
+                        else {
+                            lowest_drop = lowest_drop.min(-config.walkable_climb - 1);
+                            continue;
+                        };
+
+                        let neighbor = &self.columns[neighbor_index];
+
+                        // The space below the lowest span of the neighbor column.
+                        let neighbor_top = neighbor.first().map_or(i32::MAX, |s| s.min);
+                        if top.min(neighbor_top) - floor.max(-config.walkable_climb)
+                            > config.walkable_height
+                        {
+                            lowest_drop = lowest_drop.min(-config.walkable_climb - floor);
+                        }
+
+                        for (k, neighbor_span) in neighbor.iter().enumerate() {
+                            let neighbor_floor = neighbor_span.max;
+                            let neighbor_top = ceiling(neighbor, k);
+                            if top.min(neighbor_top) - floor.max(neighbor_floor)
+                                > config.walkable_height
+                            {
+                                lowest_drop = lowest_drop.min(neighbor_floor - floor);
+                                if (neighbor_floor - floor).abs() <= config.walkable_climb {
+                                    accessible_min = accessible_min.min(neighbor_floor);
+                                    accessible_max = accessible_max.max(neighbor_floor);
+                                }
+                            }
+                        }
+                    }
+
+                    if lowest_drop < -config.walkable_climb
+                        || accessible_max - accessible_min > config.walkable_climb
+                    {
+                        ledges.push((index, i));
+                    }
+                }
+            }
+        }
+
+        for (index, i) in ledges {
+            self.columns[index][i].walkable = false;
+        }
+    }
+
+    // Makes spans with not enough free space above them non-walkable.
+    fn filter_low_height_spans(&mut self, config: &Config) {
+        for column in self.columns.iter_mut() {
+            for i in 0..column.len() {
+                if ceiling(column, i).saturating_sub(column[i].max) < config.walkable_height {
+                    column[i].walkable = false;
+                }
+            }
+        }
+    }
+
+    fn triangulate(&self, contours: &[Vec<ContourVertex>], config: &Config) -> Navmesh {
+        let mut vertices = Vec::new();
+        let mut triangles = Vec::new();
+
+        // Contours of adjacent regions share their vertices. The vertices must be welded,
+        // otherwise the triangles of the regions will not be connected.
+        let mut welded_vertices = FxHashMap::<(i32, i32), Vec<(i32, u32)>>::default();
+
+        for contour in contours {
+            let mut indices = Vec::with_capacity(contour.len());
+            let mut points = Vec::with_capacity(contour.len());
+            for vertex in contour {
+                let candidates = welded_vertices.entry((vertex.x, vertex.z)).or_default();
+                let index = match candidates
+                    .iter()
+                    .find(|(y, _)| (y - vertex.y).abs() <= config.walkable_climb)
+                {
+                    Some((_, index)) => *index,
+                    None => {
+                        let index = vertices.len() as u32;
+                        vertices.push(Vector3::new(
+                            self.origin.x + vertex.x as f32 * self.cell_size,
+                            self.origin.y + vertex.y as f32 * self.cell_height,
+                            self.origin.z + vertex.z as f32 * self.cell_size,
+                        ));
+                        candidates.push((vertex.y, index));
+                        index
+                    }
+                };
+
+                if indices.last() != Some(&index) {
+                    indices.push(index);
+                    points.push((vertex.x as i64, vertex.z as i64));
+                }
+            }
+            if indices.len() > 1 && indices.first() == indices.last() {
+                indices.pop();
+                points.pop();
+            }
+
+            triangles.extend(
+                triangulate_polygon(&points)
+                    .into_iter()
+                    .map(|[a, b, c]| TriangleDefinition([indices[a], indices[b], indices[c]])),
+            );
+        }
+
+        Navmesh::new(triangles, vertices)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct CompactSpan {
+    x: i32,
+    z: i32,
+    floor: i32,
+    ceiling: i32,
+    walkable: bool,
+    region: u32,
+    // Indices of the connected neighbor spans in the order of `DIRECTIONS`.
+    connections: [Option<usize>; 4],
+}
+
+// A heightfield that stores the open space above the walkable spans, instead of solid spans.
+struct CompactHeightfield {
+    width: i32,
+    depth: i32,
+    columns: Vec<Range<usize>>,
+    spans: Vec<CompactSpan>,
+}
+
+impl CompactHeightfield {
+    fn new(heightfield: &Heightfield, config: &Config) -> Self {
+        let mut columns = Vec::with_capacity(heightfield.columns.len());
+        let mut spans = Vec::new();
+        for z in 0..heightfield.depth {
+            for x in 0..heightfield.width {
+                let column = &heightfield.columns[(x + z * heightfield.width) as usize];
+                let start = spans.len();
+                for (i, span) in column.iter().enumerate() {
+                    if span.walkable {
+                        spans.push(CompactSpan {
+                            x,
+                            z,
+                            floor: span.max,
+                            ceiling: ceiling(column, i),
+                            walkable: true,
+                            region: 0,
+                            connections: [None; 4],
+                        });
+                    }
+                }
+                columns.push(start..spans.len());
+            }
+        }
+
+        for i in 0..spans.len() {
+            for (dir, (dx, dz)) in DIRECTIONS.into_iter().enumerate() {
+                let span = &spans[i];
+                let Some(neighbor_column) = column_index(
+                    heightfield.width,
+                    heightfield.depth,
+                    span.x + dx,
+                    span.z + dz,
+                ) else {
+                    continue;
+                };
+
+                spans[i].connections[dir] = columns[neighbor_column].clone().find(|&k| {
+                    let neighbor = &spans[k];
+                    let bottom = span.floor.max(neighbor.floor);
+                    let top = span.ceiling.min(neighbor.ceiling);
+                    top - bottom >= config.walkable_height
+                        && (neighbor.floor - span.floor).abs() <= config.walkable_climb
+                });
+            }
+        }
+
+        Self {
+            width: heightfield.width,
+            depth: heightfield.depth,
+            columns,
+            spans,
+        }
+    }
+
+    fn remove_spans(&mut self, removed: &[bool]) {
+        for (span, removed) in self.spans.iter_mut().zip(removed) {
+            if *removed {
+                span.walkable = false;
+                span.connections = [None; 4];
+            }
+        }
+        for span in self.spans.iter_mut() {
+            for connection in span.connections.iter_mut() {
+                if connection.is_some_and(|n| removed[n]) {
+                    *connection = None;
+                }
+            }
+        }
+    }
+
+    // Shrinks walkable area by the given radius (in cells).
+    fn erode(&mut self, radius: i32) {
+        if radius <= 0 {
+            return;
+        }
+
+        let mut distances = vec![u32::MAX; self.spans.len()];
+        let mut queue = VecDeque::new();
+        for (i, span) in self.spans.iter().enumerate() {
+            if span.walkable && span.connections.iter().any(Option::is_none) {
+                distances[i] = 0;
+                queue.push_back(i);
+            }
+        }
+
+        while let Some(i) = queue.pop_front() {
+            let distance = distances[i] + 1;
+            for &neighbor in self.spans[i].connections.iter().flatten() {
+                if distances[neighbor] > distance {
+                    distances[neighbor] = distance;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        let removed = distances
+            .iter()
+            .map(|distance| *distance < radius as u32)
+            .collect::<Vec<_>>();
+        self.remove_spans(&removed);
+    }
+
+    fn remove_small_islands(&mut self, min_area: usize) {
+        let mut visited = vec![false; self.spans.len()];
+        let mut removed = vec![false; self.spans.len()];
+        let mut island = Vec::new();
+        for start in 0..self.spans.len() {
+            if visited[start] || !self.spans[start].walkable {
+                continue;
+            }
+
+            island.clear();
+            island.push(start);
+            visited[start] = true;
+            let mut i = 0;
+            while let Some(&current) = island.get(i) {
+                for &neighbor in self.spans[current].connections.iter().flatten() {
+                    if !visited[neighbor] {
+                        visited[neighbor] = true;
+                        island.push(neighbor);
+                    }
+                }
+                i += 1;
+            }
+
+            if island.len() < min_area {
+                for &span in island.iter() {
+                    removed[span] = true;
+                }
+            }
+        }
+        self.remove_spans(&removed);
+    }
+
+    // Splits walkable area into monotone regions. Every region consists of a single run of spans
+    // per row, which guarantees that regions do not have holes and could be outlined by a single
+    // contour.
+    fn build_regions(&mut self) {
+        const SEVERAL_NEIGHBORS: u32 = u32::MAX;
+
+        #[derive(Copy, Clone, Default)]
+        struct Sweep {
+            id: u32,
+            neighbor: u32,
+            connections: u32,
+        }
+
+        let mut next_region = 1;
+        let mut neighbor_connections = Vec::new();
+        let mut sweeps = Vec::new();
+        for z in 0..self.depth {
+            neighbor_connections.clear();
+            neighbor_connections.resize(next_region as usize, 0u32);
+            // Sweep indices start from 1, zero means "no region".
+            sweeps.clear();
+            sweeps.push(Sweep::default());
+
+            let row = self.columns[(z * self.width) as usize].start
+                ..self.columns[((z + 1) * self.width - 1) as usize].end;
+            for i in row.clone() {
+                let span = &self.spans[i];
+                if !span.walkable {
+                    continue;
+                }
+
+                // Continue the run of the previous span in the row, if any.
+                let mut sweep = span.connections[0].map_or(0, |n| self.spans[n].region as usize);
+                if sweep == 0 {
+                    sweeps.push(Sweep::default());
+                    sweep = sweeps.len() - 1;
+                }
+
+                // Regions of the previous row are already final.
+                if let Some(neighbor) = span.connections[3] {
+                    let region = self.spans[neighbor].region;
+                    if region != 0 {
+                        let sweep = &mut sweeps[sweep];
+                        if sweep.neighbor == 0 || sweep.neighbor == region {
+                            sweep.neighbor = region;
+                            sweep.connections += 1;
+                            neighbor_connections[region as usize] += 1;
+                        } else {
+                            sweep.neighbor = SEVERAL_NEIGHBORS;
+                        }
+                    }
+                }
+
+                self.spans[i].region = sweep as u32;
+            }
+
+            // A run continues a region of the previous row only if the region is connected to
+            // this run only.
+            for sweep in sweeps.iter_mut().skip(1) {
+                if sweep.neighbor != 0
+                    && sweep.neighbor != SEVERAL_NEIGHBORS
+                    && neighbor_connections[sweep.neighbor as usize] == sweep.connections
+                {
+                    sweep.id = sweep.neighbor;
+                } else {
+                    sweep.id = next_region;
+                    next_region += 1;
+                }
+            }
+
+            for i in row {
+                let span = &mut self.spans[i];
+                if span.walkable {
+                    span.region = sweeps[span.region as usize].id;
+                }
+            }
+        }
+    }
+
+    fn neighbor_region(&self, span: &CompactSpan, dir: usize) -> u32 {
+        span.connections[dir].map_or(0, |n| self.spans[n].region)
+    }
+
+    // Returns the height of the corner of the span, that is located at the end of its edge in
+    // the given direction.
+    fn corner_height(&self, span: &CompactSpan, dir: usize) -> i32 {
+        let next_dir = (dir + 1) % 4;
+        let mut height = span.floor;
+        for (first, second) in [(dir, next_dir), (next_dir, dir)] {
+            if let Some(a) = span.connections[first] {
+                let a = &self.spans[a];
+                height = height.max(a.floor);
+                if let Some(b) = a.connections[second] {
+                    height = height.max(self.spans[b].floor);
+                }
+            }
+        }
+        height
+    }
+
+    fn build_contours(&self, max_error: f32) -> Vec<Vec<ContourVertex>> {
+        // A set bit means that the edge of a span in the respective direction is a border of its
+        // region.
+        let mut borders = self
+            .spans
+            .iter()
+            .map(|span| {
+                if span.region == 0 {
+                    return 0u8;
+                }
+                (0..4).fold(0, |flags, dir| {
+                    if self.neighbor_region(span, dir) != span.region {
+                        flags | (1 << dir)
+                    } else {
+                        flags
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut contours = Vec::new();
+        for i in 0..self.spans.len() {
+            if borders[i] != 0 {
+                let raw = self.walk_contour(i, &mut borders);
+                let simplified = simplify_contour(&raw, max_error);
+                if simplified.len() >= 3 {
+                    contours.push(simplified);
+                }
+            }
+        }
+        contours
+    }
+
+    fn walk_contour(&self, start: usize, borders: &mut [u8]) -> Vec<ContourVertex> {
+        let start_dir = (0..4).find(|dir| borders[start] & (1 << dir) != 0).unwrap();
+        let mut i = start;
+        let mut dir = start_dir;
+        let mut contour = Vec::new();
+        // Safety limit to prevent infinite loops on malformed data.
+        for _ in 0..self.spans.len() * 8 {
+            let span = &self.spans[i];
+            if borders[i] & (1 << dir) != 0 {
+                let (mut x, mut z) = (span.x, span.z);
+                match dir {
+                    0 => z += 1,
+                    1 => {
+                        x += 1;
+                        z += 1
+                    }
+                    2 => x += 1,
+                    _ => (),
+                }
+                contour.push(ContourVertex {
+                    x,
+                    y: self.corner_height(span, dir),
+                    z,
+                    neighbor: self.neighbor_region(span, dir),
+                });
+                borders[i] &= !(1 << dir);
+                // Rotate clockwise.
+                dir = (dir + 1) % 4;
+            } else {
+                let Some(next) = span.connections[dir] else {
+                    break;
+                };
+                i = next;
+                // Rotate counterclockwise.
+                dir = (dir + 3) % 4;
+            }
+
+            if i == start && dir == start_dir {
+                break;
+            }
+        }
+        contour
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct ContourVertex {
+    x: i32,
+    y: i32,
+    z: i32,
+    // Region on the other side of the edge, that ends at this vertex.
+    neighbor: u32,
+}
+
+fn distance_sqr_to_segment(p: &ContourVertex, a: &ContourVertex, b: &ContourVertex) -> f32 {
+    let (px, pz) = ((p.x - a.x) as f32, (p.z - a.z) as f32);
+    let (dx, dz) = ((b.x - a.x) as f32, (b.z - a.z) as f32);
+    let length_sqr = dx * dx + dz * dz;
+    let t = if length_sqr > 0.0 {
+        ((px * dx + pz * dz) / length_sqr).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let (ex, ez) = (px - t * dx, pz - t * dz);
+    ex * ex + ez * ez
+}
+
+fn simplify_contour(raw: &[ContourVertex], max_error: f32) -> Vec<ContourVertex> {
+    let count = raw.len();
+    if count < 3 {
+        return Vec::new();
+    }
+
+    // Indices of the raw vertices, that form the simplified contour. Vertices, where the
+    // neighbor region changes, are always kept, so adjacent regions will share them.
+    let mut simplified = (0..count)
+        .filter(|&i| raw[i].neighbor != raw[(i + 1) % count].neighbor)
+        .collect::<Vec<_>>();
+
+    if simplified.is_empty() {
+        // Isolated region, start from the lower-left and upper-right vertices.
+        let lower_left = (0..count).min_by_key(|&i| (raw[i].x, raw[i].z)).unwrap();
+        let upper_right = (0..count).max_by_key(|&i| (raw[i].x, raw[i].z)).unwrap();
+        simplified.push(lower_left.min(upper_right));
+        simplified.push(lower_left.max(upper_right));
+    }
+
+    // Add the vertices, that deviate from the simplified edges too much. Only the edges along
+    // walls are refined, edges between regions are kept straight, so they match on both sides.
+    let max_error_sqr = max_error * max_error;
+    let mut i = 0;
+    while i < simplified.len() {
+        let a = simplified[i];
+        let b = simplified[(i + 1) % simplified.len()];
+
+        // Traverse the edge in lexicographic order, so it is simplified the same way regardless
+        // of its direction.
+        let (va, vb) = (&raw[a], &raw[b]);
+        let (mut current, step, end, va, vb) = if (vb.x, vb.z) > (va.x, va.z) {
+            ((a + 1) % count, 1, b, va, vb)
+        } else {
+            ((b + count - 1) % count, count - 1, a, vb, va)
+        };
+
+        let mut max_distance = 0.0;
+        let mut max_index = None;
+        if raw[current].neighbor == 0 {
+            while current != end {
+                let distance = distance_sqr_to_segment(&raw[current], va, vb);
+                if distance > max_distance {
+                    max_distance = distance;
+                    max_index = Some(current);
+                }
+                current = (current + step) % count;
+            }
+        }
+
+        match max_index {
+            Some(index) if max_distance > max_error_sqr => simplified.insert(i + 1, index),
+            _ => i += 1,
+        }
+    }
+
+    let mut result = simplified.into_iter().map(|i| raw[i]).collect::<Vec<_>>();
+    result.dedup_by(|a, b| a.x == b.x && a.z == b.z);
+    while result.len() > 1 {
+        let (first, last) = (result[0], result[result.len() - 1]);
+        if first.x == last.x && first.z == last.z {
+            result.pop();
+        } else {
+            break;
+        }
+    }
+    result
+}
+
+// Doubled signed area of a triangle projected on XZ plane. Positive for the triangles, whose
+// normal is facing up.
+fn area2(a: (i64, i64), b: (i64, i64), c: (i64, i64)) -> i64 {
+    (b.1 - a.1) * (c.0 - a.0) - (b.0 - a.0) * (c.1 - a.1)
+}
+
+fn is_ear(points: &[(i64, i64)], polygon: &[usize], i: usize) -> bool {
+    let count = polygon.len();
+    let (a, b, c) = (
+        polygon[(i + count - 1) % count],
+        polygon[i],
+        polygon[(i + 1) % count],
+    );
+    let (pa, pb, pc) = (points[a], points[b], points[c]);
+    if area2(pa, pb, pc) <= 0 {
+        return false;
+    }
+
+    polygon.iter().all(|&k| {
+        let p = points[k];
+        k == a
+            || k == b
+            || k == c
+            || p == pa
+            || p == pb
+            || p == pc
+            || !(area2(pa, pb, p) >= 0 && area2(pb, pc, p) >= 0 && area2(pc, pa, p) >= 0)
+    })
+}
+
+// Triangulates a simple polygon using ear clipping. Returns triangles with indices of the points.
+fn triangulate_polygon(points: &[(i64, i64)]) -> Vec<[usize; 3]> {
+    let mut polygon = (0..points.len()).collect::<Vec<_>>();
+    let signed_area = (1..points.len().saturating_sub(1))
+        .map(|i| area2(points[0], points[i], points[i + 1]))
+        .sum::<i64>();
+    if signed_area < 0 {
+        polygon.reverse();
+    }
+
+    let mut triangles = Vec::with_capacity(points.len().saturating_sub(2));
+    while polygon.len() > 3 {
+        let count = polygon.len();
+        if let Some(i) = (0..count).find(|&i| is_ear(points, &polygon, i)) {
+            triangles.push([
+                polygon[(i + count - 1) % count],
+                polygon[i],
+                polygon[(i + 1) % count],
+            ]);
+            polygon.remove(i);
+        } else if let Some(i) = (0..count).find(|&i| {
+            // Degenerate polygon, try to get rid of collinear vertices first.
+            area2(
+                points[polygon[(i + count - 1) % count]],
+                points[polygon[i]],
+                points[polygon[(i + 1) % count]],
+            ) == 0
+        }) {
+            polygon.remove(i);
+        } else {
+            break;
+        }
+    }
+
+    if let [a, b, c] = polygon[..] {
+        if area2(points[a], points[b], points[c]) > 0 {
+            triangles.push([a, b, c]);
+        }
+    }
+
+    triangles
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Vector2, Vector3},
+            math::TriangleDefinition,
+        },
+        scene::{
+            base::BaseBuilder,
+            collider::{ColliderBuilder, ColliderShape},
+            graph::Graph,
+            navmesh::NavigationalMeshBuilder,
+            transform::TransformBuilder,
+        },
+        utils::{
+            astar::PathKind,
+            navmesh::{
+                generator::{NavmeshGenerationSettings, NavmeshGenerator, NavmeshGeometry},
+                Navmesh,
+            },
+        },
+    };
+
+    fn add_box(geometry: &mut NavmeshGeometry, min: Vector3<f32>, max: Vector3<f32>) {
+        let vertices = (0..8).map(|i| {
+            Vector3::new(
+                if i & 1 == 0 { min.x } else { max.x },
+                if i & 2 == 0 { min.y } else { max.y },
+                if i & 4 == 0 { min.z } else { max.z },
+            )
+        });
+        let triangles = [
+            [0, 1, 3],
+            [0, 3, 2],
+            [4, 6, 7],
+            [4, 7, 5],
+            [0, 4, 5],
+            [0, 5, 1],
+            [2, 3, 7],
+            [2, 7, 6],
+            [0, 2, 6],
+            [0, 6, 4],
+            [1, 5, 7],
+            [1, 7, 3],
+        ]
+        .map(TriangleDefinition);
+        geometry.add_triangles(vertices, triangles);
+    }
+
+    fn find_path(navmesh: &Navmesh, from: Vector3<f32>, to: Vector3<f32>) -> PathKind {
+        let (_, from) = navmesh.query_closest(from).unwrap();
+        let (_, to) = navmesh.query_closest(to).unwrap();
+        let mut path = Vec::new();
+        navmesh.build_path(from, to, &mut path).unwrap()
+    }
+
+    #[test]
+    fn test_generate_around_obstacle() {
+        let mut geometry = NavmeshGeometry::default();
+        // Floor.
+        add_box(
+            &mut geometry,
+            Vector3::new(-5.0, -0.1, -5.0),
+            Vector3::new(5.0, 0.0, 5.0),
+        );
+        // Obstacle, that is too high to climb.
+        add_box(
+            &mut geometry,
+            Vector3::new(-1.0, 0.0, -1.0),
+            Vector3::new(1.0, 1.5, 1.0),
+        );
+
+        let settings = NavmeshGenerationSettings::default();
+        let navmesh = NavmeshGenerator::new(settings, geometry).generate();
+
+        assert!(!navmesh.triangles().is_empty());
+        for vertex in navmesh.vertices() {
+            // Every vertex must lie either on the floor or on top of the obstacle, with respect
+            // to the voxel size.
+            assert!(
+                vertex.y.abs() <= settings.cell_height * 2.0
+                    || (vertex.y - 1.5).abs() <= settings.cell_height * 2.0,
+                "{:?}",
+                vertex
+            );
+            // Walkable area is shrunk by the radius of agents.
+            assert!(vertex.x.abs() <= 5.0 - settings.agent_radius + settings.cell_size);
+            assert!(vertex.z.abs() <= 5.0 - settings.agent_radius + settings.cell_size);
+        }
+        let mut has_top = false;
+        for triangle in navmesh.triangles() {
+            let center = triangle
+                .0
+                .iter()
+                .map(|i| navmesh.vertices()[*i as usize])
+                .sum::<Vector3<f32>>()
+                .scale(1.0 / 3.0);
+            if center.y > 1.0 {
+                has_top = true;
+            } else {
+                assert!(center.x.abs() > 1.0 || center.z.abs() > 1.0, "{:?}", center);
+            }
+        }
+        // The top of the obstacle is walkable too, but it is not reachable from the floor.
+        assert!(has_top);
+        assert_eq!(
+            find_path(
+                &navmesh,
+                Vector3::new(-4.0, 0.0, -4.0),
+                Vector3::new(0.0, 1.5, 0.0)
+            ),
+            PathKind::Partial
+        );
+
+        // The entire floor around the obstacle must be connected.
+        assert_eq!(
+            find_path(
+                &navmesh,
+                Vector3::new(-4.0, 0.0, -4.0),
+                Vector3::new(4.0, 0.0, 4.0)
+            ),
+            PathKind::Full
+        );
+        assert_eq!(
+            find_path(
+                &navmesh,
+                Vector3::new(0.0, 0.0, -4.0),
+                Vector3::new(0.0, 0.0, 4.0)
+            ),
+            PathKind::Full
+        );
+    }
+
+    #[test]
+    fn test_generate_from_graph() {
+        let mut graph = Graph::new();
+        let floor = ColliderBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(10.0, -0.1, 0.0))
+                    .build(),
+            ),
+        )
+        .with_shape(ColliderShape::cuboid(4.0, 0.1, 4.0))
+        .build(&mut graph);
+        // Not a source of the navmesh.
+        ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(1.0, 0.1, 1.0))
+            .build(&mut graph);
+        let navmesh = NavigationalMeshBuilder::new(BaseBuilder::new())
+            .with_generation_sources(vec![floor])
+            .build(&mut graph);
+        graph.update(Vector2::new(800.0, 600.0), 0.0, Default::default());
+
+        let generator = graph[navmesh].as_navigational_mesh().make_generator(&graph);
+        assert!(!generator.geometry().is_empty());
+        let generated = generator.generate();
+        assert!(!generated.triangles().is_empty());
+        assert!(generated.vertices().iter().all(|v| v.x > 6.0 && v.x < 14.0));
+    }
+}
@@ -0,0 +1,195 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Off-mesh links are connections between two arbitrary points of a navmesh, that could be used
+//! by agents to jump over gaps, climb ladders, use teleporters, etc. See [`NavmeshLink`] docs for
+//! more info.
+
+use crate::core::{algebra::Vector3, reflect::prelude::*, visitor::prelude::*};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// Kind of an off-mesh link. The kind does not affect pathfinding, it is used to select the way
+/// an agent traverses the link (animation, movement, etc.).
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+pub enum NavmeshLinkKind {
+    /// Agent jumps from the beginning of the link to its end.
+    #[default]
+    Jump,
+    /// Agent climbs a ladder from the beginning of the link to its end.
+    Ladder,
+    /// Agent is moved from the beginning of the link to its end instantly.
+    Teleport,
+    /// Game-specific kind of the link.
+    Custom(u32),
+}
+
+/// Off-mesh link connects two points of a navmesh, that are not connected by navmesh triangles.
+/// Such points could be on different "islands" of the navmesh (for example, roofs of two houses)
+/// or on the same island (a shortcut). Every end of the link is attached to the closest triangle
+/// of the navmesh.
+///
+/// Agents follow links the same way as any other part of their paths, but the way an agent moves
+/// along a link could be defined by the game. See [`super::NavmeshAgent::update_with_link_handler`]
+/// for more info.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+#[visit(optional)]
+pub struct NavmeshLink {
+    /// Beginning of the link.
+    pub begin: Vector3<f32>,
+    /// End of the link.
+    pub end: Vector3<f32>,
+    /// If `true`, then the link could be traversed in both directions, otherwise it could be
+    /// traversed from the beginning to the end only.
+    pub bidirectional: bool,
+    /// Area type of the link. It is used to calculate the traversal cost of the link and to exclude
+    /// the link from pathfinding using [`super::area::NavmeshAreaFilter`].
+    pub area: u8,
+    /// Kind of the link.
+    pub kind: NavmeshLinkKind,
+}
+
+impl NavmeshLink {
+    /// Creates new unidirectional link of the given kind.
+    pub fn new(begin: Vector3<f32>, end: Vector3<f32>, kind: NavmeshLinkKind) -> Self {
+        Self {
+            begin,
+            end,
+            bidirectional: false,
+            area: super::area::DEFAULT_AREA,
+            kind,
+        }
+    }
+
+    /// Makes the link bidirectional.
+    pub fn with_bidirectional(mut self, bidirectional: bool) -> Self {
+        self.bidirectional = bidirectional;
+        self
+    }
+
+    /// Sets area type of the link.
+    pub fn with_area(mut self, area: u8) -> Self {
+        self.area = area;
+        self
+    }
+}
+
+/// State of a link traversal performed by an agent.
+#[derive(Clone, Debug, Default, PartialEq, Visit)]
+#[visit(optional)]
+pub struct NavmeshLinkTraversal {
+    pub(super) link_index: usize,
+    pub(super) link: NavmeshLink,
+    pub(super) begin: Vector3<f32>,
+    pub(super) end: Vector3<f32>,
+    pub(super) position: Vector3<f32>,
+    pub(super) elapsed: f32,
+}
+
+impl NavmeshLinkTraversal {
+    pub(super) fn new(link_index: usize, link: NavmeshLink, reversed: bool) -> Self {
+        let (begin, end) = if reversed {
+            (link.end, link.begin)
+        } else {
+            (link.begin, link.end)
+        };
+        Self {
+            link_index,
+            link,
+            begin,
+            end,
+            position: begin,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Returns index of the link in the navmesh.
+    pub fn link_index(&self) -> usize {
+        self.link_index
+    }
+
+    /// Returns a reference to the link being traversed.
+    pub fn link(&self) -> &NavmeshLink {
+        &self.link
+    }
+
+    /// Returns the point from which the traversal started. It could be the end of the link if a
+    /// bidirectional link is traversed in reverse direction.
+    pub fn begin(&self) -> Vector3<f32> {
+        self.begin
+    }
+
+    /// Returns the point at which the traversal will end.
+    pub fn end(&self) -> Vector3<f32> {
+        self.end
+    }
+
+    /// Returns current position of the agent on the link.
+    pub fn position(&self) -> Vector3<f32> {
+        self.position
+    }
+
+    /// Sets current position of the agent on the link. The agent will be moved to this position.
+    pub fn set_position(&mut self, position: Vector3<f32>) {
+        self.position = position;
+    }
+
+    /// Returns the time (in seconds) passed since the beginning of the traversal.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Returns the progress of the traversal in `[0; 1]` range, based on the current position.
+    pub fn progress(&self) -> f32 {
+        let length = self.begin.metric_distance(&self.end);
+        if length <= f32::EPSILON {
+            1.0
+        } else {
+            (self.position.metric_distance(&self.begin) / length).min(1.0)
+        }
+    }
+
+    /// Moves the agent along the link by the given distance. Teleport links are traversed
+    /// instantly. Returns `true` if the end of the link is reached. This method is used by default
+    /// to traverse links.
+    pub fn advance(&mut self, distance: f32) -> bool {
+        let delta = self.end - self.position;
+        let remaining = delta.norm();
+        if self.link.kind == NavmeshLinkKind::Teleport || remaining <= distance {
+            self.position = self.end;
+            true
+        } else {
+            self.position += delta.scale(distance / remaining);
+            false
+        }
+    }
+}
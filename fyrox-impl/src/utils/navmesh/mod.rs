@@ -30,6 +30,7 @@ use crate::{
         algebra::{Point3, Vector3},
         arrayvec::ArrayVec,
         math::{self, plane::Plane, ray::Ray, PositionProvider, TriangleDefinition, Vector3Ext},
+        pool::{Handle, Pool},
         reflect::prelude::*,
         visitor::{Visit, VisitResult, Visitor},
     },
@@ -39,6 +40,11 @@ use crate::{
     },
     utils::{
         astar::{Graph, GraphVertex, PathError, PathKind, VertexData, VertexDataProvider},
+        navmesh::{
+            area::{NavmeshAreaFilter, DEFAULT_AREA},
+            link::{NavmeshLink, NavmeshLinkTraversal},
+            obstacle::NavmeshObstacle,
+        },
         raw_mesh::{RawMeshBuilder, RawVertex},
     },
 };
@@ -46,6 +52,12 @@ use fxhash::{FxBuildHasher, FxHashMap};
 use fyrox_core::math::octree::{Octree, OctreeNode};
 use std::ops::{Deref, DerefMut};
 
+pub mod area;
+pub mod crowd;
+pub mod generator;
+pub mod link;
+pub mod obstacle;
+
 #[derive(Clone, Debug, Default, Visit)]
 struct Vertex {
     triangle_index: usize,
@@ -81,12 +93,24 @@ pub struct Navmesh {
     octree: Octree,
     triangles: Vec<TriangleDefinition>,
     vertices: Vec<Vector3<f32>>,
+    areas: Vec<u8>,
+    links: Vec<NavmeshLink>,
     graph: Graph<Vertex>,
+    // Maps a pair of triangles connected by an off-mesh link to the index of the link and its
+    // direction (`true` - reversed).
+    link_map: FxHashMap<(usize, usize), (usize, bool)>,
+    obstacles: Pool<NavmeshObstacle>,
+    // Amount of obstacles overlapping each triangle.
+    blocked: Vec<u16>,
+    revision: u64,
 }
 
 impl PartialEq for Navmesh {
     fn eq(&self, other: &Self) -> bool {
-        self.triangles == other.triangles && self.vertices == other.vertices
+        self.triangles == other.triangles
+            && self.vertices == other.vertices
+            && self.areas == other.areas
+            && self.links == other.links
     }
 }
 
@@ -112,6 +136,10 @@ impl Visit for Navmesh {
 
         self.triangles.visit("Triangles", &mut region)?;
 
+        // Optional, for backward compatibility.
+        let _ = self.areas.visit("Areas", &mut region);
+        let _ = self.links.visit("Links", &mut region);
+
         drop(region);
 
         // No need to save octree, we can restore it on load.
@@ -129,10 +157,10 @@ impl Visit for Navmesh {
                 .collect::<Vec<[Vector3<f32>; 3]>>();
 
             self.octree = Octree::new(&raw_triangles, 32);
+            self.areas.resize(self.triangles.len(), DEFAULT_AREA);
         }
 
-        let graph = make_graph(&self.triangles, &self.vertices);
-        self.graph = graph;
+        self.rebuild_graph();
 
         Ok(())
     }
@@ -231,8 +259,7 @@ pub struct NavmeshModificationContext<'a> {
 
 impl<'a> Drop for NavmeshModificationContext<'a> {
     fn drop(&mut self) {
-        let graph = make_graph(&self.navmesh.triangles, &self.navmesh.vertices);
-        self.navmesh.graph = graph;
+        self.navmesh.rebuild_graph();
     }
 }
 
@@ -240,13 +267,21 @@ impl<'a> NavmeshModificationContext<'a> {
     /// Adds the triangle to the navigational mesh and returns its index in the internal array. Vertex indices in
     /// the triangle must be valid!
     pub fn add_triangle(&mut self, triangle: TriangleDefinition) -> u32 {
+        self.add_triangle_with_area(triangle, DEFAULT_AREA)
+    }
+
+    /// Adds the triangle with the given area type to the navigational mesh and returns its index in the
+    /// internal array. Vertex indices in the triangle must be valid!
+    pub fn add_triangle_with_area(&mut self, triangle: TriangleDefinition, area: u8) -> u32 {
         let index = self.navmesh.triangles.len();
         self.navmesh.triangles.push(triangle);
+        self.navmesh.areas.push(area);
         index as u32
     }
 
     /// Removes a triangle at the given index from the navigational mesh.
     pub fn remove_triangle(&mut self, index: usize) -> TriangleDefinition {
+        self.navmesh.areas.remove(index);
         self.navmesh.triangles.remove(index)
     }
 
@@ -361,11 +396,87 @@ impl Navmesh {
             })
             .collect::<Vec<[Vector3<f32>; 3]>>();
 
-        Self {
-            graph: make_graph(&triangles, &vertices),
+        let mut navmesh = Self {
+            areas: vec![DEFAULT_AREA; triangles.len()],
             triangles,
             vertices,
             octree: Octree::new(&raw_triangles, 32),
+            ..Default::default()
+        };
+        navmesh.rebuild_graph();
+        navmesh
+    }
+
+    /// Creates new navigation mesh from given set of triangles, their area types and vertices. The
+    /// amount of area types must be equal to the amount of triangles. See [`area::NavmeshAreaFilter`]
+    /// docs for more info about area types.
+    pub fn with_areas(
+        triangles: Vec<TriangleDefinition>,
+        areas: Vec<u8>,
+        vertices: Vec<Vector3<f32>>,
+    ) -> Self {
+        assert_eq!(triangles.len(), areas.len());
+        let mut navmesh = Self::new(triangles, vertices);
+        navmesh.areas = areas;
+        navmesh
+    }
+
+    fn triangle_vertices(&self, index: usize) -> [Vector3<f32>; 3] {
+        let triangle = &self.triangles[index];
+        [
+            self.vertices[triangle[0] as usize],
+            self.vertices[triangle[1] as usize],
+            self.vertices[triangle[2] as usize],
+        ]
+    }
+
+    // Rebuilds navigation graph, attaches off-mesh links to the triangles and recalculates
+    // blocked triangles.
+    fn rebuild_graph(&mut self) {
+        self.graph = make_graph(&self.triangles, &self.vertices);
+
+        self.link_map.clear();
+        for (link_index, link) in self.links.iter().enumerate() {
+            let (Some((_, begin)), Some((_, end))) =
+                (self.query_closest(link.begin), self.query_closest(link.end))
+            else {
+                continue;
+            };
+
+            let mut directions = vec![(begin, end, false)];
+            if link.bidirectional {
+                directions.push((end, begin, true));
+            }
+
+            for (from, to, reversed) in directions {
+                // Triangles that already share an edge do not need links.
+                if from == to || self.graph.vertices[from].neighbours.contains(&(to as u32)) {
+                    continue;
+                }
+                self.graph.link_unidirect(from, to);
+                self.link_map.insert((from, to), (link_index, reversed));
+            }
+        }
+
+        self.blocked = vec![0; self.triangles.len()];
+        let obstacles = self.obstacles.iter().cloned().collect::<Vec<_>>();
+        for obstacle in obstacles {
+            self.mark_blocked(&obstacle, true);
+        }
+
+        self.revision += 1;
+    }
+
+    fn mark_blocked(&mut self, obstacle: &NavmeshObstacle, blocked: bool) {
+        for index in 0..self.triangles.len() {
+            if obstacle.overlaps_triangle(&self.triangle_vertices(index)) {
+                let counter = &mut self.blocked[index];
+                if blocked {
+                    *counter += 1;
+                } else {
+                    *counter = counter.saturating_sub(1);
+                }
+            }
         }
     }
 
@@ -488,6 +599,96 @@ impl Navmesh {
         closest
     }
 
+    /// Tries to get a projected point on the navmesh, that is closest to the given query point,
+    /// starting the search from the given triangle. The search checks the triangle and its adjacent
+    /// triangles (off-mesh links are ignored) and walks to the adjacent triangle, that contains the
+    /// closest point, until the closest point is found in the current triangle. Returns a tuple
+    /// with the projection point and the triangle index, that contains this projection point, or
+    /// `None` if the search did not settle in a few steps. In this case, [`Self::query_closest`]
+    /// should be used.
+    ///
+    /// ## Complexity
+    ///
+    /// This method has `O(1)` complexity when the query point is close to the given triangle, for
+    /// example, when it is a new position of an agent that was on the triangle during the previous
+    /// frame.
+    pub fn query_closest_near(
+        &self,
+        query_point: Vector3<f32>,
+        triangle: usize,
+    ) -> Option<(Vector3<f32>, usize)> {
+        const MAX_STEPS: usize = 8;
+
+        let mut current = triangle;
+        for _ in 0..MAX_STEPS {
+            let vertex = self.graph.vertices.get(current)?;
+            let adjacent = vertex
+                .neighbours
+                .iter()
+                .map(|neighbour| *neighbour as usize)
+                .filter(|neighbour| !self.link_map.contains_key(&(current, *neighbour)));
+
+            let mut closest = None;
+            let mut closest_distance = f32::MAX;
+            // The current triangle goes first, so it wins if the closest point is on a shared edge.
+            self.query_closest_internal(
+                &mut closest,
+                &mut closest_distance,
+                std::iter::once(current).chain(adjacent),
+                query_point,
+            );
+
+            let (point, triangle) = closest?;
+            if triangle != current {
+                current = triangle;
+                continue;
+            }
+
+            // The closest point is correct if the query point is above (or below) the triangle or
+            // the closest point is on the border of the navmesh. Otherwise, it could be a shared
+            // vertex, and some triangle that shares only this vertex could be closer.
+            return self
+                .is_closest_point_final(query_point, point, current)
+                .then_some((point, current));
+        }
+
+        None
+    }
+
+    fn is_closest_point_final(
+        &self,
+        query_point: Vector3<f32>,
+        closest_point: Vector3<f32>,
+        triangle_index: usize,
+    ) -> bool {
+        let [a, b, c] = self.triangle_vertices(triangle_index);
+        if let Some(plane) = Plane::from_triangle(&a, &b, &c) {
+            if math::is_point_inside_triangle(&plane.project(&query_point), &[a, b, c]) {
+                return true;
+            }
+        }
+
+        let neighbours = &self.graph.vertices[triangle_index].neighbours;
+        self.triangles[triangle_index]
+            .edges()
+            .into_iter()
+            .any(|edge| {
+                let is_border = neighbours.iter().all(|neighbour| {
+                    let neighbour = &self.triangles[*neighbour as usize];
+                    !(neighbour.0.contains(&edge.a) && neighbour.0.contains(&edge.b))
+                });
+                if !is_border {
+                    return false;
+                }
+                let ray = Ray::from_two_points(
+                    self.vertices[edge.a as usize],
+                    self.vertices[edge.b as usize],
+                );
+                let t = ray.project_point(&closest_point).clamp(0.0, 1.0);
+                ray.get_point(t).metric_distance(&closest_point) < 1.0e-4
+            })
+    }
+
     fn query_closest_internal(
         &self,
         closest: &mut Option<(Vector3<f32>, usize)>,
@@ -562,6 +763,141 @@ impl Navmesh {
         &self.octree
     }
 
+    /// Returns area types of the triangles. See [`area::NavmeshAreaFilter`] docs for more info.
+    pub fn areas(&self) -> &[u8] {
+        &self.areas
+    }
+
+    /// Returns area type of the triangle at the given index.
+    pub fn triangle_area(&self, index: usize) -> Option<u8> {
+        self.areas.get(index).cloned()
+    }
+
+    /// Sets area type of the triangle at the given index. See [`area::NavmeshAreaFilter`] docs for
+    /// more info.
+    pub fn set_triangle_area(&mut self, index: usize, area: u8) {
+        if let Some(triangle_area) = self.areas.get_mut(index) {
+            *triangle_area = area;
+            self.revision += 1;
+        }
+    }
+
+    /// Adds new off-mesh link and returns its index. See [`link::NavmeshLink`] docs for more info.
+    pub fn add_link(&mut self, link: NavmeshLink) -> usize {
+        let index = self.links.len();
+        self.links.push(link);
+        self.rebuild_graph();
+        index
+    }
+
+    /// Removes off-mesh link at the given index. Indices of the links after the removed one are
+    /// shifted by one.
+    pub fn remove_link(&mut self, index: usize) -> NavmeshLink {
+        let link = self.links.remove(index);
+        self.rebuild_graph();
+        link
+    }
+
+    /// Returns a reference to the array of off-mesh links.
+    pub fn links(&self) -> &[NavmeshLink] {
+        &self.links
+    }
+
+    /// Adds new temporary obstacle, that blocks every triangle it overlaps. The navmesh is not
+    /// rebuilt, so this method is cheap. See [`obstacle::NavmeshObstacle`] docs for more info.
+    pub fn add_obstacle(&mut self, obstacle: NavmeshObstacle) -> Handle<NavmeshObstacle> {
+        self.mark_blocked(&obstacle, true);
+        self.revision += 1;
+        self.obstacles.spawn(obstacle)
+    }
+
+    /// Removes the obstacle and unblocks the triangles it was overlapping (unless they're overlapped
+    /// by some other obstacles).
+    pub fn remove_obstacle(&mut self, handle: Handle<NavmeshObstacle>) -> Option<NavmeshObstacle> {
+        let obstacle = self.obstacles.try_free(handle)?;
+        self.mark_blocked(&obstacle, false);
+        self.revision += 1;
+        Some(obstacle)
+    }
+
+    /// Replaces the obstacle with the new one, this method should be used to move or resize an
+    /// obstacle.
+    pub fn set_obstacle(&mut self, handle: Handle<NavmeshObstacle>, obstacle: NavmeshObstacle) {
+        if let Some(old_obstacle) = self.obstacles.try_borrow(handle).cloned() {
+            if old_obstacle != obstacle {
+                self.mark_blocked(&old_obstacle, false);
+                self.mark_blocked(&obstacle, true);
+                self.obstacles[handle] = obstacle;
+                self.revision += 1;
+            }
+        }
+    }
+
+    /// Returns a reference to the obstacle with the given handle.
+    pub fn obstacle(&self, handle: Handle<NavmeshObstacle>) -> Option<&NavmeshObstacle> {
+        self.obstacles.try_borrow(handle)
+    }
+
+    /// Returns an iterator over all obstacles and their handles.
+    pub fn obstacles(&self) -> impl Iterator<Item = (Handle<NavmeshObstacle>, &NavmeshObstacle)> {
+        self.obstacles.pair_iter()
+    }
+
+    /// Returns `true` if the triangle at the given index is blocked by at least one obstacle.
+    pub fn is_triangle_blocked(&self, index: usize) -> bool {
+        self.blocked.get(index).is_some_and(|count| *count > 0)
+    }
+
+    /// Returns a number, that changes every time when areas, links or obstacles of the navmesh
+    /// are changed. It could be used to detect that paths built on the navmesh could be invalid.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    // Returns the cost of travel from one triangle to another or `None` if the travel is not
+    // possible.
+    fn travel_cost(&self, filter: &NavmeshAreaFilter, from: usize, to: usize) -> Option<f32> {
+        if self.is_triangle_blocked(to) {
+            return None;
+        }
+
+        let to_area_cost = filter.cost(self.areas.get(to).cloned().unwrap_or(DEFAULT_AREA))?;
+        let from_position = self.graph.vertices[from].position;
+        let to_position = self.graph.vertices[to].position;
+
+        if let Some((link_index, reversed)) = self.link_map.get(&(from, to)) {
+            let link = &self.links[*link_index];
+            let link_cost = filter.cost(link.area)?;
+            let (begin, end) = if *reversed {
+                (link.end, link.begin)
+            } else {
+                (link.begin, link.end)
+            };
+            Some(
+                (begin - from_position).norm_squared()
+                    + (end - begin).norm_squared() * link_cost
+                    + (to_position - end).norm_squared() * to_area_cost,
+            )
+        } else {
+            Some((to_position - from_position).norm_squared() * to_area_cost)
+        }
+    }
+
+    fn build_indexed_path(
+        &self,
+        filter: &NavmeshAreaFilter,
+        from: usize,
+        to: usize,
+        path: &mut Vec<usize>,
+    ) -> Result<PathKind, PathError> {
+        self.graph.build_indexed_path_with_cost(
+            from,
+            to,
+            |from, to| self.travel_cost(filter, from, to),
+            path,
+        )
+    }
+
     /// Tries to build path using indices of begin and end points.
     ///
     /// Example:
@@ -586,7 +922,30 @@ impl Navmesh {
         to: usize,
         path: &mut Vec<Vector3<f32>>,
     ) -> Result<PathKind, PathError> {
-        self.graph.build_positional_path(from, to, path)
+        self.build_path_filtered(from, to, &NavmeshAreaFilter::default(), path)
+    }
+
+    /// Tries to build path using indices of begin and end points, the same as [`Self::build_path`],
+    /// but uses the given area filter to calculate traversal costs of the triangles. Path goes
+    /// through the centers of the triangles, use [`NavmeshAgent`] to get a smooth path.
+    pub fn build_path_filtered(
+        &self,
+        from: usize,
+        to: usize,
+        filter: &NavmeshAreaFilter,
+        path: &mut Vec<Vector3<f32>>,
+    ) -> Result<PathKind, PathError> {
+        path.clear();
+
+        let mut indices = Vec::new();
+        let path_kind = self.build_indexed_path(filter, from, to, &mut indices)?;
+        path.extend(
+            indices
+                .iter()
+                .map(|index| self.graph.vertices[*index].position),
+        );
+
+        Ok(path_kind)
     }
 
     /// Tries to pick a triangle by given ray. Returns closest result.
@@ -649,8 +1008,33 @@ impl Navmesh {
     }
 }
 
+// An off-mesh link in a path of an agent.
+#[derive(Visit, Clone, Debug, Default, PartialEq)]
+#[visit(optional)]
+struct PathLink {
+    // Index of the path point from which the link starts.
+    point: u32,
+    link_index: u32,
+    // Copy of the link, it is used to detect changes of the links in the navmesh.
+    link: NavmeshLink,
+    reversed: bool,
+}
+
 /// Navmesh agent is a "pathfinding unit" that performs navigation on a mesh. It is designed to
 /// cover most of simple use cases when you need to build and follow some path from point A to point B.
+///
+/// ## Areas and off-mesh links
+///
+/// Every agent has its own area filter (see [`area::NavmeshAreaFilter`]), that defines which parts
+/// of the navmesh are preferred by the agent. Paths of the agent could contain off-mesh links
+/// (see [`link::NavmeshLink`]). By default, the agent moves along a link in a straight line with
+/// its normal speed (or instantly, if the link is a teleport), use [`Self::update_with_link_handler`]
+/// to control the traversal.
+///
+/// ## Obstacles
+///
+/// The path is recalculated automatically, when any of its triangles is blocked by an obstacle
+/// (see [`obstacle::NavmeshObstacle`]).
 #[derive(Visit, Clone, Debug)]
 #[visit(optional)]
 pub struct NavmeshAgent {
@@ -665,6 +1049,13 @@ pub struct NavmeshAgent {
     path_dirty: bool,
     radius: f32,
     interpolator: f32,
+    filter: NavmeshAreaFilter,
+    path_links: Vec<PathLink>,
+    link_traversal: Option<NavmeshLinkTraversal>,
+    #[visit(skip)]
+    path_triangles: Vec<usize>,
+    #[visit(skip)]
+    navmesh_revision: u64,
 }
 
 impl Default for NavmeshAgent {
@@ -688,6 +1079,11 @@ impl NavmeshAgent {
             path_dirty: true,
             radius: 0.2,
             interpolator: 0.0,
+            filter: Default::default(),
+            path_links: Default::default(),
+            link_traversal: None,
+            path_triangles: Default::default(),
+            navmesh_revision: 0,
         }
     }
 
@@ -736,6 +1132,28 @@ impl NavmeshAgent {
     pub fn radius(&self) -> f32 {
         self.radius
     }
+
+    /// Sets new area filter of the agent. The path will be recalculated on the next update. See
+    /// [`area::NavmeshAreaFilter`] docs for more info.
+    pub fn set_filter(&mut self, filter: NavmeshAreaFilter) {
+        self.filter = filter;
+        self.path_dirty = true;
+    }
+
+    /// Returns current area filter of the agent.
+    pub fn filter(&self) -> &NavmeshAreaFilter {
+        &self.filter
+    }
+
+    /// Returns current state of the off-mesh link traversal, if the agent is traversing a link.
+    pub fn link_traversal(&self) -> Option<&NavmeshLinkTraversal> {
+        self.link_traversal.as_ref()
+    }
+
+    /// Returns `true` if the agent is traversing an off-mesh link, `false` - otherwise.
+    pub fn is_traversing_link(&self) -> bool {
+        self.link_traversal.is_some()
+    }
 }
 
 impl NavmeshAgent {
@@ -748,9 +1166,12 @@ impl NavmeshAgent {
         dest_point: Vector3<f32>,
     ) -> Result<PathKind, PathError> {
         self.path.clear();
+        self.path_links.clear();
+        self.path_triangles.clear();
 
         self.current = 0;
         self.interpolator = 0.0;
+        self.navmesh_revision = navmesh.revision;
 
         if let Some((src_point_on_navmesh, src_triangle)) = navmesh.query_closest(src_point) {
             if let Some((dest_point_on_navmesh, dest_triangle)) = navmesh.query_closest(dest_point)
@@ -758,12 +1179,14 @@ impl NavmeshAgent {
                 if src_triangle == dest_triangle {
                     self.path.push(src_point_on_navmesh);
                     self.path.push(dest_point_on_navmesh);
+                    self.path_triangles.push(src_triangle);
 
                     return Ok(PathKind::Full);
                 }
 
                 let mut path_triangle_indices = Vec::new();
-                let path_kind = navmesh.graph.build_indexed_path(
+                let path_kind = navmesh.build_indexed_path(
+                    &self.filter,
                     src_triangle,
                     dest_triangle,
                     &mut path_triangle_indices,
@@ -771,13 +1194,51 @@ impl NavmeshAgent {
 
                 path_triangle_indices.reverse();
 
+                // Off-mesh links split the path into a set of segments, each segment is straightened
+                // separately.
+                let mut segment_begin = 0;
+                let mut segment_src_position = src_point_on_navmesh;
+                for i in 0..path_triangle_indices.len().saturating_sub(1) {
+                    let Some(&(link_index, reversed)) = navmesh
+                        .link_map
+                        .get(&(path_triangle_indices[i], path_triangle_indices[i + 1]))
+                    else {
+                        continue;
+                    };
+
+                    let link = &navmesh.links[link_index];
+                    let (begin, end) = if reversed {
+                        (link.end, link.begin)
+                    } else {
+                        (link.begin, link.end)
+                    };
+
+                    self.straighten_path(
+                        navmesh,
+                        segment_src_position,
+                        begin,
+                        &path_triangle_indices[segment_begin..=i],
+                    );
+                    self.path_links.push(PathLink {
+                        point: (self.path.len() - 1) as u32,
+                        link_index: link_index as u32,
+                        link: link.clone(),
+                        reversed,
+                    });
+
+                    segment_begin = i + 1;
+                    segment_src_position = end;
+                }
+
                 self.straighten_path(
                     navmesh,
-                    src_point_on_navmesh,
+                    segment_src_position,
                     dest_point_on_navmesh,
-                    &path_triangle_indices,
+                    &path_triangle_indices[segment_begin..],
                 );
 
+                self.path_triangles = path_triangle_indices;
+
                 return Ok(path_kind);
             }
         }
@@ -867,14 +1328,130 @@ impl NavmeshAgent {
         self.path.push(dest_position);
     }
 
+    // Marks the path as dirty, if it is no longer valid because of the changes in the navmesh.
+    fn check_navmesh_changes(&mut self, navmesh: &Navmesh) {
+        if self.navmesh_revision == navmesh.revision {
+            return;
+        }
+
+        self.navmesh_revision = navmesh.revision;
+
+        if self
+            .path_triangles
+            .iter()
+            .any(|triangle| navmesh.is_triangle_blocked(*triangle))
+            || self.path_links.iter().any(|path_link| {
+                navmesh.links.get(path_link.link_index as usize) != Some(&path_link.link)
+            })
+        {
+            self.path_dirty = true;
+        }
+    }
+
+    // Starts traversal of an off-mesh link if the current path segment is a link.
+    fn try_begin_link_traversal(&mut self) {
+        if self.link_traversal.is_some() {
+            return;
+        }
+
+        if let Some(path_link) = self
+            .path_links
+            .iter()
+            .find(|path_link| path_link.point == self.current)
+        {
+            self.link_traversal = Some(NavmeshLinkTraversal::new(
+                path_link.link_index as usize,
+                path_link.link.clone(),
+                path_link.reversed,
+            ));
+        }
+    }
+
+    // Advances current link traversal using the given handler. Returns `true` if the agent is
+    // still traversing the link.
+    fn update_link_traversal<F>(&mut self, dt: f32, handler: &mut F) -> bool
+    where
+        F: FnMut(&mut NavmeshLinkTraversal) -> bool,
+    {
+        let Some(traversal) = self.link_traversal.as_mut() else {
+            return false;
+        };
+
+        traversal.elapsed += dt;
+        if handler(traversal) {
+            self.position = traversal.end;
+            self.link_traversal = None;
+            self.current += 1;
+            self.interpolator = 0.0;
+            false
+        } else {
+            self.position = traversal.position;
+            true
+        }
+    }
+
     /// Performs single update tick that moves agent to the target along the path (which is automatically
     /// recalculated if target's position has changed).
     pub fn update(&mut self, dt: f32, navmesh: &Navmesh) -> Result<PathKind, PathError> {
+        let speed = self.speed;
+        self.update_with_link_handler(dt, navmesh, |traversal| traversal.advance(speed * dt))
+    }
+
+    /// Performs single update tick that moves agent to the target along the path, the same as
+    /// [`Self::update`], but allows you to control the traversal of off-mesh links. The handler is
+    /// called every update while the agent is on a link, it should move the agent (using
+    /// [`NavmeshLinkTraversal::set_position`] or [`NavmeshLinkTraversal::advance`]) and return `true`
+    /// when the traversal is finished. This is the place to play jump or climb animations, for example.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use fyrox_impl::utils::navmesh::{link::NavmeshLinkKind, Navmesh, NavmeshAgent};
+    /// fn update_agent(agent: &mut NavmeshAgent, navmesh: &Navmesh, dt: f32) {
+    ///     let speed = agent.speed();
+    ///     let _ = agent.update_with_link_handler(dt, navmesh, |traversal| {
+    ///         match traversal.link().kind {
+    ///             // Climb ladders slowly.
+    ///             NavmeshLinkKind::Ladder => traversal.advance(0.25 * speed * dt),
+    ///             // Jump by a parabola in 0.5 seconds.
+    ///             NavmeshLinkKind::Jump => {
+    ///                 let t = (traversal.elapsed() / 0.5).min(1.0);
+    ///                 let mut position = traversal.begin().lerp(&traversal.end(), t);
+    ///                 position.y += 4.0 * t * (1.0 - t);
+    ///                 traversal.set_position(position);
+    ///                 t >= 1.0
+    ///             }
+    ///             _ => traversal.advance(speed * dt),
+    ///         }
+    ///     });
+    /// }
+    /// ```
+    pub fn update_with_link_handler<F>(
+        &mut self,
+        dt: f32,
+        navmesh: &Navmesh,
+        mut handler: F,
+    ) -> Result<PathKind, PathError>
+    where
+        F: FnMut(&mut NavmeshLinkTraversal) -> bool,
+    {
+        // Path recalculation is postponed until the agent leaves the link.
+        if self.update_link_traversal(dt, &mut handler) {
+            return Ok(PathKind::Full);
+        }
+
+        self.check_navmesh_changes(navmesh);
+
         if self.path_dirty {
             self.calculate_path(navmesh, self.position, self.target)?;
             self.path_dirty = false;
         }
 
+        self.try_begin_link_traversal();
+        if self.update_link_traversal(dt, &mut handler) {
+            return Ok(PathKind::Full);
+        }
+
         if let Some(source) = self.path.get(self.current as usize) {
             if let Some(destination) = self.path.get((self.current + 1) as usize) {
                 let len = destination.metric_distance(source);
@@ -934,6 +1511,7 @@ pub struct NavmeshAgentBuilder {
     target: Vector3<f32>,
     recalculation_threshold: f32,
     speed: f32,
+    filter: NavmeshAreaFilter,
 }
 
 impl Default for NavmeshAgentBuilder {
@@ -950,6 +1528,7 @@ impl NavmeshAgentBuilder {
             target: Default::default(),
             recalculation_threshold: 0.25,
             speed: 1.5,
+            filter: Default::default(),
         }
     }
 
@@ -977,6 +1556,12 @@ impl NavmeshAgentBuilder {
         self
     }
 
+    /// Sets new desired area filter of the agent being built.
+    pub fn with_filter(mut self, filter: NavmeshAreaFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
     /// Build the agent.
     pub fn build(self) -> NavmeshAgent {
         NavmeshAgent {
@@ -986,6 +1571,7 @@ impl NavmeshAgentBuilder {
             last_target_position: self.target,
             recalculation_threshold: self.recalculation_threshold,
             speed: self.speed,
+            filter: self.filter,
             ..Default::default()
         }
     }
@@ -995,9 +1581,250 @@ impl NavmeshAgentBuilder {
 mod test {
     use crate::{
         core::{algebra::Vector3, math::TriangleDefinition},
-        utils::navmesh::{Navmesh, NavmeshAgent},
+        utils::{
+            astar::PathKind,
+            navmesh::{
+                area::NavmeshAreaFilter,
+                link::{NavmeshLink, NavmeshLinkKind},
+                obstacle::NavmeshObstacle,
+                Navmesh, NavmeshAgent, NavmeshAgentBuilder,
+            },
+        },
     };
 
+    // Adds a grid of 1x1 quads with the given size, starting at the given origin.
+    fn add_grid(
+        origin: Vector3<f32>,
+        width: u32,
+        height: u32,
+        triangles: &mut Vec<TriangleDefinition>,
+        vertices: &mut Vec<Vector3<f32>>,
+    ) {
+        let first = vertices.len() as u32;
+        for z in 0..=height {
+            for x in 0..=width {
+                vertices.push(origin + Vector3::new(x as f32, 0.0, z as f32));
+            }
+        }
+        let index = |x: u32, z: u32| first + z * (width + 1) + x;
+        for z in 0..height {
+            for x in 0..width {
+                let a = index(x, z);
+                let b = index(x, z + 1);
+                let c = index(x + 1, z);
+                let d = index(x + 1, z + 1);
+                triangles.push(TriangleDefinition([a, b, c]));
+                triangles.push(TriangleDefinition([b, d, c]));
+            }
+        }
+    }
+
+    fn make_grid(width: u32, height: u32) -> Navmesh {
+        let mut triangles = Vec::new();
+        let mut vertices = Vec::new();
+        add_grid(
+            Vector3::default(),
+            width,
+            height,
+            &mut triangles,
+            &mut vertices,
+        );
+        Navmesh::new(triangles, vertices)
+    }
+
+    fn triangle_center(navmesh: &Navmesh, index: usize) -> Vector3<f32> {
+        let [a, b, c] = navmesh.triangle_vertices(index);
+        (a + b + c).scale(1.0 / 3.0)
+    }
+
+    #[test]
+    fn test_query_closest_near() {
+        let navmesh = make_grid(10, 10);
+
+        let start = navmesh
+            .query_closest(Vector3::new(0.2, 0.0, 0.2))
+            .unwrap()
+            .1;
+        // Nearby points are found by walking over adjacent triangles.
+        for point in [
+            Vector3::new(0.3, 0.0, 0.4),
+            Vector3::new(1.6, 0.0, 0.3),
+            Vector3::new(-0.5, 0.0, 0.5),
+            Vector3::new(2.2, 1.0, 1.7),
+        ] {
+            let near = navmesh.query_closest_near(point, start).unwrap();
+            let closest = navmesh.query_closest(point).unwrap();
+            assert!((near.0 - closest.0).norm() < 1.0e-5, "{point}");
+        }
+        // Far points need the global search.
+        assert!(navmesh
+            .query_closest_near(Vector3::new(9.5, 0.0, 9.5), start)
+            .is_none());
+    }
+
+    #[test]
+    fn test_area_costs() {
+        const WATER: u8 = 1;
+
+        let mut navmesh = make_grid(5, 3);
+        // The middle row is water.
+        for index in 0..navmesh.triangles().len() {
+            let center = triangle_center(&navmesh, index);
+            if center.z > 1.0 && center.z < 2.0 && center.x > 1.0 && center.x < 4.0 {
+                navmesh.set_triangle_area(index, WATER);
+            }
+        }
+
+        let from = navmesh
+            .query_closest(Vector3::new(0.5, 0.0, 1.5))
+            .unwrap()
+            .1;
+        let to = navmesh
+            .query_closest(Vector3::new(4.5, 0.0, 1.5))
+            .unwrap()
+            .1;
+        let water_count = |path: &[Vector3<f32>]| {
+            path.iter()
+                .filter(|p| p.z > 1.0 && p.z < 2.0 && p.x > 1.0 && p.x < 4.0)
+                .count()
+        };
+
+        let mut path = Vec::new();
+        assert_eq!(
+            navmesh.build_path(from, to, &mut path).unwrap(),
+            PathKind::Full
+        );
+        assert!(water_count(&path) > 0);
+
+        let mut filter = NavmeshAreaFilter::default();
+        filter.set_area_cost(WATER, 100.0);
+        assert_eq!(
+            navmesh
+                .build_path_filtered(from, to, &filter, &mut path)
+                .unwrap(),
+            PathKind::Full
+        );
+        assert_eq!(water_count(&path), 0);
+
+        let mut filter = NavmeshAreaFilter::default();
+        filter.set_area_enabled(WATER, false);
+        assert!(!filter.is_area_enabled(WATER));
+        assert_eq!(
+            navmesh
+                .build_path_filtered(from, to, &filter, &mut path)
+                .unwrap(),
+            PathKind::Full
+        );
+        assert_eq!(water_count(&path), 0);
+    }
+
+    #[test]
+    fn test_off_mesh_link() {
+        let mut triangles = Vec::new();
+        let mut vertices = Vec::new();
+        add_grid(Vector3::default(), 2, 2, &mut triangles, &mut vertices);
+        add_grid(
+            Vector3::new(4.0, 1.0, 0.0),
+            2,
+            2,
+            &mut triangles,
+            &mut vertices,
+        );
+        let mut navmesh = Navmesh::new(triangles, vertices);
+
+        let begin = Vector3::new(1.8, 0.0, 1.0);
+        let end = Vector3::new(4.2, 1.0, 1.0);
+        navmesh.add_link(NavmeshLink::new(begin, end, NavmeshLinkKind::Jump));
+
+        let target = Vector3::new(5.5, 1.0, 1.5);
+        let mut agent = NavmeshAgentBuilder::new()
+            .with_position(Vector3::new(0.5, 0.0, 0.5))
+            .with_target(target)
+            .with_speed(2.0)
+            .build();
+
+        let mut traversed = false;
+        for _ in 0..300 {
+            agent
+                .update_with_link_handler(1.0 / 60.0, &navmesh, |traversal| {
+                    traversed = true;
+                    assert_eq!(traversal.begin(), begin);
+                    assert_eq!(traversal.end(), end);
+                    traversal.advance(0.1)
+                })
+                .unwrap();
+        }
+
+        assert!(traversed);
+        assert_eq!(agent.path_links.len(), 1);
+        assert!(agent.position().metric_distance(&target) < 0.1);
+
+        // The link is unidirectional, so there is no way back.
+        assert_eq!(
+            agent
+                .calculate_path(&navmesh, target, Vector3::new(0.5, 0.0, 0.5))
+                .unwrap(),
+            PathKind::Partial
+        );
+
+        navmesh.remove_link(0);
+        assert_eq!(
+            agent
+                .calculate_path(&navmesh, Vector3::new(0.5, 0.0, 0.5), target)
+                .unwrap(),
+            PathKind::Partial
+        );
+    }
+
+    #[test]
+    fn test_obstacles() {
+        let mut navmesh = make_grid(3, 3);
+
+        let mut agent = NavmeshAgentBuilder::new()
+            .with_position(Vector3::new(0.5, 0.0, 1.5))
+            .with_target(Vector3::new(2.5, 0.0, 1.5))
+            .build();
+        agent.update(0.0, &navmesh).unwrap();
+        assert!(agent.path.iter().all(|p| p.z == 1.5));
+
+        let obstacle = navmesh.add_obstacle(NavmeshObstacle::cylinder(
+            Vector3::new(1.5, 0.0, 1.5),
+            0.3,
+            2.0,
+        ));
+        let blocked = (0..navmesh.triangles().len())
+            .filter(|i| navmesh.is_triangle_blocked(*i))
+            .collect::<Vec<_>>();
+        assert_eq!(blocked.len(), 2);
+        for index in blocked.iter() {
+            let center = triangle_center(&navmesh, *index);
+            assert!(center.x > 1.0 && center.x < 2.0 && center.z > 1.0 && center.z < 2.0);
+        }
+
+        // The path must be recalculated to go around the obstacle.
+        agent.update(0.0, &navmesh).unwrap();
+        assert!(agent.path.iter().any(|p| p.z != 1.5));
+        assert!(agent
+            .path_triangles
+            .iter()
+            .all(|triangle| !blocked.contains(triangle)));
+
+        navmesh.set_obstacle(
+            obstacle,
+            NavmeshObstacle::cuboid(Vector3::new(0.5, 0.5, 0.5), Vector3::new(0.4, 0.5, 0.4)),
+        );
+        assert!(blocked.iter().all(|i| !navmesh.is_triangle_blocked(*i)));
+        assert_eq!(
+            (0..navmesh.triangles().len())
+                .filter(|i| navmesh.is_triangle_blocked(*i))
+                .count(),
+            2
+        );
+
+        assert!(navmesh.remove_obstacle(obstacle).is_some());
+        assert!((0..navmesh.triangles().len()).all(|i| !navmesh.is_triangle_blocked(i)));
+    }
+
     #[test]
     fn test_navmesh() {
         let navmesh = Navmesh::new(
@@ -0,0 +1,182 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Temporary obstacles, that block navmesh triangles without rebuilding the navmesh. See
+//! [`NavmeshObstacle`] docs for more info.
+
+use crate::core::algebra::{Vector2, Vector3};
+
+/// Shape of a navmesh obstacle.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum NavmeshObstacleShape {
+    /// Vertical cylinder, the position of the obstacle defines the center of its bottom.
+    Cylinder {
+        /// Radius of the cylinder.
+        radius: f32,
+        /// Height of the cylinder.
+        height: f32,
+    },
+    /// Axis-aligned box, the position of the obstacle defines the center of the box.
+    Box {
+        /// Half-size of the box along each axis.
+        half_extents: Vector3<f32>,
+    },
+}
+
+impl Default for NavmeshObstacleShape {
+    fn default() -> Self {
+        Self::Cylinder {
+            radius: 0.5,
+            height: 2.0,
+        }
+    }
+}
+
+/// Temporary obstacle is an object (a door, a crate, a vehicle, etc.), that blocks navmesh
+/// triangles it overlaps. Blocked triangles are excluded from pathfinding until the obstacle is
+/// removed or moved away. Adding, moving and removing obstacles is cheap, because the navmesh is
+/// not rebuilt - only the state of the overlapped triangles is changed. Obstacles block whole
+/// triangles, so the navmesh should be dense enough around places where obstacles are expected.
+///
+/// Obstacles are temporary, they're not serialized together with the navmesh.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct NavmeshObstacle {
+    /// Position of the obstacle. See [`NavmeshObstacleShape`] for more info.
+    pub position: Vector3<f32>,
+    /// Shape of the obstacle.
+    pub shape: NavmeshObstacleShape,
+}
+
+fn horizontal(v: Vector3<f32>) -> Vector2<f32> {
+    Vector2::new(v.x, v.z)
+}
+
+fn cross(a: Vector2<f32>, b: Vector2<f32>) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+fn is_point_inside_triangle(p: Vector2<f32>, triangle: &[Vector2<f32>; 3]) -> bool {
+    let [a, b, c] = *triangle;
+    let d1 = cross(b - a, p - a);
+    let d2 = cross(c - b, p - b);
+    let d3 = cross(a - c, p - c);
+    let has_negative = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let has_positive = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    !(has_negative && has_positive)
+}
+
+fn distance_to_segment(p: Vector2<f32>, a: Vector2<f32>, b: Vector2<f32>) -> f32 {
+    let ab = b - a;
+    let length_sqr = ab.norm_squared();
+    let t = if length_sqr > f32::EPSILON {
+        ((p - a).dot(&ab) / length_sqr).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (a + ab.scale(t) - p).norm()
+}
+
+// Separating axis test of a triangle and an axis-aligned rectangle. Touching shapes are not
+// considered overlapping.
+fn triangle_overlaps_rect(
+    triangle: &[Vector2<f32>; 3],
+    min: Vector2<f32>,
+    max: Vector2<f32>,
+) -> bool {
+    for axis in 0..2 {
+        let tri_min = triangle.iter().map(|v| v[axis]).fold(f32::MAX, f32::min);
+        let tri_max = triangle.iter().map(|v| v[axis]).fold(f32::MIN, f32::max);
+        if tri_min >= max[axis] || tri_max <= min[axis] {
+            return false;
+        }
+    }
+
+    let corners = [
+        min,
+        Vector2::new(max.x, min.y),
+        max,
+        Vector2::new(min.x, max.y),
+    ];
+    for i in 0..3 {
+        let edge = triangle[(i + 1) % 3] - triangle[i];
+        let normal = Vector2::new(-edge.y, edge.x);
+        let project = |p: &Vector2<f32>| normal.dot(p);
+        let tri_min = triangle.iter().map(project).fold(f32::MAX, f32::min);
+        let tri_max = triangle.iter().map(project).fold(f32::MIN, f32::max);
+        let rect_min = corners.iter().map(project).fold(f32::MAX, f32::min);
+        let rect_max = corners.iter().map(project).fold(f32::MIN, f32::max);
+        if tri_min >= rect_max || tri_max <= rect_min {
+            return false;
+        }
+    }
+
+    true
+}
+
+impl NavmeshObstacle {
+    /// Creates new cylindrical obstacle.
+    pub fn cylinder(position: Vector3<f32>, radius: f32, height: f32) -> Self {
+        Self {
+            position,
+            shape: NavmeshObstacleShape::Cylinder { radius, height },
+        }
+    }
+
+    /// Creates new box obstacle.
+    pub fn cuboid(position: Vector3<f32>, half_extents: Vector3<f32>) -> Self {
+        Self {
+            position,
+            shape: NavmeshObstacleShape::Box { half_extents },
+        }
+    }
+
+    /// Returns `true` if the obstacle overlaps the given triangle.
+    pub fn overlaps_triangle(&self, triangle: &[Vector3<f32>; 3]) -> bool {
+        let (min_y, max_y) = match self.shape {
+            NavmeshObstacleShape::Cylinder { height, .. } => {
+                (self.position.y, self.position.y + height)
+            }
+            NavmeshObstacleShape::Box { half_extents } => (
+                self.position.y - half_extents.y,
+                self.position.y + half_extents.y,
+            ),
+        };
+        let tri_min_y = triangle.iter().map(|v| v.y).fold(f32::MAX, f32::min);
+        let tri_max_y = triangle.iter().map(|v| v.y).fold(f32::MIN, f32::max);
+        if tri_min_y > max_y || tri_max_y < min_y {
+            return false;
+        }
+
+        let triangle = triangle.map(horizontal);
+        let center = horizontal(self.position);
+        match self.shape {
+            NavmeshObstacleShape::Cylinder { radius, .. } => {
+                is_point_inside_triangle(center, &triangle)
+                    || (0..3).any(|i| {
+                        distance_to_segment(center, triangle[i], triangle[(i + 1) % 3]) < radius
+                    })
+            }
+            NavmeshObstacleShape::Box { half_extents } => {
+                let half_extents = horizontal(half_extents);
+                triangle_overlaps_rect(&triangle, center - half_extents, center + half_extents)
+            }
+        }
+    }
+}
@@ -31,6 +31,7 @@ use crate::{
         log::Log,
         make_relative_path, notify,
         parking_lot::{Mutex, MutexGuard},
+        profiler::{profile_future, Profiler},
         task::TaskPool,
         watcher::FileSystemWatcher,
         TypeUuidProvider,
@@ -751,7 +752,12 @@ impl ResourceManagerState {
         let event_broadcaster = self.event_broadcaster.clone();
         let loader_future = loader.load(path.clone(), self.resource_io.clone());
         self.task_pool.spawn_task(async move {
-            match loader_future.await {
+            let result = if Profiler::is_enabled() {
+                profile_future(format!("Load {}", path.display()), loader_future).await
+            } else {
+                loader_future.await
+            };
+            match result {
                 Ok(data) => {
                     let data = data.0;
 
@@ -246,6 +246,17 @@ pub enum ButtonContent {
         /// Font size of the text. Default is 14.0
         size: f32,
     },
+    /// A shortcut to create a [crate::text::Text] widget with a localization key as the button content. The
+    /// text will be updated automatically when current locale changes. See [`crate::localization::Localization`]
+    /// docs for more info.
+    LocalizedText {
+        /// Localization key of the text.
+        key: String,
+        /// Optional font of the button. If [`None`], the default font will be used.
+        font: Option<FontResource>,
+        /// Font size of the text. Default is 14.0
+        size: f32,
+    },
     /// Arbitrary widget handle. It could be any widget handle, for example a handle of [`crate::image::Image`]
     /// widget.
     Node(Handle<UiNode>),
@@ -279,6 +290,15 @@ impl ButtonContent {
         }
     }
 
+    /// Creates [`ButtonContent::LocalizedText`] with default font.
+    pub fn localized_text<S: AsRef<str>>(key: S) -> Self {
+        Self::LocalizedText {
+            key: key.as_ref().to_owned(),
+            font: None,
+            size: 14.0,
+        }
+    }
+
     /// Creates [`ButtonContent::Node`].
     pub fn node(node: Handle<UiNode>) -> Self {
         Self::Node(node)
@@ -293,6 +313,13 @@ impl ButtonContent {
                 .with_font(font.clone().unwrap_or_else(|| ctx.default_font()))
                 .with_font_size(*size)
                 .build(ctx),
+            Self::LocalizedText { key, font, size } => TextBuilder::new(WidgetBuilder::new())
+                .with_localization_key(key)
+                .with_horizontal_text_alignment(HorizontalAlignment::Center)
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .with_font(font.clone().unwrap_or_else(|| ctx.default_font()))
+                .with_font_size(*size)
+                .build(ctx),
             Self::Node(node) => *node,
         }
     }
@@ -337,6 +364,12 @@ impl ButtonBuilder {
         self
     }
 
+    /// Sets the content of the button to be [`ButtonContent::LocalizedText`] (localized text with the default font).
+    pub fn with_localized_text(mut self, key: &str) -> Self {
+        self.content = Some(ButtonContent::localized_text(key));
+        self
+    }
+
     /// Sets the content of the button to be [`ButtonContent::Node`] (arbitrary widget handle).
     pub fn with_content(mut self, node: Handle<UiNode>) -> Self {
         self.content = Some(ButtonContent::Node(node));
@@ -0,0 +1,1090 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Data grid is a widget that shows tabular data with column headers, sorting, resizable and reorderable
+//! columns and row selection. See [`DataGrid`] docs for more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    border::BorderBuilder,
+    check_box::CheckBoxBuilder,
+    core::{
+        pool::Handle, reflect::prelude::*, type_traits::prelude::*, uuid_provider,
+        variable::InheritableVariable, visitor::prelude::*,
+    },
+    decorator::{DecoratorBuilder, DecoratorMessage},
+    define_constructor,
+    grid::{Column, GridBuilder, GridMessage, Row},
+    message::{CursorIcon, MessageDirection, MouseButton, UiMessage},
+    progress_bar::ProgressBarBuilder,
+    scroll_viewer::ScrollViewerBuilder,
+    stack_panel::StackPanelBuilder,
+    text::TextBuilder,
+    utils::{make_arrow, ArrowDirection},
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, HorizontalAlignment, Thickness, UiNode, UserInterface,
+    VerticalAlignment, BRUSH_DARK, BRUSH_DARKER, BRUSH_DARKEST, BRUSH_LIGHT,
+};
+use fyrox_graph::BaseSceneGraph;
+use std::{
+    cmp::Ordering,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    ops::{Deref, DerefMut},
+};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// Min width of a column, it prevents columns from collapsing to nothing while being resized.
+pub const MIN_COLUMN_WIDTH: f32 = 16.0;
+/// Width of the area at the right edge of a column header that could be dragged to resize the column.
+const SPLITTER_WIDTH: f32 = 4.0;
+
+/// Defines how the cells of a column are shown.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+pub enum CellTemplate {
+    /// Shows the value of a cell as is.
+    #[default]
+    Text,
+    /// Shows a check box, that is checked if the value of a cell is `true`, unchecked if it is `false` and
+    /// undefined otherwise.
+    CheckBox,
+    /// Shows a progress bar, the value of a cell is parsed as a number in `[0..1]` range.
+    ProgressBar,
+}
+
+uuid_provider!(CellTemplate = "b6a53a0c-47f4-4f3c-8d32-5b1a0a3f9e27");
+
+/// Describes a single column of [`DataGrid`].
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct DataGridColumn {
+    /// Name of the column, it is shown in the column header.
+    pub name: String,
+    /// Width of the column in local units.
+    pub width: f32,
+    /// A template that defines how the cells of the column are shown.
+    pub template: CellTemplate,
+    /// Defines whether the grid could be sorted by the column or not.
+    pub sortable: bool,
+}
+
+uuid_provider!(DataGridColumn = "0b5e3f71-8c1d-4a5e-9b4f-6d2c7e8a1f30");
+
+impl Default for DataGridColumn {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            width: 100.0,
+            template: Default::default(),
+            sortable: true,
+        }
+    }
+}
+
+impl DataGridColumn {
+    /// Creates a new sortable text column with the given name.
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the desired width of the column.
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = width.max(MIN_COLUMN_WIDTH);
+        self
+    }
+
+    /// Sets the desired cell template of the column.
+    pub fn with_template(mut self, template: CellTemplate) -> Self {
+        self.template = template;
+        self
+    }
+
+    /// Defines whether the grid could be sorted by the column or not.
+    pub fn with_sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+}
+
+/// A single row of [`DataGrid`]. Cells are stored as strings and interpreted according to the
+/// [`CellTemplate`] of their column. Missing cells are treated as empty strings.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Visit, Reflect)]
+pub struct DataGridRow {
+    /// Values of the cells of the row, one per column.
+    pub cells: Vec<String>,
+}
+
+uuid_provider!(DataGridRow = "5f0d2a8e-2c4b-4e61-b7a3-91c8e4d6f2a5");
+
+impl DataGridRow {
+    /// Creates a new row from the given cell values.
+    pub fn new<S: ToString>(cells: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            cells: cells.into_iter().map(|cell| cell.to_string()).collect(),
+        }
+    }
+
+    /// Returns a value of a cell in the given column or an empty string if there's no such cell.
+    pub fn cell(&self, column: usize) -> &str {
+        self.cells
+            .get(column)
+            .map(|cell| cell.as_str())
+            .unwrap_or("")
+    }
+}
+
+/// Direction of sorting.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+pub enum SortDirection {
+    /// Smaller values go first.
+    #[default]
+    Ascending,
+    /// Larger values go first.
+    Descending,
+}
+
+uuid_provider!(SortDirection = "e3c1b7f4-6a2d-4f8e-a5b9-0c7d3e1f2a64");
+
+/// Describes how the rows of [`DataGrid`] are sorted.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Visit, Reflect)]
+pub struct DataGridSort {
+    /// Index of a column by which the rows are sorted.
+    pub column: usize,
+    /// Direction of sorting.
+    pub direction: SortDirection,
+}
+
+uuid_provider!(DataGridSort = "9a7e4c2b-1d5f-4b3a-8e6c-2f0a9d7b5c18");
+
+/// A set of messages that can be accepted by [`DataGrid`] widget.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataGridMessage {
+    /// Used to indicate that the columns of the grid have changed ([`MessageDirection::FromWidget`]) or to set new
+    /// columns (with [`MessageDirection::ToWidget`]).
+    Columns(Vec<DataGridColumn>),
+    /// Used to indicate that the rows of the grid have changed ([`MessageDirection::FromWidget`]) or to set new
+    /// rows (with [`MessageDirection::ToWidget`]). Selected indices that are out of bounds of the new rows are
+    /// removed from the selection.
+    Rows(Vec<DataGridRow>),
+    /// Used to indicate that the sorting of the grid has changed ([`MessageDirection::FromWidget`]) or to sort the
+    /// grid (with [`MessageDirection::ToWidget`]). `None` shows the rows in their original order.
+    Sort(Option<DataGridSort>),
+    /// Used to indicate that the width of a column has changed ([`MessageDirection::FromWidget`]) or to set a new
+    /// width (with [`MessageDirection::ToWidget`]).
+    ColumnWidth {
+        /// Index of the column.
+        column: usize,
+        /// New width of the column.
+        width: f32,
+    },
+    /// Used to indicate that a column was moved ([`MessageDirection::FromWidget`]) or to move a column (with
+    /// [`MessageDirection::ToWidget`]). Cells of every row are moved together with the column.
+    MoveColumn {
+        /// Index of the column to move.
+        from: usize,
+        /// New index of the column.
+        to: usize,
+    },
+    /// Used to indicate that the selection has changed ([`MessageDirection::FromWidget`]) or to set a new
+    /// selection (with [`MessageDirection::ToWidget`]). The selection is a set of indices of rows in
+    /// [`DataGrid::rows`], it does not depend on sorting.
+    Selection(Vec<usize>),
+}
+
+impl DataGridMessage {
+    define_constructor!(
+        /// Creates [`DataGridMessage::Columns`] message.
+        DataGridMessage:Columns => fn columns(Vec<DataGridColumn>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`DataGridMessage::Rows`] message.
+        DataGridMessage:Rows => fn rows(Vec<DataGridRow>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`DataGridMessage::Sort`] message.
+        DataGridMessage:Sort => fn sort(Option<DataGridSort>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`DataGridMessage::ColumnWidth`] message.
+        DataGridMessage:ColumnWidth => fn column_width(column: usize, width: f32), layout: false
+    );
+    define_constructor!(
+        /// Creates [`DataGridMessage::MoveColumn`] message.
+        DataGridMessage:MoveColumn => fn move_column(from: usize, to: usize), layout: false
+    );
+    define_constructor!(
+        /// Creates [`DataGridMessage::Selection`] message.
+        DataGridMessage:Selection => fn selection(Vec<usize>), layout: false
+    );
+}
+
+#[derive(Clone, Debug, Default)]
+struct ColumnResize {
+    column: usize,
+    initial_width: f32,
+    initial_cursor_x: f32,
+}
+
+#[derive(Clone, Debug, Default)]
+struct SyncState {
+    fingerprint: u64,
+    widths: Vec<f32>,
+}
+
+/// Data grid is a widget that shows tabular data: a row of column headers and a list of rows below them. The grid
+/// stores its data (columns, rows, sorting and selection) and generates the views of headers and cells from it,
+/// so it is fully serializable and could be used in `.ui` scenes.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle,
+/// #     data_grid::{CellTemplate, DataGridBuilder, DataGridColumn, DataGridRow},
+/// #     widget::WidgetBuilder,
+/// #     BuildContext, UiNode,
+/// # };
+/// fn create_leaderboard(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     DataGridBuilder::new(WidgetBuilder::new().with_height(200.0))
+///         .with_columns(vec![
+///             DataGridColumn::new("Player").with_width(150.0),
+///             DataGridColumn::new("Score"),
+///             DataGridColumn::new("Online").with_template(CellTemplate::CheckBox),
+///             DataGridColumn::new("Progress").with_template(CellTemplate::ProgressBar),
+///         ])
+///         .with_rows(vec![
+///             DataGridRow::new(["Alice", "1200", "true", "0.75"]),
+///             DataGridRow::new(["Bob", "950", "false", "0.5"]),
+///         ])
+///         .build(ctx)
+/// }
+/// ```
+///
+/// ## Sorting
+///
+/// Clicking on a header of a sortable column sorts the rows by the column, the next click reverses the order.
+/// Cells are compared as numbers, if both of them could be parsed as numbers, and as strings otherwise. Sorting
+/// does not change [`DataGrid::rows`], it only changes the order in which the rows are shown. Use
+/// [`DataGridMessage::Sort`] to sort the grid from code.
+///
+/// ## Columns
+///
+/// Columns could be resized by dragging the right edge of their headers and reordered by dragging their headers
+/// onto other headers. Both actions are reported with [`DataGridMessage::ColumnWidth`] and
+/// [`DataGridMessage::MoveColumn`] messages respectively.
+///
+/// ## Selection
+///
+/// Clicking on a row selects it, `Ctrl+Click` adds a row to the selection or removes it from the selection. To
+/// fetch the new selection, listen to [`DataGridMessage::Selection`] messages:
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle,
+/// #     data_grid::DataGridMessage,
+/// #     message::{MessageDirection, UiMessage},
+/// #     UiNode,
+/// # };
+/// # fn foo(data_grid: Handle<UiNode>, message: &mut UiMessage) {
+/// if message.destination() == data_grid && message.direction() == MessageDirection::FromWidget {
+///     if let Some(DataGridMessage::Selection(selection)) = message.data() {
+///         println!("Selected rows: {:?}", selection);
+///     }
+/// }
+/// # }
+/// ```
+///
+/// ## Performance
+///
+/// The grid creates widgets for every cell, so it is meant for tables with up to a few thousands of rows. Use
+/// virtualized [`crate::list_view::ListView`] for larger data sets.
+#[derive(Default, Clone, Debug, Visit, Reflect, ComponentProvider)]
+pub struct DataGrid {
+    /// Base widget of the data grid.
+    pub widget: Widget,
+    /// Columns of the grid.
+    pub columns: InheritableVariable<Vec<DataGridColumn>>,
+    /// Rows of the grid in their original (unsorted) order.
+    pub rows: InheritableVariable<Vec<DataGridRow>>,
+    /// Current sorting of the grid.
+    pub sort: InheritableVariable<Option<DataGridSort>>,
+    /// Current selection, a set of indices of rows in [`Self::rows`].
+    pub selection: Vec<usize>,
+    /// A handle of the grid widget that contains column headers.
+    pub header: InheritableVariable<Handle<UiNode>>,
+    /// A handle of the panel widget that contains row views.
+    pub rows_panel: InheritableVariable<Handle<UiNode>>,
+    /// Handles of column headers, one per column.
+    pub header_cells: InheritableVariable<Vec<Handle<UiNode>>>,
+    /// Handles of resize areas of column headers, one per column.
+    pub splitters: InheritableVariable<Vec<Handle<UiNode>>>,
+    /// Handles of row views in the order they are shown (i.e. sorted).
+    pub row_views: InheritableVariable<Vec<Handle<UiNode>>>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    resize: Option<ColumnResize>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pressed_header: Option<usize>,
+    /// The state of the data for which the views were generated. It is used to regenerate the views when the
+    /// data is changed directly (for example, in the editor).
+    #[visit(skip)]
+    #[reflect(hidden)]
+    synced: Option<SyncState>,
+}
+
+crate::define_widget_deref!(DataGrid);
+
+uuid_provider!(DataGrid = "c2f8e6a4-3b7d-4e19-9f5a-8d1c0b2e7a43");
+
+fn compare_cells(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        _ => a
+            .to_lowercase()
+            .cmp(&b.to_lowercase())
+            .then_with(|| a.cmp(b)),
+    }
+}
+
+fn sorted_order(rows: &[DataGridRow], sort: Option<DataGridSort>) -> Vec<usize> {
+    let mut order = (0..rows.len()).collect::<Vec<_>>();
+    if let Some(sort) = sort {
+        // Stable sort keeps the original order of equal rows.
+        order.sort_by(|a, b| {
+            let ordering = compare_cells(rows[*a].cell(sort.column), rows[*b].cell(sort.column));
+            match sort.direction {
+                SortDirection::Ascending => ordering,
+                SortDirection::Descending => ordering.reverse(),
+            }
+        });
+    }
+    order
+}
+
+fn make_columns(columns: &[DataGridColumn]) -> Vec<Column> {
+    columns
+        .iter()
+        .map(|column| Column::strict(column.width))
+        .collect()
+}
+
+fn build_header_cell(
+    index: usize,
+    column: &DataGridColumn,
+    sort: Option<DataGridSort>,
+    ctx: &mut BuildContext,
+) -> Handle<UiNode> {
+    let arrow = match sort {
+        Some(sort) if sort.column == index => {
+            let direction = match sort.direction {
+                SortDirection::Ascending => ArrowDirection::Top,
+                SortDirection::Descending => ArrowDirection::Bottom,
+            };
+            let arrow = make_arrow(ctx, direction, 6.0);
+            ctx[arrow].set_column(1);
+            arrow
+        }
+        _ => Handle::NONE,
+    };
+
+    BorderBuilder::new(
+        WidgetBuilder::new()
+            .on_column(index)
+            .with_allow_drag(true)
+            .with_allow_drop(true)
+            .with_background(BRUSH_DARKER)
+            .with_foreground(BRUSH_DARKEST)
+            .with_child(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_hit_test_visibility(false)
+                        .with_child(
+                            TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_margin(Thickness::uniform(3.0))
+                                    .with_vertical_alignment(VerticalAlignment::Center),
+                            )
+                            .with_text(column.name.clone())
+                            .build(ctx),
+                        )
+                        .with_child(arrow),
+                )
+                .add_row(Row::stretch())
+                .add_column(Column::stretch())
+                .add_column(Column::strict(SPLITTER_WIDTH + 10.0))
+                .build(ctx),
+            ),
+    )
+    .with_stroke_thickness(Thickness::uniform(1.0))
+    .build(ctx)
+}
+
+fn build_splitter(index: usize, ctx: &mut BuildContext) -> Handle<UiNode> {
+    BorderBuilder::new(
+        WidgetBuilder::new()
+            .on_column(index)
+            .with_width(SPLITTER_WIDTH)
+            .with_horizontal_alignment(HorizontalAlignment::Right)
+            .with_cursor(Some(CursorIcon::ColResize))
+            .with_background(BRUSH_DARKEST),
+    )
+    .build(ctx)
+}
+
+fn build_cell(
+    index: usize,
+    template: CellTemplate,
+    value: &str,
+    ctx: &mut BuildContext,
+) -> Handle<UiNode> {
+    let widget_builder = WidgetBuilder::new()
+        .on_column(index)
+        .with_margin(Thickness::uniform(2.0))
+        .with_hit_test_visibility(false)
+        .with_vertical_alignment(VerticalAlignment::Center);
+
+    match template {
+        CellTemplate::Text => TextBuilder::new(widget_builder).with_text(value).build(ctx),
+        CellTemplate::CheckBox => CheckBoxBuilder::new(
+            widget_builder.with_horizontal_alignment(HorizontalAlignment::Left),
+        )
+        .checked(value.trim().parse::<bool>().ok())
+        .build(ctx),
+        CellTemplate::ProgressBar => ProgressBarBuilder::new(widget_builder.with_height(14.0))
+            .with_progress(value.trim().parse::<f32>().unwrap_or_default())
+            .build(ctx),
+    }
+}
+
+fn build_row(
+    row: &DataGridRow,
+    columns: &[DataGridColumn],
+    selected: bool,
+    ctx: &mut BuildContext,
+) -> Handle<UiNode> {
+    let cells = columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| build_cell(index, column.template, row.cell(index), ctx))
+        .collect::<Vec<_>>();
+
+    DecoratorBuilder::new(BorderBuilder::new(
+        WidgetBuilder::new().with_child(
+            GridBuilder::new(WidgetBuilder::new().with_children(cells))
+                .add_row(Row::auto())
+                .add_columns(make_columns(columns))
+                .build(ctx),
+        ),
+    ))
+    .with_normal_brush(BRUSH_DARK)
+    .with_hover_brush(BRUSH_LIGHT)
+    .with_selected(selected)
+    .build(ctx)
+}
+
+fn build_header(
+    columns: &[DataGridColumn],
+    sort: Option<DataGridSort>,
+    ctx: &mut BuildContext,
+) -> (Vec<Handle<UiNode>>, Vec<Handle<UiNode>>) {
+    columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            (
+                build_header_cell(index, column, sort, ctx),
+                build_splitter(index, ctx),
+            )
+        })
+        .unzip()
+}
+
+fn build_rows(
+    columns: &[DataGridColumn],
+    rows: &[DataGridRow],
+    sort: Option<DataGridSort>,
+    selection: &[usize],
+    ctx: &mut BuildContext,
+) -> Vec<Handle<UiNode>> {
+    sorted_order(rows, sort)
+        .into_iter()
+        .map(|index| build_row(&rows[index], columns, selection.contains(&index), ctx))
+        .collect()
+}
+
+impl DataGrid {
+    /// Returns indices of the rows (in [`Self::rows`]) in the order they are shown.
+    pub fn display_order(&self) -> Vec<usize> {
+        sorted_order(&self.rows, *self.sort)
+    }
+
+    fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        for column in self.columns.iter() {
+            column.name.hash(&mut hasher);
+            column.template.hash(&mut hasher);
+            column.sortable.hash(&mut hasher);
+        }
+        self.rows.hash(&mut hasher);
+        self.sort.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn widths(&self) -> Vec<f32> {
+        self.columns.iter().map(|column| column.width).collect()
+    }
+
+    fn header_index(&self, handle: Handle<UiNode>, ui: &UserInterface) -> Option<usize> {
+        self.header_cells
+            .iter()
+            .position(|cell| *cell == handle || ui.is_node_child_of(handle, *cell))
+    }
+
+    fn row_view_index(&self, mut handle: Handle<UiNode>, ui: &UserInterface) -> Option<usize> {
+        while let Some(node) = ui.try_get(handle) {
+            if node.parent() == *self.rows_panel {
+                return self.row_views.iter().position(|view| *view == handle);
+            }
+            handle = node.parent();
+        }
+        None
+    }
+
+    fn rebuild(&mut self, ui: &mut UserInterface) {
+        for handle in self
+            .header_cells
+            .iter()
+            .chain(self.splitters.iter())
+            .chain(self.row_views.iter())
+        {
+            ui.send_message(WidgetMessage::remove(*handle, MessageDirection::ToWidget));
+        }
+
+        let ctx = &mut ui.build_ctx();
+        let (header_cells, splitters) = build_header(&self.columns, *self.sort, ctx);
+        let row_views = build_rows(&self.columns, &self.rows, *self.sort, &self.selection, ctx);
+
+        for handle in header_cells.iter().chain(splitters.iter()) {
+            ui.send_message(WidgetMessage::link(
+                *handle,
+                MessageDirection::ToWidget,
+                *self.header,
+            ));
+        }
+        for handle in row_views.iter() {
+            ui.send_message(WidgetMessage::link(
+                *handle,
+                MessageDirection::ToWidget,
+                *self.rows_panel,
+            ));
+        }
+        ui.send_message(GridMessage::columns(
+            *self.header,
+            MessageDirection::ToWidget,
+            make_columns(&self.columns),
+        ));
+
+        self.header_cells.set_value_and_mark_modified(header_cells);
+        self.splitters.set_value_and_mark_modified(splitters);
+        self.row_views.set_value_and_mark_modified(row_views);
+    }
+
+    fn sync_widths(&self, ui: &UserInterface) {
+        let columns = make_columns(&self.columns);
+        ui.send_message(GridMessage::columns(
+            *self.header,
+            MessageDirection::ToWidget,
+            columns.clone(),
+        ));
+        for row_view in self.row_views.iter() {
+            if let Some(row_grid) = ui
+                .try_get(*row_view)
+                .and_then(|row_view| row_view.children().first())
+            {
+                ui.send_message(GridMessage::columns(
+                    *row_grid,
+                    MessageDirection::ToWidget,
+                    columns.clone(),
+                ));
+            }
+        }
+    }
+
+    fn sync_selection(&self, ui: &UserInterface) {
+        for (row_view, index) in self.row_views.iter().zip(self.display_order()) {
+            ui.send_message(DecoratorMessage::select(
+                *row_view,
+                MessageDirection::ToWidget,
+                self.selection.contains(&index),
+            ));
+        }
+    }
+}
+
+impl Control for DataGrid {
+    fn update(&mut self, _dt: f32, ui: &mut UserInterface) {
+        let fingerprint = self.fingerprint();
+        let widths = self.widths();
+        match self.synced {
+            // Views were loaded together with the grid, they're up to date.
+            None => (),
+            Some(ref synced) if synced.fingerprint != fingerprint => self.rebuild(ui),
+            Some(ref synced) if synced.widths != widths => self.sync_widths(ui),
+            _ => return,
+        }
+        self.synced = Some(SyncState {
+            fingerprint,
+            widths,
+        });
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<DataGridMessage>() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    DataGridMessage::Columns(columns) => {
+                        if *self.columns != *columns {
+                            self.columns.set_value_and_mark_modified(columns.clone());
+                            if self.sort.is_some_and(|sort| sort.column >= columns.len()) {
+                                self.sort.set_value_and_mark_modified(None);
+                            }
+                            ui.send_message(message.reverse());
+                        }
+                    }
+                    DataGridMessage::Rows(rows) => {
+                        if *self.rows != *rows {
+                            self.rows.set_value_and_mark_modified(rows.clone());
+                            self.selection.retain(|index| *index < rows.len());
+                            ui.send_message(message.reverse());
+                        }
+                    }
+                    DataGridMessage::Sort(sort) => {
+                        let sort = sort.filter(|sort| sort.column < self.columns.len());
+                        if *self.sort != sort {
+                            self.sort.set_value_and_mark_modified(sort);
+                            ui.send_message(DataGridMessage::sort(
+                                self.handle,
+                                MessageDirection::FromWidget,
+                                sort,
+                            ));
+                        }
+                    }
+                    &DataGridMessage::ColumnWidth { column, width } => {
+                        let width = width.max(MIN_COLUMN_WIDTH);
+                        if self.columns.get(column).is_some_and(|c| c.width != width) {
+                            self.columns.get_value_mut_and_mark_modified()[column].width = width;
+                            self.sync_widths(ui);
+                            ui.send_message(DataGridMessage::column_width(
+                                self.handle,
+                                MessageDirection::FromWidget,
+                                column,
+                                width,
+                            ));
+                        }
+                    }
+                    &DataGridMessage::MoveColumn { from, to } => {
+                        let count = self.columns.len();
+                        if from != to && from < count && to < count {
+                            let column =
+                                self.columns.get_value_mut_and_mark_modified().remove(from);
+                            self.columns
+                                .get_value_mut_and_mark_modified()
+                                .insert(to, column);
+                            for row in self.rows.get_value_mut_and_mark_modified().iter_mut() {
+                                if row.cells.len() < count {
+                                    row.cells.resize(count, Default::default());
+                                }
+                                let cell = row.cells.remove(from);
+                                row.cells.insert(to, cell);
+                            }
+                            if let Some(mut sort) = *self.sort {
+                                // Keep sorting by the same column.
+                                sort.column = if sort.column == from {
+                                    to
+                                } else if from < sort.column && sort.column <= to {
+                                    sort.column - 1
+                                } else if to <= sort.column && sort.column < from {
+                                    sort.column + 1
+                                } else {
+                                    sort.column
+                                };
+                                self.sort.set_value_and_mark_modified(Some(sort));
+                            }
+                            ui.send_message(message.reverse());
+                        }
+                    }
+                    DataGridMessage::Selection(selection) => {
+                        let mut new_selection = Vec::with_capacity(selection.len());
+                        for index in selection.iter() {
+                            if *index < self.rows.len() && !new_selection.contains(index) {
+                                new_selection.push(*index);
+                            }
+                        }
+                        if self.selection != new_selection {
+                            self.selection = new_selection;
+                            self.sync_selection(ui);
+                            ui.send_message(DataGridMessage::selection(
+                                self.handle,
+                                MessageDirection::FromWidget,
+                                self.selection.clone(),
+                            ));
+                        }
+                    }
+                }
+            }
+        } else if let Some(msg) = message.data::<WidgetMessage>() {
+            match msg {
+                WidgetMessage::MouseDown { pos, button } if *button == MouseButton::Left => {
+                    let destination = message.destination();
+                    if let Some(column) = self
+                        .splitters
+                        .iter()
+                        .position(|splitter| *splitter == destination)
+                    {
+                        self.resize = Some(ColumnResize {
+                            column,
+                            initial_width: self.columns[column].width,
+                            initial_cursor_x: pos.x,
+                        });
+                        ui.capture_mouse(self.handle);
+                        message.set_handled(true);
+                    } else if let Some(column) = self.header_index(destination, ui) {
+                        self.pressed_header = Some(column);
+                    } else if let Some(view_index) = self.row_view_index(destination, ui) {
+                        let Some(index) = self.display_order().get(view_index).cloned() else {
+                            return;
+                        };
+                        let selection = if ui.keyboard_modifiers().control {
+                            let mut selection = self.selection.clone();
+                            if let Some(existing) = selection.iter().position(|i| *i == index) {
+                                selection.remove(existing);
+                            } else {
+                                selection.push(index);
+                            }
+                            selection
+                        } else {
+                            vec![index]
+                        };
+                        ui.send_message(DataGridMessage::selection(
+                            self.handle,
+                            MessageDirection::ToWidget,
+                            selection,
+                        ));
+                    }
+                }
+                WidgetMessage::MouseMove { pos, .. } if self.resize.is_some() => {
+                    if let Some(resize) = self.resize.as_ref() {
+                        let scale = self.visual_transform().m11.max(f32::EPSILON);
+                        ui.send_message(DataGridMessage::column_width(
+                            self.handle,
+                            MessageDirection::ToWidget,
+                            resize.column,
+                            resize.initial_width + (pos.x - resize.initial_cursor_x) / scale,
+                        ));
+                    }
+                    message.set_handled(true);
+                }
+                WidgetMessage::MouseUp { .. } if self.resize.is_some() => {
+                    self.resize = None;
+                    ui.release_mouse_capture();
+                    message.set_handled(true);
+                }
+                WidgetMessage::MouseUp { button, .. } if *button == MouseButton::Left => {
+                    if let Some(column) = self.pressed_header.take() {
+                        if self.header_index(message.destination(), ui) == Some(column)
+                            && self.columns.get(column).is_some_and(|c| c.sortable)
+                        {
+                            let direction = match *self.sort {
+                                Some(sort)
+                                    if sort.column == column
+                                        && sort.direction == SortDirection::Ascending =>
+                                {
+                                    SortDirection::Descending
+                                }
+                                _ => SortDirection::Ascending,
+                            };
+                            ui.send_message(DataGridMessage::sort(
+                                self.handle,
+                                MessageDirection::ToWidget,
+                                Some(DataGridSort { column, direction }),
+                            ));
+                        }
+                    }
+                }
+                WidgetMessage::DragStarted(_) => {
+                    // Dragging a header moves the column instead of sorting by it.
+                    self.pressed_header = None;
+                }
+                WidgetMessage::Drop(dragged) => {
+                    if let (Some(from), Some(to)) = (
+                        self.header_index(*dragged, ui),
+                        self.header_index(message.destination(), ui),
+                    ) {
+                        ui.send_message(DataGridMessage::move_column(
+                            self.handle,
+                            MessageDirection::ToWidget,
+                            from,
+                            to,
+                        ));
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Data grid builder is used to create [`DataGrid`] widget instances and add them to the user interface.
+pub struct DataGridBuilder {
+    widget_builder: WidgetBuilder,
+    columns: Vec<DataGridColumn>,
+    rows: Vec<DataGridRow>,
+    sort: Option<DataGridSort>,
+    selection: Vec<usize>,
+}
+
+impl DataGridBuilder {
+    /// Creates new data grid builder instance.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            columns: Default::default(),
+            rows: Default::default(),
+            sort: None,
+            selection: Default::default(),
+        }
+    }
+
+    /// Sets the desired columns.
+    pub fn with_columns(mut self, columns: Vec<DataGridColumn>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Sets the desired rows.
+    pub fn with_rows(mut self, rows: Vec<DataGridRow>) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    /// Sets the desired sorting.
+    pub fn with_sort(mut self, sort: Option<DataGridSort>) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Sets the desired selection.
+    pub fn with_selection(mut self, selection: Vec<usize>) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Creates new data grid instance and adds it to the user interface.
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let sort = self.sort.filter(|sort| sort.column < self.columns.len());
+        let mut selection = self.selection;
+        selection.retain(|index| *index < self.rows.len());
+
+        let (header_cells, splitters) = build_header(&self.columns, sort, ctx);
+        let row_views = build_rows(&self.columns, &self.rows, sort, &selection, ctx);
+
+        let header = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_children(header_cells.iter().chain(splitters.iter()).cloned()),
+        )
+        .add_row(Row::auto())
+        .add_columns(make_columns(&self.columns))
+        .build(ctx);
+
+        let rows_panel =
+            StackPanelBuilder::new(WidgetBuilder::new().with_children(row_views.clone()))
+                .build(ctx);
+
+        let content = ScrollViewerBuilder::new(WidgetBuilder::new())
+            .with_horizontal_scroll_allowed(true)
+            .with_vertical_scroll_allowed(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new().with_child(header).with_child(
+                        ScrollViewerBuilder::new(WidgetBuilder::new().on_row(1))
+                            .with_horizontal_scroll_allowed(false)
+                            .with_vertical_scroll_allowed(true)
+                            .with_content(rows_panel)
+                            .build(ctx),
+                    ),
+                )
+                .add_row(Row::auto())
+                .add_row(Row::stretch())
+                .add_column(Column::auto())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        let mut data_grid = DataGrid {
+            widget: self
+                .widget_builder
+                .with_child(content)
+                .with_need_update(true)
+                .build(),
+            columns: self.columns.into(),
+            rows: self.rows.into(),
+            sort: sort.into(),
+            selection,
+            header: header.into(),
+            rows_panel: rows_panel.into(),
+            header_cells: header_cells.into(),
+            splitters: splitters.into(),
+            row_views: row_views.into(),
+            resize: None,
+            pressed_header: None,
+            synced: None,
+        };
+        data_grid.synced = Some(SyncState {
+            fingerprint: data_grid.fingerprint(),
+            widths: data_grid.widths(),
+        });
+
+        ctx.add_node(UiNode::new(data_grid))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector2,
+        data_grid::{
+            DataGrid, DataGridBuilder, DataGridColumn, DataGridMessage, DataGridRow, DataGridSort,
+            SortDirection,
+        },
+        message::MessageDirection,
+        widget::WidgetBuilder,
+        UserInterface,
+    };
+    use fyrox_graph::BaseSceneGraph;
+
+    fn pump(ui: &mut UserInterface) {
+        for _ in 0..2 {
+            ui.update(Vector2::new(500.0, 500.0), 0.0, &Default::default());
+            while ui.poll_message().is_some() {}
+        }
+    }
+
+    #[test]
+    fn test_data_grid() {
+        let mut ui = UserInterface::new(Vector2::new(500.0, 500.0));
+        let data_grid = DataGridBuilder::new(WidgetBuilder::new())
+            .with_columns(vec![
+                DataGridColumn::new("Name"),
+                DataGridColumn::new("Score"),
+            ])
+            .with_rows(vec![
+                DataGridRow::new(["Bob", "10"]),
+                DataGridRow::new(["alice", "9"]),
+                DataGridRow::new(["Carol", "100"]),
+            ])
+            .build(&mut ui.build_ctx());
+        pump(&mut ui);
+
+        let grid = ui.node(data_grid).query_component::<DataGrid>().unwrap();
+        assert_eq!(grid.display_order(), vec![0, 1, 2]);
+        assert_eq!(grid.row_views.len(), 3);
+
+        // Numbers are compared as numbers, not as strings.
+        ui.send_message(DataGridMessage::sort(
+            data_grid,
+            MessageDirection::ToWidget,
+            Some(DataGridSort {
+                column: 1,
+                direction: SortDirection::Descending,
+            }),
+        ));
+        pump(&mut ui);
+        let grid = ui.node(data_grid).query_component::<DataGrid>().unwrap();
+        assert_eq!(grid.display_order(), vec![2, 0, 1]);
+
+        ui.send_message(DataGridMessage::move_column(
+            data_grid,
+            MessageDirection::ToWidget,
+            1,
+            0,
+        ));
+        ui.send_message(DataGridMessage::selection(
+            data_grid,
+            MessageDirection::ToWidget,
+            vec![1, 5, 1],
+        ));
+        pump(&mut ui);
+        let grid = ui.node(data_grid).query_component::<DataGrid>().unwrap();
+        assert_eq!(grid.columns[0].name, "Score");
+        assert_eq!(grid.rows[0].cells, vec!["10", "Bob"]);
+        assert_eq!(grid.sort.unwrap().column, 0);
+        assert_eq!(grid.selection, vec![1]);
+        assert_eq!(grid.header_cells.len(), 2);
+        assert!(grid
+            .row_views
+            .iter()
+            .chain(grid.header_cells.iter())
+            .all(|handle| ui.try_get(*handle).is_some()));
+
+        ui.send_message(DataGridMessage::column_width(
+            data_grid,
+            MessageDirection::ToWidget,
+            0,
+            1.0,
+        ));
+        pump(&mut ui);
+        let grid = ui.node(data_grid).query_component::<DataGrid>().unwrap();
+        assert_eq!(grid.columns[0].width, super::MIN_COLUMN_WIDTH);
+    }
+}
@@ -33,6 +33,10 @@ use crate::{
     define_constructor,
     draw::DrawingContext,
     message::{MessageDirection, UiMessage},
+    transition::{
+        Easing, PropertyTransition, TransitionPlayer, TransitionStyle, TransitionValue,
+        VisualState, VisualStates,
+    },
     widget::{Widget, WidgetMessage},
     BuildContext, Control, UiNode, UserInterface, BRUSH_BRIGHT, BRUSH_DARKER, BRUSH_LIGHT,
     BRUSH_LIGHTER, BRUSH_LIGHTEST,
@@ -108,6 +112,26 @@ impl DecoratorMessage {
 ///         .build(ctx)
 /// }
 /// ```
+///
+/// ## Transitions
+///
+/// By default, the brushes are switched instantly. Decorator could also use a [`TransitionStyle`] to smoothly
+/// animate any property of its widget (background, foreground, margin, etc.) when it is hovered, pressed, focused or
+/// disabled. If the style has transitions of `background` property, they replace the standard hover and pressed
+/// brushes (even if the decorator is selected), while normal and selected brushes are used as the rest value of the
+/// background. The easiest way to animate the standard brushes is [`DecoratorBuilder::with_brush_transitions`]:
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     border::BorderBuilder, core::pool::Handle, decorator::DecoratorBuilder, transition::Easing,
+/// #     widget::WidgetBuilder, BuildContext, UiNode,
+/// # };
+/// fn create_smooth_decorator(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     DecoratorBuilder::new(BorderBuilder::new(WidgetBuilder::new()))
+///         .with_brush_transitions(0.15, Easing::EaseOut)
+///         .build(ctx)
+/// }
+/// ```
 #[derive(Default, Clone, Visit, Reflect, Debug, ComponentProvider)]
 pub struct Decorator {
     /// Base widget of the decorator.
@@ -125,6 +149,15 @@ pub struct Decorator {
     pub is_selected: InheritableVariable<bool>,
     /// Whether the decorator should react to mouse clicks and switch its state to `Pressed` or not.
     pub is_pressable: InheritableVariable<bool>,
+    /// A style, that defines property transitions of the decorator in various visual states.
+    #[visit(optional)]
+    pub style: InheritableVariable<TransitionStyle>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    visual_states: VisualStates,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    transitions: TransitionPlayer,
 }
 
 impl Deref for Decorator {
@@ -143,6 +176,50 @@ impl DerefMut for Decorator {
 
 uuid_provider!(Decorator = "bb4b60aa-c657-4ed6-8db6-d7f374397c73");
 
+impl Decorator {
+    /// Returns `true` if the background is controlled by the brushes of the decorator, `false` - if it is
+    /// controlled by the transition style.
+    fn uses_brushes(&self) -> bool {
+        !self.style.affects("background")
+    }
+
+    fn rest_brush(&self) -> Brush {
+        if *self.is_selected {
+            (*self.selected_brush).clone()
+        } else {
+            (*self.normal_brush).clone()
+        }
+    }
+
+    fn sync_rest_brush(&mut self) {
+        self.transitions.set_rest_value(
+            "background",
+            TransitionValue::Brush(self.rest_brush()),
+            &mut self.border.widget,
+        );
+    }
+
+    fn set_style_brush(&mut self, state: VisualState, brush: &Brush) {
+        for transition in self
+            .style
+            .get_value_mut_and_mark_modified()
+            .transitions
+            .iter_mut()
+        {
+            if transition.state == state && transition.property == "background" {
+                transition.value = TransitionValue::Brush(brush.clone());
+            }
+        }
+    }
+
+    fn apply_visual_states(&mut self) {
+        let mut states = self.visual_states;
+        states.pressed &= *self.is_pressable;
+        self.transitions
+            .apply_states(&self.style, states, &mut self.border.widget);
+    }
+}
+
 impl Control for Decorator {
     fn measure_override(&self, ui: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
         self.border.measure_override(ui, available_size)
@@ -157,7 +234,16 @@ impl Control for Decorator {
     }
 
     fn update(&mut self, dt: f32, ui: &mut UserInterface) {
-        self.border.update(dt, ui)
+        self.border.update(dt, ui);
+
+        if !self.style.is_empty() {
+            if self.visual_states.sync_disabled(&self.border.widget, ui) {
+                self.apply_visual_states();
+            }
+            if self.transitions.update(dt, &mut self.border.widget) {
+                self.invalidate_layout();
+            }
+        }
     }
 
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
@@ -169,20 +255,22 @@ impl Control for Decorator {
                     if *self.is_selected != value {
                         self.is_selected.set_value_and_mark_modified(value);
 
-                        ui.send_message(WidgetMessage::background(
-                            self.handle(),
-                            MessageDirection::ToWidget,
-                            if *self.is_selected {
-                                (*self.selected_brush).clone()
-                            } else {
-                                (*self.normal_brush).clone()
-                            },
-                        ));
+                        if self.uses_brushes() {
+                            ui.send_message(WidgetMessage::background(
+                                self.handle(),
+                                MessageDirection::ToWidget,
+                                self.rest_brush(),
+                            ));
+                        } else {
+                            self.sync_rest_brush();
+                        }
                     }
                 }
                 DecoratorMessage::HoverBrush(brush) => {
                     self.hover_brush.set_value_and_mark_modified(brush.clone());
-                    if self.is_mouse_directly_over {
+                    if !self.uses_brushes() {
+                        self.set_style_brush(VisualState::Hovered, brush);
+                    } else if self.is_mouse_directly_over {
                         ui.send_message(WidgetMessage::background(
                             self.handle(),
                             MessageDirection::ToWidget,
@@ -192,7 +280,9 @@ impl Control for Decorator {
                 }
                 DecoratorMessage::NormalBrush(brush) => {
                     self.normal_brush.set_value_and_mark_modified(brush.clone());
-                    if !*self.is_selected && !self.is_mouse_directly_over {
+                    if !self.uses_brushes() {
+                        self.sync_rest_brush();
+                    } else if !*self.is_selected && !self.is_mouse_directly_over {
                         ui.send_message(WidgetMessage::background(
                             self.handle(),
                             MessageDirection::ToWidget,
@@ -203,11 +293,16 @@ impl Control for Decorator {
                 DecoratorMessage::PressedBrush(brush) => {
                     self.pressed_brush
                         .set_value_and_mark_modified(brush.clone());
+                    if !self.uses_brushes() {
+                        self.set_style_brush(VisualState::Pressed, brush);
+                    }
                 }
                 DecoratorMessage::SelectedBrush(brush) => {
                     self.selected_brush
                         .set_value_and_mark_modified(brush.clone());
-                    if *self.is_selected {
+                    if !self.uses_brushes() {
+                        self.sync_rest_brush();
+                    } else if *self.is_selected {
                         ui.send_message(WidgetMessage::background(
                             self.handle(),
                             MessageDirection::ToWidget,
@@ -217,8 +312,17 @@ impl Control for Decorator {
                 }
             }
         } else if let Some(msg) = message.data::<WidgetMessage>() {
-            if message.destination() == self.handle()
-                || self.has_descendant(message.destination(), ui)
+            if !self.style.is_empty()
+                && self
+                    .visual_states
+                    .handle_routed_message(&self.border.widget, ui, message)
+            {
+                self.apply_visual_states();
+            }
+
+            if self.uses_brushes()
+                && (message.destination() == self.handle()
+                    || self.has_descendant(message.destination(), ui))
             {
                 match msg {
                     WidgetMessage::MouseLeave => {
@@ -281,6 +385,8 @@ pub struct DecoratorBuilder {
     selected_brush: Brush,
     pressable: bool,
     selected: bool,
+    style: TransitionStyle,
+    brush_transitions: Option<(f32, Easing)>,
 }
 
 impl DecoratorBuilder {
@@ -294,6 +400,8 @@ impl DecoratorBuilder {
             selected_brush: BRUSH_BRIGHT,
             pressable: true,
             selected: false,
+            style: Default::default(),
+            brush_transitions: None,
         }
     }
 
@@ -333,6 +441,19 @@ impl DecoratorBuilder {
         self
     }
 
+    /// Sets the desired transition style of the decorator. See [`Decorator`] docs for more info.
+    pub fn with_style(mut self, style: TransitionStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Replaces instant switching of hover and pressed brushes with smooth transitions of the given duration (in
+    /// seconds). Transitions are added to the style of the decorator when it is built.
+    pub fn with_brush_transitions(mut self, duration: f32, easing: Easing) -> Self {
+        self.brush_transitions = Some((duration, easing));
+        self
+    }
+
     /// Finishes decorator instance building.
     pub fn build(mut self, ui: &mut BuildContext) -> Handle<UiNode> {
         let normal_brush = self.normal_brush;
@@ -342,6 +463,27 @@ impl DecoratorBuilder {
             self.border_builder.widget_builder.foreground = Some(BRUSH_DARKER);
         }
 
+        let mut style = self.style;
+        if let Some((duration, easing)) = self.brush_transitions {
+            let mut add = |state, brush: &Brush| {
+                style.transitions.push(
+                    PropertyTransition::new(
+                        state,
+                        "background",
+                        TransitionValue::Brush(brush.clone()),
+                    )
+                    .with_duration(duration)
+                    .with_easing(easing),
+                )
+            };
+            add(VisualState::Hovered, &self.hover_brush);
+            add(VisualState::Pressed, &self.pressed_brush);
+        }
+
+        // Transitions are animated in `update`, it is enabled for every decorator, because the style could be
+        // changed at any time (for example, in the editor).
+        self.border_builder.widget_builder.need_update = true;
+
         let mut border = self.border_builder.build_border();
 
         if self.selected {
@@ -358,6 +500,9 @@ impl DecoratorBuilder {
             selected_brush: selected_brush.into(),
             is_selected: self.selected.into(),
             is_pressable: self.pressable.into(),
+            style: style.into(),
+            visual_states: Default::default(),
+            transitions: Default::default(),
         });
         ui.add_node(node)
     }
@@ -41,6 +41,7 @@ use crate::{
         uuid::Uuid,
         visitor::prelude::*,
     },
+    data_grid::{CellTemplate, DataGrid, DataGridColumn, DataGridRow, DataGridSort, SortDirection},
     decorator::Decorator,
     dropdown_list::DropdownList,
     expander::Expander,
@@ -89,14 +90,18 @@ use crate::{
     rect::RectEditor,
     scroll_bar::ScrollBar,
     scroll_panel::ScrollPanel,
+    slider::Slider,
     stack_panel::StackPanel,
     tab_control::TabControl,
     text::Text,
     text_box::{Position, SelectionRange, TextBox, TextCommitMode},
+    transition::{Easing, PropertyTransition, TransitionStyle, TransitionValue, VisualState},
     tree::{Tree, TreeRoot},
     uuid::UuidEditor,
     vec::VecEditor,
     vector_image::{Primitive, VectorImage},
+    virtual_tree::VirtualTree,
+    virtualizing_stack_panel::VirtualizingStackPanel,
     widget::Widget,
     window::Window,
     wrap_panel::WrapPanel,
@@ -136,6 +141,7 @@ pub mod quat;
 pub mod range;
 pub mod rect;
 pub mod refcell;
+pub mod slider;
 pub mod string;
 pub mod utf32;
 pub mod uuid;
@@ -545,6 +551,18 @@ impl PropertyEditorDefinitionContainer {
             RefCell<Vec<GridDimension>>,
         >::new());
 
+        container.register_inheritable_enum::<CellTemplate, _>();
+        container.register_inheritable_enum::<SortDirection, _>();
+        container.register_inheritable_option::<DataGridSort>();
+        container.register_inheritable_vec_collection::<DataGridColumn>();
+        container.register_inheritable_vec_collection::<DataGridRow>();
+
+        container.register_inheritable_enum::<VisualState, _>();
+        container.register_inheritable_enum::<Easing, _>();
+        container.insert(EnumPropertyEditorDefinition::<TransitionValue>::new());
+        container.register_inheritable_vec_collection::<PropertyTransition>();
+        container.register_inheritable_inspectable::<TransitionStyle>();
+
         container.insert(Utf32StringPropertyEditorDefinition);
         container.insert(InheritablePropertyEditorDefinition::<Vec<char>>::new());
 
@@ -587,6 +605,7 @@ impl PropertyEditorDefinitionContainer {
             BitField<i64>,
             Button,
             Canvas,
+            DataGrid,
             Decorator,
             DropdownList,
             Expander,
@@ -635,6 +654,7 @@ impl PropertyEditorDefinitionContainer {
             RectEditor<f64>,
             ScrollBar,
             ScrollPanel,
+            Slider,
             StackPanel,
             TabControl,
             Text,
@@ -673,13 +693,19 @@ impl PropertyEditorDefinitionContainer {
             VecEditor<f32,4>,
             VecEditor<f64,4>,
             VectorImage,
+            VirtualizingStackPanel,
+            VirtualTree,
             Window,
             WrapPanel,
             // Structs
             GradientPoint,
             Thickness,
             FormattedText,
-            GridDimension
+            GridDimension,
+            DataGridColumn,
+            DataGridRow,
+            DataGridSort,
+            PropertyTransition
         );
 
         container
@@ -0,0 +1,169 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Optional property editor for numeric values with known range. See [`SliderPropertyEditorDefinition`] docs
+//! for more info.
+
+use crate::{
+    core::{num_traits::NumCast, reflect::FieldInfo},
+    inspector::{
+        editors::{
+            numeric::NumericPropertyEditorDefinition, PropertyEditorBuildContext,
+            PropertyEditorDefinition, PropertyEditorInstance, PropertyEditorMessageContext,
+            PropertyEditorTranslationContext,
+        },
+        FieldKind, InspectorError, PropertyChanged,
+    },
+    message::{MessageDirection, UiMessage},
+    numeric::{NumericType, NumericUpDownMessage},
+    slider::{SliderBuilder, SliderMessage},
+    widget::WidgetBuilder,
+    Thickness,
+};
+use std::{any::TypeId, marker::PhantomData};
+
+/// Property editor that uses [`crate::slider::Slider`] for numeric properties that have both `min_value` and
+/// `max_value` attributes (`#[reflect(min_value = 0.0, max_value = 1.0)]`), other properties of the same type are
+/// edited using [`crate::numeric::NumericUpDown`] as usual. It is not registered by default, use it to replace
+/// the default editor of a type:
+///
+/// ```rust
+/// # use fyrox_ui::inspector::editors::{
+/// #     slider::SliderPropertyEditorDefinition, PropertyEditorDefinitionContainer,
+/// # };
+/// let container = PropertyEditorDefinitionContainer::with_default_editors();
+/// container.insert(SliderPropertyEditorDefinition::<f32>::default());
+/// ```
+///
+/// Slider works with `f32` values internally, so very large 64-bit values could lose precision.
+#[derive(Debug)]
+pub struct SliderPropertyEditorDefinition<T>
+where
+    T: NumericType,
+{
+    phantom: PhantomData<T>,
+}
+
+impl<T> Default for SliderPropertyEditorDefinition<T>
+where
+    T: NumericType,
+{
+    fn default() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+fn is_ranged(property_info: &FieldInfo) -> bool {
+    property_info.min_value.is_some() && property_info.max_value.is_some()
+}
+
+fn is_integer<T: NumericType>() -> bool {
+    <T as NumCast>::from(0.5f32).map_or(true, |half: T| half == T::zero())
+}
+
+impl<T> PropertyEditorDefinition for SliderPropertyEditorDefinition<T>
+where
+    T: NumericType,
+{
+    fn value_type_id(&self) -> TypeId {
+        TypeId::of::<T>()
+    }
+
+    fn create_instance(
+        &self,
+        ctx: PropertyEditorBuildContext,
+    ) -> Result<PropertyEditorInstance, InspectorError> {
+        let (Some(min), Some(max)) = (ctx.property_info.min_value, ctx.property_info.max_value)
+        else {
+            return NumericPropertyEditorDefinition::<T>::default().create_instance(ctx);
+        };
+
+        let value = ctx.property_info.cast_value::<T>()?;
+        let integer = is_integer::<T>();
+        Ok(PropertyEditorInstance::Simple {
+            editor: SliderBuilder::new(
+                WidgetBuilder::new().with_margin(Thickness::top_bottom(1.0)),
+            )
+            .with_min(min as f32)
+            .with_max(max as f32)
+            .with_step(
+                ctx.property_info
+                    .step
+                    .map(|step| step as f32)
+                    .unwrap_or(if integer { 1.0 } else { 0.0 }),
+            )
+            .with_value(NumCast::from(*value).unwrap_or_default())
+            .show_value(true)
+            .with_value_precision(if integer {
+                0
+            } else {
+                ctx.property_info.precision.unwrap_or(3)
+            })
+            .build(ctx.build_context),
+        })
+    }
+
+    fn create_message(
+        &self,
+        ctx: PropertyEditorMessageContext,
+    ) -> Result<Option<UiMessage>, InspectorError> {
+        if !is_ranged(ctx.property_info) {
+            return NumericPropertyEditorDefinition::<T>::default().create_message(ctx);
+        }
+
+        let value = ctx.property_info.cast_value::<T>()?;
+        Ok(Some(SliderMessage::value(
+            ctx.instance,
+            MessageDirection::ToWidget,
+            NumCast::from(*value).unwrap_or_default(),
+        )))
+    }
+
+    fn translate_message(&self, ctx: PropertyEditorTranslationContext) -> Option<PropertyChanged> {
+        if ctx.message.direction() == MessageDirection::FromWidget {
+            let value = if let Some(SliderMessage::Value(value)) = ctx.message.data() {
+                let value = if is_integer::<T>() {
+                    value.round()
+                } else {
+                    *value
+                };
+                <T as NumCast>::from(value)
+            } else if let Some(NumericUpDownMessage::Value(value)) =
+                ctx.message.data::<NumericUpDownMessage<T>>()
+            {
+                Some(*value)
+            } else {
+                None
+            };
+
+            if let Some(value) = value {
+                return Some(PropertyChanged {
+                    name: ctx.name.to_string(),
+                    owner_type_id: ctx.owner_type_id,
+                    value: FieldKind::object(value),
+                });
+            }
+        }
+
+        None
+    }
+}
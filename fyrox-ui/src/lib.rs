@@ -126,6 +126,7 @@
 //! * [`crate::image::Image`]: The Image widget is used to display a pixel image to the user.
 //! * [`crate::vector_image::VectorImage`]: The Vector Image is used to render vector instructions as a graphical element.
 //! * [`crate::rect::RectEditor`]: The Rect allows you to specify numeric values for X, Y, Width, and Height of a rectangle.
+//! * [`crate::profiler_view::ProfilerView`]: The Profiler View shows frames recorded by the profiler as a timeline.
 //! * [`crate::progress_bar::ProgressBar`]: The Progress Bar shows a bar whose fill state can be adjusted to indicate visually how full
 //! something is, for example how close to 100% is a loading process.
 //! * [`crate::decorator::Decorator`]: The Decorator is used to style any widget. It has support for different styles depending on various
@@ -144,6 +145,8 @@
 //! * [`crate::text_box::TextBox`]: The Text Box is a control that allows the editing of text.
 //! * [`crate::scroll_bar::ScrollBar`]: The Scroll Bar provides a scroll bar like control that can be used on it's own as a data input or with
 //! certain other widgets to provide content scrolling capabilities.
+//! * [`crate::slider::Slider`]: The Slider allows the user to pick a value from a range by dragging a thumb along a track, optionally
+//! with tick marks and a value label.
 //! * [`crate::numeric::NumericUpDown`]: The Numeric Field provides the ability to adjust a number via increment and decrement buttons or direct
 //! input. The number can be constrained to remain inside a specific range or have a specific step.
 //! * [`crate::range::RangeEditor`]: The Range allows the user to edit a numeric range - specify its begin and end values.
@@ -218,6 +221,7 @@ pub mod check_box;
 pub mod color;
 mod control;
 pub mod curve;
+pub mod data_grid;
 pub mod decorator;
 pub mod dock;
 pub mod draw;
@@ -233,6 +237,7 @@ pub mod inspector;
 pub mod key;
 pub mod list_view;
 pub mod loader;
+pub mod localization;
 pub mod matrix;
 pub mod menu;
 pub mod message;
@@ -243,6 +248,7 @@ mod node;
 pub mod numeric;
 pub mod path;
 pub mod popup;
+pub mod profiler_view;
 pub mod progress_bar;
 pub mod range;
 pub mod rect;
@@ -252,16 +258,20 @@ pub mod scroll_panel;
 pub mod scroll_viewer;
 pub mod searchbar;
 pub mod selector;
+pub mod slider;
 pub mod stack_panel;
 pub mod tab_control;
 pub mod text;
 pub mod text_box;
 mod thickness;
+pub mod transition;
 pub mod tree;
 pub mod utils;
 pub mod uuid;
 pub mod vec;
 pub mod vector_image;
+pub mod virtual_tree;
+pub mod virtualizing_stack_panel;
 pub mod widget;
 pub mod window;
 pub mod wrap_panel;
@@ -284,11 +294,13 @@ use crate::{
     draw::{CommandTexture, Draw, DrawingContext},
     font::FontResource,
     font::BUILT_IN_FONT,
+    localization::Localization,
     message::{
         ButtonState, CursorIcon, KeyboardModifiers, MessageDirection, MouseButton, OsEvent,
         UiMessage,
     },
     popup::{Placement, PopupMessage},
+    text::Text,
     widget::{Widget, WidgetBuilder, WidgetMessage},
 };
 use copypasta::ClipboardContext;
@@ -716,6 +728,10 @@ pub struct UserInterface {
     #[reflect(hidden)]
     double_click_entries: FxHashMap<MouseButton, DoubleClickEntry>,
     pub double_click_time_slice: f32,
+    #[reflect(hidden)]
+    localization: Localization,
+    #[reflect(hidden)]
+    localization_revision: u64,
 }
 
 impl Visit for UserInterface {
@@ -797,6 +813,8 @@ impl Clone for UserInterface {
             default_font: self.default_font.clone(),
             double_click_entries: self.double_click_entries.clone(),
             double_click_time_slice: self.double_click_time_slice,
+            localization: self.localization.clone(),
+            localization_revision: self.localization_revision,
         }
     }
 }
@@ -810,6 +828,7 @@ impl Default for UserInterface {
 #[derive(Default)]
 pub struct UiContainer {
     pool: Pool<UserInterface>,
+    localization: Localization,
 }
 
 impl UiContainer {
@@ -820,9 +839,30 @@ impl UiContainer {
 
     /// Creates a new user interface container with the given user interface.
     pub fn new_with_ui(ui: UserInterface) -> Self {
-        let mut pool = Pool::new();
-        let _ = pool.spawn(ui);
-        Self { pool }
+        let mut container = Self::default();
+        container.add(ui);
+        container
+    }
+
+    /// Returns localization, that is shared across every user interface in the container. Use it
+    /// to change current locale of every user interface at once. See [`Localization`] docs for
+    /// more info.
+    ///
+    /// The container owns the localization: user interfaces added via [`Self::add`] share it
+    /// immediately, user interfaces replaced in-place (for example via [`Self::first_mut`]) share
+    /// it after [`Self::sync_localization`] call, which is done by the engine every frame.
+    pub fn localization(&self) -> &Localization {
+        &self.localization
+    }
+
+    /// Makes sure that every user interface in the container uses the localization of the
+    /// container.
+    pub fn sync_localization(&mut self) {
+        for ui in self.pool.iter_mut() {
+            if !ui.localization.ptr_eq(&self.localization) {
+                ui.set_localization(self.localization.clone());
+            }
+        }
     }
 
     /// Returns a reference to the first user interface in the container. Panics, if the container
@@ -880,10 +920,12 @@ impl UiContainer {
         self.pool.iter_mut()
     }
 
-    /// Adds a new user interface into container.
+    /// Adds a new user interface into container. The user interface will share the localization
+    /// of the container.
     #[inline]
-    pub fn add(&mut self, scene: UserInterface) -> Handle<UserInterface> {
-        self.pool.spawn(scene)
+    pub fn add(&mut self, mut ui: UserInterface) -> Handle<UserInterface> {
+        ui.set_localization(self.localization.clone());
+        self.pool.spawn(ui)
     }
 
     /// Removes all user interfaces from container.
@@ -940,6 +982,16 @@ impl IndexMut<Handle<UserInterface>> for UiContainer {
     }
 }
 
+fn localize_node(localization: &Localization, node: &mut UiNode) {
+    if let Some(text) = node.cast_mut::<Text>() {
+        if let Some(key) = text.localization_key.as_ref() {
+            let localized = localization.localize_or_key(key);
+            text.formatted_text.borrow_mut().set_text(localized);
+            text.invalidate_layout();
+        }
+    }
+}
+
 fn is_on_screen(node: &UiNode, nodes: &Pool<UiNode, WidgetContainer>) -> bool {
     // Crawl up on tree and check if current bounds are intersects with every screen bound
     // of parents chain. This is needed because some control can move their children outside of
@@ -1093,6 +1145,8 @@ impl UserInterface {
             default_font: BUILT_IN_FONT.resource(),
             double_click_entries: Default::default(),
             double_click_time_slice: 0.5, // 500 ms is standard in most operating systems.
+            localization: Default::default(),
+            localization_revision: 0,
         };
         ui.root_canvas = ui.add_node(UiNode::new(Canvas {
             widget: WidgetBuilder::new().build(),
@@ -1105,6 +1159,24 @@ impl UserInterface {
         self.keyboard_modifiers
     }
 
+    /// Returns current localization of the user interface. See [`Localization`] docs for more info.
+    pub fn localization(&self) -> &Localization {
+        &self.localization
+    }
+
+    /// Sets new localization of the user interface and updates every localized widget.
+    pub fn set_localization(&mut self, localization: Localization) {
+        self.localization = localization;
+        self.localization_revision = self.localization.revision();
+        self.apply_localization();
+    }
+
+    fn apply_localization(&mut self) {
+        for node in self.nodes.iter_mut() {
+            localize_node(&self.localization, node);
+        }
+    }
+
     pub fn build_ctx(&mut self) -> BuildContext<'_> {
         self.into()
     }
@@ -1271,10 +1343,18 @@ impl UserInterface {
     }
 
     pub fn update(&mut self, screen_size: Vector2<f32>, dt: f32, switches: &UiUpdateSwitches) {
+        fyrox_core::scope_profile!();
+
         for entry in self.double_click_entries.values_mut() {
             entry.timer -= dt;
         }
 
+        let localization_revision = self.localization.revision();
+        if localization_revision != self.localization_revision {
+            self.localization_revision = localization_revision;
+            self.apply_localization();
+        }
+
         self.update_layout(screen_size);
 
         if let Some(node_overrides) = switches.node_overrides.as_ref() {
@@ -1316,6 +1396,8 @@ impl UserInterface {
     }
 
     pub fn draw(&mut self) -> &DrawingContext {
+        fyrox_core::scope_profile!();
+
         self.drawing_context.clear();
 
         for node in self.nodes.iter_mut() {
@@ -2979,6 +3061,7 @@ impl UserInterface {
             })
         });
         self.remap_handles(&instances);
+        self.apply_localization();
     }
 
     /// Collects all resources used by the user interface. It uses reflection to "scan" the contents
@@ -3104,6 +3187,7 @@ impl BaseSceneGraph for UserInterface {
         node.handle = node_handle;
         self.methods_registry.register(node);
         node.invalidate_layout();
+        localize_node(&self.localization, &mut self.nodes[node_handle]);
         self.layout_events_sender
             .send(LayoutEvent::VisibilityChanged(node_handle))
             .unwrap();
@@ -36,6 +36,10 @@ use crate::{
     message::{KeyCode, MessageDirection, UiMessage},
     scroll_viewer::{ScrollViewer, ScrollViewerBuilder, ScrollViewerMessage},
     stack_panel::StackPanelBuilder,
+    virtualizing_stack_panel::{
+        ItemGenerator, SharedItemGenerator, VirtualizingStackPanel, VirtualizingStackPanelBuilder,
+        VirtualizingStackPanelMessage,
+    },
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, Thickness, UiNode, UserInterface, BRUSH_DARK, BRUSH_LIGHT,
 };
@@ -55,6 +59,9 @@ pub enum ListViewMessage {
     RemoveItem(Handle<UiNode>),
     /// A message, that is used to bring an item into view.
     BringItemIntoView(Handle<UiNode>),
+    /// A message, that is used to set new amount of items of a virtualized list view. It also re-generates all
+    /// visible items, so it could be used to refresh the list when the underlying data has changed.
+    ItemCount(usize),
 }
 
 impl ListViewMessage {
@@ -78,6 +85,10 @@ impl ListViewMessage {
         /// Creates [`ListViewMessage::BringItemIntoView`] message.
         ListViewMessage:BringItemIntoView => fn bring_item_into_view(Handle<UiNode>), layout: false
     );
+    define_constructor!(
+        /// Creates [`ListViewMessage::ItemCount`] message.
+        ListViewMessage:ItemCount => fn item_count(usize), layout: false
+    );
 }
 
 /// List view is used to display lists with arbitrary items. It supports single-selection and by default, it stacks the items
@@ -233,6 +244,34 @@ impl ListViewMessage {
 ///     ));
 /// }
 /// ```
+///
+/// ## Virtualization
+///
+/// List view creates a widget for every item, which could be slow when there are thousands of items. In this case
+/// list view could be virtualized - it will create widgets only for visible items and reuse them while scrolling.
+/// Virtualized list view needs the total amount of items, the height of every item and an [`ItemGenerator`] that
+/// creates a widget for an item by its index:
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle, list_view::ListViewBuilder, text::TextBuilder, widget::WidgetBuilder,
+/// #     BuildContext, UiNode,
+/// # };
+/// fn create_huge_list(ctx: &mut BuildContext, names: Vec<String>) -> Handle<UiNode> {
+///     ListViewBuilder::new(WidgetBuilder::new())
+///         .with_virtualization(names.len(), 20.0, move |index: usize, ctx: &mut BuildContext| {
+///             TextBuilder::new(WidgetBuilder::new())
+///                 .with_text(&names[index])
+///                 .build(ctx)
+///         })
+///         .build(ctx)
+/// }
+/// ```
+///
+/// Selection works with item indices as usual. Use [`ListViewMessage::ItemCount`] to change the amount of items,
+/// [`ListViewMessage::Items`], [`ListViewMessage::AddItem`] and [`ListViewMessage::RemoveItem`] cannot be used with
+/// virtualized list views. Implement [`ItemGenerator::recycle`] to reuse the widgets of the items instead of
+/// re-creating them while scrolling.
 #[derive(Default, Clone, Visit, Reflect, Debug, ComponentProvider)]
 pub struct ListView {
     /// Base widget of the list view.
@@ -258,14 +297,42 @@ impl ListView {
         &self.items
     }
 
+    fn virtualizing_panel<'a>(&self, ui: &'a UserInterface) -> Option<&'a VirtualizingStackPanel> {
+        ui.try_get(*self.panel)
+            .and_then(|panel| panel.cast::<VirtualizingStackPanel>())
+    }
+
+    /// Returns total amount of items, including the items of a virtualized list view that do not have widgets
+    /// at the moment.
+    pub fn item_count(&self, ui: &UserInterface) -> usize {
+        self.virtualizing_panel(ui)
+            .map_or(self.items.len(), |panel| *panel.item_count)
+    }
+
+    /// Returns an index of the item, that is wrapped by the given item container.
+    pub fn item_container_index(
+        &self,
+        item_container: Handle<UiNode>,
+        ui: &UserInterface,
+    ) -> Option<usize> {
+        match self.virtualizing_panel(ui) {
+            Some(panel) => panel.index_of(item_container),
+            None => self
+                .item_containers
+                .iter()
+                .position(|c| *c == item_container),
+        }
+    }
+
     fn fix_selection(&self, ui: &UserInterface) {
         // Check if current selection is out-of-bounds.
         let mut fixed_selection = Vec::with_capacity(self.selection.len());
+        let item_count = self.item_count(ui);
 
         for &selected_index in self.selection.iter() {
-            if selected_index >= self.items.len() {
-                if !self.items.is_empty() {
-                    fixed_selection.push(self.items.len() - 1);
+            if selected_index >= item_count {
+                if item_count > 0 {
+                    fixed_selection.push(item_count - 1);
                 }
             } else {
                 fixed_selection.push(selected_index);
@@ -290,24 +357,36 @@ impl ListView {
     }
 
     fn sync_decorators(&self, ui: &UserInterface) {
-        for (i, &container) in self.item_containers.iter().enumerate() {
-            let select = self.selection.contains(&i);
-            if let Some(container) = ui.node(container).cast::<ListViewItem>() {
-                let mut stack = container.children().to_vec();
-                while let Some(handle) = stack.pop() {
-                    let node = ui.node(handle);
-
-                    if node.cast::<ListView>().is_some() {
-                        // Do nothing.
-                    } else if node.cast::<Decorator>().is_some() {
-                        ui.send_message(DecoratorMessage::select(
-                            handle,
-                            MessageDirection::ToWidget,
-                            select,
-                        ));
-                    } else {
-                        stack.extend_from_slice(node.children())
-                    }
+        match self.virtualizing_panel(ui) {
+            Some(panel) => {
+                for item in panel.realized_items() {
+                    self.sync_decorator(item.handle, self.selection.contains(&item.index), ui);
+                }
+            }
+            None => {
+                for (i, &container) in self.item_containers.iter().enumerate() {
+                    self.sync_decorator(container, self.selection.contains(&i), ui);
+                }
+            }
+        }
+    }
+
+    fn sync_decorator(&self, container: Handle<UiNode>, select: bool, ui: &UserInterface) {
+        if let Some(container) = ui.try_get(container).and_then(|c| c.cast::<ListViewItem>()) {
+            let mut stack = container.children().to_vec();
+            while let Some(handle) = stack.pop() {
+                let node = ui.node(handle);
+
+                if node.cast::<ListView>().is_some() {
+                    // Do nothing.
+                } else if node.cast::<Decorator>().is_some() {
+                    ui.send_message(DecoratorMessage::select(
+                        handle,
+                        MessageDirection::ToWidget,
+                        select,
+                    ));
+                } else {
+                    stack.extend_from_slice(node.children())
                 }
             }
         }
@@ -351,9 +430,7 @@ impl Control for ListViewItem {
                     .expect("Parent of ListViewItem must be ListView!");
 
                 let self_index = list_view
-                    .item_containers
-                    .iter()
-                    .position(|c| *c == self.handle)
+                    .item_container_index(self.handle, ui)
                     .expect("ListViewItem must be used as a child of ListView");
 
                 let new_selection = if ui.keyboard_modifiers.control {
@@ -459,15 +536,38 @@ impl Control for ListView {
                             ));
                         }
                     }
+                    &ListViewMessage::ItemCount(count) => {
+                        if self.virtualizing_panel(ui).is_some() {
+                            ui.send_message(VirtualizingStackPanelMessage::item_count(
+                                *self.panel,
+                                MessageDirection::ToWidget,
+                                count,
+                            ));
+                        }
+                    }
                 }
             }
+        } else if let Some(VirtualizingStackPanelMessage::ItemCount(_)) = message.data() {
+            if message.destination() == *self.panel
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.fix_selection(ui);
+            }
+        } else if let Some(VirtualizingStackPanelMessage::ItemsRealized) = message.data() {
+            // Newly generated (or recycled) item containers must reflect current selection.
+            if message.destination() == *self.panel
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.sync_decorators(ui);
+            }
         } else if let Some(WidgetMessage::KeyDown(key_code)) = message.data() {
             if !message.handled() {
+                let item_count = self.item_count(ui);
                 let new_selection = if *key_code == KeyCode::ArrowDown {
                     match self.largest_selection_index() {
-                        Some(i) => Some(i.saturating_add(1) % self.items.len()),
+                        Some(i) => Some(i.saturating_add(1) % item_count),
                         None => {
-                            if self.items.is_empty() {
+                            if item_count == 0 {
                                 None
                             } else {
                                 Some(0)
@@ -478,14 +578,14 @@ impl Control for ListView {
                     match self.smallest_selection_index() {
                         Some(i) => {
                             let mut index = (i as isize).saturating_sub(1);
-                            let count = self.items.len() as isize;
+                            let count = item_count as isize;
                             if index < 0 {
                                 index += count;
                             }
                             Some((index % count) as usize)
                         }
                         None => {
-                            if self.items.is_empty() {
+                            if item_count == 0 {
                                 None
                             } else {
                                 Some(0)
@@ -503,6 +603,14 @@ impl Control for ListView {
                         vec![new_selection],
                     ));
 
+                    if self.virtualizing_panel(ui).is_some() {
+                        ui.send_message(VirtualizingStackPanelMessage::bring_index_into_view(
+                            *self.panel,
+                            MessageDirection::ToWidget,
+                            new_selection,
+                        ));
+                    }
+
                     message.set_handled(true);
                 }
             }
@@ -516,6 +624,7 @@ pub struct ListViewBuilder {
     items: Vec<Handle<UiNode>>,
     panel: Option<Handle<UiNode>>,
     scroll_viewer: Option<Handle<UiNode>>,
+    virtualization: Option<(usize, f32, SharedItemGenerator)>,
 }
 
 impl ListViewBuilder {
@@ -526,6 +635,7 @@ impl ListViewBuilder {
             items: Vec::new(),
             panel: None,
             scroll_viewer: None,
+            virtualization: None,
         }
     }
 
@@ -547,17 +657,46 @@ impl ListViewBuilder {
         self
     }
 
+    /// Makes the list view virtualized, it will have the given amount of items with the given height and
+    /// the generator will be used to create widgets only for visible items. Items and items panel, that were
+    /// set by [`Self::with_items`] and [`Self::with_items_panel`] will be ignored. See "Virtualization" section
+    /// of [`ListView`] docs for more info.
+    pub fn with_virtualization<G: ItemGenerator + 'static>(
+        mut self,
+        item_count: usize,
+        item_height: f32,
+        generator: G,
+    ) -> Self {
+        self.virtualization = Some((item_count, item_height, SharedItemGenerator::new(generator)));
+        self
+    }
+
     /// Finishes list view building and adds it to the user interface.
-    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
-        let item_containers = generate_item_containers(ctx, &self.items);
+    pub fn build(mut self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let (panel, item_containers) = match self.virtualization {
+            Some((item_count, item_height, generator)) => {
+                self.items.clear();
+                let panel = VirtualizingStackPanelBuilder::new(WidgetBuilder::new())
+                    .with_item_count(item_count)
+                    .with_item_size(item_height)
+                    .with_generator(ItemContainerGenerator(generator))
+                    .build(ctx);
+                (panel, Vec::new())
+            }
+            None => {
+                let item_containers = generate_item_containers(ctx, &self.items);
 
-        let panel = self
-            .panel
-            .unwrap_or_else(|| StackPanelBuilder::new(WidgetBuilder::new()).build(ctx));
+                let panel = self
+                    .panel
+                    .unwrap_or_else(|| StackPanelBuilder::new(WidgetBuilder::new()).build(ctx));
 
-        for &item_container in item_containers.iter() {
-            ctx.link(item_container, panel);
-        }
+                for &item_container in item_containers.iter() {
+                    ctx.link(item_container, panel);
+                }
+
+                (panel, item_containers)
+            }
+        };
 
         let back = BorderBuilder::new(
             WidgetBuilder::new()
@@ -605,6 +744,23 @@ fn generate_item_container(ctx: &mut BuildContext, item: Handle<UiNode>) -> Hand
     ctx.add_node(UiNode::new(item))
 }
 
+/// Wraps the items, produced by the inner generator, into item containers.
+struct ItemContainerGenerator(SharedItemGenerator);
+
+impl ItemGenerator for ItemContainerGenerator {
+    fn generate(&mut self, index: usize, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let item = self.0 .0.lock().generate(index, ctx);
+        generate_item_container(ctx, item)
+    }
+
+    fn recycle(&mut self, index: usize, item: Handle<UiNode>, ui: &UserInterface) -> bool {
+        match ui.try_get(item).and_then(|c| c.children().first()) {
+            Some(&item) => self.0 .0.lock().recycle(index, item, ui),
+            None => false,
+        }
+    }
+}
+
 fn generate_item_containers(
     ctx: &mut BuildContext,
     items: &[Handle<UiNode>],
@@ -0,0 +1,616 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Localization subsystem. It consists of string tables (see [`StringTable`]), that stores translations
+//! of strings for a set of locales, and [`Localization`] that selects current locale and resolves
+//! localization keys to actual strings. See [`Localization`] docs for more info and examples.
+
+#![warn(missing_docs)]
+
+use crate::core::{
+    io::FileLoadError, parking_lot::Mutex, reflect::prelude::*, uuid::Uuid, uuid_provider,
+    visitor::prelude::*, TypeUuidProvider,
+};
+use fxhash::FxHashMap;
+use fyrox_resource::{io::ResourceIo, Resource, ResourceData};
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Debug, Display, Formatter},
+    fs::File,
+    io::Write,
+    path::Path,
+    sync::Arc,
+};
+
+pub mod loader;
+
+/// An error that may occur during string table loading.
+#[derive(Debug)]
+pub enum StringTableError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+    /// The file is not a valid UTF-8 text.
+    Utf8,
+    /// The table has no header row with the list of locales.
+    NoHeader,
+    /// The table contains a row that does not match the header.
+    InvalidRow {
+        /// Line number (starting from 1) of the row.
+        line: usize,
+        /// Actual amount of columns in the row.
+        columns: usize,
+        /// Expected amount of columns in the row.
+        expected: usize,
+    },
+    /// The table contains the same key more than once.
+    DuplicateKey {
+        /// Line number (starting from 1) of the duplicate row.
+        line: usize,
+        /// The duplicated key.
+        key: String,
+    },
+    /// A row of the table contains an unterminated quoted field.
+    UnterminatedQuote {
+        /// Line number (starting from 1) at which the quoted field starts.
+        line: usize,
+    },
+}
+
+impl Display for StringTableError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StringTableError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            StringTableError::Utf8 => {
+                write!(f, "The string table is not a valid UTF-8 text.")
+            }
+            StringTableError::NoHeader => {
+                write!(f, "The string table has no header row.")
+            }
+            StringTableError::InvalidRow {
+                line,
+                columns,
+                expected,
+            } => {
+                write!(
+                    f,
+                    "The row at line {line} has {columns} columns, but {expected} was expected."
+                )
+            }
+            StringTableError::DuplicateKey { line, key } => {
+                write!(f, "The key {key} at line {line} is already defined.")
+            }
+            StringTableError::UnterminatedQuote { line } => {
+                write!(f, "The quoted field at line {line} is not terminated.")
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for StringTableError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// String table is a set of localized strings keyed by a string id. The table stores translations
+/// for a set of locales at once, this makes it easy to spot missing translations.
+///
+/// ## Format
+///
+/// String tables are stored in CSV files (comma-separated values) with `.strings` extension. The
+/// first row of the table is the header: the first column is ignored (it is usually called `id`),
+/// and the rest of the columns are locale names. Every other row is a key followed by its translations.
+/// Fields could be quoted (`"..."`) to include commas, line breaks and quotes (`""`) in them. Empty
+/// field means that there's no translation for the locale.
+///
+/// ```text
+/// id,en,de,fr
+/// menu.play,Play,Spielen,Jouer
+/// menu.quit,Quit,Beenden,
+/// greeting,"Hello, world!","Hallo, Welt!","Bonjour, le monde!"
+/// ```
+#[derive(Default, Clone, Debug, Visit, Reflect, PartialEq)]
+pub struct StringTable {
+    /// A list of locales of the table. The order of locales matches the order of the columns in
+    /// each entry.
+    pub locales: Vec<String>,
+    /// Localized strings keyed by string id. Each entry stores one string per locale, empty string
+    /// means that there's no translation for a respective locale.
+    pub entries: FxHashMap<String, Vec<String>>,
+}
+
+uuid_provider!(StringTable = "b0b8e6a4-2a3e-4e4b-9b4f-59c5b2a9d8d1");
+
+impl ResourceData for StringTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut file = File::create(path)?;
+        file.write_all(self.to_csv().as_bytes())?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+}
+
+/// A missing translation of a key in a string table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MissingTranslation {
+    /// A key that has no translation.
+    pub key: String,
+    /// A locale for which the translation is missing.
+    pub locale: String,
+}
+
+fn parse_csv(text: &str) -> Result<Vec<(usize, Vec<String>)>, StringTableError> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut line = 1;
+    let mut row_line = 1;
+    let mut quoted = false;
+    let mut quote_line = 1;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+        } else {
+            match c {
+                '"' if field.is_empty() => {
+                    quoted = true;
+                    quote_line = line;
+                }
+                ',' => row.push(std::mem::take(&mut field)),
+                '\r' => (),
+                '\n' => {
+                    row.push(std::mem::take(&mut field));
+                    rows.push((row_line, std::mem::take(&mut row)));
+                    line += 1;
+                    row_line = line;
+                }
+                _ => field.push(c),
+            }
+        }
+    }
+
+    if quoted {
+        return Err(StringTableError::UnterminatedQuote { line: quote_line });
+    }
+
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push((row_line, row));
+    }
+
+    // Skip empty lines.
+    rows.retain(|(_, row)| !(row.len() == 1 && row[0].is_empty()));
+
+    Ok(rows)
+}
+
+fn write_csv_field(out: &mut String, field: &str) {
+    if field.contains([',', '"', '\n', '\r']) {
+        out.push('"');
+        out.push_str(&field.replace('"', "\"\""));
+        out.push('"');
+    } else {
+        out.push_str(field);
+    }
+}
+
+impl StringTable {
+    /// Parses a string table from the given CSV text. See [`StringTable`] docs for the format description.
+    pub fn from_csv(text: &str) -> Result<Self, StringTableError> {
+        let mut rows = parse_csv(text)?.into_iter();
+
+        let (_, header) = rows.next().ok_or(StringTableError::NoHeader)?;
+        let locales = header
+            .into_iter()
+            .skip(1)
+            .map(|locale| locale.trim().to_string())
+            .collect::<Vec<_>>();
+
+        let mut entries = FxHashMap::default();
+        for (line, row) in rows {
+            let expected = locales.len() + 1;
+            // Trailing empty fields could be omitted.
+            if row.len() > expected {
+                return Err(StringTableError::InvalidRow {
+                    line,
+                    columns: row.len(),
+                    expected,
+                });
+            }
+            let mut row = row.into_iter();
+            let key = row.next().unwrap_or_default().trim().to_string();
+            if entries.contains_key(&key) {
+                return Err(StringTableError::DuplicateKey { line, key });
+            }
+            let mut translations = row.collect::<Vec<_>>();
+            translations.resize(locales.len(), Default::default());
+            entries.insert(key, translations);
+        }
+
+        Ok(Self { locales, entries })
+    }
+
+    /// Loads a string table from the given file.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, StringTableError> {
+        let bytes = io.load_file(path).await?;
+        let text = String::from_utf8(bytes).map_err(|_| StringTableError::Utf8)?;
+        Self::from_csv(&text)
+    }
+
+    /// Writes the table in CSV format. Keys are sorted, which makes the output deterministic.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("id");
+        for locale in self.locales.iter() {
+            out.push(',');
+            write_csv_field(&mut out, locale);
+        }
+        out.push('\n');
+        for key in self.sorted_keys() {
+            write_csv_field(&mut out, key);
+            for translation in self.entries[key].iter() {
+                out.push(',');
+                write_csv_field(&mut out, translation);
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Returns index of the given locale in the table.
+    pub fn locale_index(&self, locale: &str) -> Option<usize> {
+        self.locales.iter().position(|l| l == locale)
+    }
+
+    /// Tries to find a translation of the given key for the given locale. Empty translations are
+    /// treated as missing.
+    pub fn get(&self, key: &str, locale: &str) -> Option<&str> {
+        let index = self.locale_index(locale)?;
+        self.entries
+            .get(key)
+            .and_then(|translations| translations.get(index))
+            .map(|s| s.as_str())
+            .filter(|s| !s.is_empty())
+    }
+
+    /// Returns all keys of the table in alphabetical order.
+    pub fn sorted_keys(&self) -> Vec<&String> {
+        let mut keys = self.entries.keys().collect::<Vec<_>>();
+        keys.sort();
+        keys
+    }
+
+    /// Collects every key that has no translation for some locale. Keys are sorted alphabetically,
+    /// locales are listed in the order of the table columns.
+    pub fn missing_translations(&self) -> Vec<MissingTranslation> {
+        let mut missing = Vec::new();
+        for key in self.sorted_keys() {
+            let translations = &self.entries[key];
+            for (index, locale) in self.locales.iter().enumerate() {
+                if translations.get(index).map_or(true, |s| s.is_empty()) {
+                    missing.push(MissingTranslation {
+                        key: key.clone(),
+                        locale: locale.clone(),
+                    });
+                }
+            }
+        }
+        missing
+    }
+}
+
+/// Type alias for string table resources.
+pub type StringTableResource = Resource<StringTable>;
+
+#[derive(Default)]
+struct LocalizationState {
+    locale: String,
+    fallback_locale: Option<String>,
+    tables: Vec<StringTableResource>,
+    revision: u64,
+    loaded_tables: usize,
+}
+
+/// Localization selects current locale and resolves localization keys to strings using a set of
+/// string tables (see [`StringTable`]). It is a shared object - every clone of it points to the
+/// same state, so it is enough to change current locale once and every user interface that uses
+/// this localization will be updated. [`crate::UiContainer`] shares its localization with every
+/// user interface added to it.
+///
+/// Widgets that support localization (such as [`crate::text::Text`] and [`crate::button::Button`]
+/// with localized content) stores localization key instead of actual text and resolve the key
+/// each time when current locale changes.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_resource::manager::ResourceManager;
+/// # use fyrox_ui::{
+/// #     core::pool::Handle,
+/// #     localization::StringTable,
+/// #     text::TextBuilder,
+/// #     widget::WidgetBuilder,
+/// #     UiNode, UserInterface,
+/// # };
+/// fn create_localized_text(ui: &mut UserInterface, resource_manager: &ResourceManager) -> Handle<UiNode> {
+///     let localization = ui.localization();
+///     localization.add_table(resource_manager.request::<StringTable>("data/menu.strings"));
+///     localization.set_locale("de");
+///
+///     TextBuilder::new(WidgetBuilder::new())
+///         .with_localization_key("menu.play")
+///         .build(&mut ui.build_ctx())
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct Localization {
+    state: Arc<Mutex<LocalizationState>>,
+}
+
+impl Debug for Localization {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let state = self.state.lock();
+        f.debug_struct("Localization")
+            .field("locale", &state.locale)
+            .field("fallback_locale", &state.fallback_locale)
+            .field("tables", &state.tables.len())
+            .finish()
+    }
+}
+
+impl Localization {
+    /// Creates new localization with the given locale.
+    pub fn new(locale: impl Into<String>) -> Self {
+        let localization = Self::default();
+        localization.set_locale(locale);
+        localization
+    }
+
+    /// Returns `true` if both localizations point to the same shared state.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.state, &other.state)
+    }
+
+    /// Sets new current locale. Every user interface, that uses this localization, will update
+    /// its localized widgets on next update.
+    pub fn set_locale(&self, locale: impl Into<String>) {
+        let mut state = self.state.lock();
+        state.locale = locale.into();
+        state.revision += 1;
+    }
+
+    /// Returns current locale.
+    pub fn locale(&self) -> String {
+        self.state.lock().locale.clone()
+    }
+
+    /// Sets a locale that will be used when there's no translation for current locale.
+    pub fn set_fallback_locale(&self, locale: Option<String>) {
+        let mut state = self.state.lock();
+        state.fallback_locale = locale;
+        state.revision += 1;
+    }
+
+    /// Returns current fallback locale.
+    pub fn fallback_locale(&self) -> Option<String> {
+        self.state.lock().fallback_locale.clone()
+    }
+
+    /// Adds a new string table. Tables are searched in the order they were added, so if a key is
+    /// present in multiple tables, the first table wins.
+    pub fn add_table(&self, table: StringTableResource) {
+        let mut state = self.state.lock();
+        state.tables.push(table);
+        state.revision += 1;
+    }
+
+    /// Removes the given string table.
+    pub fn remove_table(&self, table: &StringTableResource) {
+        let mut state = self.state.lock();
+        state.tables.retain(|t| t != table);
+        state.revision += 1;
+    }
+
+    /// Returns a copy of the list of the string tables.
+    pub fn tables(&self) -> Vec<StringTableResource> {
+        self.state.lock().tables.clone()
+    }
+
+    /// Returns a number that changes every time when the localization is modified or some of
+    /// its string tables was loaded. It could be used to check whether localized data needs to
+    /// be updated.
+    pub fn revision(&self) -> u64 {
+        let mut state = self.state.lock();
+        let loaded_tables = state.tables.iter().filter(|t| t.is_ok()).count();
+        if loaded_tables != state.loaded_tables {
+            state.loaded_tables = loaded_tables;
+            state.revision += 1;
+        }
+        state.revision
+    }
+
+    /// Tries to find a translation of the given key for current locale (or fallback locale, if
+    /// there's no translation for current locale). Returns [`None`] if there's no translation
+    /// or string tables are not loaded yet.
+    pub fn localize(&self, key: &str) -> Option<String> {
+        let state = self.state.lock();
+        let find = |locale: &str| {
+            state.tables.iter().find_map(|table| {
+                let table = table.data_ref();
+                table
+                    .as_loaded_ref()
+                    .and_then(|table| table.get(key, locale).map(|s| s.to_string()))
+            })
+        };
+        find(&state.locale).or_else(|| state.fallback_locale.as_deref().and_then(find))
+    }
+
+    /// The same as [`Self::localize`], but returns the key itself if there's no translation. This
+    /// makes missing translations easy to spot.
+    pub fn localize_or_key(&self, key: &str) -> String {
+        self.localize(key).unwrap_or_else(|| key.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        button::ButtonBuilder,
+        localization::{Localization, MissingTranslation, StringTable, StringTableError},
+        message::MessageDirection,
+        text::{Text, TextBuilder, TextMessage},
+        widget::WidgetBuilder,
+        UserInterface,
+    };
+    use fyrox_core::algebra::Vector2;
+    use fyrox_graph::{BaseSceneGraph, SceneGraph};
+    use fyrox_resource::{untyped::ResourceKind, Resource};
+
+    const TABLE: &str = "id,en,de\n\
+        menu.play,Play,Spielen\n\
+        menu.quit,Quit,\n\
+        greeting,\"Hello, \"\"world\"\"!\",\"Hallo,\nWelt!\"\n";
+
+    #[test]
+    fn test_string_table_parsing() {
+        let table = StringTable::from_csv(TABLE).unwrap();
+        assert_eq!(table.locales, vec!["en".to_string(), "de".to_string()]);
+        assert_eq!(table.get("menu.play", "de"), Some("Spielen"));
+        assert_eq!(table.get("menu.quit", "de"), None);
+        assert_eq!(table.get("greeting", "en"), Some("Hello, \"world\"!"));
+        assert_eq!(table.get("greeting", "de"), Some("Hallo,\nWelt!"));
+        assert_eq!(
+            table.missing_translations(),
+            vec![MissingTranslation {
+                key: "menu.quit".to_string(),
+                locale: "de".to_string()
+            }]
+        );
+        assert_eq!(StringTable::from_csv(&table.to_csv()).unwrap(), table);
+    }
+
+    #[test]
+    fn test_string_table_duplicate_keys() {
+        assert!(matches!(
+            StringTable::from_csv("id,en\nfoo,Foo\n foo ,Bar\n"),
+            Err(StringTableError::DuplicateKey { line: 3, ref key }) if key == "foo"
+        ));
+    }
+
+    #[test]
+    fn test_localization_fallback() {
+        let table = StringTable::from_csv(TABLE).unwrap();
+        let localization = Localization::new("de");
+        localization.add_table(Resource::new_ok(ResourceKind::Embedded, table));
+        assert_eq!(
+            localization.localize("menu.play").as_deref(),
+            Some("Spielen")
+        );
+        assert_eq!(localization.localize("menu.quit"), None);
+        localization.set_fallback_locale(Some("en".to_string()));
+        assert_eq!(localization.localize("menu.quit").as_deref(), Some("Quit"));
+        assert_eq!(localization.localize_or_key("unknown"), "unknown");
+    }
+
+    fn text_of(ui: &UserInterface, handle: fyrox_core::pool::Handle<crate::UiNode>) -> String {
+        ui.node(handle).cast::<Text>().unwrap().text()
+    }
+
+    #[test]
+    fn test_localized_widgets() {
+        let screen_size = Vector2::new(100.0, 100.0);
+        let mut ui = UserInterface::new(screen_size);
+        ui.localization().set_locale("en");
+        ui.localization().add_table(Resource::new_ok(
+            ResourceKind::Embedded,
+            StringTable::from_csv(TABLE).unwrap(),
+        ));
+
+        let text = TextBuilder::new(WidgetBuilder::new())
+            .with_localization_key("menu.play")
+            .build(&mut ui.build_ctx());
+        let button = ButtonBuilder::new(WidgetBuilder::new())
+            .with_localized_text("menu.quit")
+            .build(&mut ui.build_ctx());
+        let plain = TextBuilder::new(WidgetBuilder::new()).build(&mut ui.build_ctx());
+        let button_text = ui.find_handle(button, &mut |n| n.cast::<Text>().is_some());
+
+        assert_eq!(text_of(&ui, text), "Play");
+        assert_eq!(text_of(&ui, button_text), "Quit");
+
+        ui.localization().set_locale("de");
+        ui.update(screen_size, 0.0, &Default::default());
+        assert_eq!(text_of(&ui, text), "Spielen");
+        // No translation - the key is shown.
+        assert_eq!(text_of(&ui, button_text), "menu.quit");
+
+        // Assign a key using a message.
+        ui.send_message(TextMessage::localization_key(
+            plain,
+            MessageDirection::ToWidget,
+            Some("menu.play".to_string()),
+        ));
+        while ui.poll_message().is_some() {}
+        assert_eq!(text_of(&ui, plain), "Spielen");
+
+        // Explicit text removes the key, so the text stays the same after locale change.
+        ui.send_message(TextMessage::text(
+            text,
+            MessageDirection::ToWidget,
+            "Custom".to_string(),
+        ));
+        while ui.poll_message().is_some() {}
+        ui.localization().set_locale("en");
+        ui.update(screen_size, 0.0, &Default::default());
+        assert_eq!(text_of(&ui, text), "Custom");
+        assert_eq!(text_of(&ui, plain), "Play");
+    }
+}
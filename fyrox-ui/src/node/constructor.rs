@@ -31,6 +31,7 @@ use crate::{
     color::{AlphaBar, ColorField, ColorPicker, HueBar, SaturationBrightnessField},
     core::{parking_lot::Mutex, uuid::Uuid, TypeUuidProvider},
     curve::CurveEditor,
+    data_grid::DataGrid,
     decorator::Decorator,
     dock::{DockingManager, Tile},
     dropdown_list::DropdownList,
@@ -47,6 +48,7 @@ use crate::{
     numeric::NumericUpDown,
     path::PathEditor,
     popup::Popup,
+    profiler_view::ProfilerView,
     progress_bar::ProgressBar,
     range::RangeEditor,
     rect::RectEditor,
@@ -56,6 +58,7 @@ use crate::{
     scroll_viewer::ScrollViewer,
     searchbar::SearchBar,
     selector::Selector,
+    slider::Slider,
     stack_panel::StackPanel,
     tab_control::TabControl,
     text::Text,
@@ -64,6 +67,8 @@ use crate::{
     uuid::UuidEditor,
     vec::VecEditor,
     vector_image::VectorImage,
+    virtual_tree::VirtualTree,
+    virtualizing_stack_panel::VirtualizingStackPanel,
     window::Window,
     wrap_panel::WrapPanel,
     Control, UiNode,
@@ -114,6 +119,7 @@ impl WidgetConstructorContainer {
         container.add::<Button>();
         container.add::<Canvas>();
         container.add::<CheckBox>();
+        container.add::<DataGrid>();
         container.add::<Decorator>();
         container.add::<DropdownList>();
         container.add::<Expander>();
@@ -163,11 +169,13 @@ impl WidgetConstructorContainer {
         container.add::<RectEditor<f64>>();
 
         container.add::<PathEditor>();
+        container.add::<ProfilerView>();
         container.add::<ProgressBar>();
         container.add::<ScrollBar>();
         container.add::<ScrollPanel>();
         container.add::<ScrollViewer>();
         container.add::<SearchBar>();
+        container.add::<Slider>();
         container.add::<StackPanel>();
         container.add::<TabControl>();
         container.add::<Tree>();
@@ -175,6 +183,8 @@ impl WidgetConstructorContainer {
         container.add::<UuidEditor>();
 
         container.add::<VectorImage>();
+        container.add::<VirtualizingStackPanel>();
+        container.add::<VirtualTree>();
         container.add::<Window>();
         container.add::<WrapPanel>();
         container.add::<ColorGradientField>();
@@ -0,0 +1,620 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Profiler view shows frames recorded by the profiler as a timeline. See [`ProfilerView`] docs for
+//! more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    brush::Brush,
+    core::{
+        algebra::Vector2,
+        color::Color,
+        math::Rect,
+        pool::Handle,
+        profiler::{FrameRecord, Profiler},
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        visitor::prelude::*,
+    },
+    define_constructor,
+    draw::{CommandTexture, Draw, DrawingContext},
+    formatted_text::{FormattedText, FormattedTextBuilder},
+    message::{MessageDirection, MouseButton, UiMessage},
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, UiNode, UserInterface, BRUSH_DARKER, BRUSH_TEXT,
+};
+use fyrox_core::uuid_provider;
+use fyrox_core::variable::InheritableVariable;
+use std::{
+    cell::RefCell,
+    hash::{Hash, Hasher},
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
+
+/// A set of messages that can be used to modify the state of a profiler view.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProfilerViewMessage {
+    /// A message, that is used to set the frames shown by the view. It is useful when the view
+    /// is not live (for example, to show frames that were received from another application).
+    Frames(Vec<FrameRecord>),
+    /// A message, that is used to enable or disable fetching of new frames from the profiler.
+    Live(bool),
+    /// A message, that is used to select a frame to show on the timeline. `None` means the last
+    /// frame.
+    SelectFrame(Option<usize>),
+}
+
+impl ProfilerViewMessage {
+    define_constructor!(
+        /// Creates [`ProfilerViewMessage::Frames`].
+        ProfilerViewMessage:Frames => fn frames(Vec<FrameRecord>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`ProfilerViewMessage::Live`].
+        ProfilerViewMessage:Live => fn live(bool), layout: false
+    );
+    define_constructor!(
+        /// Creates [`ProfilerViewMessage::SelectFrame`].
+        ProfilerViewMessage:SelectFrame => fn select_frame(Option<usize>), layout: false
+    );
+}
+
+#[derive(Clone, Debug)]
+struct DragContext {
+    mouse_x: f32,
+    offset: f32,
+}
+
+/// Profiler view shows frames recorded by [`Profiler`] as a timeline. The top strip of the view
+/// shows durations of recent frames, spikes (frames that take twice more time than average)
+/// are highlighted. The rest of the view shows scopes of the selected frame on every thread.
+///
+/// Click on a frame in the strip to select it, this also stops fetching of new frames, so the
+/// selected frame won't go away. Use mouse wheel to zoom the timeline and drag it with left mouse
+/// button to pan it.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle, profiler_view::ProfilerViewBuilder, widget::WidgetBuilder,
+/// #     BuildContext, UiNode,
+/// # };
+/// fn create_profiler_view(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     ProfilerViewBuilder::new(WidgetBuilder::new().with_width(600.0).with_height(300.0))
+///         // Fetch new frames from the profiler automatically.
+///         .with_live(true)
+///         .build(ctx)
+/// }
+/// ```
+///
+/// Keep in mind, that the profiler must be enabled (see [`Profiler::set_enabled`]), otherwise
+/// there will be nothing to show.
+#[derive(Default, Clone, Visit, Reflect, Debug, ComponentProvider)]
+pub struct ProfilerView {
+    /// Base widget of the view.
+    pub widget: Widget,
+    /// If `true`, the view fetches new frames from the profiler every frame.
+    pub live: InheritableVariable<bool>,
+    /// Height of the strip with frame durations.
+    pub frame_strip_height: InheritableVariable<f32>,
+    /// Height of a single row of scopes.
+    pub row_height: InheritableVariable<f32>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    frames: Vec<FrameRecord>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    thread_names: Vec<String>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    selected: Option<usize>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    zoom: f32,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    offset: f32,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    drag: Option<DragContext>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    text: RefCell<FormattedText>,
+}
+
+crate::define_widget_deref!(ProfilerView);
+
+uuid_provider!(ProfilerView = "0c8f2b84-5ad0-4d85-bb5c-4bbcfb2ec1a6");
+
+const PALETTE: [Color; 8] = [
+    Color::opaque(66, 133, 180),
+    Color::opaque(92, 160, 92),
+    Color::opaque(180, 130, 60),
+    Color::opaque(150, 90, 170),
+    Color::opaque(60, 160, 160),
+    Color::opaque(170, 80, 90),
+    Color::opaque(120, 120, 180),
+    Color::opaque(150, 150, 70),
+];
+
+fn scope_color_index(name: &str) -> usize {
+    let mut hasher = fxhash::FxHasher::default();
+    name.hash(&mut hasher);
+    hasher.finish() as usize % PALETTE.len()
+}
+
+impl ProfilerView {
+    /// Returns the frames shown by the view.
+    pub fn frames(&self) -> &[FrameRecord] {
+        &self.frames
+    }
+
+    /// Returns the frame shown on the timeline.
+    pub fn selected_frame(&self) -> Option<&FrameRecord> {
+        match self.selected {
+            Some(index) => self.frames.get(index),
+            None => self.frames.last(),
+        }
+    }
+
+    fn strip_bounds(&self) -> Rect<f32> {
+        let bounds = self.bounding_rect();
+        Rect::new(
+            bounds.x(),
+            bounds.y(),
+            bounds.w(),
+            self.frame_strip_height.min(bounds.h()),
+        )
+    }
+
+    fn frame_bar_width(&self) -> f32 {
+        self.strip_bounds().w() / self.frames.len().max(Profiler::frame_capacity()).max(1) as f32
+    }
+
+    fn frame_span(frame: &FrameRecord) -> (Duration, Duration) {
+        let end = frame
+            .scopes
+            .iter()
+            .map(|scope| scope.end())
+            .max()
+            .unwrap_or_default()
+            .max(frame.start + frame.duration);
+        let start = frame
+            .scopes
+            .first()
+            .map_or(frame.start, |scope| scope.start.min(frame.start));
+        (start, end)
+    }
+
+    fn draw_frame_strip(&self, ctx: &mut DrawingContext) {
+        let strip = self.strip_bounds();
+        ctx.push_rect_filled(&strip, None);
+        ctx.commit(self.clip_bounds(), BRUSH_DARKER, CommandTexture::None, None);
+
+        if self.frames.is_empty() {
+            return;
+        }
+
+        let average = self
+            .frames
+            .iter()
+            .map(|frame| frame.duration.as_secs_f32())
+            .sum::<f32>()
+            / self.frames.len() as f32;
+        let max = self
+            .frames
+            .iter()
+            .map(|frame| frame.duration.as_secs_f32())
+            .fold(1.0 / 60.0, f32::max);
+        let bar_width = self.frame_bar_width();
+        let selected = self.selected.unwrap_or(self.frames.len() - 1);
+
+        // Normal frames, spikes and the selected frame.
+        let groups: [(Color, &dyn Fn(usize, f32) -> bool); 3] = [
+            (Color::opaque(80, 150, 80), &|i, d| {
+                i != selected && d <= average * 2.0
+            }),
+            (Color::opaque(200, 60, 60), &|i, d| {
+                i != selected && d > average * 2.0
+            }),
+            (Color::opaque(230, 230, 230), &|i, _| i == selected),
+        ];
+        for (color, filter) in groups {
+            for (i, frame) in self.frames.iter().enumerate() {
+                let duration = frame.duration.as_secs_f32();
+                if !filter(i, duration) {
+                    continue;
+                }
+                let height = (duration / max * strip.h()).max(1.0);
+                ctx.push_rect_filled(
+                    &Rect::new(
+                        strip.x() + i as f32 * bar_width,
+                        strip.y() + strip.h() - height,
+                        (bar_width - 1.0).max(1.0),
+                        height,
+                    ),
+                    None,
+                );
+            }
+            ctx.commit(
+                self.clip_bounds(),
+                Brush::Solid(color),
+                CommandTexture::None,
+                None,
+            );
+        }
+
+        let mut text = self.text.borrow_mut();
+        text.set_text(format!(
+            "Frame {}: {:.2} ms (average {:.2} ms)",
+            self.frames[selected].index,
+            self.frames[selected].duration.as_secs_f32() * 1000.0,
+            average * 1000.0
+        ))
+        .build();
+        ctx.draw_text(
+            self.clip_bounds(),
+            Vector2::new(strip.x() + 2.0, strip.y() + 2.0),
+            &text,
+        );
+    }
+
+    fn draw_timeline(&self, ctx: &mut DrawingContext) {
+        let Some(frame) = self.selected_frame() else {
+            return;
+        };
+
+        let bounds = self.bounding_rect();
+        let strip = self.strip_bounds();
+        let (start, end) = Self::frame_span(frame);
+        let span = (end - start).as_secs_f32().max(f32::EPSILON);
+        let zoom = self.zoom.max(1.0);
+        let to_x = |time: Duration| {
+            bounds.x()
+                + ((time.saturating_sub(start)).as_secs_f32() / span - self.offset)
+                    * zoom
+                    * bounds.w()
+        };
+
+        let depths = frame.scope_depths();
+        let mut threads = frame
+            .scopes
+            .iter()
+            .map(|scope| scope.thread)
+            .collect::<Vec<_>>();
+        threads.sort_unstable();
+        threads.dedup();
+
+        let row_height = *self.row_height;
+        let mut rows = Vec::with_capacity(threads.len());
+        let mut y = strip.y() + strip.h() + 2.0;
+        for thread in threads.iter() {
+            rows.push(y + row_height);
+            let max_depth = frame
+                .scopes
+                .iter()
+                .zip(depths.iter())
+                .filter(|(scope, _)| scope.thread == *thread)
+                .map(|(_, depth)| *depth)
+                .max()
+                .unwrap_or_default();
+            y += row_height * (max_depth + 2) as f32;
+        }
+        let scope_rect = |index: usize| {
+            let scope = &frame.scopes[index];
+            let row = threads.iter().position(|t| *t == scope.thread).unwrap();
+            let x = to_x(scope.start);
+            Rect::new(
+                x,
+                rows[row] + depths[index] as f32 * row_height,
+                (to_x(scope.end()) - x).max(1.0),
+                row_height - 1.0,
+            )
+        };
+
+        for (color_index, color) in PALETTE.iter().enumerate() {
+            for (index, scope) in frame.scopes.iter().enumerate() {
+                if scope_color_index(&scope.name) == color_index {
+                    ctx.push_rect_filled(&scope_rect(index), None);
+                }
+            }
+            ctx.commit(
+                self.clip_bounds(),
+                Brush::Solid(*color),
+                CommandTexture::None,
+                None,
+            );
+        }
+
+        let mut text = self.text.borrow_mut();
+        for (thread, row) in threads.iter().zip(rows.iter()) {
+            let name = self
+                .thread_names
+                .get(*thread)
+                .cloned()
+                .unwrap_or_else(|| format!("Thread {thread}"));
+            text.set_text(name).build();
+            ctx.draw_text(
+                self.clip_bounds(),
+                Vector2::new(bounds.x() + 2.0, row - row_height),
+                &text,
+            );
+        }
+        for (index, scope) in frame.scopes.iter().enumerate() {
+            let rect = scope_rect(index);
+            if rect.w() < 20.0 {
+                continue;
+            }
+            text.set_text(format!(
+                "{} ({:.3} ms)",
+                scope.name,
+                scope.duration.as_secs_f32() * 1000.0
+            ));
+            let size = text.build();
+            if size.x + 4.0 <= rect.w() {
+                ctx.draw_text(
+                    self.clip_bounds(),
+                    Vector2::new(rect.x() + 2.0, rect.y()),
+                    &text,
+                );
+            }
+        }
+    }
+
+    fn set_live(&mut self, live: bool) {
+        self.live.set_value_and_mark_modified(live);
+        if live {
+            self.selected = None;
+        }
+    }
+}
+
+impl Control for ProfilerView {
+    fn draw(&self, drawing_context: &mut DrawingContext) {
+        let bounds = self.bounding_rect();
+        drawing_context.push_rect_filled(&bounds, None);
+        drawing_context.commit(
+            self.clip_bounds(),
+            self.widget.background(),
+            CommandTexture::None,
+            None,
+        );
+
+        self.draw_frame_strip(drawing_context);
+        self.draw_timeline(drawing_context);
+    }
+
+    fn update(&mut self, _dt: f32, _ui: &mut UserInterface) {
+        if *self.live && Profiler::last_frame_index() != self.frames.last().map(|frame| frame.index)
+        {
+            self.frames = Profiler::frames();
+            self.thread_names = Profiler::thread_names();
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if message.destination() != self.handle() {
+            return;
+        }
+
+        if let Some(msg) = message.data::<WidgetMessage>() {
+            match msg {
+                WidgetMessage::MouseDown { pos, button } if *button == MouseButton::Left => {
+                    let local = self.screen_to_local(*pos);
+                    let strip = self.strip_bounds();
+                    if local.y < strip.y() + strip.h() {
+                        let index = ((local.x - strip.x()) / self.frame_bar_width()) as usize;
+                        if index < self.frames.len() {
+                            self.set_live(false);
+                            self.selected = Some(index);
+                        }
+                    } else {
+                        self.drag = Some(DragContext {
+                            mouse_x: local.x,
+                            offset: self.offset,
+                        });
+                        ui.capture_mouse(self.handle());
+                    }
+                    message.set_handled(true);
+                }
+                WidgetMessage::MouseUp { button, .. }
+                    if *button == MouseButton::Left && self.drag.is_some() =>
+                {
+                    self.drag = None;
+                    ui.release_mouse_capture();
+                }
+                WidgetMessage::MouseMove { pos, .. } => {
+                    if let Some(drag) = self.drag.as_ref() {
+                        let local = self.screen_to_local(*pos);
+                        let width = self.bounding_rect().w().max(1.0);
+                        let zoom = self.zoom.max(1.0);
+                        self.offset = (drag.offset - (local.x - drag.mouse_x) / (width * zoom))
+                            .clamp(0.0, 1.0 - 1.0 / zoom);
+                    }
+                }
+                WidgetMessage::MouseWheel { pos, amount } => {
+                    let bounds = self.bounding_rect();
+                    let fraction = ((self.screen_to_local(*pos).x - bounds.x())
+                        / bounds.w().max(1.0))
+                    .clamp(0.0, 1.0);
+                    let zoom = self.zoom.max(1.0);
+                    let time = self.offset + fraction / zoom;
+                    let new_zoom = (zoom * if *amount < 0.0 { 0.8 } else { 1.25 }).max(1.0);
+                    self.zoom = new_zoom;
+                    self.offset = (time - fraction / new_zoom).clamp(0.0, 1.0 - 1.0 / new_zoom);
+                    message.set_handled(true);
+                }
+                _ => (),
+            }
+        } else if let Some(msg) = message.data::<ProfilerViewMessage>() {
+            if message.direction() == MessageDirection::ToWidget {
+                match msg {
+                    ProfilerViewMessage::Frames(frames) => {
+                        self.frames.clone_from(frames);
+                        if self
+                            .selected
+                            .is_some_and(|selected| selected >= self.frames.len())
+                        {
+                            self.selected = None;
+                        }
+                    }
+                    ProfilerViewMessage::Live(live) => {
+                        self.set_live(*live);
+                    }
+                    ProfilerViewMessage::SelectFrame(selected) => {
+                        self.selected = selected.filter(|index| *index < self.frames.len());
+                    }
+                }
+                ui.send_message(message.reverse());
+            }
+        }
+    }
+}
+
+/// Profiler view builder creates [`ProfilerView`] instances and adds them to the user interface.
+pub struct ProfilerViewBuilder {
+    widget_builder: WidgetBuilder,
+    live: bool,
+    frames: Vec<FrameRecord>,
+    frame_strip_height: f32,
+    row_height: f32,
+}
+
+impl ProfilerViewBuilder {
+    /// Creates new profiler view builder.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            live: true,
+            frames: Default::default(),
+            frame_strip_height: 60.0,
+            row_height: 18.0,
+        }
+    }
+
+    /// Sets whether the view should fetch new frames from the profiler every frame or not.
+    pub fn with_live(mut self, live: bool) -> Self {
+        self.live = live;
+        self
+    }
+
+    /// Sets the desired set of frames.
+    pub fn with_frames(mut self, frames: Vec<FrameRecord>) -> Self {
+        self.frames = frames;
+        self
+    }
+
+    /// Sets the desired height of the strip with frame durations.
+    pub fn with_frame_strip_height(mut self, height: f32) -> Self {
+        self.frame_strip_height = height;
+        self
+    }
+
+    /// Sets the desired height of a single row of scopes.
+    pub fn with_row_height(mut self, height: f32) -> Self {
+        self.row_height = height;
+        self
+    }
+
+    /// Finishes profiler view building and adds it to the user interface.
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let view = ProfilerView {
+            widget: self
+                .widget_builder
+                .with_need_update(true)
+                .with_clip_to_bounds(true)
+                .build(),
+            live: self.live.into(),
+            frame_strip_height: self.frame_strip_height.into(),
+            row_height: self.row_height.into(),
+            frames: self.frames,
+            thread_names: Profiler::thread_names(),
+            selected: None,
+            zoom: 1.0,
+            offset: 0.0,
+            drag: None,
+            text: RefCell::new(
+                FormattedTextBuilder::new(ctx.default_font())
+                    .with_brush(BRUSH_TEXT)
+                    .build(),
+            ),
+        };
+        ctx.add_node(UiNode::new(view))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::profiler::FrameRecord,
+        message::MessageDirection,
+        profiler_view::{ProfilerView, ProfilerViewBuilder, ProfilerViewMessage},
+        widget::WidgetBuilder,
+        UserInterface,
+    };
+    use fyrox_core::algebra::Vector2;
+    use fyrox_graph::BaseSceneGraph;
+
+    #[test]
+    fn profiler_view() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let frames = (0..3)
+            .map(|index| FrameRecord {
+                index,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let view = ProfilerViewBuilder::new(WidgetBuilder::new())
+            .with_live(false)
+            .with_frames(frames)
+            .build(&mut ui.build_ctx());
+        let selected = |ui: &UserInterface| {
+            ui.node(view)
+                .query_component::<ProfilerView>()
+                .unwrap()
+                .selected_frame()
+                .map(|frame| frame.index)
+        };
+        assert_eq!(selected(&ui), Some(2));
+
+        let input_message =
+            ProfilerViewMessage::select_frame(view, MessageDirection::ToWidget, Some(1));
+        ui.send_message(input_message.clone());
+        assert_eq!(ui.poll_message(), Some(input_message.clone()));
+        assert_eq!(ui.poll_message(), Some(input_message.reverse()));
+        assert_eq!(selected(&ui), Some(1));
+
+        // Going live resets the selection.
+        ui.send_message(ProfilerViewMessage::live(
+            view,
+            MessageDirection::ToWidget,
+            true,
+        ));
+        while ui.poll_message().is_some() {}
+        assert_eq!(selected(&ui), Some(2));
+    }
+}
@@ -0,0 +1,851 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Slider is a widget that allows the user to pick a value from a finite range by dragging a thumb along a
+//! track. See [`Slider`] docs for more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    border::BorderBuilder,
+    brush::Brush,
+    core::{
+        algebra::Vector2, color::Color, math::Rect, pool::Handle, reflect::prelude::*,
+        type_traits::prelude::*, uuid_provider, variable::InheritableVariable, visitor::prelude::*,
+    },
+    decorator::DecoratorBuilder,
+    define_constructor,
+    draw::{CommandTexture, Draw, DrawingContext},
+    font::FontResource,
+    message::{KeyCode, MessageDirection, MouseButton, UiMessage},
+    text::{TextBuilder, TextMessage},
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, Orientation, Thickness, UiNode, UserInterface, BRUSH_DARK, BRUSH_LIGHT,
+    BRUSH_LIGHTER, BRUSH_LIGHTEST,
+};
+use fyrox_graph::BaseSceneGraph;
+use std::{
+    cell::Cell,
+    ops::{Deref, DerefMut},
+};
+
+/// Thickness of the track line of the slider.
+const TRACK_THICKNESS: f32 = 4.0;
+/// Length of a single tick mark.
+const TICK_LENGTH: f32 = 4.0;
+/// A gap between the track and the value label.
+const LABEL_GAP: f32 = 4.0;
+/// Max amount of tick marks that will be drawn, it prevents the slider from drawing thousands of ticks
+/// when tick frequency is too small for the range.
+const MAX_TICKS: usize = 512;
+
+/// A set of messages that can be accepted by [`Slider`] widget.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SliderMessage {
+    /// Used to indicate that the value of the slider has changed ([`MessageDirection::FromWidget`]) or to set a
+    /// new value (with [`MessageDirection::ToWidget`]). The value will be clamped to the range of the slider and
+    /// snapped to its step.
+    Value(f32),
+    /// Used to indicate that the min value of the slider has changed ([`MessageDirection::FromWidget`]) or to set a
+    /// new min value (with [`MessageDirection::ToWidget`]).
+    MinValue(f32),
+    /// Used to indicate that the max value of the slider has changed ([`MessageDirection::FromWidget`]) or to set a
+    /// new max value (with [`MessageDirection::ToWidget`]).
+    MaxValue(f32),
+    /// Used to indicate that the step of the slider has changed ([`MessageDirection::FromWidget`]) or to set a
+    /// new step (with [`MessageDirection::ToWidget`]). Zero step means that the value is not snapped.
+    Step(f32),
+    /// Used to indicate that the distance between tick marks has changed ([`MessageDirection::FromWidget`]) or to
+    /// set a new distance (with [`MessageDirection::ToWidget`]). Zero means that tick marks are not shown.
+    TickFrequency(f32),
+    /// Moves the value of the slider by the given amount of steps (negative values move it towards the min value).
+    /// It is used by the slider itself for keyboard input and could be used to adjust the slider using gamepads
+    /// or any other input devices. Accepted only with [`MessageDirection::ToWidget`].
+    StepBy(i32),
+}
+
+impl SliderMessage {
+    define_constructor!(
+        /// Creates [`SliderMessage::Value`] message.
+        SliderMessage:Value => fn value(f32), layout: false
+    );
+    define_constructor!(
+        /// Creates [`SliderMessage::MinValue`] message.
+        SliderMessage:MinValue => fn min_value(f32), layout: false
+    );
+    define_constructor!(
+        /// Creates [`SliderMessage::MaxValue`] message.
+        SliderMessage:MaxValue => fn max_value(f32), layout: false
+    );
+    define_constructor!(
+        /// Creates [`SliderMessage::Step`] message.
+        SliderMessage:Step => fn step(f32), layout: false
+    );
+    define_constructor!(
+        /// Creates [`SliderMessage::TickFrequency`] message.
+        SliderMessage:TickFrequency => fn tick_frequency(f32), layout: false
+    );
+    define_constructor!(
+        /// Creates [`SliderMessage::StepBy`] message.
+        SliderMessage:StepBy => fn step_by(i32), layout: false
+    );
+}
+
+/// Slider is a widget that allows the user to pick a value from a finite range by dragging a thumb along a
+/// track, by clicking on the track or by using keyboard.
+///
+/// ## Example
+///
+/// A simple example of how to create a new [`Slider`] could be something like this:
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle, slider::SliderBuilder, widget::WidgetBuilder, BuildContext, UiNode,
+/// # };
+/// fn create_slider(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     SliderBuilder::new(WidgetBuilder::new().with_width(200.0))
+///         .with_min(0.0)
+///         .with_max(10.0)
+///         .with_step(0.5)
+///         .with_tick_frequency(1.0)
+///         .with_value(2.5)
+///         .show_value(true)
+///         .build(ctx)
+/// }
+/// ```
+///
+/// It creates a horizontal slider with `2.5` value, a range of `[0.0..10.0]`, a tick mark on every integer
+/// value and a label that shows the current value. To fetch the new value of the slider, use
+/// [`SliderMessage::Value`] message:
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle,
+/// #     message::{MessageDirection, UiMessage},
+/// #     slider::SliderMessage,
+/// #     UiNode,
+/// # };
+/// # fn foo(slider: Handle<UiNode>, message: &mut UiMessage) {
+/// if message.destination() == slider && message.direction() == MessageDirection::FromWidget {
+///     if let Some(SliderMessage::Value(value)) = message.data() {
+///         println!("{}", value);
+///     }
+/// }
+/// # }
+/// ```
+///
+/// ## Orientation
+///
+/// Slider could be either horizontal (default) or vertical. Vertical sliders have their min value at the bottom.
+/// Use [`SliderBuilder::with_orientation`] to select the orientation.
+///
+/// ## Step and tick marks
+///
+/// Every value of the slider is snapped to the nearest multiple of its step (counting from the min value), zero
+/// step disables snapping. Tick marks are drawn on every multiple of tick frequency, zero frequency hides them.
+///
+/// ## Keyboard and gamepad
+///
+/// When the slider (or its thumb) has keyboard focus, arrow keys move the value by one step, `PageUp` and
+/// `PageDown` move it by ten steps, `Home` and `End` set it to min and max respectively. The UI does not receive
+/// gamepad input, so games should translate it to [`SliderMessage::StepBy`] messages, for example:
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle, message::MessageDirection, slider::SliderMessage, UiNode, UserInterface,
+/// # };
+/// fn on_dpad_right(ui: &UserInterface, slider: Handle<UiNode>) {
+///     ui.send_message(SliderMessage::step_by(slider, MessageDirection::ToWidget, 1));
+/// }
+/// ```
+///
+/// ## Appearance
+///
+/// The track is drawn using the background brush of the widget, tick marks - using the foreground brush. A
+/// custom thumb could be set using [`SliderBuilder::with_thumb`].
+#[derive(Default, Clone, Debug, Visit, Reflect, ComponentProvider)]
+pub struct Slider {
+    /// Base widget of the slider.
+    pub widget: Widget,
+    /// Min value of the slider.
+    pub min: InheritableVariable<f32>,
+    /// Max value of the slider.
+    pub max: InheritableVariable<f32>,
+    /// Current value of the slider.
+    pub value: InheritableVariable<f32>,
+    /// Step of the slider. Zero means that the value is not snapped.
+    pub step: InheritableVariable<f32>,
+    /// Distance between tick marks. Zero means that tick marks are hidden.
+    pub tick_frequency: InheritableVariable<f32>,
+    /// Current orientation of the slider.
+    pub orientation: InheritableVariable<Orientation>,
+    /// A handle of the thumb widget.
+    pub thumb: InheritableVariable<Handle<UiNode>>,
+    /// A handle of the [`crate::text::Text`] widget that is used to show the current value of the slider.
+    pub value_text: InheritableVariable<Handle<UiNode>>,
+    /// Current value precision in decimal places.
+    pub value_precision: InheritableVariable<usize>,
+    /// Internal flag, that could be used to check whether the slider is being dragged or not.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub is_dragging: bool,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    grab_offset: Vector2<f32>,
+    /// Local-space rectangle along which the center of the thumb moves. Calculated on each arrange pass.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    travel_bounds: Cell<Rect<f32>>,
+}
+
+crate::define_widget_deref!(Slider);
+
+uuid_provider!(Slider = "0f4c3a1e-5a38-4f5a-9c1c-2d5a7f0b8e61");
+
+impl Slider {
+    /// Clamps the given value to the range of the slider and snaps it to the step.
+    pub fn snap(&self, value: f32) -> f32 {
+        let (min, max) = (*self.min, *self.max);
+        let value = value.clamp(min, max);
+        if *self.step > 0.0 {
+            (min + ((value - min) / *self.step).round() * *self.step).clamp(min, max)
+        } else {
+            value
+        }
+    }
+
+    fn percent(&self, value: f32) -> f32 {
+        let range = *self.max - *self.min;
+        if range > 0.0 {
+            ((value - *self.min) / range).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    fn value_at(&self, local_position: Vector2<f32>) -> f32 {
+        let travel = self.travel_bounds.get();
+        let percent = match *self.orientation {
+            Orientation::Horizontal if travel.w() > 0.0 => {
+                (local_position.x - travel.x()) / travel.w()
+            }
+            Orientation::Vertical if travel.h() > 0.0 => {
+                1.0 - (local_position.y - travel.y()) / travel.h()
+            }
+            _ => 0.0,
+        };
+        *self.min + percent.clamp(0.0, 1.0) * (*self.max - *self.min)
+    }
+
+    fn thumb_center(&self, value: f32) -> Vector2<f32> {
+        let travel = self.travel_bounds.get();
+        let percent = self.percent(value);
+        match *self.orientation {
+            Orientation::Horizontal => Vector2::new(
+                travel.x() + percent * travel.w(),
+                travel.y() + travel.h() * 0.5,
+            ),
+            Orientation::Vertical => Vector2::new(
+                travel.x() + travel.w() * 0.5,
+                travel.y() + (1.0 - percent) * travel.h(),
+            ),
+        }
+    }
+
+    fn set_value(&self, ui: &UserInterface, value: f32) {
+        ui.send_message(SliderMessage::value(
+            self.handle,
+            MessageDirection::ToWidget,
+            value,
+        ));
+    }
+}
+
+impl Control for Slider {
+    fn measure_override(&self, ui: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
+        let infinity = Vector2::new(f32::INFINITY, f32::INFINITY);
+
+        let mut thumb_size = Vector2::default();
+        if self.thumb.is_some() {
+            ui.measure_node(*self.thumb, infinity);
+            thumb_size = ui.node(*self.thumb).desired_size();
+        }
+
+        let mut label_size = Vector2::default();
+        if self.value_text.is_some() {
+            ui.measure_node(*self.value_text, infinity);
+            label_size = ui.node(*self.value_text).desired_size() + Vector2::repeat(LABEL_GAP);
+        }
+
+        let track_size = 2.0 * (TRACK_THICKNESS + TICK_LENGTH);
+        let size = match *self.orientation {
+            Orientation::Horizontal => Vector2::new(
+                2.0 * thumb_size.x + label_size.x,
+                thumb_size.y.max(label_size.y).max(track_size),
+            ),
+            Orientation::Vertical => Vector2::new(
+                thumb_size.x.max(label_size.x).max(track_size),
+                2.0 * thumb_size.y + label_size.y,
+            ),
+        };
+
+        Vector2::new(size.x.min(available_size.x), size.y.min(available_size.y))
+    }
+
+    fn arrange_override(&self, ui: &UserInterface, final_size: Vector2<f32>) -> Vector2<f32> {
+        let thumb_size = if self.thumb.is_some() {
+            ui.node(*self.thumb).desired_size()
+        } else {
+            Vector2::default()
+        };
+
+        let label_size = if self.value_text.is_some() {
+            ui.node(*self.value_text).desired_size()
+        } else {
+            Vector2::default()
+        };
+        let label_space = if self.value_text.is_some() {
+            label_size + Vector2::repeat(LABEL_GAP)
+        } else {
+            Vector2::default()
+        };
+
+        let (travel, label_position) = match *self.orientation {
+            Orientation::Horizontal => {
+                let width = (final_size.x - label_space.x).max(0.0);
+                (
+                    Rect::new(
+                        thumb_size.x * 0.5,
+                        (final_size.y - TRACK_THICKNESS) * 0.5,
+                        (width - thumb_size.x).max(0.0),
+                        TRACK_THICKNESS,
+                    ),
+                    Vector2::new(
+                        final_size.x - label_size.x,
+                        (final_size.y - label_size.y) * 0.5,
+                    ),
+                )
+            }
+            Orientation::Vertical => {
+                let height = (final_size.y - label_space.y).max(0.0);
+                (
+                    Rect::new(
+                        (final_size.x - TRACK_THICKNESS) * 0.5,
+                        thumb_size.y * 0.5,
+                        TRACK_THICKNESS,
+                        (height - thumb_size.y).max(0.0),
+                    ),
+                    Vector2::new(
+                        (final_size.x - label_size.x) * 0.5,
+                        final_size.y - label_size.y,
+                    ),
+                )
+            }
+        };
+        self.travel_bounds.set(travel);
+
+        if self.thumb.is_some() {
+            let center = self.thumb_center(*self.value);
+            ui.arrange_node(
+                *self.thumb,
+                &Rect::new(
+                    center.x - thumb_size.x * 0.5,
+                    center.y - thumb_size.y * 0.5,
+                    thumb_size.x,
+                    thumb_size.y,
+                ),
+            );
+        }
+
+        if self.value_text.is_some() {
+            ui.arrange_node(
+                *self.value_text,
+                &Rect::new(
+                    label_position.x,
+                    label_position.y,
+                    label_size.x,
+                    label_size.y,
+                ),
+            );
+        }
+
+        final_size
+    }
+
+    fn draw(&self, drawing_context: &mut DrawingContext) {
+        let travel = self.travel_bounds.get();
+
+        // Extend the track under the thumb at both ends, so it looks continuous.
+        let track = match *self.orientation {
+            Orientation::Horizontal => Rect::new(
+                travel.x() - TRACK_THICKNESS,
+                travel.y(),
+                travel.w() + 2.0 * TRACK_THICKNESS,
+                travel.h(),
+            ),
+            Orientation::Vertical => Rect::new(
+                travel.x(),
+                travel.y() - TRACK_THICKNESS,
+                travel.w(),
+                travel.h() + 2.0 * TRACK_THICKNESS,
+            ),
+        };
+        drawing_context.push_rect_filled(&track, None);
+        drawing_context.commit(
+            self.clip_bounds(),
+            self.widget.background(),
+            CommandTexture::None,
+            None,
+        );
+
+        let frequency = *self.tick_frequency;
+        let range = *self.max - *self.min;
+        if frequency > 0.0 && range > 0.0 && (range / frequency) as usize <= MAX_TICKS {
+            let count = (range / frequency + f32::EPSILON).floor() as usize;
+            for i in 0..=count {
+                let center = self.thumb_center(*self.min + i as f32 * frequency);
+                let (a, b) = match *self.orientation {
+                    Orientation::Horizontal => {
+                        let y = travel.y() + travel.h() + TICK_LENGTH;
+                        (
+                            Vector2::new(center.x, y),
+                            Vector2::new(center.x, y + TICK_LENGTH),
+                        )
+                    }
+                    Orientation::Vertical => {
+                        let x = travel.x() + travel.w() + TICK_LENGTH;
+                        (
+                            Vector2::new(x, center.y),
+                            Vector2::new(x + TICK_LENGTH, center.y),
+                        )
+                    }
+                };
+                drawing_context.push_line(a, b, 1.0);
+            }
+            drawing_context.commit(
+                self.clip_bounds(),
+                self.widget.foreground(),
+                CommandTexture::None,
+                None,
+            );
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<SliderMessage>() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match *msg {
+                    SliderMessage::Value(value) => {
+                        let new_value = self.snap(value);
+                        if (new_value - *self.value).abs() > f32::EPSILON {
+                            self.value.set_value_and_mark_modified(new_value);
+                            self.invalidate_arrange();
+
+                            if self.value_text.is_some() {
+                                ui.send_message(TextMessage::text(
+                                    *self.value_text,
+                                    MessageDirection::ToWidget,
+                                    format!("{:.1$}", new_value, *self.value_precision),
+                                ));
+                            }
+
+                            let mut response = SliderMessage::value(
+                                self.handle,
+                                MessageDirection::FromWidget,
+                                new_value,
+                            );
+                            response.flags = message.flags;
+                            response.set_handled(message.handled());
+                            ui.send_message(response);
+                        }
+                    }
+                    SliderMessage::MinValue(min) => {
+                        if *self.min != min {
+                            self.min.set_value_and_mark_modified(min);
+                            if *self.min > *self.max {
+                                std::mem::swap(&mut self.min, &mut self.max);
+                            }
+                            self.invalidate_arrange();
+                            self.set_value(ui, *self.value);
+
+                            let response = SliderMessage::min_value(
+                                self.handle,
+                                MessageDirection::FromWidget,
+                                *self.min,
+                            );
+                            response.set_handled(message.handled());
+                            ui.send_message(response);
+                        }
+                    }
+                    SliderMessage::MaxValue(max) => {
+                        if *self.max != max {
+                            self.max.set_value_and_mark_modified(max);
+                            if *self.max < *self.min {
+                                std::mem::swap(&mut self.min, &mut self.max);
+                            }
+                            self.invalidate_arrange();
+                            self.set_value(ui, *self.value);
+
+                            let response = SliderMessage::max_value(
+                                self.handle,
+                                MessageDirection::FromWidget,
+                                *self.max,
+                            );
+                            response.set_handled(message.handled());
+                            ui.send_message(response);
+                        }
+                    }
+                    SliderMessage::Step(step) => {
+                        let step = step.max(0.0);
+                        if *self.step != step {
+                            self.step.set_value_and_mark_modified(step);
+                            self.set_value(ui, *self.value);
+
+                            let response = SliderMessage::step(
+                                self.handle,
+                                MessageDirection::FromWidget,
+                                step,
+                            );
+                            response.set_handled(message.handled());
+                            ui.send_message(response);
+                        }
+                    }
+                    SliderMessage::TickFrequency(frequency) => {
+                        let frequency = frequency.max(0.0);
+                        if *self.tick_frequency != frequency {
+                            self.tick_frequency.set_value_and_mark_modified(frequency);
+
+                            let response = SliderMessage::tick_frequency(
+                                self.handle,
+                                MessageDirection::FromWidget,
+                                frequency,
+                            );
+                            response.set_handled(message.handled());
+                            ui.send_message(response);
+                        }
+                    }
+                    SliderMessage::StepBy(steps) => {
+                        // Zero step means continuous value, use 1% of the range in this case.
+                        let step = if *self.step > 0.0 {
+                            *self.step
+                        } else {
+                            (*self.max - *self.min) * 0.01
+                        };
+                        self.set_value(ui, *self.value + steps as f32 * step);
+                    }
+                }
+            }
+        } else if let Some(msg) = message.data::<WidgetMessage>() {
+            match msg {
+                WidgetMessage::MouseDown { pos, button }
+                    if *button == MouseButton::Left
+                        && (message.destination() == self.handle()
+                            || message.destination() == *self.thumb) =>
+                {
+                    let local_position = self.screen_to_local(*pos);
+                    self.grab_offset = if message.destination() == *self.thumb {
+                        // Keep the thumb under the cursor at the same spot while dragging.
+                        local_position - self.thumb_center(*self.value)
+                    } else {
+                        // Clicking on the track moves the thumb to the cursor.
+                        self.set_value(ui, self.value_at(local_position));
+                        Vector2::default()
+                    };
+                    self.is_dragging = true;
+                    ui.capture_mouse(self.handle);
+                    message.set_handled(true);
+                }
+                WidgetMessage::MouseMove { pos, .. } if self.is_dragging => {
+                    let local_position = self.screen_to_local(*pos) - self.grab_offset;
+                    self.set_value(ui, self.value_at(local_position));
+                    message.set_handled(true);
+                }
+                WidgetMessage::MouseUp { .. } if self.is_dragging => {
+                    self.is_dragging = false;
+                    ui.release_mouse_capture();
+                    message.set_handled(true);
+                }
+                WidgetMessage::KeyDown(key_code) if !message.handled() => {
+                    let steps = match key_code {
+                        KeyCode::ArrowLeft | KeyCode::ArrowDown => -1,
+                        KeyCode::ArrowRight | KeyCode::ArrowUp => 1,
+                        KeyCode::PageDown => -10,
+                        KeyCode::PageUp => 10,
+                        KeyCode::Home => {
+                            self.set_value(ui, *self.min);
+                            message.set_handled(true);
+                            return;
+                        }
+                        KeyCode::End => {
+                            self.set_value(ui, *self.max);
+                            message.set_handled(true);
+                            return;
+                        }
+                        _ => return,
+                    };
+                    ui.send_message(SliderMessage::step_by(
+                        self.handle,
+                        MessageDirection::ToWidget,
+                        steps,
+                    ));
+                    message.set_handled(true);
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Slider builder is used to create [`Slider`] widget instances and add them to the user interface.
+pub struct SliderBuilder {
+    widget_builder: WidgetBuilder,
+    min: f32,
+    max: f32,
+    value: f32,
+    step: f32,
+    tick_frequency: f32,
+    orientation: Orientation,
+    thumb: Option<Handle<UiNode>>,
+    show_value: bool,
+    value_precision: usize,
+    font: Option<FontResource>,
+    font_size: f32,
+}
+
+impl SliderBuilder {
+    /// Creates new slider builder instance.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            min: 0.0,
+            max: 100.0,
+            value: 0.0,
+            step: 1.0,
+            tick_frequency: 0.0,
+            orientation: Orientation::Horizontal,
+            thumb: None,
+            show_value: false,
+            value_precision: 3,
+            font: None,
+            font_size: 14.0,
+        }
+    }
+
+    /// Sets the desired min value.
+    pub fn with_min(mut self, min: f32) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Sets the desired max value.
+    pub fn with_max(mut self, max: f32) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Sets the desired value.
+    pub fn with_value(mut self, value: f32) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Sets the desired step. Zero step disables snapping.
+    pub fn with_step(mut self, step: f32) -> Self {
+        self.step = step.max(0.0);
+        self
+    }
+
+    /// Sets the desired distance between tick marks. Zero hides tick marks.
+    pub fn with_tick_frequency(mut self, frequency: f32) -> Self {
+        self.tick_frequency = frequency.max(0.0);
+        self
+    }
+
+    /// Sets the desired orientation.
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Sets the new handle to a widget, that is used as a thumb of the slider.
+    pub fn with_thumb(mut self, thumb: Handle<UiNode>) -> Self {
+        self.thumb = Some(thumb);
+        self
+    }
+
+    /// Show or hide the value of the slider.
+    pub fn show_value(mut self, state: bool) -> Self {
+        self.show_value = state;
+        self
+    }
+
+    /// Sets the desired value precision of the slider.
+    pub fn with_value_precision(mut self, precision: usize) -> Self {
+        self.value_precision = precision;
+        self
+    }
+
+    /// Sets the desired font of the value label.
+    pub fn with_font(mut self, font: FontResource) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    /// Sets the desired font size of the value label.
+    pub fn with_font_size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self
+    }
+
+    /// Creates new slider instance and adds it to the user interface.
+    pub fn build(mut self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let (min, max) = if self.min > self.max {
+            (self.max, self.min)
+        } else {
+            (self.min, self.max)
+        };
+
+        let thumb = self.thumb.unwrap_or_else(|| {
+            let size = match self.orientation {
+                Orientation::Horizontal => Vector2::new(10.0, 20.0),
+                Orientation::Vertical => Vector2::new(20.0, 10.0),
+            };
+            DecoratorBuilder::new(
+                BorderBuilder::new(
+                    WidgetBuilder::new()
+                        .with_width(size.x)
+                        .with_height(size.y)
+                        .with_foreground(Brush::Solid(Color::TRANSPARENT)),
+                )
+                .with_corner_radius(3.0)
+                .with_pad_by_corner_radius(false)
+                .with_stroke_thickness(Thickness::uniform(1.0)),
+            )
+            .with_normal_brush(BRUSH_LIGHT)
+            .with_hover_brush(BRUSH_LIGHTER)
+            .with_pressed_brush(BRUSH_LIGHTEST)
+            .build(ctx)
+        });
+
+        let mut slider = Slider {
+            widget: Widget::default(),
+            min: min.into(),
+            max: max.into(),
+            value: Default::default(),
+            step: self.step.into(),
+            tick_frequency: self.tick_frequency.into(),
+            orientation: self.orientation.into(),
+            thumb: thumb.into(),
+            value_text: Default::default(),
+            value_precision: self.value_precision.into(),
+            is_dragging: false,
+            grab_offset: Default::default(),
+            travel_bounds: Default::default(),
+        };
+        let value = slider.snap(self.value);
+        slider.value = value.into();
+
+        let value_text = if self.show_value {
+            TextBuilder::new(WidgetBuilder::new().with_hit_test_visibility(false))
+                .with_font(self.font.unwrap_or_else(|| ctx.default_font()))
+                .with_font_size(self.font_size)
+                .with_text(format!("{:.1$}", value, self.value_precision))
+                .build(ctx)
+        } else {
+            Handle::NONE
+        };
+        slider.value_text = value_text.into();
+
+        if self.widget_builder.background.is_none() {
+            self.widget_builder.background = Some(BRUSH_DARK);
+        }
+        slider.widget = self
+            .widget_builder
+            .with_child(thumb)
+            .with_child(value_text)
+            .build();
+
+        ctx.add_node(UiNode::new(slider))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector2,
+        message::{KeyCode, MessageDirection},
+        slider::{Slider, SliderBuilder, SliderMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        UserInterface,
+    };
+    use fyrox_graph::BaseSceneGraph;
+
+    fn value(ui: &UserInterface, slider: crate::core::pool::Handle<crate::UiNode>) -> f32 {
+        *ui.node(slider).cast::<Slider>().unwrap().value
+    }
+
+    #[test]
+    fn test_slider_snapping_and_keyboard() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let slider = SliderBuilder::new(WidgetBuilder::new())
+            .with_min(0.0)
+            .with_max(10.0)
+            .with_step(0.5)
+            .with_value(2.3)
+            .build(&mut ui.build_ctx());
+        assert_eq!(value(&ui, slider), 2.5);
+
+        ui.send_message(SliderMessage::value(
+            slider,
+            MessageDirection::ToWidget,
+            42.0,
+        ));
+        while ui.poll_message().is_some() {}
+        assert_eq!(value(&ui, slider), 10.0);
+
+        ui.send_message(WidgetMessage::key_down(
+            slider,
+            MessageDirection::FromWidget,
+            KeyCode::ArrowLeft,
+        ));
+        while ui.poll_message().is_some() {}
+        assert_eq!(value(&ui, slider), 9.5);
+
+        ui.send_message(WidgetMessage::key_down(
+            slider,
+            MessageDirection::FromWidget,
+            KeyCode::Home,
+        ));
+        while ui.poll_message().is_some() {}
+        assert_eq!(value(&ui, slider), 0.0);
+
+        ui.send_message(SliderMessage::step_by(
+            slider,
+            MessageDirection::ToWidget,
+            3,
+        ));
+        while ui.poll_message().is_some() {}
+        assert_eq!(value(&ui, slider), 1.5);
+    }
+}
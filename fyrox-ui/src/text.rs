@@ -69,6 +69,8 @@ pub enum TextMessage {
     ShadowOffset(Vector2<f32>),
     /// Used to set font height of the widget.
     FontSize(f32),
+    /// Used to set new localization key of the widget. See [Text](Text#localization) for usage examples.
+    LocalizationKey(Option<String>),
 }
 
 impl TextMessage {
@@ -121,6 +123,11 @@ impl TextMessage {
         /// Creates new [`TextMessage::FontSize`] message.
         TextMessage:FontSize => fn font_size(f32), layout: false
     );
+
+    define_constructor!(
+        /// Creates new [`TextMessage::LocalizationKey`] message.
+        TextMessage:LocalizationKey => fn localization_key(Option<String>), layout: false
+    );
 }
 
 /// Text is a simple widget that allows you to print text on screen. It has various options like word wrapping, text
@@ -288,6 +295,27 @@ impl TextMessage {
 /// }
 /// ```
 ///
+/// ## Localization
+///
+/// Text widget could show a localized string instead of a fixed one. To do that, set a localization key of
+/// the widget using [`TextBuilder::with_localization_key`] or [`TextMessage::localization_key`]. The key
+/// will be resolved using current [`crate::localization::Localization`] of the user interface and the text
+/// will be updated automatically every time when current locale changes. If there's no translation for
+/// the key, the key itself will be shown. Sending [`TextMessage::Text`] to the widget removes its localization
+/// key.
+///
+/// ```rust,no_run
+/// # use fyrox_ui::{
+/// #     core::pool::Handle,
+/// #     text::TextBuilder, widget::WidgetBuilder, UiNode, UserInterface
+/// # };
+/// fn create_localized_text(ui: &mut UserInterface) -> Handle<UiNode> {
+///     TextBuilder::new(WidgetBuilder::new())
+///         .with_localization_key("menu.play")
+///         .build(&mut ui.build_ctx())
+/// }
+/// ```
+///
 /// ## Messages
 ///
 /// Text widget can accept the following list of messages at runtime (respective constructors are name with small letter -
@@ -302,6 +330,7 @@ impl TextMessage {
 /// - [`TextMessage::ShadowDilation`] - sets "thickness" of the shadows under the tex.
 /// - [`TextMessage::ShadowBrush`] - sets shadow brush (allows you to change color and even make shadow with color gradients).
 /// - [`TextMessage::ShadowOffset`] - sets offset of the shadows.
+/// - [`TextMessage::LocalizationKey`] - sets new [localization key](Text#localization).
 ///
 /// An example of changing text at runtime could be something like this:
 ///
@@ -329,6 +358,10 @@ pub struct Text {
     pub widget: Widget,
     /// [`FormattedText`] instance that is used to layout text and generate drawing commands.
     pub formatted_text: RefCell<FormattedText>,
+    /// Optional localization key of the text. If set, the text will be taken from current localization
+    /// of the user interface and will be updated automatically when the locale changes.
+    #[visit(optional)]
+    pub localization_key: Option<String>,
 }
 
 crate::define_widget_deref!(Text);
@@ -365,6 +398,8 @@ impl Control for Text {
                     TextMessage::Text(text) => {
                         text_ref.set_text(text);
                         drop(text_ref);
+                        // Explicitly set text overrides localized one.
+                        self.localization_key = None;
                         self.invalidate_layout();
                     }
                     &TextMessage::Wrap(wrap) => {
@@ -430,6 +465,16 @@ impl Control for Text {
                             self.invalidate_layout();
                         }
                     }
+                    TextMessage::LocalizationKey(key) => {
+                        if &self.localization_key != key {
+                            if let Some(key) = key {
+                                text_ref.set_text(ui.localization().localize_or_key(key));
+                            }
+                            drop(text_ref);
+                            self.localization_key.clone_from(key);
+                            self.invalidate_layout();
+                        }
+                    }
                 }
             }
         }
@@ -461,12 +506,18 @@ impl Text {
     pub fn horizontal_alignment(&self) -> HorizontalAlignment {
         self.formatted_text.borrow().horizontal_alignment()
     }
+
+    /// Returns current localization key of the widget.
+    pub fn localization_key(&self) -> Option<&str> {
+        self.localization_key.as_deref()
+    }
 }
 
 /// TextBuilder is used to create instances of [`Text`] widget and register them in the user interface.
 pub struct TextBuilder {
     widget_builder: WidgetBuilder,
     text: Option<String>,
+    localization_key: Option<String>,
     font: Option<FontResource>,
     vertical_text_alignment: VerticalAlignment,
     horizontal_text_alignment: HorizontalAlignment,
@@ -484,6 +535,7 @@ impl TextBuilder {
         Self {
             widget_builder,
             text: None,
+            localization_key: None,
             font: None,
             vertical_text_alignment: VerticalAlignment::Top,
             horizontal_text_alignment: HorizontalAlignment::Left,
@@ -502,6 +554,13 @@ impl TextBuilder {
         self
     }
 
+    /// Sets the desired localization key of the widget. The text of the widget will be taken from
+    /// current localization of the user interface. See [Text](Text#localization) for more info.
+    pub fn with_localization_key<P: AsRef<str>>(mut self, key: P) -> Self {
+        self.localization_key = Some(key.as_ref().to_owned());
+        self
+    }
+
     /// Sets the desired font of the widget.
     pub fn with_font(mut self, font: FontResource) -> Self {
         self.font = Some(font);
@@ -590,6 +649,7 @@ impl TextBuilder {
                     .with_font_size(self.font_size)
                     .build(),
             ),
+            localization_key: self.localization_key,
         };
         ui.add_node(UiNode::new(text))
     }
@@ -1408,6 +1408,8 @@ impl Control for TextBox {
                                 ui.send_message(message.reverse());
                             }
                         }
+                        // Text box content is edited by the user, so it does not support localization.
+                        TextMessage::LocalizationKey(_) => (),
                     }
                 }
             } else if let Some(msg) = message.data::<TextBoxMessage>() {
@@ -0,0 +1,693 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Declarative property transitions, that smoothly change reflected properties of widgets when the widgets change
+//! their visual state (hovered, pressed, focused, disabled). See [`TransitionStyle`] docs for more info.
+
+#![warn(missing_docs)]
+
+use crate::{
+    brush::Brush,
+    core::{
+        algebra::Vector2,
+        color::Color,
+        log::Log,
+        math::lerpf,
+        reflect::{prelude::*, ResolvePath},
+        uuid_provider,
+        visitor::prelude::*,
+    },
+    message::{MessageDirection, MouseButton, UiMessage},
+    widget::{Widget, WidgetMessage},
+    Thickness, UserInterface,
+};
+use fyrox_graph::BaseSceneGraph;
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// A visual state of a widget, that could trigger a transition. Multiple states could be active at the same time,
+/// in this case the state with the highest priority wins. The priority of the states (from highest to lowest):
+/// `Disabled`, `Pressed`, `Hovered`, `Focused`.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+pub enum VisualState {
+    /// The mouse cursor is over the widget or any of its descendants.
+    #[default]
+    Hovered,
+    /// The left mouse button was pressed on the widget (or any of its descendants) and not released yet.
+    Pressed,
+    /// The widget or any of its descendants has keyboard focus.
+    Focused,
+    /// The widget or any of its ancestors is disabled.
+    Disabled,
+}
+
+uuid_provider!(VisualState = "1c6f0e9a-4d52-4b8e-9f3a-7e2d5c8b1a46");
+
+impl VisualState {
+    fn priority(self) -> u8 {
+        match self {
+            VisualState::Focused => 0,
+            VisualState::Hovered => 1,
+            VisualState::Pressed => 2,
+            VisualState::Disabled => 3,
+        }
+    }
+}
+
+/// A set of currently active visual states of a widget.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct VisualStates {
+    /// See [`VisualState::Hovered`].
+    pub hovered: bool,
+    /// See [`VisualState::Pressed`].
+    pub pressed: bool,
+    /// See [`VisualState::Focused`].
+    pub focused: bool,
+    /// See [`VisualState::Disabled`].
+    pub disabled: bool,
+}
+
+impl VisualStates {
+    /// Returns `true` if the given state is active, `false` - otherwise.
+    pub fn contains(&self, state: VisualState) -> bool {
+        match state {
+            VisualState::Hovered => self.hovered,
+            VisualState::Pressed => self.pressed,
+            VisualState::Focused => self.focused,
+            VisualState::Disabled => self.disabled,
+        }
+    }
+
+    /// Updates the states using a message routed to the given widget. Returns `true` if any of the states has
+    /// changed. The disabled state cannot be fetched from messages, use [`Self::sync_disabled`] for it.
+    pub fn handle_routed_message(
+        &mut self,
+        widget: &Widget,
+        ui: &UserInterface,
+        message: &UiMessage,
+    ) -> bool {
+        let Some(msg) = message.data::<WidgetMessage>() else {
+            return false;
+        };
+        if message.destination() != widget.handle()
+            && !widget.has_descendant(message.destination(), ui)
+        {
+            return false;
+        }
+
+        let old = *self;
+        match msg {
+            WidgetMessage::MouseEnter => self.hovered = true,
+            WidgetMessage::MouseLeave => self.hovered = false,
+            WidgetMessage::MouseDown { button, .. } if *button == MouseButton::Left => {
+                self.pressed = true
+            }
+            WidgetMessage::MouseUp { button, .. } if *button == MouseButton::Left => {
+                self.pressed = false
+            }
+            WidgetMessage::Focus if message.direction() == MessageDirection::FromWidget => {
+                self.focused = true
+            }
+            WidgetMessage::Unfocus if message.direction() == MessageDirection::FromWidget => {
+                self.focused = false
+            }
+            _ => (),
+        }
+        old != *self
+    }
+
+    /// Updates the disabled state by checking the widget and all its ancestors. Returns `true` if the state has
+    /// changed.
+    pub fn sync_disabled(&mut self, widget: &Widget, ui: &UserInterface) -> bool {
+        let mut disabled = !widget.enabled();
+        let mut parent = widget.parent();
+        while !disabled {
+            let Some(node) = ui.try_get(parent) else {
+                break;
+            };
+            disabled = !node.enabled();
+            parent = node.parent();
+        }
+        let changed = self.disabled != disabled;
+        self.disabled = disabled;
+        changed
+    }
+}
+
+/// Easing function of a transition, it defines how the value changes over the time of the transition.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+pub enum Easing {
+    /// Constant speed.
+    #[default]
+    Linear,
+    /// Starts slowly and accelerates (quadratic).
+    EaseIn,
+    /// Starts fast and decelerates (quadratic).
+    EaseOut,
+    /// Starts and ends slowly (quadratic).
+    EaseInOut,
+    /// Starts slowly and accelerates (cubic).
+    CubicIn,
+    /// Starts fast and decelerates (cubic).
+    CubicOut,
+    /// Starts and ends slowly (cubic).
+    CubicInOut,
+}
+
+uuid_provider!(Easing = "8f2b6d41-0e7c-4a93-b5d8-3c1e9f7a2b60");
+
+impl Easing {
+    /// Maps the given normalized time (`[0..1]` range) to normalized progress of a transition.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) * 0.5
+                }
+            }
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) * 0.5
+                }
+            }
+        }
+    }
+}
+
+/// A value of a property, that could be used in a transition. The variant must match the actual type of the
+/// property.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect, AsRefStr, EnumString, VariantNames)]
+pub enum TransitionValue {
+    /// `f32` property.
+    Real(f32),
+    /// `Vector2<f32>` property.
+    Vector2(Vector2<f32>),
+    /// [`Color`] property.
+    Color(Color),
+    /// [`Brush`] property. Only solid brushes are interpolated, other brushes are switched instantly at the end of
+    /// the transition.
+    Brush(Brush),
+    /// [`Thickness`] property.
+    Thickness(Thickness),
+}
+
+uuid_provider!(TransitionValue = "4a9d7c2e-6b1f-4e05-8a3d-2f6c0b9e7d15");
+
+impl Default for TransitionValue {
+    fn default() -> Self {
+        Self::Real(0.0)
+    }
+}
+
+fn lerp_thickness(a: &Thickness, b: &Thickness, t: f32) -> Thickness {
+    Thickness {
+        left: lerpf(a.left, b.left, t),
+        top: lerpf(a.top, b.top, t),
+        right: lerpf(a.right, b.right, t),
+        bottom: lerpf(a.bottom, b.bottom, t),
+    }
+}
+
+impl TransitionValue {
+    /// Interpolates the value with the other value of the same kind. Values that cannot be interpolated are
+    /// switched at the end of the interpolation.
+    pub fn interpolate(&self, other: &Self, t: f32) -> Self {
+        match (self, other) {
+            (Self::Real(a), Self::Real(b)) => Self::Real(lerpf(*a, *b, t)),
+            (Self::Vector2(a), Self::Vector2(b)) => Self::Vector2(a.lerp(b, t)),
+            (Self::Color(a), Self::Color(b)) => Self::Color(a.lerp(*b, t)),
+            (Self::Brush(Brush::Solid(a)), Self::Brush(Brush::Solid(b))) => {
+                Self::Brush(Brush::Solid(a.lerp(*b, t)))
+            }
+            (Self::Thickness(a), Self::Thickness(b)) => Self::Thickness(lerp_thickness(a, b, t)),
+            _ => {
+                if t < 1.0 {
+                    self.clone()
+                } else {
+                    other.clone()
+                }
+            }
+        }
+    }
+
+    /// Reads a property of the same type as the current value from the given object.
+    pub fn read(&self, object: &dyn Reflect, path: &str) -> Option<Self> {
+        fn read<T: Reflect + Clone>(object: &dyn Reflect, path: &str) -> Option<T> {
+            let mut value = None;
+            object.get_resolve_path::<T>(path, &mut |result| {
+                value = result.ok().cloned();
+            });
+            value
+        }
+
+        match self {
+            Self::Real(_) => read(object, path).map(Self::Real),
+            Self::Vector2(_) => read(object, path).map(Self::Vector2),
+            Self::Color(_) => read(object, path).map(Self::Color),
+            Self::Brush(_) => read(object, path).map(Self::Brush),
+            Self::Thickness(_) => read(object, path).map(Self::Thickness),
+        }
+    }
+
+    /// Writes the value to a property of the given object.
+    pub fn write(&self, object: &mut dyn Reflect, path: &str) {
+        let value: Box<dyn Reflect> = match self {
+            Self::Real(value) => Box::new(*value),
+            Self::Vector2(value) => Box::new(*value),
+            Self::Color(value) => Box::new(*value),
+            Self::Brush(value) => Box::new(value.clone()),
+            Self::Thickness(value) => Box::new(*value),
+        };
+        object.set_field_by_path(path, value, &mut |result| {
+            if result.is_err() {
+                Log::err(format!(
+                    "Failed to set property {path} in a transition! Invalid path or types mismatch."
+                ));
+            }
+        });
+    }
+}
+
+/// A transition of a single property, that is triggered by a visual state.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct PropertyTransition {
+    /// A state that triggers the transition.
+    pub state: VisualState,
+    /// A path to a property of the widget (for example `background` or `margin`).
+    pub property: String,
+    /// A value of the property while the state is active.
+    pub value: TransitionValue,
+    /// Duration of the transition in seconds. Zero duration changes the value instantly.
+    pub duration: f32,
+    /// Easing function of the transition.
+    pub easing: Easing,
+}
+
+uuid_provider!(PropertyTransition = "d7e3a1b9-5c28-4f6e-b0a4-9e8c2d6f1b37");
+
+impl Default for PropertyTransition {
+    fn default() -> Self {
+        Self {
+            state: Default::default(),
+            property: "background".to_string(),
+            value: TransitionValue::Brush(Default::default()),
+            duration: 0.1,
+            easing: Default::default(),
+        }
+    }
+}
+
+impl PropertyTransition {
+    /// Creates a new transition of the given property to the given value, that is triggered by the given state.
+    pub fn new<S: Into<String>>(state: VisualState, property: S, value: TransitionValue) -> Self {
+        Self {
+            state,
+            property: property.into(),
+            value,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the desired duration of the transition in seconds.
+    pub fn with_duration(mut self, duration: f32) -> Self {
+        self.duration = duration.max(0.0);
+        self
+    }
+
+    /// Sets the desired easing function of the transition.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// Transition style is a set of property transitions, that defines how a widget looks in various visual states.
+/// When a state becomes active, every property that has a transition for the state smoothly changes its value to
+/// the value of the transition. When the state is deactivated, the property returns to its rest value (the value
+/// that the property had before any transition was applied) using the same duration and easing.
+///
+/// Styles are plain data, they could be shared between multiple widgets by cloning them and they could be edited
+/// in the UI editor. Use [`TransitionPlayer`] to apply a style to a custom widget, standard widgets use transition
+/// styles via [`crate::decorator::Decorator`].
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     brush::Brush,
+/// #     core::color::Color,
+/// #     transition::{Easing, PropertyTransition, TransitionStyle, TransitionValue, VisualState},
+/// #     Thickness,
+/// # };
+/// fn make_style() -> TransitionStyle {
+///     TransitionStyle::default()
+///         .with_transition(
+///             PropertyTransition::new(
+///                 VisualState::Hovered,
+///                 "background",
+///                 TransitionValue::Brush(Brush::Solid(Color::opaque(80, 80, 80))),
+///             )
+///             .with_duration(0.15)
+///             .with_easing(Easing::EaseOut),
+///         )
+///         .with_transition(PropertyTransition::new(
+///             VisualState::Pressed,
+///             "margin",
+///             TransitionValue::Thickness(Thickness::uniform(1.0)),
+///         ))
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct TransitionStyle {
+    /// A set of transitions of the style.
+    pub transitions: Vec<PropertyTransition>,
+}
+
+uuid_provider!(TransitionStyle = "6b0e8f3d-2a7c-4d19-9e5b-1f4a7c3d8e92");
+
+impl TransitionStyle {
+    /// Adds a new transition to the style.
+    pub fn with_transition(mut self, transition: PropertyTransition) -> Self {
+        self.transitions.push(transition);
+        self
+    }
+
+    /// Returns `true` if the style has no transitions, `false` - otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.transitions.is_empty()
+    }
+
+    /// Returns `true` if the style has at least one transition of the given property, `false` - otherwise.
+    pub fn affects(&self, property: &str) -> bool {
+        self.transitions
+            .iter()
+            .any(|transition| transition.property == property)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct PropertyState {
+    property: String,
+    rest_value: TransitionValue,
+    /// Index of the transition that is currently in effect or `None` if the property is at rest.
+    current: Option<usize>,
+    /// Timings of the last transition in effect, they're used when the property returns to its rest value.
+    duration: f32,
+    easing: Easing,
+    tween: Option<Tween>,
+}
+
+#[derive(Clone, Debug)]
+struct Tween {
+    from: TransitionValue,
+    to: TransitionValue,
+    elapsed: f32,
+    duration: f32,
+    easing: Easing,
+}
+
+/// Transition player applies a [`TransitionStyle`] to an object (usually a widget) according to a set of
+/// [`VisualStates`] and animates the properties of the object over time. It does not store the style, so the same
+/// player could be used with a style that is edited at runtime.
+#[derive(Clone, Debug, Default)]
+pub struct TransitionPlayer {
+    properties: Vec<PropertyState>,
+}
+
+impl TransitionPlayer {
+    /// Returns `true` if there's at least one running transition, `false` - otherwise.
+    pub fn is_animating(&self) -> bool {
+        self.properties.iter().any(|state| state.tween.is_some())
+    }
+
+    fn property_state(
+        &mut self,
+        transition: &PropertyTransition,
+        object: &dyn Reflect,
+    ) -> Option<&mut PropertyState> {
+        let index = match self
+            .properties
+            .iter()
+            .position(|state| state.property == transition.property)
+        {
+            Some(index) => index,
+            None => {
+                // The first time the property is touched, remember its current value as the rest value.
+                let rest_value = transition.value.read(object, &transition.property)?;
+                self.properties.push(PropertyState {
+                    property: transition.property.clone(),
+                    rest_value,
+                    current: None,
+                    duration: transition.duration,
+                    easing: transition.easing,
+                    tween: None,
+                });
+                self.properties.len() - 1
+            }
+        };
+        self.properties.get_mut(index)
+    }
+
+    /// Starts transitions of every property of the style, whose target value has changed due to the new set of
+    /// visual states.
+    pub fn apply_states(
+        &mut self,
+        style: &TransitionStyle,
+        states: VisualStates,
+        object: &mut dyn Reflect,
+    ) {
+        for (index, transition) in style.transitions.iter().enumerate() {
+            // Process every property only once.
+            if style.transitions[..index]
+                .iter()
+                .any(|other| other.property == transition.property)
+            {
+                continue;
+            }
+
+            let winner = style
+                .transitions
+                .iter()
+                .enumerate()
+                .filter(|(_, other)| {
+                    other.property == transition.property && states.contains(other.state)
+                })
+                .max_by_key(|(_, other)| other.state.priority())
+                .map(|(index, _)| index);
+
+            let Some(state) = self.property_state(transition, object) else {
+                Log::err(format!(
+                    "Unable to read property {} for a transition! Invalid path or types mismatch.",
+                    transition.property
+                ));
+                continue;
+            };
+
+            if state.current == winner {
+                continue;
+            }
+            state.current = winner;
+
+            let target = match winner {
+                Some(winner) => {
+                    let winner = &style.transitions[winner];
+                    state.duration = winner.duration;
+                    state.easing = winner.easing;
+                    winner.value.clone()
+                }
+                None => state.rest_value.clone(),
+            };
+            Self::start(state, target, object);
+        }
+
+        // Properties, that were removed from the style, must return to their rest values.
+        for state in self.properties.iter_mut() {
+            if state.current.is_some() && !style.affects(&state.property) {
+                state.current = None;
+                let target = state.rest_value.clone();
+                Self::start(state, target, object);
+            }
+        }
+    }
+
+    /// Sets a new rest value of a property. If the property is at rest, it transitions to the new value.
+    pub fn set_rest_value(
+        &mut self,
+        property: &str,
+        value: TransitionValue,
+        object: &mut dyn Reflect,
+    ) {
+        if let Some(state) = self
+            .properties
+            .iter_mut()
+            .find(|state| state.property == property)
+        {
+            state.rest_value = value.clone();
+            if state.current.is_none() {
+                Self::start(state, value, object);
+            }
+        } else {
+            value.write(object, property);
+        }
+    }
+
+    fn start(state: &mut PropertyState, target: TransitionValue, object: &mut dyn Reflect) {
+        if state.duration <= 0.0 {
+            target.write(object, &state.property);
+            state.tween = None;
+        } else {
+            let from = target
+                .read(object, &state.property)
+                .unwrap_or_else(|| target.clone());
+            state.tween = Some(Tween {
+                from,
+                to: target,
+                elapsed: 0.0,
+                duration: state.duration,
+                easing: state.easing,
+            });
+        }
+    }
+
+    /// Advances all running transitions by the given amount of time and writes new values to the object. Returns
+    /// `true` if any property was changed, `false` - otherwise.
+    pub fn update(&mut self, dt: f32, object: &mut dyn Reflect) -> bool {
+        let mut changed = false;
+        for state in self.properties.iter_mut() {
+            if let Some(tween) = state.tween.as_mut() {
+                tween.elapsed += dt;
+                let t = tween.easing.apply(tween.elapsed / tween.duration);
+                tween
+                    .from
+                    .interpolate(&tween.to, t)
+                    .write(object, &state.property);
+                if tween.elapsed >= tween.duration {
+                    state.tween = None;
+                }
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        brush::Brush,
+        core::color::Color,
+        transition::{
+            Easing, PropertyTransition, TransitionPlayer, TransitionStyle, TransitionValue,
+            VisualState, VisualStates,
+        },
+        widget::{Widget, WidgetBuilder},
+        Thickness,
+    };
+
+    #[test]
+    fn test_transition_player() {
+        let mut widget: Widget = WidgetBuilder::new()
+            .with_background(Brush::Solid(Color::BLACK))
+            .build();
+        let style = TransitionStyle::default()
+            .with_transition(
+                PropertyTransition::new(
+                    VisualState::Hovered,
+                    "background",
+                    TransitionValue::Brush(Brush::Solid(Color::WHITE)),
+                )
+                .with_duration(1.0),
+            )
+            .with_transition(
+                PropertyTransition::new(
+                    VisualState::Disabled,
+                    "margin",
+                    TransitionValue::Thickness(Thickness::uniform(2.0)),
+                )
+                .with_duration(0.0)
+                .with_easing(Easing::CubicInOut),
+            );
+
+        let mut player = TransitionPlayer::default();
+        let hovered = VisualStates {
+            hovered: true,
+            ..Default::default()
+        };
+        player.apply_states(&style, hovered, &mut widget);
+        assert!(player.is_animating());
+        player.update(0.5, &mut widget);
+        assert_eq!(
+            widget.background(),
+            Brush::Solid(Color::BLACK.lerp(Color::WHITE, 0.5))
+        );
+        player.update(0.5, &mut widget);
+        assert!(!player.is_animating());
+        assert_eq!(widget.background(), Brush::Solid(Color::WHITE));
+
+        // Zero duration changes the value instantly.
+        let disabled = VisualStates {
+            disabled: true,
+            ..hovered
+        };
+        player.apply_states(&style, disabled, &mut widget);
+        assert_eq!(*widget.margin, Thickness::uniform(2.0));
+
+        // Back to rest values.
+        player.apply_states(&style, VisualStates::default(), &mut widget);
+        player.update(1.0, &mut widget);
+        assert_eq!(widget.background(), Brush::Solid(Color::BLACK));
+        assert_eq!(*widget.margin, Thickness::uniform(0.0));
+    }
+}
@@ -806,7 +806,7 @@ impl TreeBuilder {
     }
 }
 
-fn build_expander(
+pub(crate) fn build_expander(
     always_show_expander: bool,
     items_populated: bool,
     is_expanded: bool,
@@ -0,0 +1,595 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Virtual tree shows hierarchical data of any size, creating widgets only for visible rows. See [`VirtualTree`] docs
+//! for more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    border::BorderBuilder,
+    brush::Brush,
+    check_box::CheckBoxMessage,
+    core::{
+        color::Color, parking_lot::Mutex, pool::Handle, reflect::prelude::*,
+        type_traits::prelude::*, uuid_provider, visitor::prelude::*,
+    },
+    decorator::DecoratorBuilder,
+    define_constructor,
+    grid::{Column, GridBuilder, Row},
+    list_view::{ListView, ListViewBuilder, ListViewMessage},
+    message::{KeyCode, MessageDirection, MouseButton, UiMessage},
+    tree::build_expander,
+    virtualizing_stack_panel::ItemGenerator,
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, Thickness, UiNode, UserInterface, BRUSH_DARK, BRUSH_DIM_BLUE,
+};
+use fxhash::FxHashSet;
+use fyrox_graph::BaseSceneGraph;
+use std::{
+    fmt::{Debug, Formatter},
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+/// Horizontal offset of every level of the hierarchy.
+const INDENT: f32 = 15.0;
+
+/// Hierarchical data source of a [`VirtualTree`]. Every item of the hierarchy is identified by a unique id, it
+/// could be an index in some array, a hash of a path, etc.
+pub trait TreeItemSource: Send {
+    /// Returns ids of the children items of the given item, [`None`] means root items.
+    fn children(&self, parent: Option<u64>) -> Vec<u64>;
+
+    /// Returns `true` if the given item has children items. Default implementation uses [`Self::children`],
+    /// override it, if there's a faster way to check it.
+    fn has_children(&self, item: u64) -> bool {
+        !self.children(Some(item)).is_empty()
+    }
+
+    /// Creates a widget for the content of the given item.
+    fn generate(&mut self, item: u64, ctx: &mut BuildContext) -> Handle<UiNode>;
+}
+
+/// A row of a flattened hierarchy, that is currently visible in a [`VirtualTree`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct VirtualTreeRow {
+    /// Id of the item.
+    pub item: u64,
+    /// Depth of the item in the hierarchy, root items have zero depth.
+    pub depth: usize,
+    /// `true` if the item has children items.
+    pub has_children: bool,
+}
+
+struct FlatTree {
+    source: Box<dyn TreeItemSource>,
+    expanded: FxHashSet<u64>,
+    rows: Vec<VirtualTreeRow>,
+}
+
+impl FlatTree {
+    fn rebuild(&mut self) {
+        self.rows.clear();
+        let mut stack = self
+            .source
+            .children(None)
+            .into_iter()
+            .rev()
+            .map(|item| (item, 0))
+            .collect::<Vec<_>>();
+        while let Some((item, depth)) = stack.pop() {
+            let has_children = self.source.has_children(item);
+            self.rows.push(VirtualTreeRow {
+                item,
+                depth,
+                has_children,
+            });
+            if has_children && self.expanded.contains(&item) {
+                stack.extend(
+                    self.source
+                        .children(Some(item))
+                        .into_iter()
+                        .rev()
+                        .map(|child| (child, depth + 1)),
+                );
+            }
+        }
+    }
+
+    fn row_index(&self, item: u64) -> Option<usize> {
+        self.rows.iter().position(|row| row.item == item)
+    }
+}
+
+/// Shared state of a [`VirtualTree`].
+#[derive(Clone)]
+struct SharedFlatTree(Arc<Mutex<FlatTree>>);
+
+impl Debug for SharedFlatTree {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SharedFlatTree")
+    }
+}
+
+impl ItemGenerator for SharedFlatTree {
+    fn generate(&mut self, index: usize, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let mut tree = self.0.lock();
+        let Some(row) = tree.rows.get(index).cloned() else {
+            // Rows could be changed, but the list view is not synced yet.
+            return BorderBuilder::new(WidgetBuilder::new()).build(ctx);
+        };
+
+        let is_expanded = tree.expanded.contains(&row.item);
+        let content = tree.source.generate(row.item, ctx);
+        if content.is_some() {
+            ctx[content].set_row(0).set_column(1);
+        }
+        let expander = build_expander(false, row.has_children, is_expanded, ctx);
+
+        DecoratorBuilder::new(BorderBuilder::new(
+            WidgetBuilder::new()
+                .with_foreground(Brush::Solid(Color::TRANSPARENT))
+                .with_background(Brush::Solid(Color::TRANSPARENT))
+                .with_child(
+                    GridBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::left(1.0 + row.depth as f32 * INDENT))
+                            .with_child(expander)
+                            .with_child(content),
+                    )
+                    .add_column(Column::strict(11.0))
+                    .add_column(Column::stretch())
+                    .add_row(Row::stretch())
+                    .build(ctx),
+                ),
+        ))
+        .with_selected_brush(BRUSH_DIM_BLUE)
+        .with_hover_brush(BRUSH_DARK)
+        .with_normal_brush(Brush::Solid(Color::TRANSPARENT))
+        .with_pressed_brush(Brush::Solid(Color::TRANSPARENT))
+        .with_pressable(false)
+        .build(ctx)
+    }
+}
+
+/// A set of messages, that could be used to alternate the state of a [`VirtualTree`] widget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VirtualTreeMessage {
+    /// A message, that is used to expand or collapse an item.
+    Expand {
+        /// Id of the item.
+        item: u64,
+        /// Expand (`true`) or collapse (`false`) the item.
+        expand: bool,
+    },
+    /// A message, that is used to fetch or set current selection of a virtual tree.
+    Selected(Vec<u64>),
+    /// A message, that is used to re-read the hierarchy from the item source. Use it when the underlying data
+    /// has changed.
+    Refresh,
+}
+
+impl VirtualTreeMessage {
+    define_constructor!(
+        /// Creates [`VirtualTreeMessage::Expand`] message.
+        VirtualTreeMessage:Expand => fn expand(item: u64, expand: bool), layout: false
+    );
+    define_constructor!(
+        /// Creates [`VirtualTreeMessage::Selected`] message.
+        VirtualTreeMessage:Selected => fn select(Vec<u64>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`VirtualTreeMessage::Refresh`] message.
+        VirtualTreeMessage:Refresh => fn refresh(), layout: false
+    );
+}
+
+/// Virtual tree shows hierarchical data just like [`crate::tree::Tree`], but it does not create a widget for
+/// every item. Instead, it flattens the expanded part of the hierarchy into a list of rows and shows them in a
+/// virtualized [`ListView`], so only visible rows have widgets. It allows the tree to show hierarchies with
+/// hundreds of thousands of items. The hierarchy is defined by a [`TreeItemSource`]:
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle, text::TextBuilder, virtual_tree::{TreeItemSource, VirtualTreeBuilder},
+/// #     widget::WidgetBuilder, BuildContext, UiNode,
+/// # };
+/// // Every item with id < 1000 has ten children.
+/// struct Numbers;
+///
+/// impl TreeItemSource for Numbers {
+///     fn children(&self, parent: Option<u64>) -> Vec<u64> {
+///         match parent {
+///             None => (0..10).collect(),
+///             Some(item) if item < 1000 => (item * 10 + 10..item * 10 + 20).collect(),
+///             Some(_) => Vec::new(),
+///         }
+///     }
+///
+///     fn generate(&mut self, item: u64, ctx: &mut BuildContext) -> Handle<UiNode> {
+///         TextBuilder::new(WidgetBuilder::new())
+///             .with_text(format!("Item {item}"))
+///             .build(ctx)
+///     }
+/// }
+///
+/// fn create_tree(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     VirtualTreeBuilder::new(WidgetBuilder::new())
+///         .with_row_height(22.0)
+///         .build(Numbers, ctx)
+/// }
+/// ```
+///
+/// Selection and expansion work with item ids, see [`VirtualTreeMessage`]. Rows are re-generated when an item is
+/// expanded or collapsed and when [`VirtualTreeMessage::Refresh`] is received.
+#[derive(Default, Clone, Debug, Visit, Reflect, ComponentProvider)]
+pub struct VirtualTree {
+    /// Base widget of the virtual tree.
+    pub widget: Widget,
+    /// Inner virtualized list view, that shows the rows.
+    pub list_view: Handle<UiNode>,
+    /// Ids of the selected items.
+    pub selected: Vec<u64>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    state: Option<SharedFlatTree>,
+}
+
+crate::define_widget_deref!(VirtualTree);
+
+uuid_provider!(VirtualTree = "3d0f6c2a-1b9e-4e7c-a7d4-6f58b1c0e2f3");
+
+impl VirtualTree {
+    /// Returns currently visible rows of the tree (including the ones that are scrolled out of view).
+    pub fn rows(&self) -> Vec<VirtualTreeRow> {
+        self.state
+            .as_ref()
+            .map(|state| state.0.lock().rows.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if the given item is expanded.
+    pub fn is_expanded(&self, item: u64) -> bool {
+        self.state
+            .as_ref()
+            .is_some_and(|state| state.0.lock().expanded.contains(&item))
+    }
+
+    /// Finds an index of the row, that contains the given widget.
+    fn row_of(&self, handle: Handle<UiNode>, ui: &UserInterface) -> Option<usize> {
+        let list_view = ui.try_get(self.list_view)?.cast::<ListView>()?;
+        let mut container = handle;
+        while let Some(node) = ui.try_get(container) {
+            if node.parent() == *list_view.panel {
+                return list_view.item_container_index(container, ui);
+            }
+            container = node.parent();
+        }
+        None
+    }
+
+    fn set_rows_selection(&self, ui: &UserInterface, state: &FlatTree) {
+        let selection = self
+            .selected
+            .iter()
+            .filter_map(|item| state.row_index(*item))
+            .collect();
+        ui.send_message(ListViewMessage::selection(
+            self.list_view,
+            MessageDirection::ToWidget,
+            selection,
+        ));
+    }
+
+    fn rebuild(&mut self, ui: &UserInterface) {
+        let Some(state) = self.state.clone() else {
+            return;
+        };
+        let mut state = state.0.lock();
+        state.rebuild();
+
+        // Some of the selected items could become hidden.
+        self.selected
+            .retain(|item| state.row_index(*item).is_some());
+
+        ui.send_message(ListViewMessage::item_count(
+            self.list_view,
+            MessageDirection::ToWidget,
+            state.rows.len(),
+        ));
+        self.set_rows_selection(ui, &state);
+    }
+
+    fn expand(&mut self, ui: &UserInterface, item: u64, expand: bool) {
+        let Some(state) = self.state.clone() else {
+            return;
+        };
+        let changed = {
+            let mut state = state.0.lock();
+            if expand {
+                state.expanded.insert(item)
+            } else {
+                state.expanded.remove(&item)
+            }
+        };
+        if changed {
+            self.rebuild(ui);
+        }
+    }
+
+    fn row(&self, index: usize) -> Option<VirtualTreeRow> {
+        self.state
+            .as_ref()
+            .and_then(|state| state.0.lock().rows.get(index).cloned())
+    }
+}
+
+impl Control for VirtualTree {
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<VirtualTreeMessage>() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    &VirtualTreeMessage::Expand { item, expand } => {
+                        self.expand(ui, item, expand);
+                    }
+                    VirtualTreeMessage::Selected(selected) => {
+                        if &self.selected != selected {
+                            self.selected.clone_from(selected);
+                            if let Some(state) = self.state.clone() {
+                                self.set_rows_selection(ui, &state.0.lock());
+                            }
+                            ui.send_message(message.reverse());
+                        }
+                    }
+                    VirtualTreeMessage::Refresh => {
+                        self.rebuild(ui);
+                    }
+                }
+            }
+        } else if let Some(ListViewMessage::SelectionChanged(rows)) = message.data() {
+            if message.destination() == self.list_view
+                && message.direction() == MessageDirection::FromWidget
+            {
+                let selected = rows
+                    .iter()
+                    .filter_map(|index| self.row(*index).map(|row| row.item))
+                    .collect::<Vec<_>>();
+                if selected != self.selected {
+                    self.selected = selected;
+                    ui.send_message(VirtualTreeMessage::select(
+                        self.handle,
+                        MessageDirection::FromWidget,
+                        self.selected.clone(),
+                    ));
+                }
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(expand))) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if let Some(row) = self
+                    .row_of(message.destination(), ui)
+                    .and_then(|index| self.row(index))
+                {
+                    // Make sure that the message came from the expander, not from the content.
+                    let is_expander = ui
+                        .try_get(message.destination())
+                        .and_then(|expander| ui.try_get(expander.parent()))
+                        .is_some_and(|grid| {
+                            grid.children().first() == Some(&message.destination())
+                        });
+                    if is_expander && self.is_expanded(row.item) != *expand {
+                        self.expand(ui, row.item, *expand);
+                    }
+                }
+            }
+        } else if let Some(msg) = message.data::<WidgetMessage>() {
+            match msg {
+                WidgetMessage::DoubleClick {
+                    button: MouseButton::Left,
+                } if !message.handled() => {
+                    if let Some(row) = self
+                        .row_of(message.destination(), ui)
+                        .and_then(|index| self.row(index))
+                    {
+                        if row.has_children {
+                            let expand = !self.is_expanded(row.item);
+                            self.expand(ui, row.item, expand);
+                            message.set_handled(true);
+                        }
+                    }
+                }
+                WidgetMessage::KeyDown(key_code) if !message.handled() => {
+                    let Some(state) = self.state.clone() else {
+                        return;
+                    };
+                    let Some((index, row)) = self.selected.first().and_then(|item| {
+                        let state = state.0.lock();
+                        state
+                            .row_index(*item)
+                            .map(|index| (index, state.rows[index]))
+                    }) else {
+                        return;
+                    };
+                    match key_code {
+                        KeyCode::ArrowRight if row.has_children => {
+                            self.expand(ui, row.item, true);
+                            message.set_handled(true);
+                        }
+                        KeyCode::ArrowLeft => {
+                            if self.is_expanded(row.item) {
+                                self.expand(ui, row.item, false);
+                            } else if let Some(parent) = state.0.lock().rows[..index]
+                                .iter()
+                                .rev()
+                                .find(|parent| parent.depth + 1 == row.depth)
+                            {
+                                ui.send_message(VirtualTreeMessage::select(
+                                    self.handle,
+                                    MessageDirection::ToWidget,
+                                    vec![parent.item],
+                                ));
+                            }
+                            message.set_handled(true);
+                        }
+                        _ => (),
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Virtual tree builder creates [`VirtualTree`] instances and adds them to the user interface.
+pub struct VirtualTreeBuilder {
+    widget_builder: WidgetBuilder,
+    row_height: f32,
+    expanded: FxHashSet<u64>,
+}
+
+impl VirtualTreeBuilder {
+    /// Creates new virtual tree builder.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            row_height: 24.0,
+            expanded: Default::default(),
+        }
+    }
+
+    /// Sets the desired height of every row.
+    pub fn with_row_height(mut self, height: f32) -> Self {
+        self.row_height = height;
+        self
+    }
+
+    /// Sets the desired set of initially expanded items.
+    pub fn with_expanded(mut self, expanded: impl IntoIterator<Item = u64>) -> Self {
+        self.expanded = expanded.into_iter().collect();
+        self
+    }
+
+    /// Finishes widget building and adds the new instance to the user interface, returning its handle.
+    pub fn build<S: TreeItemSource + 'static>(
+        self,
+        source: S,
+        ctx: &mut BuildContext,
+    ) -> Handle<UiNode> {
+        let mut flat_tree = FlatTree {
+            source: Box::new(source),
+            expanded: self.expanded,
+            rows: Default::default(),
+        };
+        flat_tree.rebuild();
+        let row_count = flat_tree.rows.len();
+        let state = SharedFlatTree(Arc::new(Mutex::new(flat_tree)));
+
+        let list_view = ListViewBuilder::new(WidgetBuilder::new())
+            .with_virtualization(row_count, self.row_height, state.clone())
+            .build(ctx);
+
+        let tree = VirtualTree {
+            widget: self.widget_builder.with_child(list_view).build(),
+            list_view,
+            selected: Default::default(),
+            state: Some(state),
+        };
+
+        ctx.add_node(UiNode::new(tree))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector2, pool::Handle},
+        text::TextBuilder,
+        virtual_tree::{TreeItemSource, VirtualTree, VirtualTreeBuilder, VirtualTreeMessage},
+        widget::WidgetBuilder,
+        BuildContext, MessageDirection, UiNode, UserInterface,
+    };
+    use fyrox_graph::BaseSceneGraph;
+
+    struct Source;
+
+    impl TreeItemSource for Source {
+        fn children(&self, parent: Option<u64>) -> Vec<u64> {
+            match parent {
+                None => vec![0, 1],
+                Some(0) => vec![2, 3],
+                Some(_) => vec![],
+            }
+        }
+
+        fn generate(&mut self, item: u64, ctx: &mut BuildContext) -> Handle<UiNode> {
+            TextBuilder::new(WidgetBuilder::new())
+                .with_text(item.to_string())
+                .build(ctx)
+        }
+    }
+
+    fn rows(ui: &UserInterface, tree: Handle<UiNode>) -> Vec<(u64, usize)> {
+        ui.node(tree)
+            .cast::<VirtualTree>()
+            .unwrap()
+            .rows()
+            .iter()
+            .map(|row| (row.item, row.depth))
+            .collect()
+    }
+
+    #[test]
+    fn test_expand_collapse() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let tree = VirtualTreeBuilder::new(WidgetBuilder::new()).build(Source, &mut ui.build_ctx());
+        assert_eq!(rows(&ui, tree), vec![(0, 0), (1, 0)]);
+
+        ui.send_message(VirtualTreeMessage::select(
+            tree,
+            MessageDirection::ToWidget,
+            vec![1],
+        ));
+        ui.send_message(VirtualTreeMessage::expand(
+            tree,
+            MessageDirection::ToWidget,
+            0,
+            true,
+        ));
+        while ui.poll_message().is_some() {}
+        assert_eq!(rows(&ui, tree), vec![(0, 0), (2, 1), (3, 1), (1, 0)]);
+        assert_eq!(
+            ui.node(tree).cast::<VirtualTree>().unwrap().selected,
+            vec![1]
+        );
+
+        ui.send_message(VirtualTreeMessage::expand(
+            tree,
+            MessageDirection::ToWidget,
+            0,
+            false,
+        ));
+        while ui.poll_message().is_some() {}
+        assert_eq!(rows(&ui, tree), vec![(0, 0), (1, 0)]);
+    }
+}
@@ -0,0 +1,564 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Virtualizing stack panel arranges a large amount of same-sized items linearly, but creates widgets only for
+//! the items that are currently visible. See [`VirtualizingStackPanel`] docs for more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    core::{
+        algebra::Vector2, math::Rect, parking_lot::Mutex, pool::Handle, reflect::prelude::*,
+        type_traits::prelude::*, uuid_provider, variable::InheritableVariable, visitor::prelude::*,
+    },
+    define_constructor,
+    message::{MessageDirection, UiMessage},
+    scroll_panel::{ScrollPanel, ScrollPanelMessage},
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, Orientation, UiNode, UserInterface,
+};
+use fyrox_graph::BaseSceneGraph;
+use std::{
+    fmt::{Debug, Formatter},
+    ops::{Deref, DerefMut, Range},
+    sync::Arc,
+};
+
+/// Item generator creates widgets for the items of virtualized containers on demand.
+pub trait ItemGenerator: Send {
+    /// Creates a new widget for the item with the given index.
+    fn generate(&mut self, index: usize, ctx: &mut BuildContext) -> Handle<UiNode>;
+
+    /// Tries to reuse a widget, that was previously generated by this generator, for the item with the given
+    /// index. The implementation should send messages to the widget to update its content and return `true`, or
+    /// return `false` if the widget cannot be reused (it will be deleted and a new one will be generated instead).
+    /// Default implementation does not reuse widgets.
+    fn recycle(&mut self, index: usize, item: Handle<UiNode>, ui: &UserInterface) -> bool {
+        let _ = (index, item, ui);
+        false
+    }
+}
+
+impl<F> ItemGenerator for F
+where
+    F: FnMut(usize, &mut BuildContext) -> Handle<UiNode> + Send,
+{
+    fn generate(&mut self, index: usize, ctx: &mut BuildContext) -> Handle<UiNode> {
+        self(index, ctx)
+    }
+}
+
+/// Shared, type-erased [`ItemGenerator`].
+#[derive(Clone)]
+pub struct SharedItemGenerator(pub Arc<Mutex<dyn ItemGenerator>>);
+
+impl SharedItemGenerator {
+    /// Wraps the given generator.
+    pub fn new<G: ItemGenerator + 'static>(generator: G) -> Self {
+        Self(Arc::new(Mutex::new(generator)))
+    }
+}
+
+impl PartialEq for SharedItemGenerator {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Debug for SharedItemGenerator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SharedItemGenerator")
+    }
+}
+
+/// A set of possible [`VirtualizingStackPanel`] widget messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VirtualizingStackPanelMessage {
+    /// Sets the new total amount of items. All visible items will be re-generated (or recycled).
+    ItemCount(usize),
+    /// Forces the panel to re-generate (or recycle) all visible items. Use it when the underlying data has
+    /// changed, but the amount of items is the same.
+    Refresh,
+    /// Scrolls the parent [`ScrollPanel`] (if any), so the item with the given index will be visible.
+    BringIndexIntoView(usize),
+    /// Sent by the panel with [`MessageDirection::FromWidget`] when the set of visible items has changed.
+    /// Containers could use it to sync the state (selection, for example) of the new items.
+    ItemsRealized,
+}
+
+impl VirtualizingStackPanelMessage {
+    define_constructor!(
+        /// Creates [`VirtualizingStackPanelMessage::ItemCount`] message.
+        VirtualizingStackPanelMessage:ItemCount => fn item_count(usize), layout: false
+    );
+    define_constructor!(
+        /// Creates [`VirtualizingStackPanelMessage::Refresh`] message.
+        VirtualizingStackPanelMessage:Refresh => fn refresh(), layout: false
+    );
+    define_constructor!(
+        /// Creates [`VirtualizingStackPanelMessage::BringIndexIntoView`] message.
+        VirtualizingStackPanelMessage:BringIndexIntoView => fn bring_index_into_view(usize), layout: true
+    );
+    define_constructor!(
+        /// Creates [`VirtualizingStackPanelMessage::ItemsRealized`] message.
+        VirtualizingStackPanelMessage:ItemsRealized => fn items_realized(), layout: false
+    );
+}
+
+/// A widget of an item with the given index, that is currently created by [`VirtualizingStackPanel`].
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RealizedItem {
+    /// Index of the item.
+    pub index: usize,
+    /// Handle of the widget that represents the item.
+    pub handle: Handle<UiNode>,
+}
+
+/// Virtualizing stack panel arranges its items linearly, either top-to-bottom or left-to-right, just like
+/// [`crate::stack_panel::StackPanel`]. The difference is that the items are not added to the panel directly,
+/// instead the panel asks an [`ItemGenerator`] to create widgets only for the items that are currently visible
+/// in the parent [`ScrollPanel`] (usually a part of [`crate::scroll_viewer::ScrollViewer`]). Widgets of the items
+/// that went out of view are either reused for new items or deleted. This allows the panel to show lists with
+/// hundreds of thousands of items without any performance issues.
+///
+/// Every item must have the same size along the orientation axis (see
+/// [`VirtualizingStackPanelBuilder::with_item_size`]), this allows the panel to find visible items without
+/// measuring them.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle, scroll_viewer::ScrollViewerBuilder, text::TextBuilder,
+/// #     virtualizing_stack_panel::VirtualizingStackPanelBuilder, widget::WidgetBuilder,
+/// #     BuildContext, UiNode,
+/// # };
+/// fn create_long_list(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     let panel = VirtualizingStackPanelBuilder::new(WidgetBuilder::new())
+///         .with_item_count(100_000)
+///         .with_item_size(20.0)
+///         .with_generator(|index: usize, ctx: &mut BuildContext| {
+///             TextBuilder::new(WidgetBuilder::new())
+///                 .with_text(format!("Item {index}"))
+///                 .build(ctx)
+///         })
+///         .build(ctx);
+///
+///     ScrollViewerBuilder::new(WidgetBuilder::new())
+///         .with_content(panel)
+///         .build(ctx)
+/// }
+/// ```
+///
+/// Items are generated in [`Control::update`], so the panel always shows the actual set of items one frame later
+/// after scrolling or resizing.
+#[derive(Default, Clone, Visit, Reflect, Debug, ComponentProvider)]
+pub struct VirtualizingStackPanel {
+    /// Base widget of the panel.
+    pub widget: Widget,
+    /// Current orientation of the panel.
+    pub orientation: InheritableVariable<Orientation>,
+    /// Total amount of items.
+    pub item_count: InheritableVariable<usize>,
+    /// Size of every item along the orientation axis.
+    pub item_size: InheritableVariable<f32>,
+    /// Amount of extra items that are generated before and after the visible range, to make scrolling smoother.
+    pub overscan: InheritableVariable<usize>,
+    /// Current item generator.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub generator: Option<SharedItemGenerator>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    realized: Vec<RealizedItem>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    needs_refresh: bool,
+}
+
+crate::define_widget_deref!(VirtualizingStackPanel);
+
+uuid_provider!(VirtualizingStackPanel = "8c3b5b8e-8f0e-4bd5-9d0b-3f2f5f3c1d7a");
+
+impl VirtualizingStackPanel {
+    /// Returns a slice with the items that currently have widgets.
+    pub fn realized_items(&self) -> &[RealizedItem] {
+        &self.realized
+    }
+
+    /// Returns an index of the item that is represented by the given widget (if it is currently realized).
+    pub fn index_of(&self, handle: Handle<UiNode>) -> Option<usize> {
+        self.realized
+            .iter()
+            .find(|item| item.handle == handle)
+            .map(|item| item.index)
+    }
+
+    /// Returns a handle of the widget that represents an item with the given index (if it is currently realized).
+    pub fn item_handle(&self, index: usize) -> Handle<UiNode> {
+        self.realized
+            .iter()
+            .find(|item| item.index == index)
+            .map(|item| item.handle)
+            .unwrap_or_default()
+    }
+
+    fn main_axis(&self, v: Vector2<f32>) -> f32 {
+        match *self.orientation {
+            Orientation::Vertical => v.y,
+            Orientation::Horizontal => v.x,
+        }
+    }
+
+    fn visible_range(&self, ui: &UserInterface) -> Range<usize> {
+        if *self.item_size <= 0.0 {
+            return 0..0;
+        }
+
+        // Parent widget (usually a scroll panel) defines the viewport, the panel itself is shifted by the
+        // scroll value.
+        let viewport = ui
+            .try_get(self.parent())
+            .map(|parent| parent.actual_local_size())
+            .unwrap_or_else(|| self.actual_local_size());
+        let offset = -self.main_axis(self.actual_local_position());
+        let viewport = self.main_axis(viewport);
+
+        let first = (offset / *self.item_size).floor().max(0.0) as usize;
+        let last = ((offset + viewport) / *self.item_size).ceil().max(0.0) as usize;
+
+        first.saturating_sub(*self.overscan).min(*self.item_count)
+            ..last.saturating_add(*self.overscan).min(*self.item_count)
+    }
+
+    fn realize(&mut self, ui: &mut UserInterface) {
+        let Some(generator) = self.generator.clone() else {
+            return;
+        };
+
+        let range = self.visible_range(ui);
+
+        let mut free = Vec::new();
+        if std::mem::take(&mut self.needs_refresh) {
+            free.extend(self.realized.drain(..).map(|item| item.handle));
+        } else {
+            let mut i = 0;
+            while i < self.realized.len() {
+                if range.contains(&self.realized[i].index) {
+                    i += 1;
+                } else {
+                    free.push(self.realized.swap_remove(i).handle);
+                }
+            }
+        }
+
+        let mut changed = !free.is_empty();
+        let mut generator = generator.0.lock();
+        for index in range {
+            if self.realized.iter().any(|item| item.index == index) {
+                continue;
+            }
+
+            changed = true;
+
+            let mut handle = Handle::NONE;
+            while let Some(candidate) = free.pop() {
+                if generator.recycle(index, candidate, ui) {
+                    handle = candidate;
+                    break;
+                }
+                ui.send_message(WidgetMessage::remove(candidate, MessageDirection::ToWidget));
+            }
+
+            if handle.is_none() {
+                handle = generator.generate(index, &mut ui.build_ctx());
+                ui.send_message(WidgetMessage::link(
+                    handle,
+                    MessageDirection::ToWidget,
+                    self.handle,
+                ));
+            }
+
+            self.realized.push(RealizedItem { index, handle });
+        }
+
+        for handle in free {
+            ui.send_message(WidgetMessage::remove(handle, MessageDirection::ToWidget));
+        }
+
+        if changed {
+            self.realized.sort_by_key(|item| item.index);
+            self.invalidate_layout();
+            ui.send_message(VirtualizingStackPanelMessage::items_realized(
+                self.handle,
+                MessageDirection::FromWidget,
+            ));
+        }
+    }
+}
+
+impl Control for VirtualizingStackPanel {
+    fn measure_override(&self, ui: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
+        let child_constraint = match *self.orientation {
+            Orientation::Vertical => Vector2::new(available_size.x, *self.item_size),
+            Orientation::Horizontal => Vector2::new(*self.item_size, available_size.y),
+        };
+
+        let mut cross_size = 0.0f32;
+        for child_handle in self.widget.children() {
+            ui.measure_node(*child_handle, child_constraint);
+            let desired = ui.node(*child_handle).desired_size();
+            cross_size = cross_size.max(match *self.orientation {
+                Orientation::Vertical => desired.x,
+                Orientation::Horizontal => desired.y,
+            });
+        }
+
+        let main_size = *self.item_count as f32 * *self.item_size;
+        match *self.orientation {
+            Orientation::Vertical => Vector2::new(cross_size, main_size),
+            Orientation::Horizontal => Vector2::new(main_size, cross_size),
+        }
+    }
+
+    fn arrange_override(&self, ui: &UserInterface, final_size: Vector2<f32>) -> Vector2<f32> {
+        for child_handle in self.widget.children() {
+            let rect = match self.index_of(*child_handle) {
+                Some(index) => {
+                    let position = index as f32 * *self.item_size;
+                    match *self.orientation {
+                        Orientation::Vertical => {
+                            Rect::new(0.0, position, final_size.x, *self.item_size)
+                        }
+                        Orientation::Horizontal => {
+                            Rect::new(position, 0.0, *self.item_size, final_size.y)
+                        }
+                    }
+                }
+                // The widget is about to be deleted.
+                None => Rect::default(),
+            };
+            ui.arrange_node(*child_handle, &rect);
+        }
+
+        final_size
+    }
+
+    fn update(&mut self, _dt: f32, ui: &mut UserInterface) {
+        self.realize(ui);
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<VirtualizingStackPanelMessage>() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match *msg {
+                    VirtualizingStackPanelMessage::ItemCount(count) => {
+                        self.item_count.set_value_and_mark_modified(count);
+                        self.needs_refresh = true;
+                        self.invalidate_layout();
+                        ui.send_message(message.reverse());
+                    }
+                    VirtualizingStackPanelMessage::Refresh => {
+                        self.needs_refresh = true;
+                    }
+                    VirtualizingStackPanelMessage::BringIndexIntoView(index) => {
+                        let Some(scroll_panel) = ui
+                            .try_get(self.parent())
+                            .and_then(|parent| parent.query_component::<ScrollPanel>())
+                        else {
+                            return;
+                        };
+
+                        let begin = index as f32 * *self.item_size;
+                        let end = begin + *self.item_size;
+                        let view_size = self.main_axis(scroll_panel.actual_local_size());
+                        let scroll = self.main_axis(scroll_panel.scroll);
+                        let new_scroll = if begin < scroll {
+                            begin
+                        } else if end > scroll + view_size {
+                            end - view_size
+                        } else {
+                            return;
+                        };
+
+                        ui.send_message(match *self.orientation {
+                            Orientation::Vertical => ScrollPanelMessage::vertical_scroll(
+                                scroll_panel.handle(),
+                                MessageDirection::ToWidget,
+                                new_scroll,
+                            ),
+                            Orientation::Horizontal => ScrollPanelMessage::horizontal_scroll(
+                                scroll_panel.handle(),
+                                MessageDirection::ToWidget,
+                                new_scroll,
+                            ),
+                        });
+                    }
+                    VirtualizingStackPanelMessage::ItemsRealized => (),
+                }
+            }
+        }
+    }
+}
+
+/// Virtualizing stack panel builder creates [`VirtualizingStackPanel`] widgets and adds them to the user interface.
+pub struct VirtualizingStackPanelBuilder {
+    widget_builder: WidgetBuilder,
+    orientation: Orientation,
+    item_count: usize,
+    item_size: f32,
+    overscan: usize,
+    generator: Option<SharedItemGenerator>,
+}
+
+impl VirtualizingStackPanelBuilder {
+    /// Creates new virtualizing stack panel builder with the base widget builder.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            orientation: Orientation::Vertical,
+            item_count: 0,
+            item_size: 20.0,
+            overscan: 1,
+            generator: None,
+        }
+    }
+
+    /// Sets the desired orientation of the panel.
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Sets the desired total amount of items.
+    pub fn with_item_count(mut self, count: usize) -> Self {
+        self.item_count = count;
+        self
+    }
+
+    /// Sets the desired size of every item along the orientation axis.
+    pub fn with_item_size(mut self, size: f32) -> Self {
+        self.item_size = size;
+        self
+    }
+
+    /// Sets the desired amount of extra items that are generated before and after the visible range.
+    pub fn with_overscan(mut self, overscan: usize) -> Self {
+        self.overscan = overscan;
+        self
+    }
+
+    /// Sets the desired item generator.
+    pub fn with_generator<G: ItemGenerator + 'static>(self, generator: G) -> Self {
+        self.with_shared_generator(SharedItemGenerator::new(generator))
+    }
+
+    /// Sets the desired item generator, that could be shared with other widgets.
+    pub fn with_shared_generator(mut self, generator: SharedItemGenerator) -> Self {
+        self.generator = Some(generator);
+        self
+    }
+
+    /// Finishes panel building and returns its instance.
+    pub fn build_virtualizing_stack_panel(self) -> VirtualizingStackPanel {
+        VirtualizingStackPanel {
+            widget: self.widget_builder.with_need_update(true).build(),
+            orientation: self.orientation.into(),
+            item_count: self.item_count.into(),
+            item_size: self.item_size.into(),
+            overscan: self.overscan.into(),
+            generator: self.generator,
+            realized: Default::default(),
+            needs_refresh: false,
+        }
+    }
+
+    /// Finishes panel building and adds the new panel to the user interface.
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        ctx.add_node(UiNode::new(self.build_virtualizing_stack_panel()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector2,
+        scroll_viewer::ScrollViewerBuilder,
+        text::TextBuilder,
+        virtualizing_stack_panel::{
+            VirtualizingStackPanel, VirtualizingStackPanelBuilder, VirtualizingStackPanelMessage,
+        },
+        widget::WidgetBuilder,
+        BuildContext, MessageDirection, UserInterface,
+    };
+    use fyrox_graph::BaseSceneGraph;
+
+    #[test]
+    fn test_only_visible_items_are_generated() {
+        let mut ui = UserInterface::new(Vector2::new(200.0, 100.0));
+        let ctx = &mut ui.build_ctx();
+        let panel = VirtualizingStackPanelBuilder::new(WidgetBuilder::new())
+            .with_item_count(10_000)
+            .with_item_size(10.0)
+            .with_overscan(0)
+            .with_generator(|index: usize, ctx: &mut BuildContext| {
+                TextBuilder::new(WidgetBuilder::new())
+                    .with_text(index.to_string())
+                    .build(ctx)
+            })
+            .build(ctx);
+        ScrollViewerBuilder::new(WidgetBuilder::new().with_width(200.0).with_height(100.0))
+            .with_content(panel)
+            .build(ctx);
+
+        let realized = |ui: &UserInterface| {
+            ui.node(panel)
+                .cast::<VirtualizingStackPanel>()
+                .unwrap()
+                .realized_items()
+                .iter()
+                .map(|item| item.index)
+                .collect::<Vec<_>>()
+        };
+
+        for _ in 0..3 {
+            ui.update(Vector2::new(200.0, 100.0), 0.0, &Default::default());
+            while ui.poll_message().is_some() {}
+        }
+        assert_eq!(realized(&ui), (0..10).collect::<Vec<_>>());
+        assert_eq!(ui.node(panel).children().len(), 10);
+
+        ui.send_message(VirtualizingStackPanelMessage::bring_index_into_view(
+            panel,
+            MessageDirection::ToWidget,
+            5000,
+        ));
+        for _ in 0..3 {
+            ui.update(Vector2::new(200.0, 100.0), 0.0, &Default::default());
+            while ui.poll_message().is_some() {}
+        }
+        assert_eq!(realized(&ui), (4991..5001).collect::<Vec<_>>());
+        assert_eq!(ui.node(panel).children().len(), 10);
+    }
+}